@@ -0,0 +1,76 @@
+#[cfg(not(target_arch = "wasm32"))]
+mod events {
+    use std::path::Path;
+    use std::sync::Arc;
+
+    use criterion::{black_box, criterion_group, Criterion};
+    use winit::dpi::{LogicalPosition, PhysicalPosition, PhysicalSize, Position};
+    use winit::event::{DeviceEvent, DeviceId, Event, ModifiersState, WindowEvent};
+    use winit::window::WindowId;
+
+    fn events() -> [(&'static str, Event<()>); 3] {
+        let window_id = unsafe { WindowId::dummy() };
+        let device_id = unsafe { DeviceId::dummy() };
+
+        #[allow(deprecated)]
+        let cursor_moved = Event::WindowEvent {
+            window_id,
+            event: WindowEvent::CursorMoved {
+                device_id,
+                position: PhysicalPosition::new(1.0, 2.0),
+                modifiers: ModifiersState::empty(),
+            },
+        };
+        let mouse_motion = Event::DeviceEvent {
+            device_id,
+            event: DeviceEvent::MouseMotion { delta: (1.0, 2.0) },
+        };
+        let dropped_file = Event::WindowEvent {
+            window_id,
+            event: WindowEvent::DroppedFile(Arc::from(Path::new("/tmp/file"))),
+        };
+
+        [
+            ("cursor_moved", cursor_moved),
+            ("mouse_motion", mouse_motion),
+            ("dropped_file", dropped_file),
+        ]
+    }
+
+    // What the backends do with every event: clone it out of their queue and convert it to the
+    // user event type of the event loop.
+    fn dispatch(c: &mut Criterion) {
+        let mut group = c.benchmark_group("dispatch");
+        for (name, event) in events() {
+            group.bench_function(name, |b| {
+                b.iter(|| black_box(&event).clone().map_nonuser_event::<()>().ok())
+            });
+        }
+        group.finish();
+    }
+
+    fn dpi(c: &mut Criterion) {
+        let mut group = c.benchmark_group("dpi");
+        group.bench_function("logical_position_to_physical", |b| {
+            let position = LogicalPosition::new(12.5, 40.25);
+            b.iter(|| black_box(position).to_physical::<i32>(black_box(1.5)))
+        });
+        group.bench_function("physical_size_to_logical", |b| {
+            let size = PhysicalSize::new(3840u32, 2160u32);
+            b.iter(|| black_box(size).to_logical::<f64>(black_box(1.5)))
+        });
+        group.bench_function("position_to_physical", |b| {
+            let position = Position::Logical(LogicalPosition::new(12.5, 40.25));
+            b.iter(|| black_box(position).to_physical::<f64>(black_box(1.5)))
+        });
+        group.finish();
+    }
+
+    criterion_group!(benches, dispatch, dpi);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+criterion::criterion_main!(events::benches);
+
+#[cfg(target_arch = "wasm32")]
+fn main() {}
@@ -0,0 +1,101 @@
+//! Needs a display, like the examples.
+
+#[cfg(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+mod proxy {
+    use std::time::{Duration, Instant};
+
+    use criterion::{criterion_group, Criterion};
+    use winit::event::Event;
+    use winit::event_loop::{ControlFlow, EventLoop, EventLoopBuilder};
+    use winit::platform::run_return::EventLoopExtRunReturn;
+
+    /// Sends `iters` events from another thread, returning the time between sending each event
+    /// and the event loop receiving it when `latency` is set, or the time it took to receive all
+    /// of them otherwise.
+    fn send_and_receive(
+        event_loop: &mut EventLoop<Instant>,
+        iters: u64,
+        latency: bool,
+    ) -> Duration {
+        let proxy = event_loop.create_proxy();
+        let start = Instant::now();
+        std::thread::spawn(move || {
+            for _ in 0..iters {
+                if proxy.send_event(Instant::now()).is_err() {
+                    return;
+                }
+                if latency {
+                    // Let the event loop go back to waiting, so that every event wakes it up.
+                    std::thread::sleep(Duration::from_micros(50));
+                }
+            }
+        });
+
+        let mut received = 0;
+        let mut total = Duration::default();
+        event_loop.run_return(|event, _, control_flow| {
+            *control_flow = ControlFlow::Wait;
+            if let Event::UserEvent(sent) = event {
+                received += 1;
+                if latency {
+                    total += sent.elapsed();
+                }
+                if received == iters {
+                    *control_flow = ControlFlow::Exit;
+                }
+            }
+        });
+
+        if latency {
+            total
+        } else {
+            start.elapsed()
+        }
+    }
+
+    fn proxy(c: &mut Criterion) {
+        // There can only be a single event loop, so it's shared by the benchmarks.
+        let mut event_loop = EventLoopBuilder::<Instant>::with_user_event().build();
+
+        let mut group = c.benchmark_group("proxy");
+        group.bench_function("latency", |b| {
+            b.iter_custom(|iters| send_and_receive(&mut event_loop, iters, true))
+        });
+        group.bench_function("throughput", |b| {
+            b.iter_custom(|iters| send_and_receive(&mut event_loop, iters, false))
+        });
+        group.finish();
+    }
+
+    criterion_group!(benches, proxy);
+}
+
+#[cfg(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+criterion::criterion_main!(proxy::benches);
+
+#[cfg(not(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+)))]
+fn main() {}
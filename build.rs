@@ -0,0 +1,18 @@
+fn main() {
+    // Bindings for the Wayland protocols `wayland-protocols` doesn't ship yet.
+    #[cfg(feature = "wayland")]
+    {
+        let out_dir = std::env::var("OUT_DIR").unwrap();
+        let out_dir = std::path::Path::new(&out_dir);
+
+        let xml = "wayland-protocols/fractional-scale-v1.xml";
+        println!("cargo:rerun-if-changed={}", xml);
+        wayland_scanner::generate_code(
+            xml,
+            out_dir.join("fractional-scale-v1_client_api.rs"),
+            wayland_scanner::Side::Client,
+        );
+    }
+
+    println!("cargo:rerun-if-changed=build.rs");
+}
@@ -38,7 +38,10 @@ fn main() {
     let mut close_requested = false;
 
     event_loop.run(move |event, _, control_flow| {
-        use winit::event::{ElementState, StartCause, VirtualKeyCode};
+        use winit::{
+            event::{ElementState, StartCause},
+            keyboard::Key,
+        };
         println!("{:?}", event);
         match event {
             Event::NewEvents(start_cause) => {
@@ -54,29 +57,32 @@ fn main() {
                 WindowEvent::KeyboardInput {
                     input:
                         KeyboardInput {
-                            virtual_keycode: Some(virtual_code),
+                            logical_key,
                             state: ElementState::Pressed,
                             ..
                         },
                     ..
-                } => match virtual_code {
-                    VirtualKeyCode::Key1 => {
-                        mode = Mode::Wait;
-                        println!("\nmode: {:?}\n", mode);
-                    }
-                    VirtualKeyCode::Key2 => {
-                        mode = Mode::WaitUntil;
-                        println!("\nmode: {:?}\n", mode);
-                    }
-                    VirtualKeyCode::Key3 => {
-                        mode = Mode::Poll;
-                        println!("\nmode: {:?}\n", mode);
-                    }
-                    VirtualKeyCode::R => {
-                        request_redraw = !request_redraw;
-                        println!("\nrequest_redraw: {}\n", request_redraw);
-                    }
-                    VirtualKeyCode::Escape => {
+                } => match logical_key {
+                    Key::Character(ch) => match ch.as_ref() {
+                        "1" => {
+                            mode = Mode::Wait;
+                            println!("\nmode: {:?}\n", mode);
+                        }
+                        "2" => {
+                            mode = Mode::WaitUntil;
+                            println!("\nmode: {:?}\n", mode);
+                        }
+                        "3" => {
+                            mode = Mode::Poll;
+                            println!("\nmode: {:?}\n", mode);
+                        }
+                        "r" => {
+                            request_redraw = !request_redraw;
+                            println!("\nrequest_redraw: {}\n", request_redraw);
+                        }
+                        _ => (),
+                    },
+                    Key::Escape => {
                         close_requested = true;
                     }
                     _ => (),
@@ -2,6 +2,7 @@ use simple_logger::SimpleLogger;
 use winit::{
     event::{DeviceEvent, ElementState, Event, KeyboardInput, ModifiersState, WindowEvent},
     event_loop::{ControlFlow, EventLoop},
+    keyboard::Key,
     window::WindowBuilder,
 };
 
@@ -26,19 +27,19 @@ fn main() {
                     input:
                         KeyboardInput {
                             state: ElementState::Released,
-                            virtual_keycode: Some(key),
+                            logical_key,
                             ..
                         },
                     ..
-                } => {
-                    use winit::event::VirtualKeyCode::*;
-                    match key {
-                        Escape => *control_flow = ControlFlow::Exit,
-                        G => window.set_cursor_grab(!modifiers.shift()).unwrap(),
-                        H => window.set_cursor_visible(modifiers.shift()),
+                } => match logical_key {
+                    Key::Escape => *control_flow = ControlFlow::Exit,
+                    Key::Character(ch) => match ch.to_lowercase().as_str() {
+                        "g" => window.set_cursor_grab(!modifiers.shift()).unwrap(),
+                        "h" => window.set_cursor_visible(modifiers.shift()),
                         _ => (),
-                    }
-                }
+                    },
+                    _ => (),
+                },
                 WindowEvent::ModifiersChanged(m) => modifiers = m,
                 _ => (),
             },
@@ -0,0 +1,23 @@
+use simple_logger::SimpleLogger;
+use winit::event_loop::EventLoop;
+
+fn main() {
+    SimpleLogger::new().init().unwrap();
+    let event_loop = EventLoop::new();
+
+    for device in event_loop.devices() {
+        println!(
+            "{:?}: {:?} \"{}\"",
+            device.id(),
+            device.kind(),
+            device.name()
+        );
+        println!("    capabilities: {:?}", device.capabilities());
+        if let (Some(vendor_id), Some(product_id)) = (device.vendor_id(), device.product_id()) {
+            println!("    vendor/product: {:04x}:{:04x}", vendor_id, product_id);
+        }
+        if let Some(persistent_id) = device.persistent_id() {
+            println!("    persistent id: {}", persistent_id);
+        }
+    }
+}
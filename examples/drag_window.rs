@@ -1,9 +1,8 @@
 use simple_logger::SimpleLogger;
 use winit::{
-    event::{
-        ElementState, Event, KeyboardInput, MouseButton, StartCause, VirtualKeyCode, WindowEvent,
-    },
+    event::{ElementState, Event, KeyboardInput, MouseButton, StartCause, WindowEvent},
     event_loop::{ControlFlow, EventLoop},
+    keyboard::Key,
     window::{Window, WindowBuilder, WindowId},
 };
 
@@ -46,11 +45,11 @@ fn main() {
                 input:
                     KeyboardInput {
                         state: ElementState::Released,
-                        virtual_keycode: Some(VirtualKeyCode::X),
+                        logical_key: Key::Character(ch),
                         ..
                     },
                 ..
-            } => {
+            } if &*ch == "x" => {
                 switched = !switched;
                 name_windows(entered_id, switched, &window_1, &window_2);
                 println!("Switched!")
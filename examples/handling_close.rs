@@ -17,10 +17,7 @@ fn main() {
     let mut close_requested = false;
 
     event_loop.run(move |event, _, control_flow| {
-        use winit::event::{
-            ElementState::Released,
-            VirtualKeyCode::{N, Y},
-        };
+        use winit::event::ElementState::Released;
         *control_flow = ControlFlow::Wait;
 
         match event {
@@ -46,14 +43,14 @@ fn main() {
                     WindowEvent::KeyboardInput {
                         input:
                             KeyboardInput {
-                                virtual_keycode: Some(virtual_code),
+                                logical_key,
                                 state: Released,
                                 ..
                             },
                         ..
                     } => {
-                        match virtual_code {
-                            Y => {
+                        match logical_key.to_text() {
+                            Some("y") => {
                                 if close_requested {
                                     // This is where you'll want to do any cleanup you need.
                                     println!("Buh-bye!");
@@ -66,7 +63,7 @@ fn main() {
                                     *control_flow = ControlFlow::Exit;
                                 }
                             }
-                            N => {
+                            Some("n") => {
                                 if close_requested {
                                     println!("Your window will continue to stay by your side.");
                                     close_requested = false;
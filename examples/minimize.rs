@@ -1,7 +1,7 @@
 extern crate winit;
 
 use simple_logger::SimpleLogger;
-use winit::event::{Event, VirtualKeyCode, WindowEvent};
+use winit::event::{Event, WindowEvent};
 use winit::event_loop::{ControlFlow, EventLoop};
 use winit::window::WindowBuilder;
 
@@ -30,7 +30,7 @@ fn main() {
             } => {
                 if window_id == window.id() {
                     // Pressing the 'M' key will minimize the window
-                    if input.virtual_keycode == Some(VirtualKeyCode::M) {
+                    if input.logical_key.to_text() == Some("m") {
                         window.set_minimized(true);
                     }
                 }
@@ -5,8 +5,9 @@ fn main() {
     use simple_logger::SimpleLogger;
     use winit::{
         dpi::{PhysicalPosition, PhysicalSize, Position, Size},
-        event::{ElementState, Event, KeyboardInput, VirtualKeyCode, WindowEvent},
+        event::{ElementState, Event, KeyboardInput, WindowEvent},
         event_loop::{ControlFlow, EventLoop},
+        keyboard::Key,
         window::{CursorIcon, Fullscreen, WindowBuilder},
     };
 
@@ -54,41 +55,46 @@ fn main() {
                         input:
                             KeyboardInput {
                                 state: ElementState::Released,
-                                virtual_keycode: Some(key),
+                                logical_key,
                                 modifiers,
                                 ..
                             },
                         ..
                     } => {
-                        window.set_title(&format!("{:?}", key));
+                        window.set_title(&format!("{:?}", logical_key));
                         let state = !modifiers.shift();
-                        use VirtualKeyCode::*;
-                        match key {
-                            A => window.set_always_on_top(state),
-                            C => window.set_cursor_icon(match state {
+                        let key = match &logical_key {
+                            Key::Character(ch) => ch.to_lowercase(),
+                            Key::ArrowLeft => "left".to_string(),
+                            Key::ArrowRight => "right".to_string(),
+                            _ => continue,
+                        };
+                        match key.as_str() {
+                            "a" => window.set_always_on_top(state),
+                            "c" => window.set_cursor_icon(match state {
                                 true => CursorIcon::Progress,
                                 false => CursorIcon::Default,
                             }),
-                            D => window.set_decorations(!state),
+                            "d" => window.set_decorations(!state),
                             // Cycle through video modes
-                            Right | Left => {
-                                video_mode_id = match key {
-                                    Left => video_mode_id.saturating_sub(1),
-                                    Right => (video_modes.len() - 1).min(video_mode_id + 1),
+                            "right" | "left" => {
+                                video_mode_id = match key.as_str() {
+                                    "left" => video_mode_id.saturating_sub(1),
+                                    "right" => (video_modes.len() - 1).min(video_mode_id + 1),
                                     _ => unreachable!(),
                                 };
                                 println!("Picking video mode: {}", video_modes[video_mode_id]);
                             }
-                            F => window.set_fullscreen(match (state, modifiers.alt()) {
+                            "f" => window.set_fullscreen(match (state, modifiers.alt()) {
                                 (true, false) => Some(Fullscreen::Borderless(None)),
                                 (true, true) => {
                                     Some(Fullscreen::Exclusive(video_modes[video_mode_id].clone()))
                                 }
                                 (false, _) => None,
                             }),
-                            G => window.set_cursor_grab(state).unwrap(),
-                            H => window.set_cursor_visible(!state),
-                            I => {
+                            "g" => window.set_cursor_grab(state).unwrap(),
+                            "h" => window.set_cursor_visible(!state),
+                            "i" => {
                                 println!("Info:");
                                 println!("-> outer_position : {:?}", window.outer_position());
                                 println!("-> inner_position : {:?}", window.inner_position());
@@ -96,28 +102,28 @@ fn main() {
                                 println!("-> inner_size     : {:?}", window.inner_size());
                                 println!("-> fullscreen     : {:?}", window.fullscreen());
                             }
-                            L => window.set_min_inner_size(match state {
+                            "l" => window.set_min_inner_size(match state {
                                 true => Some(WINDOW_SIZE),
                                 false => None,
                             }),
-                            M => window.set_maximized(state),
-                            P => window.set_outer_position({
+                            "m" => window.set_maximized(state),
+                            "p" => window.set_outer_position({
                                 let mut position = window.outer_position().unwrap();
                                 let sign = if state { 1 } else { -1 };
                                 position.x += 10 * sign;
                                 position.y += 10 * sign;
                                 position
                             }),
-                            Q => window.request_redraw(),
-                            R => window.set_resizable(state),
-                            S => window.set_inner_size(match state {
+                            "q" => window.request_redraw(),
+                            "r" => window.set_resizable(state),
+                            "s" => window.set_inner_size(match state {
                                 true => PhysicalSize::new(
                                     WINDOW_SIZE.width + 100,
                                     WINDOW_SIZE.height + 100,
                                 ),
                                 false => WINDOW_SIZE,
                             }),
-                            W => {
+                            "w" => {
                                 if let Size::Physical(size) = WINDOW_SIZE.into() {
                                     window
                                         .set_cursor_position(Position::Physical(
@@ -129,7 +135,7 @@ fn main() {
                                         .unwrap()
                                 }
                             }
-                            Z => {
+                            "z" => {
                                 window.set_visible(false);
                                 thread::sleep(Duration::from_secs(1));
                                 window.set_visible(true);
@@ -155,7 +161,7 @@ fn main() {
                     input:
                         KeyboardInput {
                             state: ElementState::Released,
-                            virtual_keycode: Some(VirtualKeyCode::Escape),
+                            logical_key: Key::Escape,
                             ..
                         },
                     ..
@@ -164,9 +170,7 @@ fn main() {
                 }
                 _ => {
                     if let Some(tx) = window_senders.get(&window_id) {
-                        if let Some(event) = event.to_static() {
-                            tx.send(event).unwrap();
-                        }
+                        tx.send(event).unwrap();
                     }
                 }
             },
@@ -0,0 +1,64 @@
+#[cfg(not(target_arch = "wasm32"))]
+fn main() {
+    use std::time::{Duration, Instant};
+
+    use simple_logger::SimpleLogger;
+    use winit::event::Event;
+    use winit::event_loop::{ControlFlow, EventLoopBuilder};
+
+    const THREADS: usize = 4;
+    const EVENTS_PER_THREAD: usize = 250_000;
+
+    SimpleLogger::new().init().unwrap();
+    let event_loop = EventLoopBuilder::<Duration>::with_user_event().build();
+
+    // Measures how long sending takes on the sending threads, which is what matters to real-time
+    // threads, and how fast the event loop receives the events.
+    let start = Instant::now();
+    for _ in 0..THREADS {
+        let proxy = event_loop.create_proxy();
+        std::thread::spawn(move || {
+            let mut slowest = Duration::default();
+            for _ in 0..EVENTS_PER_THREAD {
+                let send_start = Instant::now();
+                if proxy.send_event(Duration::default()).is_err() {
+                    return;
+                }
+                slowest = slowest.max(send_start.elapsed());
+            }
+            proxy.send_event(slowest).ok();
+        });
+    }
+
+    let mut received = 0;
+    let mut slowest_sends = Vec::new();
+    event_loop.run(move |event, _, control_flow| {
+        *control_flow = ControlFlow::Wait;
+
+        if let Event::UserEvent(slowest) = event {
+            if slowest == Duration::default() {
+                received += 1;
+            } else {
+                slowest_sends.push(slowest);
+            }
+
+            if slowest_sends.len() == THREADS {
+                let elapsed = start.elapsed();
+                println!(
+                    "received {} events from {} threads in {:?} ({:.0} events/s)",
+                    received,
+                    THREADS,
+                    elapsed,
+                    received as f64 / elapsed.as_secs_f64()
+                );
+                println!("slowest send per thread: {:?}", slowest_sends);
+                *control_flow = ControlFlow::Exit;
+            }
+        }
+    });
+}
+
+#[cfg(target_arch = "wasm32")]
+fn main() {
+    panic!("This example is not supported on web.");
+}
@@ -3,7 +3,7 @@
 use simple_logger::SimpleLogger;
 use winit::{
     dpi::{LogicalSize, PhysicalSize},
-    event::{DeviceEvent, ElementState, Event, KeyboardInput, VirtualKeyCode, WindowEvent},
+    event::{DeviceEvent, ElementState, Event, KeyboardInput, WindowEvent},
     event_loop::{ControlFlow, EventLoop},
     window::{Fullscreen, WindowBuilder},
 };
@@ -37,19 +37,19 @@ fn main() {
             Event::DeviceEvent {
                 event:
                     DeviceEvent::Key(KeyboardInput {
-                        virtual_keycode: Some(key),
+                        logical_key,
                         state: ElementState::Pressed,
                         ..
                     }),
                 ..
-            } => match key {
-                VirtualKeyCode::M => {
+            } => match logical_key.to_text() {
+                Some("m") => {
                     if minimized {
                         minimized = !minimized;
                         window.set_minimized(minimized);
                     }
                 }
-                VirtualKeyCode::V => {
+                Some("v") => {
                     if !visible {
                         visible = !visible;
                         window.set_visible(visible);
@@ -62,11 +62,11 @@ fn main() {
                 ..
             } => match input {
                 KeyboardInput {
-                    virtual_keycode: Some(key),
+                    logical_key,
                     state: ElementState::Pressed,
                     ..
-                } => match key {
-                    VirtualKeyCode::E => {
+                } => match logical_key.to_text() {
+                    Some("e") => {
                         fn area(size: PhysicalSize<u32>) -> u32 {
                             size.width * size.height
                         }
@@ -81,7 +81,7 @@ fn main() {
                             eprintln!("no video modes available");
                         }
                     }
-                    VirtualKeyCode::F => {
+                    Some("f") => {
                         if window.fullscreen().is_some() {
                             window.set_fullscreen(None);
                         } else {
@@ -89,25 +89,25 @@ fn main() {
                             window.set_fullscreen(Some(Fullscreen::Borderless(monitor)));
                         }
                     }
-                    VirtualKeyCode::P => {
+                    Some("p") => {
                         if window.fullscreen().is_some() {
                             window.set_fullscreen(None);
                         } else {
                             window.set_fullscreen(Some(Fullscreen::Borderless(None)));
                         }
                     }
-                    VirtualKeyCode::M => {
+                    Some("m") => {
                         minimized = !minimized;
                         window.set_minimized(minimized);
                     }
-                    VirtualKeyCode::Q => {
+                    Some("q") => {
                         *control_flow = ControlFlow::Exit;
                     }
-                    VirtualKeyCode::V => {
+                    Some("v") => {
                         visible = !visible;
                         window.set_visible(visible);
                     }
-                    VirtualKeyCode::X => {
+                    Some("x") => {
                         let is_maximized = window.is_maximized();
                         window.set_maximized(!is_maximized);
                     }
@@ -0,0 +1,86 @@
+//! Accessibility of windows to assistive technologies such as screen readers, with [`accesskit`].
+//!
+//! Winit owns the platform adapter of each window that enables accessibility: it answers the
+//! requests of assistive technologies from the tree of [`accesskit`] nodes supplied by the
+//! application, keeps the adapter up to date with the focus and the bounds of the window, and
+//! delivers the actions requested by assistive technologies as
+//! [`WindowEvent::AccessibilityActionRequested`].
+//!
+//! Accessibility is enabled with [`Window::enable_accessibility`], which should be done before the
+//! window is made visible, so that assistive technologies see the tree from the start. The tree
+//! is then updated with [`Window::update_accessibility_tree`] whenever the user interface changes.
+//!
+//! ```no_run
+//! use winit::{
+//!     accessibility::{
+//!         accesskit::{Node, NodeId, Role, Tree, TreeId, TreeUpdate},
+//!         TreeSource,
+//!     },
+//!     event_loop::EventLoop,
+//!     window::WindowBuilder,
+//! };
+//!
+//! const WINDOW_ID: NodeId = NodeId(0);
+//!
+//! struct InitialTree;
+//!
+//! impl TreeSource for InitialTree {
+//!     fn initial_tree(&mut self) -> Option<TreeUpdate> {
+//!         Some(TreeUpdate {
+//!             nodes: vec![(WINDOW_ID, Node::new(Role::Window))],
+//!             tree: Some(Tree::new(WINDOW_ID)),
+//!             tree_id: TreeId::ROOT,
+//!             focus: WINDOW_ID,
+//!         })
+//!     }
+//! }
+//!
+//! let event_loop = EventLoop::new();
+//! let window = WindowBuilder::new()
+//!     .with_visible(false)
+//!     .build(&event_loop)
+//!     .unwrap();
+//! window.enable_accessibility(InitialTree);
+//! window.set_visible(true);
+//! ```
+//!
+//! ## Platform-specific
+//!
+//! - **Windows:** UI Automation, answering `WM_GETOBJECT`.
+//! - **macOS:** NSAccessibility, through a subclass of the view of the window.
+//! - **X11 / Wayland:** AT-SPI. The bounds of the window are unknown on Wayland.
+//! - **iOS / Android / Web:** Unsupported, [`Window::enable_accessibility`] does nothing.
+//!
+//! [`WindowEvent::AccessibilityActionRequested`]: crate::event::WindowEvent::AccessibilityActionRequested
+//! [`Window::enable_accessibility`]: crate::window::Window::enable_accessibility
+//! [`Window::update_accessibility_tree`]: crate::window::Window::update_accessibility_tree
+
+pub use accesskit;
+
+use accesskit::TreeUpdate;
+
+/// Supplies the accessibility tree of a window, see [`Window::enable_accessibility`].
+///
+/// [`Window::enable_accessibility`]: crate::window::Window::enable_accessibility
+pub trait TreeSource: Send + 'static {
+    /// Returns the whole tree of the window, when an assistive technology starts using it.
+    ///
+    /// This is called on a thread of the platform adapter, possibly while the event loop is
+    /// running, so it can't call the methods of the window. `None` can be returned when the tree
+    /// isn't ready yet, in which case it is supplied with the next call to
+    /// [`Window::update_accessibility_tree`].
+    ///
+    /// [`Window::update_accessibility_tree`]: crate::window::Window::update_accessibility_tree
+    fn initial_tree(&mut self) -> Option<TreeUpdate>;
+}
+
+/// Supplies the initial tree of a [`TreeSource`] to the platform adapters.
+#[cfg(not(any(target_os = "android", target_os = "ios", target_arch = "wasm32")))]
+pub(crate) struct SourceHandler(pub Box<dyn TreeSource>);
+
+#[cfg(not(any(target_os = "android", target_os = "ios", target_arch = "wasm32")))]
+impl accesskit::ActivationHandler for SourceHandler {
+    fn request_initial_tree(&mut self) -> Option<TreeUpdate> {
+        self.0.initial_tree()
+    }
+}
@@ -0,0 +1,136 @@
+//! Types useful for enumerating the user's input devices.
+//!
+//! The input devices connected to the system are retrieved with
+//! [`EventLoopWindowTarget::devices`][loop_get], which returns an iterator of
+//! [`DeviceHandle`][device_handle]. The [`DeviceId`] of each handle matches the one carried by the
+//! events originating from that device, so applications can correlate the two, and a single
+//! `DeviceId` can be resolved with [`EventLoopWindowTarget::device`][loop_device].
+//!
+//! `DeviceId`s are only valid while the device is connected. To keep per-device settings across
+//! reconnects and restarts, store the [`DeviceHandle::persistent_id`] instead.
+//!
+//! [device_handle]: crate::device::DeviceHandle
+//! [loop_get]: crate::event_loop::EventLoopWindowTarget::devices
+//! [loop_device]: crate::event_loop::EventLoopWindowTarget::device
+use crate::event::DeviceId;
+
+/// Handle to an input device.
+///
+/// This is a snapshot taken when [`EventLoopWindowTarget::devices`] was called: it isn't updated
+/// when the device changes or goes away. Listen for [`DeviceEvent::Added`] and
+/// [`DeviceEvent::Removed`] and enumerate the devices again to stay up to date.
+///
+/// [`EventLoopWindowTarget::devices`]: crate::event_loop::EventLoopWindowTarget::devices
+/// [`DeviceEvent::Added`]: crate::event::DeviceEvent::Added
+/// [`DeviceEvent::Removed`]: crate::event::DeviceEvent::Removed
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DeviceHandle {
+    pub(crate) id: DeviceId,
+    pub(crate) name: String,
+    pub(crate) kind: DeviceKind,
+    pub(crate) capabilities: DeviceCapabilities,
+    pub(crate) vendor_id: Option<u16>,
+    pub(crate) product_id: Option<u16>,
+    pub(crate) persistent_id: Option<String>,
+}
+
+impl DeviceHandle {
+    /// Returns the identifier used by the events originating from this device.
+    #[inline]
+    pub fn id(&self) -> DeviceId {
+        self.id
+    }
+
+    /// Returns a human-readable name of the device.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The product string of HID devices, or the description Windows has for the
+    ///   others, which may be generic, such as "HID-compliant mouse".
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the kind of the device.
+    #[inline]
+    pub fn kind(&self) -> DeviceKind {
+        self.kind
+    }
+
+    /// Returns the kinds of input the device can produce.
+    #[inline]
+    pub fn capabilities(&self) -> DeviceCapabilities {
+        self.capabilities
+    }
+
+    /// Returns the USB vendor ID of the device, if known.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Only reported by drivers exposing the `Device Product ID` property, such as
+    ///   `libinput` and `evdev`.
+    /// - **Wayland / macOS / iOS / Android / Web:** Always `None`.
+    #[inline]
+    pub fn vendor_id(&self) -> Option<u16> {
+        self.vendor_id
+    }
+
+    /// Returns the USB product ID of the device, if known.
+    ///
+    /// See [`DeviceHandle::vendor_id`] for when it's available.
+    #[inline]
+    pub fn product_id(&self) -> Option<u16> {
+        self.product_id
+    }
+
+    /// Returns an identifier of the device that stays the same when it's reconnected, or when the
+    /// application is restarted, making it suitable for storing per-device settings.
+    ///
+    /// Two identical devices may share the same identifier.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Made of the vendor and product IDs, when known, and of the name of the device.
+    /// - **Windows:** The device interface path, which also changes when the device is plugged
+    ///   into another port.
+    /// - **Wayland / macOS / iOS / Android / Web:** Always `None`.
+    #[inline]
+    pub fn persistent_id(&self) -> Option<&str> {
+        self.persistent_id.as_deref()
+    }
+}
+
+/// Describes what kind of device an input device is.
+///
+/// This is a best effort guess based on what the platform reports about the device, and in some
+/// cases on its name.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DeviceKind {
+    Keyboard,
+    Mouse,
+    Touchpad,
+    Touchscreen,
+    /// A graphics tablet or a stylus.
+    Tablet,
+    Gamepad,
+    /// A device that doesn't fit any other kind, or whose kind couldn't be determined.
+    Other,
+}
+
+bitflags! {
+    /// Describes the kinds of input an input device can produce.
+    pub struct DeviceCapabilities: u32 {
+        /// The device has keys.
+        const KEYBOARD = 1 << 0;
+        /// The device can move a pointer, and usually has buttons.
+        const POINTER = 1 << 1;
+        /// The device can scroll.
+        const SCROLL = 1 << 2;
+        /// The device reports touches.
+        const TOUCH = 1 << 3;
+        /// The device reports pressure.
+        const PRESSURE = 1 << 4;
+    }
+}
@@ -48,7 +48,11 @@
 //! will truncate the fractional part of the float, rather than properly round to the nearest
 //! integer. Use the provided `cast` function or `From`/`Into` conversions, which handle the
 //! rounding properly. Note that precision loss will still occur when rounding from a float to an
-//! int, although rounding lessens the problem.
+//! int, although rounding lessens the problem. Conversions which can't lose precision, such as
+//! from `PhysicalSize<u32>` to `PhysicalSize<f64>`, are also available through `From`/`Into`.
+//!
+//! The position and size types support adding and subtracting values of the same type, and
+//! scaling by an `f64`, which rounds the result like `cast` does.
 //!
 //! ### Events
 //!
@@ -74,16 +78,19 @@
 //!   of pre-defined settings. All "retina displays" have a scaling factor above 1.0 by default but
 //!   the specific value varies across devices.
 //! - **X11:** Many man-hours have been spent trying to figure out how to handle DPI in X11. Winit
-//!   currently uses a three-pronged approach:
+//!   currently uses a four-pronged approach:
 //!   + Use the value in the `WINIT_X11_SCALE_FACTOR` environment variable, if present.
 //!   + If not present, use the value set in `Xft.dpi` in Xresources.
+//!   + If not present either, use the `Xft/DPI` setting of the XSETTINGS manager.
 //!   + Otherwise, calcuate the scale factor based on the millimeter monitor dimensions provided by XRandR.
 //!
 //!   If `WINIT_X11_SCALE_FACTOR` is set to `randr`, it'll ignore the `Xft.dpi` field and use the
 //!   XRandR scaling method. Generally speaking, you should try to configure the standard system
-//!   variables to do what you want before resorting to `WINIT_X11_SCALE_FACTOR`.
-//! - **Wayland:** On Wayland, scale factors are set per-screen by the server, and are always
-//!   integers (most often 1 or 2).
+//!   variables to do what you want before resorting to `WINIT_X11_SCALE_FACTOR`. Changes of
+//!   `Xft.dpi`, `Xft/DPI` and of the XRandR configuration are followed while the program runs.
+//! - **Wayland:** On Wayland, scale factors are set per-screen by the server. They're integers
+//!   (most often 1 or 2), unless the compositor supports the `wp-fractional-scale-v1` protocol,
+//!   in which case windows are given fractional scale factors such as 1.25 or 1.5.
 //! - **iOS:** Scale factors are set by Apple to the value that best suits the device, and range
 //!   from `1.0` to `3.0`. See [this article][apple_1] and [this article][apple_2] for more
 //!   information.
@@ -102,6 +109,8 @@
 //! [android_1]: https://developer.android.com/training/multiscreen/screendensities
 //! [web_1]: https://developer.mozilla.org/en-US/docs/Web/API/Window/devicePixelRatio
 
+use std::ops::{Add, AddAssign, Div, Mul, Sub, SubAssign};
+
 pub trait Pixel: Copy + Into<f64> {
     fn from_f64(f: f64) -> Self;
     fn cast<P: Pixel>(self) -> P {
@@ -509,6 +518,19 @@ impl Size {
             Size::Logical(size) => size.to_physical(scale_factor),
         }
     }
+
+    /// Clamps `input` between `min` and `max`, resolving all three against `scale_factor`.
+    ///
+    /// The result is physical, as the sizes may mix physical and logical pixels. If `min` is
+    /// greater than `max` along an axis, `min` wins.
+    pub fn clamp<S: Into<Size>>(input: S, min: S, max: S, scale_factor: f64) -> Size {
+        let (input, min, max) = (
+            input.into().to_physical::<f64>(scale_factor),
+            min.into().to_physical::<f64>(scale_factor),
+            max.into().to_physical::<f64>(scale_factor),
+        );
+        Size::Physical(input.clamp(min, max).cast())
+    }
 }
 
 impl<P: Pixel> From<PhysicalSize<P>> for Size {
@@ -566,3 +588,219 @@ impl<P: Pixel> From<LogicalPosition<P>> for Position {
         Position::Logical(position.cast())
     }
 }
+
+/// Implements the arithmetic shared by the position and size types.
+///
+/// Adding and subtracting work on values with the same pixel type, as mixing them would need a
+/// rounding choice. Multiplying and dividing by a scalar go through `f64` and round back like
+/// [`Pixel::cast`].
+macro_rules! impl_arithmetic {
+    ($ty:ident { $a:ident, $b:ident }) => {
+        impl<P: Pixel> $ty<P> {
+            /// Clamps each component between the ones of `min` and `max`.
+            ///
+            /// Unlike [`f64::clamp`], this doesn't panic if `min` is greater than `max` along an
+            /// axis, and returns the component of `min` instead.
+            #[inline]
+            pub fn clamp(self, min: Self, max: Self) -> Self {
+                fn clamp<P: Pixel>(value: P, min: P, max: P) -> P {
+                    let value = if value.into() > max.into() { max } else { value };
+                    if value.into() < min.into() {
+                        min
+                    } else {
+                        value
+                    }
+                }
+                $ty::new(
+                    clamp(self.$a, min.$a, max.$a),
+                    clamp(self.$b, min.$b, max.$b),
+                )
+            }
+        }
+
+        impl<P: Add<Output = P>> Add for $ty<P> {
+            type Output = Self;
+
+            #[inline]
+            fn add(self, other: Self) -> Self {
+                $ty::new(self.$a + other.$a, self.$b + other.$b)
+            }
+        }
+
+        impl<P: Sub<Output = P>> Sub for $ty<P> {
+            type Output = Self;
+
+            #[inline]
+            fn sub(self, other: Self) -> Self {
+                $ty::new(self.$a - other.$a, self.$b - other.$b)
+            }
+        }
+
+        impl<P: AddAssign> AddAssign for $ty<P> {
+            #[inline]
+            fn add_assign(&mut self, other: Self) {
+                self.$a += other.$a;
+                self.$b += other.$b;
+            }
+        }
+
+        impl<P: SubAssign> SubAssign for $ty<P> {
+            #[inline]
+            fn sub_assign(&mut self, other: Self) {
+                self.$a -= other.$a;
+                self.$b -= other.$b;
+            }
+        }
+
+        impl<P: Pixel> Mul<f64> for $ty<P> {
+            type Output = Self;
+
+            #[inline]
+            fn mul(self, scalar: f64) -> Self {
+                $ty::new(
+                    P::from_f64(self.$a.into() * scalar),
+                    P::from_f64(self.$b.into() * scalar),
+                )
+            }
+        }
+
+        impl<P: Pixel> Div<f64> for $ty<P> {
+            type Output = Self;
+
+            #[inline]
+            fn div(self, scalar: f64) -> Self {
+                $ty::new(
+                    P::from_f64(self.$a.into() / scalar),
+                    P::from_f64(self.$b.into() / scalar),
+                )
+            }
+        }
+
+        impl_lossless_from!($ty { $a, $b }: u8, u16, u32, i8, i16, i32, f32 => f64);
+        impl_lossless_from!($ty { $a, $b }: u8, u16, i8, i16 => f32);
+    };
+}
+
+/// Implements `From` for the pixel type conversions which can't lose precision, unlike
+/// [`Pixel::cast`] which may round.
+macro_rules! impl_lossless_from {
+    ($ty:ident { $a:ident, $b:ident }: $($from:ty),+ => $to:ty) => {
+        $(
+            impl From<$ty<$from>> for $ty<$to> {
+                #[inline]
+                fn from(value: $ty<$from>) -> Self {
+                    $ty::new(value.$a.into(), value.$b.into())
+                }
+            }
+        )+
+    };
+}
+
+impl_arithmetic!(LogicalPosition { x, y });
+impl_arithmetic!(PhysicalPosition { x, y });
+impl_arithmetic!(LogicalSize { width, height });
+impl_arithmetic!(PhysicalSize { width, height });
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scaling_integer_pixels_rounds() {
+        assert_eq!(PhysicalSize::new(3u32, 5) * 1.5, PhysicalSize::new(5, 8));
+        assert_eq!(PhysicalSize::new(3u32, 5) / 2.0, PhysicalSize::new(2, 3));
+        assert_eq!(
+            PhysicalPosition::new(-3i32, 7) / 2.0,
+            PhysicalPosition::new(-2, 4)
+        );
+        assert_eq!(
+            LogicalPosition::new(-1i8, 1) * 0.4,
+            LogicalPosition::new(0, 0)
+        );
+        // Values out of range saturate, like `Pixel::cast`.
+        assert_eq!(PhysicalSize::new(3u32, 5) * -1.0, PhysicalSize::new(0, 0));
+        assert_eq!(LogicalSize::new(200u8, 1) * 2.0, LogicalSize::new(255, 2));
+    }
+
+    #[test]
+    fn scaling_float_pixels_doesnt_round() {
+        assert_eq!(
+            LogicalSize::new(3.0f64, 5.0) * 1.5,
+            LogicalSize::new(4.5, 7.5)
+        );
+        assert_eq!(
+            PhysicalPosition::new(-3.0f32, 7.0) / 2.0,
+            PhysicalPosition::new(-1.5, 3.5)
+        );
+    }
+
+    #[test]
+    fn lossless_conversions() {
+        assert_eq!(
+            PhysicalSize::<f64>::from(PhysicalSize::new(u32::MAX, 7u32)),
+            PhysicalSize::new(u32::MAX as f64, 7.0)
+        );
+        assert_eq!(
+            LogicalPosition::<f32>::from(LogicalPosition::new(i16::MIN, 3i16)),
+            LogicalPosition::new(i16::MIN as f32, 3.0)
+        );
+        assert_eq!(
+            LogicalPosition::<f64>::from(LogicalPosition::new(0.1f32, 3.0)),
+            LogicalPosition::new(0.1f32 as f64, 3.0)
+        );
+    }
+
+    #[test]
+    fn clamp_prefers_min_over_max() {
+        let min = PhysicalSize::new(100u32, 10);
+        let max = PhysicalSize::new(80u32, 20);
+        assert_eq!(
+            PhysicalSize::new(50u32, 50).clamp(min, max),
+            PhysicalSize::new(100, 20)
+        );
+        assert_eq!(
+            PhysicalSize::new(90u32, 0).clamp(min, max),
+            PhysicalSize::new(100, 10)
+        );
+        assert_eq!(
+            Size::clamp(PhysicalSize::new(50u32, 50), min, max, 1.0),
+            Size::Physical(PhysicalSize::new(100, 20))
+        );
+    }
+
+    #[test]
+    fn clamp_mixes_logical_and_physical_sizes() {
+        let input = Size::from(LogicalSize::new(100.0, 100.0));
+        let min = Size::from(PhysicalSize::new(300u32, 0));
+        let max = Size::from(LogicalSize::new(120.0, 40.0));
+        assert_eq!(
+            Size::clamp(input, min, max, 2.0),
+            Size::Physical(PhysicalSize::new(300, 80))
+        );
+        assert_eq!(
+            Size::clamp(input, min, max, 1.0),
+            Size::Physical(PhysicalSize::new(300, 40))
+        );
+
+        // The logical sizes are rounded once resolved, not before.
+        let input = Size::from(LogicalSize::new(10.3, 10.3));
+        let max = Size::from(PhysicalSize::new(100u32, 100));
+        assert_eq!(
+            Size::clamp(input, Size::from(LogicalSize::new(0.0, 0.0)), max, 1.5),
+            Size::Physical(PhysicalSize::new(15, 15))
+        );
+    }
+
+    #[test]
+    fn arithmetic_is_componentwise() {
+        let mut position = PhysicalPosition::new(1i32, 2) + PhysicalPosition::new(10, 20);
+        assert_eq!(position, PhysicalPosition::new(11, 22));
+        position -= PhysicalPosition::new(1, 1);
+        assert_eq!(position, PhysicalPosition::new(10, 21));
+        position += PhysicalPosition::new(-10, 0);
+        assert_eq!(
+            position - PhysicalPosition::new(0, 1),
+            PhysicalPosition::new(0, 20)
+        );
+    }
+}
@@ -9,6 +9,8 @@ pub enum ExternalError {
     NotSupported(NotSupportedError),
     /// The OS cannot perform the operation.
     Os(OsError),
+    /// The operation was ignored, as it came too late to have an effect.
+    Ignored,
 }
 
 /// The error type for when the requested operation is not supported by the backend.
@@ -61,6 +63,7 @@ impl fmt::Display for ExternalError {
         match self {
             ExternalError::NotSupported(e) => e.fmt(f),
             ExternalError::Os(e) => e.fmt(f),
+            ExternalError::Ignored => f.pad("the requested operation was ignored"),
         }
     }
 }
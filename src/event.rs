@@ -34,19 +34,25 @@
 //!
 //! [event_loop_run]: crate::event_loop::EventLoop::run
 use instant::Instant;
-use std::path::PathBuf;
+use std::ops::Deref;
+use std::path::Path;
+use std::slice;
+use std::sync::{Arc, Mutex, Weak};
 
 use crate::{
     dpi::{PhysicalPosition, PhysicalSize},
+    error::ExternalError,
+    gamepad::{GamepadEvent, GamepadId},
+    keyboard::{Key, PhysicalKey, SmolStr},
     platform_impl,
-    window::{Theme, WindowId},
+    window::{ActivationToken, DecorationMode, Theme, WindowId},
 };
 
 /// Describes a generic event.
 ///
 /// See the module-level docs for more information on the event loop manages each event.
 #[derive(Debug, PartialEq)]
-pub enum Event<'a, T: 'static> {
+pub enum Event<T: 'static> {
     /// Emitted when new events arrive from the OS to be processed.
     ///
     /// This event type is useful as a place to put code that should be done before you start
@@ -58,7 +64,7 @@ pub enum Event<'a, T: 'static> {
     /// Emitted when the OS sends an event to a winit window.
     WindowEvent {
         window_id: WindowId,
-        event: WindowEvent<'a>,
+        event: WindowEvent,
     },
 
     /// Emitted when the OS sends an event to a device.
@@ -67,6 +73,46 @@ pub enum Event<'a, T: 'static> {
         event: DeviceEvent,
     },
 
+    /// Emitted when a gamepad is connected, disconnected, or receives input.
+    ///
+    /// Only emitted when the `gamepad` feature is enabled. See the [`gamepad`](crate::gamepad)
+    /// module for platform support.
+    GamepadEvent {
+        gamepad_id: GamepadId,
+        event: GamepadEvent,
+    },
+
+    /// Emitted when the active keyboard layout changes, for instance when the user switches
+    /// between input languages.
+    ///
+    /// The new layout can be queried with
+    /// [`EventLoopWindowTarget::keyboard_layout`](crate::event_loop::EventLoopWindowTarget::keyboard_layout).
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland / iOS / Android / Web:** Unsupported.
+    KeyboardLayoutChanged,
+
+    /// Emitted when the system appearance or accessibility preferences change.
+    ///
+    /// The new preferences can be queried with
+    /// [`EventLoopWindowTarget::system_preferences`](crate::event_loop::EventLoopWindowTarget::system_preferences).
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11 / Wayland / Android:** Unsupported.
+    SystemPreferencesChanged,
+
+    /// Emitted when the power source or the low power mode of the system changes.
+    ///
+    /// The new state can be queried with
+    /// [`EventLoopWindowTarget::power_state`](crate::event_loop::EventLoopWindowTarget::power_state).
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web / Android:** Unsupported.
+    PowerStateChanged,
+
     /// Emitted when an event is sent from [`EventLoopProxy::send_event`](crate::event_loop::EventLoopProxy::send_event)
     UserEvent(T),
 
@@ -76,6 +122,37 @@ pub enum Event<'a, T: 'static> {
     /// Emitted when the application has been resumed.
     Resumed,
 
+    /// Emitted when the application should pause its activity, such as playing audio or
+    /// rendering, because the system is going to sleep, the user session got locked, or the
+    /// application got hidden.
+    ///
+    /// A `SessionWake` with the same reason is emitted once it's over. Several reasons can
+    /// overlap, for instance when the system goes to sleep while the session is locked.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** `SessionSleepReason::Hidden` isn't reported.
+    /// - **Web:** Only `SessionSleepReason::Hidden` is reported, when the page isn't visible.
+    /// - **X11 / Wayland:** Only `SessionSleepReason::Locked` is reported, from the `Lock` and
+    ///   `Unlock` signals of `systemd-logind`, when the `dbus` feature is enabled.
+    /// - **iOS / Android:** Unsupported, `Suspended` and `Resumed` are emitted instead.
+    SessionSleep(SessionSleepReason),
+
+    /// Emitted when the reason of a previous `SessionSleep` is over.
+    SessionWake(SessionSleepReason),
+
+    /// Emitted when the system is running low on memory.
+    ///
+    /// Applications should release the memory they can easily recreate, such as caches, as the
+    /// OS may otherwise terminate them.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Emitted once when the available physical memory gets low, and again only
+    ///   after it got high in between.
+    /// - **macOS / X11 / Wayland / Web:** Unsupported.
+    MemoryWarning,
+
     /// Emitted when all of the event loop's input events have been processed and redraw processing
     /// is about to begin.
     ///
@@ -86,6 +163,11 @@ pub enum Event<'a, T: 'static> {
     /// [`Event::RedrawRequested`](crate::event::Event::RedrawRequested), which gets emitted
     /// immediately after this event. Programs that draw graphics continuously, like most games,
     /// can render here unconditionally for simplicity.
+    ///
+    /// Render-on-demand programs can ask for this event to only be sent when a window is about to
+    /// be redrawn, see [`EventLoopBuilder::with_main_events_cleared`].
+    ///
+    /// [`EventLoopBuilder::with_main_events_cleared`]: crate::event_loop::EventLoopBuilder::with_main_events_cleared
     MainEventsCleared,
 
     /// Emitted after `MainEventsCleared` when a window should be redrawn.
@@ -99,6 +181,12 @@ pub enum Event<'a, T: 'static> {
     /// During each iteration of the event loop, Winit will aggregate duplicate redraw requests
     /// into a single event, to help avoid duplicating rendering work.
     ///
+    /// The `RedrawRequested` events of an iteration are sent contiguously, between
+    /// `MainEventsCleared` and `RedrawEventsCleared`, so that a renderer can submit one frame
+    /// covering all of its windows. Redraws the OS asks for while the input events are handled are
+    /// held back until then. Redraws the OS asks for between iterations, e.g. during a live resize
+    /// on macOS, are sent immediately.
+    ///
     /// Mainly of interest to applications with mostly-static graphics that avoid redrawing unless
     /// something changes, like most non-game GUIs.
     RedrawRequested(WindowId),
@@ -118,7 +206,7 @@ pub enum Event<'a, T: 'static> {
     LoopDestroyed,
 }
 
-impl<T: Clone> Clone for Event<'static, T> {
+impl<T: Clone> Clone for Event<T> {
     fn clone(&self) -> Self {
         use self::Event::*;
         match self {
@@ -131,6 +219,16 @@ impl<T: Clone> Clone for Event<'static, T> {
                 device_id: *device_id,
                 event: event.clone(),
             },
+            GamepadEvent { gamepad_id, event } => GamepadEvent {
+                gamepad_id: *gamepad_id,
+                event: event.clone(),
+            },
+            KeyboardLayoutChanged => KeyboardLayoutChanged,
+            SystemPreferencesChanged => SystemPreferencesChanged,
+            PowerStateChanged => PowerStateChanged,
+            SessionSleep(reason) => SessionSleep(*reason),
+            SessionWake(reason) => SessionWake(*reason),
+            MemoryWarning => MemoryWarning,
             NewEvents(cause) => NewEvents(*cause),
             MainEventsCleared => MainEventsCleared,
             RedrawRequested(wid) => RedrawRequested(*wid),
@@ -142,13 +240,21 @@ impl<T: Clone> Clone for Event<'static, T> {
     }
 }
 
-impl<'a, T> Event<'a, T> {
-    pub fn map_nonuser_event<U>(self) -> Result<Event<'a, U>, Event<'a, T>> {
+impl<T> Event<T> {
+    #[allow(clippy::result_large_err)]
+    pub fn map_nonuser_event<U>(self) -> Result<Event<U>, Event<T>> {
         use self::Event::*;
         match self {
             UserEvent(_) => Err(self),
             WindowEvent { window_id, event } => Ok(WindowEvent { window_id, event }),
             DeviceEvent { device_id, event } => Ok(DeviceEvent { device_id, event }),
+            GamepadEvent { gamepad_id, event } => Ok(GamepadEvent { gamepad_id, event }),
+            KeyboardLayoutChanged => Ok(KeyboardLayoutChanged),
+            SystemPreferencesChanged => Ok(SystemPreferencesChanged),
+            PowerStateChanged => Ok(PowerStateChanged),
+            SessionSleep(reason) => Ok(SessionSleep(reason)),
+            SessionWake(reason) => Ok(SessionWake(reason)),
+            MemoryWarning => Ok(MemoryWarning),
             NewEvents(cause) => Ok(NewEvents(cause)),
             MainEventsCleared => Ok(MainEventsCleared),
             RedrawRequested(wid) => Ok(RedrawRequested(wid)),
@@ -158,26 +264,18 @@ impl<'a, T> Event<'a, T> {
             Resumed => Ok(Resumed),
         }
     }
+}
 
-    /// If the event doesn't contain a reference, turn it into an event with a `'static` lifetime.
-    /// Otherwise, return `None`.
-    pub fn to_static(self) -> Option<Event<'static, T>> {
-        use self::Event::*;
-        match self {
-            WindowEvent { window_id, event } => event
-                .to_static()
-                .map(|event| WindowEvent { window_id, event }),
-            UserEvent(event) => Some(UserEvent(event)),
-            DeviceEvent { device_id, event } => Some(DeviceEvent { device_id, event }),
-            NewEvents(cause) => Some(NewEvents(cause)),
-            MainEventsCleared => Some(MainEventsCleared),
-            RedrawRequested(wid) => Some(RedrawRequested(wid)),
-            RedrawEventsCleared => Some(RedrawEventsCleared),
-            LoopDestroyed => Some(LoopDestroyed),
-            Suspended => Some(Suspended),
-            Resumed => Some(Resumed),
-        }
-    }
+/// Describes why the application should pause, see [`Event::SessionSleep`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SessionSleepReason {
+    /// The system is going to sleep.
+    SystemSleep,
+    /// The user session is locked.
+    Locked,
+    /// The application is hidden.
+    Hidden,
 }
 
 /// Describes the reason the event loop is resuming.
@@ -198,6 +296,19 @@ pub enum StartCause {
         requested_resume: Option<Instant>,
     },
 
+    /// Sent if user events sent through an `EventLoopProxy` ended a wait, and the platform had no
+    /// events of its own. Contains the moment the wait was requested and the resume time, if
+    /// requested.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11 / Wayland:** Supported.
+    /// - **Other platforms:** `WaitCancelled` is sent instead.
+    UserEventReceived {
+        start: Instant,
+        requested_resume: Option<Instant>,
+    },
+
     /// Sent if the event loop is being resumed after the loop's control flow was set to
     /// `ControlFlow::Poll`.
     Poll,
@@ -208,7 +319,7 @@ pub enum StartCause {
 
 /// Describes an event from a `Window`.
 #[derive(Debug, PartialEq)]
-pub enum WindowEvent<'a> {
+pub enum WindowEvent {
     /// The size of the window has changed. Contains the client area's new dimensions.
     Resized(PhysicalSize<u32>),
 
@@ -224,14 +335,14 @@ pub enum WindowEvent<'a> {
     /// A file has been dropped into the window.
     ///
     /// When the user drops multiple files at once, this event will be emitted for each file
-    /// separately.
-    DroppedFile(PathBuf),
+    /// separately. The path is reference-counted, so that cloning the event doesn't allocate.
+    DroppedFile(Arc<Path>),
 
     /// A file is being hovered over the window.
     ///
     /// When the user hovers multiple files at once, this event will be emitted for each file
-    /// separately.
-    HoveredFile(PathBuf),
+    /// separately. The path is reference-counted, so that cloning the event doesn't allocate.
+    HoveredFile(Arc<Path>),
 
     /// A file was hovered, but has exited the window.
     ///
@@ -292,7 +403,7 @@ pub enum WindowEvent<'a> {
     MouseWheel {
         device_id: DeviceId,
         delta: MouseScrollDelta,
-        phase: TouchPhase,
+        phase: ScrollPhase,
         #[deprecated = "Deprecated in favor of WindowEvent::ModifiersChanged"]
         modifiers: ModifiersState,
     },
@@ -302,18 +413,73 @@ pub enum WindowEvent<'a> {
         device_id: DeviceId,
         state: ElementState,
         button: MouseButton,
+        /// The number of presses of this button made in quick succession, as defined by the
+        /// platform's double-click settings: `1` for a single click, `2` for a double click, and
+        /// so on. Releases carry the count of the press they end.
+        ///
+        /// ## Platform-specific
+        ///
+        /// - **X11 / Wayland / Web:** The platform doesn't expose double-click settings, a 400ms
+        ///   interval and a 5 pixels distance are used.
+        click_count: u32,
         #[deprecated = "Deprecated in favor of WindowEvent::ModifiersChanged"]
         modifiers: ModifiersState,
     },
 
+    /// Two-finger pinch gesture, often used for magnification.
+    ///
+    /// `delta` is the change of scale since the previous event, the content should be scaled by
+    /// `1.0 + delta`. Positive values zoom in.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only available on **macOS**, **Wayland** and **Windows**.
+    /// - **Windows:** Only reported for touch screens, and for the windows built with
+    ///   `WindowBuilderExtWindows::with_touch_gestures`, which don't receive
+    ///   [`WindowEvent::Touch`] for them.
+    PinchGesture {
+        device_id: DeviceId,
+        delta: f64,
+        phase: TouchPhase,
+    },
+
+    /// Double tap gesture, used on macOS for "smart magnification" of the content under the
+    /// pointer.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only available on **macOS**.
+    DoubleTapGesture { device_id: DeviceId },
+
+    /// Two-finger rotation gesture.
+    ///
+    /// `delta` is the rotation since the previous event, in degrees. Positive values are
+    /// counterclockwise.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only available on **macOS**, **Wayland** and **Windows**.
+    /// - **Windows:** Only reported for touch screens, see [`WindowEvent::PinchGesture`].
+    RotationGesture {
+        device_id: DeviceId,
+        delta: f32,
+        phase: TouchPhase,
+    },
+
     /// Touchpad pressure event.
     ///
-    /// At the moment, only supported on Apple forcetouch-capable macbooks.
-    /// The parameters are: pressure level (value between 0 and 1 representing how hard the touchpad
-    /// is being pressed) and stage (integer representing the click level).
+    /// Sent whenever the pressure applied on a pressure-sensitive touchpad changes while it is
+    /// being clicked, which allows implementing force clicks and pressure-sensitive controls.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only available on **macOS**, with Force Touch trackpads.
     TouchpadPressure {
         device_id: DeviceId,
+        /// How hard the touchpad is being pressed, between `0.0` and `1.0`.
         pressure: f32,
+        /// The click level reached: `0` when the touchpad isn't clicked, `1` for a normal click,
+        /// and `2` for a force click.
         stage: i64,
     },
 
@@ -327,6 +493,14 @@ pub enum WindowEvent<'a> {
     /// Touch event has been received
     Touch(Touch),
 
+    /// Pen or stylus event has been received.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Pens no longer emit `Touch` events.
+    /// - **iOS / Android / Web:** Unsupported, pens are reported as `Touch` events.
+    Pen(Pen),
+
     /// The window's scale factor has changed.
     ///
     /// The following user actions can cause DPI changes:
@@ -335,14 +509,20 @@ pub enum WindowEvent<'a> {
     /// * Changing the display's scale factor (e.g. in Control Panel on Windows).
     /// * Moving the window to a display with a different scale factor.
     ///
-    /// After this event callback has been processed, the window will be resized to whatever value
-    /// is pointed to by the `new_inner_size` reference. By default, this will contain the size suggested
-    /// by the OS, but it can be changed to any value.
+    /// After this event callback has been processed, the window will be resized to the size
+    /// requested through [`InnerSizeWriter::request_inner_size`]. By default, this is the size
+    /// suggested by the OS, but it can be changed to any value.
     ///
     /// For more information about DPI in general, see the [`dpi`](crate::dpi) module.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The window switches scale factor once most of it is on a monitor with
+    ///   another DPI, and this event is followed by the `Moved` and `Resized` events of the window
+    ///   at the new scale factor, in that order.
     ScaleFactorChanged {
         scale_factor: f64,
-        new_inner_size: &'a mut PhysicalSize<u32>,
+        inner_size_writer: InnerSizeWriter,
     },
 
     /// The system window theme has changed.
@@ -352,9 +532,48 @@ pub enum WindowEvent<'a> {
     ///
     /// At the moment this is only supported on Windows.
     ThemeChanged(Theme),
+
+    /// An activation token requested with `WindowExtUnix::request_activation_token` is ready.
+    ///
+    /// The token can be passed to another application to let it activate its windows, usually
+    /// through the `XDG_ACTIVATION_TOKEN` environment variable of the process it's launched in.
+    ///
+    /// At the moment this is only supported on Wayland.
+    ActivationTokenDone(ActivationToken),
+
+    /// The side drawing the decorations of the window changed, see
+    /// `WindowExtUnix::decoration_mode`.
+    ///
+    /// At the moment this is only supported on Wayland.
+    DecorationModeChanged(DecorationMode),
+
+    /// A button of the taskbar thumbnail toolbar was clicked, with the identifier it was given in
+    /// `WindowExtWindows::set_thumbnail_buttons`.
+    ///
+    /// At the moment this is only supported on Windows.
+    ThumbnailButtonClicked(u32),
+
+    /// The "+" button of the tab bar of the window was clicked, requesting a new tab, which can be
+    /// added with `WindowExtMacOS::add_tab`.
+    ///
+    /// At the moment this is only supported on macOS.
+    NewTabRequested,
+
+    /// The parts of the window covered by system bars changed, the new insets can be read with
+    /// `WindowExtAndroid::insets` or `WindowExtIOS::safe_area_insets`.
+    ///
+    /// At the moment this is only supported on Android and iOS.
+    InsetsChanged,
+
+    /// An assistive technology requested an action on a node of the accessibility tree of the
+    /// window, see [`Window::enable_accessibility`](crate::window::Window::enable_accessibility).
+    ///
+    /// At the moment this is only supported on Windows, macOS, X11 and Wayland.
+    #[cfg(feature = "accesskit")]
+    AccessibilityActionRequested(accesskit::ActionRequest),
 }
 
-impl Clone for WindowEvent<'static> {
+impl Clone for WindowEvent {
     fn clone(&self) -> Self {
         use self::WindowEvent::*;
         return match self {
@@ -373,7 +592,7 @@ impl Clone for WindowEvent<'static> {
                 is_synthetic,
             } => KeyboardInput {
                 device_id: *device_id,
-                input: *input,
+                input: input.clone(),
                 is_synthetic: *is_synthetic,
             },
 
@@ -411,13 +630,36 @@ impl Clone for WindowEvent<'static> {
                 device_id,
                 state,
                 button,
+                click_count,
                 modifiers,
             } => MouseInput {
                 device_id: *device_id,
                 state: *state,
                 button: *button,
+                click_count: *click_count,
                 modifiers: *modifiers,
             },
+            PinchGesture {
+                device_id,
+                delta,
+                phase,
+            } => PinchGesture {
+                device_id: *device_id,
+                delta: *delta,
+                phase: *phase,
+            },
+            DoubleTapGesture { device_id } => DoubleTapGesture {
+                device_id: *device_id,
+            },
+            RotationGesture {
+                device_id,
+                delta,
+                phase,
+            } => RotationGesture {
+                device_id: *device_id,
+                delta: *delta,
+                phase: *phase,
+            },
             TouchpadPressure {
                 device_id,
                 pressure,
@@ -437,98 +679,64 @@ impl Clone for WindowEvent<'static> {
                 value: *value,
             },
             Touch(touch) => Touch(*touch),
+            Pen(pen) => Pen(*pen),
             ThemeChanged(theme) => ThemeChanged(*theme),
-            ScaleFactorChanged { .. } => {
-                unreachable!("Static event can't be about scale factor changing")
-            }
+            ActivationTokenDone(token) => ActivationTokenDone(token.clone()),
+            DecorationModeChanged(mode) => DecorationModeChanged(*mode),
+            ThumbnailButtonClicked(id) => ThumbnailButtonClicked(*id),
+            NewTabRequested => NewTabRequested,
+            InsetsChanged => InsetsChanged,
+            #[cfg(feature = "accesskit")]
+            AccessibilityActionRequested(request) => AccessibilityActionRequested(request.clone()),
+            ScaleFactorChanged {
+                scale_factor,
+                inner_size_writer,
+            } => ScaleFactorChanged {
+                scale_factor: *scale_factor,
+                inner_size_writer: inner_size_writer.clone(),
+            },
         };
     }
 }
 
-impl<'a> WindowEvent<'a> {
-    pub fn to_static(self) -> Option<WindowEvent<'static>> {
-        use self::WindowEvent::*;
-        match self {
-            Resized(size) => Some(Resized(size)),
-            Moved(position) => Some(Moved(position)),
-            CloseRequested => Some(CloseRequested),
-            Destroyed => Some(Destroyed),
-            DroppedFile(file) => Some(DroppedFile(file)),
-            HoveredFile(file) => Some(HoveredFile(file)),
-            HoveredFileCancelled => Some(HoveredFileCancelled),
-            ReceivedCharacter(c) => Some(ReceivedCharacter(c)),
-            Focused(focused) => Some(Focused(focused)),
-            KeyboardInput {
-                device_id,
-                input,
-                is_synthetic,
-            } => Some(KeyboardInput {
-                device_id,
-                input,
-                is_synthetic,
-            }),
-            ModifiersChanged(modifiers) => Some(ModifiersChanged(modifiers)),
-            #[allow(deprecated)]
-            CursorMoved {
-                device_id,
-                position,
-                modifiers,
-            } => Some(CursorMoved {
-                device_id,
-                position,
-                modifiers,
-            }),
-            CursorEntered { device_id } => Some(CursorEntered { device_id }),
-            CursorLeft { device_id } => Some(CursorLeft { device_id }),
-            #[allow(deprecated)]
-            MouseWheel {
-                device_id,
-                delta,
-                phase,
-                modifiers,
-            } => Some(MouseWheel {
-                device_id,
-                delta,
-                phase,
-                modifiers,
-            }),
-            #[allow(deprecated)]
-            MouseInput {
-                device_id,
-                state,
-                button,
-                modifiers,
-            } => Some(MouseInput {
-                device_id,
-                state,
-                button,
-                modifiers,
-            }),
-            TouchpadPressure {
-                device_id,
-                pressure,
-                stage,
-            } => Some(TouchpadPressure {
-                device_id,
-                pressure,
-                stage,
-            }),
-            AxisMotion {
-                device_id,
-                axis,
-                value,
-            } => Some(AxisMotion {
-                device_id,
-                axis,
-                value,
-            }),
-            Touch(touch) => Some(Touch(touch)),
-            ThemeChanged(theme) => Some(ThemeChanged(theme)),
-            ScaleFactorChanged { .. } => None,
+/// Handle to request the new inner size of a window from [`WindowEvent::ScaleFactorChanged`].
+///
+/// The handle doesn't borrow the window, so the event can be stored or sent to another thread.
+/// Requests only take effect while the event is being handled though, the window is resized once
+/// the event callback returns.
+#[derive(Debug, Clone)]
+pub struct InnerSizeWriter {
+    pub(crate) new_inner_size: Weak<Mutex<PhysicalSize<u32>>>,
+}
+
+impl InnerSizeWriter {
+    pub(crate) fn new(new_inner_size: Weak<Mutex<PhysicalSize<u32>>>) -> Self {
+        Self { new_inner_size }
+    }
+
+    /// Requests the window to be resized to `new_inner_size` once the event is handled.
+    ///
+    /// Returns [`ExternalError::Ignored`] if the event was already handled.
+    pub fn request_inner_size(
+        &mut self,
+        new_inner_size: PhysicalSize<u32>,
+    ) -> Result<(), ExternalError> {
+        match self.new_inner_size.upgrade() {
+            Some(inner) => {
+                *inner.lock().unwrap() = new_inner_size;
+                Ok(())
+            }
+            None => Err(ExternalError::Ignored),
         }
     }
 }
 
+impl PartialEq for InnerSizeWriter {
+    fn eq(&self, other: &Self) -> bool {
+        self.new_inner_size.ptr_eq(&other.new_inner_size)
+    }
+}
+
 /// Identifier of an input device.
 ///
 /// Whenever you receive an event arising from a particular input device, this event contains a `DeviceId` which
@@ -575,6 +783,19 @@ pub enum DeviceEvent {
         delta: (f64, f64),
     },
 
+    /// Several `MouseMotion`s of a pointing device, delivered at once.
+    ///
+    /// Sent instead of `MouseMotion` while batching is enabled with
+    /// [`EventLoopWindowTarget::set_mouse_motion_batching`]. The motions of a device are batched
+    /// until an event of another kind is delivered, so that there's at most one batch per device
+    /// and loop iteration.
+    ///
+    /// [`EventLoopWindowTarget::set_mouse_motion_batching`]: crate::event_loop::EventLoopWindowTarget::set_mouse_motion_batching
+    MouseMotionBatch {
+        /// The motions, in the order they arrived.
+        samples: MouseMotionSamples,
+    },
+
     /// Physical scroll event
     MouseWheel {
         delta: MouseScrollDelta,
@@ -600,8 +821,61 @@ pub enum DeviceEvent {
     },
 }
 
+/// A single motion of a pointing device, see [`DeviceEvent::MouseMotionBatch`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MouseMotionSample {
+    /// (x, y) change in position in unspecified units, as in `DeviceEvent::MouseMotion`.
+    pub delta: (f64, f64),
+
+    /// When the motion happened, see [`EventLoopWindowTarget::event_timestamp`].
+    ///
+    /// [`EventLoopWindowTarget::event_timestamp`]: crate::event_loop::EventLoopWindowTarget::event_timestamp
+    pub timestamp: Instant,
+}
+
+/// The motions of a [`DeviceEvent::MouseMotionBatch`], dereferencing to a slice.
+///
+/// The storage of the samples is handed back to the event loop for the next batches once the
+/// event and its clones are dropped, so that batching doesn't allocate.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MouseMotionSamples(Arc<Vec<MouseMotionSample>>);
+
+impl MouseMotionSamples {
+    pub(crate) fn push(&mut self, sample: MouseMotionSample) {
+        Arc::make_mut(&mut self.0).push(sample);
+    }
+
+    /// Empties the samples to reuse their storage, if they aren't shared anymore.
+    pub(crate) fn reclaim(&mut self) -> bool {
+        match Arc::get_mut(&mut self.0) {
+            Some(samples) => {
+                samples.clear();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl Deref for MouseMotionSamples {
+    type Target = [MouseMotionSample];
+
+    fn deref(&self) -> &[MouseMotionSample] {
+        &self.0
+    }
+}
+
+impl<'a> IntoIterator for &'a MouseMotionSamples {
+    type Item = &'a MouseMotionSample;
+    type IntoIter = slice::Iter<'a, MouseMotionSample>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
 /// Describes a keyboard input event.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct KeyboardInput {
     /// Identifies the physical key pressed
@@ -609,15 +883,39 @@ pub struct KeyboardInput {
     /// This should not change if the user adjusts the host's keyboard map. Use when the physical location of the
     /// key is more important than the key's host GUI semantics, such as for movement controls in a first-person
     /// game.
-    pub scancode: ScanCode,
+    pub physical_key: PhysicalKey,
 
     pub state: ElementState,
 
     /// Identifies the semantic meaning of the key
     ///
     /// Use when the semantics of the key are more important than the physical location of the key, such as when
-    /// implementing appropriate behavior for "page up."
-    pub virtual_keycode: Option<VirtualKeyCode>,
+    /// implementing appropriate behavior for "page up." This takes the active keyboard layout into account.
+    pub logical_key: Key,
+
+    /// Contains the text produced by this keypress.
+    ///
+    /// This is `None` on release, and for the keys that don't produce text, such as the arrow keys. The
+    /// <kbd>Ctrl</kbd> modifier is ignored when producing it, so that <kbd>Ctrl</kbd>+<kbd>a</kbd> reports `"a"`;
+    /// the text including every modifier is available through
+    /// [`KeyEventExtModifierSupplement::text_with_all_modifiers`].
+    ///
+    /// Text input should still be handled through [`WindowEvent::ReceivedCharacter`], which also reports the text
+    /// committed by input methods.
+    ///
+    /// [`KeyEventExtModifierSupplement::text_with_all_modifiers`]: crate::platform::modifier_supplement::KeyEventExtModifierSupplement::text_with_all_modifiers
+    pub text: Option<SmolStr>,
+
+    /// Whether this press is a repeat caused by the key being held down.
+    ///
+    /// This is always `false` for releases and synthetic events, as well as in
+    /// [`DeviceEvent::Key`], which doesn't report key repeats.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The repeats are detected by receiving several presses of the same key without
+    ///   a release in between.
+    pub repeat: bool,
 
     /// Modifier keys active at the time of this input.
     ///
@@ -625,6 +923,9 @@ pub struct KeyboardInput {
     /// this device are not being delivered to the application, e.g. due to keyboard focus being elsewhere.
     #[deprecated = "Deprecated in favor of WindowEvent::ModifiersChanged"]
     pub modifiers: ModifiersState,
+
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) platform_specific: platform_impl::KeyEventExtra,
 }
 
 /// Describes touch-screen input state.
@@ -669,8 +970,59 @@ pub struct Touch {
     pub id: u64,
 }
 
+/// Describes the state of a pen in a `Pen` event.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PenPhase {
+    /// The pen came in range of the tablet while over the window.
+    Entered,
+    /// The pen touched the tablet.
+    Down,
+    /// The pen moved, or the state of one of its properties changed.
+    Moved,
+    /// The pen was lifted from the tablet.
+    Up,
+    /// The pen went out of range of the tablet, or out of the window.
+    Left,
+}
+
+/// Represents a pen or stylus event.
+///
+/// A pen coming close to the tablet generates an `Entered` event, followed by `Moved` events
+/// while it hovers the tablet. Touching the tablet generates a `Down` event, and lifting the pen
+/// an `Up` event. When the pen goes out of range, a `Left` event is generated.
+///
+/// Depending on the platform, the pen may also generate emulated mouse events, such as
+/// `CursorMoved` and `MouseInput`.
+///
+/// ## Platform-specific
+///
+/// - **X11:** `Entered` and `Left` aren't reported, and `twist` is always `0.0`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pen {
+    pub device_id: DeviceId,
+    pub phase: PenPhase,
+    pub location: PhysicalPosition<f64>,
+    /// How hard the pen is pressed against the tablet, between `0.0` and `1.0`. This is `0.0`
+    /// while the pen isn't touching the tablet, or if the pen isn't pressure sensitive.
+    pub pressure: f64,
+    /// The tilt of the pen along the X and Y axes, in degrees between `-90.0` and `90.0`.
+    ///
+    /// A pen perpendicular to the tablet has a tilt of `(0.0, 0.0)`. Positive values indicate a
+    /// pen whose top is tilted towards the right, and towards the user.
+    pub tilt: (f64, f64),
+    /// The clockwise rotation of the pen around its own axis, in degrees between `0.0` and
+    /// `360.0`.
+    pub twist: f64,
+    /// Whether the barrel button of the pen is pressed.
+    pub barrel_button: bool,
+    /// Whether the eraser end of the pen is being used.
+    pub eraser: bool,
+}
+
 /// Describes the force of a touch event
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Force {
     /// On iOS, the force is calibrated so that the same number corresponds to
     /// roughly the same amount of pressure on the screen regardless of the
@@ -725,9 +1077,6 @@ impl Force {
     }
 }
 
-/// Hardware-dependent keyboard scan code.
-pub type ScanCode = u32;
-
 /// Identifier for a specific analog axis on some device.
 pub type AxisId = u32;
 
@@ -743,12 +1092,25 @@ pub enum ElementState {
 }
 
 /// Describes a button of a mouse controller.
+///
+/// ## Platform-specific
+///
+/// The numbering of `Other` buttons is platform-dependent:
+///
+/// - **X11:** The X button number.
+/// - **Wayland:** The Linux input event code.
+/// - **macOS:** The `NSEvent` button number.
+/// - **Web:** The `MouseEvent` button number minus 3.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum MouseButton {
     Left,
     Right,
     Middle,
+    /// The "back" navigation button, usually found on the side of the mouse.
+    Back,
+    /// The "forward" navigation button, usually found on the side of the mouse.
+    Forward,
     Other(u16),
 }
 
@@ -771,207 +1133,36 @@ pub enum MouseScrollDelta {
     PixelDelta(PhysicalPosition<f64>),
 }
 
-/// Symbolic name for a keyboard key.
-#[derive(Debug, Hash, Ord, PartialOrd, PartialEq, Eq, Clone, Copy)]
-#[repr(u32)]
+/// Describes the phase of a scroll gesture.
+///
+/// A touchpad scroll starts with a `Started` event, followed by `Moved` events while the fingers
+/// are on the touchpad, and an `Ended` event when they are lifted. If the platform applies
+/// momentum, the scroll then continues with a `MomentumStarted` event, `Moved` events, and a
+/// `MomentumEnded` event. Scrolling devices without gestures, such as mouse wheels, only report
+/// `Moved`.
+///
+/// ## Platform-specific
+///
+/// - **macOS:** Reports every phase.
+/// - **Wayland:** Reports `Started`, `Moved` and `Ended`. The compositor doesn't apply momentum.
+/// - **Windows:** Reports `Started`, `Moved` and `Ended` for precision touchpads, from Windows 8.1
+///   onwards. Their scrolls are delivered as wheel messages, which don't tell when the fingers
+///   are lifted, so `Ended` is reported once the messages stopped for 100 ms. Windows doesn't
+///   apply momentum.
+/// - **X11 / Web:** Always `Moved`.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub enum VirtualKeyCode {
-    /// The '1' key over the letters.
-    Key1,
-    /// The '2' key over the letters.
-    Key2,
-    /// The '3' key over the letters.
-    Key3,
-    /// The '4' key over the letters.
-    Key4,
-    /// The '5' key over the letters.
-    Key5,
-    /// The '6' key over the letters.
-    Key6,
-    /// The '7' key over the letters.
-    Key7,
-    /// The '8' key over the letters.
-    Key8,
-    /// The '9' key over the letters.
-    Key9,
-    /// The '0' key over the 'O' and 'P' keys.
-    Key0,
-
-    A,
-    B,
-    C,
-    D,
-    E,
-    F,
-    G,
-    H,
-    I,
-    J,
-    K,
-    L,
-    M,
-    N,
-    O,
-    P,
-    Q,
-    R,
-    S,
-    T,
-    U,
-    V,
-    W,
-    X,
-    Y,
-    Z,
-
-    /// The Escape key, next to F1.
-    Escape,
-
-    F1,
-    F2,
-    F3,
-    F4,
-    F5,
-    F6,
-    F7,
-    F8,
-    F9,
-    F10,
-    F11,
-    F12,
-    F13,
-    F14,
-    F15,
-    F16,
-    F17,
-    F18,
-    F19,
-    F20,
-    F21,
-    F22,
-    F23,
-    F24,
-
-    /// Print Screen/SysRq.
-    Snapshot,
-    /// Scroll Lock.
-    Scroll,
-    /// Pause/Break key, next to Scroll lock.
-    Pause,
-
-    /// `Insert`, next to Backspace.
-    Insert,
-    Home,
-    Delete,
-    End,
-    PageDown,
-    PageUp,
-
-    Left,
-    Up,
-    Right,
-    Down,
-
-    /// The Backspace key, right over Enter.
-    // TODO: rename
-    Back,
-    /// The Enter key.
-    Return,
-    /// The space bar.
-    Space,
-
-    /// The "Compose" key on Linux.
-    Compose,
-
-    Caret,
-
-    Numlock,
-    Numpad0,
-    Numpad1,
-    Numpad2,
-    Numpad3,
-    Numpad4,
-    Numpad5,
-    Numpad6,
-    Numpad7,
-    Numpad8,
-    Numpad9,
-    NumpadAdd,
-    NumpadDivide,
-    NumpadDecimal,
-    NumpadComma,
-    NumpadEnter,
-    NumpadEquals,
-    NumpadMultiply,
-    NumpadSubtract,
-
-    AbntC1,
-    AbntC2,
-    Apostrophe,
-    Apps,
-    Asterisk,
-    At,
-    Ax,
-    Backslash,
-    Calculator,
-    Capital,
-    Colon,
-    Comma,
-    Convert,
-    Equals,
-    Grave,
-    Kana,
-    Kanji,
-    LAlt,
-    LBracket,
-    LControl,
-    LShift,
-    LWin,
-    Mail,
-    MediaSelect,
-    MediaStop,
-    Minus,
-    Mute,
-    MyComputer,
-    // also called "Next"
-    NavigateForward,
-    // also called "Prior"
-    NavigateBackward,
-    NextTrack,
-    NoConvert,
-    OEM102,
-    Period,
-    PlayPause,
-    Plus,
-    Power,
-    PrevTrack,
-    RAlt,
-    RBracket,
-    RControl,
-    RShift,
-    RWin,
-    Semicolon,
-    Slash,
-    Sleep,
-    Stop,
-    Sysrq,
-    Tab,
-    Underline,
-    Unlabeled,
-    VolumeDown,
-    VolumeUp,
-    Wake,
-    WebBack,
-    WebFavorites,
-    WebForward,
-    WebHome,
-    WebRefresh,
-    WebSearch,
-    WebStop,
-    Yen,
-    Copy,
-    Paste,
-    Cut,
+pub enum ScrollPhase {
+    /// The user started a scroll gesture.
+    Started,
+    /// The scroll moved, either from the user or from momentum.
+    Moved,
+    /// The user ended the scroll gesture.
+    Ended,
+    /// The platform started scrolling with the momentum of the gesture that just ended.
+    MomentumStarted,
+    /// The momentum scrolling came to a stop, or was interrupted.
+    MomentumEnded,
 }
 
 impl ModifiersState {
@@ -997,26 +1188,43 @@ bitflags! {
     /// Represents the current state of the keyboard modifiers
     ///
     /// Each flag represents a modifier and is set if this modifier is active.
+    ///
+    /// The side-specific flags, such as `LSHIFT` and `RSHIFT`, are set along with the generic one
+    /// on the platforms that can tell which key is held. Both sides can be set at once. Check
+    /// for modifiers with `contains` or the methods below rather than comparing states for
+    /// equality, since the side-specific flags may or may not be set.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** When <kbd>AltGr</kbd> is used as a modifier, neither <kbd>Ctrl</kbd> nor
+    ///   <kbd>Alt</kbd> are reported.
+    /// - **iOS / Android / Web:** The side-specific flags are never set.
     #[derive(Default)]
     pub struct ModifiersState: u32 {
-        // left and right modifiers are currently commented out, but we should be able to support
-        // them in a future release
         /// The "shift" key.
         const SHIFT = 0b100;
-        // const LSHIFT = 0b010;
-        // const RSHIFT = 0b001;
+        /// The left "shift" key.
+        const LSHIFT = 0b010;
+        /// The right "shift" key.
+        const RSHIFT = 0b001;
         /// The "control" key.
         const CTRL = 0b100 << 3;
-        // const LCTRL = 0b010 << 3;
-        // const RCTRL = 0b001 << 3;
+        /// The left "control" key.
+        const LCTRL = 0b010 << 3;
+        /// The right "control" key.
+        const RCTRL = 0b001 << 3;
         /// The "alt" key.
         const ALT = 0b100 << 6;
-        // const LALT = 0b010 << 6;
-        // const RALT = 0b001 << 6;
+        /// The left "alt" key.
+        const LALT = 0b010 << 6;
+        /// The right "alt" key.
+        const RALT = 0b001 << 6;
         /// This is the "windows" key on PC and "command" key on Mac.
         const LOGO = 0b100 << 9;
-        // const LLOGO = 0b010 << 9;
-        // const RLOGO = 0b001 << 9;
+        /// The left "logo" key.
+        const LLOGO = 0b010 << 9;
+        /// The right "logo" key.
+        const RLOGO = 0b001 << 9;
     }
 }
 
@@ -1033,6 +1241,14 @@ mod modifiers_serde {
         pub ctrl: bool,
         pub alt: bool,
         pub logo: bool,
+        pub lshift: bool,
+        pub rshift: bool,
+        pub lctrl: bool,
+        pub rctrl: bool,
+        pub lalt: bool,
+        pub ralt: bool,
+        pub llogo: bool,
+        pub rlogo: bool,
     }
 
     impl Serialize for ModifiersState {
@@ -1045,6 +1261,14 @@ mod modifiers_serde {
                 ctrl: self.ctrl(),
                 alt: self.alt(),
                 logo: self.logo(),
+                lshift: self.contains(ModifiersState::LSHIFT),
+                rshift: self.contains(ModifiersState::RSHIFT),
+                lctrl: self.contains(ModifiersState::LCTRL),
+                rctrl: self.contains(ModifiersState::RCTRL),
+                lalt: self.contains(ModifiersState::LALT),
+                ralt: self.contains(ModifiersState::RALT),
+                llogo: self.contains(ModifiersState::LLOGO),
+                rlogo: self.contains(ModifiersState::RLOGO),
             };
             s.serialize(serializer)
         }
@@ -1060,12 +1284,28 @@ mod modifiers_serde {
                 ctrl,
                 alt,
                 logo,
+                lshift,
+                rshift,
+                lctrl,
+                rctrl,
+                lalt,
+                ralt,
+                llogo,
+                rlogo,
             } = ModifiersStateSerialize::deserialize(deserializer)?;
             let mut m = ModifiersState::empty();
             m.set(ModifiersState::SHIFT, shift);
             m.set(ModifiersState::CTRL, ctrl);
             m.set(ModifiersState::ALT, alt);
             m.set(ModifiersState::LOGO, logo);
+            m.set(ModifiersState::LSHIFT, lshift);
+            m.set(ModifiersState::RSHIFT, rshift);
+            m.set(ModifiersState::LCTRL, lctrl);
+            m.set(ModifiersState::RCTRL, rctrl);
+            m.set(ModifiersState::LALT, lalt);
+            m.set(ModifiersState::RALT, ralt);
+            m.set(ModifiersState::LLOGO, llogo);
+            m.set(ModifiersState::RLOGO, rlogo);
             Ok(m)
         }
     }
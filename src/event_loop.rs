@@ -10,11 +10,29 @@
 //! [event_loop_proxy]: crate::event_loop::EventLoopProxy
 //! [send_event]: crate::event_loop::EventLoopProxy::send_event
 use instant::Instant;
+use std::any::{self, Any};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::marker::PhantomData;
 use std::ops::Deref;
-use std::{error, fmt};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, Weak};
+use std::{error, fmt, mem};
 
-use crate::{event::Event, monitor::MonitorHandle, platform_impl};
+use crate::{
+    device::DeviceHandle,
+    dpi::PhysicalPosition,
+    error::NotSupportedError,
+    event::{
+        DeviceEvent, DeviceId, ElementState, Event, ModifiersState, MouseButton, MouseMotionSample,
+        MouseMotionSamples, Touch, TouchPhase, WindowEvent,
+    },
+    keyboard::KeyCode,
+    monitor::MonitorHandle,
+    platform_impl,
+    window::WindowId,
+};
 
 /// Provides a way to retrieve events from the system and from the windows that were registered to
 /// the events loop.
@@ -32,6 +50,7 @@ use crate::{event::Event, monitor::MonitorHandle, platform_impl};
 ///
 pub struct EventLoop<T: 'static> {
     pub(crate) event_loop: platform_impl::EventLoop<T>,
+    pub(crate) user_events: UserEventsGuard,
     pub(crate) _marker: PhantomData<*mut ()>, // Not Send nor Sync
 }
 
@@ -43,16 +62,26 @@ pub struct EventLoop<T: 'static> {
 /// `&EventLoop`.
 pub struct EventLoopWindowTarget<T: 'static> {
     pub(crate) p: platform_impl::EventLoopWindowTarget<T>,
+    pub(crate) coalescing: Rc<EventCoalescing>,
+    /// See [`EventLoopWindowTarget::exit_with_code`].
+    pub(crate) exit_code: Rc<Cell<Option<i32>>>,
+    /// See [`EventLoopWindowTarget::event_timestamp`].
+    pub(crate) event_timestamp: Rc<Cell<Option<Instant>>>,
+    /// Used to dispatch the events of the loops started with [`EventLoopWindowTarget::run_nested`].
+    pub(crate) dispatcher_settings: Rc<RefCell<DispatcherSettings>>,
     pub(crate) _marker: PhantomData<*mut ()>, // Not Send nor Sync
 }
 
 /// Object that allows building the event loop.
 ///
 /// This is used to make specifying options that affect the whole application
-/// easier. But note that constructing multiple event loops is not supported.
+/// easier. Only one event loop may exist at a time, but on Windows, X11 and
+/// Wayland a new one can be built once the previous one has been dropped.
 #[derive(Debug, Clone, Default)]
 pub struct EventLoopBuilder<T: 'static> {
     pub(crate) platform_specific: platform_impl::PlatformSpecificEventLoopAttributes,
+    mouse_touch_emulation: bool,
+    main_events_cleared: MainEventsClearedMode,
     _p: PhantomData<T>,
 }
 
@@ -71,10 +100,46 @@ impl<T> EventLoopBuilder<T> {
     pub fn with_user_event() -> Self {
         Self {
             platform_specific: Default::default(),
+            mouse_touch_emulation: false,
+            main_events_cleared: MainEventsClearedMode::Always,
             _p: PhantomData,
         }
     }
 
+    /// Sets whether `WindowEvent::Touch` events are synthesized from the left mouse button.
+    ///
+    /// This is a development aid, to exercise touch interfaces on machines without a touchscreen.
+    /// Pressing the button starts a touch, dragging the mouse moves it, and releasing the button
+    /// ends it. The touch is cancelled if the window loses focus. The mouse events are still
+    /// delivered, right before the touch events synthesized from them.
+    ///
+    /// The emulated touch has the [`MOUSE_TOUCH_ID`] id, no force, and the `DeviceId` of the
+    /// mouse.
+    ///
+    /// The default is `false`.
+    #[inline]
+    pub fn with_mouse_touch_emulation(&mut self, emulation: bool) -> &mut Self {
+        self.mouse_touch_emulation = emulation;
+        self
+    }
+
+    /// Sets when [`Event::MainEventsCleared`] is sent.
+    ///
+    /// With [`MainEventsClearedMode::BeforeRedraws`], it's only sent in the iterations of the event
+    /// loop where a window is about to be redrawn, so applications that render on demand don't
+    /// have to do their per-frame work after every batch of events. Redraws must then be requested
+    /// from the handling of the events causing them, since requesting one while handling
+    /// `MainEventsCleared` only takes effect in the next iteration.
+    ///
+    /// The default is [`MainEventsClearedMode::Always`].
+    ///
+    /// [`Event::MainEventsCleared`]: crate::event::Event::MainEventsCleared
+    #[inline]
+    pub fn with_main_events_cleared(&mut self, mode: MainEventsClearedMode) -> &mut Self {
+        self.main_events_cleared = mode;
+        self
+    }
+
     /// Builds a new event loop.
     ///
     /// ***For cross-platform compatibility, the `EventLoop` must be created on the main thread.***
@@ -87,15 +152,30 @@ impl<T> EventLoopBuilder<T> {
     /// Usage will result in display backend initialisation, this can be controlled on linux
     /// using an environment variable `WINIT_UNIX_BACKEND`. Legal values are `x11` and `wayland`.
     /// If it is not set, winit will try to connect to a wayland connection, and if it fails will
-    /// fallback on x11. If this variable is set with any other value, winit will panic.
+    /// fallback on x11. If this variable is set with any other value, winit will panic. The backend
+    /// can also be forced with `EventLoopBuilderExtUnix`, and queried with
+    /// `EventLoopWindowTargetExtUnix::backend`.
+    ///
+    /// Building an event loop while another one is still alive is not supported. On Windows, X11
+    /// and Wayland, an event loop can be built again after the previous one (and all of its
+    /// windows) has been dropped, e.g. to run several independent scenarios in one test binary.
     ///
     /// ## Platform-specific
     ///
     /// - **iOS:** Can only be called on the main thread.
+    /// - **macOS / iOS:** Can only be called once per process.
     #[inline]
     pub fn build(&mut self) -> EventLoop<T> {
+        let event_loop = platform_impl::EventLoop::new(&self.platform_specific);
+        let user_events = UserEventsGuard(Default::default());
+        *event_loop.window_target().dispatcher_settings.borrow_mut() = DispatcherSettings {
+            user_events: user_events.0.clone(),
+            mouse_touch_emulation: self.mouse_touch_emulation,
+            main_events_cleared: self.main_events_cleared,
+        };
         EventLoop {
-            event_loop: platform_impl::EventLoop::new(&self.platform_specific),
+            event_loop,
+            user_events,
             _marker: PhantomData,
         }
     }
@@ -140,6 +220,19 @@ pub enum ControlFlow {
     Wait,
     /// When the current loop iteration finishes, suspend the thread until either another event
     /// arrives or the given time is reached.
+    ///
+    /// The event loop will never resume before the given time, but the operating system may
+    /// resume it slightly late.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Linux:** Backed by a `timerfd`, so wakeups are usually accurate to within tens of
+    ///   microseconds (subject to the thread's timer slack, 50µs by default).
+    /// - **Other Unix / macOS / iOS:** Usually accurate to within a millisecond.
+    /// - **Windows:** The scheduler period is lowered with `timeBeginPeriod` for the duration of
+    ///   the wait and the remaining time is spun away, so wakeups are usually accurate to within a
+    ///   millisecond.
+    /// - **Web:** Backed by `setTimeout`, which browsers may clamp to 4ms or more.
     WaitUntil(Instant),
     /// Send a `LoopDestroyed` event and stop the event loop. This variant is *sticky* - once set,
     /// `control_flow` cannot be changed from `ExitWithCode`, and any future attempts to do so will
@@ -197,24 +290,65 @@ impl<T> EventLoop<T> {
     ///
     /// Any values not passed to this function will *not* be dropped.
     ///
+    /// If the closure panics, no further events are delivered and the panic is resumed from this
+    /// function once the platform's own frames have been unwound, so it never crosses a native
+    /// callback boundary.
+    ///
     /// ## Platform-specific
     ///
     /// - **X11 / Wayland**: The program terminates with exit code 1 if the display server
     ///   disconnects.
+    /// - **iOS:** `UIApplicationMain` never returns, so a panic in the closure aborts the process.
     ///
     /// [`ControlFlow`]: crate::event_loop::ControlFlow
     #[inline]
-    pub fn run<F>(self, event_handler: F) -> !
+    pub fn run<F>(self, mut event_handler: F) -> !
     where
-        F: 'static + FnMut(Event<'_, T>, &EventLoopWindowTarget<T>, &mut ControlFlow),
+        F: 'static + FnMut(Event<T>, &EventLoopWindowTarget<T>, &mut ControlFlow),
     {
-        self.event_loop.run(event_handler)
+        let mut dispatcher = EventDispatcher::new(self.dispatcher_settings.borrow().clone());
+        self.event_loop.run(move |event, target, control_flow| {
+            dispatcher.dispatch(event, target, control_flow, &mut event_handler)
+        })
     }
 
     /// Creates an `EventLoopProxy` that can be used to dispatch user events to the main event loop.
     pub fn create_proxy(&self) -> EventLoopProxy<T> {
         EventLoopProxy {
             event_loop_proxy: self.event_loop.create_proxy(),
+            user_events: self.user_events.0.clone(),
+        }
+    }
+
+    /// Creates a [`BoundedEventLoopProxy`], which applies backpressure once `capacity` user events
+    /// are waiting to be delivered to the event loop.
+    ///
+    /// The capacity is checked against all the user events sent to this event loop and not yet
+    /// delivered, including the ones sent through unbounded proxies.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn create_bounded_proxy(&self, capacity: usize) -> BoundedEventLoopProxy<T> {
+        assert!(capacity > 0, "a bounded proxy needs a non-zero capacity");
+        BoundedEventLoopProxy {
+            proxy: self.create_proxy(),
+            capacity,
+        }
+    }
+}
+
+impl EventLoop<AnyUserEvent> {
+    /// Creates a [`TypedEventLoopProxy`], which sends events of type `U` to an event loop whose
+    /// user events are type-erased.
+    ///
+    /// Any number of typed proxies, with different types, can be created for the same event
+    /// loop. The events they send are delivered in order as `Event::UserEvent`, and the handler
+    /// gets them back with [`AnyUserEvent::downcast`].
+    pub fn create_typed_proxy<U: Send + 'static>(&self) -> TypedEventLoopProxy<U> {
+        TypedEventLoopProxy {
+            proxy: self.create_proxy(),
+            _marker: PhantomData,
         }
     }
 }
@@ -247,17 +381,345 @@ impl<T> EventLoopWindowTarget<T> {
     pub fn primary_monitor(&self) -> Option<MonitorHandle> {
         self.p.primary_monitor()
     }
+
+    /// Returns the list of all the input devices available on the system.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Both the virtual "core" devices, which window events originate from, and the
+    ///   physical devices, which device events originate from, are listed.
+    /// - **Wayland:** One device is listed per capability of each seat, since Wayland doesn't expose
+    ///   physical devices. Each of them has its own `DeviceId`, made of the seat and the capability.
+    ///   The seat is identified by the ID of its protocol object, which the compositor can reuse
+    ///   for a seat added after the previous one was removed.
+    /// - **Windows:** Window events are reported with a single virtual `DeviceId` that isn't listed.
+    /// - **macOS / iOS / Android / Web:** Unsupported, always returns an empty iterator.
+    #[inline]
+    pub fn devices(&self) -> impl Iterator<Item = DeviceHandle> {
+        self.p.devices().into_iter()
+    }
+
+    /// Returns the input device an event with the given [`DeviceId`] originates from.
+    ///
+    /// Returns `None` if the device is gone, or if it can't be told apart from other devices.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Returns the capability of the seat the event originates from. The `DeviceId`
+    ///   of a removed seat can end up referring to a seat added later, since their protocol object
+    ///   IDs can be reused.
+    /// - **Windows:** Window events carry a virtual `DeviceId`, for which this returns `None`.
+    /// - **macOS / iOS / Android / Web:** Unsupported, always returns `None`.
+    pub fn device(&self, id: DeviceId) -> Option<DeviceHandle> {
+        let mut devices = self.devices().filter(|device| device.id() == id);
+        match (devices.next(), devices.next()) {
+            (Some(device), None) => Some(device),
+            _ => None,
+        }
+    }
+
+    /// Changes when [`DeviceEvent`]s are delivered to the event loop.
+    ///
+    /// Defaults to [`DeviceEvents::Always`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** `WhenFocused` behaves like `Always`, since device events are only ever
+    ///   reported while the pointer is over one of the application's windows.
+    /// - **macOS / iOS / Android / Web:** Unsupported.
+    ///
+    /// [`DeviceEvent`]: crate::event::DeviceEvent
+    #[inline]
+    pub fn listen_device_events(&self, allowed: DeviceEvents) {
+        self.p.listen_device_events(allowed);
+    }
+
+    /// Sets whether [`DeviceEvent::MouseMotion`]s are batched into
+    /// [`DeviceEvent::MouseMotionBatch`]es.
+    ///
+    /// High-rate mice can report thousands of motions per second. Batching them delivers a single
+    /// event per device and loop iteration while keeping every motion, unlike
+    /// [`Window::set_cursor_moved_coalescing`], which sums them. Batching takes precedence over
+    /// coalescing.
+    ///
+    /// The default is `false`.
+    ///
+    /// [`DeviceEvent::MouseMotion`]: crate::event::DeviceEvent::MouseMotion
+    /// [`DeviceEvent::MouseMotionBatch`]: crate::event::DeviceEvent::MouseMotionBatch
+    /// [`Window::set_cursor_moved_coalescing`]: crate::window::Window::set_cursor_moved_coalescing
+    #[inline]
+    pub fn set_mouse_motion_batching(&self, batching: bool) {
+        self.coalescing.mouse_motion_batching.set(batching);
+    }
+
+    /// Returns an identifier of the active keyboard layout, if it can be determined.
+    ///
+    /// The format of the identifier depends on the platform, it is meant to be compared and
+    /// stored rather than parsed. [`Event::KeyboardLayoutChanged`] is emitted when the layout
+    /// changes.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The keyboard layout identifier, such as `00000409` for US English.
+    /// - **macOS:** The identifier of the keyboard input source, such as
+    ///   `com.apple.keylayout.US`.
+    /// - **X11:** The name of the active XKB group, such as `English (US)`.
+    /// - **Wayland / iOS / Android / Web:** Unsupported, always returns `None`.
+    ///
+    /// [`Event::KeyboardLayoutChanged`]: crate::event::Event::KeyboardLayoutChanged
+    #[inline]
+    pub fn keyboard_layout(&self) -> Option<String> {
+        self.p.keyboard_layout()
+    }
+
+    /// Returns the text the key at the `code` position would produce under the active keyboard
+    /// layout while the `modifiers` are held, without the key having to be pressed.
+    ///
+    /// This is meant to display keyboard shortcuts, such as in menus. As with
+    /// [`KeyboardInput::text`], <kbd>Ctrl</kbd> is ignored, and `None` is returned for the keys
+    /// that don't produce text. Dead keys produce the character they stand for.
+    ///
+    /// The key is found by scanning the table of the platform's key codes rather than through a
+    /// reverse table, which is cheap enough for displaying shortcuts but not meant to be done for
+    /// every event.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** <kbd>Ctrl</kbd> together with <kbd>Alt</kbd> is treated as <kbd>AltGr</kbd>.
+    /// - **Wayland / iOS / Android / Web:** Unsupported, always returns `None`.
+    ///
+    /// [`KeyboardInput::text`]: crate::event::KeyboardInput::text
+    #[inline]
+    pub fn key_code_to_text(&self, code: KeyCode, modifiers: ModifiersState) -> Option<String> {
+        self.p.key_code_to_text(code, modifiers)
+    }
+
+    /// Returns the system appearance and accessibility preferences.
+    ///
+    /// [`Event::SystemPreferencesChanged`] is emitted when they change.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS:** The accent color isn't reported.
+    /// - **Web:** The accent color isn't reported, and high contrast is reported when the user
+    ///   prefers more contrast.
+    /// - **X11 / Wayland / Android:** Unsupported, always returns the default preferences.
+    ///
+    /// [`Event::SystemPreferencesChanged`]: crate::event::Event::SystemPreferencesChanged
+    #[inline]
+    pub fn system_preferences(&self) -> SystemPreferences {
+        self.p.system_preferences()
+    }
+
+    /// Returns where the system draws its power from, and whether it's saving power.
+    ///
+    /// [`Event::PowerStateChanged`] is emitted when it changes.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11 / Wayland:** Read from `/sys/class/power_supply`, the low power mode being the
+    ///   `low-power` ACPI platform profile.
+    /// - **Web / Android:** Unsupported, always returns the default state.
+    ///
+    /// [`Event::PowerStateChanged`]: crate::event::Event::PowerStateChanged
+    #[inline]
+    pub fn power_state(&self) -> PowerState {
+        self.p.power_state()
+    }
+
+    /// Requests the event loop to exit with `code`, like setting the control flow to
+    /// [`ControlFlow::ExitWithCode`].
+    ///
+    /// This is meant for code that only has access to the window target, such as helpers shared
+    /// between event handlers. The control flow is set before and after the next time the event
+    /// handler gets called, and then stays sticky. Only the first requested code is used.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android / iOS / Web:** The exit code is unused, see [`ControlFlow::ExitWithCode`].
+    #[inline]
+    pub fn exit_with_code(&self, code: i32) {
+        if self.exit_code.get().is_none() {
+            self.exit_code.set(Some(code));
+        }
+    }
+
+    /// Returns when the input event being handled happened.
+    ///
+    /// This is the timestamp the platform attached to the event when there's one, and otherwise
+    /// the time at which winit received the event. It's a monotonic time, meant for measuring input
+    /// latency or the velocity of a gesture; coalesced events have the time of the latest event
+    /// merged into them. Returns `None` outside of the handling of an input event, i.e. window
+    /// events caused by the user, device events and gamepad events.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Taken from the server timestamp, when the server uses the monotonic clock.
+    /// - **Windows:** Taken from the message time. Device events read on the dedicated thread of
+    ///   `EventLoopBuilderExtWindows::with_raw_input_thread` have the time at which that thread read them.
+    /// - **Wayland:** The time at which the event was read from the compositor.
+    /// - **macOS / iOS / Android / Web:** The time at which the event was dispatched.
+    #[inline]
+    pub fn event_timestamp(&self) -> Option<Instant> {
+        self.event_timestamp.get()
+    }
+
+    /// Runs a nested event loop from within the event handler, for example while showing a
+    /// blocking dialog or tracking a drag, and returns once `event_handler` sets the control flow
+    /// to [`ControlFlow::ExitWithCode`], with that code.
+    ///
+    /// The events received in the meantime are sent to `event_handler`, starting with
+    /// `NewEvents(StartCause::Init)` and without a final `LoopDestroyed`. The handler that called
+    /// this function is still running, so it can't be given these events itself; forward them to
+    /// the logic it shares with `event_handler` instead. Once the nested loop returns, the outer
+    /// loop carries on from where it was. Requesting an exit with
+    /// [`exit_with_code`](Self::exit_with_code) ends both loops.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Runs a nested message loop, like the ones of modal dialogs.
+    /// - **macOS:** Pumps the application's events from a nested run loop, in the default mode.
+    /// - **X11 / Wayland / iOS / Android / Web:** Unsupported.
+    pub fn run_nested<F>(&self, mut event_handler: F) -> Result<i32, NotSupportedError>
+    where
+        F: FnMut(Event<T>, &EventLoopWindowTarget<T>, &mut ControlFlow),
+    {
+        let mut dispatcher = EventDispatcher::new(self.dispatcher_settings.borrow().clone());
+        self.p.run_nested(|event, control_flow| {
+            dispatcher.dispatch(event, self, control_flow, &mut event_handler)
+        })
+    }
+}
+
+#[cfg(feature = "rwh_05")]
+unsafe impl<T> rwh_05::HasRawDisplayHandle for EventLoop<T> {
+    /// Returns a `raw-window-handle` 0.5 `RawDisplayHandle` for the display of the event loop
+    fn raw_display_handle(&self) -> rwh_05::RawDisplayHandle {
+        rwh_05::HasRawDisplayHandle::raw_display_handle(&**self)
+    }
+}
+
+#[cfg(feature = "rwh_05")]
+unsafe impl<T> rwh_05::HasRawDisplayHandle for EventLoopWindowTarget<T> {
+    /// Returns a `raw-window-handle` 0.5 `RawDisplayHandle` for the display of the event loop
+    fn raw_display_handle(&self) -> rwh_05::RawDisplayHandle {
+        self.p.raw_display_handle_rwh_05()
+    }
+}
+
+#[cfg(feature = "rwh_06")]
+impl<T> rwh_06::HasDisplayHandle for EventLoop<T> {
+    /// Returns a `raw-window-handle` 0.6 `DisplayHandle` for the display of the event loop
+    fn display_handle(&self) -> Result<rwh_06::DisplayHandle<'_>, rwh_06::HandleError> {
+        rwh_06::HasDisplayHandle::display_handle(&**self)
+    }
+}
+
+#[cfg(feature = "rwh_06")]
+impl<T> rwh_06::HasDisplayHandle for EventLoopWindowTarget<T> {
+    /// Returns a `raw-window-handle` 0.6 `DisplayHandle` for the display of the event loop
+    fn display_handle(&self) -> Result<rwh_06::DisplayHandle<'_>, rwh_06::HandleError> {
+        let raw = self.p.raw_display_handle_rwh_06()?;
+        // The display stays open for as long as the event loop is borrowed.
+        Ok(unsafe { rwh_06::DisplayHandle::borrow_raw(raw) })
+    }
+}
+
+/// System appearance and accessibility preferences, see
+/// [`EventLoopWindowTarget::system_preferences`].
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SystemPreferences {
+    /// The accent color chosen by the user, as red, green and blue sRGB components.
+    pub accent_color: Option<[u8; 3]>,
+    /// Whether the user asked for a high contrast appearance.
+    pub high_contrast: bool,
+    /// Whether the user asked for animations to be reduced.
+    pub reduced_motion: bool,
+}
+
+/// Power state of the system, see [`EventLoopWindowTarget::power_state`].
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PowerState {
+    /// Where the system draws its power from.
+    pub source: PowerSource,
+    /// Whether the user or the system asked applications to save power, such as with the battery
+    /// saver of Windows or the low power mode of macOS and iOS.
+    pub low_power_mode: bool,
+}
+
+/// Where the system draws its power from, see [`PowerState`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PowerSource {
+    /// The power source can't be determined, such as on desktops without power supply
+    /// information.
+    Unknown,
+    /// The system is plugged in.
+    Ac,
+    /// The system runs on battery.
+    Battery,
+}
+
+impl Default for PowerSource {
+    #[inline]
+    fn default() -> Self {
+        PowerSource::Unknown
+    }
+}
+
+/// Filter controlling when [`DeviceEvent`]s are delivered, see
+/// [`EventLoopWindowTarget::listen_device_events`].
+///
+/// [`DeviceEvent`]: crate::event::DeviceEvent
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DeviceEvents {
+    /// Report device events regardless of window focus.
+    Always,
+    /// Only report device events while one of the application's windows is focused.
+    WhenFocused,
+    /// Never report device events. Where possible, winit also stops listening for raw input
+    /// altogether.
+    Never,
+}
+
+impl Default for DeviceEvents {
+    #[inline]
+    fn default() -> Self {
+        DeviceEvents::Always
+    }
+}
+
+/// When [`Event::MainEventsCleared`] is sent, see [`EventLoopBuilder::with_main_events_cleared`].
+///
+/// [`Event::MainEventsCleared`]: crate::event::Event::MainEventsCleared
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum MainEventsClearedMode {
+    /// After every batch of events.
+    Always,
+    /// Only right before the `RedrawRequested` events, when a window is about to be redrawn.
+    BeforeRedraws,
+}
+
+impl Default for MainEventsClearedMode {
+    #[inline]
+    fn default() -> Self {
+        MainEventsClearedMode::Always
+    }
 }
 
 /// Used to send custom events to `EventLoop`.
 pub struct EventLoopProxy<T: 'static> {
     event_loop_proxy: platform_impl::EventLoopProxy<T>,
+    user_events: Arc<UserEventQueue>,
 }
 
 impl<T: 'static> Clone for EventLoopProxy<T> {
     fn clone(&self) -> Self {
         Self {
             event_loop_proxy: self.event_loop_proxy.clone(),
+            user_events: self.user_events.clone(),
         }
     }
 }
@@ -269,7 +731,29 @@ impl<T: 'static> EventLoopProxy<T> {
     ///
     /// Returns an `Err` if the associated `EventLoop` no longer exists.
     pub fn send_event(&self, event: T) -> Result<(), EventLoopClosed<T>> {
-        self.event_loop_proxy.send_event(event)
+        self.user_events.push();
+        self.send_reserved(event)
+    }
+
+    /// Sends `event`, for which a slot was already counted in `user_events`, giving the slot back
+    /// if the event loop is gone.
+    fn send_reserved(&self, event: T) -> Result<(), EventLoopClosed<T>> {
+        self.event_loop_proxy
+            .send_event(event)
+            .inspect_err(|_| self.user_events.pop())
+    }
+
+    /// Sets how events sent through this proxy wake up the event loop.
+    ///
+    /// Proxies start out with [`ProxyWakeup::Coalesced`], and clones inherit the setting of the
+    /// proxy they were cloned from.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Web:** Unsupported, every event wakes up the event loop and the events
+    ///   received while it is awake are delivered in the same iteration.
+    pub fn set_wakeup(&mut self, wakeup: ProxyWakeup) {
+        self.event_loop_proxy.set_wakeup(wakeup)
     }
 }
 
@@ -279,6 +763,821 @@ impl<T: 'static> fmt::Debug for EventLoopProxy<T> {
     }
 }
 
+/// Describes how an [`EventLoopProxy`] wakes up the event loop.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ProxyWakeup {
+    /// The event loop is only woken up if it doesn't already have a wakeup pending. Bursts of
+    /// events are delivered together, in a single loop iteration.
+    ///
+    /// This is the default.
+    Coalesced,
+
+    /// Every event gets its own wakeup. The event loop will finish the current iteration
+    /// (emitting [`MainEventsCleared`] and [`RedrawEventsCleared`]) after delivering this event,
+    /// before delivering any event sent after it.
+    ///
+    /// [`MainEventsCleared`]: crate::event::Event::MainEventsCleared
+    /// [`RedrawEventsCleared`]: crate::event::Event::RedrawEventsCleared
+    PerEvent,
+}
+
+impl Default for ProxyWakeup {
+    #[inline]
+    fn default() -> Self {
+        ProxyWakeup::Coalesced
+    }
+}
+
+/// An [`EventLoopProxy`] that limits how many user events can be waiting for the event loop.
+///
+/// Created with [`EventLoop::create_bounded_proxy`].
+pub struct BoundedEventLoopProxy<T: 'static> {
+    proxy: EventLoopProxy<T>,
+    capacity: usize,
+}
+
+impl<T: 'static> Clone for BoundedEventLoopProxy<T> {
+    fn clone(&self) -> Self {
+        Self {
+            proxy: self.proxy.clone(),
+            capacity: self.capacity,
+        }
+    }
+}
+
+impl<T: 'static> BoundedEventLoopProxy<T> {
+    /// Send an event to the `EventLoop` from which this proxy was created, blocking the calling
+    /// thread until the event loop has room for it.
+    ///
+    /// This must not be called from the thread running the event loop, since nothing would be
+    /// able to make room for the event and this would block forever.
+    ///
+    /// Returns an `Err` if the associated `EventLoop` no longer exists.
+    pub fn send_event(&self, event: T) -> Result<(), EventLoopClosed<T>> {
+        if !self.proxy.user_events.reserve(self.capacity) {
+            return Err(EventLoopClosed(event));
+        }
+        self.proxy.send_reserved(event)
+    }
+
+    /// Send an event to the `EventLoop` from which this proxy was created, failing instead of
+    /// blocking if the event loop has no room for it.
+    pub fn try_send_event(&self, event: T) -> Result<(), TrySendEventError<T>> {
+        if !self.proxy.user_events.try_reserve(self.capacity) {
+            return Err(TrySendEventError::Full(event));
+        }
+        self.proxy
+            .send_reserved(event)
+            .map_err(|EventLoopClosed(event)| TrySendEventError::Closed(event))
+    }
+
+    /// Returns the number of user events this proxy is allowed to leave waiting in the event
+    /// loop.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Sets how events sent through this proxy wake up the event loop.
+    ///
+    /// See [`EventLoopProxy::set_wakeup`].
+    pub fn set_wakeup(&mut self, wakeup: ProxyWakeup) {
+        self.proxy.set_wakeup(wakeup)
+    }
+}
+
+impl<T: 'static> fmt::Debug for BoundedEventLoopProxy<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoundedEventLoopProxy")
+            .field("capacity", &self.capacity)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A user event of any type, sent through a [`TypedEventLoopProxy`].
+///
+/// Use it as the user event type of the event loop to let several independent parts of the
+/// application send their own event types.
+pub struct AnyUserEvent {
+    event: Box<dyn Any + Send>,
+    type_name: &'static str,
+}
+
+impl AnyUserEvent {
+    /// Wraps `event` in an `AnyUserEvent`.
+    pub fn new<U: Send + 'static>(event: U) -> Self {
+        AnyUserEvent {
+            event: Box::new(event),
+            type_name: any::type_name::<U>(),
+        }
+    }
+
+    /// Returns `true` if the wrapped event is of type `U`.
+    pub fn is<U: 'static>(&self) -> bool {
+        self.event.is::<U>()
+    }
+
+    /// Returns the name of the type of the wrapped event, for diagnostics.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Returns a reference to the wrapped event if it is of type `U`.
+    pub fn downcast_ref<U: 'static>(&self) -> Option<&U> {
+        self.event.downcast_ref()
+    }
+
+    /// Returns a mutable reference to the wrapped event if it is of type `U`.
+    pub fn downcast_mut<U: 'static>(&mut self) -> Option<&mut U> {
+        self.event.downcast_mut()
+    }
+
+    /// Unwraps the event if it is of type `U`, or gives `self` back otherwise.
+    pub fn downcast<U: 'static>(self) -> Result<U, Self> {
+        let type_name = self.type_name;
+        self.event
+            .downcast()
+            .map(|event| *event)
+            .map_err(|event| AnyUserEvent { event, type_name })
+    }
+}
+
+impl fmt::Debug for AnyUserEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AnyUserEvent")
+            .field("type_name", &self.type_name)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Sends events of type `U` to an event loop whose user event type is [`AnyUserEvent`].
+///
+/// Created with [`EventLoop::create_typed_proxy`].
+pub struct TypedEventLoopProxy<U: 'static> {
+    proxy: EventLoopProxy<AnyUserEvent>,
+    _marker: PhantomData<fn(U)>,
+}
+
+impl<U: 'static> Clone for TypedEventLoopProxy<U> {
+    fn clone(&self) -> Self {
+        Self {
+            proxy: self.proxy.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<U: Send + 'static> TypedEventLoopProxy<U> {
+    /// Send an event to the `EventLoop` from which this proxy was created. This emits a
+    /// `UserEvent(event)` event in the event loop, where `event` is an [`AnyUserEvent`] wrapping
+    /// the value passed to this function.
+    ///
+    /// Returns an `Err` if the associated `EventLoop` no longer exists.
+    pub fn send_event(&self, event: U) -> Result<(), EventLoopClosed<U>> {
+        self.proxy.send_event(AnyUserEvent::new(event)).map_err(
+            |EventLoopClosed(event)| match event.downcast() {
+                Ok(event) => EventLoopClosed(event),
+                Err(_) => unreachable!(),
+            },
+        )
+    }
+
+    /// Sets how events sent through this proxy wake up the event loop.
+    ///
+    /// See [`EventLoopProxy::set_wakeup`].
+    pub fn set_wakeup(&mut self, wakeup: ProxyWakeup) {
+        self.proxy.set_wakeup(wakeup)
+    }
+}
+
+impl<U: 'static> fmt::Debug for TypedEventLoopProxy<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypedEventLoopProxy")
+            .field("type_name", &any::type_name::<U>())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Bookkeeping of the user events which were sent but not yet delivered, shared between an
+/// `EventLoop` and its proxies.
+///
+/// The count is atomic, so that sending and delivering events doesn't take a lock. The lock and
+/// the condition variable are only used while a bounded proxy waits for room.
+#[derive(Default)]
+pub(crate) struct UserEventQueue {
+    pending: AtomicUsize,
+    /// The number of bounded proxies waiting for room.
+    waiting: AtomicUsize,
+    closed: AtomicBool,
+    lock: Mutex<()>,
+    room: Condvar,
+}
+
+impl UserEventQueue {
+    fn push(&self) {
+        self.pending.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn pop(&self) {
+        let _ = self
+            .pending
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |pending| {
+                pending.checked_sub(1)
+            });
+        // A waiting proxy counts itself before checking for room, so either it sees the room made
+        // above, or it's seen here. The lock is taken so that it's waiting on `room` by then.
+        if self.waiting.load(Ordering::SeqCst) > 0 {
+            drop(self.lock.lock().unwrap());
+            self.room.notify_all();
+        }
+    }
+
+    /// Counts one more pending event if less than `capacity` events are pending, in a single
+    /// atomic step so that concurrent senders can't exceed `capacity` together.
+    fn try_reserve(&self, capacity: usize) -> bool {
+        self.pending
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |pending| {
+                (pending < capacity).then(|| pending + 1)
+            })
+            .is_ok()
+    }
+
+    /// Blocks until less than `capacity` events are pending, and counts one more. Returns `false`
+    /// if the event loop was dropped in the meantime.
+    fn reserve(&self, capacity: usize) -> bool {
+        loop {
+            if self.closed.load(Ordering::SeqCst) {
+                return false;
+            }
+            if self.try_reserve(capacity) {
+                return true;
+            }
+
+            let guard = self.lock.lock().unwrap();
+            self.waiting.fetch_add(1, Ordering::SeqCst);
+            let guard = self
+                .room
+                .wait_while(guard, |_| {
+                    !self.closed.load(Ordering::SeqCst)
+                        && self.pending.load(Ordering::SeqCst) >= capacity
+                })
+                .unwrap();
+            self.waiting.fetch_sub(1, Ordering::SeqCst);
+            drop(guard);
+        }
+    }
+
+    fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+        drop(self.lock.lock().unwrap());
+        self.room.notify_all();
+    }
+
+    fn track<T>(&self, event: &Event<T>) {
+        if let Event::UserEvent(_) = event {
+            self.pop();
+        }
+    }
+}
+
+/// Wakes up the senders blocked on a full event loop once the event loop goes away.
+pub(crate) struct UserEventsGuard(pub(crate) Arc<UserEventQueue>);
+
+impl Deref for UserEventsGuard {
+    type Target = UserEventQueue;
+    fn deref(&self) -> &UserEventQueue {
+        &self.0
+    }
+}
+
+impl Drop for UserEventsGuard {
+    fn drop(&mut self) {
+        self.0.close();
+    }
+}
+
+/// The windows which asked for their `CursorMoved` events to be coalesced, see
+/// [`Window::set_cursor_moved_coalescing`].
+///
+/// [`Window::set_cursor_moved_coalescing`]: crate::window::Window::set_cursor_moved_coalescing
+#[derive(Default)]
+pub(crate) struct EventCoalescing {
+    windows: RefCell<HashMap<WindowId, Weak<AtomicBool>>>,
+    /// See [`EventLoopWindowTarget::set_mouse_motion_batching`].
+    mouse_motion_batching: Cell<bool>,
+}
+
+impl EventCoalescing {
+    pub(crate) fn register(&self, window_id: WindowId, coalescing: &Arc<AtomicBool>) {
+        let mut windows = self.windows.borrow_mut();
+        windows.retain(|_, coalescing| coalescing.strong_count() > 0);
+        windows.insert(window_id, Arc::downgrade(coalescing));
+    }
+
+    fn is_enabled(coalescing: &Weak<AtomicBool>) -> bool {
+        coalescing
+            .upgrade()
+            .is_some_and(|coalescing| coalescing.load(Ordering::Relaxed))
+    }
+
+    fn cursor_moved(&self, window_id: WindowId) -> bool {
+        self.windows
+            .borrow()
+            .get(&window_id)
+            .is_some_and(Self::is_enabled)
+    }
+
+    fn mouse_motion(&self) -> bool {
+        self.windows.borrow().values().any(Self::is_enabled)
+    }
+}
+
+/// The id of the touch emulated from the mouse, see
+/// [`EventLoopBuilder::with_mouse_touch_emulation`].
+pub const MOUSE_TOUCH_ID: u64 = u64::MAX;
+
+/// Tracks the left mouse button to emulate touches, see
+/// [`EventLoopBuilder::with_mouse_touch_emulation`].
+#[derive(Default)]
+struct TouchEmulation {
+    /// The last position of the cursor in each window.
+    cursor_positions: HashMap<WindowId, PhysicalPosition<f64>>,
+    /// The window and the mouse of the ongoing touch.
+    touch: Option<(WindowId, DeviceId)>,
+}
+
+impl TouchEmulation {
+    /// Returns the touch to synthesize after `event`, if any.
+    fn touch<T>(&mut self, event: &Event<T>) -> Option<(WindowId, Touch)> {
+        let (window_id, event) = match event {
+            Event::WindowEvent { window_id, event } => (*window_id, event),
+            _ => return None,
+        };
+        let destroyed_position = match event {
+            WindowEvent::Destroyed => self.cursor_positions.remove(&window_id),
+            _ => None,
+        };
+        let (device_id, phase) = match *event {
+            WindowEvent::CursorMoved {
+                device_id,
+                position,
+                ..
+            } => {
+                self.cursor_positions.insert(window_id, position);
+                match self.touch {
+                    Some((touch_window_id, touch_device_id))
+                        if touch_window_id == window_id && touch_device_id == device_id =>
+                    {
+                        (device_id, TouchPhase::Moved)
+                    }
+                    _ => return None,
+                }
+            }
+            WindowEvent::MouseInput {
+                device_id,
+                state: ElementState::Pressed,
+                button: MouseButton::Left,
+                ..
+            } if self.touch.is_none() => {
+                self.touch = Some((window_id, device_id));
+                (device_id, TouchPhase::Started)
+            }
+            WindowEvent::MouseInput {
+                device_id,
+                state: ElementState::Released,
+                button: MouseButton::Left,
+                ..
+            } if self.touch == Some((window_id, device_id)) => {
+                self.touch = None;
+                (device_id, TouchPhase::Ended)
+            }
+            WindowEvent::Focused(false) | WindowEvent::Destroyed => match self.touch {
+                Some((touch_window_id, device_id)) if touch_window_id == window_id => {
+                    self.touch = None;
+                    (device_id, TouchPhase::Cancelled)
+                }
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        let location = destroyed_position
+            .or_else(|| self.cursor_positions.get(&window_id).copied())
+            .unwrap_or_default();
+        Some((
+            window_id,
+            Touch {
+                device_id,
+                phase,
+                location,
+                force: None,
+                id: MOUSE_TOUCH_ID,
+            },
+        ))
+    }
+}
+
+/// How many delivered batches of motions [`EventDispatcher`] keeps to reuse their storage.
+const MAX_SPARE_MOTION_SAMPLES: usize = 8;
+
+/// What [`EventDispatcher`] needs from the `EventLoopBuilder`.
+#[derive(Clone, Default)]
+pub(crate) struct DispatcherSettings {
+    pub(crate) user_events: Arc<UserEventQueue>,
+    pub(crate) mouse_touch_emulation: bool,
+    pub(crate) main_events_cleared: MainEventsClearedMode,
+}
+
+/// What [`EventDispatcher`] needs from the target it hands to the user's event handler.
+pub(crate) trait DispatchTarget {
+    fn coalescing(&self) -> &EventCoalescing;
+
+    /// See [`EventLoopWindowTarget::exit_with_code`].
+    fn exit_code(&self) -> Option<i32>;
+
+    /// The timestamp the platform gave the event being dispatched, if any.
+    fn platform_event_timestamp(&self) -> Option<Instant>;
+
+    /// See [`EventLoopWindowTarget::event_timestamp`].
+    fn set_event_timestamp(&self, timestamp: Option<Instant>);
+}
+
+impl<T> DispatchTarget for EventLoopWindowTarget<T> {
+    fn coalescing(&self) -> &EventCoalescing {
+        &self.coalescing
+    }
+
+    fn exit_code(&self) -> Option<i32> {
+        self.exit_code.get()
+    }
+
+    fn platform_event_timestamp(&self) -> Option<Instant> {
+        self.p.event_timestamp()
+    }
+
+    fn set_event_timestamp(&self, timestamp: Option<Instant>) {
+        self.event_timestamp.set(timestamp);
+    }
+}
+
+/// Sits between the platform event loop and the user's event handler, to do the bookkeeping
+/// shared by all the platforms.
+pub(crate) struct EventDispatcher<T: 'static> {
+    user_events: Arc<UserEventQueue>,
+    /// Coalesced events waiting for an event of another kind, in the order they first arrived, with
+    /// the timestamp of the latest event merged into them.
+    coalesced: Vec<(Event<T>, Instant)>,
+    /// The samples of the batches delivered so far, reused for the next batches once the
+    /// application dropped them.
+    spare_motion_samples: Vec<MouseMotionSamples>,
+    main_events_cleared: MainEventsClearedMode,
+    /// Whether a `MainEventsCleared` is held back until it's known whether redraws follow it.
+    main_events_cleared_pending: bool,
+    /// Whether the events between `NewEvents` and `MainEventsCleared` are being handled.
+    handling_main_events: bool,
+    /// Windows the platform asked to redraw while handling the main events.
+    deferred_redraws: Vec<WindowId>,
+    /// Windows redrawn from `deferred_redraws` in the current iteration.
+    redrawn: Vec<WindowId>,
+    touch_emulation: Option<TouchEmulation>,
+}
+
+/// The name of the variant of `event`, recorded on the dispatch spans.
+#[cfg(feature = "tracing")]
+fn event_name<T>(event: &Event<T>) -> &'static str {
+    match event {
+        Event::NewEvents(_) => "NewEvents",
+        Event::WindowEvent { .. } => "WindowEvent",
+        Event::DeviceEvent { .. } => "DeviceEvent",
+        Event::GamepadEvent { .. } => "GamepadEvent",
+        Event::KeyboardLayoutChanged => "KeyboardLayoutChanged",
+        Event::SystemPreferencesChanged => "SystemPreferencesChanged",
+        Event::PowerStateChanged => "PowerStateChanged",
+        Event::UserEvent(_) => "UserEvent",
+        Event::Suspended => "Suspended",
+        Event::Resumed => "Resumed",
+        Event::SessionSleep(_) => "SessionSleep",
+        Event::SessionWake(_) => "SessionWake",
+        Event::MemoryWarning => "MemoryWarning",
+        Event::MainEventsCleared => "MainEventsCleared",
+        Event::RedrawRequested(_) => "RedrawRequested",
+        Event::RedrawEventsCleared => "RedrawEventsCleared",
+        Event::LoopDestroyed => "LoopDestroyed",
+    }
+}
+
+/// Whether `event` is caused by the user's input, and so has a timestamp.
+fn is_input_event<T>(event: &Event<T>) -> bool {
+    match event {
+        Event::WindowEvent { event, .. } => matches!(
+            event,
+            WindowEvent::ReceivedCharacter(_)
+                | WindowEvent::KeyboardInput { .. }
+                | WindowEvent::ModifiersChanged(_)
+                | WindowEvent::CursorMoved { .. }
+                | WindowEvent::CursorEntered { .. }
+                | WindowEvent::CursorLeft { .. }
+                | WindowEvent::MouseWheel { .. }
+                | WindowEvent::MouseInput { .. }
+                | WindowEvent::PinchGesture { .. }
+                | WindowEvent::DoubleTapGesture { .. }
+                | WindowEvent::RotationGesture { .. }
+                | WindowEvent::TouchpadPressure { .. }
+                | WindowEvent::AxisMotion { .. }
+                | WindowEvent::Touch(_)
+                | WindowEvent::Pen(_)
+        ),
+        Event::DeviceEvent { event, .. } => {
+            !matches!(event, DeviceEvent::Added | DeviceEvent::Removed)
+        }
+        Event::GamepadEvent { .. } => true,
+        _ => false,
+    }
+}
+
+impl<T: 'static> EventDispatcher<T> {
+    pub(crate) fn new(settings: DispatcherSettings) -> Self {
+        let DispatcherSettings {
+            user_events,
+            mouse_touch_emulation,
+            main_events_cleared,
+        } = settings;
+        EventDispatcher {
+            user_events,
+            coalesced: Vec::new(),
+            spare_motion_samples: Vec::new(),
+            main_events_cleared,
+            main_events_cleared_pending: false,
+            handling_main_events: false,
+            deferred_redraws: Vec::new(),
+            redrawn: Vec::new(),
+            touch_emulation: if mouse_touch_emulation {
+                Some(TouchEmulation::default())
+            } else {
+                None
+            },
+        }
+    }
+
+    pub(crate) fn dispatch<W, F>(
+        &mut self,
+        event: Event<T>,
+        target: &W,
+        control_flow: &mut ControlFlow,
+        event_handler: &mut F,
+    ) where
+        W: DispatchTarget,
+        F: FnMut(Event<T>, &W, &mut ControlFlow),
+    {
+        let touch = self
+            .touch_emulation
+            .as_mut()
+            .and_then(|touch_emulation| touch_emulation.touch(&event));
+
+        Self::apply_exit_code(target, control_flow);
+        self.dispatch_event(event, target, control_flow, event_handler);
+
+        if let Some((window_id, touch)) = touch {
+            let event = Event::WindowEvent {
+                window_id,
+                event: WindowEvent::Touch(touch),
+            };
+            self.dispatch_event(event, target, control_flow, event_handler);
+        }
+        Self::apply_exit_code(target, control_flow);
+    }
+
+    /// Applies the exit requested through [`EventLoopWindowTarget::exit_with_code`], unless the
+    /// control flow already exits with another code.
+    fn apply_exit_code(target: &impl DispatchTarget, control_flow: &mut ControlFlow) {
+        if let Some(code) = target.exit_code() {
+            if !matches!(control_flow, ControlFlow::ExitWithCode(_)) {
+                *control_flow = ControlFlow::ExitWithCode(code);
+            }
+        }
+    }
+
+    fn dispatch_event<W, F>(
+        &mut self,
+        event: Event<T>,
+        target: &W,
+        control_flow: &mut ControlFlow,
+        event_handler: &mut F,
+    ) where
+        W: DispatchTarget,
+        F: FnMut(Event<T>, &W, &mut ControlFlow),
+    {
+        self.user_events.track(&event);
+
+        let timestamp = if is_input_event(&event) {
+            Some(
+                target
+                    .platform_event_timestamp()
+                    .unwrap_or_else(Instant::now),
+            )
+        } else {
+            None
+        };
+
+        let coalescing = target.coalescing();
+        if let Event::DeviceEvent {
+            device_id,
+            event: DeviceEvent::MouseMotion { delta },
+        } = event
+        {
+            if coalescing.mouse_motion_batching.get() {
+                // Raw motions are input events.
+                let timestamp = timestamp.unwrap_or_else(Instant::now);
+                self.batch(device_id, MouseMotionSample { delta, timestamp });
+                return;
+            }
+        }
+        let coalescable = match &event {
+            Event::WindowEvent {
+                window_id,
+                event: WindowEvent::CursorMoved { .. },
+            } => coalescing.cursor_moved(*window_id),
+            Event::DeviceEvent {
+                event: DeviceEvent::MouseMotion { .. },
+                ..
+            } => coalescing.mouse_motion(),
+            _ => false,
+        };
+
+        if coalescable {
+            // Coalescable events are all input events.
+            let timestamp = timestamp.unwrap_or_else(Instant::now);
+            if let Err(event) = self.merge(event, timestamp) {
+                self.coalesced.push((event, timestamp));
+            }
+            return;
+        }
+
+        for (coalesced, timestamp) in self.coalesced.drain(..) {
+            if let Event::DeviceEvent {
+                event: DeviceEvent::MouseMotionBatch { samples },
+                ..
+            } = &coalesced
+            {
+                if self.spare_motion_samples.len() < MAX_SPARE_MOTION_SAMPLES {
+                    self.spare_motion_samples.push(samples.clone());
+                }
+            }
+            Self::deliver(
+                coalesced,
+                Some(timestamp),
+                target,
+                control_flow,
+                event_handler,
+            );
+        }
+
+        match event {
+            Event::NewEvents(_) => self.handling_main_events = true,
+            // Redraws sent by the platform while the main events are still being handled wait for
+            // `MainEventsCleared`, so that all the redraws of an iteration are contiguous.
+            Event::RedrawRequested(window_id) if self.handling_main_events => {
+                if !self.deferred_redraws.contains(&window_id) {
+                    self.deferred_redraws.push(window_id);
+                }
+                return;
+            }
+            Event::MainEventsCleared => {
+                self.handling_main_events = false;
+                if self.main_events_cleared == MainEventsClearedMode::BeforeRedraws
+                    && self.deferred_redraws.is_empty()
+                {
+                    self.main_events_cleared_pending = true;
+                    return;
+                }
+                Self::deliver(event, None, target, control_flow, event_handler);
+                for window_id in mem::take(&mut self.deferred_redraws) {
+                    self.redrawn.push(window_id);
+                    let event = Event::RedrawRequested(window_id);
+                    Self::deliver(event, None, target, control_flow, event_handler);
+                }
+                return;
+            }
+            Event::RedrawRequested(window_id) => {
+                // The window has already been redrawn in this iteration.
+                if self.redrawn.contains(&window_id) {
+                    return;
+                }
+                if mem::take(&mut self.main_events_cleared_pending) {
+                    let event = Event::MainEventsCleared;
+                    Self::deliver(event, None, target, control_flow, event_handler);
+                }
+            }
+            Event::RedrawEventsCleared | Event::LoopDestroyed => {
+                self.handling_main_events = false;
+                self.main_events_cleared_pending = false;
+                self.deferred_redraws.clear();
+                self.redrawn.clear();
+            }
+            _ => self.main_events_cleared_pending = false,
+        }
+        Self::deliver(event, timestamp, target, control_flow, event_handler);
+    }
+
+    fn deliver<W, F>(
+        event: Event<T>,
+        timestamp: Option<Instant>,
+        target: &W,
+        control_flow: &mut ControlFlow,
+        event_handler: &mut F,
+    ) where
+        W: DispatchTarget,
+        F: FnMut(Event<T>, &W, &mut ControlFlow),
+    {
+        trace_span!("dispatch", event = event_name(&event));
+        target.set_event_timestamp(timestamp);
+        event_handler(event, target, control_flow);
+        target.set_event_timestamp(None);
+    }
+
+    /// Merges `event` into the pending event it should be coalesced with, if there's one.
+    #[allow(deprecated, clippy::result_large_err)]
+    fn merge(&mut self, event: Event<T>, timestamp: Instant) -> Result<(), Event<T>> {
+        for (pending, pending_timestamp) in &mut self.coalesced {
+            let merged = match (pending, &event) {
+                (
+                    Event::WindowEvent {
+                        window_id,
+                        event:
+                            WindowEvent::CursorMoved {
+                                device_id,
+                                position,
+                                modifiers,
+                            },
+                    },
+                    Event::WindowEvent {
+                        window_id: new_window_id,
+                        event:
+                            WindowEvent::CursorMoved {
+                                device_id: new_device_id,
+                                position: new_position,
+                                modifiers: new_modifiers,
+                            },
+                    },
+                ) if window_id == new_window_id && device_id == new_device_id => {
+                    *position = *new_position;
+                    *modifiers = *new_modifiers;
+                    true
+                }
+                (
+                    Event::DeviceEvent {
+                        device_id,
+                        event: DeviceEvent::MouseMotion { delta },
+                    },
+                    Event::DeviceEvent {
+                        device_id: new_device_id,
+                        event: DeviceEvent::MouseMotion { delta: new_delta },
+                    },
+                ) if device_id == new_device_id => {
+                    delta.0 += new_delta.0;
+                    delta.1 += new_delta.1;
+                    true
+                }
+                _ => false,
+            };
+            if merged {
+                *pending_timestamp = timestamp;
+                return Ok(());
+            }
+        }
+        Err(event)
+    }
+
+    /// Adds a raw motion to the pending batch of its device, starting one if there's none.
+    fn batch(&mut self, device_id: DeviceId, sample: MouseMotionSample) {
+        for (pending, pending_timestamp) in &mut self.coalesced {
+            if let Event::DeviceEvent {
+                device_id: pending_device_id,
+                event: DeviceEvent::MouseMotionBatch { samples },
+            } = pending
+            {
+                if *pending_device_id == device_id {
+                    samples.push(sample);
+                    *pending_timestamp = sample.timestamp;
+                    return;
+                }
+            }
+        }
+
+        let mut samples = self
+            .spare_motion_samples
+            .iter_mut()
+            .position(MouseMotionSamples::reclaim)
+            .map(|index| self.spare_motion_samples.swap_remove(index))
+            .unwrap_or_default();
+        samples.push(sample);
+        let event = Event::DeviceEvent {
+            device_id,
+            event: DeviceEvent::MouseMotionBatch { samples },
+        };
+        self.coalesced.push((event, sample.timestamp));
+    }
+}
+
 /// The error that is returned when an `EventLoopProxy` attempts to wake up an `EventLoop` that
 /// no longer exists. Contains the original event given to `send_event`.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
@@ -291,3 +1590,255 @@ impl<T> fmt::Display for EventLoopClosed<T> {
 }
 
 impl<T: fmt::Debug> error::Error for EventLoopClosed<T> {}
+
+/// The error that is returned when a [`BoundedEventLoopProxy`] fails to send an event without
+/// blocking. Contains the original event given to `try_send_event`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TrySendEventError<T> {
+    /// The event loop has too many user events waiting to be delivered.
+    Full(T),
+    /// The event loop no longer exists.
+    Closed(T),
+}
+
+impl<T> TrySendEventError<T> {
+    /// Returns the event that failed to be sent.
+    pub fn into_inner(self) -> T {
+        match self {
+            TrySendEventError::Full(event) | TrySendEventError::Closed(event) => event,
+        }
+    }
+}
+
+impl<T> fmt::Display for TrySendEventError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrySendEventError::Full(_) => f.write_str("The `EventLoop` has no room for the event"),
+            TrySendEventError::Closed(_) => f.write_str("Tried to wake up a closed `EventLoop`"),
+        }
+    }
+}
+
+impl<T: fmt::Debug> error::Error for TrySendEventError<T> {}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+
+    use instant::Instant;
+
+    use super::{
+        ControlFlow, DispatchTarget, DispatcherSettings, EventCoalescing, EventDispatcher,
+        UserEventQueue, UserEventsGuard,
+    };
+    use crate::dpi::PhysicalPosition;
+    use crate::event::{
+        DeviceEvent, DeviceId, ElementState, Event, KeyboardInput, ModifiersState, StartCause,
+        WindowEvent,
+    };
+    use crate::keyboard::{Key, KeyCode, PhysicalKey, SmolStr};
+    use crate::platform_impl;
+    use crate::window::WindowId;
+
+    /// Counts the allocations of each thread, as the tests run in parallel.
+    struct CountingAllocator;
+
+    thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    fn count_allocation() {
+        let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            count_allocation();
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            count_allocation();
+            System.realloc(ptr, layout, new_size)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    pub(crate) fn allocations(f: impl FnOnce()) -> usize {
+        let before = ALLOCATIONS.with(Cell::get);
+        f();
+        ALLOCATIONS.with(Cell::get) - before
+    }
+
+    /// Stands in for the `EventLoopWindowTarget` of a platform.
+    #[derive(Default)]
+    struct Target {
+        coalescing: EventCoalescing,
+        event_timestamp: Cell<Option<Instant>>,
+    }
+
+    impl DispatchTarget for Target {
+        fn coalescing(&self) -> &EventCoalescing {
+            &self.coalescing
+        }
+
+        fn exit_code(&self) -> Option<i32> {
+            None
+        }
+
+        fn platform_event_timestamp(&self) -> Option<Instant> {
+            None
+        }
+
+        fn set_event_timestamp(&self, timestamp: Option<Instant>) {
+            self.event_timestamp.set(timestamp);
+        }
+    }
+
+    /// Dispatches the events of a loop iteration in which the cursor moves, the mouse reports raw
+    /// motions, and a character key is pressed, building the key event as the platforms do.
+    #[allow(deprecated)]
+    fn dispatch_iteration(
+        dispatcher: &mut EventDispatcher<()>,
+        target: &Target,
+        delivered: &mut usize,
+    ) {
+        let window_id = unsafe { WindowId::dummy() };
+        let device_id = unsafe { DeviceId::dummy() };
+        let mut control_flow = ControlFlow::Poll;
+        let mut handler = |event: Event<()>, _: &Target, _: &mut ControlFlow| {
+            if let Event::DeviceEvent {
+                event: DeviceEvent::MouseMotionBatch { samples },
+                ..
+            } = &event
+            {
+                *delivered += samples.len();
+            }
+        };
+
+        let mut dispatch =
+            |event| dispatcher.dispatch(event, target, &mut control_flow, &mut handler);
+        dispatch(Event::NewEvents(StartCause::Poll));
+        for i in 0..4 {
+            dispatch(Event::WindowEvent {
+                window_id,
+                event: WindowEvent::CursorMoved {
+                    device_id,
+                    position: PhysicalPosition::new(i as f64, 2.0),
+                    modifiers: ModifiersState::empty(),
+                },
+            });
+            dispatch(Event::DeviceEvent {
+                device_id,
+                event: DeviceEvent::MouseMotion { delta: (1.0, 2.0) },
+            });
+        }
+        dispatch(Event::WindowEvent {
+            window_id,
+            event: WindowEvent::KeyboardInput {
+                device_id,
+                input: KeyboardInput {
+                    physical_key: PhysicalKey::Code(KeyCode::KeyA),
+                    state: ElementState::Pressed,
+                    logical_key: Key::Character(SmolStr::new("a")),
+                    text: Some(SmolStr::new("a")),
+                    repeat: false,
+                    modifiers: ModifiersState::empty(),
+                    platform_specific: platform_impl::KeyEventExtra::default(),
+                },
+                is_synthetic: false,
+            },
+        });
+        dispatch(Event::MainEventsCleared);
+        dispatch(Event::RedrawEventsCleared);
+    }
+
+    // The first iterations may allocate the buffers the dispatcher reuses afterwards.
+    #[test]
+    fn dispatching_common_events_doesnt_allocate() {
+        let window_id = unsafe { WindowId::dummy() };
+        let target = Target::default();
+        let cursor_moved_coalescing = Arc::new(AtomicBool::new(true));
+        target
+            .coalescing
+            .register(window_id, &cursor_moved_coalescing);
+        target.coalescing.mouse_motion_batching.set(true);
+        let mut dispatcher = EventDispatcher::new(DispatcherSettings::default());
+
+        let mut delivered = 0;
+        for _ in 0..2 {
+            dispatch_iteration(&mut dispatcher, &target, &mut delivered);
+        }
+        let count = allocations(|| dispatch_iteration(&mut dispatcher, &target, &mut delivered));
+        assert_eq!(count, 0, "dispatching the events allocated {} times", count);
+        assert_eq!(delivered, 12);
+    }
+
+    #[test]
+    fn bounded_senders_respect_capacity() {
+        const CAPACITY: usize = 3;
+        const SENDERS: usize = 8;
+        const EVENTS: usize = 2000;
+
+        let queue = UserEventsGuard(Arc::new(UserEventQueue::default()));
+        let sent = Arc::new(AtomicUsize::new(0));
+        let senders: Vec<_> = (0..SENDERS)
+            .map(|i| {
+                let queue = queue.0.clone();
+                let sent = sent.clone();
+                thread::spawn(move || {
+                    for _ in 0..EVENTS {
+                        if i % 2 == 0 {
+                            assert!(queue.reserve(CAPACITY));
+                        } else {
+                            while !queue.try_reserve(CAPACITY) {
+                                thread::yield_now();
+                            }
+                        }
+                        assert!(queue.pending.load(Ordering::SeqCst) <= CAPACITY);
+                        sent.fetch_add(1, Ordering::SeqCst);
+                    }
+                })
+            })
+            .collect();
+
+        // Deliver the events as the event loop would.
+        let mut delivered = 0;
+        while delivered < SENDERS * EVENTS {
+            if sent.load(Ordering::SeqCst) > delivered {
+                queue.pop();
+                delivered += 1;
+            } else {
+                thread::yield_now();
+            }
+        }
+        for sender in senders {
+            sender.join().unwrap();
+        }
+        assert_eq!(queue.pending.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn blocked_senders_fail_when_the_event_loop_is_dropped() {
+        let queue = UserEventsGuard(Arc::new(UserEventQueue::default()));
+        assert!(queue.try_reserve(1));
+        assert!(!queue.try_reserve(1));
+
+        let sender = {
+            let queue = queue.0.clone();
+            thread::spawn(move || queue.reserve(1))
+        };
+        drop(queue);
+        assert!(!sender.join().unwrap());
+    }
+}
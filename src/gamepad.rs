@@ -0,0 +1,138 @@
+//! Types describing gamepad input.
+//!
+//! Gamepad events are delivered through the event loop as [`Event::GamepadEvent`], in the same
+//! stream as window and device events, so that applications don't need to poll gamepads from a
+//! separate loop.
+//!
+//! Gamepads are only monitored when the `gamepad` feature is enabled. Without it, no
+//! [`Event::GamepadEvent`] is ever emitted.
+//!
+//! ## Platform-specific
+//!
+//! - **Windows:** Uses XInput, which supports up to four controllers and doesn't report their
+//!   name.
+//! - **X11 / Wayland:** Reads the `evdev` nodes in `/dev/input`, which requires the user to have
+//!   read access to them. Devices are picked up when they are plugged in.
+//! - **macOS:** Uses the GameController framework, which only reports input while the
+//!   application is active.
+//! - **iOS / Android / Web:** Unsupported.
+//!
+//! [`Event::GamepadEvent`]: crate::event::Event::GamepadEvent
+use crate::event::ElementState;
+
+/// Identifier of a gamepad.
+///
+/// The identifier is assigned when the gamepad is connected and stays the same until it is
+/// disconnected. Identifiers are never reused, so a gamepad that is plugged in again gets a new
+/// one.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GamepadId(u64);
+
+impl GamepadId {
+    /// Returns a dummy `GamepadId`, useful for unit testing.
+    ///
+    /// # Safety
+    ///
+    /// The only guarantee made about the return value of this function is that
+    /// it will always be equal to itself and to future values returned by this function.
+    /// No other guarantees are made. This may be equal to a real `GamepadId`.
+    ///
+    /// **Passing this into a winit function will result in undefined behavior.**
+    pub const unsafe fn dummy() -> Self {
+        GamepadId(0)
+    }
+
+    #[cfg(all(
+        feature = "gamepad",
+        any(target_os = "windows", target_os = "linux", target_os = "macos")
+    ))]
+    pub(crate) fn next() -> Self {
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+        GamepadId(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// Describes an event from a gamepad.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GamepadEvent {
+    /// The gamepad has been connected.
+    ///
+    /// This is also emitted at startup for the gamepads that are already connected.
+    Connected {
+        /// A human-readable name of the gamepad, empty if the platform doesn't provide one.
+        name: String,
+    },
+
+    /// The gamepad has been disconnected. Its [`GamepadId`] won't be used again.
+    Disconnected,
+
+    /// A button has been pressed or released.
+    Button {
+        button: GamepadButton,
+        state: ElementState,
+    },
+
+    /// An axis has moved.
+    Axis {
+        axis: GamepadAxis,
+        /// The new position of the axis.
+        ///
+        /// Sticks range from `-1.0` to `1.0`, positive values pointing right and up. Triggers
+        /// range from `0.0` when released to `1.0` when fully pressed. No dead zone is applied.
+        value: f64,
+    },
+}
+
+/// Describes a button of a gamepad.
+///
+/// The face buttons are named after their position, since their labels differ between
+/// manufacturers.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GamepadButton {
+    /// The bottom face button, `A` on Xbox controllers.
+    South,
+    /// The right face button, `B` on Xbox controllers.
+    East,
+    /// The top face button, `Y` on Xbox controllers.
+    North,
+    /// The left face button, `X` on Xbox controllers.
+    West,
+    LeftShoulder,
+    RightShoulder,
+    /// Only reported by gamepads with digital triggers, see [`GamepadAxis::LeftTrigger`].
+    LeftTrigger,
+    /// Only reported by gamepads with digital triggers, see [`GamepadAxis::RightTrigger`].
+    RightTrigger,
+    Select,
+    Start,
+    /// The button in the middle of the gamepad, such as the Xbox or PlayStation button.
+    Mode,
+    /// Pressing the left stick.
+    LeftThumb,
+    /// Pressing the right stick.
+    RightThumb,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+    /// A button that doesn't fit any other variant, with a platform-specific code.
+    Other(u16),
+}
+
+/// Describes an axis of a gamepad.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+    LeftTrigger,
+    RightTrigger,
+    /// An axis that doesn't fit any other variant, with a platform-specific code.
+    Other(u16),
+}
@@ -0,0 +1,375 @@
+//! Creation of OpenGL contexts and surfaces for windows, with [`glutin`].
+//!
+//! Picking an OpenGL config is tied to creating the window on some platforms: on X11, the window
+//! must be created with the visual of the config, which is also what makes it transparent, and
+//! WGL needs the window to tell which configs support it. [`DisplayBuilder`] takes care of this
+//! and returns the window along with the config picked for it. The context and the surface are
+//! then created with the [`glutin`] API, which is re-exported, from the attributes built by
+//! [`GlWindow`].
+//!
+//! ```no_run
+//! use winit::{
+//!     event_loop::EventLoop,
+//!     gl::{
+//!         glutin::{
+//!             config::{ConfigTemplateBuilder, GlConfig},
+//!             context::{ContextAttributesBuilder, NotCurrentGlContext},
+//!             display::{GetGlDisplay, GlDisplay},
+//!         },
+//!         DisplayBuilder, GlWindow,
+//!     },
+//!     window::WindowBuilder,
+//! };
+//!
+//! let event_loop = EventLoop::new();
+//! let template = ConfigTemplateBuilder::new().with_alpha_size(8);
+//! let (window, config) = DisplayBuilder::new()
+//!     .with_window_builder(Some(WindowBuilder::new().with_transparent(true)))
+//!     .build(&event_loop, template, |configs| {
+//!         // Pick the config with the most samples.
+//!         configs
+//!             .reduce(|best, config| {
+//!                 if config.num_samples() > best.num_samples() {
+//!                     config
+//!                 } else {
+//!                     best
+//!                 }
+//!             })
+//!             .unwrap()
+//!     })
+//!     .unwrap();
+//! let window = window.unwrap();
+//!
+//! let display = config.display();
+//! let context_attributes = window
+//!     .build_context_attributes(ContextAttributesBuilder::new())
+//!     .unwrap();
+//! let surface_attributes = window.build_surface_attributes(Default::default()).unwrap();
+//! let surface = unsafe { display.create_window_surface(&config, &surface_attributes) }.unwrap();
+//! let context = unsafe { display.create_context(&config, &context_attributes) }
+//!     .unwrap()
+//!     .make_current(&surface)
+//!     .unwrap();
+//! // Call `window.resize_surface(&surface, &context)` on `WindowEvent::Resized`.
+//! ```
+//!
+//! ## Platform-specific
+//!
+//! - **X11:** GLX is used along with EGL, see [`ApiPreference`]. Its errors are handled through
+//!   `platform::unix::register_xlib_error_hook`.
+//! - **Windows:** WGL is used along with EGL, see [`ApiPreference`].
+//! - **macOS:** Only CGL is used.
+//! - **Wayland / Android:** Only EGL is used.
+//! - **iOS / Web:** Unsupported, this module isn't available.
+
+pub use glutin;
+
+use std::{error::Error, fmt, num::NonZeroU32};
+
+use glutin::{
+    config::{Config, ConfigTemplateBuilder, GlConfig},
+    context::{ContextAttributes, ContextAttributesBuilder, PossiblyCurrentContext},
+    display::{Display, DisplayApiPreference, GlDisplay},
+    surface::{
+        GlSurface, ResizeableSurface, Surface, SurfaceAttributes, SurfaceAttributesBuilder,
+        SurfaceTypeTrait, WindowSurface,
+    },
+};
+use rwh_06::{HandleError, HasDisplayHandle, HasWindowHandle, RawWindowHandle};
+
+use crate::{
+    error::OsError,
+    event_loop::EventLoopWindowTarget,
+    window::{Window, WindowBuilder},
+};
+
+/// The OpenGL platform to use when there are several of them, see [`DisplayBuilder::with_preference`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApiPreference {
+    /// Prefer EGL, and fall back to the native platform, i.e. GLX on X11 and WGL on Windows.
+    PreferEgl,
+    /// Prefer the native platform, and fall back to EGL.
+    #[default]
+    FallbackEgl,
+}
+
+/// Picks an OpenGL config, and creates the window that goes with it.
+#[derive(Debug, Default)]
+pub struct DisplayBuilder {
+    preference: ApiPreference,
+    window_builder: Option<WindowBuilder>,
+}
+
+impl DisplayBuilder {
+    /// Creates a builder that doesn't create a window.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sets the builder of the window to create with the config, `None` by default.
+    ///
+    /// The window is created before picking the config with WGL, and after it on the other
+    /// platforms, with the visual of the config on X11. When it's `None`, the configs returned
+    /// on Windows might not support windows.
+    pub fn with_window_builder(mut self, window_builder: Option<WindowBuilder>) -> Self {
+        self.window_builder = window_builder;
+        self
+    }
+
+    /// Sets the OpenGL platform to prefer, [`ApiPreference::FallbackEgl`] by default.
+    pub fn with_preference(mut self, preference: ApiPreference) -> Self {
+        self.preference = preference;
+        self
+    }
+
+    /// Connects to the OpenGL platform of `target`, and picks one of the configs matching
+    /// `template_builder` with `config_picker`.
+    ///
+    /// Returns the window created with [`with_window_builder`](Self::with_window_builder), if
+    /// any, along with the picked config, whose [`display`](glutin::display::GetGlDisplay::display)
+    /// creates the context and the surface.
+    pub fn build<T, Picker>(
+        self,
+        target: &EventLoopWindowTarget<T>,
+        mut template_builder: ConfigTemplateBuilder,
+        config_picker: Picker,
+    ) -> Result<(Option<Window>, Config), GlError>
+    where
+        Picker: FnOnce(Box<dyn Iterator<Item = Config> + '_>) -> Config,
+    {
+        // WGL needs the window to pick a config that supports it.
+        #[cfg(target_os = "windows")]
+        let window = match self.window_builder.as_ref() {
+            Some(builder) => Some(builder.clone().build(target)?),
+            None => None,
+        };
+        #[cfg(not(target_os = "windows"))]
+        let window: Option<Window> = None;
+
+        let raw_window_handle = match window.as_ref() {
+            Some(window) => Some(window.window_handle()?.as_raw()),
+            None => None,
+        };
+        if let Some(raw_window_handle) = raw_window_handle {
+            template_builder = template_builder.compatible_with_native_window(raw_window_handle);
+        }
+
+        let display = create_display(target, self.preference, raw_window_handle)?;
+        let config = {
+            let configs = unsafe { display.find_configs(template_builder.build()) }?;
+            config_picker(configs)
+        };
+
+        let window = match (window, self.window_builder) {
+            (Some(window), _) => Some(window),
+            (None, Some(builder)) => Some(finalize_window(target, builder, &config)?),
+            (None, None) => None,
+        };
+
+        Ok((window, config))
+    }
+}
+
+fn create_display<T>(
+    target: &EventLoopWindowTarget<T>,
+    preference: ApiPreference,
+    raw_window_handle: Option<RawWindowHandle>,
+) -> Result<Display, GlError> {
+    #[cfg(all(
+        feature = "x11",
+        any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+        )
+    ))]
+    let preference = {
+        use crate::platform::unix::register_xlib_error_hook;
+
+        let _ = raw_window_handle;
+        match preference {
+            ApiPreference::PreferEgl => {
+                DisplayApiPreference::EglThenGlx(Box::new(register_xlib_error_hook))
+            }
+            ApiPreference::FallbackEgl => {
+                DisplayApiPreference::GlxThenEgl(Box::new(register_xlib_error_hook))
+            }
+        }
+    };
+    #[cfg(all(
+        not(feature = "x11"),
+        any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+        )
+    ))]
+    let preference = {
+        let _ = (preference, raw_window_handle);
+        DisplayApiPreference::Egl
+    };
+    #[cfg(target_os = "windows")]
+    let preference = match preference {
+        ApiPreference::PreferEgl => DisplayApiPreference::EglThenWgl(raw_window_handle),
+        ApiPreference::FallbackEgl => DisplayApiPreference::WglThenEgl(raw_window_handle),
+    };
+    #[cfg(target_os = "macos")]
+    let preference = {
+        let _ = (preference, raw_window_handle);
+        DisplayApiPreference::Cgl
+    };
+    #[cfg(target_os = "android")]
+    let preference = {
+        let _ = (preference, raw_window_handle);
+        DisplayApiPreference::Egl
+    };
+
+    let raw_display_handle = target.display_handle()?.as_raw();
+    Ok(unsafe { Display::new(raw_display_handle, preference) }?)
+}
+
+/// Creates a window from `builder` that can be used with `config`.
+///
+/// This disables the transparency of the window when the config doesn't support it, and uses
+/// the visual of the config on X11. [`DisplayBuilder::build`] calls this when it's given a
+/// window builder, but it's also needed to create more windows for the same config.
+pub fn finalize_window<T>(
+    target: &EventLoopWindowTarget<T>,
+    mut builder: WindowBuilder,
+    config: &Config,
+) -> Result<Window, OsError> {
+    if config.supports_transparency() == Some(false) {
+        builder = builder.with_transparent(false);
+    }
+
+    #[cfg(all(
+        feature = "x11",
+        any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+        )
+    ))]
+    {
+        use glutin::platform::x11::X11GlConfigExt;
+
+        use crate::platform::unix::{EventLoopWindowTargetExtUnix, WindowBuilderExtUnix};
+
+        if let (Some(visual), Some(xconn)) = (config.x11_visual(), target.xlib_xconnection()) {
+            let visual = visual.into_raw();
+            // The builder keeps a copy of the visual info.
+            builder = builder.with_x11_visual(visual);
+            unsafe { (xconn.xlib.XFree)(visual as *mut _) };
+        }
+    }
+
+    builder.build(target)
+}
+
+/// Methods on [`Window`] to create its OpenGL context and surface.
+pub trait GlWindow {
+    /// Builds the attributes of a context that can be made current with the surface of the window.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** WGL can only make the context current with the surfaces of this window.
+    fn build_context_attributes(
+        &self,
+        builder: ContextAttributesBuilder,
+    ) -> Result<ContextAttributes, HandleError>;
+
+    /// Builds the attributes of a surface for the window, with its current size.
+    fn build_surface_attributes(
+        &self,
+        builder: SurfaceAttributesBuilder<WindowSurface>,
+    ) -> Result<SurfaceAttributes<WindowSurface>, HandleError>;
+
+    /// Resizes `surface` to the current size of the window, which should be done on
+    /// [`WindowEvent::Resized`](crate::event::WindowEvent::Resized).
+    ///
+    /// This does nothing while the window has a zero width or height.
+    fn resize_surface(
+        &self,
+        surface: &Surface<impl SurfaceTypeTrait + ResizeableSurface>,
+        context: &PossiblyCurrentContext,
+    );
+}
+
+impl GlWindow for Window {
+    fn build_context_attributes(
+        &self,
+        builder: ContextAttributesBuilder,
+    ) -> Result<ContextAttributes, HandleError> {
+        Ok(builder.build(Some(self.window_handle()?.as_raw())))
+    }
+
+    fn build_surface_attributes(
+        &self,
+        builder: SurfaceAttributesBuilder<WindowSurface>,
+    ) -> Result<SurfaceAttributes<WindowSurface>, HandleError> {
+        let size = self.inner_size();
+        let width = NonZeroU32::new(size.width.max(1)).unwrap();
+        let height = NonZeroU32::new(size.height.max(1)).unwrap();
+        Ok(builder.build(self.window_handle()?.as_raw(), width, height))
+    }
+
+    fn resize_surface(
+        &self,
+        surface: &Surface<impl SurfaceTypeTrait + ResizeableSurface>,
+        context: &PossiblyCurrentContext,
+    ) {
+        let size = self.inner_size();
+        if let (Some(width), Some(height)) =
+            (NonZeroU32::new(size.width), NonZeroU32::new(size.height))
+        {
+            surface.resize(context, width, height);
+        }
+    }
+}
+
+/// The error type for when an OpenGL config or its window can't be created.
+#[derive(Debug)]
+pub enum GlError {
+    /// The window couldn't be created.
+    Os(OsError),
+    /// The handle of the window or of the display isn't available.
+    Handle(HandleError),
+    /// The OpenGL platform failed.
+    Gl(glutin::error::Error),
+}
+
+impl fmt::Display for GlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            GlError::Os(e) => e.fmt(f),
+            GlError::Handle(e) => e.fmt(f),
+            GlError::Gl(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for GlError {}
+
+impl From<OsError> for GlError {
+    fn from(error: OsError) -> Self {
+        GlError::Os(error)
+    }
+}
+
+impl From<HandleError> for GlError {
+    fn from(error: HandleError) -> Self {
+        GlError::Handle(error)
+    }
+}
+
+impl From<glutin::error::Error> for GlError {
+    fn from(error: glutin::error::Error) -> Self {
+        GlError::Gl(error)
+    }
+}
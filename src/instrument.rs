@@ -0,0 +1,22 @@
+//! Instrumentation emitted when the `tracing` feature is enabled.
+//!
+//! Both macros expand to nothing without the feature, so the field expressions passed to them
+//! are not evaluated either.
+
+/// Enters a `TRACE` span for the rest of the enclosing scope.
+#[allow(unused_macros)]
+macro_rules! trace_span {
+    ($name:literal $(, $($field:tt)+)?) => {
+        #[cfg(feature = "tracing")]
+        let _span = ::tracing::trace_span!($name $(, $($field)+)?).entered();
+    };
+}
+
+/// Emits a `TRACE` event.
+#[allow(unused_macros)]
+macro_rules! trace_event {
+    ($message:literal $(, $($field:tt)+)?) => {
+        #[cfg(feature = "tracing")]
+        ::tracing::trace!($($($field)+,)? $message);
+    };
+}
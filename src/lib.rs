@@ -148,14 +148,26 @@ extern crate bitflags;
 #[macro_use]
 extern crate objc;
 
+#[macro_use]
+mod instrument;
+
+#[cfg(feature = "accesskit")]
+pub mod accessibility;
+pub mod device;
 pub mod dpi;
 #[macro_use]
 pub mod error;
 pub mod event;
 pub mod event_loop;
+pub mod gamepad;
+#[cfg(all(feature = "gl", not(any(target_os = "ios", target_arch = "wasm32"))))]
+pub mod gl;
 mod icon;
+pub mod keyboard;
 pub mod monitor;
 mod platform_impl;
+#[cfg(all(feature = "ash", not(target_arch = "wasm32")))]
+pub mod vulkan;
 pub mod window;
 
 pub mod platform;
@@ -20,6 +20,12 @@ pub trait WindowExtAndroid {
     fn content_rect(&self) -> Rect;
 
     fn config(&self) -> Configuration;
+
+    /// Returns the distances from each edge of the window to the content rect, i.e. the parts of
+    /// the window covered by system bars and cutouts.
+    ///
+    /// `WindowEvent::InsetsChanged` is emitted when they change.
+    fn insets(&self) -> Rect;
 }
 
 impl WindowExtAndroid for Window {
@@ -30,6 +36,10 @@ impl WindowExtAndroid for Window {
     fn config(&self) -> Configuration {
         self.window.config()
     }
+
+    fn insets(&self) -> Rect {
+        self.window.insets()
+    }
 }
 
 impl<T> EventLoopWindowTargetExtAndroid for EventLoopWindowTarget<T> {}
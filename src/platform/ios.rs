@@ -94,6 +94,15 @@ pub trait WindowExtIOS {
     /// and then calls
     /// [`-[UIViewController setNeedsStatusBarAppearanceUpdate]`](https://developer.apple.com/documentation/uikit/uiviewcontroller/1621354-setneedsstatusbarappearanceupdat?language=objc).
     fn set_prefers_status_bar_hidden(&self, hidden: bool);
+
+    /// Returns the [`safeAreaInsets`] of the [`Window`], the distances from its edges to the area
+    /// not covered by the status bar, the home indicator and the sensor housing.
+    ///
+    /// [`WindowEvent::InsetsChanged`](crate::event::WindowEvent::InsetsChanged) is emitted when
+    /// they change. Before iOS 11.0, only the status bar is accounted for.
+    ///
+    /// [`safeAreaInsets`]: https://developer.apple.com/documentation/uikit/uiview/2891103-safeareainsets?language=objc
+    fn safe_area_insets(&self) -> SafeAreaInsets;
 }
 
 impl WindowExtIOS for Window {
@@ -137,6 +146,11 @@ impl WindowExtIOS for Window {
     fn set_prefers_status_bar_hidden(&self, hidden: bool) {
         self.window.set_prefers_status_bar_hidden(hidden)
     }
+
+    #[inline]
+    fn safe_area_insets(&self) -> SafeAreaInsets {
+        self.window.safe_area_insets()
+    }
 }
 
 /// Additional methods on [`WindowBuilder`] that are specific to iOS.
@@ -282,6 +296,15 @@ impl Default for ValidOrientations {
     }
 }
 
+/// The safe area insets of a [`Window`], in points.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SafeAreaInsets {
+    pub top: f64,
+    pub left: f64,
+    pub bottom: f64,
+    pub right: f64,
+}
+
 /// The device [idiom].
 ///
 /// [idiom]: https://developer.apple.com/documentation/uikit/uidevice/1620037-userinterfaceidiom?language=objc
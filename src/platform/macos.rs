@@ -3,7 +3,7 @@
 use std::os::raw::c_void;
 
 use crate::{
-    dpi::LogicalSize,
+    dpi::{LogicalPosition, LogicalSize},
     event_loop::{EventLoopBuilder, EventLoopWindowTarget},
     monitor::MonitorHandle,
     window::{Window, WindowBuilder},
@@ -38,6 +38,76 @@ pub trait WindowExtMacOS {
 
     /// Sets whether or not the window has shadow.
     fn set_has_shadow(&self, has_shadow: bool);
+
+    /// Makes the titlebar transparent and allows the content to appear behind it, see
+    /// [`WindowBuilderExtMacOS::with_titlebar_transparent`].
+    fn set_titlebar_transparent(&self, transparent: bool);
+
+    /// Hides the window title, see [`WindowBuilderExtMacOS::with_title_hidden`].
+    fn set_title_hidden(&self, hidden: bool);
+
+    /// Makes the window content appear behind the titlebar, see
+    /// [`WindowBuilderExtMacOS::with_fullsize_content_view`].
+    fn set_fullsize_content_view(&self, fullsize_content_view: bool);
+
+    /// Moves the close, minimize and zoom buttons of the titlebar to `inset` from the top left
+    /// corner of the window, e.g. to center them in a taller custom titlebar drawn by the
+    /// application with [`set_fullsize_content_view`](Self::set_fullsize_content_view). The
+    /// buttons stay there across resizes.
+    fn set_traffic_light_inset(&self, inset: LogicalPosition<f64>);
+
+    /// Returns a pointer to the `CAMetalLayer` of the `NSView` of the window, which is installed
+    /// the first time this is called unless the view already has one, e.g. to create a Metal or
+    /// Vulkan surface.
+    ///
+    /// Its `contentsScale` is kept in sync with the scale factor of the window, and its
+    /// `drawableSize` with the inner size. The pointer will become invalid when the `Window` is
+    /// destroyed.
+    fn metal_layer(&self) -> *mut c_void;
+
+    /// Returns the identifier of the windows the window can be grouped with as tabs.
+    fn tabbing_identifier(&self) -> String;
+
+    /// Sets the identifier of the windows the window can be grouped with as tabs, see
+    /// [`WindowBuilderExtMacOS::with_tabbing_identifier`].
+    fn set_tabbing_identifier(&self, identifier: &str);
+
+    /// Adds `window` as a tab following the tab of the window, e.g. in response to
+    /// [`WindowEvent::NewTabRequested`](crate::event::WindowEvent::NewTabRequested).
+    fn add_tab(&self, window: &Window);
+
+    /// Merges all the windows sharing the tabbing identifier of the window as tabs of a single
+    /// window.
+    fn merge_all_windows(&self);
+
+    /// Selects the tab following the tab of the window, wrapping around.
+    fn select_next_tab(&self);
+
+    /// Selects the tab preceding the tab of the window, wrapping around.
+    fn select_previous_tab(&self);
+
+    /// Selects the tab at `index` in the tab group of the window. Out-of-bounds indices are
+    /// ignored.
+    fn select_tab_at_index(&self, index: usize);
+
+    /// Returns the number of tabs in the tab group of the window, 1 if it isn't part of one.
+    fn num_tabs(&self) -> usize;
+
+    /// Sets how the window enters borderless fullscreen, see
+    /// [`WindowBuilderExtMacOS::with_borderless_fullscreen_mode`]. Takes effect the next time
+    /// the window enters borderless fullscreen.
+    fn set_borderless_fullscreen_mode(&self, mode: BorderlessFullscreenMode);
+
+    /// Returns how the window enters borderless fullscreen.
+    fn borderless_fullscreen_mode(&self) -> BorderlessFullscreenMode;
+
+    /// Sets the presentation options of the application while the window is in borderless
+    /// fullscreen, or keeps the system defaults if `None` was provided. Options AppKit doesn't
+    /// support together are adjusted, e.g. hiding the menu bar also hides the dock.
+    ///
+    /// The options also apply to [`set_simple_fullscreen`](Self::set_simple_fullscreen). They're
+    /// ignored in exclusive fullscreen, which always hides the dock and the menu bar.
+    fn set_fullscreen_presentation_options(&self, options: Option<FullscreenPresentationOptions>);
 }
 
 impl WindowExtMacOS for Window {
@@ -70,16 +140,146 @@ impl WindowExtMacOS for Window {
     fn set_has_shadow(&self, has_shadow: bool) {
         self.window.set_has_shadow(has_shadow)
     }
+
+    #[inline]
+    fn set_titlebar_transparent(&self, transparent: bool) {
+        self.window.set_titlebar_transparent(transparent)
+    }
+
+    #[inline]
+    fn set_title_hidden(&self, hidden: bool) {
+        self.window.set_title_hidden(hidden)
+    }
+
+    #[inline]
+    fn set_fullsize_content_view(&self, fullsize_content_view: bool) {
+        self.window.set_fullsize_content_view(fullsize_content_view)
+    }
+
+    #[inline]
+    fn set_traffic_light_inset(&self, inset: LogicalPosition<f64>) {
+        self.window.set_traffic_light_inset(inset)
+    }
+
+    #[inline]
+    fn metal_layer(&self) -> *mut c_void {
+        self.window.metal_layer()
+    }
+
+    #[inline]
+    fn tabbing_identifier(&self) -> String {
+        self.window.tabbing_identifier()
+    }
+
+    #[inline]
+    fn set_tabbing_identifier(&self, identifier: &str) {
+        self.window.set_tabbing_identifier(identifier)
+    }
+
+    #[inline]
+    fn add_tab(&self, window: &Window) {
+        self.window.add_tab(window)
+    }
+
+    #[inline]
+    fn merge_all_windows(&self) {
+        self.window.merge_all_windows()
+    }
+
+    #[inline]
+    fn select_next_tab(&self) {
+        self.window.select_next_tab()
+    }
+
+    #[inline]
+    fn select_previous_tab(&self) {
+        self.window.select_previous_tab()
+    }
+
+    #[inline]
+    fn select_tab_at_index(&self, index: usize) {
+        self.window.select_tab_at_index(index)
+    }
+
+    #[inline]
+    fn num_tabs(&self) -> usize {
+        self.window.num_tabs()
+    }
+
+    #[inline]
+    fn set_borderless_fullscreen_mode(&self, mode: BorderlessFullscreenMode) {
+        self.window.set_borderless_fullscreen_mode(mode)
+    }
+
+    #[inline]
+    fn borderless_fullscreen_mode(&self) -> BorderlessFullscreenMode {
+        self.window.borderless_fullscreen_mode()
+    }
+
+    #[inline]
+    fn set_fullscreen_presentation_options(&self, options: Option<FullscreenPresentationOptions>) {
+        self.window.set_fullscreen_presentation_options(options)
+    }
+}
+
+/// How a window enters [`Fullscreen::Borderless`](crate::window::Fullscreen::Borderless).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BorderlessFullscreenMode {
+    /// The window moves to a separate space with an animation, like with the fullscreen button of
+    /// the title bar.
+    NativeSpace,
+    /// The window covers the screen in its current space, like with
+    /// [`WindowExtMacOS::set_simple_fullscreen`], which is faster and allows other windows over
+    /// it. The fullscreen button of the title bar still uses a separate space.
+    Simple,
+}
+
+impl Default for BorderlessFullscreenMode {
+    fn default() -> Self {
+        BorderlessFullscreenMode::NativeSpace
+    }
+}
+
+bitflags! {
+    /// The presentation options of the application while a window is in borderless fullscreen,
+    /// see [`WindowExtMacOS::set_fullscreen_presentation_options`].
+    pub struct FullscreenPresentationOptions: u8 {
+        /// The dock shows up when the cursor reaches its edge of the screen.
+        const AUTO_HIDE_DOCK = 1 << 0;
+        /// The dock is hidden.
+        const HIDE_DOCK = 1 << 1;
+        /// The menu bar shows up when the cursor reaches the top of the screen.
+        const AUTO_HIDE_MENU_BAR = 1 << 2;
+        /// The menu bar is hidden.
+        const HIDE_MENU_BAR = 1 << 3;
+        /// Command-Tab and Command-Shift-Tab are disabled. Requires hiding the dock.
+        const DISABLE_PROCESS_SWITCHING = 1 << 4;
+        /// Command-Option-Escape is disabled.
+        const DISABLE_FORCE_QUIT = 1 << 5;
+        /// The "Hide" item of the application menu is disabled.
+        const DISABLE_HIDE_APPLICATION = 1 << 6;
+    }
+}
+
+impl Default for FullscreenPresentationOptions {
+    /// Auto-hides the dock and the menu bar.
+    fn default() -> Self {
+        FullscreenPresentationOptions::AUTO_HIDE_DOCK
+            | FullscreenPresentationOptions::AUTO_HIDE_MENU_BAR
+    }
 }
 
 /// Corresponds to `NSApplicationActivationPolicy`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ActivationPolicy {
-    /// Corresponds to `NSApplicationActivationPolicyRegular`.
+    /// Corresponds to `NSApplicationActivationPolicyRegular`, an ordinary application with a Dock
+    /// icon and a menu bar.
     Regular,
-    /// Corresponds to `NSApplicationActivationPolicyAccessory`.
+    /// Corresponds to `NSApplicationActivationPolicyAccessory`, an application without a Dock
+    /// icon nor a menu bar whose windows can still be activated, e.g. a menu bar extra.
     Accessory,
-    /// Corresponds to `NSApplicationActivationPolicyProhibited`.
+    /// Corresponds to `NSApplicationActivationPolicyProhibited`, a background agent which can't
+    /// be activated.
     Prohibited,
 }
 
@@ -117,6 +317,20 @@ pub trait WindowBuilderExtMacOS {
     fn with_resize_increments(self, increments: LogicalSize<f64>) -> WindowBuilder;
     fn with_disallow_hidpi(self, disallow_hidpi: bool) -> WindowBuilder;
     fn with_has_shadow(self, has_shadow: bool) -> WindowBuilder;
+    /// Moves the titlebar buttons, see [`WindowExtMacOS::set_traffic_light_inset`].
+    fn with_traffic_light_inset(self, inset: LogicalPosition<f64>) -> WindowBuilder;
+    /// Groups the window as a tab with the windows sharing the identifier. By default, the
+    /// identifier is derived from the class of the window, which all the windows share.
+    fn with_tabbing_identifier(self, identifier: &str) -> WindowBuilder;
+    /// Sets how the window enters borderless fullscreen. By default, it moves to a separate
+    /// space.
+    fn with_borderless_fullscreen_mode(self, mode: BorderlessFullscreenMode) -> WindowBuilder;
+    /// Sets the presentation options of the application while the window is in borderless
+    /// fullscreen, see [`WindowExtMacOS::set_fullscreen_presentation_options`].
+    fn with_fullscreen_presentation_options(
+        self,
+        options: FullscreenPresentationOptions,
+    ) -> WindowBuilder;
 }
 
 impl WindowBuilderExtMacOS for WindowBuilder {
@@ -176,6 +390,33 @@ impl WindowBuilderExtMacOS for WindowBuilder {
         self.platform_specific.has_shadow = has_shadow;
         self
     }
+
+    #[inline]
+    fn with_traffic_light_inset(mut self, inset: LogicalPosition<f64>) -> WindowBuilder {
+        self.platform_specific.traffic_light_inset = Some(inset);
+        self
+    }
+
+    #[inline]
+    fn with_tabbing_identifier(mut self, identifier: &str) -> WindowBuilder {
+        self.platform_specific.tabbing_identifier = Some(identifier.to_owned());
+        self
+    }
+
+    #[inline]
+    fn with_borderless_fullscreen_mode(mut self, mode: BorderlessFullscreenMode) -> WindowBuilder {
+        self.platform_specific.borderless_fullscreen_mode = mode;
+        self
+    }
+
+    #[inline]
+    fn with_fullscreen_presentation_options(
+        mut self,
+        options: FullscreenPresentationOptions,
+    ) -> WindowBuilder {
+        self.platform_specific.fullscreen_presentation_options = Some(options);
+        self
+    }
 }
 
 pub trait EventLoopBuilderExtMacOS {
@@ -263,6 +504,21 @@ pub trait EventLoopWindowTargetExtMacOS {
     fn hide_application(&self);
     /// Hide the other applications. In most applications this is typically triggered with Command+Option-H.
     fn hide_other_applications(&self);
+    /// Sets the activation policy of the application, see
+    /// [`EventLoopBuilderExtMacOS::with_activation_policy`].
+    ///
+    /// Switching to [`ActivationPolicy::Accessory`] or [`ActivationPolicy::Prohibited`] removes
+    /// the Dock icon and the menu bar of the application, e.g. when its last window closes in a
+    /// menu bar app. Switching back to [`ActivationPolicy::Regular`] shows them again, although
+    /// the menu bar is only shown once the application is activated.
+    fn set_activation_policy(&self, activation_policy: ActivationPolicy);
+    /// Returns the activation policy of the application.
+    fn activation_policy(&self) -> ActivationPolicy;
+    /// Sets whether the system may group the windows as tabs, depending on the "Prefer tabs"
+    /// setting of the user. Enabled by default.
+    fn set_allows_automatic_window_tabbing(&self, enabled: bool);
+    /// Returns whether the system may group the windows as tabs.
+    fn allows_automatic_window_tabbing(&self) -> bool;
 }
 
 impl<T> EventLoopWindowTargetExtMacOS for EventLoopWindowTarget<T> {
@@ -273,4 +529,20 @@ impl<T> EventLoopWindowTargetExtMacOS for EventLoopWindowTarget<T> {
     fn hide_other_applications(&self) {
         self.p.hide_other_applications()
     }
+
+    fn set_activation_policy(&self, activation_policy: ActivationPolicy) {
+        self.p.set_activation_policy(activation_policy)
+    }
+
+    fn activation_policy(&self) -> ActivationPolicy {
+        self.p.activation_policy()
+    }
+
+    fn set_allows_automatic_window_tabbing(&self, enabled: bool) {
+        self.p.set_allows_automatic_window_tabbing(enabled)
+    }
+
+    fn allows_automatic_window_tabbing(&self) -> bool {
+        self.p.allows_automatic_window_tabbing()
+    }
 }
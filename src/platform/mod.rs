@@ -12,6 +12,7 @@
 //! And the following platform-specific module:
 //!
 //! - `run_return` (available on `windows`, `unix`, `macos`, and `android`)
+//! - `modifier_supplement` (available on `windows`, `unix`, and `macos`)
 //!
 //! However only the module corresponding to the platform you're compiling to will be available.
 
@@ -21,5 +22,6 @@ pub mod macos;
 pub mod unix;
 pub mod windows;
 
+pub mod modifier_supplement;
 pub mod run_return;
 pub mod web;
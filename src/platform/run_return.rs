@@ -11,7 +11,7 @@
 
 use crate::{
     event::Event,
-    event_loop::{ControlFlow, EventLoop, EventLoopWindowTarget},
+    event_loop::{ControlFlow, EventDispatcher, EventLoop, EventLoopWindowTarget},
 };
 
 /// Additional methods on `EventLoop` to return control flow to the caller.
@@ -22,7 +22,8 @@ pub trait EventLoopExtRunReturn {
     /// Initializes the `winit` event loop.
     ///
     /// Unlike `run`, this function accepts non-`'static` (i.e. non-`move`) closures and returns
-    /// control flow to the caller when `control_flow` is set to `ControlFlow::Exit`.
+    /// control flow to the caller when `control_flow` is set to `ControlFlow::ExitWithCode`,
+    /// returning the exit code.
     ///
     /// # Caveats
     /// Despite its appearance at first glance, this is *not* a perfect replacement for
@@ -34,30 +35,30 @@ pub trait EventLoopExtRunReturn {
     ///
     /// You are strongly encouraged to use `run`, unless the use of this is absolutely necessary.
     ///
+    /// If `event_handler` panics, the panic is resumed from this function after the platform's
+    /// event loop has been left, and the event loop can be run again afterwards.
+    ///
     /// ## Platform-specific
     ///
     /// - **Unix-alikes** (**X11** or **Wayland**): This function returns `1` upon disconnection from
     ///   the display server.
     fn run_return<F>(&mut self, event_handler: F) -> i32
     where
-        F: FnMut(
-            Event<'_, Self::UserEvent>,
-            &EventLoopWindowTarget<Self::UserEvent>,
-            &mut ControlFlow,
-        );
+        F: FnMut(Event<Self::UserEvent>, &EventLoopWindowTarget<Self::UserEvent>, &mut ControlFlow);
 }
 
 impl<T> EventLoopExtRunReturn for EventLoop<T> {
     type UserEvent = T;
 
-    fn run_return<F>(&mut self, event_handler: F) -> i32
+    fn run_return<F>(&mut self, mut event_handler: F) -> i32
     where
-        F: FnMut(
-            Event<'_, Self::UserEvent>,
-            &EventLoopWindowTarget<Self::UserEvent>,
-            &mut ControlFlow,
-        ),
+        F: FnMut(Event<Self::UserEvent>, &EventLoopWindowTarget<Self::UserEvent>, &mut ControlFlow),
     {
-        self.event_loop.run_return(event_handler)
+        // Like the control flow, an exit requested during a previous call doesn't persist.
+        self.exit_code.set(None);
+        let mut dispatcher = EventDispatcher::new(self.dispatcher_settings.borrow().clone());
+        self.event_loop.run_return(|event, target, control_flow| {
+            dispatcher.dispatch(event, target, control_flow, &mut event_handler)
+        })
     }
 }
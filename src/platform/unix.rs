@@ -10,6 +10,12 @@ use std::os::raw;
 #[cfg(feature = "x11")]
 use std::{ptr, sync::Arc};
 
+#[cfg(feature = "x11")]
+use crate::error::ExternalError;
+#[cfg(feature = "wayland")]
+use crate::error::NotSupportedError;
+#[cfg(feature = "wayland")]
+use crate::window::{ActivationToken, DecorationMode};
 use crate::{
     event_loop::{EventLoopBuilder, EventLoopWindowTarget},
     monitor::MonitorHandle,
@@ -29,7 +35,10 @@ use crate::platform_impl::{
 #[cfg(feature = "x11")]
 pub use crate::platform_impl::x11;
 #[cfg(feature = "x11")]
-pub use crate::platform_impl::{x11::util::WindowType as XWindowType, XNotSupported};
+pub use crate::platform_impl::{
+    x11::util::{WindowState as XWindowState, WindowType as XWindowType},
+    XNotSupported, XlibErrorHook,
+};
 
 /// Additional methods on `EventLoopWindowTarget` that are specific to Unix.
 pub trait EventLoopWindowTargetExtUnix {
@@ -41,6 +50,10 @@ pub trait EventLoopWindowTargetExtUnix {
     #[cfg(feature = "x11")]
     fn is_x11(&self) -> bool;
 
+    /// Returns the backend the event loop uses, e.g. to report it in diagnostics. See
+    /// [`EventLoopBuilderExtUnix`] to pick it.
+    fn backend(&self) -> UnixBackend;
+
     #[doc(hidden)]
     #[cfg(feature = "x11")]
     fn xlib_xconnection(&self) -> Option<Arc<XConnection>>;
@@ -53,6 +66,27 @@ pub trait EventLoopWindowTargetExtUnix {
     /// The pointer will become invalid when the winit `EventLoop` is destroyed.
     #[cfg(feature = "wayland")]
     fn wayland_display(&self) -> Option<*mut raw::c_void>;
+
+    /// Returns the globals advertised by the compositor on the connection of
+    /// [`wayland_display`](Self::wayland_display), to bind Wayland protocols winit doesn't use,
+    /// e.g. screencopy or gamma control, on the same connection.
+    ///
+    /// The globals should be bound with a `wl_registry` created on an event queue of the
+    /// application, e.g. with `wl_display_create_queue`, which the application dispatches. Winit
+    /// only dispatches its own queue, which panics on events of objects it didn't create.
+    ///
+    /// Returns an empty list if the `EventLoop` doesn't use wayland.
+    #[cfg(feature = "wayland")]
+    fn wayland_globals(&self) -> Vec<WaylandGlobal>;
+
+    /// Takes the activation token the application was launched with from the
+    /// `XDG_ACTIVATION_TOKEN` environment variable, to pass it to
+    /// [`WindowBuilderExtUnix::with_activation_token`].
+    ///
+    /// The variable is removed from the environment, so that the token isn't inherited by the
+    /// processes spawned later on.
+    #[cfg(feature = "wayland")]
+    fn take_activation_token_from_env(&self) -> Option<ActivationToken>;
 }
 
 impl<T> EventLoopWindowTargetExtUnix for EventLoopWindowTarget<T> {
@@ -68,6 +102,16 @@ impl<T> EventLoopWindowTargetExtUnix for EventLoopWindowTarget<T> {
         !self.p.is_wayland()
     }
 
+    #[inline]
+    fn backend(&self) -> UnixBackend {
+        match self.p {
+            #[cfg(feature = "x11")]
+            LinuxEventLoopWindowTarget::X(_) => UnixBackend::X11,
+            #[cfg(feature = "wayland")]
+            LinuxEventLoopWindowTarget::Wayland(_) => UnixBackend::Wayland,
+        }
+    }
+
     #[inline]
     #[doc(hidden)]
     #[cfg(feature = "x11")]
@@ -90,14 +134,66 @@ impl<T> EventLoopWindowTargetExtUnix for EventLoopWindowTarget<T> {
             _ => None,
         }
     }
+
+    #[cfg(feature = "wayland")]
+    fn wayland_globals(&self) -> Vec<WaylandGlobal> {
+        match self.p {
+            LinuxEventLoopWindowTarget::Wayland(ref p) => p
+                .env
+                .manager
+                .list()
+                .into_iter()
+                .map(|(name, interface, version)| WaylandGlobal {
+                    name,
+                    interface,
+                    version,
+                })
+                .collect(),
+            #[cfg(feature = "x11")]
+            _ => Vec::new(),
+        }
+    }
+
+    #[inline]
+    #[cfg(feature = "wayland")]
+    fn take_activation_token_from_env(&self) -> Option<ActivationToken> {
+        const VAR: &str = "XDG_ACTIVATION_TOKEN";
+
+        let token = std::env::var(VAR).ok()?;
+        std::env::remove_var(VAR);
+        Some(ActivationToken::from_raw(token))
+    }
 }
 
 /// Additional methods on [`EventLoopBuilder`] that are specific to Unix.
+///
+/// By default, the backend is picked from the `WINIT_UNIX_BACKEND` environment variable, which
+/// can be set to `x11` or `wayland`, and otherwise Wayland is tried before X11. Forcing a backend
+/// with these methods takes precedence over the environment variable. Building the event loop
+/// panics if the requested backend isn't available.
 pub trait EventLoopBuilderExtUnix {
     /// Force using X11.
     #[cfg(feature = "x11")]
     fn with_x11(&mut self) -> &mut Self;
 
+    /// Force using X11, over a display the application opened itself, e.g. to share its
+    /// connection with another library instead of opening a second one.
+    ///
+    /// The application keeps owning the display: winit doesn't close it, and stops using it once
+    /// the event loop and its windows are dropped. Creating the event loop fails with
+    /// [`XNotSupported::DisplayAlreadyOpen`] if a previous event loop is already using another
+    /// display, including the one winit opens when this isn't called.
+    ///
+    /// Winit replaces the Xlib error handler with its own until then, and restores the previous
+    /// one afterwards. Use [`register_xlib_error_hook`] to handle errors in the meantime.
+    ///
+    /// # Safety
+    ///
+    /// `display` must be a valid Xlib `Display` that outlives the event loop and its windows. If
+    /// it's used from several threads, `XInitThreads` must have been called before opening it.
+    #[cfg(feature = "x11")]
+    unsafe fn with_x11_display(&mut self, display: *mut raw::c_void) -> &mut Self;
+
     /// Force using Wayland.
     #[cfg(feature = "wayland")]
     fn with_wayland(&mut self) -> &mut Self;
@@ -117,6 +213,14 @@ impl<T> EventLoopBuilderExtUnix for EventLoopBuilder<T> {
         self
     }
 
+    #[inline]
+    #[cfg(feature = "x11")]
+    unsafe fn with_x11_display(&mut self, display: *mut raw::c_void) -> &mut Self {
+        self.platform_specific.forced_backend = Some(Backend::X);
+        self.platform_specific.x11_display = Some(display as _);
+        self
+    }
+
     #[inline]
     #[cfg(feature = "wayland")]
     fn with_wayland(&mut self) -> &mut Self {
@@ -178,6 +282,83 @@ pub trait WindowExtUnix {
     #[cfg(feature = "wayland")]
     fn wayland_display(&self) -> Option<*mut raw::c_void>;
 
+    /// Requests an activation token from the compositor, to let another application activate
+    /// its windows, e.g. one launched from this window. The token is delivered to the window in
+    /// a [`WindowEvent::ActivationTokenDone`](crate::event::WindowEvent::ActivationTokenDone)
+    /// event, and is usually passed through the `XDG_ACTIVATION_TOKEN` environment variable of
+    /// the launched process.
+    ///
+    /// Returns an error if the window doesn't use Wayland, or if the compositor doesn't support
+    /// the `xdg_activation_v1` protocol.
+    #[cfg(feature = "wayland")]
+    fn request_activation_token(&self) -> Result<(), NotSupportedError>;
+
+    /// Sets the side that should draw the decorations of the window, `None` following the
+    /// preference of the compositor, which is the default. Only relevant on Wayland, where the
+    /// compositor has the final say. Compositors without the `xdg-decoration` protocol only
+    /// support client-side decorations, which are also used for good once requested.
+    #[cfg(feature = "wayland")]
+    fn set_preferred_decoration_mode(&self, mode: Option<DecorationMode>);
+
+    /// Returns the side drawing the decorations of the window, which is reported in
+    /// [`WindowEvent::DecorationModeChanged`](crate::event::WindowEvent::DecorationModeChanged)
+    /// when it changes.
+    ///
+    /// Returns `None` if the window isn't decorated, doesn't use Wayland, or the compositor
+    /// hasn't picked a mode yet.
+    #[cfg(feature = "wayland")]
+    fn decoration_mode(&self) -> Option<DecorationMode>;
+
+    /// Changes the `WM_CLASS` of the window, see [`WindowBuilderExtUnix::with_class`]. Only
+    /// relevant on X11.
+    ///
+    /// Some window managers only read `WM_CLASS` when the window is mapped, and won't regroup
+    /// the window until it's hidden and shown again.
+    #[cfg(feature = "x11")]
+    fn set_class(&self, instance: String, class: String);
+
+    /// Adds the extended `_NET_WM_STATE` hints in `state` to the window when `enabled`, or removes
+    /// them otherwise. Only relevant on X11.
+    ///
+    /// The window manager has the final say, and ignores these requests while the window isn't
+    /// visible.
+    #[cfg(feature = "x11")]
+    fn set_x11_window_state(&self, state: XWindowState, enabled: bool);
+
+    /// Returns the extended `_NET_WM_STATE` hints of the window, as set by the window manager.
+    ///
+    /// Returns an empty set if the window doesn't use X11.
+    #[cfg(feature = "x11")]
+    fn x11_window_state(&self) -> XWindowState;
+
+    /// Embeds the window `client` of another X11 client into this window through the XEmbed
+    /// protocol, as systray hosts and browser-plugin containers do.
+    ///
+    /// The client is reparented to the top-left corner of the window and follows its size. It's
+    /// shown as it requests through `_XEMBED_INFO`, and gets the keyboard focus whenever the
+    /// window does. The client is forgotten once it's destroyed or reparented elsewhere.
+    ///
+    /// Returns an error if `client` doesn't exist or the window doesn't use X11.
+    #[cfg(feature = "x11")]
+    fn embed_x11_window(&self, client: raw::c_ulong) -> Result<(), ExternalError>;
+
+    /// Changes the application ID of the window, see [`WindowBuilderExtUnix::with_app_id`].
+    /// Only relevant on Wayland, and ignored for layer-shell surfaces.
+    #[cfg(feature = "wayland")]
+    fn set_app_id(&self, app_id: String);
+
+    /// Lets the compositor stretch the buffers of the window to its logical size, whatever
+    /// their size, or goes back to buffers matching the scale factor.
+    ///
+    /// This allows rendering at a reduced resolution, e.g. for pixel-art or low-power
+    /// applications, while the window keeps its size. The scaling is done by the compositor and
+    /// is cheap, but its filtering is up to it. Takes effect with the next commit of the window.
+    ///
+    /// Returns an error if the window doesn't use Wayland, or if the compositor doesn't support
+    /// the `wp_viewporter` protocol.
+    #[cfg(feature = "wayland")]
+    fn set_viewport_scaling(&self, viewport_scaling: bool) -> Result<(), NotSupportedError>;
+
     /// Check if the window is ready for drawing
     ///
     /// It is a remnant of a previous implementation detail for the
@@ -260,6 +441,96 @@ impl WindowExtUnix for Window {
         }
     }
 
+    #[inline]
+    #[cfg(feature = "wayland")]
+    fn request_activation_token(&self) -> Result<(), NotSupportedError> {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w.request_activation_token(),
+            #[cfg(feature = "x11")]
+            _ => Err(NotSupportedError::new()),
+        }
+    }
+
+    #[inline]
+    #[cfg(feature = "wayland")]
+    fn set_preferred_decoration_mode(&self, mode: Option<DecorationMode>) {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w.set_preferred_decoration_mode(mode),
+            #[cfg(feature = "x11")]
+            _ => (),
+        }
+    }
+
+    #[inline]
+    #[cfg(feature = "wayland")]
+    fn decoration_mode(&self) -> Option<DecorationMode> {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w.decoration_mode(),
+            #[cfg(feature = "x11")]
+            _ => None,
+        }
+    }
+
+    #[inline]
+    #[cfg(feature = "x11")]
+    fn set_class(&self, instance: String, class: String) {
+        match self.window {
+            LinuxWindow::X(ref w) => w.set_class(&instance, &class),
+            #[cfg(feature = "wayland")]
+            _ => (),
+        }
+    }
+
+    #[inline]
+    #[cfg(feature = "x11")]
+    fn set_x11_window_state(&self, state: XWindowState, enabled: bool) {
+        match self.window {
+            LinuxWindow::X(ref w) => w.set_window_state(state, enabled),
+            #[cfg(feature = "wayland")]
+            _ => (),
+        }
+    }
+
+    #[inline]
+    #[cfg(feature = "x11")]
+    fn x11_window_state(&self) -> XWindowState {
+        match self.window {
+            LinuxWindow::X(ref w) => w.window_state(),
+            #[cfg(feature = "wayland")]
+            _ => XWindowState::empty(),
+        }
+    }
+
+    #[inline]
+    #[cfg(feature = "x11")]
+    fn embed_x11_window(&self, client: raw::c_ulong) -> Result<(), ExternalError> {
+        match self.window {
+            LinuxWindow::X(ref w) => w.embed_client(client),
+            #[cfg(feature = "wayland")]
+            _ => Err(ExternalError::NotSupported(NotSupportedError::new())),
+        }
+    }
+
+    #[inline]
+    #[cfg(feature = "wayland")]
+    fn set_app_id(&self, app_id: String) {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w.set_app_id(app_id),
+            #[cfg(feature = "x11")]
+            _ => (),
+        }
+    }
+
+    #[inline]
+    #[cfg(feature = "wayland")]
+    fn set_viewport_scaling(&self, viewport_scaling: bool) -> Result<(), NotSupportedError> {
+        match self.window {
+            LinuxWindow::Wayland(ref w) => w.set_viewport_scaling(viewport_scaling),
+            #[cfg(feature = "x11")]
+            _ => Err(NotSupportedError::new()),
+        }
+    }
+
     #[inline]
     fn is_ready(&self) -> bool {
         true
@@ -277,11 +548,35 @@ pub trait WindowBuilderExtUnix {
     #[cfg(feature = "x11")]
     fn with_class(self, class: String, instance: String) -> Self;
     /// Build window with override-redirect flag; defaults to false. Only relevant on X11.
+    ///
+    /// Override-redirect windows bypass the window manager: they aren't decorated, reparented
+    /// or moved, and are placed exactly at the position of the builder, which defaults to the
+    /// origin of the screen. This is what menus, tooltips and drop-downs are made of, typically
+    /// along with [`with_x11_parent`](Self::with_x11_parent) and a matching
+    /// [`with_x11_window_type`](Self::with_x11_window_type). Since the window manager doesn't
+    /// focus them either, they only get keyboard input while the pointer is over them or the
+    /// keyboard is grabbed.
     #[cfg(feature = "x11")]
     fn with_override_redirect(self, override_redirect: bool) -> Self;
+    /// Build window as a popup of `parent`, the [`xlib_window`](WindowExtUnix::xlib_window) of
+    /// another window. The position of the builder becomes relative to the client area of
+    /// `parent`, and `WM_TRANSIENT_FOR` is set so that the window manager keeps the window above
+    /// its parent. Only relevant on X11.
+    #[cfg(feature = "x11")]
+    fn with_x11_parent(self, parent: raw::c_ulong) -> Self;
+    /// Build window as an XEmbed client of `parent`, a window of another X11 client such as a
+    /// systray host or a plugin container. The window is created as a child of `parent`, which
+    /// controls its visibility and keyboard focus; [`Window::focus_window`] asks it for the focus.
+    /// Only relevant on X11.
+    #[cfg(feature = "x11")]
+    fn with_x11_embed_parent(self, parent: raw::c_ulong) -> Self;
     /// Build window with `_NET_WM_WINDOW_TYPE` hints; defaults to `Normal`. Only relevant on X11.
     #[cfg(feature = "x11")]
     fn with_x11_window_type(self, x11_window_type: Vec<XWindowType>) -> Self;
+    /// Build window with the given extended `_NET_WM_STATE` hints; defaults to none. Only
+    /// relevant on X11.
+    #[cfg(feature = "x11")]
+    fn with_x11_window_state(self, x11_window_state: XWindowState) -> Self;
     /// Build window with `_GTK_THEME_VARIANT` hint set to the specified value. Currently only relevant on X11.
     #[cfg(feature = "x11")]
     fn with_gtk_theme_variant(self, variant: String) -> Self;
@@ -321,6 +616,51 @@ pub trait WindowBuilderExtUnix {
     /// [Desktop Entry Spec](https://specifications.freedesktop.org/desktop-entry-spec/desktop-entry-spec-latest.html#desktop-file-id)
     #[cfg(feature = "wayland")]
     fn with_app_id<T: Into<String>>(self, app_id: T) -> Self;
+
+    /// Build the window as a [wlr-layer-shell] surface on `layer`, instead of a regular window.
+    /// This is what panels, bars, wallpapers and lock screens are made of. Only relevant on
+    /// Wayland, where building the window fails if the compositor doesn't support the protocol.
+    ///
+    /// Layer-shell surfaces are placed by the compositor according to their anchor, and their
+    /// namespace is the application ID set with [`with_app_id`](Self::with_app_id). They have no
+    /// decorations, and ignore the title, the size limits and the fullscreen, maximized,
+    /// minimized and resizable states. A `0` width or height in the inner size lets the surface
+    /// stretch between the opposite edges it's anchored to.
+    ///
+    /// [wlr-layer-shell]: https://wayland.app/protocols/wlr-layer-shell-unstable-v1
+    #[cfg(feature = "wayland")]
+    fn with_layer_shell(self, layer: Layer) -> Self;
+
+    /// Build a layer-shell surface anchored to the given edges of the output. Defaults to no
+    /// edges, which centers the surface. Only relevant with
+    /// [`with_layer_shell`](Self::with_layer_shell).
+    #[cfg(feature = "wayland")]
+    fn with_layer_anchor(self, anchor: LayerAnchor) -> Self;
+
+    /// Build a layer-shell surface reserving `exclusive_zone` logical pixels along the edge it's
+    /// anchored to, so that other surfaces aren't placed over it. `0`, the default, reserves
+    /// nothing and `-1` asks not to be moved for the zones of other surfaces. Only relevant with
+    /// [`with_layer_shell`](Self::with_layer_shell).
+    #[cfg(feature = "wayland")]
+    fn with_layer_exclusive_zone(self, exclusive_zone: i32) -> Self;
+
+    /// Build a layer-shell surface with the given keyboard focus behavior. Defaults to
+    /// [`KeyboardInteractivity::None`]. Only relevant with
+    /// [`with_layer_shell`](Self::with_layer_shell).
+    #[cfg(feature = "wayland")]
+    fn with_layer_keyboard_interactivity(self, interactivity: KeyboardInteractivity) -> Self;
+
+    /// Build the window activated with `token`, typically the one the application was launched
+    /// with, see [`EventLoopWindowTargetExtUnix::take_activation_token_from_env`]. This lets the
+    /// compositor bring the window to the front and focus it. Only relevant on Wayland, with
+    /// compositors supporting the `xdg_activation_v1` protocol.
+    #[cfg(feature = "wayland")]
+    fn with_activation_token(self, token: ActivationToken) -> Self;
+
+    /// Build the window with decorations preferably drawn by `mode`'s side, see
+    /// [`WindowExtUnix::set_preferred_decoration_mode`].
+    #[cfg(feature = "wayland")]
+    fn with_preferred_decoration_mode(self, mode: DecorationMode) -> Self;
 }
 
 impl WindowBuilderExtUnix for WindowBuilder {
@@ -355,6 +695,20 @@ impl WindowBuilderExtUnix for WindowBuilder {
         self
     }
 
+    #[inline]
+    #[cfg(feature = "x11")]
+    fn with_x11_parent(mut self, parent: raw::c_ulong) -> Self {
+        self.platform_specific.x11_parent = Some(parent);
+        self
+    }
+
+    #[inline]
+    #[cfg(feature = "x11")]
+    fn with_x11_embed_parent(mut self, parent: raw::c_ulong) -> Self {
+        self.platform_specific.x11_embed_parent = Some(parent);
+        self
+    }
+
     #[inline]
     #[cfg(feature = "x11")]
     fn with_x11_window_type(mut self, x11_window_types: Vec<XWindowType>) -> Self {
@@ -362,6 +716,13 @@ impl WindowBuilderExtUnix for WindowBuilder {
         self
     }
 
+    #[inline]
+    #[cfg(feature = "x11")]
+    fn with_x11_window_state(mut self, x11_window_state: XWindowState) -> Self {
+        self.platform_specific.x11_window_state = x11_window_state;
+        self
+    }
+
     #[inline]
     #[cfg(feature = "x11")]
     fn with_gtk_theme_variant(mut self, variant: String) -> Self {
@@ -389,6 +750,111 @@ impl WindowBuilderExtUnix for WindowBuilder {
         self.platform_specific.app_id = Some(app_id.into());
         self
     }
+
+    #[inline]
+    #[cfg(feature = "wayland")]
+    fn with_layer_shell(mut self, layer: Layer) -> Self {
+        self.platform_specific.layer = Some(layer);
+        self
+    }
+
+    #[inline]
+    #[cfg(feature = "wayland")]
+    fn with_layer_anchor(mut self, anchor: LayerAnchor) -> Self {
+        self.platform_specific.layer_anchor = anchor;
+        self
+    }
+
+    #[inline]
+    #[cfg(feature = "wayland")]
+    fn with_layer_exclusive_zone(mut self, exclusive_zone: i32) -> Self {
+        self.platform_specific.layer_exclusive_zone = exclusive_zone;
+        self
+    }
+
+    #[inline]
+    #[cfg(feature = "wayland")]
+    fn with_layer_keyboard_interactivity(mut self, interactivity: KeyboardInteractivity) -> Self {
+        self.platform_specific.layer_keyboard_interactivity = interactivity;
+        self
+    }
+
+    #[inline]
+    #[cfg(feature = "wayland")]
+    fn with_activation_token(mut self, token: ActivationToken) -> Self {
+        self.platform_specific.activation_token = Some(token);
+        self
+    }
+
+    #[inline]
+    #[cfg(feature = "wayland")]
+    fn with_preferred_decoration_mode(mut self, mode: DecorationMode) -> Self {
+        self.platform_specific.preferred_decoration_mode = Some(mode);
+        self
+    }
+}
+
+/// A windowing system backend on Unix, see [`EventLoopWindowTargetExtUnix::backend`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnixBackend {
+    X11,
+    Wayland,
+}
+
+/// A global advertised by the Wayland compositor, see
+/// [`EventLoopWindowTargetExtUnix::wayland_globals`].
+#[cfg(feature = "wayland")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WaylandGlobal {
+    /// The numeric name of the global, to pass to `wl_registry.bind`.
+    pub name: u32,
+    /// The interface of the global, e.g. `zwlr_screencopy_manager_v1`.
+    pub interface: String,
+    /// The highest version of the interface supported by the compositor.
+    pub version: u32,
+}
+
+/// The layer of a Wayland layer-shell surface, see [`WindowBuilderExtUnix::with_layer_shell`].
+///
+/// Layers are stacked from `Background` at the bottom to `Overlay` at the top, with regular
+/// windows between `Bottom` and `Top`.
+#[cfg(feature = "wayland")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Layer {
+    Background,
+    Bottom,
+    Top,
+    Overlay,
+}
+
+#[cfg(feature = "wayland")]
+bitflags! {
+    /// The edges of the output a Wayland layer-shell surface is anchored to, see
+    /// [`WindowBuilderExtUnix::with_layer_anchor`].
+    #[derive(Default)]
+    pub struct LayerAnchor: u8 {
+        const TOP    = 1 << 0;
+        const BOTTOM = 1 << 1;
+        const LEFT   = 1 << 2;
+        const RIGHT  = 1 << 3;
+    }
+}
+
+/// Whether a Wayland layer-shell surface gets the keyboard focus, see
+/// [`WindowBuilderExtUnix::with_layer_keyboard_interactivity`].
+#[cfg(feature = "wayland")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyboardInteractivity {
+    /// The surface never gets the keyboard focus.
+    None,
+    /// The surface takes the keyboard focus while it's on the top or overlay layer, e.g. for a
+    /// lock screen.
+    Exclusive,
+    /// The surface can get the keyboard focus like a regular window, e.g. when it's clicked.
+    ///
+    /// Falls back to `Exclusive` on compositors implementing versions of the protocol that
+    /// don't support it.
+    OnDemand,
 }
 
 /// Additional methods on `MonitorHandle` that are specific to Linux.
@@ -403,3 +869,14 @@ impl MonitorHandleExtUnix for MonitorHandle {
         self.inner.native_identifier()
     }
 }
+
+/// Registers a hook run on the Xlib errors of the process before winit handles them.
+///
+/// The hook is called with the `Display` and the `XErrorEvent` of the error, and returns whether
+/// it handled it, in which case winit neither logs nor reports it. Libraries making Xlib calls
+/// that are expected to fail, e.g. GLX while looking for a supported context, need this as Xlib
+/// only has one error handler for the whole process.
+#[cfg(feature = "x11")]
+pub fn register_xlib_error_hook(hook: XlibErrorHook) {
+    crate::platform_impl::XLIB_ERROR_HOOKS.lock().push(hook);
+}
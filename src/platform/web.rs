@@ -9,15 +9,69 @@ use crate::window::WindowBuilder;
 
 use web_sys::HtmlCanvasElement;
 
+bitflags! {
+    /// The categories of events whose default browser action is prevented by the canvas.
+    ///
+    /// The default prevents the default action of keyboard and wheel events, so that e.g. the
+    /// space key or the mouse wheel don't scroll the page.
+    pub struct PreventDefault: u32 {
+        /// `keydown`, `keyup` and `keypress`. Key presses producing text input are never
+        /// prevented, so that `ReceivedCharacter` is still emitted for them.
+        const KEYBOARD = 1 << 0;
+        /// `wheel`.
+        const WHEEL = 1 << 1;
+        /// `pointerdown`, `pointerup` and `pointermove`, or the matching mouse events.
+        const POINTER = 1 << 2;
+        /// `contextmenu`, which hides the context menu of the browser on right click.
+        const CONTEXT_MENU = 1 << 3;
+    }
+}
+
+impl Default for PreventDefault {
+    fn default() -> Self {
+        PreventDefault::KEYBOARD | PreventDefault::WHEEL
+    }
+}
+
 pub trait WindowExtWebSys {
     fn canvas(&self) -> HtmlCanvasElement;
 
     /// Whether the browser reports the preferred color scheme to be "dark".
     fn is_dark_mode(&self) -> bool;
+
+    /// Sets the categories of events whose default browser action is prevented.
+    fn set_prevent_default(&self, prevent_default: PreventDefault);
+
+    /// Returns the categories of events whose default browser action is prevented.
+    fn prevent_default(&self) -> PreventDefault;
+
+    /// Makes the canvas fill its parent element and follow its size, emitting
+    /// `WindowEvent::Resized` when it changes.
+    ///
+    /// Calling `set_inner_size` stops following the parent.
+    fn set_follow_parent_size(&self, follow: bool);
 }
 
 pub trait WindowBuilderExtWebSys {
+    /// Uses an existing canvas element instead of creating a new one.
+    ///
+    /// Unless `with_inner_size` is used, the canvas keeps its current size on the page.
     fn with_canvas(self, canvas: Option<HtmlCanvasElement>) -> Self;
+
+    /// Uses the existing canvas element with the given `id` instead of creating a new one.
+    ///
+    /// Building the window fails if the document has no such canvas element.
+    fn with_canvas_id(self, id: &str) -> Self;
+
+    /// Sets the categories of events whose default browser action is prevented.
+    ///
+    /// The default is `PreventDefault::default()`.
+    fn with_prevent_default(self, prevent_default: PreventDefault) -> Self;
+
+    /// Makes the canvas fill its parent element and follow its size.
+    ///
+    /// See `WindowExtWebSys::set_follow_parent_size`.
+    fn with_follow_parent_size(self, follow: bool) -> Self;
 }
 
 impl WindowBuilderExtWebSys for WindowBuilder {
@@ -26,4 +80,22 @@ impl WindowBuilderExtWebSys for WindowBuilder {
 
         self
     }
+
+    fn with_canvas_id(mut self, id: &str) -> Self {
+        self.platform_specific.canvas_id = Some(id.to_owned());
+
+        self
+    }
+
+    fn with_prevent_default(mut self, prevent_default: PreventDefault) -> Self {
+        self.platform_specific.prevent_default = prevent_default;
+
+        self
+    }
+
+    fn with_follow_parent_size(mut self, follow: bool) -> Self {
+        self.platform_specific.follow_parent_size = follow;
+
+        self
+    }
 }
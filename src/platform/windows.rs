@@ -3,13 +3,14 @@
 use std::os::raw::c_void;
 use std::path::Path;
 
-use winapi::shared::minwindef::WORD;
+use winapi::shared::minwindef::{LPARAM, LRESULT, UINT, WORD, WPARAM};
 use winapi::shared::windef::{HMENU, HWND};
 
 use crate::{
-    dpi::PhysicalSize,
+    dpi::{PhysicalPosition, PhysicalSize},
+    error::OsError,
     event::DeviceId,
-    event_loop::EventLoopBuilder,
+    event_loop::{EventLoopBuilder, EventLoopWindowTarget},
     monitor::MonitorHandle,
     platform_impl::{Parent, WinIcon},
     window::{BadIcon, Icon, Theme, Window, WindowBuilder},
@@ -51,6 +52,34 @@ pub trait EventLoopBuilderExtWindows {
     /// # }
     /// ```
     fn with_dpi_aware(&mut self, dpi_aware: bool) -> &mut Self;
+
+    /// Sets the classes of devices the event loop registers for raw input, which the device
+    /// events are read from.
+    ///
+    /// By default, mice and keyboards are registered. Whether the device events are delivered
+    /// while the application is in the background is controlled with
+    /// [`EventLoopWindowTarget::listen_device_events`].
+    fn with_raw_input_devices(&mut self, devices: RawInputDevices) -> &mut Self;
+
+    /// Whether to read all the raw input queued for the thread at once with
+    /// `GetRawInputBuffer`, instead of one `WM_INPUT` message at a time.
+    ///
+    /// This reduces the overhead of high-frequency devices, such as gaming mice polling at
+    /// 1000 Hz or more. By default, buffered reads are disabled.
+    fn with_buffered_raw_input(&mut self, buffered: bool) -> &mut Self;
+
+    /// Whether to read the raw input on a dedicated thread, which delivers it to the event loop
+    /// in batches.
+    ///
+    /// The device events are then timestamped when they're read, instead of when the event loop
+    /// gets to them, so [`EventLoopWindowTarget::event_timestamp`] stays accurate while the
+    /// application is busy, e.g. rendering a frame. The input read in the meantime is delivered
+    /// at once on the next iteration of the event loop. This only affects the device events, and
+    /// replaces [`with_buffered_raw_input`](Self::with_buffered_raw_input). By default, the raw
+    /// input is read on the thread of the event loop.
+    ///
+    /// [`EventLoopWindowTarget::event_timestamp`]: crate::event_loop::EventLoopWindowTarget::event_timestamp
+    fn with_raw_input_thread(&mut self, raw_input_thread: bool) -> &mut Self;
 }
 
 impl<T> EventLoopBuilderExtWindows for EventLoopBuilder<T> {
@@ -65,6 +94,126 @@ impl<T> EventLoopBuilderExtWindows for EventLoopBuilder<T> {
         self.platform_specific.dpi_aware = dpi_aware;
         self
     }
+
+    #[inline]
+    fn with_raw_input_devices(&mut self, devices: RawInputDevices) -> &mut Self {
+        self.platform_specific.raw_input_devices = devices;
+        self
+    }
+
+    #[inline]
+    fn with_buffered_raw_input(&mut self, buffered: bool) -> &mut Self {
+        self.platform_specific.buffered_raw_input = buffered;
+        self
+    }
+
+    #[inline]
+    fn with_raw_input_thread(&mut self, raw_input_thread: bool) -> &mut Self {
+        self.platform_specific.raw_input_thread = raw_input_thread;
+        self
+    }
+}
+
+bitflags! {
+    /// The classes of devices registered for raw input, see
+    /// [`EventLoopBuilderExtWindows::with_raw_input_devices`].
+    pub struct RawInputDevices: u8 {
+        const MOUSE    = 1 << 0;
+        const KEYBOARD = 1 << 1;
+    }
+}
+
+/// Additional methods on `EventLoopWindowTarget` that are specific to Windows.
+pub trait EventLoopWindowTargetExtWindows {
+    /// Sets a hook called with the messages sent or posted to the windows of the event loop,
+    /// before winit handles them, replacing the previous hook.
+    ///
+    /// The hook observes a message by returning `None`, after which winit handles it as usual, or
+    /// consumes it by returning `Some(result)`, which the window procedure returns as is without
+    /// winit seeing the message. Consuming messages winit relies on, such as `WM_DESTROY` or
+    /// `WM_NCDESTROY`, leaves the window in an inconsistent state.
+    ///
+    /// The hook doesn't see the messages sent while the window is being created, nor the messages
+    /// it sends itself.
+    fn set_window_proc_hook<F>(&self, hook: F)
+    where
+        F: FnMut(HWND, UINT, WPARAM, LPARAM) -> Option<LRESULT> + 'static;
+
+    /// Removes the hook set by [`set_window_proc_hook`](Self::set_window_proc_hook).
+    fn remove_window_proc_hook(&self);
+}
+
+impl<T> EventLoopWindowTargetExtWindows for EventLoopWindowTarget<T> {
+    #[inline]
+    fn set_window_proc_hook<F>(&self, hook: F)
+    where
+        F: FnMut(HWND, UINT, WPARAM, LPARAM) -> Option<LRESULT> + 'static,
+    {
+        self.p.set_window_proc_hook(Some(Box::new(hook)));
+    }
+
+    #[inline]
+    fn remove_window_proc_hook(&self) {
+        self.p.set_window_proc_hook(None);
+    }
+}
+
+/// The material DWM draws behind a window, from Windows 11 22H2 onwards. Corresponds to
+/// `DWM_SYSTEMBACKDROP_TYPE`.
+///
+/// The material shows through the title bar, and through the client area where the window is
+/// transparent, see [`WindowBuilder::with_transparent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BackdropType {
+    /// Corresponds to `DWMSBT_AUTO`, letting DWM pick the material.
+    Auto = 0,
+    /// Corresponds to `DWMSBT_NONE`.
+    None = 1,
+    /// Corresponds to `DWMSBT_MAINWINDOW`, the Mica material of long-lived windows.
+    MainWindow = 2,
+    /// Corresponds to `DWMSBT_TRANSIENTWINDOW`, the Acrylic material of transient windows such as
+    /// menus.
+    TransientWindow = 3,
+    /// Corresponds to `DWMSBT_TABBEDWINDOW`, the Mica Alt material of windows with tabs in their
+    /// title bar.
+    TabbedWindow = 4,
+}
+
+impl Default for BackdropType {
+    fn default() -> Self {
+        BackdropType::Auto
+    }
+}
+
+/// A button of the toolbar shown in the taskbar thumbnail of a window, see
+/// [`WindowExtWindows::set_thumbnail_buttons`].
+#[derive(Debug, Clone)]
+pub struct ThumbnailButton {
+    /// The identifier reported by
+    /// [`WindowEvent::ThumbnailButtonClicked`](crate::event::WindowEvent::ThumbnailButtonClicked)
+    /// when the button is clicked.
+    pub id: u32,
+    /// The icon of the button. A good size is 16x16, scaled by the scale factor of the window.
+    pub icon: Icon,
+    /// The tooltip of the button, truncated to 259 UTF-16 code units.
+    pub tooltip: String,
+    /// Whether the button can be clicked, or is grayed out.
+    pub enabled: bool,
+    /// Whether the thumbnail closes when the button is clicked.
+    pub dismiss_on_click: bool,
+}
+
+impl ThumbnailButton {
+    /// Creates an enabled button that doesn't close the thumbnail when clicked.
+    pub fn new(id: u32, icon: Icon, tooltip: &str) -> Self {
+        Self {
+            id,
+            icon,
+            tooltip: tooltip.to_owned(),
+            enabled: true,
+            dismiss_on_click: false,
+        }
+    }
 }
 
 /// Additional methods on `Window` that are specific to Windows.
@@ -96,6 +245,50 @@ pub trait WindowExtWindows {
 
     /// Returns the current window theme.
     fn theme(&self) -> Theme;
+
+    /// Forces a theme or uses the system settings if `None` was provided, see
+    /// [`WindowBuilderExtWindows::with_theme`]. The title bar is drawn in dark colors with the
+    /// dark theme.
+    fn set_theme(&self, theme: Option<Theme>);
+
+    /// Sets the material DWM draws behind the window. Ignored before Windows 11 22H2.
+    fn set_system_backdrop(&self, backdrop_type: BackdropType);
+
+    /// Sets the buttons of the toolbar shown in the taskbar thumbnail of the window, e.g. the
+    /// playback controls of a media player. An empty list removes the toolbar.
+    ///
+    /// The taskbar shows up to 7 buttons, the following ones are ignored. The buttons are kept
+    /// when the taskbar is restarted.
+    fn set_thumbnail_buttons(&self, buttons: Vec<ThumbnailButton>);
+
+    /// Sets the area of the client area shown in the taskbar thumbnail of the window, or shows
+    /// the whole window if `None` was provided.
+    fn set_thumbnail_clip(&self, clip: Option<(PhysicalPosition<i32>, PhysicalSize<u32>)>);
+
+    /// Sets the tooltip shown when the taskbar thumbnail of the window is hovered, or uses the
+    /// window title if `None` was provided.
+    fn set_thumbnail_tooltip(&self, tooltip: Option<&str>);
+
+    /// Returns a pointer to the `IDCompositionDevice` of the DirectComposition visual tree of
+    /// the window, which is created on first use along with a target for the window, e.g. to
+    /// create more visuals or commit changes made to them.
+    ///
+    /// The pointer will become invalid when the window is destroyed. DirectComposition needs
+    /// Windows 8.1, an error is returned on earlier versions.
+    fn composition_device(&self) -> Result<*mut c_void, OsError>;
+
+    /// Returns a pointer to the root `IDCompositionVisual` of the DirectComposition visual tree
+    /// of the window, see [`composition_device`](Self::composition_device).
+    fn composition_visual(&self) -> Result<*mut c_void, OsError>;
+
+    /// Sets the content of the root visual of the window and commits it, or removes it if
+    /// `content` is null. This is typically a flip model swap chain created with
+    /// `IDXGIFactory2::CreateSwapChainForComposition`, which is drawn with its alpha channel on
+    /// windows built with [`WindowBuilderExtWindows::with_no_redirection_bitmap`].
+    ///
+    /// The visual takes a reference to `content`. See
+    /// [`composition_device`](Self::composition_device) for the requirements.
+    fn set_composition_content(&self, content: *mut c_void) -> Result<(), OsError>;
 }
 
 impl WindowExtWindows for Window {
@@ -123,6 +316,47 @@ impl WindowExtWindows for Window {
     fn theme(&self) -> Theme {
         self.window.theme()
     }
+
+    #[inline]
+    fn set_theme(&self, theme: Option<Theme>) {
+        self.window.set_theme(theme)
+    }
+
+    #[inline]
+    fn set_system_backdrop(&self, backdrop_type: BackdropType) {
+        self.window.set_system_backdrop(backdrop_type)
+    }
+
+    #[inline]
+    fn set_thumbnail_buttons(&self, buttons: Vec<ThumbnailButton>) {
+        self.window.set_thumbnail_buttons(buttons)
+    }
+
+    #[inline]
+    fn set_thumbnail_clip(&self, clip: Option<(PhysicalPosition<i32>, PhysicalSize<u32>)>) {
+        self.window.set_thumbnail_clip(clip)
+    }
+
+    #[inline]
+    fn set_thumbnail_tooltip(&self, tooltip: Option<&str>) {
+        self.window
+            .set_thumbnail_tooltip(tooltip.map(str::to_owned))
+    }
+
+    #[inline]
+    fn composition_device(&self) -> Result<*mut c_void, OsError> {
+        self.window.composition_device()
+    }
+
+    #[inline]
+    fn composition_visual(&self) -> Result<*mut c_void, OsError> {
+        self.window.composition_visual()
+    }
+
+    #[inline]
+    fn set_composition_content(&self, content: *mut c_void) -> Result<(), OsError> {
+        self.window.set_composition_content(content)
+    }
 }
 
 /// Additional methods on `WindowBuilder` that are specific to Windows.
@@ -130,6 +364,9 @@ pub trait WindowBuilderExtWindows {
     /// Sets a parent to the window to be created.
     ///
     /// A child window has the WS_CHILD style and is confined to the client area of its parent window.
+    /// The parent doesn't need to be a winit window, which lets Win32 hosts embed the window; the
+    /// messages the host forwards can be inspected with
+    /// [`EventLoopWindowTargetExtWindows::set_window_proc_hook`].
     ///
     /// For more information, see <https://docs.microsoft.com/en-us/windows/win32/winmsg/window-features#child-windows>
     fn with_parent_window(self, parent: HWND) -> WindowBuilder;
@@ -171,7 +408,25 @@ pub trait WindowBuilderExtWindows {
     fn with_drag_and_drop(self, flag: bool) -> WindowBuilder;
 
     /// Forces a theme or uses the system settings if `None` was provided.
+    ///
+    /// The title bar is drawn in dark colors with the dark theme, from Windows 10 October 2018
+    /// Update onwards.
     fn with_theme(self, theme: Option<Theme>) -> WindowBuilder;
+
+    /// Sets the material DWM draws behind the window; defaults to [`BackdropType::Auto`]. Ignored
+    /// before Windows 11 22H2.
+    fn with_system_backdrop(self, backdrop_type: BackdropType) -> WindowBuilder;
+
+    /// Reports the two-finger pinches and rotations made on a touch screen as
+    /// [`WindowEvent::PinchGesture`] and [`WindowEvent::RotationGesture`] (disabled by default).
+    ///
+    /// Windows only recognizes these gestures for windows which don't ask for the raw touch
+    /// input, so the window won't receive [`WindowEvent::Touch`] for touch screens anymore.
+    ///
+    /// [`WindowEvent::PinchGesture`]: crate::event::WindowEvent::PinchGesture
+    /// [`WindowEvent::RotationGesture`]: crate::event::WindowEvent::RotationGesture
+    /// [`WindowEvent::Touch`]: crate::event::WindowEvent::Touch
+    fn with_touch_gestures(self, flag: bool) -> WindowBuilder;
 }
 
 impl WindowBuilderExtWindows for WindowBuilder {
@@ -216,6 +471,18 @@ impl WindowBuilderExtWindows for WindowBuilder {
         self.platform_specific.preferred_theme = theme;
         self
     }
+
+    #[inline]
+    fn with_system_backdrop(mut self, backdrop_type: BackdropType) -> WindowBuilder {
+        self.platform_specific.backdrop_type = backdrop_type;
+        self
+    }
+
+    #[inline]
+    fn with_touch_gestures(mut self, flag: bool) -> WindowBuilder {
+        self.platform_specific.touch_gestures = flag;
+        self
+    }
 }
 
 /// Additional methods on `MonitorHandle` that are specific to Windows.
@@ -1,14 +1,16 @@
 #![cfg(target_os = "android")]
 
 use crate::{
+    device,
     dpi::{PhysicalPosition, PhysicalSize, Position, Size},
     error, event,
     event_loop::{self, ControlFlow},
+    keyboard::{Key, KeyCode, NativeKey, NativeKeyCode, PhysicalKey},
     monitor, window,
 };
 use ndk::{
     configuration::Configuration,
-    event::{InputEvent, KeyAction, MotionAction},
+    event::{InputEvent, KeyAction, Keycode, MetaState, MotionAction},
     looper::{ForeignLooper, Poll, ThreadLooper},
 };
 use ndk_glue::{Event, Rect};
@@ -33,6 +35,200 @@ lazy_static! {
     static ref INTERNAL_EVENT: RwLock<Option<InternalEvent>> = RwLock::new(None);
 }
 
+fn to_physical_key(keycode: Keycode, scancode: i32) -> PhysicalKey {
+    PhysicalKey::Code(match keycode {
+        Keycode::A => KeyCode::KeyA,
+        Keycode::B => KeyCode::KeyB,
+        Keycode::C => KeyCode::KeyC,
+        Keycode::D => KeyCode::KeyD,
+        Keycode::E => KeyCode::KeyE,
+        Keycode::F => KeyCode::KeyF,
+        Keycode::G => KeyCode::KeyG,
+        Keycode::H => KeyCode::KeyH,
+        Keycode::I => KeyCode::KeyI,
+        Keycode::J => KeyCode::KeyJ,
+        Keycode::K => KeyCode::KeyK,
+        Keycode::L => KeyCode::KeyL,
+        Keycode::M => KeyCode::KeyM,
+        Keycode::N => KeyCode::KeyN,
+        Keycode::O => KeyCode::KeyO,
+        Keycode::P => KeyCode::KeyP,
+        Keycode::Q => KeyCode::KeyQ,
+        Keycode::R => KeyCode::KeyR,
+        Keycode::S => KeyCode::KeyS,
+        Keycode::T => KeyCode::KeyT,
+        Keycode::U => KeyCode::KeyU,
+        Keycode::V => KeyCode::KeyV,
+        Keycode::W => KeyCode::KeyW,
+        Keycode::X => KeyCode::KeyX,
+        Keycode::Y => KeyCode::KeyY,
+        Keycode::Z => KeyCode::KeyZ,
+        Keycode::Keycode0 => KeyCode::Digit0,
+        Keycode::Keycode1 => KeyCode::Digit1,
+        Keycode::Keycode2 => KeyCode::Digit2,
+        Keycode::Keycode3 => KeyCode::Digit3,
+        Keycode::Keycode4 => KeyCode::Digit4,
+        Keycode::Keycode5 => KeyCode::Digit5,
+        Keycode::Keycode6 => KeyCode::Digit6,
+        Keycode::Keycode7 => KeyCode::Digit7,
+        Keycode::Keycode8 => KeyCode::Digit8,
+        Keycode::Keycode9 => KeyCode::Digit9,
+        Keycode::Grave => KeyCode::Backquote,
+        Keycode::Minus => KeyCode::Minus,
+        Keycode::Equals => KeyCode::Equal,
+        Keycode::LeftBracket => KeyCode::BracketLeft,
+        Keycode::RightBracket => KeyCode::BracketRight,
+        Keycode::Backslash => KeyCode::Backslash,
+        Keycode::Semicolon => KeyCode::Semicolon,
+        Keycode::Apostrophe => KeyCode::Quote,
+        Keycode::Comma => KeyCode::Comma,
+        Keycode::Period => KeyCode::Period,
+        Keycode::Slash => KeyCode::Slash,
+        Keycode::Space => KeyCode::Space,
+        Keycode::Tab => KeyCode::Tab,
+        Keycode::Enter => KeyCode::Enter,
+        Keycode::Del => KeyCode::Backspace,
+        Keycode::ForwardDel => KeyCode::Delete,
+        Keycode::Escape => KeyCode::Escape,
+        Keycode::ShiftLeft => KeyCode::ShiftLeft,
+        Keycode::ShiftRight => KeyCode::ShiftRight,
+        Keycode::CtrlLeft => KeyCode::ControlLeft,
+        Keycode::CtrlRight => KeyCode::ControlRight,
+        Keycode::AltLeft => KeyCode::AltLeft,
+        Keycode::AltRight => KeyCode::AltRight,
+        Keycode::MetaLeft => KeyCode::SuperLeft,
+        Keycode::MetaRight => KeyCode::SuperRight,
+        Keycode::Function => KeyCode::Fn,
+        Keycode::CapsLock => KeyCode::CapsLock,
+        Keycode::ScrollLock => KeyCode::ScrollLock,
+        Keycode::NumLock => KeyCode::NumLock,
+        Keycode::Sysrq => KeyCode::PrintScreen,
+        Keycode::Break => KeyCode::Pause,
+        Keycode::Insert => KeyCode::Insert,
+        Keycode::MoveHome => KeyCode::Home,
+        Keycode::MoveEnd => KeyCode::End,
+        Keycode::PageUp => KeyCode::PageUp,
+        Keycode::PageDown => KeyCode::PageDown,
+        Keycode::DpadUp => KeyCode::ArrowUp,
+        Keycode::DpadDown => KeyCode::ArrowDown,
+        Keycode::DpadLeft => KeyCode::ArrowLeft,
+        Keycode::DpadRight => KeyCode::ArrowRight,
+        Keycode::Menu => KeyCode::ContextMenu,
+        Keycode::F1 => KeyCode::F1,
+        Keycode::F2 => KeyCode::F2,
+        Keycode::F3 => KeyCode::F3,
+        Keycode::F4 => KeyCode::F4,
+        Keycode::F5 => KeyCode::F5,
+        Keycode::F6 => KeyCode::F6,
+        Keycode::F7 => KeyCode::F7,
+        Keycode::F8 => KeyCode::F8,
+        Keycode::F9 => KeyCode::F9,
+        Keycode::F10 => KeyCode::F10,
+        Keycode::F11 => KeyCode::F11,
+        Keycode::F12 => KeyCode::F12,
+        Keycode::Numpad0 => KeyCode::Numpad0,
+        Keycode::Numpad1 => KeyCode::Numpad1,
+        Keycode::Numpad2 => KeyCode::Numpad2,
+        Keycode::Numpad3 => KeyCode::Numpad3,
+        Keycode::Numpad4 => KeyCode::Numpad4,
+        Keycode::Numpad5 => KeyCode::Numpad5,
+        Keycode::Numpad6 => KeyCode::Numpad6,
+        Keycode::Numpad7 => KeyCode::Numpad7,
+        Keycode::Numpad8 => KeyCode::Numpad8,
+        Keycode::Numpad9 => KeyCode::Numpad9,
+        Keycode::NumpadDivide => KeyCode::NumpadDivide,
+        Keycode::NumpadMultiply => KeyCode::NumpadMultiply,
+        Keycode::NumpadSubtract => KeyCode::NumpadSubtract,
+        Keycode::NumpadAdd => KeyCode::NumpadAdd,
+        Keycode::NumpadDot => KeyCode::NumpadDecimal,
+        Keycode::NumpadComma => KeyCode::NumpadComma,
+        Keycode::NumpadEnter => KeyCode::NumpadEnter,
+        Keycode::NumpadEquals => KeyCode::NumpadEqual,
+        Keycode::NumpadLeftParen => KeyCode::NumpadParenLeft,
+        Keycode::NumpadRightParen => KeyCode::NumpadParenRight,
+        Keycode::Back => KeyCode::BrowserBack,
+        Keycode::Home => KeyCode::BrowserHome,
+        Keycode::Search => KeyCode::BrowserSearch,
+        Keycode::VolumeUp => KeyCode::AudioVolumeUp,
+        Keycode::VolumeDown => KeyCode::AudioVolumeDown,
+        Keycode::VolumeMute => KeyCode::AudioVolumeMute,
+        Keycode::MediaPlayPause => KeyCode::MediaPlayPause,
+        Keycode::MediaStop => KeyCode::MediaStop,
+        Keycode::MediaNext => KeyCode::MediaTrackNext,
+        Keycode::MediaPrevious => KeyCode::MediaTrackPrevious,
+        Keycode::Power => KeyCode::Power,
+        _ => return PhysicalKey::Unidentified(NativeKeyCode::Android(scancode as u32)),
+    })
+}
+
+fn to_modifiers(meta_state: MetaState) -> event::ModifiersState {
+    let mut modifiers = event::ModifiersState::empty();
+    modifiers.set(event::ModifiersState::SHIFT, meta_state.shift_on());
+    modifiers.set(event::ModifiersState::CTRL, meta_state.ctrl_on());
+    modifiers.set(event::ModifiersState::ALT, meta_state.alt_on());
+    modifiers.set(event::ModifiersState::LOGO, meta_state.meta_on());
+    modifiers
+}
+
+/// Maps an Android keycode to the named logical key it represents.
+///
+/// The characters a key produces depend on its `KeyCharacterMap`, which isn't available through
+/// the NDK, so character keys are reported as [`Key::Unidentified`].
+fn to_logical_key(keycode: Keycode) -> Key {
+    match keycode {
+        Keycode::Space => Key::Space,
+        Keycode::Tab => Key::Tab,
+        Keycode::Enter | Keycode::NumpadEnter => Key::Enter,
+        Keycode::Del => Key::Backspace,
+        Keycode::ForwardDel => Key::Delete,
+        Keycode::Escape => Key::Escape,
+        Keycode::ShiftLeft | Keycode::ShiftRight => Key::Shift,
+        Keycode::CtrlLeft | Keycode::CtrlRight => Key::Control,
+        Keycode::AltLeft | Keycode::AltRight => Key::Alt,
+        Keycode::MetaLeft | Keycode::MetaRight => Key::Super,
+        Keycode::Function => Key::Fn,
+        Keycode::CapsLock => Key::CapsLock,
+        Keycode::ScrollLock => Key::ScrollLock,
+        Keycode::NumLock => Key::NumLock,
+        Keycode::Sysrq => Key::PrintScreen,
+        Keycode::Break => Key::Pause,
+        Keycode::Insert => Key::Insert,
+        Keycode::MoveHome => Key::Home,
+        Keycode::MoveEnd => Key::End,
+        Keycode::PageUp => Key::PageUp,
+        Keycode::PageDown => Key::PageDown,
+        Keycode::DpadUp => Key::ArrowUp,
+        Keycode::DpadDown => Key::ArrowDown,
+        Keycode::DpadLeft => Key::ArrowLeft,
+        Keycode::DpadRight => Key::ArrowRight,
+        Keycode::Menu => Key::ContextMenu,
+        Keycode::F1 => Key::F1,
+        Keycode::F2 => Key::F2,
+        Keycode::F3 => Key::F3,
+        Keycode::F4 => Key::F4,
+        Keycode::F5 => Key::F5,
+        Keycode::F6 => Key::F6,
+        Keycode::F7 => Key::F7,
+        Keycode::F8 => Key::F8,
+        Keycode::F9 => Key::F9,
+        Keycode::F10 => Key::F10,
+        Keycode::F11 => Key::F11,
+        Keycode::F12 => Key::F12,
+        Keycode::Back => Key::BrowserBack,
+        Keycode::Home => Key::BrowserHome,
+        Keycode::Search => Key::BrowserSearch,
+        Keycode::VolumeUp => Key::AudioVolumeUp,
+        Keycode::VolumeDown => Key::AudioVolumeDown,
+        Keycode::VolumeMute => Key::AudioVolumeMute,
+        Keycode::MediaPlayPause => Key::MediaPlayPause,
+        Keycode::MediaStop => Key::MediaStop,
+        Keycode::MediaNext => Key::MediaTrackNext,
+        Keycode::MediaPrevious => Key::MediaTrackPrevious,
+        Keycode::Power => Key::Power,
+        keycode => Key::Unidentified(NativeKey::Android(keycode as u32)),
+    }
+}
+
 enum InternalEvent {
     RedrawRequested,
 }
@@ -92,6 +288,10 @@ impl<T: 'static> EventLoop<T> {
                 p: EventLoopWindowTarget {
                     _marker: std::marker::PhantomData,
                 },
+                coalescing: Default::default(),
+                exit_code: Default::default(),
+                event_timestamp: Default::default(),
+                dispatcher_settings: Default::default(),
                 _marker: std::marker::PhantomData,
             },
             user_queue: Default::default(),
@@ -105,7 +305,7 @@ impl<T: 'static> EventLoop<T> {
     pub fn run<F>(mut self, event_handler: F) -> !
     where
         F: 'static
-            + FnMut(event::Event<'_, T>, &event_loop::EventLoopWindowTarget<T>, &mut ControlFlow),
+            + FnMut(event::Event<T>, &event_loop::EventLoopWindowTarget<T>, &mut ControlFlow),
     {
         let exit_code = self.run_return(event_handler);
         ::std::process::exit(exit_code);
@@ -113,7 +313,7 @@ impl<T: 'static> EventLoop<T> {
 
     pub fn run_return<F>(&mut self, mut event_handler: F) -> i32
     where
-        F: FnMut(event::Event<'_, T>, &event_loop::EventLoopWindowTarget<T>, &mut ControlFlow),
+        F: FnMut(event::Event<T>, &event_loop::EventLoopWindowTarget<T>, &mut ControlFlow),
     {
         let mut control_flow = ControlFlow::default();
 
@@ -148,6 +348,14 @@ impl<T: 'static> EventLoop<T> {
                             event::Event::Suspended
                         );
                     }
+                    Event::LowMemory => {
+                        call_event_handler!(
+                            event_handler,
+                            self.window_target(),
+                            control_flow,
+                            event::Event::MemoryWarning
+                        );
+                    }
                     Event::Pause => self.running = false,
                     Event::Resume => self.running = true,
                     Event::ConfigChanged => {
@@ -158,11 +366,13 @@ impl<T: 'static> EventLoop<T> {
                         *CONFIG.write().unwrap() = config;
                         let scale_factor = MonitorHandle.scale_factor();
                         if (scale_factor - old_scale_factor).abs() < f64::EPSILON {
-                            let mut size = MonitorHandle.size();
+                            let new_inner_size = Arc::new(Mutex::new(MonitorHandle.size()));
                             let event = event::Event::WindowEvent {
                                 window_id: window::WindowId(WindowId),
                                 event: event::WindowEvent::ScaleFactorChanged {
-                                    new_inner_size: &mut size,
+                                    inner_size_writer: event::InnerSizeWriter::new(Arc::downgrade(
+                                        &new_inner_size,
+                                    )),
                                     scale_factor,
                                 },
                             };
@@ -174,6 +384,17 @@ impl<T: 'static> EventLoop<T> {
                             );
                         }
                     }
+                    Event::ContentRectChanged => {
+                        call_event_handler!(
+                            event_handler,
+                            self.window_target(),
+                            control_flow,
+                            event::Event::WindowEvent {
+                                window_id: window::WindowId(WindowId),
+                                event: event::WindowEvent::InsetsChanged,
+                            }
+                        );
+                    }
                     Event::WindowHasFocus => {
                         call_event_handler!(
                             event_handler,
@@ -277,10 +498,16 @@ impl<T: 'static> EventLoop<T> {
                                             event: event::WindowEvent::KeyboardInput {
                                                 device_id,
                                                 input: event::KeyboardInput {
-                                                    scancode: key.scan_code() as u32,
+                                                    physical_key: to_physical_key(
+                                                        key.key_code(),
+                                                        key.scan_code(),
+                                                    ),
                                                     state,
-                                                    virtual_keycode: None,
-                                                    modifiers: event::ModifiersState::default(),
+                                                    logical_key: to_logical_key(key.key_code()),
+                                                    text: None,
+                                                    repeat: key.repeat_count() > 0,
+                                                    modifiers: to_modifiers(key.meta_state()),
+                                                    platform_specific: KeyEventExtra,
                                                 },
                                                 is_synthetic: false,
                                             },
@@ -365,6 +592,7 @@ impl<T: 'static> EventLoop<T> {
                     self.start_cause = event::StartCause::Poll;
                 }
                 ControlFlow::Wait => {
+                    trace_span!("wait");
                     self.first_event = poll(self.looper.poll_all().unwrap());
                     self.start_cause = event::StartCause::WaitCancelled {
                         start: Instant::now(),
@@ -378,6 +606,7 @@ impl<T: 'static> EventLoop<T> {
                     } else {
                         instant - start
                     };
+                    trace_span!("wait", timeout = ?duration);
                     self.first_event = poll(self.looper.poll_all_timeout(duration).unwrap());
                     self.start_cause = if self.first_event.is_some() {
                         event::StartCause::WaitCancelled {
@@ -418,6 +647,8 @@ impl<T> EventLoopProxy<T> {
         self.looper.wake();
         Ok(())
     }
+
+    pub fn set_wakeup(&mut self, _wakeup: event_loop::ProxyWakeup) {}
 }
 
 impl<T> Clone for EventLoopProxy<T> {
@@ -434,6 +665,20 @@ pub struct EventLoopWindowTarget<T: 'static> {
 }
 
 impl<T: 'static> EventLoopWindowTarget<T> {
+    #[cfg(feature = "rwh_05")]
+    pub fn raw_display_handle_rwh_05(&self) -> rwh_05::RawDisplayHandle {
+        rwh_05::RawDisplayHandle::Android(rwh_05::AndroidDisplayHandle::empty())
+    }
+
+    #[cfg(feature = "rwh_06")]
+    pub fn raw_display_handle_rwh_06(
+        &self,
+    ) -> Result<rwh_06::RawDisplayHandle, rwh_06::HandleError> {
+        Ok(rwh_06::RawDisplayHandle::Android(
+            rwh_06::AndroidDisplayHandle::new(),
+        ))
+    }
+
     pub fn primary_monitor(&self) -> Option<monitor::MonitorHandle> {
         Some(monitor::MonitorHandle {
             inner: MonitorHandle,
@@ -445,6 +690,43 @@ impl<T: 'static> EventLoopWindowTarget<T> {
         v.push_back(MonitorHandle);
         v
     }
+
+    pub fn devices(&self) -> Vec<device::DeviceHandle> {
+        Vec::new()
+    }
+
+    pub fn listen_device_events(&self, _allowed: event_loop::DeviceEvents) {}
+
+    pub fn keyboard_layout(&self) -> Option<String> {
+        None
+    }
+
+    pub fn key_code_to_text(
+        &self,
+        _code: KeyCode,
+        _modifiers: event::ModifiersState,
+    ) -> Option<String> {
+        None
+    }
+
+    pub fn system_preferences(&self) -> event_loop::SystemPreferences {
+        event_loop::SystemPreferences::default()
+    }
+
+    pub fn power_state(&self) -> event_loop::PowerState {
+        event_loop::PowerState::default()
+    }
+
+    pub fn event_timestamp(&self) -> Option<Instant> {
+        None
+    }
+
+    pub fn run_nested<F>(&self, _event_handler: F) -> Result<i32, error::NotSupportedError>
+    where
+        F: FnMut(event::Event<T>, &mut ControlFlow),
+    {
+        Err(error::NotSupportedError::new())
+    }
 }
 
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
@@ -465,6 +747,9 @@ impl DeviceId {
     }
 }
 
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct KeyEventExtra;
+
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
 pub struct PlatformSpecificWindowBuilderAttributes;
 
@@ -502,6 +787,12 @@ impl Window {
         })
     }
 
+    #[cfg(feature = "accesskit")]
+    pub fn enable_accessibility(&self, _source: Box<dyn crate::accessibility::TreeSource>) {}
+
+    #[cfg(feature = "accesskit")]
+    pub fn update_accessibility_tree(&self, _update: impl FnOnce() -> accesskit::TreeUpdate) {}
+
     pub fn scale_factor(&self) -> f64 {
         MonitorHandle.scale_factor()
     }
@@ -601,6 +892,8 @@ impl Window {
 
     pub fn set_cursor_visible(&self, _: bool) {}
 
+    pub fn set_key_repeat_enabled(&self, _: bool) {}
+
     pub fn drag_window(&self) -> Result<(), error::ExternalError> {
         Err(error::ExternalError::NotSupported(
             error::NotSupportedError::new(),
@@ -617,6 +910,42 @@ impl Window {
         RawWindowHandle::AndroidNdk(handle)
     }
 
+    #[cfg(feature = "rwh_05")]
+    pub fn raw_window_handle_rwh_05(&self) -> rwh_05::RawWindowHandle {
+        let mut handle = rwh_05::AndroidNdkWindowHandle::empty();
+        if let Some(native_window) = ndk_glue::native_window().as_ref() {
+            handle.a_native_window = unsafe { native_window.ptr().as_mut() as *mut _ as *mut _ }
+        } else {
+            panic!("Cannot get the native window, it's null and will always be null before Event::Resumed and after Event::Suspended. Make sure you only call this function between those events.");
+        };
+        rwh_05::RawWindowHandle::AndroidNdk(handle)
+    }
+
+    #[cfg(feature = "rwh_05")]
+    pub fn raw_display_handle_rwh_05(&self) -> rwh_05::RawDisplayHandle {
+        rwh_05::RawDisplayHandle::Android(rwh_05::AndroidDisplayHandle::empty())
+    }
+
+    #[cfg(feature = "rwh_06")]
+    pub fn raw_window_handle_rwh_06(&self) -> Result<rwh_06::RawWindowHandle, rwh_06::HandleError> {
+        // The native window only exists between `Event::Resumed` and `Event::Suspended`.
+        let native_window = ndk_glue::native_window();
+        let native_window = native_window
+            .as_ref()
+            .ok_or(rwh_06::HandleError::Unavailable)?;
+        let handle = rwh_06::AndroidNdkWindowHandle::new(native_window.ptr().cast());
+        Ok(rwh_06::RawWindowHandle::AndroidNdk(handle))
+    }
+
+    #[cfg(feature = "rwh_06")]
+    pub fn raw_display_handle_rwh_06(
+        &self,
+    ) -> Result<rwh_06::RawDisplayHandle, rwh_06::HandleError> {
+        Ok(rwh_06::RawDisplayHandle::Android(
+            rwh_06::AndroidDisplayHandle::new(),
+        ))
+    }
+
     pub fn config(&self) -> Configuration {
         CONFIG.read().unwrap().clone()
     }
@@ -624,6 +953,17 @@ impl Window {
     pub fn content_rect(&self) -> Rect {
         ndk_glue::content_rect()
     }
+
+    pub fn insets(&self) -> Rect {
+        let content_rect = ndk_glue::content_rect();
+        let size = MonitorHandle.size();
+        Rect {
+            left: content_rect.left,
+            top: content_rect.top,
+            right: (size.width as i32 - content_rect.right).max(0),
+            bottom: (size.height as i32 - content_rect.bottom).max(0),
+        }
+    }
 }
 
 #[derive(Default, Clone, Debug)]
@@ -0,0 +1,86 @@
+//! Detection of double and triple clicks, for the platforms which don't report click counts.
+
+use instant::Instant;
+use std::time::Duration;
+
+use crate::{
+    dpi::{PhysicalPosition, PhysicalSize},
+    event::MouseButton,
+    window::WindowId,
+};
+
+/// How close to each other two presses must be to count as repeated clicks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct DoubleClickSettings {
+    /// Maximum time between two presses.
+    pub interval: Duration,
+    /// Maximum distance between two presses, along each axis.
+    pub distance: PhysicalSize<f64>,
+}
+
+impl Default for DoubleClickSettings {
+    /// The defaults used by GTK, for the platforms which don't expose these settings.
+    fn default() -> Self {
+        DoubleClickSettings {
+            interval: Duration::from_millis(400),
+            distance: PhysicalSize::new(5.0, 5.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Click {
+    window_id: WindowId,
+    button: MouseButton,
+    position: PhysicalPosition<f64>,
+    time: Instant,
+    count: u32,
+}
+
+/// Counts the presses of a mouse button made in quick succession at the same place.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct ClickCounter {
+    last: Option<Click>,
+}
+
+impl ClickCounter {
+    /// Registers a press of `button`, returning the click count to report with it.
+    pub fn press(
+        &mut self,
+        window_id: WindowId,
+        button: MouseButton,
+        position: PhysicalPosition<f64>,
+        settings: DoubleClickSettings,
+    ) -> u32 {
+        let time = Instant::now();
+        let count = match self.last {
+            Some(last)
+                if last.window_id == window_id
+                    && last.button == button
+                    && time.duration_since(last.time) <= settings.interval
+                    && (position.x - last.position.x).abs() <= settings.distance.width
+                    && (position.y - last.position.y).abs() <= settings.distance.height =>
+            {
+                last.count + 1
+            }
+            _ => 1,
+        };
+        self.last = Some(Click {
+            window_id,
+            button,
+            position,
+            time,
+            count,
+        });
+        count
+    }
+
+    /// Returns the click count to report with a release of `button`, which is the one of the
+    /// press being released.
+    pub fn release(&self, button: MouseButton) -> u32 {
+        match self.last {
+            Some(last) if last.button == button => last.count,
+            _ => 1,
+        }
+    }
+}
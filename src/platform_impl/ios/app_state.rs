@@ -6,6 +6,7 @@ use std::{
     mem,
     os::raw::c_void,
     ptr,
+    sync::{Arc, Mutex},
     time::Instant,
 };
 
@@ -13,7 +14,7 @@ use objc::runtime::{BOOL, YES};
 
 use crate::{
     dpi::LogicalSize,
-    event::{Event, StartCause, WindowEvent},
+    event::{Event, InnerSizeWriter, StartCause, WindowEvent},
     event_loop::ControlFlow,
     platform_impl::platform::{
         event_loop::{EventHandler, EventProxy, EventWrapper, Never},
@@ -50,7 +51,7 @@ enum UserCallbackTransitionResult<'a> {
     },
 }
 
-impl Event<'static, Never> {
+impl Event<Never> {
     fn is_redraw(&self) -> bool {
         if let Event::RedrawRequested(_) = self {
             true
@@ -861,18 +862,17 @@ fn handle_hidpi_proxy(
     scale_factor: f64,
     window_id: id,
 ) {
-    let mut size = suggested_size.to_physical(scale_factor);
-    let new_inner_size = &mut size;
+    let new_inner_size = Arc::new(Mutex::new(suggested_size.to_physical(scale_factor)));
     let event = Event::WindowEvent {
         window_id: RootWindowId(window_id.into()),
         event: WindowEvent::ScaleFactorChanged {
             scale_factor,
-            new_inner_size,
+            inner_size_writer: InnerSizeWriter::new(Arc::downgrade(&new_inner_size)),
         },
     };
     event_handler.handle_nonuser_event(event, &mut control_flow);
     let (view, screen_frame) = get_view_and_screen_frame(window_id);
-    let physical_size = *new_inner_size;
+    let physical_size = *new_inner_size.lock().unwrap();
     let logical_size = physical_size.to_logical(scale_factor);
     let size = CGSize::new(logical_size);
     let new_frame: CGRect = CGRect::new(screen_frame.origin, size);
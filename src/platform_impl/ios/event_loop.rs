@@ -3,16 +3,26 @@ use std::{
     ffi::c_void,
     fmt::{self, Debug},
     marker::PhantomData,
-    mem, ptr,
+    mem,
+    panic::{catch_unwind, AssertUnwindSafe},
+    process, ptr,
     sync::mpsc::{self, Receiver, Sender},
+    time::Instant,
 };
 
+use objc::runtime::{BOOL, YES};
+
 use crate::{
+    device::DeviceHandle,
     dpi::LogicalSize,
-    event::Event,
+    error::NotSupportedError,
+    event::{Event, ModifiersState},
     event_loop::{
-        ControlFlow, EventLoopClosed, EventLoopWindowTarget as RootEventLoopWindowTarget,
+        ControlFlow, DeviceEvents, EventLoopClosed,
+        EventLoopWindowTarget as RootEventLoopWindowTarget, PowerSource, PowerState, ProxyWakeup,
+        SystemPreferences,
     },
+    keyboard::KeyCode,
     monitor::MonitorHandle as RootMonitorHandle,
     platform::ios::Idiom,
 };
@@ -25,15 +35,16 @@ use crate::platform_impl::platform::{
         CFRunLoopActivity, CFRunLoopAddObserver, CFRunLoopAddSource, CFRunLoopGetMain,
         CFRunLoopObserverCreate, CFRunLoopObserverRef, CFRunLoopSourceContext,
         CFRunLoopSourceCreate, CFRunLoopSourceInvalidate, CFRunLoopSourceRef,
-        CFRunLoopSourceSignal, CFRunLoopWakeUp, NSStringRust, UIApplicationMain,
-        UIUserInterfaceIdiom,
+        CFRunLoopSourceSignal, CFRunLoopWakeUp, NSStringRust,
+        UIAccessibilityDarkerSystemColorsEnabled, UIAccessibilityIsReduceMotionEnabled,
+        UIApplicationMain, UIDeviceBatteryState, UIUserInterfaceIdiom,
     },
     monitor, view, MonitorHandle,
 };
 
 #[derive(Debug)]
 pub enum EventWrapper {
-    StaticEvent(Event<'static, Never>),
+    StaticEvent(Event<Never>),
     EventProxy(EventProxy),
 }
 
@@ -57,12 +68,86 @@ impl<T: 'static> EventLoopWindowTarget<T> {
         unsafe { monitor::uiscreens() }
     }
 
+    #[cfg(feature = "rwh_05")]
+    pub fn raw_display_handle_rwh_05(&self) -> rwh_05::RawDisplayHandle {
+        rwh_05::RawDisplayHandle::UiKit(rwh_05::UiKitDisplayHandle::empty())
+    }
+
+    #[cfg(feature = "rwh_06")]
+    pub fn raw_display_handle_rwh_06(
+        &self,
+    ) -> Result<rwh_06::RawDisplayHandle, rwh_06::HandleError> {
+        Ok(rwh_06::RawDisplayHandle::UiKit(
+            rwh_06::UiKitDisplayHandle::new(),
+        ))
+    }
+
     pub fn primary_monitor(&self) -> Option<RootMonitorHandle> {
         // guaranteed to be on main thread
         let monitor = unsafe { monitor::main_uiscreen() };
 
         Some(RootMonitorHandle { inner: monitor })
     }
+
+    pub fn devices(&self) -> Vec<DeviceHandle> {
+        Vec::new()
+    }
+
+    pub fn listen_device_events(&self, _allowed: DeviceEvents) {}
+
+    pub fn keyboard_layout(&self) -> Option<String> {
+        None
+    }
+
+    pub fn key_code_to_text(&self, _code: KeyCode, _modifiers: ModifiersState) -> Option<String> {
+        None
+    }
+
+    pub fn system_preferences(&self) -> SystemPreferences {
+        unsafe {
+            SystemPreferences {
+                accent_color: None,
+                high_contrast: UIAccessibilityDarkerSystemColorsEnabled() == YES,
+                reduced_motion: UIAccessibilityIsReduceMotionEnabled() == YES,
+            }
+        }
+    }
+
+    pub fn power_state(&self) -> PowerState {
+        power_state()
+    }
+
+    pub fn event_timestamp(&self) -> Option<Instant> {
+        None
+    }
+
+    pub fn run_nested<F>(&self, _event_handler: F) -> Result<i32, NotSupportedError>
+    where
+        F: FnMut(Event<T>, &mut ControlFlow),
+    {
+        Err(NotSupportedError::new())
+    }
+}
+
+pub(crate) fn power_state() -> PowerState {
+    unsafe {
+        let device: id = msg_send![class!(UIDevice), currentDevice];
+        // The battery state is always unknown while the monitoring is disabled.
+        let _: () = msg_send![device, setBatteryMonitoringEnabled: YES];
+        let battery_state: UIDeviceBatteryState = msg_send![device, batteryState];
+        let source = match battery_state {
+            UIDeviceBatteryState::Unplugged => PowerSource::Battery,
+            UIDeviceBatteryState::Charging | UIDeviceBatteryState::Full => PowerSource::Ac,
+            _ => PowerSource::Unknown,
+        };
+
+        let process_info: id = msg_send![class!(NSProcessInfo), processInfo];
+        let low_power_mode: BOOL = msg_send![process_info, isLowPowerModeEnabled];
+        PowerState {
+            source,
+            low_power_mode: low_power_mode == YES,
+        }
+    }
 }
 
 pub struct EventLoop<T: 'static> {
@@ -97,6 +182,10 @@ impl<T: 'static> EventLoop<T> {
                     receiver,
                     sender_to_clone,
                 },
+                coalescing: Default::default(),
+                exit_code: Default::default(),
+                event_timestamp: Default::default(),
+                dispatcher_settings: Default::default(),
                 _marker: PhantomData,
             },
         }
@@ -104,7 +193,7 @@ impl<T: 'static> EventLoop<T> {
 
     pub fn run<F>(self, event_handler: F) -> !
     where
-        F: 'static + FnMut(Event<'_, T>, &RootEventLoopWindowTarget<T>, &mut ControlFlow),
+        F: 'static + FnMut(Event<T>, &RootEventLoopWindowTarget<T>, &mut ControlFlow),
     {
         unsafe {
             let application: *mut c_void = msg_send![class!(UIApplication), sharedApplication];
@@ -202,6 +291,8 @@ impl<T> EventLoopProxy<T> {
         }
         Ok(())
     }
+
+    pub fn set_wakeup(&mut self, _wakeup: ProxyWakeup) {}
 }
 
 fn setup_control_flow_observers() {
@@ -302,7 +393,7 @@ fn setup_control_flow_observers() {
 pub enum Never {}
 
 pub trait EventHandler: Debug {
-    fn handle_nonuser_event(&mut self, event: Event<'_, Never>, control_flow: &mut ControlFlow);
+    fn handle_nonuser_event(&mut self, event: Event<Never>, control_flow: &mut ControlFlow);
     fn handle_user_events(&mut self, control_flow: &mut ControlFlow);
 }
 
@@ -321,21 +412,36 @@ impl<F, T: 'static> Debug for EventLoopHandler<F, T> {
 
 impl<F, T> EventHandler for EventLoopHandler<F, T>
 where
-    F: 'static + FnMut(Event<'_, T>, &RootEventLoopWindowTarget<T>, &mut ControlFlow),
+    F: 'static + FnMut(Event<T>, &RootEventLoopWindowTarget<T>, &mut ControlFlow),
     T: 'static,
 {
-    fn handle_nonuser_event(&mut self, event: Event<'_, Never>, control_flow: &mut ControlFlow) {
-        (self.f)(
-            event.map_nonuser_event().unwrap(),
-            &self.event_loop,
-            control_flow,
-        );
+    fn handle_nonuser_event(&mut self, event: Event<Never>, control_flow: &mut ControlFlow) {
+        abort_on_panic(|| {
+            (self.f)(
+                event.map_nonuser_event().unwrap(),
+                &self.event_loop,
+                control_flow,
+            )
+        });
     }
 
     fn handle_user_events(&mut self, control_flow: &mut ControlFlow) {
-        for event in self.event_loop.p.receiver.try_iter() {
-            (self.f)(Event::UserEvent(event), &self.event_loop, control_flow);
-        }
+        abort_on_panic(|| {
+            for event in self.event_loop.p.receiver.try_iter() {
+                (self.f)(Event::UserEvent(event), &self.event_loop, control_flow);
+            }
+        });
+    }
+}
+
+/// Runs the user callback, aborting the process if it panics.
+///
+/// The callback is always called from UIKit, so a panic can't unwind through the platform frames
+/// above it, and since `UIApplicationMain` never returns there is nowhere to resume it either. The
+/// panic hook has already reported the panic by the time this aborts.
+fn abort_on_panic<F: FnOnce()>(f: F) {
+    if catch_unwind(AssertUnwindSafe(f)).is_err() {
+        process::abort();
     }
 }
 
@@ -2,7 +2,10 @@
 
 use std::{convert::TryInto, ffi::CString, ops::BitOr, os::raw::*};
 
-use objc::{runtime::Object, Encode, Encoding};
+use objc::{
+    runtime::{Object, BOOL},
+    Encode, Encoding,
+};
 
 use crate::{
     dpi::LogicalSize,
@@ -237,6 +240,24 @@ impl Into<ScreenEdge> for UIRectEdge {
     }
 }
 
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UIDeviceBatteryState(NSInteger);
+
+unsafe impl Encode for UIDeviceBatteryState {
+    fn encode() -> Encoding {
+        NSInteger::encode()
+    }
+}
+
+#[allow(dead_code)]
+impl UIDeviceBatteryState {
+    pub const Unknown: UIDeviceBatteryState = UIDeviceBatteryState(0);
+    pub const Unplugged: UIDeviceBatteryState = UIDeviceBatteryState(1);
+    pub const Charging: UIDeviceBatteryState = UIDeviceBatteryState(2);
+    pub const Full: UIDeviceBatteryState = UIDeviceBatteryState(3);
+}
+
 #[repr(transparent)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct UIScreenOverscanCompensation(NSInteger);
@@ -260,6 +281,14 @@ extern "C" {
     pub static kCFRunLoopDefaultMode: CFRunLoopMode;
     pub static kCFRunLoopCommonModes: CFRunLoopMode;
 
+    pub static UIAccessibilityDarkerSystemColorsStatusDidChangeNotification: id;
+    pub static UIAccessibilityReduceMotionStatusDidChangeNotification: id;
+    pub static UIDeviceBatteryStateDidChangeNotification: id;
+    pub static NSProcessInfoPowerStateDidChangeNotification: id;
+
+    pub fn UIAccessibilityDarkerSystemColorsEnabled() -> BOOL;
+    pub fn UIAccessibilityIsReduceMotionEnabled() -> BOOL;
+
     pub fn UIApplicationMain(
         argc: c_int,
         argv: *const c_char,
@@ -104,6 +104,9 @@ impl DeviceId {
 unsafe impl Send for DeviceId {}
 unsafe impl Sync for DeviceId {}
 
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct KeyEventExtra;
+
 #[derive(Debug)]
 pub enum OsError {}
 
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{cell::Cell, collections::HashMap};
 
 use objc::{
     declare::ClassDecl,
@@ -8,13 +8,17 @@ use objc::{
 use crate::{
     dpi::PhysicalPosition,
     event::{DeviceId as RootDeviceId, Event, Force, Touch, TouchPhase, WindowEvent},
+    event_loop::PowerState,
     platform::ios::MonitorHandleExtIOS,
     platform_impl::platform::{
         app_state::{self, OSCapabilities},
         event_loop::{self, EventProxy, EventWrapper},
         ffi::{
-            id, nil, CGFloat, CGPoint, CGRect, UIForceTouchCapability, UIInterfaceOrientationMask,
-            UIRectEdge, UITouchPhase, UITouchType,
+            id, nil, CGFloat, CGPoint, CGRect, NSProcessInfoPowerStateDidChangeNotification,
+            UIAccessibilityDarkerSystemColorsStatusDidChangeNotification,
+            UIAccessibilityReduceMotionStatusDidChangeNotification,
+            UIDeviceBatteryStateDidChangeNotification, UIForceTouchCapability,
+            UIInterfaceOrientationMask, UIRectEdge, UITouchPhase, UITouchType,
         },
         window::PlatformSpecificWindowBuilderAttributes,
         DeviceId,
@@ -150,6 +154,22 @@ unsafe fn get_view_class(root_view_class: &'static Class) -> &'static Class {
             }
         }
 
+        extern "C" fn safe_area_insets_did_change(object: &Object, _: Sel) {
+            unsafe {
+                let superclass: &'static Class = msg_send![object, superclass];
+                let () = msg_send![super(object, superclass), safeAreaInsetsDidChange];
+
+                let window: id = msg_send![object, window];
+                if window.is_null() {
+                    return;
+                }
+                app_state::handle_nonuser_event(EventWrapper::StaticEvent(Event::WindowEvent {
+                    window_id: RootWindowId(window.into()),
+                    event: WindowEvent::InsetsChanged,
+                }));
+            }
+        }
+
         extern "C" fn set_content_scale_factor(
             object: &mut Object,
             _: Sel,
@@ -291,6 +311,12 @@ unsafe fn get_view_class(root_view_class: &'static Class) -> &'static Class {
             sel!(layoutSubviews),
             layout_subviews as extern "C" fn(&Object, Sel),
         );
+        if app_state::os_capabilities().safe_area {
+            decl.add_method(
+                sel!(safeAreaInsetsDidChange),
+                safe_area_insets_did_change as extern "C" fn(&Object, Sel),
+            );
+        }
         decl.add_method(
             sel!(setContentScaleFactor:),
             set_content_scale_factor as extern "C" fn(&mut Object, Sel, CGFloat),
@@ -544,14 +570,75 @@ pub unsafe fn create_window(
     window
 }
 
+thread_local! {
+    /// Last state reported through `Event::PowerStateChanged`.
+    static POWER_STATE: Cell<PowerState> = Cell::new(PowerState::default());
+}
+
 pub fn create_delegate_class() {
-    extern "C" fn did_finish_launching(_: &mut Object, _: Sel, _: id, _: id) -> BOOL {
+    extern "C" fn did_finish_launching(this: &mut Object, _: Sel, _: id, _: id) -> BOOL {
         unsafe {
+            let center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+            for name in [
+                UIAccessibilityDarkerSystemColorsStatusDidChangeNotification,
+                UIAccessibilityReduceMotionStatusDidChangeNotification,
+            ] {
+                let _: () = msg_send![
+                    center,
+                    addObserver: &*this
+                    selector: sel!(accessibilityPreferencesChanged:)
+                    name: name
+                    object: nil
+                ];
+            }
+            POWER_STATE.with(|state| state.set(event_loop::power_state()));
+            for name in [
+                UIDeviceBatteryStateDidChangeNotification,
+                NSProcessInfoPowerStateDidChangeNotification,
+            ] {
+                let _: () = msg_send![
+                    center,
+                    addObserver: &*this
+                    selector: sel!(powerStateChanged:)
+                    name: name
+                    object: nil
+                ];
+            }
             app_state::did_finish_launching();
         }
         YES
     }
 
+    extern "C" fn accessibility_preferences_changed(_: &Object, _: Sel, _: id) {
+        unsafe {
+            app_state::handle_nonuser_event(EventWrapper::StaticEvent(
+                Event::SystemPreferencesChanged,
+            ))
+        }
+    }
+
+    extern "C" fn power_state_changed(this: &Object, _: Sel, _: id) {
+        unsafe {
+            // The low power mode notification is posted on the thread which changed it.
+            let is_main_thread: BOOL = msg_send!(class!(NSThread), isMainThread);
+            if is_main_thread == NO {
+                let _: () = msg_send![
+                    this,
+                    performSelectorOnMainThread: sel!(powerStateChanged:)
+                    withObject: nil
+                    waitUntilDone: NO
+                ];
+                return;
+            }
+
+            // Going from charging to full is notified as well.
+            let state = event_loop::power_state();
+            if POWER_STATE.with(|last| last.replace(state)) != state {
+                app_state::handle_nonuser_event(EventWrapper::StaticEvent(Event::PowerStateChanged))
+            }
+        }
+    }
+
     extern "C" fn did_become_active(_: &Object, _: Sel, _: id) {
         unsafe { app_state::handle_nonuser_event(EventWrapper::StaticEvent(Event::Resumed)) }
     }
@@ -563,6 +650,10 @@ pub fn create_delegate_class() {
     extern "C" fn will_enter_foreground(_: &Object, _: Sel, _: id) {}
     extern "C" fn did_enter_background(_: &Object, _: Sel, _: id) {}
 
+    extern "C" fn did_receive_memory_warning(_: &Object, _: Sel, _: id) {
+        unsafe { app_state::handle_nonuser_event(EventWrapper::StaticEvent(Event::MemoryWarning)) }
+    }
+
     extern "C" fn will_terminate(_: &Object, _: Sel, _: id) {
         unsafe {
             let app: id = msg_send![class!(UIApplication), sharedApplication];
@@ -597,6 +688,15 @@ pub fn create_delegate_class() {
             did_finish_launching as extern "C" fn(&mut Object, Sel, id, id) -> BOOL,
         );
 
+        decl.add_method(
+            sel!(accessibilityPreferencesChanged:),
+            accessibility_preferences_changed as extern "C" fn(&Object, Sel, id),
+        );
+        decl.add_method(
+            sel!(powerStateChanged:),
+            power_state_changed as extern "C" fn(&Object, Sel, id),
+        );
+
         decl.add_method(
             sel!(applicationDidBecomeActive:),
             did_become_active as extern "C" fn(&Object, Sel, id),
@@ -613,6 +713,10 @@ pub fn create_delegate_class() {
             sel!(applicationDidEnterBackground:),
             did_enter_background as extern "C" fn(&Object, Sel, id),
         );
+        decl.add_method(
+            sel!(applicationDidReceiveMemoryWarning:),
+            did_receive_memory_warning as extern "C" fn(&Object, Sel, id),
+        );
 
         decl.add_method(
             sel!(applicationWillTerminate:),
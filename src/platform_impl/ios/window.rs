@@ -12,7 +12,7 @@ use crate::{
     event::{Event, WindowEvent},
     icon::Icon,
     monitor::MonitorHandle as RootMonitorHandle,
-    platform::ios::{MonitorHandleExtIOS, ScreenEdge, ValidOrientations},
+    platform::ios::{MonitorHandleExtIOS, SafeAreaInsets, ScreenEdge, ValidOrientations},
     platform_impl::platform::{
         app_state,
         event_loop::{self, EventProxy, EventWrapper},
@@ -192,6 +192,10 @@ impl Inner {
         debug!("`Window::set_cursor_visible` is ignored on iOS")
     }
 
+    pub fn set_key_repeat_enabled(&self, _enabled: bool) {
+        debug!("`Window::set_key_repeat_enabled` is ignored on iOS")
+    }
+
     pub fn drag_window(&self) -> Result<(), ExternalError> {
         Err(ExternalError::NotSupported(NotSupportedError::new()))
     }
@@ -318,6 +322,12 @@ impl Inner {
         Some(RootMonitorHandle { inner: monitor })
     }
 
+    #[cfg(feature = "accesskit")]
+    pub fn enable_accessibility(&self, _source: Box<dyn crate::accessibility::TreeSource>) {}
+
+    #[cfg(feature = "accesskit")]
+    pub fn update_accessibility_tree(&self, _update: impl FnOnce() -> accesskit::TreeUpdate) {}
+
     pub fn id(&self) -> WindowId {
         self.window.into()
     }
@@ -329,6 +339,38 @@ impl Inner {
         handle.ui_view_controller = self.view_controller as _;
         RawWindowHandle::UiKit(handle)
     }
+
+    #[cfg(feature = "rwh_05")]
+    pub fn raw_window_handle_rwh_05(&self) -> rwh_05::RawWindowHandle {
+        let mut handle = rwh_05::UiKitWindowHandle::empty();
+        handle.ui_window = self.window as _;
+        handle.ui_view = self.view as _;
+        handle.ui_view_controller = self.view_controller as _;
+        rwh_05::RawWindowHandle::UiKit(handle)
+    }
+
+    #[cfg(feature = "rwh_05")]
+    pub fn raw_display_handle_rwh_05(&self) -> rwh_05::RawDisplayHandle {
+        rwh_05::RawDisplayHandle::UiKit(rwh_05::UiKitDisplayHandle::empty())
+    }
+
+    #[cfg(feature = "rwh_06")]
+    pub fn raw_window_handle_rwh_06(&self) -> Result<rwh_06::RawWindowHandle, rwh_06::HandleError> {
+        let ui_view =
+            std::ptr::NonNull::new(self.view as _).ok_or(rwh_06::HandleError::Unavailable)?;
+        let mut handle = rwh_06::UiKitWindowHandle::new(ui_view);
+        handle.ui_view_controller = std::ptr::NonNull::new(self.view_controller as _);
+        Ok(rwh_06::RawWindowHandle::UiKit(handle))
+    }
+
+    #[cfg(feature = "rwh_06")]
+    pub fn raw_display_handle_rwh_06(
+        &self,
+    ) -> Result<rwh_06::RawDisplayHandle, rwh_06::HandleError> {
+        Ok(rwh_06::RawDisplayHandle::UiKit(
+            rwh_06::UiKitDisplayHandle::new(),
+        ))
+    }
 }
 
 pub struct Window {
@@ -541,6 +583,27 @@ impl Inner {
             ];
         }
     }
+
+    pub fn safe_area_insets(&self) -> SafeAreaInsets {
+        unsafe {
+            if app_state::os_capabilities().safe_area {
+                let safe_area: UIEdgeInsets = msg_send![self.window, safeAreaInsets];
+                SafeAreaInsets {
+                    top: safe_area.top as f64,
+                    left: safe_area.left as f64,
+                    bottom: safe_area.bottom as f64,
+                    right: safe_area.right as f64,
+                }
+            } else {
+                let app: id = msg_send![class!(UIApplication), sharedApplication];
+                let status_bar_frame: CGRect = msg_send![app, statusBarFrame];
+                SafeAreaInsets {
+                    top: status_bar_frame.size.height as f64,
+                    ..Default::default()
+                }
+            }
+        }
+    }
 }
 
 impl Inner {
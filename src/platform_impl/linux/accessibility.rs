@@ -0,0 +1,91 @@
+//! Accessibility of a window, see [`Window::enable_accessibility`].
+//!
+//! The adapter serves the tree over AT-SPI from its own thread, and forwards the actions to the
+//! event loop through a sink. The X11 and Wayland backends only differ in how the sink wakes up
+//! their event loop, and in that the bounds of the window are unknown on Wayland.
+//!
+//! [`Window::enable_accessibility`]: crate::window::Window::enable_accessibility
+
+use std::sync::{Arc, Mutex};
+
+use accesskit::{ActionHandler, ActionRequest, DeactivationHandler, Rect, TreeUpdate};
+use accesskit_unix::Adapter;
+
+use crate::{
+    accessibility::{SourceHandler, TreeSource},
+    platform_impl::WindowId,
+};
+
+/// Receives the actions requested on a window, from the thread of the adapter.
+pub type ActionSink = Arc<dyn Fn(WindowId, ActionRequest) + Send + Sync>;
+
+/// The adapter of a window, along with the state it's created with.
+#[derive(Default)]
+pub struct Accessibility {
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    adapter: Option<Adapter>,
+    focused: bool,
+    bounds: Option<(Rect, Rect)>,
+}
+
+impl Accessibility {
+    pub fn enable(&self, window_id: WindowId, source: Box<dyn TreeSource>, sink: ActionSink) {
+        let mut adapter = Adapter::new(
+            SourceHandler(source),
+            ActionForwarder { window_id, sink },
+            Deactivation,
+        );
+        let mut state = self.state.lock().unwrap();
+        adapter.update_window_focus_state(state.focused);
+        if let Some((outer, inner)) = state.bounds {
+            adapter.set_root_window_bounds(outer, inner);
+        }
+        state.adapter = Some(adapter);
+    }
+
+    pub fn update_tree(&self, update: impl FnOnce() -> TreeUpdate) {
+        if let Some(adapter) = self.state.lock().unwrap().adapter.as_mut() {
+            adapter.update_if_active(update);
+        }
+    }
+
+    pub fn set_focused(&self, focused: bool) {
+        let mut state = self.state.lock().unwrap();
+        state.focused = focused;
+        if let Some(adapter) = state.adapter.as_mut() {
+            adapter.update_window_focus_state(focused);
+        }
+    }
+
+    /// Sets the bounds of the window in screen coordinates, with and without the decorations.
+    #[cfg(feature = "x11")]
+    pub fn set_bounds(&self, outer: Rect, inner: Rect) {
+        let mut state = self.state.lock().unwrap();
+        state.bounds = Some((outer, inner));
+        if let Some(adapter) = state.adapter.as_mut() {
+            adapter.set_root_window_bounds(outer, inner);
+        }
+    }
+}
+
+struct ActionForwarder {
+    window_id: WindowId,
+    sink: ActionSink,
+}
+
+impl ActionHandler for ActionForwarder {
+    fn do_action(&mut self, request: ActionRequest) {
+        (self.sink)(self.window_id, request);
+    }
+}
+
+/// The tree is requested again from the source when assistive technologies come back.
+struct Deactivation;
+
+impl DeactivationHandler for Deactivation {
+    fn deactivate_accessibility(&mut self) {}
+}
@@ -0,0 +1,75 @@
+//! Threads watching the system in the background, such as the gamepads or the power state.
+//!
+//! Each [`Monitor`] runs on a thread of its own, and hands what it observes to a sink. The threads
+//! are shared by the X11 and Wayland backends, which only differ in how the sink wakes up their
+//! event loop.
+
+use std::io;
+use std::os::unix::io::RawFd;
+use std::thread::{self, JoinHandle};
+
+/// Receives what a monitor observes, from its thread.
+pub type Sink<E> = Box<dyn FnMut(E) + Send>;
+
+/// Something watched from a background thread, which stops when the monitor is dropped.
+pub trait Monitor: Sized {
+    type Event: Send + 'static;
+
+    /// What is monitored, for the logs.
+    const NAME: &'static str;
+
+    fn spawn(sink: Sink<Self::Event>) -> io::Result<Self>;
+}
+
+/// Starts a monitor, or logs why it couldn't be started.
+pub fn spawn<M: Monitor>(sink: impl FnMut(M::Event) + Send + 'static) -> Option<M> {
+    M::spawn(Box::new(sink))
+        .map_err(|err| warn!("Failed to start monitoring {}: {}", M::NAME, err))
+        .ok()
+}
+
+/// A thread which is stopped and joined when dropped.
+///
+/// The thread is handed a file descriptor to poll along with its own sources, which becomes
+/// readable once it should stop.
+pub struct StoppableThread {
+    stop_fd: RawFd,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl StoppableThread {
+    pub fn spawn(name: &str, run: impl FnOnce(RawFd) + Send + 'static) -> io::Result<Self> {
+        // Closing the write end wakes up the thread, as the read end then polls as hung up.
+        let mut fds = [0; 2];
+        if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let [read_fd, stop_fd] = fds;
+
+        match thread::Builder::new().name(name.into()).spawn(move || {
+            run(read_fd);
+            unsafe { libc::close(read_fd) };
+        }) {
+            Ok(thread) => Ok(Self {
+                stop_fd,
+                thread: Some(thread),
+            }),
+            Err(err) => {
+                unsafe {
+                    libc::close(read_fd);
+                    libc::close(stop_fd);
+                }
+                Err(err)
+            }
+        }
+    }
+}
+
+impl Drop for StoppableThread {
+    fn drop(&mut self) {
+        unsafe { libc::close(self.stop_fd) };
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
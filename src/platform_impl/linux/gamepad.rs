@@ -0,0 +1,427 @@
+//! Gamepad support backed by `evdev`.
+//!
+//! A background thread watches `/dev/input` for `event*` nodes, opens the ones that look like
+//! gamepads and forwards their input to the event loop through a sink.
+
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read};
+use std::mem;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::OpenOptionsExt;
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::path::{Path, PathBuf};
+
+use super::background::{Monitor, Sink, StoppableThread};
+use crate::event::ElementState;
+use crate::gamepad::{GamepadAxis, GamepadButton, GamepadEvent, GamepadId};
+
+const INPUT_DIR: &str = "/dev/input";
+
+const EV_KEY: u16 = 0x01;
+const EV_ABS: u16 = 0x03;
+const KEY_CNT: usize = 0x300;
+const ABS_CNT: usize = 0x40;
+
+const BTN_JOYSTICK: u16 = 0x120;
+const BTN_SOUTH: u16 = 0x130;
+const BTN_EAST: u16 = 0x131;
+const BTN_NORTH: u16 = 0x133;
+const BTN_WEST: u16 = 0x134;
+const BTN_TL: u16 = 0x136;
+const BTN_TR: u16 = 0x137;
+const BTN_TL2: u16 = 0x138;
+const BTN_TR2: u16 = 0x139;
+const BTN_SELECT: u16 = 0x13a;
+const BTN_START: u16 = 0x13b;
+const BTN_MODE: u16 = 0x13c;
+const BTN_THUMBL: u16 = 0x13d;
+const BTN_THUMBR: u16 = 0x13e;
+const BTN_DPAD_UP: u16 = 0x220;
+const BTN_DPAD_DOWN: u16 = 0x221;
+const BTN_DPAD_LEFT: u16 = 0x222;
+const BTN_DPAD_RIGHT: u16 = 0x223;
+
+const ABS_X: u16 = 0x00;
+const ABS_Y: u16 = 0x01;
+const ABS_Z: u16 = 0x02;
+const ABS_RX: u16 = 0x03;
+const ABS_RY: u16 = 0x04;
+const ABS_RZ: u16 = 0x05;
+const ABS_HAT0X: u16 = 0x10;
+const ABS_HAT0Y: u16 = 0x11;
+
+const fn ioc_read(nr: u8, size: usize) -> libc::c_ulong {
+    (2 << 30)
+        | ((size as libc::c_ulong) << 16)
+        | ((b'E' as libc::c_ulong) << 8)
+        | nr as libc::c_ulong
+}
+
+/// `EVIOCGNAME(len)`
+const fn eviocgname(len: usize) -> libc::c_ulong {
+    ioc_read(0x06, len)
+}
+
+/// `EVIOCGBIT(ev, len)`
+const fn eviocgbit(ev: u16, len: usize) -> libc::c_ulong {
+    ioc_read(0x20 + ev as u8, len)
+}
+
+/// `EVIOCGABS(abs)`
+const fn eviocgabs(abs: u16) -> libc::c_ulong {
+    ioc_read(0x40 + abs as u8, mem::size_of::<libc::input_absinfo>())
+}
+
+/// Owns the thread monitoring gamepads, and stops it when dropped.
+pub struct GamepadMonitor {
+    _thread: StoppableThread,
+}
+
+impl Monitor for GamepadMonitor {
+    type Event = (GamepadId, GamepadEvent);
+
+    const NAME: &'static str = "gamepads";
+
+    fn spawn(sink: Sink<Self::Event>) -> io::Result<Self> {
+        let inotify = unsafe { libc::inotify_init1(libc::IN_CLOEXEC | libc::IN_NONBLOCK) };
+        if inotify < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // udev creates the node before fixing up its permissions, so watch for both.
+        let mask = libc::IN_CREATE | libc::IN_ATTRIB | libc::IN_DELETE;
+        let path = format!("{}\0", INPUT_DIR);
+        if unsafe { libc::inotify_add_watch(inotify, path.as_ptr() as *const _, mask) } < 0 {
+            warn!(
+                "Failed to watch {} for gamepads: {}",
+                INPUT_DIR,
+                io::Error::last_os_error()
+            );
+        }
+
+        let watcher = Watcher {
+            sink,
+            inotify,
+            gamepads: HashMap::new(),
+        };
+        let thread = StoppableThread::spawn("winit gamepad", move |stop_fd| watcher.run(stop_fd))?;
+        Ok(Self { _thread: thread })
+    }
+}
+
+struct Watcher {
+    sink: Sink<(GamepadId, GamepadEvent)>,
+    inotify: RawFd,
+    gamepads: HashMap<PathBuf, Gamepad>,
+}
+
+impl Watcher {
+    fn run(mut self, stop_fd: RawFd) {
+        if let Ok(entries) = fs::read_dir(INPUT_DIR) {
+            for entry in entries.flatten() {
+                self.try_open(&entry.path());
+            }
+        }
+
+        loop {
+            let mut fds = vec![
+                libc::pollfd {
+                    fd: stop_fd,
+                    events: libc::POLLIN,
+                    revents: 0,
+                },
+                libc::pollfd {
+                    fd: self.inotify,
+                    events: libc::POLLIN,
+                    revents: 0,
+                },
+            ];
+            let paths: Vec<PathBuf> = self.gamepads.keys().cloned().collect();
+            fds.extend(paths.iter().map(|path| libc::pollfd {
+                fd: self.gamepads[path].file.as_raw_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            }));
+
+            let result = unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as _, -1) };
+            if result < 0 {
+                let err = io::Error::last_os_error();
+                if err.raw_os_error() == Some(libc::EINTR) {
+                    continue;
+                }
+                warn!("Failed to poll gamepads: {}", err);
+                return;
+            }
+
+            if fds[0].revents != 0 {
+                return;
+            }
+            if fds[1].revents != 0 {
+                self.handle_inotify();
+            }
+            for (path, pollfd) in paths.iter().zip(&fds[2..]) {
+                if pollfd.revents != 0 {
+                    self.handle_input(path);
+                }
+            }
+        }
+    }
+
+    fn try_open(&mut self, path: &Path) {
+        let is_event_node = path
+            .file_name()
+            .map_or(false, |name| name.as_bytes().starts_with(b"event"));
+        if !is_event_node || self.gamepads.contains_key(path) {
+            return;
+        }
+
+        if let Some(gamepad) = Gamepad::open(path) {
+            let id = gamepad.id;
+            let name = gamepad.name.clone();
+            self.gamepads.insert(path.to_owned(), gamepad);
+            (self.sink)((id, GamepadEvent::Connected { name }));
+        }
+    }
+
+    fn remove(&mut self, path: &Path) {
+        if let Some(gamepad) = self.gamepads.remove(path) {
+            (self.sink)((gamepad.id, GamepadEvent::Disconnected));
+        }
+    }
+
+    fn handle_inotify(&mut self) {
+        let mut buffer = [0u8; 4096];
+        loop {
+            let len =
+                unsafe { libc::read(self.inotify, buffer.as_mut_ptr() as *mut _, buffer.len()) };
+            if len <= 0 {
+                return;
+            }
+
+            let mut offset = 0;
+            while offset + mem::size_of::<libc::inotify_event>() <= len as usize {
+                let event = unsafe {
+                    (buffer.as_ptr().add(offset) as *const libc::inotify_event).read_unaligned()
+                };
+                let name_start = offset + mem::size_of::<libc::inotify_event>();
+                let name_bytes = &buffer[name_start..name_start + event.len as usize];
+                offset = name_start + event.len as usize;
+
+                let path = Path::new(INPUT_DIR).join(OsStr::from_bytes(until_nul(name_bytes)));
+                if event.mask & libc::IN_DELETE != 0 {
+                    self.remove(&path);
+                } else {
+                    self.try_open(&path);
+                }
+            }
+        }
+    }
+
+    fn handle_input(&mut self, path: &Path) {
+        let gamepad = match self.gamepads.get_mut(path) {
+            Some(gamepad) => gamepad,
+            None => return,
+        };
+
+        let mut events = Vec::new();
+        let alive = gamepad.read_events(&mut events);
+        let id = gamepad.id;
+        for event in events {
+            (self.sink)((id, event));
+        }
+
+        if !alive {
+            self.remove(path);
+        }
+    }
+}
+
+impl Drop for Watcher {
+    fn drop(&mut self) {
+        unsafe { libc::close(self.inotify) };
+    }
+}
+
+struct Gamepad {
+    id: GamepadId,
+    name: String,
+    file: File,
+    axes: HashMap<u16, libc::input_absinfo>,
+    hat: (i32, i32),
+}
+
+impl Gamepad {
+    fn open(path: &Path) -> Option<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .custom_flags(libc::O_NONBLOCK | libc::O_CLOEXEC)
+            .open(path)
+            .ok()?;
+        let fd = file.as_raw_fd();
+
+        let mut keys = [0u8; KEY_CNT / 8];
+        if unsafe { libc::ioctl(fd, eviocgbit(EV_KEY, keys.len()), keys.as_mut_ptr()) } < 0 {
+            return None;
+        }
+        let has_key = |code: u16| keys[code as usize / 8] & (1 << (code % 8)) != 0;
+        if !has_key(BTN_SOUTH) && !has_key(BTN_JOYSTICK) {
+            return None;
+        }
+
+        let mut name = [0u8; 256];
+        let name = if unsafe { libc::ioctl(fd, eviocgname(name.len()), name.as_mut_ptr()) } >= 0 {
+            String::from_utf8_lossy(until_nul(&name)).into_owned()
+        } else {
+            String::new()
+        };
+
+        let mut abs = [0u8; ABS_CNT / 8];
+        let mut axes = HashMap::new();
+        if unsafe { libc::ioctl(fd, eviocgbit(EV_ABS, abs.len()), abs.as_mut_ptr()) } >= 0 {
+            for code in 0..ABS_CNT as u16 {
+                if abs[code as usize / 8] & (1 << (code % 8)) == 0 {
+                    continue;
+                }
+                let mut info: libc::input_absinfo = unsafe { mem::zeroed() };
+                if unsafe { libc::ioctl(fd, eviocgabs(code), &mut info) } >= 0 {
+                    axes.insert(code, info);
+                }
+            }
+        }
+
+        Some(Self {
+            id: GamepadId::next(),
+            name,
+            file,
+            axes,
+            hat: (0, 0),
+        })
+    }
+
+    /// Reads the pending input, returning `false` once the device is gone.
+    fn read_events(&mut self, events: &mut Vec<GamepadEvent>) -> bool {
+        const EVENT_SIZE: usize = mem::size_of::<libc::input_event>();
+        let mut buffer = [0u8; EVENT_SIZE * 64];
+        loop {
+            let len = match self.file.read(&mut buffer) {
+                Ok(len) => len,
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => return true,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(_) => return false,
+            };
+            if len == 0 {
+                return false;
+            }
+
+            for chunk in buffer[..len].chunks_exact(EVENT_SIZE) {
+                let event =
+                    unsafe { (chunk.as_ptr() as *const libc::input_event).read_unaligned() };
+                match event.type_ {
+                    // Skip the autorepeat of held buttons.
+                    EV_KEY if event.value == 2 => (),
+                    EV_KEY => events.push(GamepadEvent::Button {
+                        button: button(event.code),
+                        state: if event.value == 0 {
+                            ElementState::Released
+                        } else {
+                            ElementState::Pressed
+                        },
+                    }),
+                    EV_ABS => self.handle_axis(event.code, event.value, events),
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    fn handle_axis(&mut self, code: u16, value: i32, events: &mut Vec<GamepadEvent>) {
+        // Most gamepads report their d-pad as a hat, translate it to buttons.
+        if code == ABS_HAT0X || code == ABS_HAT0Y {
+            let (previous, negative, positive) = if code == ABS_HAT0X {
+                (
+                    &mut self.hat.0,
+                    GamepadButton::DPadLeft,
+                    GamepadButton::DPadRight,
+                )
+            } else {
+                (
+                    &mut self.hat.1,
+                    GamepadButton::DPadUp,
+                    GamepadButton::DPadDown,
+                )
+            };
+            let value = value.signum();
+            if *previous == value {
+                return;
+            }
+            for (direction, button) in [(-1, negative), (1, positive)] {
+                if *previous == direction {
+                    events.push(GamepadEvent::Button {
+                        button,
+                        state: ElementState::Released,
+                    });
+                }
+                if value == direction {
+                    events.push(GamepadEvent::Button {
+                        button,
+                        state: ElementState::Pressed,
+                    });
+                }
+            }
+            *previous = value;
+            return;
+        }
+
+        let axis = match code {
+            ABS_X => GamepadAxis::LeftStickX,
+            ABS_Y => GamepadAxis::LeftStickY,
+            ABS_RX => GamepadAxis::RightStickX,
+            ABS_RY => GamepadAxis::RightStickY,
+            ABS_Z => GamepadAxis::LeftTrigger,
+            ABS_RZ => GamepadAxis::RightTrigger,
+            code => GamepadAxis::Other(code),
+        };
+
+        let (minimum, maximum) = match self.axes.get(&code) {
+            Some(info) if info.maximum > info.minimum => (info.minimum, info.maximum),
+            _ => return,
+        };
+        let normalized = (value - minimum) as f64 / (maximum - minimum) as f64;
+        let value = match axis {
+            GamepadAxis::LeftTrigger | GamepadAxis::RightTrigger => normalized,
+            // evdev points the vertical axes down.
+            GamepadAxis::LeftStickY | GamepadAxis::RightStickY => 1.0 - normalized * 2.0,
+            _ => normalized * 2.0 - 1.0,
+        };
+        events.push(GamepadEvent::Axis { axis, value });
+    }
+}
+
+fn button(code: u16) -> GamepadButton {
+    match code {
+        BTN_SOUTH => GamepadButton::South,
+        BTN_EAST => GamepadButton::East,
+        BTN_NORTH => GamepadButton::North,
+        BTN_WEST => GamepadButton::West,
+        BTN_TL => GamepadButton::LeftShoulder,
+        BTN_TR => GamepadButton::RightShoulder,
+        BTN_TL2 => GamepadButton::LeftTrigger,
+        BTN_TR2 => GamepadButton::RightTrigger,
+        BTN_SELECT => GamepadButton::Select,
+        BTN_START => GamepadButton::Start,
+        BTN_MODE => GamepadButton::Mode,
+        BTN_THUMBL => GamepadButton::LeftThumb,
+        BTN_THUMBR => GamepadButton::RightThumb,
+        BTN_DPAD_UP => GamepadButton::DPadUp,
+        BTN_DPAD_DOWN => GamepadButton::DPadDown,
+        BTN_DPAD_LEFT => GamepadButton::DPadLeft,
+        BTN_DPAD_RIGHT => GamepadButton::DPadRight,
+        code => GamepadButton::Other(code),
+    }
+}
+
+fn until_nul(bytes: &[u8]) -> &[u8] {
+    let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    &bytes[..len]
+}
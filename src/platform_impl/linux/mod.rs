@@ -11,9 +11,9 @@ compile_error!("Please select a feature to build for unix: `x11`, `wayland`");
 
 #[cfg(feature = "wayland")]
 use std::error::Error;
-use std::{collections::VecDeque, env, fmt};
+use std::{collections::VecDeque, env, fmt, sync::Arc, time::Instant};
 #[cfg(feature = "x11")]
-use std::{ffi::CStr, mem::MaybeUninit, os::raw::*, sync::Arc};
+use std::{ffi::CStr, mem::MaybeUninit, os::raw::*};
 
 #[cfg(feature = "x11")]
 use parking_lot::Mutex;
@@ -22,19 +22,45 @@ use raw_window_handle::RawWindowHandle;
 #[cfg(feature = "x11")]
 pub use self::x11::XNotSupported;
 #[cfg(feature = "x11")]
-use self::x11::{ffi::XVisualInfo, util::WindowType as XWindowType, XConnection, XError};
+use self::x11::{
+    ffi::XVisualInfo,
+    util::{WindowState as XWindowState, WindowType as XWindowType},
+    XConnection, XError,
+};
+#[cfg(feature = "wayland")]
+use crate::platform::unix::{KeyboardInteractivity, Layer, LayerAnchor};
+#[cfg(feature = "wayland")]
+use crate::window::{ActivationToken, DecorationMode};
 use crate::{
+    device::DeviceHandle,
     dpi::{PhysicalPosition, PhysicalSize, Position, Size},
     error::{ExternalError, NotSupportedError, OsError as RootOsError},
-    event::Event,
-    event_loop::{ControlFlow, EventLoopClosed, EventLoopWindowTarget as RootELW},
+    event::{Event, ModifiersState},
+    event_loop::{
+        ControlFlow, DeviceEvents, EventLoopClosed, EventLoopWindowTarget as RootELW, PowerState,
+        ProxyWakeup, SystemPreferences,
+    },
     icon::Icon,
+    keyboard::{Key, KeyCode, NativeKey, SmolStr},
     monitor::{MonitorHandle as RootMonitorHandle, VideoMode as RootVideoMode},
     window::{CursorIcon, Fullscreen, UserAttentionType, WindowAttributes},
 };
 
 pub(crate) use crate::icon::RgbaIcon as PlatformIcon;
 
+#[cfg(feature = "accesskit")]
+mod accessibility;
+mod background;
+#[cfg(all(target_os = "linux", feature = "gamepad"))]
+mod gamepad;
+mod keymap;
+#[cfg(target_os = "linux")]
+mod power;
+#[cfg(feature = "dbus")]
+mod session_lock;
+#[cfg(target_os = "linux")]
+mod timer;
+mod user_queue;
 #[cfg(feature = "wayland")]
 pub mod wayland;
 #[cfg(feature = "x11")]
@@ -61,6 +87,8 @@ pub(crate) enum Backend {
 pub(crate) struct PlatformSpecificEventLoopAttributes {
     pub(crate) forced_backend: Option<Backend>,
     pub(crate) any_thread: bool,
+    #[cfg(feature = "x11")]
+    pub(crate) x11_display: Option<*mut x11::ffi::Display>,
 }
 
 impl Default for PlatformSpecificEventLoopAttributes {
@@ -68,6 +96,8 @@ impl Default for PlatformSpecificEventLoopAttributes {
         Self {
             forced_backend: None,
             any_thread: false,
+            #[cfg(feature = "x11")]
+            x11_display: None,
         }
     }
 }
@@ -87,11 +117,29 @@ pub struct PlatformSpecificWindowBuilderAttributes {
     #[cfg(feature = "x11")]
     pub override_redirect: bool,
     #[cfg(feature = "x11")]
+    pub x11_parent: Option<x11::ffi::Window>,
+    #[cfg(feature = "x11")]
+    pub x11_embed_parent: Option<x11::ffi::Window>,
+    #[cfg(feature = "x11")]
     pub x11_window_types: Vec<XWindowType>,
     #[cfg(feature = "x11")]
+    pub x11_window_state: XWindowState,
+    #[cfg(feature = "x11")]
     pub gtk_theme_variant: Option<String>,
     #[cfg(feature = "wayland")]
     pub app_id: Option<String>,
+    #[cfg(feature = "wayland")]
+    pub layer: Option<Layer>,
+    #[cfg(feature = "wayland")]
+    pub layer_anchor: LayerAnchor,
+    #[cfg(feature = "wayland")]
+    pub layer_exclusive_zone: i32,
+    #[cfg(feature = "wayland")]
+    pub layer_keyboard_interactivity: KeyboardInteractivity,
+    #[cfg(feature = "wayland")]
+    pub activation_token: Option<ActivationToken>,
+    #[cfg(feature = "wayland")]
+    pub preferred_decoration_mode: Option<DecorationMode>,
 }
 
 impl Default for PlatformSpecificWindowBuilderAttributes {
@@ -110,21 +158,63 @@ impl Default for PlatformSpecificWindowBuilderAttributes {
             #[cfg(feature = "x11")]
             override_redirect: false,
             #[cfg(feature = "x11")]
+            x11_parent: None,
+            #[cfg(feature = "x11")]
+            x11_embed_parent: None,
+            #[cfg(feature = "x11")]
             x11_window_types: vec![XWindowType::Normal],
             #[cfg(feature = "x11")]
+            x11_window_state: XWindowState::empty(),
+            #[cfg(feature = "x11")]
             gtk_theme_variant: None,
             #[cfg(feature = "wayland")]
             app_id: None,
+            #[cfg(feature = "wayland")]
+            layer: None,
+            #[cfg(feature = "wayland")]
+            layer_anchor: LayerAnchor::empty(),
+            #[cfg(feature = "wayland")]
+            layer_exclusive_zone: 0,
+            #[cfg(feature = "wayland")]
+            layer_keyboard_interactivity: KeyboardInteractivity::None,
+            #[cfg(feature = "wayland")]
+            activation_token: None,
+            #[cfg(feature = "wayland")]
+            preferred_decoration_mode: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyEventExtra {
+    pub text_with_all_modifiers: Option<SmolStr>,
+    pub key_without_modifiers: Key,
+}
+
+impl Default for KeyEventExtra {
+    fn default() -> Self {
+        Self {
+            text_with_all_modifiers: None,
+            key_without_modifiers: Key::Unidentified(NativeKey::Unidentified),
         }
     }
 }
 
 #[cfg(feature = "x11")]
 lazy_static! {
-    pub static ref X11_BACKEND: Mutex<Result<Arc<XConnection>, XNotSupported>> =
-        Mutex::new(XConnection::new(Some(x_error_callback)).map(Arc::new));
+    /// The connection to the X server, opened along with the first event loop using X11.
+    pub static ref X11_BACKEND: Mutex<Option<Result<Arc<XConnection>, XNotSupported>>> =
+        Mutex::new(None);
+    /// The hooks run on Xlib errors before winit handles them, see
+    /// [`register_xlib_error_hook`](crate::platform::unix::register_xlib_error_hook).
+    pub static ref XLIB_ERROR_HOOKS: Mutex<Vec<XlibErrorHook>> = Mutex::new(Vec::new());
 }
 
+/// A hook called with the `Display` and the `XErrorEvent` of an Xlib error, which returns whether
+/// it handled the error.
+#[cfg(feature = "x11")]
+pub type XlibErrorHook = Box<dyn Fn(*mut c_void, *mut c_void) -> bool + Send + Sync>;
+
 #[derive(Debug, Clone)]
 pub enum OsError {
     #[cfg(feature = "x11")]
@@ -391,6 +481,11 @@ impl Window {
         x11_or_wayland!(match self; Window(window) => window.set_cursor_visible(visible))
     }
 
+    #[inline]
+    pub fn set_key_repeat_enabled(&self, enabled: bool) {
+        x11_or_wayland!(match self; Window(window) => window.set_key_repeat_enabled(enabled))
+    }
+
     #[inline]
     pub fn drag_window(&self) -> Result<(), ExternalError> {
         x11_or_wayland!(match self; Window(window) => window.drag_window())
@@ -468,12 +563,7 @@ impl Window {
 
     #[inline]
     pub fn focus_window(&self) {
-        match self {
-            #[cfg(feature = "x11")]
-            Window::X(ref w) => w.focus_window(),
-            #[cfg(feature = "wayland")]
-            Window::Wayland(_) => (),
-        }
+        x11_or_wayland!(match self; Window(w) => w.focus_window())
     }
     pub fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
         match self {
@@ -542,6 +632,18 @@ impl Window {
         }
     }
 
+    #[cfg(feature = "accesskit")]
+    #[inline]
+    pub fn enable_accessibility(&self, source: Box<dyn crate::accessibility::TreeSource>) {
+        x11_or_wayland!(match self; Window(w) => w.enable_accessibility(source))
+    }
+
+    #[cfg(feature = "accesskit")]
+    #[inline]
+    pub fn update_accessibility_tree(&self, update: impl FnOnce() -> accesskit::TreeUpdate) {
+        x11_or_wayland!(match self; Window(w) => w.update_accessibility_tree(update))
+    }
+
     pub fn raw_window_handle(&self) -> RawWindowHandle {
         match self {
             #[cfg(feature = "x11")]
@@ -550,6 +652,28 @@ impl Window {
             Window::Wayland(ref window) => RawWindowHandle::Wayland(window.raw_window_handle()),
         }
     }
+
+    #[cfg(feature = "rwh_05")]
+    pub fn raw_window_handle_rwh_05(&self) -> rwh_05::RawWindowHandle {
+        x11_or_wayland!(match self; Window(window) => window.raw_window_handle_rwh_05())
+    }
+
+    #[cfg(feature = "rwh_05")]
+    pub fn raw_display_handle_rwh_05(&self) -> rwh_05::RawDisplayHandle {
+        x11_or_wayland!(match self; Window(window) => window.raw_display_handle_rwh_05())
+    }
+
+    #[cfg(feature = "rwh_06")]
+    pub fn raw_window_handle_rwh_06(&self) -> Result<rwh_06::RawWindowHandle, rwh_06::HandleError> {
+        x11_or_wayland!(match self; Window(window) => window.raw_window_handle_rwh_06())
+    }
+
+    #[cfg(feature = "rwh_06")]
+    pub fn raw_display_handle_rwh_06(
+        &self,
+    ) -> Result<rwh_06::RawDisplayHandle, rwh_06::HandleError> {
+        x11_or_wayland!(match self; Window(window) => window.raw_display_handle_rwh_06())
+    }
 }
 
 #[cfg(feature = "x11")]
@@ -557,8 +681,18 @@ unsafe extern "C" fn x_error_callback(
     display: *mut x11::ffi::Display,
     event: *mut x11::ffi::XErrorEvent,
 ) -> c_int {
+    // The errors handled by a hook, e.g. the ones expected while probing for GLX contexts, are
+    // neither logged nor reported.
+    let handled = XLIB_ERROR_HOOKS
+        .lock()
+        .iter()
+        .any(|hook| hook(display as *mut c_void, event as *mut c_void));
+    if handled {
+        return 0;
+    }
+
     let xconn_lock = X11_BACKEND.lock();
-    if let Ok(ref xconn) = *xconn_lock {
+    if let Some(Ok(ref xconn)) = *xconn_lock {
         // `assume_init` is safe here because the array consists of `MaybeUninit` values,
         // which do not require initialization.
         let mut buf: [MaybeUninit<c_char>; 1024] = MaybeUninit::uninit().assume_init();
@@ -585,6 +719,8 @@ unsafe extern "C" fn x_error_callback(
     0
 }
 
+// There is only ever one event loop, so boxing the larger backend wouldn't save anything.
+#[allow(clippy::large_enum_variant)]
 pub enum EventLoop<T: 'static> {
     #[cfg(feature = "wayland")]
     Wayland(wayland::EventLoop<T>),
@@ -619,7 +755,7 @@ impl<T: 'static> EventLoop<T> {
         #[cfg(feature = "x11")]
         if attributes.forced_backend == Some(Backend::X) {
             // TODO: Propagate
-            return EventLoop::new_x11_any_thread().unwrap();
+            return EventLoop::new_x11_any_thread(attributes).unwrap();
         }
 
         #[cfg(feature = "wayland")]
@@ -633,7 +769,7 @@ impl<T: 'static> EventLoop<T> {
                 "x11" => {
                     // TODO: propagate
                     #[cfg(feature = "x11")]
-                    return EventLoop::new_x11_any_thread()
+                    return EventLoop::new_x11_any_thread(attributes)
                         .expect("Failed to initialize X11 backend");
                     #[cfg(not(feature = "x11"))]
                     panic!("x11 feature is not enabled")
@@ -659,7 +795,7 @@ impl<T: 'static> EventLoop<T> {
         };
 
         #[cfg(feature = "x11")]
-        let x11_err = match EventLoop::new_x11_any_thread() {
+        let x11_err = match EventLoop::new_x11_any_thread(attributes) {
             Ok(event_loop) => return event_loop,
             Err(err) => err,
         };
@@ -681,11 +817,34 @@ impl<T: 'static> EventLoop<T> {
     }
 
     #[cfg(feature = "x11")]
-    fn new_x11_any_thread() -> Result<EventLoop<T>, XNotSupported> {
-        let xconn = match X11_BACKEND.lock().as_ref() {
+    fn new_x11_any_thread(
+        attributes: &PlatformSpecificEventLoopAttributes,
+    ) -> Result<EventLoop<T>, XNotSupported> {
+        let mut backend = X11_BACKEND.lock();
+        if let Some(display) = attributes.x11_display {
+            match &*backend {
+                Some(Ok(xconn)) if xconn.display != display => {
+                    return Err(XNotSupported::DisplayAlreadyOpen)
+                }
+                Some(Ok(_)) => (),
+                // Failing to open a connection of our own doesn't prevent using the application's.
+                _ => *backend = None,
+            }
+        }
+        let xconn = backend.get_or_insert_with(|| {
+            match attributes.x11_display {
+                Some(display) => unsafe {
+                    XConnection::from_display(display, Some(x_error_callback))
+                },
+                None => XConnection::new(Some(x_error_callback)),
+            }
+            .map(Arc::new)
+        });
+        let xconn = match xconn {
             Ok(xconn) => xconn.clone(),
             Err(err) => return Err(err.clone()),
         };
+        drop(backend);
 
         Ok(EventLoop::X(x11::EventLoop::new(xconn)))
     }
@@ -696,14 +855,14 @@ impl<T: 'static> EventLoop<T> {
 
     pub fn run_return<F>(&mut self, callback: F) -> i32
     where
-        F: FnMut(crate::event::Event<'_, T>, &RootELW<T>, &mut ControlFlow),
+        F: FnMut(crate::event::Event<T>, &RootELW<T>, &mut ControlFlow),
     {
         x11_or_wayland!(match self; EventLoop(evlp) => evlp.run_return(callback))
     }
 
     pub fn run<F>(self, callback: F) -> !
     where
-        F: 'static + FnMut(crate::event::Event<'_, T>, &RootELW<T>, &mut ControlFlow),
+        F: 'static + FnMut(crate::event::Event<T>, &RootELW<T>, &mut ControlFlow),
     {
         x11_or_wayland!(match self; EventLoop(evlp) => evlp.run(callback))
     }
@@ -717,6 +876,10 @@ impl<T: 'static> EventLoopProxy<T> {
     pub fn send_event(&self, event: T) -> Result<(), EventLoopClosed<T>> {
         x11_or_wayland!(match self; EventLoopProxy(proxy) => proxy.send_event(event))
     }
+
+    pub fn set_wakeup(&mut self, wakeup: ProxyWakeup) {
+        x11_or_wayland!(match self; EventLoopProxy(proxy) => proxy.set_wakeup(wakeup))
+    }
 }
 
 pub enum EventLoopWindowTarget<T> {
@@ -770,16 +933,109 @@ impl<T> EventLoopWindowTarget<T> {
             }
         }
     }
+
+    #[inline]
+    pub fn devices(&self) -> Vec<DeviceHandle> {
+        x11_or_wayland!(match self; EventLoopWindowTarget(evlp) => evlp.devices())
+    }
+
+    #[inline]
+    pub fn listen_device_events(&self, allowed: DeviceEvents) {
+        x11_or_wayland!(match self; EventLoopWindowTarget(evlp) => evlp.listen_device_events(allowed))
+    }
+
+    #[inline]
+    pub fn event_timestamp(&self) -> Option<Instant> {
+        x11_or_wayland!(match self; EventLoopWindowTarget(evlp) => evlp.event_timestamp())
+    }
+
+    #[cfg(feature = "rwh_05")]
+    #[inline]
+    pub fn raw_display_handle_rwh_05(&self) -> rwh_05::RawDisplayHandle {
+        x11_or_wayland!(match self; EventLoopWindowTarget(evlp) => evlp.raw_display_handle_rwh_05())
+    }
+
+    #[cfg(feature = "rwh_06")]
+    #[inline]
+    pub fn raw_display_handle_rwh_06(
+        &self,
+    ) -> Result<rwh_06::RawDisplayHandle, rwh_06::HandleError> {
+        x11_or_wayland!(match self; EventLoopWindowTarget(evlp) => evlp.raw_display_handle_rwh_06())
+    }
+
+    #[inline]
+    pub fn run_nested<F>(&self, _event_handler: F) -> Result<i32, NotSupportedError>
+    where
+        F: FnMut(Event<T>, &mut ControlFlow),
+    {
+        Err(NotSupportedError::new())
+    }
+
+    #[inline]
+    pub fn keyboard_layout(&self) -> Option<String> {
+        match *self {
+            #[cfg(feature = "wayland")]
+            EventLoopWindowTarget::Wayland(_) => None,
+            #[cfg(feature = "x11")]
+            EventLoopWindowTarget::X(ref evlp) => evlp.x_connection().keyboard_layout(),
+        }
+    }
+
+    #[inline]
+    #[cfg_attr(not(feature = "x11"), allow(unused_variables))]
+    pub fn key_code_to_text(&self, code: KeyCode, modifiers: ModifiersState) -> Option<String> {
+        match *self {
+            #[cfg(feature = "wayland")]
+            EventLoopWindowTarget::Wayland(_) => None,
+            #[cfg(feature = "x11")]
+            EventLoopWindowTarget::X(ref evlp) => evlp.key_code_to_text(code, modifiers),
+        }
+    }
+
+    #[inline]
+    pub fn system_preferences(&self) -> SystemPreferences {
+        SystemPreferences::default()
+    }
+
+    #[inline]
+    pub fn power_state(&self) -> PowerState {
+        #[cfg(target_os = "linux")]
+        return power::power_state();
+        #[cfg(not(target_os = "linux"))]
+        return PowerState::default();
+    }
+
+    /// Keeps the accessibility adapter of the window up to date with the events it receives.
+    #[cfg(feature = "accesskit")]
+    fn update_accessibility(&self, window_id: WindowId, event: &crate::event::WindowEvent) {
+        match (self, window_id) {
+            #[cfg(feature = "wayland")]
+            (EventLoopWindowTarget::Wayland(evlp), WindowId::Wayland(id)) => {
+                evlp.update_accessibility(id, event)
+            }
+            #[cfg(feature = "x11")]
+            (EventLoopWindowTarget::X(evlp), WindowId::X(id)) => {
+                evlp.update_accessibility(id, event)
+            }
+            #[cfg(all(feature = "wayland", feature = "x11"))]
+            _ => (),
+        }
+    }
 }
 
 fn sticky_exit_callback<T, F>(
-    evt: Event<'_, T>,
+    evt: Event<T>,
     target: &RootELW<T>,
     control_flow: &mut ControlFlow,
     callback: &mut F,
 ) where
-    F: FnMut(Event<'_, T>, &RootELW<T>, &mut ControlFlow),
+    F: FnMut(Event<T>, &RootELW<T>, &mut ControlFlow),
 {
+    #[cfg(feature = "accesskit")]
+    if let Event::WindowEvent { window_id, event } = &evt {
+        target.p.update_accessibility(window_id.0, event);
+    }
+
     // make ControlFlow::ExitWithCode sticky by providing a dummy
     // control flow reference if it is already ExitWithCode.
     if let ControlFlow::ExitWithCode(code) = *control_flow {
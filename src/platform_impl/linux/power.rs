@@ -0,0 +1,202 @@
+//! Power state backed by `sysfs`.
+//!
+//! The power supplies are listed in `/sys/class/power_supply`, and the low power mode is the
+//! `low-power` ACPI platform profile. A background thread listens to the kernel uevents of the
+//! power supplies and to the changes of the platform profile, and notifies the event loop through
+//! a sink when the power state changes.
+
+use std::fs::{self, File};
+use std::io::{self, Read, Seek, SeekFrom};
+use std::mem;
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::path::Path;
+
+use super::background::{Monitor, Sink, StoppableThread};
+use crate::event_loop::{PowerSource, PowerState};
+
+const POWER_SUPPLY_DIR: &str = "/sys/class/power_supply";
+const PLATFORM_PROFILE: &str = "/sys/firmware/acpi/platform_profile";
+
+/// Multicast group of the uevents sent by the kernel, as opposed to the ones relayed by udev.
+const KERNEL_UEVENT_GROUP: u32 = 1;
+
+pub fn power_state() -> PowerState {
+    let mut has_battery = false;
+    let mut ac_online = false;
+    if let Ok(entries) = fs::read_dir(POWER_SUPPLY_DIR) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            match read_attribute(&path.join("type")).as_deref() {
+                Some("Mains") | Some("USB") => {
+                    ac_online |= read_attribute(&path.join("online")).as_deref() == Some("1");
+                }
+                // Peripherals, such as wireless mice, report their own batteries.
+                Some("Battery") => {
+                    has_battery |= read_attribute(&path.join("scope")).as_deref() != Some("Device");
+                }
+                _ => (),
+            }
+        }
+    }
+
+    let source = if ac_online {
+        PowerSource::Ac
+    } else if has_battery {
+        PowerSource::Battery
+    } else {
+        PowerSource::Unknown
+    };
+    let low_power_mode =
+        read_attribute(Path::new(PLATFORM_PROFILE)).as_deref() == Some("low-power");
+    PowerState {
+        source,
+        low_power_mode,
+    }
+}
+
+fn read_attribute(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|value| value.trim().to_owned())
+}
+
+/// Owns the thread monitoring the power state, and stops it when dropped.
+pub struct PowerMonitor {
+    _thread: StoppableThread,
+}
+
+impl Monitor for PowerMonitor {
+    type Event = ();
+
+    const NAME: &'static str = "the power state";
+
+    fn spawn(sink: Sink<()>) -> io::Result<Self> {
+        let uevents = unsafe {
+            libc::socket(
+                libc::AF_NETLINK,
+                libc::SOCK_DGRAM | libc::SOCK_CLOEXEC,
+                libc::NETLINK_KOBJECT_UEVENT,
+            )
+        };
+        if uevents < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let mut address: libc::sockaddr_nl = unsafe { mem::zeroed() };
+        address.nl_family = libc::AF_NETLINK as _;
+        address.nl_groups = KERNEL_UEVENT_GROUP;
+        let result = unsafe {
+            libc::bind(
+                uevents,
+                &address as *const libc::sockaddr_nl as *const libc::sockaddr,
+                mem::size_of::<libc::sockaddr_nl>() as _,
+            )
+        };
+        if result < 0 {
+            let err = io::Error::last_os_error();
+            unsafe { libc::close(uevents) };
+            return Err(err);
+        }
+
+        let watcher = Watcher {
+            sink,
+            uevents,
+            // `sysfs` notifies the changes of the attributes which support it with `POLLPRI`,
+            // after which they need to be read again from the start.
+            platform_profile: File::open(PLATFORM_PROFILE).ok(),
+        };
+        let thread = StoppableThread::spawn("winit power", move |stop_fd| watcher.run(stop_fd))?;
+        Ok(Self { _thread: thread })
+    }
+}
+
+struct Watcher {
+    sink: Sink<()>,
+    uevents: RawFd,
+    platform_profile: Option<File>,
+}
+
+impl Watcher {
+    fn run(mut self, stop_fd: RawFd) {
+        self.read_platform_profile();
+        let mut state = power_state();
+        let mut buffer = [0u8; 8192];
+        loop {
+            let mut fds = vec![
+                libc::pollfd {
+                    fd: stop_fd,
+                    events: libc::POLLIN,
+                    revents: 0,
+                },
+                libc::pollfd {
+                    fd: self.uevents,
+                    events: libc::POLLIN,
+                    revents: 0,
+                },
+            ];
+            if let Some(ref platform_profile) = self.platform_profile {
+                fds.push(libc::pollfd {
+                    fd: platform_profile.as_raw_fd(),
+                    events: libc::POLLPRI | libc::POLLERR,
+                    revents: 0,
+                });
+            }
+
+            let result = unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as _, -1) };
+            if result < 0 {
+                let err = io::Error::last_os_error();
+                if err.raw_os_error() == Some(libc::EINTR) {
+                    continue;
+                }
+                warn!("Failed to poll the power state: {}", err);
+                return;
+            }
+
+            if fds[0].revents != 0 {
+                return;
+            }
+            let mut changed = false;
+            if fds[1].revents != 0 {
+                let len = unsafe {
+                    libc::recv(
+                        self.uevents,
+                        buffer.as_mut_ptr() as *mut _,
+                        buffer.len(),
+                        libc::MSG_DONTWAIT,
+                    )
+                };
+                // The uevent is a sequence of nul-terminated `KEY=value` strings.
+                changed |= len > 0
+                    && buffer[..len as usize]
+                        .split(|&byte| byte == 0)
+                        .any(|line| line == b"SUBSYSTEM=power_supply");
+            }
+            if matches!(fds.get(2), Some(fd) if fd.revents != 0) {
+                self.read_platform_profile();
+                changed = true;
+            }
+
+            if changed {
+                let new_state = power_state();
+                if new_state != state {
+                    state = new_state;
+                    (self.sink)(());
+                }
+            }
+        }
+    }
+
+    /// Consumes the pending notification of the platform profile.
+    fn read_platform_profile(&mut self) {
+        if let Some(ref mut platform_profile) = self.platform_profile {
+            let mut value = String::new();
+            let _ = platform_profile.seek(SeekFrom::Start(0));
+            let _ = platform_profile.read_to_string(&mut value);
+        }
+    }
+}
+
+impl Drop for Watcher {
+    fn drop(&mut self) {
+        unsafe { libc::close(self.uevents) };
+    }
+}
@@ -0,0 +1,198 @@
+//! Session lock notifications backed by `systemd-logind`.
+//!
+//! A background thread listens on the D-Bus system bus to the `Lock` and `Unlock` signals of the
+//! session the application runs in, and forwards them to the event loop through a sink. Connecting
+//! to the bus happens on that thread as well, so that a slow bus doesn't hold up the creation of
+//! the event loop. The thread drives the bus until it fails or the stop pipe becomes readable.
+
+use std::future::Future;
+use std::io;
+use std::os::unix::io::{BorrowedFd, RawFd};
+
+use async_io::Async;
+use futures_lite::{future, StreamExt};
+use zbus::{zvariant::OwnedObjectPath, Connection, Proxy};
+
+use super::background::{Monitor, Sink, StoppableThread};
+
+const LOGIND: &str = "org.freedesktop.login1";
+const MANAGER_PATH: &str = "/org/freedesktop/login1";
+const MANAGER_INTERFACE: &str = "org.freedesktop.login1.Manager";
+const SESSION_INTERFACE: &str = "org.freedesktop.login1.Session";
+
+/// Owns the thread monitoring the session, and stops it when dropped.
+pub struct SessionLockMonitor {
+    _thread: StoppableThread,
+}
+
+impl Monitor for SessionLockMonitor {
+    /// Whether the session got locked.
+    type Event = bool;
+
+    const NAME: &'static str = "session locks";
+
+    fn spawn(sink: Sink<bool>) -> io::Result<Self> {
+        let thread = StoppableThread::spawn("winit session lock", move |stop_fd| {
+            // The bus is unavailable in some environments, such as containers.
+            if let Err(err) = run(stop_fd, Connection::system(), sink) {
+                debug!("Stopped monitoring session locks: {}", err);
+            }
+        })?;
+        Ok(Self { _thread: thread })
+    }
+}
+
+/// Forwards the locks of the session until the bus fails, or until `stop_fd` becomes readable,
+/// which drops the connection.
+fn run(
+    stop_fd: RawFd,
+    connection: impl Future<Output = zbus::Result<Connection>>,
+    sink: Sink<bool>,
+) -> zbus::Result<()> {
+    let stop = Async::new(unsafe { BorrowedFd::borrow_raw(stop_fd) })?;
+    async_io::block_on(future::or(
+        async { watch_session(&connection.await?, sink).await },
+        async { Ok(stop.readable().await?) },
+    ))
+}
+
+async fn watch_session(connection: &Connection, mut sink: Sink<bool>) -> zbus::Result<()> {
+    let manager = Proxy::new(connection, LOGIND, MANAGER_PATH, MANAGER_INTERFACE).await?;
+    // `auto` stands for the session of the caller, or else the graphical session of its user.
+    let session: OwnedObjectPath = manager.call("GetSession", &("auto",)).await?;
+    let session = Proxy::new(connection, LOGIND, session, SESSION_INTERFACE).await?;
+
+    let mut signals = session.receive_all_signals().await?;
+    while let Some(signal) = signals.next().await {
+        match signal.header().member().map(|member| member.as_str()) {
+            Some("Lock") => sink(true),
+            Some("Unlock") => sink(false),
+            _ => (),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader};
+    use std::process::{Child, Command, Stdio};
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    use zbus::{connection, interface, object_server::SignalEmitter};
+
+    use super::*;
+
+    const SESSION_PATH: &str = "/org/freedesktop/login1/session/_31";
+
+    /// A bus of its own, which is shut down when dropped.
+    struct Daemon(Child);
+
+    impl Daemon {
+        /// Starts a bus, returning its address, or `None` if D-Bus isn't installed.
+        fn start() -> Option<(Self, String)> {
+            let mut child = Command::new("dbus-daemon")
+                .args(["--session", "--nofork", "--print-address"])
+                .stdout(Stdio::piped())
+                .stderr(Stdio::null())
+                .spawn()
+                .ok()?;
+            let mut address = String::new();
+            BufReader::new(child.stdout.as_mut().unwrap())
+                .read_line(&mut address)
+                .unwrap();
+            Some((Self(child), address.trim().to_owned()))
+        }
+    }
+
+    impl Drop for Daemon {
+        fn drop(&mut self) {
+            let _ = self.0.kill();
+            let _ = self.0.wait();
+        }
+    }
+
+    struct Manager;
+
+    #[interface(name = "org.freedesktop.login1.Manager")]
+    impl Manager {
+        fn get_session(&self, id: &str) -> OwnedObjectPath {
+            assert_eq!(id, "auto");
+            OwnedObjectPath::try_from(SESSION_PATH).unwrap()
+        }
+    }
+
+    struct Session;
+
+    #[interface(name = "org.freedesktop.login1.Session")]
+    impl Session {
+        #[zbus(signal)]
+        async fn lock(emitter: &SignalEmitter<'_>) -> zbus::Result<()>;
+
+        #[zbus(signal)]
+        async fn unlock(emitter: &SignalEmitter<'_>) -> zbus::Result<()>;
+    }
+
+    #[test]
+    fn forwards_the_locks_of_the_session() {
+        let (_daemon, address) = match Daemon::start() {
+            Some(daemon) => daemon,
+            None => return,
+        };
+        let logind = async_io::block_on(async {
+            connection::Builder::address(address.as_str())?
+                .name(LOGIND)?
+                .serve_at(MANAGER_PATH, Manager)?
+                .serve_at(SESSION_PATH, Session)?
+                .build()
+                .await
+        })
+        .unwrap();
+        let emit = |locked: bool| {
+            async_io::block_on(async {
+                let emitter = SignalEmitter::new(&logind, SESSION_PATH).unwrap();
+                if locked {
+                    Session::lock(&emitter).await
+                } else {
+                    Session::unlock(&emitter).await
+                }
+            })
+            .unwrap()
+        };
+
+        let (sender, receiver) = mpsc::channel();
+        let thread = StoppableThread::spawn("winit session lock test", move |stop_fd| {
+            let connection = connection::Builder::address(address.as_str())
+                .unwrap()
+                .build();
+            run(
+                stop_fd,
+                connection,
+                Box::new(move |locked| {
+                    let _ = sender.send(locked);
+                }),
+            )
+            .unwrap();
+        })
+        .unwrap();
+
+        // The signals sent before the monitor subscribed to them are lost.
+        let mut locked = None;
+        for _ in 0..500 {
+            emit(true);
+            locked = receiver.recv_timeout(Duration::from_millis(10)).ok();
+            if locked.is_some() {
+                break;
+            }
+        }
+        assert_eq!(locked, Some(true));
+
+        // Skip the locks emitted while the monitor was subscribing.
+        emit(false);
+        while receiver.recv_timeout(Duration::from_secs(5)).unwrap() {}
+
+        // Stopping the thread interrupts the bus.
+        drop(thread);
+    }
+}
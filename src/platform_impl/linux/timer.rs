@@ -0,0 +1,84 @@
+//! A one-shot wakeup timer backed by `timerfd`.
+//!
+//! `epoll_wait` only accepts timeouts with millisecond granularity, and rounds them up, which
+//! makes `ControlFlow::WaitUntil` resume up to a millisecond (plus scheduler latency) after the
+//! requested deadline. Registering a `timerfd` next to the other event sources and blocking
+//! without a timeout lets the kernel wake us up with nanosecond resolution instead.
+
+use std::io;
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::time::Duration;
+
+pub struct WakeupTimer {
+    fd: RawFd,
+}
+
+impl WakeupTimer {
+    pub fn new() -> io::Result<Self> {
+        let fd = unsafe {
+            libc::timerfd_create(
+                libc::CLOCK_MONOTONIC,
+                libc::TFD_NONBLOCK | libc::TFD_CLOEXEC,
+            )
+        };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(Self { fd })
+    }
+
+    /// Arm the timer to fire once after `timeout` has elapsed.
+    ///
+    /// A zero `timeout` is rounded up to a single nanosecond, since a zeroed `itimerspec`
+    /// would disarm the timer instead.
+    pub fn arm(&self, timeout: Duration) {
+        let timeout = timeout.max(Duration::from_nanos(1));
+        self.set_time(libc::timespec {
+            tv_sec: timeout.as_secs() as libc::time_t,
+            tv_nsec: timeout.subsec_nanos() as libc::c_long,
+        });
+    }
+
+    /// Disarm the timer.
+    ///
+    /// This also resets the expiration count, so the file descriptor stops being readable.
+    pub fn disarm(&self) {
+        self.set_time(libc::timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        });
+    }
+
+    fn set_time(&self, it_value: libc::timespec) {
+        let spec = libc::itimerspec {
+            it_interval: libc::timespec {
+                tv_sec: 0,
+                tv_nsec: 0,
+            },
+            it_value,
+        };
+
+        let result = unsafe { libc::timerfd_settime(self.fd, 0, &spec, std::ptr::null_mut()) };
+        if result != 0 {
+            warn!(
+                "Failed to update the wakeup timer: {}",
+                io::Error::last_os_error()
+            );
+        }
+    }
+}
+
+impl AsRawFd for WakeupTimer {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd
+    }
+}
+
+impl Drop for WakeupTimer {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.fd);
+        }
+    }
+}
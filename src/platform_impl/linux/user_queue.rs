@@ -0,0 +1,368 @@
+//! The queue carrying user events from the `EventLoopProxy`s to the event loop.
+//!
+//! Sending an event neither takes a lock nor allocates, so that it can be done from real-time
+//! threads without risking priority inversion: the queue is a multi-producer single-consumer
+//! linked list, where producers only swap the head and link the previous node to theirs.
+//! Producers of coalesced events only ask for a wakeup when none is pending yet.
+//!
+//! The nodes come from a pool allocated with the queue, to which the receiver returns them. The
+//! free nodes form a stack whose head is tagged with a counter, so that a producer popping a node
+//! can't be fooled by the same node having been popped and pushed back in the meantime. Nodes are
+//! only allocated once the pool runs out.
+
+use std::cell::{Cell, UnsafeCell};
+use std::marker::PhantomData;
+use std::ptr;
+use std::sync::atomic::{AtomicBool, AtomicPtr, AtomicU32, AtomicU64, Ordering};
+use std::sync::Arc;
+
+use crate::event_loop::ProxyWakeup;
+
+/// How many events can wait in the queue before sending one allocates.
+const POOL_SIZE: usize = 64;
+
+/// The index of the nodes which don't belong to the pool.
+const NOT_POOLED: u32 = u32::MAX;
+
+struct Node<T> {
+    next: AtomicPtr<Node<T>>,
+    value: UnsafeCell<Option<(T, ProxyWakeup)>>,
+    /// The position of the node in the pool, or `NOT_POOLED`.
+    index: u32,
+    /// The free node below this one, plus one, or `0` at the bottom of the stack.
+    next_free: AtomicU32,
+}
+
+impl<T> Node<T> {
+    fn new(index: u32) -> Self {
+        Node {
+            next: AtomicPtr::new(ptr::null_mut()),
+            value: UnsafeCell::new(None),
+            index,
+            next_free: AtomicU32::new(0),
+        }
+    }
+}
+
+struct Queue<T> {
+    /// The last node pushed.
+    head: AtomicPtr<Node<T>>,
+    /// The node whose value was popped last, only accessed by the receiver.
+    tail: UnsafeCell<*mut Node<T>>,
+    pool: Box<[Node<T>]>,
+    /// The top free node of the pool plus one (`0` if there's none) in the low half, and a counter
+    /// bumped by each change in the high half.
+    free: AtomicU64,
+    /// Set when a coalesced event asked for a wakeup, until the receiver starts draining.
+    wakeup_pending: AtomicBool,
+    closed: AtomicBool,
+}
+
+unsafe impl<T: Send> Send for Queue<T> {}
+unsafe impl<T: Send> Sync for Queue<T> {}
+
+impl<T> Queue<T> {
+    /// Takes a node out of the pool, or allocates one if the pool is empty.
+    fn take_node(&self) -> *mut Node<T> {
+        let mut free = self.free.load(Ordering::Acquire);
+        loop {
+            let top = free as u32;
+            if top == 0 {
+                return Box::into_raw(Box::new(Node::new(NOT_POOLED)));
+            }
+            let node = &self.pool[top as usize - 1];
+            // This may be stale if the node was popped in the meantime, but then the counter
+            // changed and the exchange fails.
+            let next_free = node.next_free.load(Ordering::Relaxed);
+            let new = ((free >> 32).wrapping_add(1) << 32) | next_free as u64;
+            match self
+                .free
+                .compare_exchange_weak(free, new, Ordering::Acquire, Ordering::Acquire)
+            {
+                Ok(_) => {
+                    node.next.store(ptr::null_mut(), Ordering::Relaxed);
+                    return node as *const Node<T> as *mut Node<T>;
+                }
+                Err(current) => free = current,
+            }
+        }
+    }
+
+    /// Returns a node whose value was taken to the pool, or frees it.
+    unsafe fn release_node(&self, node: *mut Node<T>) {
+        let index = (*node).index;
+        if index == NOT_POOLED {
+            drop(Box::from_raw(node));
+            return;
+        }
+        let node = &self.pool[index as usize];
+        let mut free = self.free.load(Ordering::Relaxed);
+        loop {
+            node.next_free.store(free as u32, Ordering::Relaxed);
+            let new = ((free >> 32).wrapping_add(1) << 32) | (index as u64 + 1);
+            match self
+                .free
+                .compare_exchange_weak(free, new, Ordering::Release, Ordering::Relaxed)
+            {
+                Ok(_) => return,
+                Err(current) => free = current,
+            }
+        }
+    }
+}
+
+impl<T> Drop for Queue<T> {
+    fn drop(&mut self) {
+        // The pooled nodes are dropped along with the pool.
+        let mut node = *self.tail.get_mut();
+        while !node.is_null() {
+            let next = unsafe { (*node).next.load(Ordering::Relaxed) };
+            if unsafe { (*node).index } == NOT_POOLED {
+                drop(unsafe { Box::from_raw(node) });
+            }
+            node = next;
+        }
+    }
+}
+
+pub fn channel<T>() -> (UserEventSender<T>, UserEventReceiver<T>) {
+    let pool: Box<[Node<T>]> = (0..POOL_SIZE as u32).map(Node::new).collect();
+    // The first node is the stub the queue starts with, the others are free, each of them linked
+    // to the one before it.
+    for (index, node) in pool.iter().enumerate().skip(2) {
+        node.next_free.store(index as u32, Ordering::Relaxed);
+    }
+    let stub = &pool[0] as *const Node<T> as *mut Node<T>;
+    let queue = Arc::new(Queue {
+        head: AtomicPtr::new(stub),
+        tail: UnsafeCell::new(stub),
+        free: AtomicU64::new(POOL_SIZE as u64),
+        pool,
+        wakeup_pending: AtomicBool::new(false),
+        closed: AtomicBool::new(false),
+    });
+    (
+        UserEventSender {
+            queue: queue.clone(),
+        },
+        UserEventReceiver {
+            queue,
+            _not_sync: PhantomData,
+        },
+    )
+}
+
+pub struct UserEventSender<T> {
+    queue: Arc<Queue<T>>,
+}
+
+impl<T> Clone for UserEventSender<T> {
+    fn clone(&self) -> Self {
+        UserEventSender {
+            queue: self.queue.clone(),
+        }
+    }
+}
+
+impl<T> UserEventSender<T> {
+    /// Queues `event`, returning whether the event loop needs to be woken up, or the event back if
+    /// the event loop is gone.
+    pub fn send(&self, event: T, wakeup: ProxyWakeup) -> Result<bool, T> {
+        if self.queue.closed.load(Ordering::Acquire) {
+            return Err(event);
+        }
+
+        let node = self.queue.take_node();
+        unsafe { *(*node).value.get() = Some((event, wakeup)) };
+        let prev = self.queue.head.swap(node, Ordering::AcqRel);
+        // The receiver clears `wakeup_pending` before checking for events, so either it sees this
+        // node, or we see the flag cleared and wake it up. This needs both sides to be `SeqCst`.
+        unsafe { (*prev).next.store(node, Ordering::SeqCst) };
+
+        // The receiver may have been dropped since the check above, in which case nobody is going
+        // to take the event. The node stays in the queue, which is dropped with the last sender.
+        if self.queue.closed.load(Ordering::SeqCst) {
+            let (event, _) = unsafe { (*(*node).value.get()).take() }.unwrap();
+            return Err(event);
+        }
+
+        Ok(wakeup == ProxyWakeup::PerEvent
+            || !self.queue.wakeup_pending.swap(true, Ordering::SeqCst))
+    }
+}
+
+pub struct UserEventReceiver<T> {
+    queue: Arc<Queue<T>>,
+    // Only one thread may pop events.
+    _not_sync: PhantomData<Cell<()>>,
+}
+
+impl<T> UserEventReceiver<T> {
+    /// Lets the next coalesced event wake up the event loop again. Called before draining the
+    /// queue.
+    pub fn clear_wakeup(&self) {
+        self.queue.wakeup_pending.store(false, Ordering::SeqCst);
+    }
+
+    pub fn try_recv(&self) -> Option<(T, ProxyWakeup)> {
+        unsafe {
+            let tail = *self.queue.tail.get();
+            let next = (*tail).next.load(Ordering::SeqCst);
+            if next.is_null() {
+                // Either empty, or a producer swapped the head but didn't link its node yet, in
+                // which case it's going to wake us up.
+                return None;
+            }
+            *self.queue.tail.get() = next;
+            // The producer which linked `next` is done with `tail`.
+            self.queue.release_node(tail);
+            (*(*next).value.get()).take()
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        unsafe {
+            let tail = *self.queue.tail.get();
+            (*tail).next.load(Ordering::SeqCst).is_null()
+        }
+    }
+}
+
+impl<T> Drop for UserEventReceiver<T> {
+    fn drop(&mut self) {
+        self.queue.closed.store(true, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+
+    use super::{channel, POOL_SIZE};
+    use crate::event_loop::{tests::allocations, ProxyWakeup};
+
+    #[test]
+    fn many_producers() {
+        const PRODUCERS: usize = 8;
+        const EVENTS: usize = 10_000;
+
+        let (sender, receiver) = channel();
+        let wakeups = Arc::new(AtomicUsize::new(0));
+        let producers: Vec<_> = (0..PRODUCERS)
+            .map(|producer| {
+                let sender = sender.clone();
+                let wakeups = wakeups.clone();
+                thread::spawn(move || {
+                    for i in 0..EVENTS {
+                        let wakeup = if i % 100 == 0 {
+                            ProxyWakeup::PerEvent
+                        } else {
+                            ProxyWakeup::Coalesced
+                        };
+                        if sender.send((producer, i), wakeup).unwrap() {
+                            wakeups.fetch_add(1, Ordering::SeqCst);
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        // Each producer's events arrive in the order they were sent.
+        let mut next = [0; PRODUCERS];
+        let mut received = 0;
+        while received < PRODUCERS * EVENTS {
+            receiver.clear_wakeup();
+            while let Some(((producer, i), _)) = receiver.try_recv() {
+                assert_eq!(i, next[producer]);
+                next[producer] += 1;
+                received += 1;
+            }
+            thread::yield_now();
+        }
+        for producer in producers {
+            producer.join().unwrap();
+        }
+        assert!(receiver.is_empty());
+        assert!(wakeups.load(Ordering::SeqCst) >= PRODUCERS * EVENTS / 100);
+    }
+
+    #[test]
+    fn coalesced_events_wake_up_once_per_drain() {
+        let (sender, receiver) = channel();
+        assert!(sender.send(0, ProxyWakeup::Coalesced).unwrap());
+        assert!(!sender.send(1, ProxyWakeup::Coalesced).unwrap());
+        assert!(sender.send(2, ProxyWakeup::PerEvent).unwrap());
+
+        receiver.clear_wakeup();
+        assert_eq!(receiver.try_recv(), Some((0, ProxyWakeup::Coalesced)));
+        assert!(sender.send(3, ProxyWakeup::Coalesced).unwrap());
+    }
+
+    #[test]
+    fn send_fails_once_the_receiver_is_dropped() {
+        let event = Arc::new(());
+        let (sender, receiver) = channel();
+        sender.send(event.clone(), ProxyWakeup::Coalesced).unwrap();
+        drop(receiver);
+        assert!(sender.send(event.clone(), ProxyWakeup::Coalesced).is_err());
+
+        // The events left in the queue are dropped along with it.
+        assert_eq!(Arc::strong_count(&event), 2);
+        drop(sender);
+        assert_eq!(Arc::strong_count(&event), 1);
+    }
+
+    #[test]
+    fn sending_doesnt_allocate() {
+        let (sender, receiver) = channel();
+        let sent = allocations(|| {
+            for round in 0..3 {
+                for i in 0..POOL_SIZE - 1 {
+                    sender.send((round, i), ProxyWakeup::Coalesced).unwrap();
+                }
+                receiver.clear_wakeup();
+                for i in 0..POOL_SIZE - 1 {
+                    assert_eq!(
+                        receiver.try_recv(),
+                        Some(((round, i), ProxyWakeup::Coalesced))
+                    );
+                }
+            }
+        });
+        assert_eq!(sent, 0);
+    }
+
+    #[test]
+    fn sending_past_the_pool_allocates() {
+        let event = Arc::new(());
+        let (sender, receiver) = channel();
+        for i in 0..POOL_SIZE * 3 {
+            sender
+                .send((i, event.clone()), ProxyWakeup::Coalesced)
+                .unwrap();
+        }
+        for i in 0..POOL_SIZE * 2 {
+            assert_eq!(receiver.try_recv().unwrap().0 .0, i);
+        }
+        // Both the pooled and the allocated nodes can be reused.
+        for i in 0..POOL_SIZE * 2 {
+            sender
+                .send((POOL_SIZE * 3 + i, event.clone()), ProxyWakeup::Coalesced)
+                .unwrap();
+        }
+        for i in POOL_SIZE * 2..POOL_SIZE * 5 {
+            assert_eq!(receiver.try_recv().unwrap().0 .0, i);
+        }
+        assert!(receiver.is_empty());
+
+        for _ in 0..10 {
+            sender
+                .send((0, event.clone()), ProxyWakeup::Coalesced)
+                .unwrap();
+        }
+        drop((sender, receiver));
+        assert_eq!(Arc::strong_count(&event), 1);
+    }
+}
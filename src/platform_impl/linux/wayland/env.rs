@@ -12,8 +12,12 @@ use sctk::reexports::client::protocol::wl_shm::WlShm;
 use sctk::reexports::protocols::xdg_shell::client::xdg_wm_base::XdgWmBase;
 use sctk::reexports::protocols::unstable::relative_pointer::v1::client::zwp_relative_pointer_manager_v1::ZwpRelativePointerManagerV1;
 use sctk::reexports::protocols::unstable::pointer_constraints::v1::client::zwp_pointer_constraints_v1::ZwpPointerConstraintsV1;
+use sctk::reexports::protocols::unstable::pointer_gestures::v1::client::zwp_pointer_gestures_v1::ZwpPointerGesturesV1;
 use sctk::reexports::protocols::unstable::text_input::v3::client::zwp_text_input_manager_v3::ZwpTextInputManagerV3;
+use sctk::reexports::protocols::unstable::tablet::v2::client::zwp_tablet_manager_v2::ZwpTabletManagerV2;
 use sctk::reexports::protocols::staging::xdg_activation::v1::client::xdg_activation_v1::XdgActivationV1;
+use sctk::reexports::protocols::wlr::unstable::layer_shell::v1::client::zwlr_layer_shell_v1::ZwlrLayerShellV1;
+use sctk::reexports::protocols::viewporter::client::wp_viewporter::WpViewporter;
 
 use sctk::environment::{Environment, SimpleGlobal};
 use sctk::output::{OutputHandler, OutputHandling, OutputInfo, OutputStatusListener};
@@ -21,11 +25,14 @@ use sctk::seat::{SeatData, SeatHandler, SeatHandling, SeatListener};
 use sctk::shell::{Shell, ShellHandler, ShellHandling};
 use sctk::shm::ShmHandler;
 
+use super::protocols::fractional_scale_v1::client::wp_fractional_scale_manager_v1::WpFractionalScaleManagerV1;
+
 /// Set of extra features that are supported by the compositor.
 #[derive(Debug, Clone, Copy)]
 pub struct WindowingFeatures {
     cursor_grab: bool,
     xdg_activation: bool,
+    viewporter: bool,
 }
 
 impl WindowingFeatures {
@@ -33,9 +40,11 @@ impl WindowingFeatures {
     pub fn new(env: &Environment<WinitEnv>) -> Self {
         let cursor_grab = env.get_global::<ZwpPointerConstraintsV1>().is_some();
         let xdg_activation = env.get_global::<XdgActivationV1>().is_some();
+        let viewporter = env.get_global::<WpViewporter>().is_some();
         Self {
             cursor_grab,
             xdg_activation,
+            viewporter,
         }
     }
 
@@ -46,6 +55,10 @@ impl WindowingFeatures {
     pub fn xdg_activation(&self) -> bool {
         self.xdg_activation
     }
+
+    pub fn viewporter(&self) -> bool {
+        self.viewporter
+    }
 }
 
 sctk::environment!(WinitEnv,
@@ -59,8 +72,13 @@ sctk::environment!(WinitEnv,
         ZxdgDecorationManagerV1 => decoration_manager,
         ZwpRelativePointerManagerV1 => relative_pointer_manager,
         ZwpPointerConstraintsV1 => pointer_constraints,
+        ZwpPointerGesturesV1 => pointer_gestures,
         ZwpTextInputManagerV3 => text_input_manager,
+        ZwpTabletManagerV2 => tablet_manager,
         XdgActivationV1 => xdg_activation,
+        ZwlrLayerShellV1 => layer_shell,
+        WpViewporter => viewporter,
+        WpFractionalScaleManagerV1 => fractional_scale_manager,
     ],
     multis = [
         WlSeat => seats,
@@ -86,11 +104,21 @@ pub struct WinitEnv {
 
     pointer_constraints: SimpleGlobal<ZwpPointerConstraintsV1>,
 
+    pointer_gestures: SimpleGlobal<ZwpPointerGesturesV1>,
+
     text_input_manager: SimpleGlobal<ZwpTextInputManagerV3>,
 
+    tablet_manager: SimpleGlobal<ZwpTabletManagerV2>,
+
     decoration_manager: SimpleGlobal<ZxdgDecorationManagerV1>,
 
     xdg_activation: SimpleGlobal<XdgActivationV1>,
+
+    layer_shell: SimpleGlobal<ZwlrLayerShellV1>,
+
+    viewporter: SimpleGlobal<WpViewporter>,
+
+    fractional_scale_manager: SimpleGlobal<WpFractionalScaleManagerV1>,
 }
 
 impl WinitEnv {
@@ -119,12 +147,25 @@ impl WinitEnv {
         // Pointer grab functionality.
         let pointer_constraints = SimpleGlobal::new();
 
+        // Touchpad gestures.
+        let pointer_gestures = SimpleGlobal::new();
+
         // IME handling.
         let text_input_manager = SimpleGlobal::new();
 
+        // Pen input.
+        let tablet_manager = SimpleGlobal::new();
+
         // Surface activation.
         let xdg_activation = SimpleGlobal::new();
 
+        // Panels, bars and other desktop shell surfaces.
+        let layer_shell = SimpleGlobal::new();
+
+        // Fractional scaling.
+        let viewporter = SimpleGlobal::new();
+        let fractional_scale_manager = SimpleGlobal::new();
+
         Self {
             seats,
             outputs,
@@ -135,8 +176,13 @@ impl WinitEnv {
             decoration_manager,
             relative_pointer_manager,
             pointer_constraints,
+            pointer_gestures,
             text_input_manager,
+            tablet_manager,
             xdg_activation,
+            layer_shell,
+            viewporter,
+            fractional_scale_manager,
         }
     }
 }
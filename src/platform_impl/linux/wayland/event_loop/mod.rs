@@ -1,9 +1,12 @@
-use std::cell::RefCell;
-use std::collections::HashMap;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, VecDeque};
 use std::error::Error;
 use std::io::Result as IOResult;
+#[cfg(target_os = "linux")]
+use std::os::unix::io::AsRawFd;
 use std::process;
 use std::rc::Rc;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 use sctk::reexports::client::protocol::wl_compositor::WlCompositor;
@@ -16,9 +19,27 @@ use sctk::environment::Environment;
 use sctk::seat::pointer::{ThemeManager, ThemeSpec};
 use sctk::WaylandSource;
 
-use crate::event::{Event, StartCause, WindowEvent};
-use crate::event_loop::{ControlFlow, EventLoopWindowTarget as RootEventLoopWindowTarget};
+#[cfg(feature = "accesskit")]
+use crate::accessibility::accesskit::ActionRequest;
+use crate::event::{Event, InnerSizeWriter, StartCause, WindowEvent};
+use crate::event_loop::{
+    ControlFlow, DeviceEvents, EventLoopWindowTarget as RootEventLoopWindowTarget, ProxyWakeup,
+};
+#[cfg(all(target_os = "linux", feature = "gamepad"))]
+use crate::gamepad::{GamepadEvent, GamepadId};
+#[cfg(feature = "accesskit")]
+use crate::platform_impl::platform::accessibility::ActionSink;
+use crate::platform_impl::platform::background::{self, Monitor};
+#[cfg(all(target_os = "linux", feature = "gamepad"))]
+use crate::platform_impl::platform::gamepad::GamepadMonitor;
+#[cfg(target_os = "linux")]
+use crate::platform_impl::platform::power::PowerMonitor;
+#[cfg(feature = "dbus")]
+use crate::platform_impl::platform::session_lock::SessionLockMonitor;
 use crate::platform_impl::platform::sticky_exit_callback;
+#[cfg(target_os = "linux")]
+use crate::platform_impl::platform::timer::WakeupTimer;
+use crate::platform_impl::platform::user_queue::{self, UserEventReceiver, UserEventSender};
 use crate::platform_impl::EventLoopWindowTarget as PlatformEventLoopWindowTarget;
 
 use super::env::{WindowingFeatures, WinitEnv};
@@ -69,9 +90,48 @@ pub struct EventLoopWindowTarget<T> {
     /// multiple similar themes.
     pub theme_manager: ThemeManager,
 
+    /// When device events should be delivered.
+    pub device_events: Cell<DeviceEvents>,
+
+    /// When the window event being delivered was received.
+    pub event_time: Cell<Option<Instant>>,
+
+    /// Forwards the accessibility actions of the windows to the event loop.
+    #[cfg(feature = "accesskit")]
+    pub accessibility_sink: ActionSink,
+
     _marker: std::marker::PhantomData<T>,
 }
 
+/// Events sent to the event loop from another thread, queued until it delivers them.
+type PendingQueue<E> = Rc<RefCell<VecDeque<E>>>;
+
+/// Creates a channel to the event loop, whose messages are queued in `pending`.
+fn pending_channel<E: 'static>(
+    handle: &calloop::LoopHandle<'static, WinitState>,
+    pending: &PendingQueue<E>,
+) -> Result<calloop::channel::Sender<E>, Box<dyn Error>> {
+    let (sender, channel) = calloop::channel::channel();
+    let pending = Rc::clone(pending);
+    handle.insert_source(channel, move |event, _, _| {
+        if let calloop::channel::Event::Msg(msg) = event {
+            pending.borrow_mut().push_back(msg);
+        }
+    })?;
+    Ok(sender)
+}
+
+/// Starts a background monitor, whose events are queued in `pending`.
+fn spawn_monitor<M: Monitor>(
+    handle: &calloop::LoopHandle<'static, WinitState>,
+    pending: &PendingQueue<M::Event>,
+) -> Result<Option<M>, Box<dyn Error>> {
+    let sender = pending_channel(handle, pending)?;
+    Ok(background::spawn(move |event| {
+        let _ = sender.send(event);
+    }))
+}
+
 pub struct EventLoop<T: 'static> {
     /// Event loop.
     event_loop: calloop::EventLoop<'static, WinitState>,
@@ -80,10 +140,45 @@ pub struct EventLoop<T: 'static> {
     display: Display,
 
     /// Pending user events.
-    pending_user_events: Rc<RefCell<Vec<T>>>,
+    user_events_receiver: UserEventReceiver<T>,
 
     /// Sender of user events.
-    user_events_sender: calloop::channel::Sender<T>,
+    user_events_sender: UserEventSender<T>,
+
+    /// Wakes up the event loop when user events are sent.
+    user_events_ping: calloop::ping::Ping,
+
+    /// Pending gamepad events.
+    #[cfg(all(target_os = "linux", feature = "gamepad"))]
+    pending_gamepad_events: PendingQueue<(GamepadId, GamepadEvent)>,
+
+    /// Thread monitoring gamepads, sending their events to the event loop.
+    #[cfg(all(target_os = "linux", feature = "gamepad"))]
+    _gamepad_monitor: Option<GamepadMonitor>,
+
+    /// Pending session locks and unlocks.
+    #[cfg(feature = "dbus")]
+    pending_session_locks: PendingQueue<bool>,
+
+    /// Thread monitoring the session, sending its locks to the event loop.
+    #[cfg(feature = "dbus")]
+    _session_lock_monitor: Option<SessionLockMonitor>,
+
+    /// Pending accessibility actions.
+    #[cfg(feature = "accesskit")]
+    pending_accessibility_actions: PendingQueue<(crate::platform_impl::WindowId, ActionRequest)>,
+
+    /// Pending power state changes.
+    #[cfg(target_os = "linux")]
+    pending_power_changes: PendingQueue<()>,
+
+    /// Thread monitoring the power state, notifying its changes to the event loop.
+    #[cfg(target_os = "linux")]
+    _power_monitor: Option<PowerMonitor>,
+
+    /// Timer used to wake up precisely for `ControlFlow::WaitUntil`.
+    #[cfg(target_os = "linux")]
+    wakeup_timer: WakeupTimer,
 
     /// Dispatcher of Wayland events.
     pub wayland_dispatcher: WinitDispatcher,
@@ -137,19 +232,43 @@ impl<T: 'static> EventLoop<T> {
             .handle()
             .register_dispatcher(wayland_dispatcher.clone())?;
 
-        // A source of user events.
-        let pending_user_events = Rc::new(RefCell::new(Vec::new()));
-        let pending_user_events_clone = pending_user_events.clone();
-        let (user_events_sender, user_events_channel) = calloop::channel::channel();
-
-        // User events channel.
+        // A source of user events, the ping only interrupts the dispatch so that they're drained.
+        let (user_events_sender, user_events_receiver) = user_queue::channel();
+        let (user_events_ping, user_events_ping_source) = calloop::ping::make_ping()?;
         event_loop
             .handle()
-            .insert_source(user_events_channel, move |event, _, _| {
-                if let calloop::channel::Event::Msg(msg) = event {
-                    pending_user_events_clone.borrow_mut().push(msg);
-                }
-            })?;
+            .insert_source(user_events_ping_source, |_, _, _| {})?;
+
+        // Background monitors.
+        #[cfg(all(target_os = "linux", feature = "gamepad"))]
+        let pending_gamepad_events = PendingQueue::default();
+        #[cfg(all(target_os = "linux", feature = "gamepad"))]
+        let gamepad_monitor =
+            spawn_monitor::<GamepadMonitor>(&event_loop.handle(), &pending_gamepad_events)?;
+        #[cfg(feature = "dbus")]
+        let pending_session_locks = PendingQueue::default();
+        #[cfg(feature = "dbus")]
+        let session_lock_monitor =
+            spawn_monitor::<SessionLockMonitor>(&event_loop.handle(), &pending_session_locks)?;
+        #[cfg(target_os = "linux")]
+        let pending_power_changes = PendingQueue::default();
+        #[cfg(target_os = "linux")]
+        let power_monitor =
+            spawn_monitor::<PowerMonitor>(&event_loop.handle(), &pending_power_changes)?;
+
+        // Accessibility actions channel.
+        #[cfg(feature = "accesskit")]
+        let pending_accessibility_actions = PendingQueue::default();
+        #[cfg(feature = "accesskit")]
+        let accessibility_sink: ActionSink = {
+            let actions_sender = Mutex::new(pending_channel(
+                &event_loop.handle(),
+                &pending_accessibility_actions,
+            )?);
+            Arc::new(move |window_id, request| {
+                let _ = actions_sender.lock().unwrap().send((window_id, request));
+            })
+        };
 
         // An event's loop awakener to wake up for window events from winit's windows.
         let (event_loop_awakener, event_loop_awakener_source) = calloop::ping::make_ping()?;
@@ -162,6 +281,19 @@ impl<T: 'static> EventLoop<T> {
             },
         )?;
 
+        // Wakeup timer, its expiration only needs to interrupt the dispatch.
+        #[cfg(target_os = "linux")]
+        let wakeup_timer = WakeupTimer::new()?;
+        #[cfg(target_os = "linux")]
+        event_loop.handle().insert_source(
+            calloop::generic::Generic::from_fd(
+                wakeup_timer.as_raw_fd(),
+                calloop::Interest::READ,
+                calloop::Mode::Edge,
+            ),
+            |_, _, _| Ok(calloop::PostAction::Continue),
+        )?;
+
         let event_loop_handle = event_loop.handle();
         let window_map = HashMap::new();
         let event_sink = EventSink::new();
@@ -182,6 +314,10 @@ impl<T: 'static> EventLoop<T> {
             wayland_dispatcher: wayland_dispatcher.clone(),
             windowing_features,
             theme_manager,
+            device_events: Default::default(),
+            event_time: Default::default(),
+            #[cfg(feature = "accesskit")]
+            accessibility_sink,
             _marker: std::marker::PhantomData,
         };
 
@@ -189,12 +325,33 @@ impl<T: 'static> EventLoop<T> {
         let event_loop = Self {
             event_loop,
             display,
-            pending_user_events,
+            user_events_receiver,
             wayland_dispatcher,
             _seat_manager: seat_manager,
             user_events_sender,
+            user_events_ping,
+            #[cfg(all(target_os = "linux", feature = "gamepad"))]
+            pending_gamepad_events,
+            #[cfg(all(target_os = "linux", feature = "gamepad"))]
+            _gamepad_monitor: gamepad_monitor,
+            #[cfg(feature = "dbus")]
+            pending_session_locks,
+            #[cfg(feature = "dbus")]
+            _session_lock_monitor: session_lock_monitor,
+            #[cfg(feature = "accesskit")]
+            pending_accessibility_actions,
+            #[cfg(target_os = "linux")]
+            pending_power_changes,
+            #[cfg(target_os = "linux")]
+            _power_monitor: power_monitor,
+            #[cfg(target_os = "linux")]
+            wakeup_timer,
             window_target: RootEventLoopWindowTarget {
                 p: PlatformEventLoopWindowTarget::Wayland(event_loop_window_target),
+                coalescing: Default::default(),
+                exit_code: Default::default(),
+                event_timestamp: Default::default(),
+                dispatcher_settings: Default::default(),
                 _marker: std::marker::PhantomData,
             },
         };
@@ -204,7 +361,7 @@ impl<T: 'static> EventLoop<T> {
 
     pub fn run<F>(mut self, callback: F) -> !
     where
-        F: FnMut(Event<'_, T>, &RootEventLoopWindowTarget<T>, &mut ControlFlow) + 'static,
+        F: FnMut(Event<T>, &RootEventLoopWindowTarget<T>, &mut ControlFlow) + 'static,
     {
         let exit_code = self.run_return(callback);
         process::exit(exit_code);
@@ -212,15 +369,13 @@ impl<T: 'static> EventLoop<T> {
 
     pub fn run_return<F>(&mut self, mut callback: F) -> i32
     where
-        F: FnMut(Event<'_, T>, &RootEventLoopWindowTarget<T>, &mut ControlFlow),
+        F: FnMut(Event<T>, &RootEventLoopWindowTarget<T>, &mut ControlFlow),
     {
         // Send pending events to the server.
         let _ = self.display.flush();
 
         let mut control_flow = ControlFlow::default();
 
-        let pending_user_events = self.pending_user_events.clone();
-
         callback(
             Event::NewEvents(StartCause::Init),
             &self.window_target,
@@ -237,15 +392,85 @@ impl<T: 'static> EventLoop<T> {
         // with an API to do that via some event.
         // Still, we set the exit code to the error's OS error code, or to 1 if not possible.
         let exit_code = loop {
-            // Handle pending user events. We don't need back buffer, since we can't dispatch
-            // user events indirectly via callback to the user.
-            for user_event in pending_user_events.borrow_mut().drain(..) {
+            // Handle pending user events, up to the first event asking for its own wakeup. We
+            // don't need back buffer, since we can't dispatch user events indirectly via callback
+            // to the user.
+            self.user_events_receiver.clear_wakeup();
+            loop {
+                let (user_event, wakeup) = match self.user_events_receiver.try_recv() {
+                    Some(pending) => pending,
+                    None => break,
+                };
                 sticky_exit_callback(
                     Event::UserEvent(user_event),
                     &self.window_target,
                     &mut control_flow,
                     &mut callback,
                 );
+                if wakeup == ProxyWakeup::PerEvent {
+                    break;
+                }
+            }
+
+            // Handle pending gamepad events.
+            #[cfg(all(target_os = "linux", feature = "gamepad"))]
+            loop {
+                let (gamepad_id, event) = match self.pending_gamepad_events.borrow_mut().pop_front()
+                {
+                    Some(pending) => pending,
+                    None => break,
+                };
+                sticky_exit_callback(
+                    Event::GamepadEvent { gamepad_id, event },
+                    &self.window_target,
+                    &mut control_flow,
+                    &mut callback,
+                );
+            }
+
+            // Handle pending session locks.
+            #[cfg(feature = "dbus")]
+            loop {
+                let locked = match self.pending_session_locks.borrow_mut().pop_front() {
+                    Some(locked) => locked,
+                    None => break,
+                };
+                let event = if locked {
+                    Event::SessionSleep(crate::event::SessionSleepReason::Locked)
+                } else {
+                    Event::SessionWake(crate::event::SessionSleepReason::Locked)
+                };
+                sticky_exit_callback(event, &self.window_target, &mut control_flow, &mut callback);
+            }
+
+            // Handle pending accessibility actions.
+            #[cfg(feature = "accesskit")]
+            loop {
+                let (window_id, request) =
+                    match self.pending_accessibility_actions.borrow_mut().pop_front() {
+                        Some(pending) => pending,
+                        None => break,
+                    };
+                sticky_exit_callback(
+                    Event::WindowEvent {
+                        window_id: crate::window::WindowId(window_id),
+                        event: WindowEvent::AccessibilityActionRequested(request),
+                    },
+                    &self.window_target,
+                    &mut control_flow,
+                    &mut callback,
+                );
+            }
+
+            // Handle a pending power state change.
+            #[cfg(target_os = "linux")]
+            if self.pending_power_changes.borrow_mut().drain(..).count() > 0 {
+                sticky_exit_callback(
+                    Event::PowerStateChanged,
+                    &self.window_target,
+                    &mut control_flow,
+                    &mut callback,
+                );
             }
 
             // Process 'new' pending updates.
@@ -260,9 +485,10 @@ impl<T: 'static> EventLoop<T> {
             });
 
             for (window_id, window_update) in window_updates.iter_mut() {
-                if let Some(scale_factor) = window_update.scale_factor.map(|f| f as f64) {
-                    let mut physical_size = self.with_state(|state| {
+                if let Some(scale_factor) = window_update.scale_factor {
+                    let physical_size = self.with_state(|state| {
                         let window_handle = state.window_map.get(window_id).unwrap();
+                        *window_handle.scale_factor.lock().unwrap() = scale_factor;
                         let mut size = window_handle.size.lock().unwrap();
 
                         // Update the new logical size if it was changed.
@@ -271,6 +497,7 @@ impl<T: 'static> EventLoop<T> {
 
                         window_size.to_physical(scale_factor)
                     });
+                    let new_inner_size = Arc::new(Mutex::new(physical_size));
 
                     sticky_exit_callback(
                         Event::WindowEvent {
@@ -279,13 +506,16 @@ impl<T: 'static> EventLoop<T> {
                             ),
                             event: WindowEvent::ScaleFactorChanged {
                                 scale_factor,
-                                new_inner_size: &mut physical_size,
+                                inner_size_writer: InnerSizeWriter::new(Arc::downgrade(
+                                    &new_inner_size,
+                                )),
                             },
                         },
                         &self.window_target,
                         &mut control_flow,
                         &mut callback,
                     );
+                    let physical_size = *new_inner_size.lock().unwrap();
 
                     // We don't update size on a window handle since we'll do that later
                     // when handling size update.
@@ -305,12 +535,14 @@ impl<T: 'static> EventLoop<T> {
                                 None
                             } else {
                                 *window_size = size;
-                                let scale_factor =
-                                    sctk::get_surface_scale_factor(window_handle.window.surface());
-                                let physical_size = size.to_physical(scale_factor as f64);
+                                let physical_size = size.to_physical(window_handle.scale_factor());
                                 Some(physical_size)
                             };
 
+                        if let Some(surface_scaling) = window_handle.surface_scaling.as_ref() {
+                            surface_scaling.set_logical_size(size);
+                        }
+
                         // We still perform all of those resize related logic even if the size
                         // hasn't changed, since GNOME relies on `set_geometry` calls after
                         // configures.
@@ -364,10 +596,24 @@ impl<T: 'static> EventLoop<T> {
             });
 
             // Handle pending window events.
-            for event in event_sink_back_buffer.drain(..) {
+            let device_events_allowed = match &self.window_target.p {
+                PlatformEventLoopWindowTarget::Wayland(window_target) => {
+                    window_target.device_events.get() != DeviceEvents::Never
+                }
+                #[cfg(feature = "x11")]
+                _ => unreachable!(),
+            };
+            for (event, time) in event_sink_back_buffer.drain(..) {
                 let event = event.map_nonuser_event().unwrap();
+                if let Event::DeviceEvent { .. } = event {
+                    if !device_events_allowed {
+                        continue;
+                    }
+                }
+                self.set_event_time(Some(time));
                 sticky_exit_callback(event, &self.window_target, &mut control_flow, &mut callback);
             }
+            self.set_event_time(None);
 
             // Send events cleared.
             sticky_exit_callback(
@@ -438,6 +684,9 @@ impl<T: 'static> EventLoop<T> {
                 }
             };
 
+            // User events left behind by a `ProxyWakeup::PerEvent` event need another iteration.
+            let instant_wakeup = instant_wakeup || !self.user_events_receiver.is_empty();
+
             match control_flow {
                 ControlFlow::ExitWithCode(code) => break code,
                 ControlFlow::Poll => {
@@ -454,21 +703,21 @@ impl<T: 'static> EventLoop<T> {
                     );
                 }
                 ControlFlow::Wait => {
-                    let timeout = if instant_wakeup {
-                        Some(Duration::from_millis(0))
+                    let result = if instant_wakeup {
+                        self.loop_dispatch(Some(Duration::from_millis(0)))
                     } else {
-                        None
+                        self.wait(None)
                     };
 
-                    if let Err(error) = self.loop_dispatch(timeout) {
+                    if let Err(error) = result {
                         break error.raw_os_error().unwrap_or(1);
                     }
 
+                    let user_events_pending = !self.user_events_receiver.is_empty();
+                    let cause =
+                        self.wait_cancelled_cause(user_events_pending, Instant::now(), None);
                     callback(
-                        Event::NewEvents(StartCause::WaitCancelled {
-                            start: Instant::now(),
-                            requested_resume: None,
-                        }),
+                        Event::NewEvents(cause),
                         &self.window_target,
                         &mut control_flow,
                     );
@@ -476,25 +725,24 @@ impl<T: 'static> EventLoop<T> {
                 ControlFlow::WaitUntil(deadline) => {
                     let start = Instant::now();
 
-                    // Compute the amount of time we'll block for.
-                    let duration = if deadline > start && !instant_wakeup {
-                        deadline - start
+                    let result = if deadline > start && !instant_wakeup {
+                        self.wait(Some(deadline))
                     } else {
-                        Duration::from_millis(0)
+                        self.loop_dispatch(Some(Duration::from_millis(0)))
                     };
 
-                    if let Err(error) = self.loop_dispatch(Some(duration)) {
+                    if let Err(error) = result {
                         break error.raw_os_error().unwrap_or(1);
                     }
 
                     let now = Instant::now();
 
                     if now < deadline {
+                        let user_events_pending = !self.user_events_receiver.is_empty();
+                        let cause =
+                            self.wait_cancelled_cause(user_events_pending, start, Some(deadline));
                         callback(
-                            Event::NewEvents(StartCause::WaitCancelled {
-                                start,
-                                requested_resume: Some(deadline),
-                            }),
+                            Event::NewEvents(cause),
                             &self.window_target,
                             &mut control_flow,
                         )
@@ -518,7 +766,10 @@ impl<T: 'static> EventLoop<T> {
 
     #[inline]
     pub fn create_proxy(&self) -> EventLoopProxy<T> {
-        EventLoopProxy::new(self.user_events_sender.clone())
+        EventLoopProxy::new(
+            self.user_events_sender.clone(),
+            self.user_events_ping.clone(),
+        )
     }
 
     #[inline]
@@ -536,6 +787,110 @@ impl<T: 'static> EventLoop<T> {
         f(state)
     }
 
+    /// The cause of a wait ending before its deadline, telling apart wakeups by user events.
+    fn wait_cancelled_cause(
+        &mut self,
+        user_events_pending: bool,
+        start: Instant,
+        requested_resume: Option<Instant>,
+    ) -> StartCause {
+        if user_events_pending && self.with_state(|state| state.event_sink.window_events.is_empty())
+        {
+            StartCause::UserEventReceived {
+                start,
+                requested_resume,
+            }
+        } else {
+            StartCause::WaitCancelled {
+                start,
+                requested_resume,
+            }
+        }
+    }
+
+    fn set_event_time(&self, time: Option<Instant>) {
+        match &self.window_target.p {
+            PlatformEventLoopWindowTarget::Wayland(window_target) => {
+                window_target.event_time.set(time)
+            }
+            #[cfg(feature = "x11")]
+            _ => unreachable!(),
+        }
+    }
+
+    /// Whether an iteration of the event loop has anything to deliver.
+    fn has_pending_work(&mut self) -> bool {
+        if !self.user_events_receiver.is_empty() {
+            return true;
+        }
+        #[cfg(all(target_os = "linux", feature = "gamepad"))]
+        if !self.pending_gamepad_events.borrow().is_empty() {
+            return true;
+        }
+        #[cfg(feature = "dbus")]
+        if !self.pending_session_locks.borrow().is_empty() {
+            return true;
+        }
+        #[cfg(feature = "accesskit")]
+        if !self.pending_accessibility_actions.borrow().is_empty() {
+            return true;
+        }
+        #[cfg(target_os = "linux")]
+        if !self.pending_power_changes.borrow().is_empty() {
+            return true;
+        }
+        self.with_state(|state| {
+            !state.event_sink.window_events.is_empty()
+                || state
+                    .window_updates
+                    .values()
+                    .any(|window_update| !window_update.is_empty())
+        })
+    }
+
+    /// Blocks until there's something to deliver, or until `deadline` is reached.
+    ///
+    /// Some wakeups don't leave anything to deliver, e.g. a ping from the compositor or a window
+    /// request handled right away, so those go back to waiting instead of running an iteration.
+    fn wait(&mut self, deadline: Option<Instant>) -> IOResult<()> {
+        loop {
+            let timeout = match deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return Ok(());
+                    }
+                    Some(deadline - now)
+                }
+                None => None,
+            };
+
+            // `epoll` rounds its timeout up to the next millisecond, so timeouts are delegated
+            // to the wakeup timer instead.
+            #[cfg(target_os = "linux")]
+            let timeout: Option<Duration> = {
+                if let Some(timeout) = timeout {
+                    self.wakeup_timer.arm(timeout);
+                }
+                None
+            };
+
+            let result = self.loop_dispatch(timeout);
+
+            #[cfg(target_os = "linux")]
+            self.wakeup_timer.disarm();
+
+            result?;
+
+            // Replies to the compositor, e.g. pongs, must go out even if we keep waiting.
+            let _ = self.display.flush();
+
+            if self.has_pending_work() {
+                return Ok(());
+            }
+        }
+    }
+
     fn loop_dispatch<D: Into<Option<std::time::Duration>>>(&mut self, timeout: D) -> IOResult<()> {
         let state = match &mut self.window_target.p {
             PlatformEventLoopWindowTarget::Wayland(window_target) => window_target.state.get_mut(),
@@ -543,6 +898,49 @@ impl<T: 'static> EventLoop<T> {
             _ => unreachable!(),
         };
 
+        // Waits for and reads the messages from the compositor.
+        let timeout = timeout.into();
+        trace_span!("wait", timeout = ?timeout);
         self.event_loop.dispatch(timeout, state)
     }
 }
+
+impl<T> EventLoopWindowTarget<T> {
+    #[inline]
+    pub fn listen_device_events(&self, allowed: DeviceEvents) {
+        self.device_events.set(allowed);
+    }
+
+    /// The bounds of the windows are unknown on Wayland, so only their focus is tracked.
+    #[cfg(feature = "accesskit")]
+    pub fn update_accessibility(&self, window_id: WindowId, event: &WindowEvent) {
+        if let WindowEvent::Focused(focused) = *event {
+            if let Some(window_handle) = self.state.borrow().window_map.get(&window_id) {
+                window_handle.accessibility.set_focused(focused);
+            }
+        }
+    }
+
+    #[inline]
+    pub fn event_timestamp(&self) -> Option<Instant> {
+        self.event_time.get()
+    }
+
+    #[cfg(feature = "rwh_05")]
+    pub fn raw_display_handle_rwh_05(&self) -> rwh_05::RawDisplayHandle {
+        let mut handle = rwh_05::WaylandDisplayHandle::empty();
+        handle.display = self.display.get_display_ptr() as *mut _;
+        rwh_05::RawDisplayHandle::Wayland(handle)
+    }
+
+    #[cfg(feature = "rwh_06")]
+    pub fn raw_display_handle_rwh_06(
+        &self,
+    ) -> Result<rwh_06::RawDisplayHandle, rwh_06::HandleError> {
+        let display = std::ptr::NonNull::new(self.display.get_display_ptr() as *mut _)
+            .ok_or(rwh_06::HandleError::Unavailable)?;
+        Ok(rwh_06::RawDisplayHandle::Wayland(
+            rwh_06::WaylandDisplayHandle::new(display),
+        ))
+    }
+}
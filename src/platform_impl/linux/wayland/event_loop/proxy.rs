@@ -1,32 +1,49 @@
 //! An event loop proxy.
 
-use std::sync::mpsc::SendError;
+use sctk::reexports::calloop::ping::Ping;
 
-use sctk::reexports::calloop::channel::Sender;
-
-use crate::event_loop::EventLoopClosed;
+use crate::event_loop::{EventLoopClosed, ProxyWakeup};
+use crate::platform_impl::platform::user_queue::UserEventSender;
 
 /// A handle that can be sent across the threads and used to wake up the `EventLoop`.
 pub struct EventLoopProxy<T: 'static> {
-    user_events_sender: Sender<T>,
+    user_events_sender: UserEventSender<T>,
+    ping: Ping,
+    wakeup: ProxyWakeup,
 }
 
 impl<T: 'static> Clone for EventLoopProxy<T> {
     fn clone(&self) -> Self {
         EventLoopProxy {
             user_events_sender: self.user_events_sender.clone(),
+            ping: self.ping.clone(),
+            wakeup: self.wakeup,
         }
     }
 }
 
 impl<T: 'static> EventLoopProxy<T> {
-    pub fn new(user_events_sender: Sender<T>) -> Self {
-        Self { user_events_sender }
+    pub fn new(user_events_sender: UserEventSender<T>, ping: Ping) -> Self {
+        Self {
+            user_events_sender,
+            ping,
+            wakeup: ProxyWakeup::default(),
+        }
     }
 
     pub fn send_event(&self, event: T) -> Result<(), EventLoopClosed<T>> {
-        self.user_events_sender
-            .send(event)
-            .map_err(|SendError(error)| EventLoopClosed(error))
+        // Any event still waiting in the queue is going to be drained along with this one.
+        let needs_wakeup = self
+            .user_events_sender
+            .send(event, self.wakeup)
+            .map_err(EventLoopClosed)?;
+        if needs_wakeup {
+            self.ping.ping();
+        }
+        Ok(())
+    }
+
+    pub fn set_wakeup(&mut self, wakeup: ProxyWakeup) {
+        self.wakeup = wakeup;
     }
 }
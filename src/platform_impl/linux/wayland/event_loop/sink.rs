@@ -1,5 +1,7 @@
 //! An event loop's sink to deliver events from the Wayland event callbacks.
 
+use std::time::Instant;
+
 use crate::event::{DeviceEvent, DeviceId as RootDeviceId, Event, WindowEvent};
 use crate::platform_impl::platform::{DeviceId as PlatformDeviceId, WindowId as PlatformWindowId};
 use crate::window::WindowId as RootWindowId;
@@ -10,7 +12,8 @@ use super::{DeviceId, WindowId};
 /// to the winit's user.
 #[derive(Default)]
 pub struct EventSink {
-    pub window_events: Vec<Event<'static, ()>>,
+    /// The events, with the time at which they were received.
+    pub window_events: Vec<(Event<()>, Instant)>,
 }
 
 impl EventSink {
@@ -20,17 +23,23 @@ impl EventSink {
 
     /// Add new device event to a queue.
     pub fn push_device_event(&mut self, event: DeviceEvent, device_id: DeviceId) {
-        self.window_events.push(Event::DeviceEvent {
-            event,
-            device_id: RootDeviceId(PlatformDeviceId::Wayland(device_id)),
-        });
+        self.window_events.push((
+            Event::DeviceEvent {
+                event,
+                device_id: RootDeviceId(PlatformDeviceId::Wayland(device_id)),
+            },
+            Instant::now(),
+        ));
     }
 
     /// Add new window event to a queue.
-    pub fn push_window_event(&mut self, event: WindowEvent<'static>, window_id: WindowId) {
-        self.window_events.push(Event::WindowEvent {
-            event,
-            window_id: RootWindowId(PlatformWindowId::Wayland(window_id)),
-        });
+    pub fn push_window_event(&mut self, event: WindowEvent, window_id: WindowId) {
+        self.window_events.push((
+            Event::WindowEvent {
+                event,
+                window_id: RootWindowId(PlatformWindowId::Wayland(window_id)),
+            },
+            Instant::now(),
+        ));
     }
 }
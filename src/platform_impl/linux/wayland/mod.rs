@@ -6,6 +6,7 @@
     target_os = "openbsd"
 ))]
 
+use sctk::reexports::client::protocol::wl_seat::WlSeat;
 use sctk::reexports::client::protocol::wl_surface::WlSurface;
 
 pub use event_loop::{EventLoop, EventLoopProxy, EventLoopWindowTarget};
@@ -15,15 +16,35 @@ pub use window::Window;
 mod env;
 mod event_loop;
 mod output;
+mod protocols;
 mod seat;
 mod window;
 
+use seat::SeatCapability;
+
+/// A capability of a seat, each of them being reported as a separate device.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct DeviceId;
+pub struct DeviceId {
+    /// The protocol ID of the seat.
+    seat: u32,
+
+    /// The capability of the seat.
+    capability: SeatCapability,
+}
 
 impl DeviceId {
     pub const unsafe fn dummy() -> Self {
-        DeviceId
+        DeviceId {
+            seat: 0,
+            capability: SeatCapability::Pointer,
+        }
+    }
+
+    fn new(seat: &WlSeat, capability: SeatCapability) -> Self {
+        DeviceId {
+            seat: seat.as_ref().id(),
+            capability,
+        }
     }
 }
 
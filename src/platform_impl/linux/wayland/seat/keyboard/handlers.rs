@@ -5,10 +5,14 @@ use sctk::reexports::client::protocol::wl_keyboard::KeyState;
 use sctk::seat::keyboard::Event as KeyboardEvent;
 
 use crate::event::{ElementState, KeyboardInput, ModifiersState, WindowEvent};
+use crate::keyboard::{Key, SmolStr};
+use crate::platform_impl::platform::keymap::{
+    keysym_to_key, keysym_to_unmodified_key, modifier_side, scancode_to_physicalkey,
+};
+use crate::platform_impl::platform::KeyEventExtra;
+use crate::platform_impl::wayland;
 use crate::platform_impl::wayland::event_loop::WinitState;
-use crate::platform_impl::wayland::{self, DeviceId};
 
-use super::keymap;
 use super::KeyboardInner;
 
 #[inline]
@@ -18,15 +22,25 @@ pub(super) fn handle_keyboard(
     winit_state: &mut WinitState,
 ) {
     let event_sink = &mut winit_state.event_sink;
+    let device_id =
+        crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(inner.device_id));
     match event {
-        KeyboardEvent::Enter { surface, .. } => {
+        KeyboardEvent::Enter {
+            surface, rawkeys, ..
+        } => {
             let window_id = wayland::make_wid(&surface);
 
             // Window gained focus.
             event_sink.push_window_event(WindowEvent::Focused(true), window_id);
 
+            inner.modifier_sides = rawkeys
+                .iter()
+                .map(|&rawkey| modifier_side(scancode_to_physicalkey(rawkey)))
+                .collect();
+
             // Dispatch modifers changes that we've received before getting `Enter` event.
             if let Some(modifiers) = inner.pending_modifers_state.take() {
+                let modifiers = with_sides(modifiers, inner.modifier_sides);
                 *inner.modifiers_state.borrow_mut() = modifiers;
                 event_sink.push_window_event(WindowEvent::ModifiersChanged(modifiers), window_id);
             }
@@ -44,6 +58,8 @@ pub(super) fn handle_keyboard(
                 );
             }
 
+            inner.modifier_sides = ModifiersState::empty();
+
             // Window lost focus.
             event_sink.push_window_event(WindowEvent::Focused(false), window_id);
 
@@ -68,25 +84,61 @@ pub(super) fn handle_keyboard(
                 _ => unreachable!(),
             };
 
-            let virtual_keycode = keymap::keysym_to_vkey(keysym);
+            let logical_key = keysym_to_key(keysym, utf8.as_deref());
+            let text_with_all_modifiers = match state {
+                ElementState::Pressed => utf8
+                    .as_deref()
+                    .filter(|text| !text.is_empty())
+                    .map(SmolStr::new),
+                ElementState::Released => None,
+            };
+            let text = match state {
+                ElementState::Pressed => text_without_ctrl(
+                    &logical_key,
+                    text_with_all_modifiers.as_deref(),
+                    *inner.modifiers_state.borrow(),
+                ),
+                ElementState::Released => None,
+            };
 
             event_sink.push_window_event(
                 #[allow(deprecated)]
                 WindowEvent::KeyboardInput {
-                    device_id: crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
-                        DeviceId,
-                    )),
+                    device_id,
                     input: KeyboardInput {
                         state,
-                        scancode: rawkey,
-                        virtual_keycode,
+                        physical_key: scancode_to_physicalkey(rawkey),
+                        logical_key,
+                        text,
+                        repeat: false,
                         modifiers: *inner.modifiers_state.borrow(),
+                        platform_specific: KeyEventExtra {
+                            text_with_all_modifiers,
+                            key_without_modifiers: keysym_to_unmodified_key(keysym),
+                        },
                     },
                     is_synthetic: false,
                 },
                 window_id,
             );
 
+            // Releasing one side of a modifier while the other is held doesn't change the
+            // compositor's modifiers, so the sides are updated here.
+            let side = modifier_side(scancode_to_physicalkey(rawkey));
+            if !side.is_empty() {
+                inner
+                    .modifier_sides
+                    .set(side, state == ElementState::Pressed);
+
+                let old_modifiers = *inner.modifiers_state.borrow();
+                let modifiers = with_sides(old_modifiers, inner.modifier_sides);
+                if modifiers != old_modifiers {
+                    *inner.modifiers_state.borrow_mut() = modifiers;
+                    event_sink
+                        .push_window_event(WindowEvent::ModifiersChanged(modifiers), window_id);
+                }
+            }
+
             // Send ReceivedCharacter event only on ElementState::Pressed.
             if ElementState::Released == state {
                 return;
@@ -109,19 +161,40 @@ pub(super) fn handle_keyboard(
                 None => return,
             };
 
-            let virtual_keycode = keymap::keysym_to_vkey(keysym);
+            // The repeats are synthesized on our side, so they're simply dropped when disabled.
+            if matches!(
+                winit_state.window_map.get(&window_id),
+                Some(window_handle) if !window_handle.key_repeat_enabled.get()
+            ) {
+                return;
+            }
+
+            let logical_key = keysym_to_key(keysym, utf8.as_deref());
+            let text_with_all_modifiers: Option<SmolStr> = utf8
+                .as_deref()
+                .filter(|text| !text.is_empty())
+                .map(SmolStr::new);
+            let text = text_without_ctrl(
+                &logical_key,
+                text_with_all_modifiers.as_deref(),
+                *inner.modifiers_state.borrow(),
+            );
 
             event_sink.push_window_event(
                 #[allow(deprecated)]
                 WindowEvent::KeyboardInput {
-                    device_id: crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
-                        DeviceId,
-                    )),
+                    device_id,
                     input: KeyboardInput {
                         state: ElementState::Pressed,
-                        scancode: rawkey,
-                        virtual_keycode,
+                        physical_key: scancode_to_physicalkey(rawkey),
+                        logical_key,
+                        text,
+                        repeat: true,
                         modifiers: *inner.modifiers_state.borrow(),
+                        platform_specific: KeyEventExtra {
+                            text_with_all_modifiers,
+                            key_without_modifiers: keysym_to_unmodified_key(keysym),
+                        },
                     },
                     is_synthetic: false,
                 },
@@ -137,6 +210,7 @@ pub(super) fn handle_keyboard(
         KeyboardEvent::Modifiers { modifiers } => {
             let modifiers = ModifiersState::from(modifiers);
             if let Some(window_id) = inner.target_window_id {
+                let modifiers = with_sides(modifiers, inner.modifier_sides);
                 *inner.modifiers_state.borrow_mut() = modifiers;
 
                 event_sink.push_window_event(WindowEvent::ModifiersChanged(modifiers), window_id);
@@ -149,3 +223,47 @@ pub(super) fn handle_keyboard(
         }
     }
 }
+
+/// Ctrl turns letters into control characters, which aren't useful as text, so the logical key's
+/// text is reported instead while it's held.
+fn text_without_ctrl(
+    logical_key: &Key,
+    text: Option<&str>,
+    modifiers: ModifiersState,
+) -> Option<SmolStr> {
+    if modifiers.ctrl() {
+        logical_key.to_text().map(SmolStr::new)
+    } else {
+        text.map(SmolStr::new)
+    }
+}
+
+/// Replaces the side-specific flags of `modifiers` with the held `sides` whose modifier is active.
+fn with_sides(modifiers: ModifiersState, sides: ModifiersState) -> ModifiersState {
+    let generic =
+        ModifiersState::SHIFT | ModifiersState::CTRL | ModifiersState::ALT | ModifiersState::LOGO;
+    let mut modifiers = modifiers & generic;
+    for (flag, sides_of_flag) in [
+        (
+            ModifiersState::SHIFT,
+            ModifiersState::LSHIFT | ModifiersState::RSHIFT,
+        ),
+        (
+            ModifiersState::CTRL,
+            ModifiersState::LCTRL | ModifiersState::RCTRL,
+        ),
+        (
+            ModifiersState::ALT,
+            ModifiersState::LALT | ModifiersState::RALT,
+        ),
+        (
+            ModifiersState::LOGO,
+            ModifiersState::LLOGO | ModifiersState::RLOGO,
+        ),
+    ] {
+        if modifiers.contains(flag) {
+            modifiers |= sides & sides_of_flag;
+        }
+    }
+    modifiers
+}
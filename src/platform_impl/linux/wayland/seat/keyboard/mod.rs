@@ -13,10 +13,11 @@ use sctk::seat::keyboard;
 
 use crate::event::ModifiersState;
 use crate::platform_impl::wayland::event_loop::WinitState;
-use crate::platform_impl::wayland::WindowId;
+use crate::platform_impl::wayland::{DeviceId, WindowId};
+
+use super::SeatCapability;
 
 mod handlers;
-mod keymap;
 
 pub(crate) struct Keyboard {
     pub keyboard: WlKeyboard,
@@ -34,7 +35,8 @@ impl Keyboard {
         loop_handle: LoopHandle<'static, WinitState>,
         modifiers_state: Rc<RefCell<ModifiersState>>,
     ) -> Option<Self> {
-        let mut inner = KeyboardInner::new(modifiers_state);
+        let device_id = DeviceId::new(seat, SeatCapability::Keyboard);
+        let mut inner = KeyboardInner::new(device_id, modifiers_state);
         let keyboard_data = keyboard::map_keyboard_repeat(
             loop_handle.clone(),
             seat,
@@ -69,6 +71,9 @@ impl Drop for Keyboard {
 }
 
 struct KeyboardInner {
+    /// The keyboard device of the seat.
+    device_id: DeviceId,
+
     /// Currently focused surface.
     target_window_id: Option<WindowId>,
 
@@ -81,14 +86,19 @@ struct KeyboardInner {
 
     /// Current state of modifiers keys.
     modifiers_state: Rc<RefCell<ModifiersState>>,
+
+    /// Side-specific flags of the modifier keys held down on the focused surface.
+    modifier_sides: ModifiersState,
 }
 
 impl KeyboardInner {
-    fn new(modifiers_state: Rc<RefCell<ModifiersState>>) -> Self {
+    fn new(device_id: DeviceId, modifiers_state: Rc<RefCell<ModifiersState>>) -> Self {
         Self {
+            device_id,
             target_window_id: None,
             pending_modifers_state: None,
             modifiers_state,
+            modifier_sides: ModifiersState::empty(),
         }
     }
 }
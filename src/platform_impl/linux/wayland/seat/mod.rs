@@ -5,7 +5,9 @@ use std::rc::Rc;
 
 use sctk::reexports::protocols::unstable::relative_pointer::v1::client::zwp_relative_pointer_manager_v1::ZwpRelativePointerManagerV1;
 use sctk::reexports::protocols::unstable::pointer_constraints::v1::client::zwp_pointer_constraints_v1::ZwpPointerConstraintsV1;
+use sctk::reexports::protocols::unstable::pointer_gestures::v1::client::zwp_pointer_gestures_v1::ZwpPointerGesturesV1;
 use sctk::reexports::protocols::unstable::text_input::v3::client::zwp_text_input_manager_v3::ZwpTextInputManagerV3;
+use sctk::reexports::protocols::unstable::tablet::v2::client::zwp_tablet_manager_v2::ZwpTabletManagerV2;
 
 use sctk::reexports::client::protocol::wl_seat::WlSeat;
 use sctk::reexports::client::Attached;
@@ -16,16 +18,20 @@ use sctk::seat::pointer::ThemeManager;
 use sctk::seat::{SeatData, SeatListener};
 
 use super::env::WinitEnv;
-use super::event_loop::WinitState;
-use crate::event::ModifiersState;
+use super::event_loop::{EventLoopWindowTarget, WinitState};
+use super::DeviceId;
+use crate::device::{DeviceCapabilities, DeviceHandle, DeviceKind};
+use crate::event::{DeviceEvent, ModifiersState};
 
 mod keyboard;
 pub mod pointer;
+mod tablet;
 pub mod text_input;
 mod touch;
 
 use keyboard::Keyboard;
 use pointer::Pointers;
+use tablet::TabletSeat;
 use text_input::TextInput;
 use touch::Touch;
 
@@ -42,13 +48,17 @@ impl SeatManager {
     ) -> Self {
         let relative_pointer_manager = env.get_global::<ZwpRelativePointerManagerV1>();
         let pointer_constraints = env.get_global::<ZwpPointerConstraintsV1>();
+        let pointer_gestures = env.get_global::<ZwpPointerGesturesV1>();
         let text_input_manager = env.get_global::<ZwpTextInputManagerV3>();
+        let tablet_manager = env.get_global::<ZwpTabletManagerV2>();
 
         let mut inner = SeatManagerInner::new(
             theme_manager,
             relative_pointer_manager,
             pointer_constraints,
+            pointer_gestures,
             text_input_manager,
+            tablet_manager,
             loop_handle,
         );
 
@@ -59,11 +69,12 @@ impl SeatManager {
                 None => continue,
             };
 
-            inner.process_seat_update(&seat, &seat_data);
+            inner.process_seat_update(&seat, &seat_data, None);
         }
 
-        let seat_listener = env.listen_for_seats(move |seat, seat_data, _| {
-            inner.process_seat_update(&seat, seat_data);
+        let seat_listener = env.listen_for_seats(move |seat, seat_data, mut dispatch_data| {
+            let winit_state = dispatch_data.get::<WinitState>();
+            inner.process_seat_update(&seat, seat_data, winit_state);
         });
 
         Self {
@@ -86,9 +97,15 @@ struct SeatManagerInner {
     /// Pointer constraints.
     pointer_constraints: Option<Attached<ZwpPointerConstraintsV1>>,
 
+    /// Pointer gestures.
+    pointer_gestures: Option<Attached<ZwpPointerGesturesV1>>,
+
     /// Text input manager.
     text_input_manager: Option<Attached<ZwpTextInputManagerV3>>,
 
+    /// Tablet manager.
+    tablet_manager: Option<Attached<ZwpTabletManagerV2>>,
+
     /// A theme manager.
     theme_manager: ThemeManager,
 }
@@ -98,7 +115,9 @@ impl SeatManagerInner {
         theme_manager: ThemeManager,
         relative_pointer_manager: Option<Attached<ZwpRelativePointerManagerV1>>,
         pointer_constraints: Option<Attached<ZwpPointerConstraintsV1>>,
+        pointer_gestures: Option<Attached<ZwpPointerGesturesV1>>,
         text_input_manager: Option<Attached<ZwpTextInputManagerV3>>,
+        tablet_manager: Option<Attached<ZwpTabletManagerV2>>,
         loop_handle: LoopHandle<'static, WinitState>,
     ) -> Self {
         Self {
@@ -106,13 +125,23 @@ impl SeatManagerInner {
             loop_handle,
             relative_pointer_manager,
             pointer_constraints,
+            pointer_gestures,
             text_input_manager,
+            tablet_manager,
             theme_manager,
         }
     }
 
     /// Handle seats update from the `SeatListener`.
-    pub fn process_seat_update(&mut self, seat: &Attached<WlSeat>, seat_data: &SeatData) {
+    ///
+    /// The devices which appeared or went away are reported to `winit_state`, unless the seat
+    /// was there before the event loop.
+    pub fn process_seat_update(
+        &mut self,
+        seat: &Attached<WlSeat>,
+        seat_data: &SeatData,
+        winit_state: Option<&mut WinitState>,
+    ) {
         let detached_seat = seat.detach();
 
         let position = self.seats.iter().position(|si| si.seat == detached_seat);
@@ -122,6 +151,7 @@ impl SeatManagerInner {
         });
 
         let seat_info = &mut self.seats[index];
+        let old_capabilities = seat_info.capabilities();
 
         // Pointer handling.
         if seat_data.has_pointer && !seat_data.defunct {
@@ -131,6 +161,7 @@ impl SeatManagerInner {
                     &self.theme_manager,
                     &self.relative_pointer_manager,
                     &self.pointer_constraints,
+                    &self.pointer_gestures,
                     seat_info.modifiers_state.clone(),
                 ));
             }
@@ -168,6 +199,32 @@ impl SeatManagerInner {
                 seat_info.text_input = Some(TextInput::new(seat, text_input_manager));
             }
         }
+
+        // Handle tablets.
+        if let Some(tablet_manager) = self.tablet_manager.as_ref() {
+            if seat_data.defunct {
+                seat_info.tablet_seat = None;
+            } else if seat_info.tablet_seat.is_none() {
+                seat_info.tablet_seat = Some(TabletSeat::new(seat, tablet_manager));
+            }
+        }
+
+        if let Some(winit_state) = winit_state {
+            let new_capabilities = seat_info.capabilities();
+            let added = new_capabilities
+                .iter()
+                .filter(|capability| !old_capabilities.contains(capability))
+                .map(|&capability| (DeviceEvent::Added, capability));
+            let removed = old_capabilities
+                .iter()
+                .filter(|capability| !new_capabilities.contains(capability))
+                .map(|&capability| (DeviceEvent::Removed, capability));
+
+            for (event, capability) in added.chain(removed) {
+                let device_id = DeviceId::new(&seat_info.seat, capability);
+                winit_state.event_sink.push_device_event(event, device_id);
+            }
+        }
     }
 }
 
@@ -188,6 +245,9 @@ struct SeatInfo {
     /// Text input handling aka IME.
     text_input: Option<TextInput>,
 
+    /// Tablet handling.
+    tablet_seat: Option<TabletSeat>,
+
     /// The current state of modifiers observed in keyboard handler.
     ///
     /// We keep modifiers state on a seat, since it's being used by pointer events as well.
@@ -202,7 +262,90 @@ impl SeatInfo {
             pointer: None,
             touch: None,
             text_input: None,
+            tablet_seat: None,
             modifiers_state: Rc::new(RefCell::new(ModifiersState::default())),
         }
     }
+
+    /// The capabilities of the seat reported as devices.
+    fn capabilities(&self) -> Vec<SeatCapability> {
+        let mut capabilities = Vec::new();
+        if self.keyboard.is_some() {
+            capabilities.push(SeatCapability::Keyboard);
+        }
+        if self.pointer.is_some() {
+            capabilities.push(SeatCapability::Pointer);
+        }
+        if self.touch.is_some() {
+            capabilities.push(SeatCapability::Touch);
+        }
+        capabilities
+    }
+}
+
+/// A capability of a seat, which winit exposes as a device.
+///
+/// Devices are only identified by the seat they belong to and their capability, since Wayland
+/// merges the physical devices of a seat.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SeatCapability {
+    Keyboard,
+    Pointer,
+    Touch,
+
+    /// The tablet tools of the seat, which aren't listed as a device.
+    Tablet,
+}
+
+impl<T> EventLoopWindowTarget<T> {
+    pub fn devices(&self) -> Vec<DeviceHandle> {
+        let mut devices = Vec::new();
+        for seat in self.env.get_all_seats() {
+            let seat_data = match sctk::seat::clone_seat_data(&seat) {
+                Some(seat_data) if !seat_data.defunct => seat_data,
+                _ => continue,
+            };
+
+            // Wayland only exposes the capabilities of a seat, so report each of them as a device.
+            let capabilities = [
+                (
+                    seat_data.has_keyboard,
+                    SeatCapability::Keyboard,
+                    "keyboard",
+                    DeviceKind::Keyboard,
+                    DeviceCapabilities::KEYBOARD,
+                ),
+                (
+                    seat_data.has_pointer,
+                    SeatCapability::Pointer,
+                    "pointer",
+                    DeviceKind::Mouse,
+                    DeviceCapabilities::POINTER | DeviceCapabilities::SCROLL,
+                ),
+                (
+                    seat_data.has_touch,
+                    SeatCapability::Touch,
+                    "touch",
+                    DeviceKind::Touchscreen,
+                    DeviceCapabilities::TOUCH,
+                ),
+            ];
+            for (present, seat_capability, capability, kind, capabilities) in capabilities {
+                if present {
+                    let id = DeviceId::new(&seat, seat_capability);
+                    devices.push(DeviceHandle {
+                        id: crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(id)),
+                        name: format!("{} {}", seat_data.name, capability),
+                        kind,
+                        capabilities,
+                        vendor_id: None,
+                        product_id: None,
+                        persistent_id: None,
+                    });
+                }
+            }
+        }
+
+        devices
+    }
 }
@@ -8,10 +8,16 @@ use sctk::reexports::client::Attached;
 use sctk::reexports::protocols::unstable::pointer_constraints::v1::client::zwp_pointer_constraints_v1::{ZwpPointerConstraintsV1};
 use sctk::reexports::protocols::unstable::pointer_constraints::v1::client::zwp_confined_pointer_v1::ZwpConfinedPointerV1;
 
-use crate::event::{ModifiersState, TouchPhase};
+use crate::dpi::PhysicalPosition;
+use crate::event::{ModifiersState, ScrollPhase};
+use crate::platform_impl::click::ClickCounter;
+use crate::platform_impl::wayland::DeviceId;
 
 /// A data being used by pointer handlers.
 pub(super) struct PointerData {
+    /// The pointer device of the seat.
+    pub device_id: DeviceId,
+
     /// Winit's surface the pointer is currently over.
     pub surface: Option<WlSurface>,
 
@@ -31,21 +37,31 @@ pub(super) struct PointerData {
 
     /// The currently accumulated axis data on a pointer.
     pub axis_data: AxisData,
+
+    /// Latest position of the pointer on the surface it is over.
+    pub position: PhysicalPosition<f64>,
+
+    /// Click counter for the pointer's buttons.
+    pub click_counter: ClickCounter,
 }
 
 impl PointerData {
     pub fn new(
+        device_id: DeviceId,
         confined_pointer: Rc<RefCell<Option<ZwpConfinedPointerV1>>>,
         pointer_constraints: Option<Attached<ZwpPointerConstraintsV1>>,
         modifiers_state: Rc<RefCell<ModifiersState>>,
     ) -> Self {
         Self {
+            device_id,
             surface: None,
             latest_serial: Rc::new(Cell::new(0)),
             confined_pointer,
             modifiers_state,
             pointer_constraints,
             axis_data: AxisData::new(),
+            position: Default::default(),
+            click_counter: Default::default(),
         }
     }
 }
@@ -54,21 +70,35 @@ impl PointerData {
 #[derive(Clone, Copy)]
 pub(super) struct AxisData {
     /// Current state of the axis.
-    pub axis_state: TouchPhase,
+    pub axis_state: ScrollPhase,
 
     /// A buffer for `PixelDelta` event.
     pub axis_buffer: Option<(f32, f32)>,
 
     /// A buffer for `LineDelta` event.
     pub axis_discrete_buffer: Option<(f32, f32)>,
+
+    /// Whether the axis events of the current frame come from a wheel, which has no gestures.
+    pub axis_from_wheel: bool,
 }
 
 impl AxisData {
     pub fn new() -> Self {
         Self {
-            axis_state: TouchPhase::Ended,
+            axis_state: ScrollPhase::Ended,
             axis_buffer: None,
             axis_discrete_buffer: None,
+            axis_from_wheel: false,
         }
     }
 }
+
+/// Data of the pinch gesture in progress.
+#[derive(Default)]
+pub(super) struct PinchGestureData {
+    /// The surface the gesture is happening on.
+    pub surface: Option<WlSurface>,
+
+    /// The scale of the previous event, relative to the start of the gesture.
+    pub scale: f64,
+}
@@ -6,22 +6,28 @@ use std::rc::Rc;
 use sctk::reexports::client::protocol::wl_pointer::{self, Event as PointerEvent};
 use sctk::reexports::client::protocol::wl_seat::WlSeat;
 use sctk::reexports::protocols::unstable::relative_pointer::v1::client::zwp_relative_pointer_v1::Event as RelativePointerEvent;
+use sctk::reexports::protocols::unstable::pointer_gestures::v1::client::zwp_pointer_gesture_pinch_v1::Event as PinchGestureEvent;
 
 use sctk::seat::pointer::ThemedPointer;
 
 use crate::dpi::LogicalPosition;
 use crate::event::{
-    DeviceEvent, ElementState, MouseButton, MouseScrollDelta, TouchPhase, WindowEvent,
+    DeviceEvent, ElementState, MouseButton, MouseScrollDelta, ScrollPhase, TouchPhase, WindowEvent,
 };
 use crate::platform_impl::wayland::event_loop::WinitState;
+use crate::platform_impl::wayland::window::shim::surface_scale_factor;
 use crate::platform_impl::wayland::{self, DeviceId};
 
-use super::{PointerData, WinitPointer};
+use super::{PinchGestureData, PointerData, WinitPointer};
 
 // These values are comming from <linux/input-event-codes.h>.
 const BTN_LEFT: u32 = 0x110;
 const BTN_RIGHT: u32 = 0x111;
 const BTN_MIDDLE: u32 = 0x112;
+const BTN_SIDE: u32 = 0x113;
+const BTN_EXTRA: u32 = 0x114;
+const BTN_FORWARD: u32 = 0x115;
+const BTN_BACK: u32 = 0x116;
 
 #[inline]
 pub(super) fn handle_pointer(
@@ -33,6 +39,9 @@ pub(super) fn handle_pointer(
 ) {
     let event_sink = &mut winit_state.event_sink;
     let mut pointer_data = pointer_data.borrow_mut();
+    let device_id = crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
+        pointer_data.device_id,
+    ));
     match event {
         PointerEvent::Enter {
             surface,
@@ -52,7 +61,7 @@ pub(super) fn handle_pointer(
                 None => return,
             };
 
-            let scale_factor = sctk::get_surface_scale_factor(&surface) as f64;
+            let scale_factor = window_handle.scale_factor();
             pointer_data.surface = Some(surface);
 
             // Notify window that pointer entered the surface.
@@ -65,22 +74,14 @@ pub(super) fn handle_pointer(
             };
             window_handle.pointer_entered(winit_pointer);
 
-            event_sink.push_window_event(
-                WindowEvent::CursorEntered {
-                    device_id: crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
-                        DeviceId,
-                    )),
-                },
-                window_id,
-            );
+            event_sink.push_window_event(WindowEvent::CursorEntered { device_id }, window_id);
 
             let position = LogicalPosition::new(surface_x, surface_y).to_physical(scale_factor);
+            pointer_data.position = position;
 
             event_sink.push_window_event(
                 WindowEvent::CursorMoved {
-                    device_id: crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
-                        DeviceId,
-                    )),
+                    device_id,
                     position,
                     modifiers: *pointer_data.modifiers_state.borrow(),
                 },
@@ -108,14 +109,7 @@ pub(super) fn handle_pointer(
             };
             window_handle.pointer_left(winit_pointer);
 
-            event_sink.push_window_event(
-                WindowEvent::CursorLeft {
-                    device_id: crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
-                        DeviceId,
-                    )),
-                },
-                window_id,
-            );
+            event_sink.push_window_event(WindowEvent::CursorLeft { device_id }, window_id);
         }
         PointerEvent::Motion {
             surface_x,
@@ -129,14 +123,13 @@ pub(super) fn handle_pointer(
 
             let window_id = wayland::make_wid(surface);
 
-            let scale_factor = sctk::get_surface_scale_factor(surface) as f64;
+            let scale_factor = surface_scale_factor(&winit_state.window_map, surface);
             let position = LogicalPosition::new(surface_x, surface_y).to_physical(scale_factor);
+            pointer_data.position = position;
 
             event_sink.push_window_event(
                 WindowEvent::CursorMoved {
-                    device_id: crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
-                        DeviceId,
-                    )),
+                    device_id,
                     position,
                     modifiers: *pointer_data.modifiers_state.borrow(),
                 },
@@ -165,16 +158,30 @@ pub(super) fn handle_pointer(
                 BTN_LEFT => MouseButton::Left,
                 BTN_RIGHT => MouseButton::Right,
                 BTN_MIDDLE => MouseButton::Middle,
+                BTN_SIDE | BTN_BACK => MouseButton::Back,
+                BTN_EXTRA | BTN_FORWARD => MouseButton::Forward,
                 button => MouseButton::Other(button as u16),
             };
 
+            let root_window_id =
+                crate::window::WindowId(crate::platform_impl::WindowId::Wayland(window_id));
+            let position = pointer_data.position;
+            let click_count = match state {
+                ElementState::Pressed => pointer_data.click_counter.press(
+                    root_window_id,
+                    button,
+                    position,
+                    Default::default(),
+                ),
+                ElementState::Released => pointer_data.click_counter.release(button),
+            };
+
             event_sink.push_window_event(
                 WindowEvent::MouseInput {
-                    device_id: crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
-                        DeviceId,
-                    )),
+                    device_id,
                     state,
                     button,
+                    click_count,
                     modifiers: *pointer_data.modifiers_state.borrow(),
                 },
                 window_id,
@@ -199,16 +206,14 @@ pub(super) fn handle_pointer(
                     _ => unreachable!(),
                 }
 
-                let scale_factor = sctk::get_surface_scale_factor(surface) as f64;
+                let scale_factor = surface_scale_factor(&winit_state.window_map, surface);
                 let delta = LogicalPosition::new(x as f64, y as f64).to_physical(scale_factor);
 
                 event_sink.push_window_event(
                     WindowEvent::MouseWheel {
-                        device_id: crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
-                            DeviceId,
-                        )),
+                        device_id,
                         delta: MouseScrollDelta::PixelDelta(delta),
-                        phase: TouchPhase::Moved,
+                        phase: ScrollPhase::Moved,
                         modifiers: *pointer_data.modifiers_state.borrow(),
                     },
                     window_id,
@@ -225,8 +230,8 @@ pub(super) fn handle_pointer(
                 pointer_data.axis_data.axis_buffer = Some((x, y));
 
                 pointer_data.axis_data.axis_state = match pointer_data.axis_data.axis_state {
-                    TouchPhase::Started | TouchPhase::Moved => TouchPhase::Moved,
-                    _ => TouchPhase::Started,
+                    ScrollPhase::Started | ScrollPhase::Moved => ScrollPhase::Moved,
+                    _ => ScrollPhase::Started,
                 }
             }
         }
@@ -246,18 +251,34 @@ pub(super) fn handle_pointer(
             pointer_data.axis_data.axis_discrete_buffer = Some((x, y));
 
             pointer_data.axis_data.axis_state = match pointer_data.axis_data.axis_state {
-                TouchPhase::Started | TouchPhase::Moved => TouchPhase::Moved,
-                _ => TouchPhase::Started,
+                ScrollPhase::Started | ScrollPhase::Moved => ScrollPhase::Moved,
+                _ => ScrollPhase::Started,
             }
         }
-        PointerEvent::AxisSource { .. } => (),
+        PointerEvent::AxisSource { axis_source } => {
+            pointer_data.axis_data.axis_from_wheel = matches!(
+                axis_source,
+                wl_pointer::AxisSource::Wheel | wl_pointer::AxisSource::WheelTilt
+            );
+        }
         PointerEvent::AxisStop { .. } => {
-            pointer_data.axis_data.axis_state = TouchPhase::Ended;
+            pointer_data.axis_data.axis_state = ScrollPhase::Ended;
+
+            // The stop usually comes in a frame of its own, make sure it still produces an event.
+            pointer_data.axis_data.axis_buffer.get_or_insert((0.0, 0.0));
         }
         PointerEvent::Frame => {
             let axis_buffer = pointer_data.axis_data.axis_buffer.take();
             let axis_discrete_buffer = pointer_data.axis_data.axis_discrete_buffer.take();
 
+            // Wheels don't send stops, their events are never part of a gesture.
+            let phase = if std::mem::take(&mut pointer_data.axis_data.axis_from_wheel) {
+                pointer_data.axis_data.axis_state = ScrollPhase::Ended;
+                ScrollPhase::Moved
+            } else {
+                pointer_data.axis_data.axis_state
+            };
+
             let surface = match pointer_data.surface.as_ref() {
                 Some(surface) => surface,
                 None => return,
@@ -266,23 +287,19 @@ pub(super) fn handle_pointer(
 
             let window_event = if let Some((x, y)) = axis_discrete_buffer {
                 WindowEvent::MouseWheel {
-                    device_id: crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
-                        DeviceId,
-                    )),
+                    device_id,
                     delta: MouseScrollDelta::LineDelta(x, y),
-                    phase: pointer_data.axis_data.axis_state,
+                    phase,
                     modifiers: *pointer_data.modifiers_state.borrow(),
                 }
             } else if let Some((x, y)) = axis_buffer {
-                let scale_factor = sctk::get_surface_scale_factor(surface) as f64;
+                let scale_factor = surface_scale_factor(&winit_state.window_map, surface);
                 let delta = LogicalPosition::new(x, y).to_physical(scale_factor);
 
                 WindowEvent::MouseWheel {
-                    device_id: crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
-                        DeviceId,
-                    )),
+                    device_id,
                     delta: MouseScrollDelta::PixelDelta(delta),
-                    phase: pointer_data.axis_data.axis_state,
+                    phase,
                     modifiers: *pointer_data.modifiers_state.borrow(),
                 }
             } else {
@@ -296,7 +313,11 @@ pub(super) fn handle_pointer(
 }
 
 #[inline]
-pub(super) fn handle_relative_pointer(event: RelativePointerEvent, winit_state: &mut WinitState) {
+pub(super) fn handle_relative_pointer(
+    event: RelativePointerEvent,
+    device_id: DeviceId,
+    winit_state: &mut WinitState,
+) {
     match event {
         RelativePointerEvent::RelativeMotion {
             dx_unaccel,
@@ -306,8 +327,77 @@ pub(super) fn handle_relative_pointer(event: RelativePointerEvent, winit_state:
             DeviceEvent::MouseMotion {
                 delta: (dx_unaccel, dy_unaccel),
             },
-            DeviceId,
+            device_id,
         ),
         _ => (),
     }
 }
+
+#[inline]
+pub(super) fn handle_pinch_gesture(
+    event: PinchGestureEvent,
+    pinch_gesture_data: &mut PinchGestureData,
+    device_id: DeviceId,
+    winit_state: &mut WinitState,
+) {
+    let device_id = crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(device_id));
+
+    let (delta, rotation, phase) = match event {
+        PinchGestureEvent::Begin { surface, .. } => {
+            if !winit_state
+                .window_map
+                .contains_key(&wayland::make_wid(&surface))
+            {
+                return;
+            }
+
+            pinch_gesture_data.surface = Some(surface);
+            pinch_gesture_data.scale = 1.0;
+            (0.0, 0.0, TouchPhase::Started)
+        }
+        PinchGestureEvent::Update {
+            scale, rotation, ..
+        } => {
+            // The scale is relative to the start of the gesture, turn it into a delta.
+            let delta = scale / pinch_gesture_data.scale - 1.0;
+            pinch_gesture_data.scale = scale;
+            // Wayland rotation sign convention is the inverse of winit.
+            (delta, -rotation as f32, TouchPhase::Moved)
+        }
+        PinchGestureEvent::End { cancelled, .. } => {
+            let phase = if cancelled == 0 {
+                TouchPhase::Ended
+            } else {
+                TouchPhase::Cancelled
+            };
+            (0.0, 0.0, phase)
+        }
+        _ => return,
+    };
+
+    let window_id = match pinch_gesture_data.surface.as_ref() {
+        Some(surface) => wayland::make_wid(surface),
+        None => return,
+    };
+
+    if phase == TouchPhase::Ended || phase == TouchPhase::Cancelled {
+        pinch_gesture_data.surface = None;
+    }
+
+    winit_state.event_sink.push_window_event(
+        WindowEvent::PinchGesture {
+            device_id,
+            delta,
+            phase,
+        },
+        window_id,
+    );
+    winit_state.event_sink.push_window_event(
+        WindowEvent::RotationGesture {
+            device_id,
+            delta: rotation,
+            phase,
+        },
+        window_id,
+    );
+}
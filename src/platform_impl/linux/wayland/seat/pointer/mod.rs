@@ -11,18 +11,24 @@ use sctk::reexports::protocols::unstable::relative_pointer::v1::client::zwp_rela
 use sctk::reexports::protocols::unstable::relative_pointer::v1::client::zwp_relative_pointer_v1::ZwpRelativePointerV1;
 use sctk::reexports::protocols::unstable::pointer_constraints::v1::client::zwp_pointer_constraints_v1::{ZwpPointerConstraintsV1, Lifetime};
 use sctk::reexports::protocols::unstable::pointer_constraints::v1::client::zwp_confined_pointer_v1::ZwpConfinedPointerV1;
+use sctk::reexports::protocols::unstable::pointer_gestures::v1::client::zwp_pointer_gestures_v1::ZwpPointerGesturesV1;
+use sctk::reexports::protocols::unstable::pointer_gestures::v1::client::zwp_pointer_gesture_pinch_v1::ZwpPointerGesturePinchV1;
 
 use sctk::seat::pointer::{ThemeManager, ThemedPointer};
-use sctk::window::{FallbackFrame, Window};
+use sctk::window::Window;
 
 use crate::event::ModifiersState;
 use crate::platform_impl::wayland::event_loop::WinitState;
+use crate::platform_impl::wayland::window::frame::WinitFrame;
+use crate::platform_impl::wayland::DeviceId;
 use crate::window::CursorIcon;
 
 mod data;
 mod handlers;
 
-use data::PointerData;
+use data::{PinchGestureData, PointerData};
+
+use super::SeatCapability;
 
 /// A proxy to Wayland pointer, which serves requests from a `WindowHandle`.
 pub struct WinitPointer {
@@ -150,9 +156,19 @@ impl WinitPointer {
         }
     }
 
-    pub fn drag_window(&self, window: &Window<FallbackFrame>) {
+    pub fn drag_window(&self, window: &Window<WinitFrame>) {
         window.start_interactive_move(&self.seat, self.latest_serial.get());
     }
+
+    /// The seat of the pointer.
+    pub fn seat(&self) -> &WlSeat {
+        &self.seat
+    }
+
+    /// Latest observed serial in pointer events.
+    pub fn latest_serial(&self) -> u32 {
+        self.latest_serial.get()
+    }
 }
 
 /// A pointer wrapper for easy releasing and managing pointers.
@@ -165,6 +181,9 @@ pub(super) struct Pointers {
 
     /// Confined pointer.
     confined_pointer: Rc<RefCell<Option<ZwpConfinedPointerV1>>>,
+
+    /// A pinch gesture handler.
+    pinch_gesture: Option<ZwpPointerGesturePinchV1>,
 }
 
 impl Pointers {
@@ -173,10 +192,13 @@ impl Pointers {
         theme_manager: &ThemeManager,
         relative_pointer_manager: &Option<Attached<ZwpRelativePointerManagerV1>>,
         pointer_constraints: &Option<Attached<ZwpPointerConstraintsV1>>,
+        pointer_gestures: &Option<Attached<ZwpPointerGesturesV1>>,
         modifiers_state: Rc<RefCell<ModifiersState>>,
     ) -> Self {
+        let device_id = DeviceId::new(seat, SeatCapability::Pointer);
         let confined_pointer = Rc::new(RefCell::new(None));
         let pointer_data = Rc::new(RefCell::new(PointerData::new(
+            device_id,
             confined_pointer.clone(),
             pointer_constraints.clone(),
             modifiers_state,
@@ -200,13 +222,19 @@ impl Pointers {
         let relative_pointer = relative_pointer_manager
             .as_ref()
             .map(|relative_pointer_manager| {
-                init_relative_pointer(relative_pointer_manager, &*pointer)
+                init_relative_pointer(relative_pointer_manager, &pointer, device_id)
             });
 
+        // Setup pinch gestures if they're available.
+        let pinch_gesture = pointer_gestures
+            .as_ref()
+            .map(|pointer_gestures| init_pinch_gesture(pointer_gestures, &pointer, device_id));
+
         Self {
             pointer,
             relative_pointer,
             confined_pointer,
+            pinch_gesture,
         }
     }
 }
@@ -218,6 +246,11 @@ impl Drop for Pointers {
             relative_pointer.destroy();
         }
 
+        // Drop pinch gesture.
+        if let Some(pinch_gesture) = self.pinch_gesture.take() {
+            pinch_gesture.destroy();
+        }
+
         // Drop confined pointer.
         if let Some(confined_pointer) = self.confined_pointer.borrow_mut().take() {
             confined_pointer.destroy();
@@ -233,16 +266,32 @@ impl Drop for Pointers {
 pub(super) fn init_relative_pointer(
     relative_pointer_manager: &ZwpRelativePointerManagerV1,
     pointer: &WlPointer,
+    device_id: DeviceId,
 ) -> ZwpRelativePointerV1 {
     let relative_pointer = relative_pointer_manager.get_relative_pointer(&*pointer);
     relative_pointer.quick_assign(move |_, event, mut dispatch_data| {
         let winit_state = dispatch_data.get::<WinitState>().unwrap();
-        handlers::handle_relative_pointer(event, winit_state);
+        handlers::handle_relative_pointer(event, device_id, winit_state);
     });
 
     relative_pointer.detach()
 }
 
+pub(super) fn init_pinch_gesture(
+    pointer_gestures: &ZwpPointerGesturesV1,
+    pointer: &WlPointer,
+    device_id: DeviceId,
+) -> ZwpPointerGesturePinchV1 {
+    let pinch_gesture = pointer_gestures.get_pinch_gesture(pointer);
+    let mut pinch_gesture_data = PinchGestureData::default();
+    pinch_gesture.quick_assign(move |_, event, mut dispatch_data| {
+        let winit_state = dispatch_data.get::<WinitState>().unwrap();
+        handlers::handle_pinch_gesture(event, &mut pinch_gesture_data, device_id, winit_state);
+    });
+
+    pinch_gesture.detach()
+}
+
 pub(super) fn init_confined_pointer(
     pointer_constraints: &Attached<ZwpPointerConstraintsV1>,
     surface: &WlSurface,
@@ -0,0 +1,140 @@
+//! Various handlers for tablet events.
+
+use sctk::reexports::protocols::unstable::tablet::v2::client::zwp_tablet_pad_group_v2::Event as TabletPadGroupEvent;
+use sctk::reexports::protocols::unstable::tablet::v2::client::zwp_tablet_pad_v2::Event as TabletPadEvent;
+use sctk::reexports::protocols::unstable::tablet::v2::client::zwp_tablet_seat_v2::Event as TabletSeatEvent;
+use sctk::reexports::protocols::unstable::tablet::v2::client::zwp_tablet_tool_v2::{
+    ButtonState, Event as TabletToolEvent, Type as TabletToolType, ZwpTabletToolV2,
+};
+
+use crate::dpi::LogicalPosition;
+use crate::event::{Pen, PenPhase, WindowEvent};
+
+use crate::platform_impl::wayland::event_loop::WinitState;
+use crate::platform_impl::wayland::window::shim::surface_scale_factor;
+use crate::platform_impl::wayland::{self, DeviceId};
+
+use super::TabletToolInner;
+
+// These values are comming from <linux/input-event-codes.h>.
+const BTN_STYLUS: u32 = 0x14b;
+const BTN_STYLUS2: u32 = 0x14c;
+
+/// Handle ZwpTabletSeatV2 events.
+#[inline]
+pub(super) fn handle_tablet_seat(event: TabletSeatEvent, device_id: DeviceId) {
+    match event {
+        TabletSeatEvent::ToolAdded { id } => {
+            let mut inner = TabletToolInner::default();
+            id.quick_assign(move |tool, event, mut dispatch_data| {
+                let winit_state = dispatch_data.get::<WinitState>().unwrap();
+                handle_tablet_tool(&tool, event, &mut inner, device_id, winit_state);
+            });
+        }
+        // Tablets and pads aren't used, but their events must be handled.
+        TabletSeatEvent::TabletAdded { id } => id.quick_assign(move |_, _, _| {}),
+        TabletSeatEvent::PadAdded { id } => id.quick_assign(move |_, event, _| {
+            if let TabletPadEvent::Group { pad_group } = event {
+                pad_group.quick_assign(move |_, event, _| match event {
+                    TabletPadGroupEvent::Ring { ring } => ring.quick_assign(move |_, _, _| {}),
+                    TabletPadGroupEvent::Strip { strip } => strip.quick_assign(move |_, _, _| {}),
+                    _ => (),
+                });
+            }
+        }),
+        _ => (),
+    }
+}
+
+/// Handle ZwpTabletToolV2 events.
+#[inline]
+fn handle_tablet_tool(
+    tool: &ZwpTabletToolV2,
+    event: TabletToolEvent,
+    inner: &mut TabletToolInner,
+    device_id: DeviceId,
+    winit_state: &mut WinitState,
+) {
+    let device_id = crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(device_id));
+
+    match event {
+        TabletToolEvent::Type { tool_type } => {
+            inner.eraser = tool_type == TabletToolType::Eraser;
+        }
+        TabletToolEvent::ProximityIn { surface, .. } => {
+            if !winit_state
+                .window_map
+                .contains_key(&wayland::make_wid(&surface))
+            {
+                return;
+            }
+
+            inner.surface = Some(surface);
+            inner.pending_phases.push(PenPhase::Entered);
+        }
+        TabletToolEvent::ProximityOut if inner.surface.is_some() => {
+            inner.pending_phases.push(PenPhase::Left);
+        }
+        TabletToolEvent::Down { .. } => inner.pending_phases.push(PenPhase::Down),
+        TabletToolEvent::Up => inner.pending_phases.push(PenPhase::Up),
+        TabletToolEvent::Motion { x, y } => {
+            inner.position = LogicalPosition::new(x, y);
+            inner.changed = true;
+        }
+        TabletToolEvent::Pressure { pressure } => {
+            inner.pressure = pressure as f64 / 65535.0;
+            inner.changed = true;
+        }
+        TabletToolEvent::Tilt { tilt_x, tilt_y } => {
+            inner.tilt = (tilt_x, tilt_y);
+            inner.changed = true;
+        }
+        TabletToolEvent::Rotation { degrees } => {
+            inner.twist = degrees;
+            inner.changed = true;
+        }
+        TabletToolEvent::Button { button, state, .. }
+            if button == BTN_STYLUS || button == BTN_STYLUS2 =>
+        {
+            inner.barrel_button = state == ButtonState::Pressed;
+            inner.changed = true;
+        }
+        TabletToolEvent::Frame { .. } => {
+            let mut phases = std::mem::take(&mut inner.pending_phases);
+            if phases.is_empty() && inner.changed {
+                phases.push(PenPhase::Moved);
+            }
+            inner.changed = false;
+
+            let (scale_factor, window_id) = match inner.surface.as_ref() {
+                Some(surface) => (
+                    surface_scale_factor(&winit_state.window_map, surface),
+                    wayland::make_wid(surface),
+                ),
+                None => return,
+            };
+
+            for phase in phases {
+                winit_state.event_sink.push_window_event(
+                    WindowEvent::Pen(Pen {
+                        device_id,
+                        phase,
+                        location: inner.position.to_physical(scale_factor),
+                        pressure: inner.pressure,
+                        tilt: inner.tilt,
+                        twist: inner.twist,
+                        barrel_button: inner.barrel_button,
+                        eraser: inner.eraser,
+                    }),
+                    window_id,
+                );
+
+                if phase == PenPhase::Left {
+                    inner.surface = None;
+                }
+            }
+        }
+        TabletToolEvent::Removed => tool.destroy(),
+        _ => (),
+    }
+}
@@ -0,0 +1,71 @@
+//! Tablet handling.
+
+use sctk::reexports::client::protocol::wl_seat::WlSeat;
+use sctk::reexports::client::protocol::wl_surface::WlSurface;
+use sctk::reexports::client::Attached;
+use sctk::reexports::protocols::unstable::tablet::v2::client::zwp_tablet_manager_v2::ZwpTabletManagerV2;
+use sctk::reexports::protocols::unstable::tablet::v2::client::zwp_tablet_seat_v2::ZwpTabletSeatV2;
+
+use crate::dpi::LogicalPosition;
+use crate::event::PenPhase;
+use crate::platform_impl::wayland::DeviceId;
+
+use super::SeatCapability;
+
+mod handlers;
+
+/// A wrapper around tablet seat to automatically destroy the object on `Drop`.
+pub struct TabletSeat {
+    tablet_seat: ZwpTabletSeatV2,
+}
+
+impl TabletSeat {
+    pub fn new(seat: &Attached<WlSeat>, tablet_manager: &ZwpTabletManagerV2) -> Self {
+        let device_id = DeviceId::new(seat, SeatCapability::Tablet);
+        let tablet_seat = tablet_manager.get_tablet_seat(seat);
+        tablet_seat.quick_assign(move |_, event, _| {
+            handlers::handle_tablet_seat(event, device_id);
+        });
+
+        Self {
+            tablet_seat: tablet_seat.detach(),
+        }
+    }
+}
+
+impl Drop for TabletSeat {
+    fn drop(&mut self) {
+        self.tablet_seat.destroy();
+    }
+}
+
+/// The data used by tablet tool handlers.
+#[derive(Default)]
+pub(super) struct TabletToolInner {
+    /// Whether the tool is an eraser.
+    eraser: bool,
+
+    /// The surface the tool is over.
+    surface: Option<WlSurface>,
+
+    /// Location of the tool on the surface.
+    position: LogicalPosition<f64>,
+
+    /// Pressure, between 0 and 1.
+    pressure: f64,
+
+    /// Tilt along the X and Y axes, in degrees.
+    tilt: (f64, f64),
+
+    /// Clockwise rotation, in degrees.
+    twist: f64,
+
+    /// Whether the barrel button is pressed.
+    barrel_button: bool,
+
+    /// Phases changes to report at the end of the current frame.
+    pending_phases: Vec<PenPhase>,
+
+    /// Whether the state of the tool changed during the current frame.
+    changed: bool,
+}
@@ -5,8 +5,9 @@ use sctk::reexports::client::protocol::wl_touch::Event as TouchEvent;
 use crate::dpi::LogicalPosition;
 use crate::event::{TouchPhase, WindowEvent};
 
+use crate::platform_impl::wayland;
 use crate::platform_impl::wayland::event_loop::WinitState;
-use crate::platform_impl::wayland::{self, DeviceId};
+use crate::platform_impl::wayland::window::shim::surface_scale_factor;
 
 use super::{TouchInner, TouchPoint};
 
@@ -18,6 +19,8 @@ pub(super) fn handle_touch(
     winit_state: &mut WinitState,
 ) {
     let event_sink = &mut winit_state.event_sink;
+    let device_id =
+        crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(inner.device_id));
 
     match event {
         TouchEvent::Down {
@@ -28,14 +31,12 @@ pub(super) fn handle_touch(
                 return;
             }
 
-            let scale_factor = sctk::get_surface_scale_factor(&surface) as f64;
+            let scale_factor = surface_scale_factor(&winit_state.window_map, &surface);
             let position = LogicalPosition::new(x, y);
 
             event_sink.push_window_event(
                 WindowEvent::Touch(crate::event::Touch {
-                    device_id: crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
-                        DeviceId,
-                    )),
+                    device_id,
                     phase: TouchPhase::Started,
                     location: position.to_physical(scale_factor),
                     force: None, // TODO
@@ -54,15 +55,13 @@ pub(super) fn handle_touch(
                 None => return,
             };
 
-            let scale_factor = sctk::get_surface_scale_factor(&touch_point.surface) as f64;
+            let scale_factor = surface_scale_factor(&winit_state.window_map, &touch_point.surface);
             let location = touch_point.position.to_physical(scale_factor);
             let window_id = wayland::make_wid(&touch_point.surface);
 
             event_sink.push_window_event(
                 WindowEvent::Touch(crate::event::Touch {
-                    device_id: crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
-                        DeviceId,
-                    )),
+                    device_id,
                     phase: TouchPhase::Ended,
                     location,
                     force: None, // TODO
@@ -79,15 +78,13 @@ pub(super) fn handle_touch(
 
             touch_point.position = LogicalPosition::new(x, y);
 
-            let scale_factor = sctk::get_surface_scale_factor(&touch_point.surface) as f64;
+            let scale_factor = surface_scale_factor(&winit_state.window_map, &touch_point.surface);
             let location = touch_point.position.to_physical(scale_factor);
             let window_id = wayland::make_wid(&touch_point.surface);
 
             event_sink.push_window_event(
                 WindowEvent::Touch(crate::event::Touch {
-                    device_id: crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
-                        DeviceId,
-                    )),
+                    device_id,
                     phase: TouchPhase::Moved,
                     location,
                     force: None, // TODO
@@ -99,15 +96,14 @@ pub(super) fn handle_touch(
         TouchEvent::Frame => (),
         TouchEvent::Cancel => {
             for touch_point in inner.touch_points.drain(..) {
-                let scale_factor = sctk::get_surface_scale_factor(&touch_point.surface) as f64;
+                let scale_factor =
+                    surface_scale_factor(&winit_state.window_map, &touch_point.surface);
                 let location = touch_point.position.to_physical(scale_factor);
                 let window_id = wayland::make_wid(&touch_point.surface);
 
                 event_sink.push_window_event(
                     WindowEvent::Touch(crate::event::Touch {
-                        device_id: crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
-                            DeviceId,
-                        )),
+                        device_id,
                         phase: TouchPhase::Cancelled,
                         location,
                         force: None, // TODO
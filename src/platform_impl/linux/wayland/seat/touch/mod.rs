@@ -8,6 +8,9 @@ use sctk::reexports::client::Attached;
 use crate::dpi::LogicalPosition;
 
 use crate::platform_impl::wayland::event_loop::WinitState;
+use crate::platform_impl::wayland::DeviceId;
+
+use super::SeatCapability;
 
 mod handlers;
 
@@ -20,7 +23,7 @@ pub struct Touch {
 impl Touch {
     pub fn new(seat: &Attached<WlSeat>) -> Self {
         let touch = seat.get_touch();
-        let mut inner = TouchInner::new();
+        let mut inner = TouchInner::new(DeviceId::new(seat, SeatCapability::Touch));
 
         touch.quick_assign(move |_, event, mut dispatch_data| {
             let winit_state = dispatch_data.get::<WinitState>().unwrap();
@@ -43,13 +46,17 @@ impl Drop for Touch {
 
 /// The data used by touch handlers.
 pub(super) struct TouchInner {
+    /// The touch device of the seat.
+    device_id: DeviceId,
+
     /// Current touch points.
     touch_points: Vec<TouchPoint>,
 }
 
 impl TouchInner {
-    fn new() -> Self {
+    fn new(device_id: DeviceId) -> Self {
         Self {
+            device_id,
             touch_points: Vec::new(),
         }
     }
@@ -0,0 +1,198 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+use sctk::reexports::client::protocol::wl_compositor::WlCompositor;
+use sctk::reexports::client::protocol::wl_seat::WlSeat;
+use sctk::reexports::client::protocol::wl_shm::WlShm;
+use sctk::reexports::client::protocol::wl_subcompositor::WlSubcompositor;
+use sctk::reexports::client::protocol::wl_surface::WlSurface;
+use sctk::reexports::client::{Attached, DispatchData};
+use sctk::seat::pointer::ThemeManager;
+use sctk::window::{Decorations, FallbackFrame, Frame, FrameRequest, State, Window};
+
+use crate::window::DecorationMode;
+
+/// SCTK's fallback frame, sharing whether it's hidden.
+///
+/// SCTK doesn't expose the decoration mode the compositor picked, but hides the frame when the
+/// compositor draws the decorations, which is how we tell the modes apart.
+pub struct WinitFrame {
+    frame: FallbackFrame,
+    hidden: Rc<Cell<bool>>,
+}
+
+impl Frame for WinitFrame {
+    type Error = <FallbackFrame as Frame>::Error;
+
+    /// Where to share the hidden state of the frame.
+    type Config = Rc<Cell<bool>>;
+
+    fn init(
+        base_surface: &WlSurface,
+        compositor: &Attached<WlCompositor>,
+        subcompositor: &Attached<WlSubcompositor>,
+        shm: &Attached<WlShm>,
+        theme_manager: Option<ThemeManager>,
+        callback: Box<dyn FnMut(FrameRequest, u32, DispatchData<'_>)>,
+    ) -> Result<Self, Self::Error> {
+        let frame = FallbackFrame::init(
+            base_surface,
+            compositor,
+            subcompositor,
+            shm,
+            theme_manager,
+            callback,
+        )?;
+
+        Ok(Self {
+            frame,
+            hidden: Rc::new(Cell::new(false)),
+        })
+    }
+
+    fn set_states(&mut self, states: &[State]) -> bool {
+        self.frame.set_states(states)
+    }
+
+    fn set_hidden(&mut self, hidden: bool) {
+        self.hidden.set(hidden);
+        self.frame.set_hidden(hidden);
+    }
+
+    fn set_resizable(&mut self, resizable: bool) {
+        self.frame.set_resizable(resizable);
+    }
+
+    fn new_seat(&mut self, seat: &Attached<WlSeat>) {
+        self.frame.new_seat(seat);
+    }
+
+    fn remove_seat(&mut self, seat: &WlSeat) {
+        self.frame.remove_seat(seat);
+    }
+
+    fn resize(&mut self, newsize: (u32, u32)) {
+        self.frame.resize(newsize);
+    }
+
+    fn redraw(&mut self) {
+        self.frame.redraw();
+    }
+
+    fn subtract_borders(&self, width: i32, height: i32) -> (i32, i32) {
+        self.frame.subtract_borders(width, height)
+    }
+
+    fn add_borders(&self, width: i32, height: i32) -> (i32, i32) {
+        self.frame.add_borders(width, height)
+    }
+
+    fn location(&self) -> (i32, i32) {
+        self.frame.location()
+    }
+
+    fn set_config(&mut self, hidden: Rc<Cell<bool>>) {
+        hidden.set(self.hidden.get());
+        self.hidden = hidden;
+    }
+
+    fn set_title(&mut self, title: String) {
+        self.frame.set_title(title);
+    }
+}
+
+/// The decorations of a window, and the side drawing them.
+pub struct DecorationState {
+    /// Whether the frame of the window is hidden.
+    frame_hidden: Rc<Cell<bool>>,
+
+    /// Whether the compositor can still draw the decorations. SCTK gives up on server-side
+    /// decorations for good once client-side ones were requested.
+    server_side_available: bool,
+
+    /// Whether the window is decorated.
+    decorated: bool,
+
+    /// The mode requested by the application, `None` following the compositor.
+    preferred_mode: Option<DecorationMode>,
+
+    /// The current mode, `None` when the window isn't decorated.
+    mode: Arc<Mutex<Option<DecorationMode>>>,
+}
+
+impl DecorationState {
+    pub fn new(
+        server_side_available: bool,
+        decorated: bool,
+        preferred_mode: Option<DecorationMode>,
+        mode: Arc<Mutex<Option<DecorationMode>>>,
+    ) -> Self {
+        Self {
+            frame_hidden: Rc::new(Cell::new(false)),
+            server_side_available,
+            decorated,
+            preferred_mode,
+            mode,
+        }
+    }
+
+    /// The state to share with the frame of the window, see `WinitFrame`.
+    pub fn frame_hidden(&self) -> Rc<Cell<bool>> {
+        self.frame_hidden.clone()
+    }
+
+    pub fn set_decorated(&mut self, decorated: bool) {
+        self.decorated = decorated;
+    }
+
+    pub fn set_preferred_mode(&mut self, preferred_mode: Option<DecorationMode>) {
+        self.preferred_mode = preferred_mode;
+    }
+
+    /// Applies the decorations to the window.
+    ///
+    /// Returns the new mode when it changed right away, rather than once the compositor
+    /// configures the window.
+    pub fn apply(&mut self, window: &mut Window<WinitFrame>) -> Option<DecorationMode> {
+        let decorations = match (self.decorated, self.preferred_mode) {
+            (false, _) => Decorations::None,
+            (true, None) => Decorations::FollowServer,
+            (true, Some(DecorationMode::ServerSide)) => Decorations::ServerSide,
+            (true, Some(DecorationMode::ClientSide)) => Decorations::ClientSide,
+        };
+        if decorations == Decorations::ClientSide {
+            self.server_side_available = false;
+        }
+        let awaits_configure = self.server_side_available
+            && matches!(
+                decorations,
+                Decorations::FollowServer | Decorations::ServerSide
+            );
+
+        window.set_decorate(decorations);
+
+        if awaits_configure {
+            None
+        } else {
+            self.update_mode()
+        }
+    }
+
+    /// Updates the mode from the state of the frame, returning it when it changed.
+    pub fn update_mode(&mut self) -> Option<DecorationMode> {
+        let new_mode = match (self.decorated, self.frame_hidden.get()) {
+            (false, _) => None,
+            // Only the compositor hides the frame of decorated windows.
+            (true, true) => Some(DecorationMode::ServerSide),
+            (true, false) => Some(DecorationMode::ClientSide),
+        };
+
+        let mut mode = self.mode.lock().unwrap();
+        if *mode == new_mode {
+            return None;
+        }
+        *mode = new_mode;
+        new_mode
+    }
+}
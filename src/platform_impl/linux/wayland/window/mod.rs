@@ -4,28 +4,49 @@ use std::sync::{Arc, Mutex};
 
 use sctk::reexports::client::protocol::wl_surface::WlSurface;
 use sctk::reexports::client::Display;
+use sctk::reexports::protocols::staging::xdg_activation::v1::client::xdg_activation_v1::XdgActivationV1;
+use sctk::reexports::protocols::unstable::xdg_decoration::v1::client::zxdg_decoration_manager_v1::ZxdgDecorationManagerV1;
+use sctk::reexports::protocols::viewporter::client::wp_viewporter::WpViewporter;
+use sctk::reexports::protocols::wlr::unstable::layer_shell::v1::client::zwlr_layer_shell_v1::ZwlrLayerShellV1;
 
 use sctk::reexports::calloop;
 
 use raw_window_handle::WaylandHandle;
-use sctk::window::{Decorations, FallbackFrame};
+use sctk::window::Window as SctkWindow;
 
 use crate::dpi::{LogicalSize, PhysicalPosition, PhysicalSize, Position, Size};
 use crate::error::{ExternalError, NotSupportedError, OsError as RootOsError};
+use crate::event::WindowEvent;
 use crate::monitor::MonitorHandle as RootMonitorHandle;
 use crate::platform_impl::{
     MonitorHandle as PlatformMonitorHandle, OsError,
     PlatformSpecificWindowBuilderAttributes as PlatformAttributes,
 };
-use crate::window::{CursorIcon, Fullscreen, UserAttentionType, WindowAttributes};
+use crate::window::{CursorIcon, DecorationMode, Fullscreen, UserAttentionType, WindowAttributes};
+
+#[cfg(feature = "accesskit")]
+use crate::{
+    accessibility::{accesskit::TreeUpdate, TreeSource},
+    platform_impl::{
+        platform::accessibility::{Accessibility, ActionSink},
+        WindowId as PlatformWindowId,
+    },
+};
 
 use super::env::WindowingFeatures;
 use super::event_loop::WinitState;
 use super::output::{MonitorHandle, OutputManagerHandle};
+use super::protocols::fractional_scale_v1::client::wp_fractional_scale_manager_v1::WpFractionalScaleManagerV1;
 use super::{EventLoopWindowTarget, WindowId};
 
+pub mod frame;
+pub mod scale;
+pub mod shell;
 pub mod shim;
 
+use frame::{DecorationState, WinitFrame};
+use scale::SurfaceScaling;
+use shell::{LayerSurface, LayerSurfaceAttributes, WindowShell};
 use shim::{WindowHandle, WindowRequest, WindowUpdate};
 
 pub struct Window {
@@ -41,6 +62,12 @@ pub struct Window {
     /// The current window size.
     size: Arc<Mutex<LogicalSize<u32>>>,
 
+    /// The current scale factor.
+    scale_factor: Arc<Mutex<f64>>,
+
+    /// The side drawing the decorations.
+    decoration_mode: Arc<Mutex<Option<DecorationMode>>>,
+
     /// A handle to output manager.
     output_manager_handle: OutputManagerHandle,
 
@@ -58,17 +85,38 @@ pub struct Window {
 
     /// Requests that SCTK window should perform.
     window_requests: Arc<Mutex<Vec<WindowRequest>>>,
+
+    /// The accessibility adapter, shared with the window handle.
+    #[cfg(feature = "accesskit")]
+    accessibility: Arc<Accessibility>,
+
+    /// Forwards the accessibility actions to the event loop.
+    #[cfg(feature = "accesskit")]
+    accessibility_sink: ActionSink,
 }
 
 impl Window {
     pub fn new<T>(
         event_loop_window_target: &EventLoopWindowTarget<T>,
         attributes: WindowAttributes,
-        platform_attributes: PlatformAttributes,
+        mut platform_attributes: PlatformAttributes,
     ) -> Result<Self, RootOsError> {
-        let surface = event_loop_window_target
-            .env
+        let activation_token = platform_attributes.activation_token.take();
+
+        let env = &event_loop_window_target.env;
+        let viewporter = env.get_global::<WpViewporter>();
+        let fractional_scale_manager = viewporter
+            .as_ref()
+            .and(env.get_global::<WpFractionalScaleManagerV1>());
+        let use_fractional_scaling = fractional_scale_manager.is_some();
+
+        let surface = env
             .create_surface_with_scale_callback(move |scale, surface, mut dispatch_data| {
+                // The preferred fractional scale supersedes the integer one of the outputs.
+                if use_fractional_scaling {
+                    return;
+                }
+
                 let winit_state = dispatch_data.get::<WinitState>().unwrap();
 
                 // Get the window that receiced the event.
@@ -76,123 +124,89 @@ impl Window {
                 let mut window_update = winit_state.window_updates.get_mut(&window_id).unwrap();
 
                 // Set pending scale factor.
-                window_update.scale_factor = Some(scale);
+                window_update.scale_factor = Some(scale as f64);
                 window_update.redraw_requested = true;
 
-                surface.set_buffer_scale(scale);
+                // Viewport scaling keeps the buffer scale at 1.
+                let viewport_scaling = winit_state
+                    .window_map
+                    .get(&window_id)
+                    .and_then(|window_handle| window_handle.surface_scaling.as_ref())
+                    .map(|surface_scaling| surface_scaling.overrides_buffer_scale())
+                    .unwrap_or(false);
+                if !viewport_scaling {
+                    surface.set_buffer_scale(scale);
+                }
             })
             .detach();
 
-        let scale_factor = sctk::get_surface_scale_factor(&surface);
+        let surface_scaling = viewporter.map(|viewporter| {
+            SurfaceScaling::new(&viewporter, fractional_scale_manager.as_ref(), &surface)
+        });
+
+        let scale_factor = sctk::get_surface_scale_factor(&surface) as f64;
 
-        let window_id = super::make_wid(&surface);
         let maximized = Arc::new(AtomicBool::new(false));
-        let maximzied_clone = maximized.clone();
         let fullscreen = Arc::new(AtomicBool::new(false));
-        let fullscreen_clone = fullscreen.clone();
+
+        let decoration_mode = Arc::new(Mutex::new(None));
+        let mut decorations = DecorationState::new(
+            env.get_global::<ZxdgDecorationManagerV1>().is_some(),
+            attributes.decorations && platform_attributes.layer.is_none(),
+            platform_attributes.preferred_decoration_mode,
+            decoration_mode.clone(),
+        );
 
         let (width, height) = attributes
             .inner_size
-            .map(|size| size.to_logical::<f64>(scale_factor as f64).into())
+            .map(|size| size.to_logical::<f64>(scale_factor).into())
             .unwrap_or((800, 600));
 
-        let theme_manager = event_loop_window_target.theme_manager.clone();
-        let mut window = event_loop_window_target
-            .env
-            .create_window::<FallbackFrame, _>(
-                surface.clone(),
-                Some(theme_manager),
-                (width, height),
-                move |event, mut dispatch_data| {
-                    use sctk::window::{Event, State};
-
-                    let winit_state = dispatch_data.get::<WinitState>().unwrap();
-                    let mut window_update = winit_state.window_updates.get_mut(&window_id).unwrap();
-
-                    match event {
-                        Event::Refresh => {
-                            window_update.refresh_frame = true;
-                        }
-                        Event::Configure { new_size, states } => {
-                            let is_maximized = states.contains(&State::Maximized);
-                            maximzied_clone.store(is_maximized, Ordering::Relaxed);
-                            let is_fullscreen = states.contains(&State::Fullscreen);
-                            fullscreen_clone.store(is_fullscreen, Ordering::Relaxed);
-
-                            window_update.refresh_frame = true;
-                            window_update.redraw_requested = true;
-                            if let Some((w, h)) = new_size {
-                                window_update.size = Some(LogicalSize::new(w, h));
-                            }
-                        }
-                        Event::Close => {
-                            window_update.close_window = true;
-                        }
-                    }
-                },
-            )
-            .map_err(|_| os_error!(OsError::WaylandMisc("failed to create window.")))?;
-
-        // Set decorations.
-        if attributes.decorations {
-            window.set_decorate(Decorations::FollowServer);
-        } else {
-            window.set_decorate(Decorations::None);
-        }
-        // Without this commit here at least on kwin 5.23.3 the initial configure
-        // will have a size (1,1), the second configure including the decoration
-        // mode will have the min_size as its size. With this commit the initial
-        // configure will have no size, the application will draw it's content
-        // with the initial size and everything works as expected afterwards.
-        window.surface().commit();
-
-        // Min dimensions.
-        let min_size = attributes
-            .min_inner_size
-            .map(|size| size.to_logical::<f64>(scale_factor as f64).into());
-        window.set_min_size(min_size);
-
-        // Max dimensions.
-        let max_size = attributes
-            .max_inner_size
-            .map(|size| size.to_logical::<f64>(scale_factor as f64).into());
-        window.set_max_size(max_size);
-
-        // Set Wayland specific window attributes.
-        if let Some(app_id) = platform_attributes.app_id {
-            window.set_app_id(app_id);
+        if let Some(surface_scaling) = surface_scaling.as_ref() {
+            surface_scaling.set_logical_size(LogicalSize::new(width, height));
         }
 
-        // Set common window attributes.
-        //
-        // We set resizable after other attributes, since it touches min and max size under
-        // the hood.
-        window.set_resizable(attributes.resizable);
-        window.set_title(attributes.title);
-
-        // Set fullscreen/maximized if so was requested.
-        match attributes.fullscreen {
-            Some(Fullscreen::Exclusive(_)) => {
-                warn!("`Fullscreen::Exclusive` is ignored on Wayland")
+        let window = match platform_attributes.layer {
+            Some(layer) => {
+                let layer_shell = event_loop_window_target
+                    .env
+                    .get_global::<ZwlrLayerShellV1>()
+                    .ok_or_else(|| {
+                        os_error!(OsError::WaylandMisc(
+                            "compositor doesn't support wlr-layer-shell."
+                        ))
+                    })?;
+                let attributes = LayerSurfaceAttributes {
+                    layer,
+                    anchor: platform_attributes.layer_anchor,
+                    exclusive_zone: platform_attributes.layer_exclusive_zone,
+                    keyboard_interactivity: platform_attributes.layer_keyboard_interactivity,
+                    namespace: platform_attributes
+                        .app_id
+                        .unwrap_or_else(|| String::from("winit")),
+                };
+                WindowShell::Layer(LayerSurface::new(
+                    &layer_shell,
+                    surface.clone(),
+                    LogicalSize::new(width, height),
+                    attributes,
+                ))
             }
-            Some(Fullscreen::Borderless(monitor)) => {
-                let monitor =
-                    monitor.and_then(|RootMonitorHandle { inner: monitor }| match monitor {
-                        PlatformMonitorHandle::Wayland(monitor) => Some(monitor.proxy),
-                        #[cfg(feature = "x11")]
-                        PlatformMonitorHandle::X(_) => None,
-                    });
-
-                window.set_fullscreen(monitor.as_ref());
-            }
-            None => {
-                if attributes.maximized {
-                    window.set_maximized();
-                }
-            }
-        }
+            None => WindowShell::Xdg(create_xdg_window(
+                event_loop_window_target,
+                surface.clone(),
+                (width, height),
+                attributes,
+                platform_attributes,
+                scale_factor,
+                maximized.clone(),
+                fullscreen.clone(),
+                &mut decorations,
+            )?),
+        };
 
         let size = Arc::new(Mutex::new(LogicalSize::new(width, height)));
+        let scale_factor = Arc::new(Mutex::new(scale_factor));
 
         // We should trigger redraw and commit the surface for the newly created window.
         let mut window_update = WindowUpdate::new();
@@ -207,8 +221,13 @@ impl Window {
             &event_loop_window_target.env,
             window,
             size.clone(),
+            scale_factor.clone(),
+            surface_scaling,
+            decorations,
             window_requests.clone(),
         );
+        #[cfg(feature = "accesskit")]
+        let accessibility = window_handle.accessibility.clone();
 
         let mut winit_state = event_loop_window_target.state.borrow_mut();
 
@@ -235,6 +254,15 @@ impl Window {
         let window_handle = winit_state.window_map.get_mut(&window_id).unwrap();
         window_handle.window.refresh();
 
+        // Activate the window with the token we were launched with.
+        if let Some(token) = activation_token {
+            if let Some(xdg_activation) =
+                event_loop_window_target.env.get_global::<XdgActivationV1>()
+            {
+                xdg_activation.activate(token.into_raw(), &surface);
+            }
+        }
+
         let output_manager_handle = event_loop_window_target.output_manager.handle();
 
         let window = Self {
@@ -243,17 +271,143 @@ impl Window {
             display: event_loop_window_target.display.clone(),
             output_manager_handle,
             size,
+            scale_factor,
+            decoration_mode,
             window_requests,
             event_loop_awakener: event_loop_window_target.event_loop_awakener.clone(),
             fullscreen,
             maximized,
             windowing_features,
+            #[cfg(feature = "accesskit")]
+            accessibility,
+            #[cfg(feature = "accesskit")]
+            accessibility_sink: event_loop_window_target.accessibility_sink.clone(),
         };
 
         Ok(window)
     }
 }
 
+/// Creates the SCTK window giving `surface` the role of a regular window.
+#[allow(clippy::too_many_arguments)]
+fn create_xdg_window<T>(
+    event_loop_window_target: &EventLoopWindowTarget<T>,
+    surface: WlSurface,
+    (width, height): (u32, u32),
+    attributes: WindowAttributes,
+    platform_attributes: PlatformAttributes,
+    scale_factor: f64,
+    maximized: Arc<AtomicBool>,
+    fullscreen: Arc<AtomicBool>,
+    decorations: &mut DecorationState,
+) -> Result<SctkWindow<WinitFrame>, RootOsError> {
+    let window_id = super::make_wid(&surface);
+    let theme_manager = event_loop_window_target.theme_manager.clone();
+    let mut window = event_loop_window_target
+        .env
+        .create_window::<WinitFrame, _>(
+            surface.clone(),
+            Some(theme_manager),
+            (width, height),
+            move |event, mut dispatch_data| {
+                use sctk::window::{Event, State};
+
+                let winit_state = dispatch_data.get::<WinitState>().unwrap();
+                let mut window_update = winit_state.window_updates.get_mut(&window_id).unwrap();
+
+                match event {
+                    Event::Refresh => {
+                        window_update.refresh_frame = true;
+                    }
+                    Event::Configure { new_size, states } => {
+                        let is_maximized = states.contains(&State::Maximized);
+                        maximized.store(is_maximized, Ordering::Relaxed);
+                        let is_fullscreen = states.contains(&State::Fullscreen);
+                        fullscreen.store(is_fullscreen, Ordering::Relaxed);
+
+                        window_update.refresh_frame = true;
+                        window_update.redraw_requested = true;
+                        if let Some((w, h)) = new_size {
+                            window_update.size = Some(LogicalSize::new(w, h));
+                        }
+
+                        // The compositor may have picked another decoration mode.
+                        if let Some(window_handle) = winit_state.window_map.get_mut(&window_id) {
+                            if let Some(mode) = window_handle.decorations.update_mode() {
+                                winit_state.event_sink.push_window_event(
+                                    WindowEvent::DecorationModeChanged(mode),
+                                    window_id,
+                                );
+                            }
+                        }
+                    }
+                    Event::Close => {
+                        window_update.close_window = true;
+                    }
+                }
+            },
+        )
+        .map_err(|_| os_error!(OsError::WaylandMisc("failed to create window.")))?;
+
+    // Set decorations.
+    window.set_frame_config(decorations.frame_hidden());
+    decorations.apply(&mut window);
+
+    // Without this commit here at least on kwin 5.23.3 the initial configure
+    // will have a size (1,1), the second configure including the decoration
+    // mode will have the min_size as its size. With this commit the initial
+    // configure will have no size, the application will draw it's content
+    // with the initial size and everything works as expected afterwards.
+    window.surface().commit();
+
+    // Min dimensions.
+    let min_size = attributes
+        .min_inner_size
+        .map(|size| size.to_logical::<f64>(scale_factor).into());
+    window.set_min_size(min_size);
+
+    // Max dimensions.
+    let max_size = attributes
+        .max_inner_size
+        .map(|size| size.to_logical::<f64>(scale_factor).into());
+    window.set_max_size(max_size);
+
+    // Set Wayland specific window attributes.
+    if let Some(app_id) = platform_attributes.app_id {
+        window.set_app_id(app_id);
+    }
+
+    // Set common window attributes.
+    //
+    // We set resizable after other attributes, since it touches min and max size under
+    // the hood.
+    window.set_resizable(attributes.resizable);
+    window.set_title(attributes.title);
+
+    // Set fullscreen/maximized if so was requested.
+    match attributes.fullscreen {
+        Some(Fullscreen::Exclusive(_)) => {
+            warn!("`Fullscreen::Exclusive` is ignored on Wayland")
+        }
+        Some(Fullscreen::Borderless(monitor)) => {
+            let monitor = monitor.and_then(|RootMonitorHandle { inner: monitor }| match monitor {
+                PlatformMonitorHandle::Wayland(monitor) => Some(monitor.proxy),
+                #[cfg(feature = "x11")]
+                PlatformMonitorHandle::X(_) => None,
+            });
+
+            window.set_fullscreen(monitor.as_ref());
+        }
+        None => {
+            if attributes.maximized {
+                window.set_maximized();
+            }
+        }
+    }
+
+    Ok(window)
+}
+
 impl Window {
     #[inline]
     pub fn id(&self) -> WindowId {
@@ -265,6 +419,11 @@ impl Window {
         self.send_request(WindowRequest::Title(title.to_owned()));
     }
 
+    #[inline]
+    pub fn set_app_id(&self, app_id: String) {
+        self.send_request(WindowRequest::AppId(app_id));
+    }
+
     #[inline]
     pub fn set_visible(&self, _visible: bool) {
         // Not possible on Wayland.
@@ -291,10 +450,7 @@ impl Window {
     }
 
     pub fn inner_size(&self) -> PhysicalSize<u32> {
-        self.size
-            .lock()
-            .unwrap()
-            .to_physical(self.scale_factor() as f64)
+        self.size.lock().unwrap().to_physical(self.scale_factor())
     }
 
     #[inline]
@@ -304,15 +460,12 @@ impl Window {
 
     #[inline]
     pub fn outer_size(&self) -> PhysicalSize<u32> {
-        self.size
-            .lock()
-            .unwrap()
-            .to_physical(self.scale_factor() as f64)
+        self.size.lock().unwrap().to_physical(self.scale_factor())
     }
 
     #[inline]
     pub fn set_inner_size(&self, size: Size) {
-        let scale_factor = self.scale_factor() as f64;
+        let scale_factor = self.scale_factor();
 
         let size = size.to_logical::<u32>(scale_factor);
         *self.size.lock().unwrap() = size;
@@ -322,7 +475,7 @@ impl Window {
 
     #[inline]
     pub fn set_min_inner_size(&self, dimensions: Option<Size>) {
-        let scale_factor = self.scale_factor() as f64;
+        let scale_factor = self.scale_factor();
         let size = dimensions.map(|size| size.to_logical::<u32>(scale_factor));
 
         self.send_request(WindowRequest::MinSize(size));
@@ -330,7 +483,7 @@ impl Window {
 
     #[inline]
     pub fn set_max_inner_size(&self, dimensions: Option<Size>) {
-        let scale_factor = self.scale_factor() as f64;
+        let scale_factor = self.scale_factor();
         let size = dimensions.map(|size| size.to_logical::<u32>(scale_factor));
 
         self.send_request(WindowRequest::MaxSize(size));
@@ -347,10 +500,8 @@ impl Window {
     }
 
     #[inline]
-    pub fn scale_factor(&self) -> u32 {
-        // The scale factor from `get_surface_scale_factor` is always greater than zero, so
-        // u32 conversion is safe.
-        sctk::get_surface_scale_factor(&self.surface) as u32
+    pub fn scale_factor(&self) -> f64 {
+        *self.scale_factor.lock().unwrap()
     }
 
     #[inline]
@@ -429,6 +580,11 @@ impl Window {
         self.send_request(WindowRequest::ShowCursor(visible));
     }
 
+    #[inline]
+    pub fn set_key_repeat_enabled(&self, enabled: bool) {
+        self.send_request(WindowRequest::KeyRepeat(enabled));
+    }
+
     #[inline]
     pub fn set_cursor_grab(&self, grab: bool) -> Result<(), ExternalError> {
         if !self.windowing_features.cursor_grab() {
@@ -449,6 +605,44 @@ impl Window {
         self.send_request(WindowRequest::Attention(request_type));
     }
 
+    pub fn set_viewport_scaling(&self, viewport_scaling: bool) -> Result<(), NotSupportedError> {
+        if !self.windowing_features.viewporter() {
+            return Err(NotSupportedError::new());
+        }
+
+        self.send_request(WindowRequest::ViewportScaling(viewport_scaling));
+
+        Ok(())
+    }
+
+    pub fn set_preferred_decoration_mode(&self, mode: Option<DecorationMode>) {
+        self.send_request(WindowRequest::PreferredDecorationMode(mode));
+    }
+
+    #[inline]
+    pub fn decoration_mode(&self) -> Option<DecorationMode> {
+        *self.decoration_mode.lock().unwrap()
+    }
+
+    pub fn focus_window(&self) {
+        if !self.windowing_features.xdg_activation() {
+            warn!("`focus_window` isn't supported");
+            return;
+        }
+
+        self.send_request(WindowRequest::Focus);
+    }
+
+    pub fn request_activation_token(&self) -> Result<(), NotSupportedError> {
+        if !self.windowing_features.xdg_activation() {
+            return Err(NotSupportedError::new());
+        }
+
+        self.send_request(WindowRequest::ActivationToken);
+
+        Ok(())
+    }
+
     #[inline]
     pub fn set_cursor_position(&self, _: Position) -> Result<(), ExternalError> {
         // XXX This is possible if the locked pointer is being used. We don't have any
@@ -470,7 +664,7 @@ impl Window {
 
     #[inline]
     pub fn set_ime_position(&self, position: Position) {
-        let scale_factor = self.scale_factor() as f64;
+        let scale_factor = self.scale_factor();
         let position = position.to_logical(scale_factor);
         self.send_request(WindowRequest::IMEPosition(position));
     }
@@ -501,6 +695,20 @@ impl Window {
         None
     }
 
+    #[cfg(feature = "accesskit")]
+    pub fn enable_accessibility(&self, source: Box<dyn TreeSource>) {
+        self.accessibility.enable(
+            PlatformWindowId::Wayland(self.window_id),
+            source,
+            self.accessibility_sink.clone(),
+        );
+    }
+
+    #[cfg(feature = "accesskit")]
+    pub fn update_accessibility_tree(&self, update: impl FnOnce() -> TreeUpdate) {
+        self.accessibility.update_tree(update);
+    }
+
     #[inline]
     pub fn raw_window_handle(&self) -> WaylandHandle {
         let mut handle = WaylandHandle::empty();
@@ -509,6 +717,40 @@ impl Window {
         handle
     }
 
+    #[cfg(feature = "rwh_05")]
+    pub fn raw_window_handle_rwh_05(&self) -> rwh_05::RawWindowHandle {
+        let mut handle = rwh_05::WaylandWindowHandle::empty();
+        handle.surface = self.surface.as_ref().c_ptr() as *mut _;
+        rwh_05::RawWindowHandle::Wayland(handle)
+    }
+
+    #[cfg(feature = "rwh_05")]
+    pub fn raw_display_handle_rwh_05(&self) -> rwh_05::RawDisplayHandle {
+        let mut handle = rwh_05::WaylandDisplayHandle::empty();
+        handle.display = self.display.get_display_ptr() as *mut _;
+        rwh_05::RawDisplayHandle::Wayland(handle)
+    }
+
+    #[cfg(feature = "rwh_06")]
+    pub fn raw_window_handle_rwh_06(&self) -> Result<rwh_06::RawWindowHandle, rwh_06::HandleError> {
+        let surface = std::ptr::NonNull::new(self.surface.as_ref().c_ptr() as *mut _)
+            .ok_or(rwh_06::HandleError::Unavailable)?;
+        Ok(rwh_06::RawWindowHandle::Wayland(
+            rwh_06::WaylandWindowHandle::new(surface),
+        ))
+    }
+
+    #[cfg(feature = "rwh_06")]
+    pub fn raw_display_handle_rwh_06(
+        &self,
+    ) -> Result<rwh_06::RawDisplayHandle, rwh_06::HandleError> {
+        let display = std::ptr::NonNull::new(self.display.get_display_ptr() as *mut _)
+            .ok_or(rwh_06::HandleError::Unavailable)?;
+        Ok(rwh_06::RawDisplayHandle::Wayland(
+            rwh_06::WaylandDisplayHandle::new(display),
+        ))
+    }
+
     #[inline]
     fn send_request(&self, request: WindowRequest) {
         self.window_requests.lock().unwrap().push(request);
@@ -0,0 +1,106 @@
+use sctk::reexports::client::protocol::wl_surface::WlSurface;
+use sctk::reexports::client::{Attached, Main};
+use sctk::reexports::protocols::viewporter::client::wp_viewport::WpViewport;
+use sctk::reexports::protocols::viewporter::client::wp_viewporter::WpViewporter;
+
+use crate::dpi::LogicalSize;
+use crate::platform_impl::wayland;
+use crate::platform_impl::wayland::event_loop::WinitState;
+use crate::platform_impl::wayland::protocols::fractional_scale_v1::client::wp_fractional_scale_manager_v1::WpFractionalScaleManagerV1;
+use crate::platform_impl::wayland::protocols::fractional_scale_v1::client::wp_fractional_scale_v1::{
+    self, WpFractionalScaleV1,
+};
+
+/// The denominator of the scales sent by `wp_fractional_scale_v1`.
+const SCALE_DENOMINATOR: f64 = 120.;
+
+/// Scaling of the buffers of a surface by the compositor, through wp-viewporter.
+///
+/// The viewport maps the buffers to the logical size of the window when either:
+/// - the compositor supports wp-fractional-scale-v1, in which case buffers are rendered at the
+///   physical size for fractional scale factors;
+/// - the application asked for its buffers to be stretched to the window whatever their size,
+///   see `WindowExtUnix::set_viewport_scaling`.
+///
+/// The buffer scale of the surface stays at 1 in both cases.
+pub struct SurfaceScaling {
+    viewport: Main<WpViewport>,
+    fractional_scale: Option<Main<WpFractionalScaleV1>>,
+    viewport_scaling: bool,
+}
+
+impl SurfaceScaling {
+    pub fn new(
+        viewporter: &Attached<WpViewporter>,
+        fractional_scale_manager: Option<&Attached<WpFractionalScaleManagerV1>>,
+        surface: &WlSurface,
+    ) -> Self {
+        let viewport = viewporter.get_viewport(surface);
+
+        let window_id = wayland::make_wid(surface);
+        let fractional_scale = fractional_scale_manager.map(|fractional_scale_manager| {
+            let fractional_scale = fractional_scale_manager.get_fractional_scale(surface);
+            fractional_scale.quick_assign(move |_, event, mut dispatch_data| {
+                let wp_fractional_scale_v1::Event::PreferredScale { scale } = event;
+
+                let winit_state = dispatch_data.get::<WinitState>().unwrap();
+                let window_update = match winit_state.window_updates.get_mut(&window_id) {
+                    Some(window_update) => window_update,
+                    None => return,
+                };
+
+                window_update.scale_factor = Some(scale as f64 / SCALE_DENOMINATOR);
+                window_update.redraw_requested = true;
+            });
+            fractional_scale
+        });
+
+        Self {
+            viewport,
+            fractional_scale,
+            viewport_scaling: false,
+        }
+    }
+
+    /// Whether the buffer scale of the surface must stay at 1.
+    pub fn overrides_buffer_scale(&self) -> bool {
+        self.fractional_scale.is_some() || self.viewport_scaling
+    }
+
+    /// Sets the size the buffers are scaled to, applied with the next commit of the surface.
+    pub fn set_logical_size(&self, size: LogicalSize<u32>) {
+        if self.overrides_buffer_scale() {
+            self.viewport
+                .set_destination(size.width as i32, size.height as i32);
+        }
+    }
+
+    /// Stretches the buffers to `size` whatever their size, or goes back to buffers matching
+    /// the scale factor.
+    pub fn set_viewport_scaling(
+        &mut self,
+        surface: &WlSurface,
+        viewport_scaling: bool,
+        size: LogicalSize<u32>,
+    ) {
+        self.viewport_scaling = viewport_scaling;
+
+        if self.overrides_buffer_scale() {
+            surface.set_buffer_scale(1);
+            self.set_logical_size(size);
+        } else {
+            // The surface size comes from the buffer again.
+            surface.set_buffer_scale(sctk::get_surface_scale_factor(surface));
+            self.viewport.set_destination(-1, -1);
+        }
+    }
+}
+
+impl Drop for SurfaceScaling {
+    fn drop(&mut self) {
+        if let Some(fractional_scale) = self.fractional_scale.as_ref() {
+            fractional_scale.destroy();
+        }
+        self.viewport.destroy();
+    }
+}
@@ -0,0 +1,228 @@
+use sctk::reexports::client::protocol::wl_output::WlOutput;
+use sctk::reexports::client::protocol::wl_surface::WlSurface;
+use sctk::reexports::client::{Attached, Main};
+use sctk::reexports::protocols::wlr::unstable::layer_shell::v1::client::zwlr_layer_shell_v1::{
+    self, ZwlrLayerShellV1,
+};
+use sctk::reexports::protocols::wlr::unstable::layer_shell::v1::client::zwlr_layer_surface_v1::{
+    self, ZwlrLayerSurfaceV1,
+};
+
+use sctk::window::Window;
+
+use crate::dpi::LogicalSize;
+use crate::platform::unix::{KeyboardInteractivity, Layer, LayerAnchor};
+use crate::platform_impl::wayland;
+use crate::platform_impl::wayland::event_loop::WinitState;
+use crate::window::DecorationMode;
+
+use super::frame::{DecorationState, WinitFrame};
+
+/// The role of the surface of a window.
+pub enum WindowShell {
+    /// A regular window, decorated by SCTK when the server doesn't.
+    Xdg(Window<WinitFrame>),
+
+    /// A wlr-layer-shell surface, which ignores all the requests specific to regular windows.
+    Layer(LayerSurface),
+}
+
+impl WindowShell {
+    pub fn surface(&self) -> &WlSurface {
+        match self {
+            WindowShell::Xdg(window) => window.surface(),
+            WindowShell::Layer(layer) => &layer.surface,
+        }
+    }
+
+    /// Applies the size the server configured.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        if let WindowShell::Xdg(window) = self {
+            window.resize(width, height);
+        }
+    }
+
+    pub fn refresh(&mut self) {
+        if let WindowShell::Xdg(window) = self {
+            window.refresh();
+        }
+    }
+
+    pub fn set_fullscreen(&self, output: Option<&WlOutput>) {
+        if let WindowShell::Xdg(window) = self {
+            window.set_fullscreen(output);
+        }
+    }
+
+    pub fn unset_fullscreen(&self) {
+        if let WindowShell::Xdg(window) = self {
+            window.unset_fullscreen();
+        }
+    }
+
+    pub fn set_maximized(&self) {
+        if let WindowShell::Xdg(window) = self {
+            window.set_maximized();
+        }
+    }
+
+    pub fn unset_maximized(&self) {
+        if let WindowShell::Xdg(window) = self {
+            window.unset_maximized();
+        }
+    }
+
+    pub fn set_minimized(&self) {
+        if let WindowShell::Xdg(window) = self {
+            window.set_minimized();
+        }
+    }
+
+    /// Applies the decorations, returning the new decoration mode when it changed right away.
+    pub fn apply_decorations(
+        &mut self,
+        decorations: &mut DecorationState,
+    ) -> Option<DecorationMode> {
+        match self {
+            WindowShell::Xdg(window) => decorations.apply(window),
+            WindowShell::Layer(_) => None,
+        }
+    }
+
+    pub fn set_resizable(&mut self, resizable: bool) {
+        if let WindowShell::Xdg(window) = self {
+            window.set_resizable(resizable);
+        }
+    }
+
+    pub fn set_title(&mut self, title: String) {
+        if let WindowShell::Xdg(window) = self {
+            window.set_title(title);
+        }
+    }
+
+    pub fn set_app_id(&self, app_id: String) {
+        // The namespace of layer-shell surfaces can't change.
+        if let WindowShell::Xdg(window) = self {
+            window.set_app_id(app_id);
+        }
+    }
+
+    pub fn set_min_size(&mut self, size: Option<(u32, u32)>) {
+        if let WindowShell::Xdg(window) = self {
+            window.set_min_size(size);
+        }
+    }
+
+    pub fn set_max_size(&mut self, size: Option<(u32, u32)>) {
+        if let WindowShell::Xdg(window) = self {
+            window.set_max_size(size);
+        }
+    }
+
+    /// Requests a new size from the application.
+    pub fn request_size(&mut self, width: u32, height: u32) {
+        match self {
+            WindowShell::Xdg(window) => window.resize(width, height),
+            // Applied with the next commit of the surface.
+            WindowShell::Layer(layer) => layer.layer_surface.set_size(width, height),
+        }
+    }
+}
+
+/// A surface with the role of a wlr-layer-shell surface.
+pub struct LayerSurface {
+    surface: WlSurface,
+    layer_surface: Main<ZwlrLayerSurfaceV1>,
+}
+
+/// The settings of a layer-shell surface, from the `WindowBuilder`.
+pub struct LayerSurfaceAttributes {
+    pub layer: Layer,
+    pub anchor: LayerAnchor,
+    pub exclusive_zone: i32,
+    pub keyboard_interactivity: KeyboardInteractivity,
+    pub namespace: String,
+}
+
+impl LayerSurface {
+    pub fn new(
+        layer_shell: &Attached<ZwlrLayerShellV1>,
+        surface: WlSurface,
+        size: LogicalSize<u32>,
+        attributes: LayerSurfaceAttributes,
+    ) -> Self {
+        let layer = match attributes.layer {
+            Layer::Background => zwlr_layer_shell_v1::Layer::Background,
+            Layer::Bottom => zwlr_layer_shell_v1::Layer::Bottom,
+            Layer::Top => zwlr_layer_shell_v1::Layer::Top,
+            Layer::Overlay => zwlr_layer_shell_v1::Layer::Overlay,
+        };
+        let layer_surface =
+            layer_shell.get_layer_surface(&surface, None, layer, attributes.namespace);
+
+        layer_surface.set_size(size.width, size.height);
+        layer_surface.set_anchor(zwlr_layer_surface_v1::Anchor::from_bits_truncate(
+            attributes.anchor.bits() as u32,
+        ));
+        layer_surface.set_exclusive_zone(attributes.exclusive_zone);
+
+        let keyboard_interactivity = match attributes.keyboard_interactivity {
+            KeyboardInteractivity::None => zwlr_layer_surface_v1::KeyboardInteractivity::None,
+            KeyboardInteractivity::Exclusive => {
+                zwlr_layer_surface_v1::KeyboardInteractivity::Exclusive
+            }
+            // `on_demand` only exists since version 4, where `exclusive` is the closest match.
+            KeyboardInteractivity::OnDemand if layer_surface.as_ref().version() < 4 => {
+                zwlr_layer_surface_v1::KeyboardInteractivity::Exclusive
+            }
+            KeyboardInteractivity::OnDemand => {
+                zwlr_layer_surface_v1::KeyboardInteractivity::OnDemand
+            }
+        };
+        layer_surface.set_keyboard_interactivity(keyboard_interactivity);
+
+        let window_id = wayland::make_wid(&surface);
+        layer_surface.quick_assign(move |layer_surface, event, mut dispatch_data| {
+            let winit_state = dispatch_data.get::<WinitState>().unwrap();
+            let window_update = match winit_state.window_updates.get_mut(&window_id) {
+                Some(window_update) => window_update,
+                None => return,
+            };
+
+            match event {
+                zwlr_layer_surface_v1::Event::Configure {
+                    serial,
+                    width,
+                    height,
+                } => {
+                    layer_surface.ack_configure(serial);
+
+                    // A zero dimension leaves it up to us, so we keep the current size.
+                    if width != 0 && height != 0 {
+                        window_update.size = Some(LogicalSize::new(width, height));
+                    }
+                    window_update.redraw_requested = true;
+                }
+                zwlr_layer_surface_v1::Event::Closed => {
+                    window_update.close_window = true;
+                }
+                _ => (),
+            }
+        });
+
+        // The server configures the surface in response to this initial commit, without a buffer.
+        surface.commit();
+
+        Self {
+            surface,
+            layer_surface,
+        }
+    }
+}
+
+impl Drop for LayerSurface {
+    fn drop(&mut self) {
+        self.layer_surface.destroy();
+    }
+}
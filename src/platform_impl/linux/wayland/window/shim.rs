@@ -1,15 +1,18 @@
 use std::cell::Cell;
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
 use sctk::reexports::client::protocol::wl_output::WlOutput;
+use sctk::reexports::client::protocol::wl_surface::WlSurface;
 use sctk::reexports::client::Attached;
 use sctk::reexports::protocols::staging::xdg_activation::v1::client::xdg_activation_token_v1;
 use sctk::reexports::protocols::staging::xdg_activation::v1::client::xdg_activation_v1::XdgActivationV1;
 
 use sctk::environment::Environment;
-use sctk::window::{Decorations, FallbackFrame, Window};
 
 use crate::dpi::{LogicalPosition, LogicalSize};
+#[cfg(feature = "accesskit")]
+use crate::platform_impl::platform::accessibility::Accessibility;
 
 use crate::event::WindowEvent;
 use crate::platform_impl::wayland;
@@ -18,7 +21,11 @@ use crate::platform_impl::wayland::event_loop::WinitState;
 use crate::platform_impl::wayland::seat::pointer::WinitPointer;
 use crate::platform_impl::wayland::seat::text_input::TextInputHandler;
 use crate::platform_impl::wayland::WindowId;
-use crate::window::{CursorIcon, UserAttentionType};
+use crate::window::{ActivationToken, CursorIcon, DecorationMode, UserAttentionType};
+
+use super::frame::DecorationState;
+use super::scale::SurfaceScaling;
+use super::shell::WindowShell;
 
 /// A request to SCTK window from Winit window.
 #[derive(Debug, Clone)]
@@ -40,6 +47,9 @@ pub enum WindowRequest {
     /// Grab cursor.
     GrabCursor(bool),
 
+    /// Deliver key auto-repeats or not.
+    KeyRepeat(bool),
+
     /// Drag window.
     DragWindow,
 
@@ -52,12 +62,18 @@ pub enum WindowRequest {
     /// Request decorations change.
     Decorate(bool),
 
+    /// Change the side preferably drawing the decorations.
+    PreferredDecorationMode(Option<DecorationMode>),
+
     /// Make the window resizeable.
     Resizeable(bool),
 
     /// Set the title for window.
     Title(String),
 
+    /// Set the application ID of the window.
+    AppId(String),
+
     /// Min size.
     MinSize(Option<LogicalSize<u32>>),
 
@@ -67,6 +83,9 @@ pub enum WindowRequest {
     /// New frame size.
     FrameSize(LogicalSize<u32>),
 
+    /// Stretch the buffers to the window whatever their size.
+    ViewportScaling(bool),
+
     /// Set IME window position.
     IMEPosition(LogicalPosition<u32>),
 
@@ -75,6 +94,12 @@ pub enum WindowRequest {
     /// `None` unsets the attention request.
     Attention(Option<UserAttentionType>),
 
+    /// Bring the window to the front and focus it.
+    Focus,
+
+    /// Request an activation token for another application.
+    ActivationToken,
+
     /// Redraw was requested.
     Redraw,
 
@@ -89,7 +114,7 @@ pub struct WindowUpdate {
     pub size: Option<LogicalSize<u32>>,
 
     /// New scale factor.
-    pub scale_factor: Option<i32>,
+    pub scale_factor: Option<f64>,
 
     /// Whether `redraw` was requested.
     pub redraw_requested: bool,
@@ -112,6 +137,15 @@ impl WindowUpdate {
         }
     }
 
+    /// Whether there's nothing to update.
+    pub fn is_empty(&self) -> bool {
+        self.size.is_none()
+            && self.scale_factor.is_none()
+            && !self.redraw_requested
+            && !self.refresh_frame
+            && !self.close_window
+    }
+
     pub fn take(&mut self) -> Self {
         let size = self.size.take();
         let scale_factor = self.scale_factor.take();
@@ -135,15 +169,34 @@ impl WindowUpdate {
     }
 }
 
+/// What an activation token is requested for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActivationTokenUse {
+    /// Activate the window itself.
+    Activate,
+
+    /// Hand the token to the application, to activate another one.
+    Export,
+}
+
 /// A handle to perform operations on SCTK window
 /// and react to events.
 pub struct WindowHandle {
     /// An actual window.
-    pub window: Window<FallbackFrame>,
+    pub window: WindowShell,
 
     /// The current size of the window.
     pub size: Arc<Mutex<LogicalSize<u32>>>,
 
+    /// The current scale factor of the window.
+    pub scale_factor: Arc<Mutex<f64>>,
+
+    /// Scaling of the surface by the compositor, when it supports wp-viewporter.
+    pub surface_scaling: Option<SurfaceScaling>,
+
+    /// The decorations of the window.
+    pub decorations: DecorationState,
+
     /// A pending requests to SCTK window.
     pub pending_window_requests: Arc<Mutex<Vec<WindowRequest>>>,
 
@@ -167,13 +220,23 @@ pub struct WindowHandle {
 
     /// Indicator whether user attention is requested.
     attention_requested: Cell<bool>,
+
+    /// Whether key auto-repeats are delivered to the window.
+    pub key_repeat_enabled: Cell<bool>,
+
+    /// The accessibility adapter, shared with the window.
+    #[cfg(feature = "accesskit")]
+    pub accessibility: Arc<Accessibility>,
 }
 
 impl WindowHandle {
     pub fn new(
         env: &Environment<WinitEnv>,
-        window: Window<FallbackFrame>,
+        window: WindowShell,
         size: Arc<Mutex<LogicalSize<u32>>>,
+        scale_factor: Arc<Mutex<f64>>,
+        surface_scaling: Option<SurfaceScaling>,
+        decorations: DecorationState,
         pending_window_requests: Arc<Mutex<Vec<WindowRequest>>>,
     ) -> Self {
         let xdg_activation = env.get_global::<XdgActivationV1>();
@@ -181,6 +244,9 @@ impl WindowHandle {
         Self {
             window,
             size,
+            scale_factor,
+            surface_scaling,
+            decorations,
             pending_window_requests,
             cursor_icon: Cell::new(CursorIcon::Default),
             confined: Cell::new(false),
@@ -189,9 +255,16 @@ impl WindowHandle {
             text_inputs: Vec::new(),
             xdg_activation,
             attention_requested: Cell::new(false),
+            key_repeat_enabled: Cell::new(true),
+            #[cfg(feature = "accesskit")]
+            accessibility: Default::default(),
         }
     }
 
+    pub fn scale_factor(&self) -> f64 {
+        *self.scale_factor.lock().unwrap()
+    }
+
     pub fn set_cursor_grab(&self, grab: bool) {
         // The new requested state matches the current confine status, return.
         if self.confined.get() == grab {
@@ -211,17 +284,24 @@ impl WindowHandle {
     }
 
     pub fn set_user_attention(&self, request_type: Option<UserAttentionType>) {
-        let xdg_activation = match self.xdg_activation.as_ref() {
-            None => return,
-            Some(xdg_activation) => xdg_activation,
-        };
-
         //  Urgency is only removed by the compositor and there's no need to raise urgency when it
         //  was already raised.
-        if request_type.is_none() || self.attention_requested.get() {
+        if self.xdg_activation.is_none() || request_type.is_none() || self.attention_requested.get()
+        {
             return;
         }
 
+        self.request_activation_token(ActivationTokenUse::Activate);
+        self.attention_requested.replace(true);
+    }
+
+    /// Requests an activation token for the window from the compositor.
+    pub fn request_activation_token(&self, token_use: ActivationTokenUse) {
+        let xdg_activation = match self.xdg_activation.as_ref() {
+            None => return,
+            Some(xdg_activation) => xdg_activation,
+        };
+
         let xdg_activation_token = xdg_activation.get_activation_token();
         let surface = self.window.surface();
         let window_id = wayland::make_wid(surface);
@@ -234,22 +314,41 @@ impl WindowHandle {
             };
 
             let winit_state = dispatch_data.get::<WinitState>().unwrap();
-            let window_handle = match winit_state.window_map.get_mut(&window_id) {
-                Some(window_handle) => window_handle,
-                None => return,
-            };
+            match token_use {
+                ActivationTokenUse::Activate => {
+                    let window_handle = match winit_state.window_map.get_mut(&window_id) {
+                        Some(window_handle) => window_handle,
+                        None => return,
+                    };
+
+                    let surface = window_handle.window.surface();
+                    xdg_activation.activate(token, surface);
 
-            let surface = window_handle.window.surface();
-            xdg_activation.activate(token, surface);
+                    // Mark that attention request was done.
+                    window_handle.attention_requested.replace(false);
+                }
+                ActivationTokenUse::Export => {
+                    if winit_state.window_map.contains_key(&window_id) {
+                        winit_state.event_sink.push_window_event(
+                            WindowEvent::ActivationTokenDone(ActivationToken::from_raw(token)),
+                            window_id,
+                        );
+                    }
+                }
+            }
 
-            // Mark that attention request was done and drop the token.
-            window_handle.attention_requested.replace(false);
+            // Drop the token.
             xdg_token.destroy();
         });
 
+        // Compositors only honor tokens created in response to input, so pass them the latest
+        // input event on the window.
+        if let Some(pointer) = self.pointers.last() {
+            xdg_activation_token.set_serial(pointer.latest_serial(), pointer.seat());
+        }
+
         xdg_activation_token.set_surface(surface);
         xdg_activation_token.commit();
-        self.attention_requested.replace(true);
     }
 
     /// Pointer appeared over the window.
@@ -329,12 +428,26 @@ impl WindowHandle {
     }
 
     pub fn drag_window(&self) {
-        for pointer in self.pointers.iter() {
-            pointer.drag_window(&self.window);
+        if let WindowShell::Xdg(window) = &self.window {
+            for pointer in self.pointers.iter() {
+                pointer.drag_window(window);
+            }
         }
     }
 }
 
+/// The scale factor of the window of `surface`, or the integer scale of its outputs when it
+/// isn't a window anymore.
+pub fn surface_scale_factor(
+    window_map: &HashMap<WindowId, WindowHandle>,
+    surface: &WlSurface,
+) -> f64 {
+    window_map
+        .get(&wayland::make_wid(surface))
+        .map(WindowHandle::scale_factor)
+        .unwrap_or_else(|| sctk::get_surface_scale_factor(surface) as f64)
+}
+
 #[inline]
 pub fn handle_window_requests(winit_state: &mut WinitState) {
     let window_map = &mut winit_state.window_map;
@@ -364,6 +477,9 @@ pub fn handle_window_requests(winit_state: &mut WinitState) {
                 WindowRequest::GrabCursor(grab) => {
                     window_handle.set_cursor_grab(grab);
                 }
+                WindowRequest::KeyRepeat(enabled) => {
+                    window_handle.key_repeat_enabled.set(enabled);
+                }
                 WindowRequest::DragWindow => {
                     window_handle.drag_window();
                 }
@@ -378,12 +494,34 @@ pub fn handle_window_requests(winit_state: &mut WinitState) {
                     window_handle.window.set_minimized();
                 }
                 WindowRequest::Decorate(decorate) => {
-                    let decorations = match decorate {
-                        true => Decorations::FollowServer,
-                        false => Decorations::None,
-                    };
+                    window_handle.decorations.set_decorated(decorate);
+                    if let Some(mode) = window_handle
+                        .window
+                        .apply_decorations(&mut window_handle.decorations)
+                    {
+                        let event_sink = &mut winit_state.event_sink;
+                        event_sink.push_window_event(
+                            WindowEvent::DecorationModeChanged(mode),
+                            *window_id,
+                        );
+                    }
 
-                    window_handle.window.set_decorate(decorations);
+                    // We should refresh the frame to apply decorations change.
+                    let window_update = window_updates.get_mut(window_id).unwrap();
+                    window_update.refresh_frame = true;
+                }
+                WindowRequest::PreferredDecorationMode(mode) => {
+                    window_handle.decorations.set_preferred_mode(mode);
+                    if let Some(mode) = window_handle
+                        .window
+                        .apply_decorations(&mut window_handle.decorations)
+                    {
+                        let event_sink = &mut winit_state.event_sink;
+                        event_sink.push_window_event(
+                            WindowEvent::DecorationModeChanged(mode),
+                            *window_id,
+                        );
+                    }
 
                     // We should refresh the frame to apply decorations change.
                     let window_update = window_updates.get_mut(window_id).unwrap();
@@ -403,6 +541,9 @@ pub fn handle_window_requests(winit_state: &mut WinitState) {
                     let window_update = window_updates.get_mut(window_id).unwrap();
                     window_update.refresh_frame = true;
                 }
+                WindowRequest::AppId(app_id) => {
+                    window_handle.window.set_app_id(app_id);
+                }
                 WindowRequest::MinSize(size) => {
                     let size = size.map(|size| (size.width, size.height));
                     window_handle.window.set_min_size(size);
@@ -419,15 +560,34 @@ pub fn handle_window_requests(winit_state: &mut WinitState) {
                 }
                 WindowRequest::FrameSize(size) => {
                     // Set new size.
-                    window_handle.window.resize(size.width, size.height);
+                    window_handle.window.request_size(size.width, size.height);
 
                     // We should refresh the frame after resize.
                     let window_update = window_updates.get_mut(window_id).unwrap();
                     window_update.refresh_frame = true;
                 }
+                WindowRequest::ViewportScaling(viewport_scaling) => {
+                    let size = *window_handle.size.lock().unwrap();
+                    if let Some(surface_scaling) = window_handle.surface_scaling.as_mut() {
+                        surface_scaling.set_viewport_scaling(
+                            window_handle.window.surface(),
+                            viewport_scaling,
+                            size,
+                        );
+                    }
+
+                    let window_update = window_updates.get_mut(window_id).unwrap();
+                    window_update.redraw_requested = true;
+                }
                 WindowRequest::Attention(request_type) => {
                     window_handle.set_user_attention(request_type);
                 }
+                WindowRequest::Focus => {
+                    window_handle.request_activation_token(ActivationTokenUse::Activate);
+                }
+                WindowRequest::ActivationToken => {
+                    window_handle.request_activation_token(ActivationTokenUse::Export);
+                }
                 WindowRequest::Redraw => {
                     let window_update = window_updates.get_mut(window_id).unwrap();
                     window_update.redraw_requested = true;
@@ -1,10 +1,4 @@
-use std::{
-    io,
-    os::raw::*,
-    path::{Path, PathBuf},
-    str::Utf8Error,
-    sync::Arc,
-};
+use std::{cell::OnceCell, io, os::raw::*, path::Path, str::Utf8Error, sync::Arc};
 
 use percent_encoding::percent_decode;
 
@@ -89,26 +83,33 @@ impl From<io::Error> for DndDataParseError {
 
 pub struct Dnd {
     xconn: Arc<XConnection>,
-    pub atoms: DndAtoms,
+    // Interned on first use, as most applications never see a drag and drop
+    atoms: OnceCell<DndAtoms>,
     // Populated by XdndEnter event handler
     pub version: Option<c_long>,
     pub type_list: Option<Vec<c_ulong>>,
     // Populated by XdndPosition event handler
     pub source_window: Option<c_ulong>,
     // Populated by SelectionNotify event handler (triggered by XdndPosition event handler)
-    pub result: Option<Result<Vec<PathBuf>, DndDataParseError>>,
+    pub result: Option<Result<Vec<Arc<Path>>, DndDataParseError>>,
 }
 
 impl Dnd {
-    pub fn new(xconn: Arc<XConnection>) -> Result<Self, XError> {
-        let atoms = DndAtoms::new(&xconn)?;
-        Ok(Dnd {
+    pub fn new(xconn: Arc<XConnection>) -> Self {
+        Dnd {
             xconn,
-            atoms,
+            atoms: OnceCell::new(),
             version: None,
             type_list: None,
             source_window: None,
             result: None,
+        }
+    }
+
+    pub fn atoms(&self) -> &DndAtoms {
+        self.atoms.get_or_init(|| {
+            DndAtoms::new(&self.xconn)
+                .expect("Failed to call XInternAtoms when initializing drag and drop")
         })
     }
 
@@ -126,14 +127,14 @@ impl Dnd {
         state: DndState,
     ) -> Result<(), XError> {
         let (accepted, action) = match state {
-            DndState::Accepted => (1, self.atoms.action_private as c_long),
-            DndState::Rejected => (0, self.atoms.none as c_long),
+            DndState::Accepted => (1, self.atoms().action_private as c_long),
+            DndState::Rejected => (0, self.atoms().none as c_long),
         };
         self.xconn
             .send_client_msg(
                 target_window,
                 target_window,
-                self.atoms.status,
+                self.atoms().status,
                 None,
                 [this_window as c_long, accepted, 0, 0, action],
             )
@@ -147,14 +148,14 @@ impl Dnd {
         state: DndState,
     ) -> Result<(), XError> {
         let (accepted, action) = match state {
-            DndState::Accepted => (1, self.atoms.action_private as c_long),
-            DndState::Rejected => (0, self.atoms.none as c_long),
+            DndState::Accepted => (1, self.atoms().action_private as c_long),
+            DndState::Rejected => (0, self.atoms().none as c_long),
         };
         self.xconn
             .send_client_msg(
                 target_window,
                 target_window,
-                self.atoms.finished,
+                self.atoms().finished,
                 None,
                 [this_window as c_long, accepted, action, 0, 0],
             )
@@ -166,15 +167,15 @@ impl Dnd {
         source_window: c_ulong,
     ) -> Result<Vec<ffi::Atom>, util::GetPropertyError> {
         self.xconn
-            .get_property(source_window, self.atoms.type_list, ffi::XA_ATOM)
+            .get_property(source_window, self.atoms().type_list, ffi::XA_ATOM)
     }
 
     pub unsafe fn convert_selection(&self, window: c_ulong, time: c_ulong) {
         (self.xconn.xlib.XConvertSelection)(
             self.xconn.display,
-            self.atoms.selection,
-            self.atoms.uri_list,
-            self.atoms.selection,
+            self.atoms().selection,
+            self.atoms().uri_list,
+            self.atoms().selection,
             window,
             time,
         );
@@ -185,10 +186,10 @@ impl Dnd {
         window: c_ulong,
     ) -> Result<Vec<c_uchar>, util::GetPropertyError> {
         self.xconn
-            .get_property(window, self.atoms.selection, self.atoms.uri_list)
+            .get_property(window, self.atoms().selection, self.atoms().uri_list)
     }
 
-    pub fn parse_data(&self, data: &mut Vec<c_uchar>) -> Result<Vec<PathBuf>, DndDataParseError> {
+    pub fn parse_data(&self, data: &[c_uchar]) -> Result<Vec<Arc<Path>>, DndDataParseError> {
         if !data.is_empty() {
             let mut path_list = Vec::new();
             let decoded = percent_decode(data).decode_utf8()?.into_owned();
@@ -209,7 +210,7 @@ impl Dnd {
                 };
 
                 let path = Path::new(&path_str).canonicalize()?;
-                path_list.push(path);
+                path_list.push(path.into());
             }
             Ok(path_list)
         } else {
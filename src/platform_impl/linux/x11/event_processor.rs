@@ -1,13 +1,18 @@
-use std::{cell::RefCell, collections::HashMap, rc::Rc, slice, sync::Arc};
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    slice,
+    sync::{Arc, Mutex},
+};
 
-use libc::{c_char, c_int, c_long, c_uint, c_ulong};
+use libc::{c_char, c_int, c_long, c_ulong};
 
 use parking_lot::MutexGuard;
 
 use super::{
-    events, ffi, get_xtarget, mkdid, mkwid, monitor, util, Device, DeviceId, DeviceInfo, Dnd,
-    DndState, GenericEventCookie, ImeReceiver, ScrollOrientation, UnownedWindow, WindowId,
-    XExtension,
+    ffi, get_xtarget, handle_map::HandleMap, mkdid, mkwid, monitor, util, Device, DeviceId,
+    DeviceInfo, Dnd, DndState, GenericEventCookie, ImeReceiver, RawAxis, ScrollOrientation,
+    UnownedWindow, WindowId, XExtension,
 };
 
 use util::modifiers::{ModifierKeyState, ModifierKeymap};
@@ -15,9 +20,15 @@ use util::modifiers::{ModifierKeyState, ModifierKeymap};
 use crate::{
     dpi::{PhysicalPosition, PhysicalSize},
     event::{
-        DeviceEvent, ElementState, Event, KeyboardInput, ModifiersState, TouchPhase, WindowEvent,
+        DeviceEvent, ElementState, Event, InnerSizeWriter, KeyboardInput, ModifiersState, Pen,
+        PenPhase, ScrollPhase, TouchPhase, WindowEvent,
     },
     event_loop::EventLoopWindowTarget as RootELW,
+    keyboard::SmolStr,
+    platform_impl::{
+        click::ClickCounter,
+        platform::{keymap, KeyEventExtra},
+    },
 };
 
 /// The X11 documentation states: "Keycodes lie in the inclusive range [8,255]".
@@ -27,7 +38,8 @@ pub(super) struct EventProcessor<T: 'static> {
     pub(super) dnd: Dnd,
     pub(super) ime_receiver: ImeReceiver,
     pub(super) randr_event_offset: c_int,
-    pub(super) devices: RefCell<HashMap<DeviceId, Device>>,
+    pub(super) xkb_event_offset: Option<c_int>,
+    pub(super) devices: RefCell<HandleMap<DeviceId, Device>>,
     pub(super) xi2ext: XExtension,
     pub(super) target: Rc<RootELW<T>>,
     pub(super) mod_keymap: ModifierKeymap,
@@ -37,6 +49,10 @@ pub(super) struct EventProcessor<T: 'static> {
     pub(super) first_touch: Option<u64>,
     // Currently focused window belonging to this process
     pub(super) active_window: Option<ffi::Window>,
+    pub(super) click_counter: ClickCounter,
+    // The key of the last `KeyPress` that wasn't followed by a `KeyRelease`, used to detect the
+    // repeats, which X11 doesn't flag.
+    pub(super) held_key_press: Option<ffi::KeyCode>,
 }
 
 impl<T: 'static> EventProcessor<T> {
@@ -74,10 +90,73 @@ impl<T: 'static> EventProcessor<T> {
         result
     }
 
+    /// Updates the state of the pen the event comes from, returning the pen event to report if
+    /// the event comes from a pen.
+    fn pen_event(&self, xev: &ffi::XIDeviceEvent, phase: PenPhase) -> Option<Pen> {
+        let mut devices = self.devices.borrow_mut();
+        let pen = devices.get_mut(&DeviceId(xev.sourceid))?.pen.as_mut()?;
+
+        let mask =
+            unsafe { slice::from_raw_parts(xev.valuators.mask, xev.valuators.mask_len as usize) };
+        let mut value = xev.valuators.values;
+        for i in 0..xev.valuators.mask_len * 8 {
+            if ffi::XIMaskIsSet(mask, i) {
+                let x = unsafe { *value };
+                if i == pen.pressure_axis.number {
+                    let axis = pen.pressure_axis;
+                    pen.pressure = ((x - axis.min) / (axis.max - axis.min)).clamp(0.0, 1.0);
+                } else if let Some(axis) = pen.tilt_x_axis.filter(|axis| axis.number == i) {
+                    pen.tilt.0 = axis.tilt_degrees(x);
+                } else if let Some(axis) = pen.tilt_y_axis.filter(|axis| axis.number == i) {
+                    pen.tilt.1 = axis.tilt_degrees(x);
+                }
+                value = unsafe { value.offset(1) };
+            }
+        }
+
+        let buttons =
+            unsafe { slice::from_raw_parts(xev.buttons.mask, xev.buttons.mask_len as usize) };
+        let barrel_button = (xev.buttons.mask_len > 0)
+            && (ffi::XIMaskIsSet(buttons, 2) || ffi::XIMaskIsSet(buttons, 3));
+
+        Some(Pen {
+            device_id: mkdid(xev.sourceid),
+            phase,
+            location: PhysicalPosition::new(xev.event_x, xev.event_y),
+            pressure: if phase == PenPhase::Up {
+                0.0
+            } else {
+                pen.pressure
+            },
+            tilt: pen.tilt,
+            twist: 0.0,
+            barrel_button,
+            eraser: pen.eraser,
+        })
+    }
+
     fn window_exists(&self, window_id: ffi::Window) -> bool {
         self.with_window(window_id, |_| ()).is_some()
     }
 
+    /// Calls `callback` on every window of the event loop, e.g. to find the embedder of an
+    /// XEmbed client.
+    fn for_each_window<F>(&self, mut callback: F)
+    where
+        F: FnMut(&Arc<UnownedWindow>),
+    {
+        let wt = get_xtarget(&self.target);
+        let windows: Vec<_> = wt
+            .windows
+            .borrow()
+            .values()
+            .filter_map(|window| window.upgrade())
+            .collect();
+        for window in &windows {
+            callback(window);
+        }
+    }
+
     pub(super) fn poll(&self) -> bool {
         let wt = get_xtarget(&self.target);
         let result = unsafe { (wt.xconn.xlib.XPending)(wt.xconn.display) };
@@ -114,7 +193,7 @@ impl<T: 'static> EventProcessor<T> {
 
     pub(super) fn process_event<F>(&mut self, xev: &mut ffi::XEvent, mut callback: F)
     where
-        F: FnMut(Event<'_, T>),
+        F: FnMut(Event<T>),
     {
         let wt = get_xtarget(&self.target);
         // XFilterEvent tells us when an event has been discarded by the input method.
@@ -154,6 +233,13 @@ impl<T: 'static> EventProcessor<T> {
         }
 
         let event_type = xev.get_type();
+        wt.event_time.set(match event_type {
+            ffi::KeyPress | ffi::KeyRelease => {
+                let xkev: &ffi::XKeyEvent = xev.as_ref();
+                util::server_time_to_instant(xkev.time)
+            }
+            _ => None,
+        });
         match event_type {
             ffi::MappingNotify => {
                 let mapping: &ffi::XMappingEvent = xev.as_ref();
@@ -178,13 +264,19 @@ impl<T: 'static> EventProcessor<T> {
 
                 let window = client_msg.window;
                 let window_id = mkwid(window);
+                let xembed_atom = unsafe { wt.xconn.get_atom_unchecked(b"_XEMBED\0") };
+                let manager_atom = unsafe { wt.xconn.get_atom_unchecked(b"MANAGER\0") };
+
+                let wm_delete_window =
+                    unsafe { wt.xconn.get_atom_unchecked(b"WM_DELETE_WINDOW\0") };
+                let net_wm_ping = unsafe { wt.xconn.get_atom_unchecked(b"_NET_WM_PING\0") };
 
-                if client_msg.data.get_long(0) as ffi::Atom == wt.wm_delete_window {
+                if client_msg.data.get_long(0) as ffi::Atom == wm_delete_window {
                     callback(Event::WindowEvent {
                         window_id,
                         event: WindowEvent::CloseRequested,
                     });
-                } else if client_msg.data.get_long(0) as ffi::Atom == wt.net_wm_ping {
+                } else if client_msg.data.get_long(0) as ffi::Atom == net_wm_ping {
                     let response_msg: &mut ffi::XClientMessageEvent = xev.as_mut();
                     response_msg.window = wt.root;
                     wt.xconn
@@ -194,7 +286,53 @@ impl<T: 'static> EventProcessor<T> {
                             *response_msg,
                         )
                         .queue();
-                } else if client_msg.message_type == self.dnd.atoms.enter {
+                } else if client_msg.message_type == manager_atom
+                    && client_msg.data.get_long(1) as ffi::Atom == wt.xconn.xsettings_selection()
+                {
+                    // A new XSETTINGS manager took over, possibly with another DPI.
+                    wt.xconn.select_xsettings_input().queue();
+                    self.process_dpi_change(&mut callback);
+                } else if client_msg.message_type == xembed_atom {
+                    match util::XEmbedMessage::from_raw(client_msg.data.get_long(1)) {
+                        Some(util::XEmbedMessage::EmbeddedNotify) => {
+                            let embedder = client_msg.data.get_long(3) as ffi::Window;
+                            self.with_window(window, |window| {
+                                window.shared_state.lock().xembed_embedder = Some(embedder);
+                            });
+                        }
+                        Some(util::XEmbedMessage::RequestFocus) => {
+                            let is_active = self.active_window == Some(window);
+                            self.with_window(window, |window| {
+                                if is_active {
+                                    window.xembed_focus_in();
+                                } else {
+                                    window.focus_window();
+                                }
+                            });
+                        }
+                        // Embedders may keep the X focus on a proxy window, so these are all
+                        // we get to know about the focus.
+                        Some(util::XEmbedMessage::FocusIn)
+                            if self.active_window != Some(window) && self.window_exists(window) =>
+                        {
+                            self.active_window = Some(window);
+                            callback(Event::WindowEvent {
+                                window_id,
+                                event: WindowEvent::Focused(true),
+                            });
+                        }
+                        Some(util::XEmbedMessage::FocusOut)
+                            if self.active_window == Some(window) =>
+                        {
+                            self.active_window = None;
+                            callback(Event::WindowEvent {
+                                window_id,
+                                event: WindowEvent::Focused(false),
+                            });
+                        }
+                        _ => (),
+                    }
+                } else if client_msg.message_type == self.dnd.atoms().enter {
                     let source_window = client_msg.data.get_long(0) as c_ulong;
                     let flags = client_msg.data.get_long(1);
                     let version = flags >> 24;
@@ -211,7 +349,7 @@ impl<T: 'static> EventProcessor<T> {
                     {
                         self.dnd.type_list = Some(more_types);
                     }
-                } else if client_msg.message_type == self.dnd.atoms.position {
+                } else if client_msg.message_type == self.dnd.atoms().position {
                     // This event occurs every time the mouse moves while a file's being dragged
                     // over our window. We emit HoveredFile in response; while the macOS backend
                     // does that upon a drag entering, XDND doesn't have access to the actual drop
@@ -238,7 +376,7 @@ impl<T: 'static> EventProcessor<T> {
                     //let action = client_msg.data.get_long(4);
 
                     let accepted = if let Some(ref type_list) = self.dnd.type_list {
-                        type_list.contains(&self.dnd.atoms.uri_list)
+                        type_list.contains(&self.dnd.atoms().uri_list)
                     } else {
                         false
                     };
@@ -268,7 +406,7 @@ impl<T: 'static> EventProcessor<T> {
                         }
                         self.dnd.reset();
                     }
-                } else if client_msg.message_type == self.dnd.atoms.drop {
+                } else if client_msg.message_type == self.dnd.atoms().drop {
                     let (source_window, state) = if let Some(source_window) = self.dnd.source_window
                     {
                         if let Some(Ok(ref path_list)) = self.dnd.result {
@@ -292,7 +430,7 @@ impl<T: 'static> EventProcessor<T> {
                             .expect("Failed to send `XdndFinished` message.");
                     }
                     self.dnd.reset();
-                } else if client_msg.message_type == self.dnd.atoms.leave {
+                } else if client_msg.message_type == self.dnd.atoms().leave {
                     self.dnd.reset();
                     callback(Event::WindowEvent {
                         window_id,
@@ -307,12 +445,12 @@ impl<T: 'static> EventProcessor<T> {
                 let window = xsel.requestor;
                 let window_id = mkwid(window);
 
-                if xsel.property == self.dnd.atoms.selection {
+                if xsel.property == self.dnd.atoms().selection {
                     let mut result = None;
 
                     // This is where we receive data from drag and drop
-                    if let Ok(mut data) = unsafe { self.dnd.read_data(window) } {
-                        let parse_result = self.dnd.parse_data(&mut data);
+                    if let Ok(data) = unsafe { self.dnd.read_data(window) } {
+                        let parse_result = self.dnd.parse_data(&data);
                         if let Ok(ref path_list) = parse_result {
                             for path in path_list {
                                 callback(Event::WindowEvent {
@@ -435,7 +573,8 @@ impl<T: 'static> EventProcessor<T> {
                             );
 
                             let old_inner_size = PhysicalSize::new(width, height);
-                            let mut new_inner_size = PhysicalSize::new(new_width, new_height);
+                            let new_inner_size =
+                                Arc::new(Mutex::new(PhysicalSize::new(new_width, new_height)));
 
                             // Temporarily unlock shared state to prevent deadlock
                             MutexGuard::unlocked(&mut shared_state_lock, || {
@@ -443,10 +582,13 @@ impl<T: 'static> EventProcessor<T> {
                                     window_id,
                                     event: WindowEvent::ScaleFactorChanged {
                                         scale_factor: new_scale_factor,
-                                        new_inner_size: &mut new_inner_size,
+                                        inner_size_writer: InnerSizeWriter::new(Arc::downgrade(
+                                            &new_inner_size,
+                                        )),
                                     },
                                 });
                             });
+                            let new_inner_size = *new_inner_size.lock().unwrap();
 
                             if new_inner_size != old_inner_size {
                                 window.set_inner_size_physical(
@@ -466,7 +608,9 @@ impl<T: 'static> EventProcessor<T> {
                     // WMs constrain the window size, making the resize fail. This would cause an endless stream of
                     // XResizeWindow requests, making Xorg, the winit client, and the WM consume 100% of CPU.
                     if let Some(adjusted_size) = shared_state_lock.dpi_adjusted {
-                        if new_inner_size == adjusted_size || !util::wm_name_is_one_of(&["Xfwm4"]) {
+                        if new_inner_size == adjusted_size
+                            || !wt.xconn.wm_name_is_one_of(&["Xfwm4"])
+                        {
                             // When this finally happens, the event will not be synthetic.
                             shared_state_lock.dpi_adjusted = None;
                         } else {
@@ -478,6 +622,8 @@ impl<T: 'static> EventProcessor<T> {
                         // Drop the shared state lock to prevent deadlock
                         drop(shared_state_lock);
 
+                        window.resize_xembed_clients(new_inner_size);
+
                         callback(Event::WindowEvent {
                             window_id,
                             event: WindowEvent::Resized(new_inner_size.into()),
@@ -494,11 +640,31 @@ impl<T: 'static> EventProcessor<T> {
                 // (which is almost all of them). Failing to correctly update WM info doesn't
                 // really have much impact, since on the WMs affected (xmonad, dwm, etc.) the only
                 // effect is that we waste some time trying to query unsupported properties.
-                wt.xconn.update_cached_wm_info(wt.root);
+                wt.xconn.invalidate_cached_wm_info();
 
                 self.with_window(xev.window, |window| {
                     window.invalidate_cached_frame_extents();
                 });
+
+                // XEmbed clients reparented elsewhere aren't embedded anymore.
+                self.for_each_window(|window| {
+                    window.forget_xembed_client(xev.window, Some(xev.parent));
+                });
+            }
+
+            ffi::PropertyNotify => {
+                let xev: &ffi::XPropertyEvent = xev.as_ref();
+
+                let xembed_info_atom = unsafe { wt.xconn.get_atom_unchecked(b"_XEMBED_INFO\0") };
+                let xsettings_atom =
+                    unsafe { wt.xconn.get_atom_unchecked(b"_XSETTINGS_SETTINGS\0") };
+                if xev.atom == xembed_info_atom {
+                    self.for_each_window(|window| window.update_xembed_client_mapping(xev.window));
+                } else if (xev.atom == ffi::XA_RESOURCE_MANAGER && xev.window == wt.root)
+                    || xev.atom == xsettings_atom
+                {
+                    self.process_dpi_change(&mut callback);
+                }
             }
 
             ffi::DestroyNotify => {
@@ -507,16 +673,26 @@ impl<T: 'static> EventProcessor<T> {
                 let window = xev.window;
                 let window_id = mkwid(window);
 
+                // The destruction of XEmbed clients is followed as well, but they aren't ours.
+                let mut was_embedded = false;
+                self.for_each_window(|embedder| {
+                    was_embedded |= embedder.forget_xembed_client(window, None);
+                });
+                if was_embedded {
+                    return;
+                }
+
                 // In the event that the window's been destroyed without being dropped first, we
                 // cleanup again here.
                 wt.windows.borrow_mut().remove(&WindowId(window));
 
                 // Since all XIM stuff needs to happen from the same thread, we destroy the input
                 // context here instead of when dropping the window.
-                wt.ime
-                    .borrow_mut()
-                    .remove_context(window)
-                    .expect("Failed to destroy input context");
+                if let Some(ime) = wt.ime.get() {
+                    ime.borrow_mut()
+                        .remove_context(window)
+                        .expect("Failed to destroy input context");
+                }
 
                 callback(Event::WindowEvent {
                     window_id,
@@ -565,12 +741,45 @@ impl<T: 'static> EventProcessor<T> {
                 let device_id = mkdid(device);
                 let keycode = xkev.keycode;
 
+                let written = if state == Pressed {
+                    wt.ime
+                        .get()
+                        .and_then(|ime| ime.borrow().get_context(window))
+                        .map(|ic| wt.xconn.lookup_utf8(ic, xkev))
+                } else {
+                    None
+                };
+
                 // When a compose sequence or IME pre-edit is finished, it ends in a KeyPress with
                 // a keycode of 0.
                 if keycode != 0 {
-                    let scancode = keycode - KEYCODE_OFFSET as u32;
+                    let physical_key =
+                        keymap::scancode_to_physicalkey(keycode - KEYCODE_OFFSET as u32);
                     let keysym = wt.xconn.lookup_keysym(xkev);
-                    let virtual_keycode = events::keysym_to_element(keysym as c_uint);
+                    let logical_key = keymap::keysym_to_key(keysym as u32, written.as_deref());
+                    let keycode = keycode as ffi::KeyCode;
+                    let key_without_modifiers = keymap::keysym_to_unmodified_key(
+                        wt.xconn.keycode_to_keysym(keycode) as u32,
+                    );
+                    let text_with_all_modifiers: Option<SmolStr> = written
+                        .as_deref()
+                        .filter(|text| !text.is_empty())
+                        .map(SmolStr::new);
+                    // Ctrl turns letters into control characters, which aren't useful as text.
+                    let text = if state == Pressed && xkev.state & ffi::ControlMask != 0 {
+                        logical_key.to_text().map(SmolStr::new)
+                    } else {
+                        text_with_all_modifiers.clone()
+                    };
+                    let repeat = match state {
+                        Pressed => self.held_key_press.replace(keycode) == Some(keycode),
+                        Released => {
+                            if self.held_key_press == Some(keycode) {
+                                self.held_key_press = None;
+                            }
+                            false
+                        }
+                    };
 
                     update_modifiers!(
                         ModifiersState::from_x11_mask(xkev.state),
@@ -586,22 +795,35 @@ impl<T: 'static> EventProcessor<T> {
                             device_id,
                             input: KeyboardInput {
                                 state,
-                                scancode,
-                                virtual_keycode,
+                                physical_key,
+                                logical_key,
+                                text,
+                                repeat,
                                 modifiers,
+                                platform_specific: KeyEventExtra {
+                                    text_with_all_modifiers,
+                                    key_without_modifiers,
+                                },
                             },
                             is_synthetic: false,
                         },
                     });
-                }
 
-                if state == Pressed {
-                    let written = if let Some(ic) = wt.ime.borrow().get_context(window) {
-                        wt.xconn.lookup_utf8(ic, xkev)
-                    } else {
-                        return;
-                    };
+                    // Track which side's modifier keys are held, even without raw device events.
+                    if let Some(modifier) = self.mod_keymap.get_modifier(keycode) {
+                        self.device_mod_state.key_event(state, keycode, modifier);
 
+                        let new_modifiers = self.device_mod_state.modifiers();
+                        if modifiers != new_modifiers {
+                            callback(Event::WindowEvent {
+                                window_id,
+                                event: WindowEvent::ModifiersChanged(new_modifiers),
+                            });
+                        }
+                    }
+                }
+
+                if let Some(written) = written {
                     for chr in written.chars() {
                         let event = Event::WindowEvent {
                             window_id,
@@ -622,10 +844,12 @@ impl<T: 'static> EventProcessor<T> {
                 if self.xi2ext.opcode != xev.extension {
                     return;
                 }
+                let time = unsafe { (*(xev.data as *const ffi::XIEvent)).time };
+                wt.event_time.set(util::server_time_to_instant(time));
 
                 use crate::event::{
                     ElementState::{Pressed, Released},
-                    MouseButton::{Left, Middle, Other, Right},
+                    MouseButton::{Back, Forward, Left, Middle, Other, Right},
                     MouseScrollDelta::LineDelta,
                     Touch,
                     WindowEvent::{
@@ -638,7 +862,7 @@ impl<T: 'static> EventProcessor<T> {
                     ffi::XI_ButtonPress | ffi::XI_ButtonRelease => {
                         let xev: &ffi::XIDeviceEvent = unsafe { &*(xev.data as *const _) };
                         let window_id = mkwid(xev.event);
-                        let device_id = mkdid(xev.deviceid);
+                        let device_id = mkdid(xev.sourceid);
                         if (xev.flags & ffi::XIPointerEmulated) != 0 {
                             // Deliver multi-touch events instead of emulated mouse events.
                             return;
@@ -652,34 +876,10 @@ impl<T: 'static> EventProcessor<T> {
                         } else {
                             Released
                         };
-                        match xev.detail as u32 {
-                            ffi::Button1 => callback(Event::WindowEvent {
-                                window_id,
-                                event: MouseInput {
-                                    device_id,
-                                    state,
-                                    button: Left,
-                                    modifiers,
-                                },
-                            }),
-                            ffi::Button2 => callback(Event::WindowEvent {
-                                window_id,
-                                event: MouseInput {
-                                    device_id,
-                                    state,
-                                    button: Middle,
-                                    modifiers,
-                                },
-                            }),
-                            ffi::Button3 => callback(Event::WindowEvent {
-                                window_id,
-                                event: MouseInput {
-                                    device_id,
-                                    state,
-                                    button: Right,
-                                    modifiers,
-                                },
-                            }),
+                        let button = match xev.detail as u32 {
+                            ffi::Button1 => Some(Left),
+                            ffi::Button2 => Some(Middle),
+                            ffi::Button3 => Some(Right),
 
                             // Suppress emulated scroll wheel clicks, since we handle the real motion events for those.
                             // In practice, even clicky scroll wheels appear to be reported by evdev (and XInput2 in
@@ -697,27 +897,57 @@ impl<T: 'static> EventProcessor<T> {
                                                 7 => LineDelta(1.0, 0.0),
                                                 _ => unreachable!(),
                                             },
-                                            phase: TouchPhase::Moved,
+                                            phase: ScrollPhase::Moved,
                                             modifiers,
                                         },
                                     });
                                 }
+                                None
                             }
 
-                            x => callback(Event::WindowEvent {
+                            8 => Some(Back),
+                            9 => Some(Forward),
+                            x => Some(Other(x as u16)),
+                        };
+
+                        if let Some(button) = button {
+                            let click_count = match state {
+                                Pressed => self.click_counter.press(
+                                    window_id,
+                                    button,
+                                    (xev.event_x, xev.event_y).into(),
+                                    Default::default(),
+                                ),
+                                Released => self.click_counter.release(button),
+                            };
+
+                            callback(Event::WindowEvent {
                                 window_id,
                                 event: MouseInput {
                                     device_id,
                                     state,
-                                    button: Other(x as u16),
+                                    button,
+                                    click_count,
                                     modifiers,
                                 },
-                            }),
+                            });
+                        }
+
+                        let phase = match (xev.detail as u32, state) {
+                            (ffi::Button1, Pressed) => PenPhase::Down,
+                            (ffi::Button1, Released) => PenPhase::Up,
+                            _ => PenPhase::Moved,
+                        };
+                        if let Some(pen) = self.pen_event(xev, phase) {
+                            callback(Event::WindowEvent {
+                                window_id,
+                                event: WindowEvent::Pen(pen),
+                            });
                         }
                     }
                     ffi::XI_Motion => {
                         let xev: &ffi::XIDeviceEvent = unsafe { &*(xev.data as *const _) };
-                        let device_id = mkdid(xev.deviceid);
+                        let device_id = mkdid(xev.sourceid);
                         let window_id = mkwid(xev.event);
                         let new_cursor_pos = (xev.event_x, xev.event_y);
 
@@ -782,7 +1012,7 @@ impl<T: 'static> EventProcessor<T> {
                                                         LineDelta(0.0, -delta as f32)
                                                     }
                                                 },
-                                                phase: TouchPhase::Moved,
+                                                phase: ScrollPhase::Moved,
                                                 modifiers,
                                             },
                                         });
@@ -803,13 +1033,20 @@ impl<T: 'static> EventProcessor<T> {
                         for event in events {
                             callback(event);
                         }
+
+                        if let Some(pen) = self.pen_event(xev, PenPhase::Moved) {
+                            callback(Event::WindowEvent {
+                                window_id,
+                                event: WindowEvent::Pen(pen),
+                            });
+                        }
                     }
 
                     ffi::XI_Enter => {
                         let xev: &ffi::XIEnterEvent = unsafe { &*(xev.data as *const _) };
 
                         let window_id = mkwid(xev.event);
-                        let device_id = mkdid(xev.deviceid);
+                        let device_id = mkdid(xev.sourceid);
 
                         if let Some(all_info) = DeviceInfo::get(&wt.xconn, ffi::XIAllDevices) {
                             let mut devices = self.devices.borrow_mut();
@@ -870,7 +1107,7 @@ impl<T: 'static> EventProcessor<T> {
                             callback(Event::WindowEvent {
                                 window_id: mkwid(xev.event),
                                 event: CursorLeft {
-                                    device_id: mkdid(xev.deviceid),
+                                    device_id: mkdid(xev.sourceid),
                                 },
                             });
                         }
@@ -878,18 +1115,28 @@ impl<T: 'static> EventProcessor<T> {
                     ffi::XI_FocusIn => {
                         let xev: &ffi::XIFocusInEvent = unsafe { &*(xev.data as *const _) };
 
-                        wt.ime
-                            .borrow_mut()
-                            .focus(xev.event)
-                            .expect("Failed to focus input context");
+                        if let Some(ime) = wt.ime.get() {
+                            ime.borrow_mut()
+                                .focus(xev.event)
+                                .expect("Failed to focus input context");
+                        }
 
                         let modifiers = ModifiersState::from_x11(&xev.mods);
 
                         self.device_mod_state.update_state(&modifiers, None);
 
+                        // XEmbed clients get the focus of their embedder.
+                        self.with_window(xev.event, |window| window.xembed_focus_in());
+
                         if self.active_window != Some(xev.event) {
                             self.active_window = Some(xev.event);
 
+                            if self.with_window(xev.event, |window| !window.key_repeat_enabled())
+                                == Some(true)
+                            {
+                                wt.xconn.disable_auto_repeat();
+                            }
+
                             let window_id = mkwid(xev.event);
                             let position = PhysicalPosition::new(xev.event_x, xev.event_y);
 
@@ -939,14 +1186,22 @@ impl<T: 'static> EventProcessor<T> {
                         if !self.window_exists(xev.event) {
                             return;
                         }
-                        wt.ime
-                            .borrow_mut()
-                            .unfocus(xev.event)
-                            .expect("Failed to unfocus input context");
+                        // The focus only moved to an embedded XEmbed client.
+                        if xev.detail == ffi::XINotifyInferior {
+                            return;
+                        }
+                        self.with_window(xev.event, |window| window.xembed_focus_out());
+                        if let Some(ime) = wt.ime.get() {
+                            ime.borrow_mut()
+                                .unfocus(xev.event)
+                                .expect("Failed to unfocus input context");
+                        }
 
                         if self.active_window.take() == Some(xev.event) {
                             let window_id = mkwid(xev.event);
 
+                            wt.xconn.restore_auto_repeat();
+
                             // Issue key release events for all pressed keys
                             Self::handle_pressed_keys(
                                 wt,
@@ -1001,7 +1256,7 @@ impl<T: 'static> EventProcessor<T> {
                             callback(Event::WindowEvent {
                                 window_id,
                                 event: WindowEvent::Touch(Touch {
-                                    device_id: mkdid(xev.deviceid),
+                                    device_id: mkdid(xev.sourceid),
                                     phase,
                                     location,
                                     force: None, // TODO
@@ -1015,7 +1270,7 @@ impl<T: 'static> EventProcessor<T> {
                         let xev: &ffi::XIRawEvent = unsafe { &*(xev.data as *const _) };
                         if xev.flags & ffi::XIPointerEmulated == 0 {
                             callback(Event::DeviceEvent {
-                                device_id: mkdid(xev.deviceid),
+                                device_id: mkdid(xev.sourceid),
                                 event: DeviceEvent::Button {
                                     button: xev.detail as u32,
                                     state: match xev.evtype {
@@ -1030,7 +1285,7 @@ impl<T: 'static> EventProcessor<T> {
 
                     ffi::XI_RawMotion => {
                         let xev: &ffi::XIRawEvent = unsafe { &*(xev.data as *const _) };
-                        let did = mkdid(xev.deviceid);
+                        let did = mkdid(xev.sourceid);
 
                         let mask = unsafe {
                             slice::from_raw_parts(
@@ -1038,43 +1293,55 @@ impl<T: 'static> EventProcessor<T> {
                                 xev.valuators.mask_len as usize,
                             )
                         };
-                        let mut value = xev.raw_values;
+                        let mut events = Vec::new();
                         let mut mouse_delta = (0.0, 0.0);
                         let mut scroll_delta = (0.0, 0.0);
-                        for i in 0..xev.valuators.mask_len * 8 {
-                            if ffi::XIMaskIsSet(mask, i) {
-                                let x = unsafe { *value };
-                                // We assume that every XInput2 device with analog axes is a pointing device emitting
-                                // relative coordinates.
-                                match i {
-                                    0 => mouse_delta.0 = x,
-                                    1 => mouse_delta.1 = x,
-                                    2 => scroll_delta.0 = x as f32,
-                                    3 => scroll_delta.1 = x as f32,
-                                    _ => {}
-                                }
-                                callback(Event::DeviceEvent {
-                                    device_id: did,
-                                    event: DeviceEvent::Motion {
+                        {
+                            let devices = self.devices.borrow();
+                            let physical_device = devices.get(&DeviceId(xev.sourceid));
+
+                            let mut value = xev.raw_values;
+                            for i in 0..xev.valuators.mask_len * 8 {
+                                if ffi::XIMaskIsSet(mask, i) {
+                                    let x = unsafe { *value };
+                                    let axis = match physical_device {
+                                        Some(device) => device.raw_axis(i),
+                                        // Assume unknown devices are mice.
+                                        None => RawAxis::default_for(i),
+                                    };
+                                    match axis {
+                                        RawAxis::MotionX => mouse_delta.0 = x,
+                                        RawAxis::MotionY => mouse_delta.1 = x,
+                                        RawAxis::Scroll(
+                                            ScrollOrientation::Horizontal,
+                                            increment,
+                                        ) => scroll_delta.0 = (x / increment) as f32,
+                                        // X11 vertical scroll coordinates are opposite to winit's
+                                        RawAxis::Scroll(ScrollOrientation::Vertical, increment) => {
+                                            scroll_delta.1 = (-x / increment) as f32
+                                        }
+                                        RawAxis::Other => {}
+                                    }
+                                    events.push(DeviceEvent::Motion {
                                         axis: i as u32,
                                         value: x,
-                                    },
-                                });
-                                value = unsafe { value.offset(1) };
+                                    });
+                                    value = unsafe { value.offset(1) };
+                                }
                             }
                         }
                         if mouse_delta != (0.0, 0.0) {
-                            callback(Event::DeviceEvent {
-                                device_id: did,
-                                event: DeviceEvent::MouseMotion { delta: mouse_delta },
-                            });
+                            events.push(DeviceEvent::MouseMotion { delta: mouse_delta });
                         }
                         if scroll_delta != (0.0, 0.0) {
+                            events.push(DeviceEvent::MouseWheel {
+                                delta: LineDelta(scroll_delta.0, scroll_delta.1),
+                            });
+                        }
+                        for event in events {
                             callback(Event::DeviceEvent {
                                 device_id: did,
-                                event: DeviceEvent::MouseWheel {
-                                    delta: LineDelta(scroll_delta.0, scroll_delta.1),
-                                },
+                                event,
                             });
                         }
                     }
@@ -1090,22 +1357,32 @@ impl<T: 'static> EventProcessor<T> {
 
                         let device_id = mkdid(xev.sourceid);
                         let keycode = xev.detail;
-                        let scancode = keycode - KEYCODE_OFFSET as i32;
-                        if scancode < 0 {
+                        if keycode < KEYCODE_OFFSET as i32 {
                             return;
                         }
+                        let physical_key = keymap::scancode_to_physicalkey(
+                            (keycode - KEYCODE_OFFSET as i32) as u32,
+                        );
                         let keysym = wt.xconn.keycode_to_keysym(keycode as ffi::KeyCode);
-                        let virtual_keycode = events::keysym_to_element(keysym as c_uint);
+                        let logical_key = keymap::keysym_to_key(keysym as u32, None);
                         let modifiers = self.device_mod_state.modifiers();
 
                         #[allow(deprecated)]
                         callback(Event::DeviceEvent {
                             device_id,
                             event: DeviceEvent::Key(KeyboardInput {
-                                scancode: scancode as u32,
-                                virtual_keycode,
+                                physical_key,
+                                logical_key,
+                                text: None,
+                                repeat: false,
                                 state,
                                 modifiers,
+                                platform_specific: KeyEventExtra {
+                                    text_with_all_modifiers: None,
+                                    key_without_modifiers: keymap::keysym_to_unmodified_key(
+                                        keysym as u32,
+                                    ),
+                                },
                             }),
                         });
 
@@ -1158,70 +1435,104 @@ impl<T: 'static> EventProcessor<T> {
                 }
             }
             _ => {
-                if event_type == self.randr_event_offset {
-                    // In the future, it would be quite easy to emit monitor hotplug events.
-                    let prev_list = monitor::invalidate_cached_monitor_list();
-                    if let Some(prev_list) = prev_list {
-                        let new_list = wt.xconn.available_monitors();
-                        for new_monitor in new_list {
-                            // Previous list may be empty, in case of disconnecting and
-                            // reconnecting the only one monitor. We still need to emit events in
-                            // this case.
-                            let maybe_prev_scale_factor = prev_list
-                                .iter()
-                                .find(|prev_monitor| prev_monitor.name == new_monitor.name)
-                                .map(|prev_monitor| prev_monitor.scale_factor);
-                            if Some(new_monitor.scale_factor) != maybe_prev_scale_factor {
-                                for (window_id, window) in wt.windows.borrow().iter() {
-                                    if let Some(window) = window.upgrade() {
-                                        // Check if the window is on this monitor
-                                        let monitor = window.current_monitor();
-                                        if monitor.name == new_monitor.name {
-                                            let (width, height) = window.inner_size_physical();
-                                            let (new_width, new_height) = window.adjust_for_dpi(
-                                                // If there all monitors are closed before, scale
-                                                // factor would be already changed to 1.0.
-                                                maybe_prev_scale_factor.unwrap_or(1.0),
-                                                new_monitor.scale_factor,
-                                                width,
-                                                height,
-                                                &*window.shared_state.lock(),
-                                            );
-
-                                            let window_id = crate::window::WindowId(
-                                                crate::platform_impl::platform::WindowId::X(
-                                                    *window_id,
-                                                ),
-                                            );
-                                            let old_inner_size = PhysicalSize::new(width, height);
-                                            let mut new_inner_size =
-                                                PhysicalSize::new(new_width, new_height);
-
-                                            callback(Event::WindowEvent {
-                                                window_id,
-                                                event: WindowEvent::ScaleFactorChanged {
-                                                    scale_factor: new_monitor.scale_factor,
-                                                    new_inner_size: &mut new_inner_size,
-                                                },
-                                            });
-
-                                            if new_inner_size != old_inner_size {
-                                                let (new_width, new_height) = new_inner_size.into();
-                                                window
-                                                    .set_inner_size_physical(new_width, new_height);
-                                            }
-                                        }
-                                    }
-                                }
-                            }
+                if Some(event_type) == self.xkb_event_offset {
+                    let xkev = unsafe { &*(xev as *const ffi::XEvent as *const ffi::XkbAnyEvent) };
+                    let layout_changed = match xkev.xkb_type {
+                        ffi::XkbNewKeyboardNotify | ffi::XkbNamesNotify => true,
+                        ffi::XkbStateNotify => {
+                            let xkev = unsafe {
+                                &*(xev as *const ffi::XEvent as *const ffi::XkbStateNotifyEvent)
+                            };
+                            xkev.changed as c_ulong & ffi::XkbGroupStateMask != 0
+                        }
+                        _ => false,
+                    };
+                    // The notifications are also sent when the layout doesn't end up changing,
+                    // e.g. when the same keymap is set again.
+                    if layout_changed {
+                        // The layout isn't queried up front, so the first change is always reported.
+                        let layout = Some(wt.xconn.keyboard_layout());
+                        if layout != *wt.keyboard_layout.borrow() {
+                            *wt.keyboard_layout.borrow_mut() = layout;
+                            callback(Event::KeyboardLayoutChanged);
                         }
                     }
+                } else if event_type == self.randr_event_offset {
+                    self.process_dpi_change(&mut callback);
                 }
             }
         }
 
         if let Ok((window_id, x, y)) = self.ime_receiver.try_recv() {
-            wt.ime.borrow_mut().send_xim_spot(window_id, x, y);
+            if let Some(ime) = wt.ime.get() {
+                ime.borrow_mut().send_xim_spot(window_id, x, y);
+            }
+        }
+    }
+
+    /// Emits `ScaleFactorChanged` for the windows on the monitors whose scale factor changed,
+    /// following a change of the RandR configuration or of the DPI set by the desktop.
+    fn process_dpi_change<F>(&self, callback: &mut F)
+    where
+        F: FnMut(Event<T>),
+    {
+        let wt = get_xtarget(&self.target);
+        // In the future, it would be quite easy to emit monitor hotplug events.
+        let prev_list = monitor::invalidate_cached_monitor_list();
+        if let Some(prev_list) = prev_list {
+            let new_list = wt.xconn.available_monitors();
+            for new_monitor in new_list {
+                // Previous list may be empty, in case of disconnecting and
+                // reconnecting the only one monitor. We still need to emit events in
+                // this case.
+                let maybe_prev_scale_factor = prev_list
+                    .iter()
+                    .find(|prev_monitor| prev_monitor.name == new_monitor.name)
+                    .map(|prev_monitor| prev_monitor.scale_factor);
+                if Some(new_monitor.scale_factor) != maybe_prev_scale_factor {
+                    for (window_id, window) in wt.windows.borrow().iter() {
+                        if let Some(window) = window.upgrade() {
+                            // Check if the window is on this monitor
+                            let monitor = window.current_monitor();
+                            if monitor.name == new_monitor.name {
+                                let (width, height) = window.inner_size_physical();
+                                let (new_width, new_height) = window.adjust_for_dpi(
+                                    // If there all monitors are closed before, scale
+                                    // factor would be already changed to 1.0.
+                                    maybe_prev_scale_factor.unwrap_or(1.0),
+                                    new_monitor.scale_factor,
+                                    width,
+                                    height,
+                                    &window.shared_state.lock(),
+                                );
+
+                                let window_id = crate::window::WindowId(
+                                    crate::platform_impl::platform::WindowId::X(*window_id),
+                                );
+                                let old_inner_size = PhysicalSize::new(width, height);
+                                let new_inner_size =
+                                    Arc::new(Mutex::new(PhysicalSize::new(new_width, new_height)));
+
+                                callback(Event::WindowEvent {
+                                    window_id,
+                                    event: WindowEvent::ScaleFactorChanged {
+                                        scale_factor: new_monitor.scale_factor,
+                                        inner_size_writer: InnerSizeWriter::new(Arc::downgrade(
+                                            &new_inner_size,
+                                        )),
+                                    },
+                                });
+                                let new_inner_size = *new_inner_size.lock().unwrap();
+
+                                if new_inner_size != old_inner_size {
+                                    let (new_width, new_height) = new_inner_size.into();
+                                    window.set_inner_size_physical(new_width, new_height);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
         }
     }
 
@@ -1233,7 +1544,7 @@ impl<T: 'static> EventProcessor<T> {
         device_mod_state: &mut ModifierKeyState,
         callback: &mut F,
     ) where
-        F: FnMut(Event<'_, T>),
+        F: FnMut(Event<T>),
     {
         let device_id = mkdid(util::VIRTUAL_CORE_KEYBOARD);
         let modifiers = device_mod_state.modifiers();
@@ -1245,9 +1556,9 @@ impl<T: 'static> EventProcessor<T> {
             .into_iter()
             .filter(|k| *k >= KEYCODE_OFFSET)
         {
-            let scancode = (keycode - KEYCODE_OFFSET) as u32;
+            let physical_key = keymap::scancode_to_physicalkey((keycode - KEYCODE_OFFSET) as u32);
             let keysym = wt.xconn.keycode_to_keysym(keycode);
-            let virtual_keycode = events::keysym_to_element(keysym as c_uint);
+            let logical_key = keymap::keysym_to_key(keysym as u32, None);
 
             if let Some(modifier) = mod_keymap.get_modifier(keycode as ffi::KeyCode) {
                 device_mod_state.key_event(
@@ -1263,10 +1574,16 @@ impl<T: 'static> EventProcessor<T> {
                 event: WindowEvent::KeyboardInput {
                     device_id,
                     input: KeyboardInput {
-                        scancode,
+                        physical_key,
                         state,
-                        virtual_keycode,
+                        logical_key,
+                        text: None,
+                        repeat: false,
                         modifiers,
+                        platform_specific: KeyEventExtra {
+                            text_with_all_modifiers: None,
+                            key_without_modifiers: keymap::keysym_to_unmodified_key(keysym as u32),
+                        },
                     },
                     is_synthetic: true,
                 },
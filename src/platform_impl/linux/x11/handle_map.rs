@@ -0,0 +1,228 @@
+//! A map keyed by X handles, looked up for nearly every event.
+//!
+//! Handles are small integers, so instead of hashing them with SipHash like `HashMap` does, they
+//! are spread with a multiplication and stored in a linearly probed table.
+
+use std::mem;
+
+/// A handle that can be used as a key of a [`HandleMap`].
+pub trait Handle: Copy + Eq {
+    fn to_u64(self) -> u64;
+}
+
+pub struct HandleMap<K, V> {
+    slots: Vec<Option<(K, V)>>,
+    len: usize,
+}
+
+impl<K, V> Default for HandleMap<K, V> {
+    fn default() -> Self {
+        HandleMap {
+            slots: Vec::new(),
+            len: 0,
+        }
+    }
+}
+
+impl<K: Handle, V> HandleMap<K, V> {
+    fn mask(&self) -> usize {
+        self.slots.len() - 1
+    }
+
+    fn ideal_slot(&self, key: K) -> usize {
+        // Fibonacci hashing.
+        let hash = key.to_u64().wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        (hash >> (64 - self.slots.len().trailing_zeros())) as usize
+    }
+
+    fn find(&self, key: &K) -> Option<usize> {
+        if self.len == 0 {
+            return None;
+        }
+        let mut index = self.ideal_slot(*key);
+        loop {
+            match &self.slots[index] {
+                Some((slot_key, _)) if slot_key == key => return Some(index),
+                Some(_) => index = (index + 1) & self.mask(),
+                None => return None,
+            }
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let index = self.find(key)?;
+        self.slots[index].as_ref().map(|(_, value)| value)
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let index = self.find(key)?;
+        self.slots[index].as_mut().map(|(_, value)| value)
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(index) = self.find(&key) {
+            let (_, old) = self.slots[index].as_mut().unwrap();
+            return Some(mem::replace(old, value));
+        }
+
+        // Keep the table at most half full, so that probe sequences stay short.
+        if (self.len + 1) * 2 > self.slots.len() {
+            self.grow();
+        }
+        let mut index = self.ideal_slot(key);
+        while self.slots[index].is_some() {
+            index = (index + 1) & self.mask();
+        }
+        self.slots[index] = Some((key, value));
+        self.len += 1;
+        None
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let mut index = self.find(key)?;
+        let (_, value) = self.slots[index].take().unwrap();
+        self.len -= 1;
+
+        // Shift the following entries of the probe sequence back, so that lookups don't stop at
+        // the hole.
+        let mut next = (index + 1) & self.mask();
+        while let Some((next_key, _)) = &self.slots[next] {
+            let ideal = self.ideal_slot(*next_key);
+            // Whether `ideal` is cyclically outside of `(index, next]`.
+            let movable = if index <= next {
+                ideal <= index || ideal > next
+            } else {
+                ideal <= index && ideal > next
+            };
+            if movable {
+                self.slots[index] = self.slots[next].take();
+                index = next;
+            }
+            next = (next + 1) & self.mask();
+        }
+        Some(value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.slots
+            .iter()
+            .filter_map(|slot| slot.as_ref().map(|(key, value)| (key, value)))
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    fn grow(&mut self) {
+        let capacity = (self.slots.len() * 2).max(8);
+        let old = mem::replace(&mut self.slots, (0..capacity).map(|_| None).collect());
+        self.len = 0;
+        for (key, value) in old.into_iter().flatten() {
+            self.insert(key, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{Handle, HandleMap};
+
+    impl Handle for u64 {
+        fn to_u64(self) -> u64 {
+            self
+        }
+    }
+
+    /// Returns `count` keys whose ideal slot is `slot` at the current capacity of `map`.
+    fn keys_for_slot(map: &HandleMap<u64, u64>, slot: usize, count: usize) -> Vec<u64> {
+        (0..)
+            .filter(|&key| map.ideal_slot(key) == slot)
+            .take(count)
+            .collect()
+    }
+
+    #[test]
+    fn colliding_keys() {
+        let mut map = HandleMap::default();
+        map.grow();
+        let keys = keys_for_slot(&map, 3, 3);
+        for &key in &keys {
+            assert_eq!(map.insert(key, key * 10), None);
+        }
+        assert_eq!(map.slots.len(), 8);
+        assert_eq!(map.find(&keys[2]), Some(5));
+
+        for &key in &keys {
+            assert_eq!(map.get(&key), Some(&(key * 10)));
+        }
+        assert_eq!(map.insert(keys[1], 1), Some(keys[1] * 10));
+        assert_eq!(map.remove(&keys[0]), Some(keys[0] * 10));
+        assert_eq!(map.get(&keys[0]), None);
+        assert_eq!(map.get(&keys[1]), Some(&1));
+        assert_eq!(map.get(&keys[2]), Some(&(keys[2] * 10)));
+    }
+
+    #[test]
+    fn remove_across_wrap_around() {
+        let mut map = HandleMap::default();
+        map.grow();
+        // The probe sequence of these keys starts at the last slot and wraps around.
+        let last = map.slots.len() - 1;
+        let keys = keys_for_slot(&map, last, 3);
+        for &key in &keys {
+            map.insert(key, key);
+        }
+        assert_eq!(map.find(&keys[0]), Some(last));
+        assert_eq!(map.find(&keys[1]), Some(0));
+        assert_eq!(map.find(&keys[2]), Some(1));
+
+        assert_eq!(map.remove(&keys[0]), Some(keys[0]));
+        assert_eq!(map.len, 2);
+        // The other entries are shifted back across the end of the table.
+        assert_eq!(map.find(&keys[1]), Some(last));
+        assert_eq!(map.find(&keys[2]), Some(0));
+        for &key in &keys[1..] {
+            assert_eq!(map.get(&key), Some(&key));
+        }
+    }
+
+    #[test]
+    fn growth() {
+        let mut map = HandleMap::default();
+        for key in 0..1000 {
+            assert_eq!(map.insert(key, key), None);
+        }
+        assert_eq!(map.len, 1000);
+        assert!(map.slots.len() >= 2000);
+        for key in 0..1000 {
+            assert_eq!(map.get(&key), Some(&key));
+        }
+        assert_eq!(map.iter().count(), 1000);
+    }
+
+    #[test]
+    fn matches_hash_map() {
+        let mut map = HandleMap::default();
+        let mut reference = HashMap::new();
+        // A linear congruential generator over a small key space, so that keys are reused.
+        let mut state = 1u64;
+        for _ in 0..10_000 {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1);
+            let key = (state >> 33) % 256;
+            if state & (1 << 20) == 0 {
+                assert_eq!(map.insert(key, state), reference.insert(key, state));
+            } else {
+                assert_eq!(map.remove(&key), reference.remove(&key));
+            }
+            assert_eq!(map.len, reference.len());
+        }
+        for (key, value) in &reference {
+            assert_eq!(map.get(key), Some(value));
+        }
+        assert_eq!(map.values().count(), reference.len());
+    }
+}
@@ -156,6 +156,10 @@ impl Ime {
 impl Drop for Ime {
     fn drop(&mut self) {
         unsafe {
+            // The connection outlives the event loop, so make sure a pending instantiate
+            // callback can't fire with a pointer to our freed state.
+            let client_data = &mut *self.inner as *mut ImeInner as ffi::XPointer;
+            let _ = unset_instantiate_callback(&self.xconn, client_data);
             let _ = self.inner.destroy_all_contexts_if_necessary();
             let _ = self.inner.close_im_if_necessary();
         }
@@ -8,8 +8,8 @@
 
 mod dnd;
 mod event_processor;
-mod events;
 pub mod ffi;
+mod handle_map;
 mod ime;
 mod monitor;
 pub mod util;
@@ -23,8 +23,8 @@ pub use self::{
 };
 
 use std::{
-    cell::RefCell,
-    collections::{HashMap, HashSet},
+    cell::{Cell, OnceCell, RefCell},
+    collections::HashSet,
     ffi::CStr,
     mem::{self, MaybeUninit},
     ops::Deref,
@@ -37,6 +37,9 @@ use std::{
     time::{Duration, Instant},
 };
 
+#[cfg(target_os = "linux")]
+use std::os::unix::io::AsRawFd;
+
 use libc::{self, setlocale, LC_CTYPE};
 
 use mio::{unix::SourceFd, Events, Interest, Poll, Token, Waker};
@@ -44,25 +47,73 @@ use mio::{unix::SourceFd, Events, Interest, Poll, Token, Waker};
 use self::{
     dnd::{Dnd, DndState},
     event_processor::EventProcessor,
+    handle_map::{Handle, HandleMap},
     ime::{Ime, ImeCreationError, ImeReceiver, ImeSender},
     util::modifiers::ModifierKeymap,
 };
+#[cfg(feature = "accesskit")]
+use super::accessibility::ActionSink;
+use super::background::{self, Monitor};
+#[cfg(all(target_os = "linux", feature = "gamepad"))]
+use super::gamepad::GamepadMonitor;
+#[cfg(target_os = "linux")]
+use super::power::PowerMonitor;
+#[cfg(feature = "dbus")]
+use super::session_lock::SessionLockMonitor;
+#[cfg(target_os = "linux")]
+use super::timer::WakeupTimer;
+use super::user_queue::{self, UserEventReceiver, UserEventSender};
+#[cfg(feature = "accesskit")]
+use crate::{accessibility::accesskit::ActionRequest, event::WindowEvent};
 use crate::{
+    device::{DeviceCapabilities, DeviceHandle as RootDeviceHandle, DeviceKind},
     error::OsError as RootOsError,
-    event::{Event, StartCause},
-    event_loop::{ControlFlow, EventLoopClosed, EventLoopWindowTarget as RootELW},
-    platform_impl::{platform::sticky_exit_callback, PlatformSpecificWindowBuilderAttributes},
+    event::{Event, ModifiersState, StartCause},
+    event_loop::{
+        ControlFlow, DeviceEvents, EventLoopClosed, EventLoopWindowTarget as RootELW, ProxyWakeup,
+    },
+    keyboard::{Key, KeyCode},
+    platform_impl::{
+        platform::{keymap, sticky_exit_callback},
+        PlatformSpecificWindowBuilderAttributes,
+    },
     window::WindowAttributes,
 };
 
 const X_TOKEN: Token = Token(0);
 const USER_REDRAW_TOKEN: Token = Token(1);
+#[cfg(target_os = "linux")]
+const WAKEUP_TIMER_TOKEN: Token = Token(2);
 
 struct WakeSender<T> {
     sender: Sender<T>,
     waker: Arc<Waker>,
 }
 
+impl<T> WakeSender<T> {
+    fn send(&self, value: T) {
+        if self.sender.send(value).is_ok() {
+            self.waker.wake().unwrap();
+        }
+    }
+}
+
+/// Creates a channel to the event loop, which is woken up by each send.
+fn wake_channel<T>(waker: &Arc<Waker>) -> (WakeSender<T>, PeekableReceiver<T>) {
+    let (sender, receiver) = mpsc::channel();
+    let sender = WakeSender {
+        sender,
+        waker: waker.clone(),
+    };
+    (sender, PeekableReceiver::from_recv(receiver))
+}
+
+/// Starts a background monitor, whose events are received by the event loop.
+fn spawn_monitor<M: Monitor>(waker: &Arc<Waker>) -> (PeekableReceiver<M::Event>, Option<M>) {
+    let (sender, receiver) = wake_channel(waker);
+    (receiver, background::spawn(move |event| sender.send(event)))
+}
+
 struct PeekableReceiver<T> {
     recv: Receiver<T>,
     first: Option<T>,
@@ -98,29 +149,53 @@ impl<T> PeekableReceiver<T> {
 
 pub struct EventLoopWindowTarget<T> {
     xconn: Arc<XConnection>,
-    wm_delete_window: ffi::Atom,
-    net_wm_ping: ffi::Atom,
     ime_sender: ImeSender,
     root: ffi::Window,
-    ime: RefCell<Ime>,
-    windows: RefCell<HashMap<WindowId, Weak<UnownedWindow>>>,
+    // Opened along with the first window, as connecting to the input method server may be slow
+    ime: OnceCell<RefCell<Ime>>,
+    windows: RefCell<HandleMap<WindowId, Weak<UnownedWindow>>>,
     redraw_sender: WakeSender<WindowId>,
+    device_events: Cell<DeviceEvents>,
+    // Last keyboard layout reported through `Event::KeyboardLayoutChanged`, `None` until the
+    // first XKB event
+    keyboard_layout: RefCell<Option<Option<String>>>,
+    // Server timestamp of the X event being processed, if it has one
+    event_time: Cell<Option<Instant>>,
+    #[cfg(feature = "accesskit")]
+    accessibility_sink: ActionSink,
     _marker: ::std::marker::PhantomData<T>,
 }
 
 pub struct EventLoop<T: 'static> {
     poll: Poll,
     waker: Arc<Waker>,
+    #[cfg(target_os = "linux")]
+    wakeup_timer: WakeupTimer,
     event_processor: EventProcessor<T>,
     redraw_receiver: PeekableReceiver<WindowId>,
-    user_receiver: PeekableReceiver<T>, //waker.wake needs to be called whenever something gets sent
-    user_sender: Sender<T>,
+    user_receiver: UserEventReceiver<T>, //waker.wake needs to be called when `send` asks for it
+    user_sender: UserEventSender<T>,
+    #[cfg(all(target_os = "linux", feature = "gamepad"))]
+    gamepad_receiver: PeekableReceiver<(crate::gamepad::GamepadId, crate::gamepad::GamepadEvent)>,
+    #[cfg(all(target_os = "linux", feature = "gamepad"))]
+    _gamepad_monitor: Option<GamepadMonitor>,
+    #[cfg(feature = "dbus")]
+    session_lock_receiver: PeekableReceiver<bool>,
+    #[cfg(feature = "dbus")]
+    _session_lock_monitor: Option<SessionLockMonitor>,
+    #[cfg(feature = "accesskit")]
+    accessibility_receiver: PeekableReceiver<(super::WindowId, ActionRequest)>,
+    #[cfg(target_os = "linux")]
+    power_receiver: PeekableReceiver<()>,
+    #[cfg(target_os = "linux")]
+    _power_monitor: Option<PowerMonitor>,
     target: Rc<RootELW<T>>,
 }
 
 pub struct EventLoopProxy<T: 'static> {
-    user_sender: Sender<T>,
+    user_sender: UserEventSender<T>,
     waker: Arc<Waker>,
+    wakeup: ProxyWakeup,
 }
 
 impl<T: 'static> Clone for EventLoopProxy<T> {
@@ -128,6 +203,7 @@ impl<T: 'static> Clone for EventLoopProxy<T> {
         EventLoopProxy {
             user_sender: self.user_sender.clone(),
             waker: self.waker.clone(),
+            wakeup: self.wakeup,
         }
     }
 }
@@ -136,49 +212,22 @@ impl<T: 'static> EventLoop<T> {
     pub fn new(xconn: Arc<XConnection>) -> EventLoop<T> {
         let root = unsafe { (xconn.xlib.XDefaultRootWindow)(xconn.display) };
 
-        let wm_delete_window = unsafe { xconn.get_atom_unchecked(b"WM_DELETE_WINDOW\0") };
-
-        let net_wm_ping = unsafe { xconn.get_atom_unchecked(b"_NET_WM_PING\0") };
-
-        let dnd = Dnd::new(Arc::clone(&xconn))
-            .expect("Failed to call XInternAtoms when initializing drag and drop");
+        let dnd = Dnd::new(Arc::clone(&xconn));
 
         let (ime_sender, ime_receiver) = mpsc::channel();
-        // Input methods will open successfully without setting the locale, but it won't be
-        // possible to actually commit pre-edit sequences.
-        unsafe {
-            // Remember default locale to restore it if target locale is unsupported
-            // by Xlib
-            let default_locale = setlocale(LC_CTYPE, ptr::null());
-            setlocale(LC_CTYPE, b"\0".as_ptr() as *const _);
-
-            // Check if set locale is supported by Xlib.
-            // If not, calls to some Xlib functions like `XSetLocaleModifiers`
-            // will fail.
-            let locale_supported = (xconn.xlib.XSupportsLocale)() == 1;
-            if !locale_supported {
-                let unsupported_locale = setlocale(LC_CTYPE, ptr::null());
-                warn!(
-                    "Unsupported locale \"{}\". Restoring default locale \"{}\".",
-                    CStr::from_ptr(unsupported_locale).to_string_lossy(),
-                    CStr::from_ptr(default_locale).to_string_lossy()
-                );
-                // Restore default locale
-                setlocale(LC_CTYPE, default_locale);
-            }
-        }
-        let ime = RefCell::new({
-            let result = Ime::new(Arc::clone(&xconn));
-            if let Err(ImeCreationError::OpenFailure(ref state)) = result {
-                panic!("Failed to open input method: {:#?}", state);
-            }
-            result.expect("Failed to set input method destruction callback")
-        });
 
         let randr_event_offset = xconn
             .select_xrandr_input(root)
             .expect("Failed to query XRandR extension");
 
+        // The DPI the scale factors come from may change along with the desktop settings.
+        xconn.select_dpi_input(root).queue();
+
+        let xkb_event_offset = xconn.select_xkb_layout_events();
+        if xkb_event_offset.is_none() {
+            warn!("Failed to select the XKB events, keyboard layout changes won't be reported");
+        }
+
         let xi2ext = unsafe {
             let mut ext = XExtension::default();
 
@@ -213,8 +262,6 @@ impl<T: 'static> EventLoop<T> {
             }
         }
 
-        xconn.update_cached_wm_info(root);
-
         let mut mod_keymap = ModifierKeymap::new();
         mod_keymap.reset_from_x_connection(&xconn);
 
@@ -225,24 +272,57 @@ impl<T: 'static> EventLoop<T> {
             .register(&mut SourceFd(&xconn.x11_fd), X_TOKEN, Interest::READABLE)
             .unwrap();
 
-        let (user_sender, user_channel) = std::sync::mpsc::channel();
+        #[cfg(target_os = "linux")]
+        let wakeup_timer = WakeupTimer::new().expect("Failed to create the wakeup timer");
+        #[cfg(target_os = "linux")]
+        poll.registry()
+            .register(
+                &mut SourceFd(&wakeup_timer.as_raw_fd()),
+                WAKEUP_TIMER_TOKEN,
+                Interest::READABLE,
+            )
+            .unwrap();
+
+        let (user_sender, user_receiver) = user_queue::channel();
         let (redraw_sender, redraw_channel) = std::sync::mpsc::channel();
 
+        #[cfg(all(target_os = "linux", feature = "gamepad"))]
+        let (gamepad_receiver, gamepad_monitor) = spawn_monitor::<GamepadMonitor>(&waker);
+        #[cfg(feature = "dbus")]
+        let (session_lock_receiver, session_lock_monitor) =
+            spawn_monitor::<SessionLockMonitor>(&waker);
+        #[cfg(target_os = "linux")]
+        let (power_receiver, power_monitor) = spawn_monitor::<PowerMonitor>(&waker);
+
+        #[cfg(feature = "accesskit")]
+        let (accessibility_sender, accessibility_receiver) = wake_channel(&waker);
+        #[cfg(feature = "accesskit")]
+        let accessibility_sink: ActionSink = Arc::new(move |window_id, request| {
+            accessibility_sender.send((window_id, request));
+        });
+
         let target = Rc::new(RootELW {
             p: super::EventLoopWindowTarget::X(EventLoopWindowTarget {
-                ime,
+                ime: OnceCell::new(),
                 root,
                 windows: Default::default(),
                 _marker: ::std::marker::PhantomData,
                 ime_sender,
                 xconn,
-                wm_delete_window,
-                net_wm_ping,
                 redraw_sender: WakeSender {
                     sender: redraw_sender, // not used again so no clone
                     waker: waker.clone(),
                 },
+                device_events: Default::default(),
+                keyboard_layout: RefCell::new(None),
+                event_time: Cell::new(None),
+                #[cfg(feature = "accesskit")]
+                accessibility_sink,
             }),
+            coalescing: Default::default(),
+            exit_code: Default::default(),
+            event_timestamp: Default::default(),
+            dispatcher_settings: Default::default(),
             _marker: ::std::marker::PhantomData,
         });
 
@@ -251,6 +331,7 @@ impl<T: 'static> EventLoop<T> {
             dnd,
             devices: Default::default(),
             randr_event_offset,
+            xkb_event_offset,
             ime_receiver,
             xi2ext,
             mod_keymap,
@@ -258,6 +339,8 @@ impl<T: 'static> EventLoop<T> {
             num_touch: 0,
             first_touch: None,
             active_window: None,
+            click_counter: Default::default(),
+            held_key_press: None,
         };
 
         // Register for device hotplug events
@@ -272,10 +355,26 @@ impl<T: 'static> EventLoop<T> {
         EventLoop {
             poll,
             waker,
+            #[cfg(target_os = "linux")]
+            wakeup_timer,
             event_processor,
             redraw_receiver: PeekableReceiver::from_recv(redraw_channel),
-            user_receiver: PeekableReceiver::from_recv(user_channel),
+            user_receiver,
             user_sender,
+            #[cfg(all(target_os = "linux", feature = "gamepad"))]
+            gamepad_receiver,
+            #[cfg(all(target_os = "linux", feature = "gamepad"))]
+            _gamepad_monitor: gamepad_monitor,
+            #[cfg(feature = "dbus")]
+            session_lock_receiver,
+            #[cfg(feature = "dbus")]
+            _session_lock_monitor: session_lock_monitor,
+            #[cfg(feature = "accesskit")]
+            accessibility_receiver,
+            #[cfg(target_os = "linux")]
+            power_receiver,
+            #[cfg(target_os = "linux")]
+            _power_monitor: power_monitor,
             target,
         }
     }
@@ -284,6 +383,7 @@ impl<T: 'static> EventLoop<T> {
         EventLoopProxy {
             user_sender: self.user_sender.clone(),
             waker: self.waker.clone(),
+            wakeup: ProxyWakeup::default(),
         }
     }
 
@@ -293,7 +393,7 @@ impl<T: 'static> EventLoop<T> {
 
     pub fn run_return<F>(&mut self, mut callback: F) -> i32
     where
-        F: FnMut(Event<'_, T>, &RootELW<T>, &mut ControlFlow),
+        F: FnMut(Event<T>, &RootELW<T>, &mut ControlFlow),
     {
         struct IterationResult {
             deadline: Option<Instant>,
@@ -307,7 +407,7 @@ impl<T: 'static> EventLoop<T> {
             callback: &mut F,
         ) -> IterationResult
         where
-            F: FnMut(Event<'_, T>, &RootELW<T>, &mut ControlFlow),
+            F: FnMut(Event<T>, &RootELW<T>, &mut ControlFlow),
         {
             sticky_exit_callback(
                 crate::event::Event::NewEvents(*cause),
@@ -319,15 +419,63 @@ impl<T: 'static> EventLoop<T> {
             // Process all pending events
             this.drain_events(callback, control_flow);
 
-            // Empty the user event buffer
+            #[cfg(all(target_os = "linux", feature = "gamepad"))]
+            while let Ok((gamepad_id, event)) = this.gamepad_receiver.try_recv() {
+                sticky_exit_callback(
+                    crate::event::Event::GamepadEvent { gamepad_id, event },
+                    &this.target,
+                    control_flow,
+                    callback,
+                );
+            }
+
+            #[cfg(feature = "dbus")]
+            while let Ok(locked) = this.session_lock_receiver.try_recv() {
+                let event = if locked {
+                    Event::SessionSleep(crate::event::SessionSleepReason::Locked)
+                } else {
+                    Event::SessionWake(crate::event::SessionSleepReason::Locked)
+                };
+                sticky_exit_callback(event, &this.target, control_flow, callback);
+            }
+
+            #[cfg(feature = "accesskit")]
+            while let Ok((window_id, request)) = this.accessibility_receiver.try_recv() {
+                sticky_exit_callback(
+                    Event::WindowEvent {
+                        window_id: crate::window::WindowId(window_id),
+                        event: WindowEvent::AccessibilityActionRequested(request),
+                    },
+                    &this.target,
+                    control_flow,
+                    callback,
+                );
+            }
+
+            #[cfg(target_os = "linux")]
+            if this.power_receiver.try_recv().is_ok() {
+                while this.power_receiver.try_recv().is_ok() {}
+                sticky_exit_callback(
+                    Event::PowerStateChanged,
+                    &this.target,
+                    control_flow,
+                    callback,
+                );
+            }
+
+            // Empty the user event buffer, up to the first event asking for its own wakeup
             {
-                while let Ok(event) = this.user_receiver.try_recv() {
+                this.user_receiver.clear_wakeup();
+                while let Some((event, wakeup)) = this.user_receiver.try_recv() {
                     sticky_exit_callback(
                         crate::event::Event::UserEvent(event),
                         &this.target,
                         control_flow,
                         callback,
                     );
+                    if wakeup == ProxyWakeup::PerEvent {
+                        break;
+                    }
                 }
             }
             // send MainEventsCleared
@@ -422,27 +570,9 @@ impl<T: 'static> EventLoop<T> {
             if let ControlFlow::ExitWithCode(code) = control_flow {
                 break code;
             }
-            let has_pending = self.event_processor.poll()
-                || self.user_receiver.has_incoming()
-                || self.redraw_receiver.has_incoming();
-            if !has_pending {
-                // Wait until
-                if let Err(e) = self.poll.poll(&mut events, iter_result.timeout) {
-                    if e.raw_os_error() != Some(libc::EINTR) {
-                        panic!("epoll returned an error: {:?}", e);
-                    }
-                }
-                events.clear();
-
-                if control_flow == ControlFlow::Wait {
-                    // We don't go straight into executing the event loop iteration, we instead go
-                    // to the start of this loop and check again if there's any pending event. We
-                    // must do this because during the execution of the iteration we sometimes wake
-                    // the mio waker, and if the waker is already awaken before we call poll(),
-                    // then poll doesn't block, but it returns immediately. This caused the event
-                    // loop to run continously even if the control_flow was `Wait`
-                    continue;
-                }
+            // `ControlFlow::Poll` doesn't wait at all.
+            if iter_result.timeout != Some(Duration::ZERO) {
+                self.wait(&mut events, iter_result.deadline);
             }
 
             let wait_cancelled = iter_result
@@ -456,6 +586,22 @@ impl<T: 'static> EventLoop<T> {
                 };
             }
 
+            if let StartCause::WaitCancelled {
+                start,
+                requested_resume,
+            } = cause
+            {
+                if !self.user_receiver.is_empty()
+                    && !self.redraw_receiver.has_incoming()
+                    && !self.event_processor.poll()
+                {
+                    cause = StartCause::UserEventReceived {
+                        start,
+                        requested_resume,
+                    };
+                }
+            }
+
             iter_result = single_iteration(self, &mut control_flow, &mut cause, &mut callback);
         };
 
@@ -467,9 +613,69 @@ impl<T: 'static> EventLoop<T> {
         exit_code
     }
 
+    /// Whether an iteration of the event loop has anything to deliver.
+    fn has_pending(&mut self) -> bool {
+        let has_pending = self.event_processor.poll()
+            || !self.user_receiver.is_empty()
+            || self.redraw_receiver.has_incoming();
+        #[cfg(feature = "dbus")]
+        let has_pending = has_pending || self.session_lock_receiver.has_incoming();
+        #[cfg(target_os = "linux")]
+        let has_pending = has_pending || self.power_receiver.has_incoming();
+        #[cfg(feature = "accesskit")]
+        let has_pending = has_pending || self.accessibility_receiver.has_incoming();
+        #[cfg(all(target_os = "linux", feature = "gamepad"))]
+        let has_pending = has_pending || self.gamepad_receiver.has_incoming();
+        has_pending
+    }
+
+    /// Blocks until there's something to deliver, or until `deadline` is reached.
+    ///
+    /// The X connection, the waker and the wakeup timer are all polled by the same `epoll`
+    /// instance. The waker is woken for every message sent to the event loop, including the ones
+    /// the previous iteration already drained, so wakeups with nothing to deliver go back to
+    /// waiting instead of running an iteration.
+    fn wait(&mut self, events: &mut Events, deadline: Option<Instant>) {
+        while !self.has_pending() {
+            let timeout = match deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return;
+                    }
+                    Some(deadline - now)
+                }
+                None => None,
+            };
+
+            // `epoll` rounds its timeout up to the next millisecond, so timeouts are delegated to
+            // the wakeup timer instead.
+            #[cfg(target_os = "linux")]
+            let timeout: Option<Duration> = {
+                if let Some(timeout) = timeout {
+                    self.wakeup_timer.arm(timeout);
+                }
+                None
+            };
+
+            {
+                trace_span!("wait", timeout = ?timeout);
+                if let Err(e) = self.poll.poll(events, timeout) {
+                    if e.raw_os_error() != Some(libc::EINTR) {
+                        panic!("epoll returned an error: {:?}", e);
+                    }
+                }
+            }
+            events.clear();
+
+            #[cfg(target_os = "linux")]
+            self.wakeup_timer.disarm();
+        }
+    }
+
     pub fn run<F>(mut self, callback: F) -> !
     where
-        F: 'static + FnMut(Event<'_, T>, &RootELW<T>, &mut ControlFlow),
+        F: 'static + FnMut(Event<T>, &RootELW<T>, &mut ControlFlow),
     {
         let exit_code = self.run_return(callback);
         ::std::process::exit(exit_code);
@@ -477,7 +683,7 @@ impl<T: 'static> EventLoop<T> {
 
     fn drain_events<F>(&mut self, callback: &mut F, control_flow: &mut ControlFlow)
     where
-        F: FnMut(Event<'_, T>, &RootELW<T>, &mut ControlFlow),
+        F: FnMut(Event<T>, &RootELW<T>, &mut ControlFlow),
     {
         let target = &self.target;
         let mut xev = MaybeUninit::uninit();
@@ -485,7 +691,18 @@ impl<T: 'static> EventLoop<T> {
 
         while unsafe { self.event_processor.poll_one_event(xev.as_mut_ptr()) } {
             let mut xev = unsafe { xev.assume_init() };
+            trace_span!("x11 event", kind = xev.get_type());
+            let device_events_allowed = match wt.device_events.get() {
+                DeviceEvents::Always => true,
+                DeviceEvents::WhenFocused => self.event_processor.active_window.is_some(),
+                DeviceEvents::Never => false,
+            };
             self.event_processor.process_event(&mut xev, |event| {
+                if let Event::DeviceEvent { .. } = event {
+                    if !device_events_allowed {
+                        return;
+                    }
+                }
                 sticky_exit_callback(
                     event,
                     target,
@@ -503,6 +720,22 @@ impl<T: 'static> EventLoop<T> {
                     },
                 );
             });
+            wt.event_time.set(None);
+        }
+    }
+}
+
+impl<T: 'static> Drop for EventLoop<T> {
+    fn drop(&mut self) {
+        // The display of the application is only borrowed for as long as this event loop lives:
+        // a later event loop must not reuse it once the application may have closed it.
+        let xconn = &get_xtarget(&self.target).xconn;
+        if xconn.owns_display() {
+            return;
+        }
+        let mut backend = super::X11_BACKEND.lock();
+        if matches!(&*backend, Some(Ok(backend_xconn)) if Arc::ptr_eq(backend_xconn, xconn)) {
+            *backend = None;
         }
     }
 }
@@ -521,14 +754,262 @@ impl<T> EventLoopWindowTarget<T> {
     pub fn x_connection(&self) -> &Arc<XConnection> {
         &self.xconn
     }
+
+    /// Returns the input method, opening it if this is the first window.
+    fn ime(&self) -> &RefCell<Ime> {
+        self.ime.get_or_init(|| {
+            // Input methods will open successfully without setting the locale, but it won't be
+            // possible to actually commit pre-edit sequences.
+            unsafe {
+                // Remember default locale to restore it if target locale is unsupported
+                // by Xlib
+                let default_locale = setlocale(LC_CTYPE, ptr::null());
+                setlocale(LC_CTYPE, b"\0".as_ptr() as *const _);
+
+                // Check if set locale is supported by Xlib.
+                // If not, calls to some Xlib functions like `XSetLocaleModifiers`
+                // will fail.
+                let locale_supported = (self.xconn.xlib.XSupportsLocale)() == 1;
+                if !locale_supported {
+                    let unsupported_locale = setlocale(LC_CTYPE, ptr::null());
+                    warn!(
+                        "Unsupported locale \"{}\". Restoring default locale \"{}\".",
+                        CStr::from_ptr(unsupported_locale).to_string_lossy(),
+                        CStr::from_ptr(default_locale).to_string_lossy()
+                    );
+                    // Restore default locale
+                    setlocale(LC_CTYPE, default_locale);
+                }
+            }
+            let result = Ime::new(Arc::clone(&self.xconn));
+            if let Err(ImeCreationError::OpenFailure(ref state)) = result {
+                panic!("Failed to open input method: {:#?}", state);
+            }
+            RefCell::new(result.expect("Failed to set input method destruction callback"))
+        })
+    }
+
+    #[cfg(feature = "rwh_05")]
+    pub fn raw_display_handle_rwh_05(&self) -> rwh_05::RawDisplayHandle {
+        let mut handle = rwh_05::XlibDisplayHandle::empty();
+        handle.display = self.xconn.display as *mut _;
+        handle.screen = unsafe { (self.xconn.xlib.XDefaultScreen)(self.xconn.display) };
+        rwh_05::RawDisplayHandle::Xlib(handle)
+    }
+
+    #[cfg(feature = "rwh_06")]
+    pub fn raw_display_handle_rwh_06(
+        &self,
+    ) -> Result<rwh_06::RawDisplayHandle, rwh_06::HandleError> {
+        let display = std::ptr::NonNull::new(self.xconn.display as *mut _);
+        let screen = unsafe { (self.xconn.xlib.XDefaultScreen)(self.xconn.display) };
+        Ok(rwh_06::RawDisplayHandle::Xlib(
+            rwh_06::XlibDisplayHandle::new(display, screen),
+        ))
+    }
+
+    pub fn listen_device_events(&self, allowed: DeviceEvents) {
+        let mask_before = self.raw_event_mask();
+        self.device_events.set(allowed);
+        let mask = self.raw_event_mask();
+        if mask == mask_before {
+            return;
+        }
+
+        if let Some(info) = DeviceInfo::get(&self.xconn, ffi::XIAllDevices) {
+            for info in info.iter().filter(|info| Device::physical_device(info)) {
+                self.xconn
+                    .select_xinput_events(self.root, info.deviceid, mask)
+                    .queue();
+            }
+        }
+        self.xconn.flush_requests().unwrap_or_else(|err| {
+            warn!("Failed to update the raw input event selection: {:?}", err)
+        });
+    }
+
+    pub fn event_timestamp(&self) -> Option<Instant> {
+        self.event_time.get()
+    }
+
+    #[cfg(feature = "accesskit")]
+    pub fn update_accessibility(&self, window_id: WindowId, event: &WindowEvent) {
+        if !matches!(
+            event,
+            WindowEvent::Focused(_) | WindowEvent::Moved(_) | WindowEvent::Resized(_)
+        ) {
+            return;
+        }
+        let window = match self
+            .windows
+            .borrow()
+            .get(&window_id)
+            .and_then(Weak::upgrade)
+        {
+            Some(window) => window,
+            None => return,
+        };
+        match *event {
+            WindowEvent::Focused(focused) => window.accessibility.set_focused(focused),
+            _ => window.update_accessibility_bounds(),
+        }
+    }
+
+    pub fn key_code_to_text(&self, code: KeyCode, modifiers: ModifiersState) -> Option<String> {
+        let keycode = keymap::physicalkey_to_xkb_keycode(code)?;
+        // The XKB group is stored in bits 13 and 14 of the core state mask.
+        let mut state = (self.xconn.xkb_group().unwrap_or(0) as c_uint) << 13;
+        if modifiers.shift() {
+            state |= ffi::ShiftMask;
+        }
+        if modifiers.alt() {
+            state |= ffi::Mod1Mask;
+        }
+        if modifiers.logo() {
+            state |= ffi::Mod4Mask;
+        }
+
+        let keysym = self
+            .xconn
+            .lookup_keysym_with_state(keycode as ffi::KeyCode, state);
+        match keymap::keysym_to_key(keysym as u32, None) {
+            Key::Character(text) => Some(text.to_string()),
+            Key::Dead(ch) => ch.map(String::from),
+            _ => None,
+        }
+    }
+
+    /// The XInput2 raw events to select on physical devices.
+    fn raw_event_mask(&self) -> i32 {
+        match self.device_events.get() {
+            DeviceEvents::Never => 0,
+            DeviceEvents::Always | DeviceEvents::WhenFocused => {
+                ffi::XI_RawMotionMask
+                    | ffi::XI_RawButtonPressMask
+                    | ffi::XI_RawButtonReleaseMask
+                    | ffi::XI_RawKeyPressMask
+                    | ffi::XI_RawKeyReleaseMask
+            }
+        }
+    }
+
+    pub fn devices(&self) -> Vec<RootDeviceHandle> {
+        let info = match DeviceInfo::get(&self.xconn, ffi::XIAllDevices) {
+            Some(info) => info,
+            None => return Vec::new(),
+        };
+        let pressure_label = unsafe { self.xconn.get_atom_unchecked(b"Abs Pressure\0") };
+
+        info.iter()
+            .map(|info| {
+                let name = unsafe { CStr::from_ptr(info.name) }
+                    .to_string_lossy()
+                    .into_owned();
+
+                let mut capabilities = DeviceCapabilities::empty();
+                let mut touch_mode = None;
+                for class_ptr in Device::classes(info) {
+                    let class = unsafe { &**class_ptr };
+                    match class._type {
+                        ffi::XIKeyClass => capabilities |= DeviceCapabilities::KEYBOARD,
+                        ffi::XIButtonClass => capabilities |= DeviceCapabilities::POINTER,
+                        ffi::XIScrollClass => capabilities |= DeviceCapabilities::SCROLL,
+                        ffi::XIValuatorClass => {
+                            let info = unsafe {
+                                mem::transmute::<&ffi::XIAnyClassInfo, &ffi::XIValuatorClassInfo>(
+                                    class,
+                                )
+                            };
+                            if info.label == pressure_label {
+                                capabilities |= DeviceCapabilities::PRESSURE;
+                            }
+                        }
+                        ffi::XITouchClass => {
+                            let info = unsafe {
+                                mem::transmute::<&ffi::XIAnyClassInfo, &ffi::XITouchClassInfo>(
+                                    class,
+                                )
+                            };
+                            capabilities |= DeviceCapabilities::TOUCH;
+                            touch_mode = Some(info.mode);
+                        }
+                        _ => (),
+                    }
+                }
+
+                let kind = match info._use {
+                    ffi::XIMasterKeyboard => DeviceKind::Keyboard,
+                    ffi::XIMasterPointer => DeviceKind::Mouse,
+                    _ => device_kind(&name, capabilities, touch_mode),
+                };
+
+                let product_id = self.xconn.device_product_id(info.deviceid);
+                let persistent_id = match product_id {
+                    Some((vendor, product)) => format!("{:04x}:{:04x}:{}", vendor, product, name),
+                    None => name.clone(),
+                };
+
+                RootDeviceHandle {
+                    id: mkdid(info.deviceid),
+                    name,
+                    kind,
+                    capabilities,
+                    vendor_id: product_id.map(|(vendor, _)| vendor),
+                    product_id: product_id.map(|(_, product)| product),
+                    persistent_id: Some(persistent_id),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Guesses the kind of a physical device, since XInput2 only describes its classes.
+fn device_kind(
+    name: &str,
+    capabilities: DeviceCapabilities,
+    touch_mode: Option<c_int>,
+) -> DeviceKind {
+    let name = name.to_lowercase();
+    let name_has = |words: &[&str]| words.iter().any(|word| name.contains(word));
+
+    match touch_mode {
+        Some(ffi::XIDirectTouch) => return DeviceKind::Touchscreen,
+        Some(ffi::XIDependentTouch) => return DeviceKind::Touchpad,
+        _ => (),
+    }
+
+    if name_has(&["touchpad", "trackpad", "glidepoint"]) {
+        DeviceKind::Touchpad
+    } else if name_has(&["stylus", "pen", "eraser", "wacom", "tablet"])
+        && capabilities.contains(DeviceCapabilities::POINTER)
+    {
+        DeviceKind::Tablet
+    } else if name_has(&["gamepad", "joystick", "controller"]) {
+        DeviceKind::Gamepad
+    } else if capabilities.contains(DeviceCapabilities::POINTER) {
+        DeviceKind::Mouse
+    } else if capabilities.contains(DeviceCapabilities::KEYBOARD) {
+        DeviceKind::Keyboard
+    } else {
+        DeviceKind::Other
+    }
 }
 
 impl<T: 'static> EventLoopProxy<T> {
     pub fn send_event(&self, event: T) -> Result<(), EventLoopClosed<T>> {
-        self.user_sender
-            .send(event)
-            .map_err(|e| EventLoopClosed(e.0))
-            .map(|_| self.waker.wake().unwrap())
+        // Any event still waiting in the queue is going to be drained along with this one.
+        let needs_wakeup = self
+            .user_sender
+            .send(event, self.wakeup)
+            .map_err(EventLoopClosed)?;
+        if needs_wakeup {
+            self.waker.wake().unwrap();
+        }
+        Ok(())
+    }
+
+    pub fn set_wakeup(&mut self, wakeup: ProxyWakeup) {
+        self.wakeup = wakeup;
     }
 }
 
@@ -581,6 +1062,14 @@ impl WindowId {
     }
 }
 
+impl Handle for WindowId {
+    // XIDs are only 64 bits wide on 64-bit targets.
+    #[allow(clippy::useless_conversion)]
+    fn to_u64(self) -> u64 {
+        u64::from(self.0)
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DeviceId(c_int);
 
@@ -590,6 +1079,12 @@ impl DeviceId {
     }
 }
 
+impl Handle for DeviceId {
+    fn to_u64(self) -> u64 {
+        self.0 as u64
+    }
+}
+
 pub struct Window(Arc<UnownedWindow>);
 
 impl Deref for Window {
@@ -619,6 +1114,9 @@ impl Drop for Window {
     fn drop(&mut self) {
         let window = self.deref();
         let xconn = &window.xconn;
+        if !window.key_repeat_enabled() && window.has_focus() {
+            xconn.restore_auto_repeat();
+        }
         unsafe {
             (xconn.xlib.XDestroyWindow)(xconn.display, window.id().0);
             // If the window was somehow already destroyed, we'll get a `BadWindow` error, which we don't care about.
@@ -676,11 +1174,36 @@ fn mkdid(w: c_int) -> crate::event::DeviceId {
 struct Device {
     _name: String,
     scroll_axes: Vec<(i32, ScrollAxis)>,
+    // Present for the tablet tools, identified by their pressure axis.
+    pen: Option<PenState>,
+    // The valuators of the relative motion along the X and Y axes.
+    motion_axes: (i32, i32),
     // For master devices, this is the paired device (pointer <-> keyboard).
     // For slave devices, this is the master.
     attachment: c_int,
 }
 
+/// What a valuator of a device reports in raw events.
+#[derive(Debug, Copy, Clone)]
+enum RawAxis {
+    MotionX,
+    MotionY,
+    /// Scrolling, with the valuator increment of a scroll step.
+    Scroll(ScrollOrientation, f64),
+    Other,
+}
+
+impl RawAxis {
+    /// The axis of the valuator `number` of a mouse.
+    fn default_for(number: i32) -> Self {
+        match number {
+            0 => RawAxis::MotionX,
+            1 => RawAxis::MotionY,
+            _ => RawAxis::Other,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 struct ScrollAxis {
     increment: f64,
@@ -694,26 +1217,66 @@ enum ScrollOrientation {
     Horizontal,
 }
 
+#[derive(Debug, Copy, Clone)]
+struct PenAxis {
+    number: i32,
+    min: f64,
+    max: f64,
+    resolution: i32,
+}
+
+impl PenAxis {
+    /// Converts the value of a tilt axis to degrees, from its resolution in units per radian when
+    /// the driver reports it, as the Wacom driver does, and otherwise from its range.
+    fn tilt_degrees(&self, value: f64) -> f64 {
+        let degrees = if self.resolution > 0 {
+            (value / self.resolution as f64).to_degrees()
+        } else if self.max > self.min {
+            let center = (self.min + self.max) / 2.0;
+            (value - center) / (self.max - center) * 90.0
+        } else {
+            0.0
+        };
+        degrees.clamp(-90.0, 90.0)
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+struct PenState {
+    pressure_axis: PenAxis,
+    tilt_x_axis: Option<PenAxis>,
+    tilt_y_axis: Option<PenAxis>,
+    eraser: bool,
+    pressure: f64,
+    tilt: (f64, f64),
+}
+
 impl Device {
     fn new<T: 'static>(el: &EventProcessor<T>, info: &ffi::XIDeviceInfo) -> Self {
         let name = unsafe { CStr::from_ptr(info.name).to_string_lossy() };
         let mut scroll_axes = Vec::new();
+        let mut motion_axes = (0, 1);
+        let mut pen = None;
 
         let wt = get_xtarget(&el.target);
 
         if Device::physical_device(info) {
-            // Register for global raw events
-            let mask = ffi::XI_RawMotionMask
-                | ffi::XI_RawButtonPressMask
-                | ffi::XI_RawButtonReleaseMask
-                | ffi::XI_RawKeyPressMask
-                | ffi::XI_RawKeyReleaseMask;
-            // The request buffer is flushed when we poll for events
-            wt.xconn
-                .select_xinput_events(wt.root, info.deviceid, mask)
-                .queue();
-
-            // Identify scroll axes
+            // Register for global raw events, unless device events are disabled
+            let mask = wt.raw_event_mask();
+            if mask != 0 {
+                // The request buffer is flushed when we poll for events
+                wt.xconn
+                    .select_xinput_events(wt.root, info.deviceid, mask)
+                    .queue();
+            }
+
+            // Identify scroll axes and pen axes
+            let pressure_label = unsafe { wt.xconn.get_atom_unchecked(b"Abs Pressure\0") };
+            let tilt_x_label = unsafe { wt.xconn.get_atom_unchecked(b"Abs Tilt X\0") };
+            let tilt_y_label = unsafe { wt.xconn.get_atom_unchecked(b"Abs Tilt Y\0") };
+            let rel_x_label = unsafe { wt.xconn.get_atom_unchecked(b"Rel X\0") };
+            let rel_y_label = unsafe { wt.xconn.get_atom_unchecked(b"Rel Y\0") };
+            let (mut pressure_axis, mut tilt_x_axis, mut tilt_y_axis) = (None, None, None);
             for class_ptr in Device::classes(info) {
                 let class = unsafe { &**class_ptr };
                 if class._type == ffi::XIScrollClass {
@@ -732,13 +1295,46 @@ impl Device {
                             position: 0.0,
                         },
                     ));
+                } else if class._type == ffi::XIValuatorClass {
+                    let info = unsafe {
+                        mem::transmute::<&ffi::XIAnyClassInfo, &ffi::XIValuatorClassInfo>(class)
+                    };
+                    let axis = Some(PenAxis {
+                        number: info.number,
+                        min: info.min,
+                        max: info.max,
+                        resolution: info.resolution,
+                    });
+                    if info.label == pressure_label {
+                        pressure_axis = axis;
+                    } else if info.label == tilt_x_label {
+                        tilt_x_axis = axis;
+                    } else if info.label == tilt_y_label {
+                        tilt_y_axis = axis;
+                    } else if info.label == rel_x_label {
+                        motion_axes.0 = info.number;
+                    } else if info.label == rel_y_label {
+                        motion_axes.1 = info.number;
+                    }
                 }
             }
+
+            pen = pressure_axis.map(|pressure_axis| PenState {
+                pressure_axis,
+                tilt_x_axis,
+                tilt_y_axis,
+                // The drivers expose the eraser end of the pens as a device of its own.
+                eraser: name.to_lowercase().contains("eraser"),
+                pressure: 0.0,
+                tilt: (0.0, 0.0),
+            });
         }
 
         let mut device = Device {
             _name: name.into_owned(),
             scroll_axes,
+            pen,
+            motion_axes,
             attachment: info.attachment,
         };
         device.reset_scroll_position(info);
@@ -765,6 +1361,19 @@ impl Device {
         }
     }
 
+    /// What the valuator `number` of the device reports in raw events.
+    fn raw_axis(&self, number: i32) -> RawAxis {
+        if let Some(&(_, axis)) = self.scroll_axes.iter().find(|&&(axis, _)| axis == number) {
+            RawAxis::Scroll(axis.orientation, axis.increment)
+        } else if number == self.motion_axes.0 {
+            RawAxis::MotionX
+        } else if number == self.motion_axes.1 {
+            RawAxis::MotionY
+        } else {
+            RawAxis::Other
+        }
+    }
+
     #[inline]
     fn physical_device(info: &ffi::XIDeviceInfo) -> bool {
         info._use == ffi::XISlaveKeyboard
@@ -191,7 +191,7 @@ impl XConnection {
     fn get_frame_extents(&self, window: ffi::Window) -> Option<FrameExtents> {
         let extents_atom = unsafe { self.get_atom_unchecked(b"_NET_FRAME_EXTENTS\0") };
 
-        if !hint_is_supported(extents_atom) {
+        if !self.hint_is_supported(extents_atom) {
             return None;
         }
 
@@ -219,7 +219,7 @@ impl XConnection {
     pub fn is_top_level(&self, window: ffi::Window, root: ffi::Window) -> Option<bool> {
         let client_list_atom = unsafe { self.get_atom_unchecked(b"_NET_CLIENT_LIST\0") };
 
-        if !hint_is_supported(client_list_atom) {
+        if !self.hint_is_supported(client_list_atom) {
             return None;
         }
 
@@ -93,6 +93,56 @@ impl WindowType {
     }
 }
 
+bitflags! {
+    /// Extended X window states. Maps directly to a subset of
+    /// [`_NET_WM_STATE`](https://specifications.freedesktop.org/wm-spec/wm-spec-1.5.html).
+    #[derive(Default)]
+    pub struct WindowState: u8 {
+        /// The window keeps its position when the virtual desktop scrolls, and is shown on all
+        /// desktops by most window managers.
+        const STICKY            = 1 << 0;
+        /// The window isn't shown by pagers.
+        const SKIP_PAGER        = 1 << 1;
+        /// The window is kept above most others.
+        const ABOVE             = 1 << 2;
+        /// The window is kept below most others.
+        const BELOW             = 1 << 3;
+        /// The window needs attention, e.g. its taskbar entry is highlighted.
+        const DEMANDS_ATTENTION = 1 << 4;
+    }
+}
+
+impl WindowState {
+    const ATOM_NAMES: [(WindowState, &'static [u8]); 5] = [
+        (WindowState::STICKY, b"_NET_WM_STATE_STICKY\0"),
+        (WindowState::SKIP_PAGER, b"_NET_WM_STATE_SKIP_PAGER\0"),
+        (WindowState::ABOVE, b"_NET_WM_STATE_ABOVE\0"),
+        (WindowState::BELOW, b"_NET_WM_STATE_BELOW\0"),
+        (
+            WindowState::DEMANDS_ATTENTION,
+            b"_NET_WM_STATE_DEMANDS_ATTENTION\0",
+        ),
+    ];
+
+    pub(crate) fn as_atoms(&self, xconn: &Arc<XConnection>) -> Vec<ffi::Atom> {
+        Self::ATOM_NAMES
+            .iter()
+            .filter(|(state, _)| self.contains(*state))
+            .map(|(_, atom_name)| unsafe { xconn.get_atom_unchecked(atom_name) })
+            .collect()
+    }
+
+    pub(crate) fn from_atoms(atoms: &[ffi::Atom], xconn: &Arc<XConnection>) -> Self {
+        Self::ATOM_NAMES
+            .iter()
+            .filter(|(_, atom_name)| {
+                let atom = unsafe { xconn.get_atom_unchecked(atom_name) };
+                atoms.contains(&atom)
+            })
+            .fold(WindowState::empty(), |states, (state, _)| states | *state)
+    }
+}
+
 pub struct MotifHints {
     hints: MwmHints,
 }
@@ -81,7 +81,6 @@ impl XConnection {
         Flusher::new(self)
     }
 
-    #[allow(dead_code)]
     pub fn select_xkb_events(&self, device_id: c_uint, mask: c_ulong) -> Option<Flusher<'_>> {
         let status = unsafe { (self.xlib.XkbSelectEvents)(self.display, device_id, mask, mask) };
         if status == ffi::True {
@@ -91,6 +90,44 @@ impl XConnection {
         }
     }
 
+    /// Returns the USB vendor and product IDs of an XInput2 device, as reported by its driver
+    /// through the `Device Product ID` property.
+    pub fn device_product_id(&self, device_id: c_int) -> Option<(u16, u16)> {
+        let property = unsafe { self.get_atom_unchecked(b"Device Product ID\0") };
+        let mut actual_type = 0;
+        let mut actual_format = 0;
+        let mut num_items = 0;
+        let mut bytes_after = 0;
+        let mut data: *mut c_uchar = ptr::null_mut();
+        let status = unsafe {
+            (self.xinput2.XIGetProperty)(
+                self.display,
+                device_id,
+                property,
+                0,
+                2,
+                ffi::False,
+                ffi::XA_INTEGER,
+                &mut actual_type,
+                &mut actual_format,
+                &mut num_items,
+                &mut bytes_after,
+                &mut data,
+            )
+        };
+        if status != ffi::Success as c_int {
+            return None;
+        }
+        let data = XSmartPointer::new(self, data)?;
+        if actual_type != ffi::XA_INTEGER || actual_format != 32 || num_items < 2 {
+            return None;
+        }
+
+        // Unlike `XGetWindowProperty`, XInput2 doesn't widen 32-bit items to `c_long`.
+        let ids = unsafe { slice::from_raw_parts(data.ptr as *const u32, 2) };
+        Some((ids[0] as u16, ids[1] as u16))
+    }
+
     pub fn query_pointer(
         &self,
         window: ffi::Window,
@@ -1,7 +1,11 @@
-use std::{iter::Enumerate, ptr, slice::Iter};
+use std::{ffi::CStr, iter::Enumerate, ptr, slice::Iter};
 
 use super::*;
 
+// Not defined by `x11-dl`.
+const XKB_USE_CORE_KBD: c_uint = 0x0100;
+const XKB_GROUP_NAMES_MASK: c_uint = 1 << 12;
+
 pub struct Keymap {
     keys: [u8; 32],
 }
@@ -76,6 +80,16 @@ impl XConnection {
         keysym
     }
 
+    /// Returns the keysym the key produces with the modifiers of the X11 `state` mask.
+    pub fn lookup_keysym_with_state(&self, keycode: ffi::KeyCode, state: c_uint) -> ffi::KeySym {
+        let mut xkev: ffi::XKeyEvent = unsafe { mem::zeroed() };
+        xkev.type_ = ffi::KeyPress;
+        xkev.display = self.display;
+        xkev.keycode = keycode as c_uint;
+        xkev.state = state;
+        self.lookup_keysym(&mut xkev)
+    }
+
     pub fn query_keymap(&self) -> Keymap {
         let mut keys = [0; 32];
 
@@ -85,6 +99,112 @@ impl XConnection {
 
         Keymap { keys }
     }
+
+    /// Selects the XKB events notifying about keyboard layout changes on the core keyboard.
+    ///
+    /// Returns the event type the XKB events are reported with, or `None` if the XKB extension
+    /// isn't available.
+    pub fn select_xkb_layout_events(&self) -> Option<c_int> {
+        let mut opcode = 0;
+        let mut event_base = 0;
+        let mut error_base = 0;
+        let mut major = 1;
+        let mut minor = 0;
+        let has_xkb = unsafe {
+            (self.xlib.XkbQueryExtension)(
+                self.display,
+                &mut opcode,
+                &mut event_base,
+                &mut error_base,
+                &mut major,
+                &mut minor,
+            )
+        };
+        if has_xkb != ffi::True {
+            return None;
+        }
+
+        self.select_xkb_events(
+            XKB_USE_CORE_KBD,
+            ffi::XkbNewKeyboardNotifyMask | ffi::XkbNamesNotifyMask,
+        )?
+        .queue();
+        unsafe {
+            (self.xlib.XkbSelectEventDetails)(
+                self.display,
+                XKB_USE_CORE_KBD,
+                ffi::XkbStateNotify as c_uint,
+                ffi::XkbGroupStateMask,
+                ffi::XkbGroupStateMask,
+            );
+        }
+        self.flush_requests().ok()?;
+
+        Some(event_base)
+    }
+
+    /// Returns the active XKB group of the core keyboard.
+    pub fn xkb_group(&self) -> Option<u8> {
+        unsafe {
+            let mut state = MaybeUninit::<ffi::XkbStateRec>::uninit();
+            if (self.xlib.XkbGetState)(self.display, XKB_USE_CORE_KBD, state.as_mut_ptr())
+                != ffi::Success as c_int
+            {
+                return None;
+            }
+            Some(state.assume_init().group)
+        }
+    }
+
+    /// Returns the name of the active XKB group of the core keyboard, such as `English (US)`.
+    pub fn keyboard_layout(&self) -> Option<String> {
+        let group = self.xkb_group()? as usize;
+        unsafe {
+            let desc = (self.xlib.XkbAllocKeyboard)();
+            if desc.is_null() {
+                return None;
+            }
+            let mut name = None;
+            if (self.xlib.XkbGetNames)(self.display, XKB_GROUP_NAMES_MASK, desc)
+                == ffi::Success as c_int
+                && !(*desc).names.is_null()
+            {
+                let atom = (*(*desc).names).groups[group.min(ffi::XkbNumKbdGroups - 1)];
+                if atom != 0 {
+                    name = XSmartPointer::new(self, (self.xlib.XGetAtomName)(self.display, atom))
+                        .map(|name| CStr::from_ptr(name.ptr).to_string_lossy().into_owned());
+                }
+            }
+            (self.xlib.XkbFreeKeyboard)(desc, 0, ffi::True);
+
+            name
+        }
+    }
+
+    /// Turns the server's key auto-repeat off, remembering the previous setting for
+    /// `restore_auto_repeat`.
+    pub fn disable_auto_repeat(&self) {
+        let mut saved = self.saved_auto_repeat.lock();
+        unsafe {
+            if saved.is_none() {
+                let mut state = MaybeUninit::<ffi::XKeyboardState>::uninit();
+                (self.xlib.XGetKeyboardControl)(self.display, state.as_mut_ptr());
+                *saved = Some(state.assume_init().global_auto_repeat == ffi::AutoRepeatModeOn);
+            }
+            (self.xlib.XAutoRepeatOff)(self.display);
+        }
+        let _ = self.flush_requests();
+    }
+
+    /// Restores the key auto-repeat setting saved by `disable_auto_repeat`, if any.
+    pub fn restore_auto_repeat(&self) {
+        if let Some(enabled) = self.saved_auto_repeat.lock().take() {
+            if enabled {
+                unsafe { (self.xlib.XAutoRepeatOn)(self.display) };
+                let _ = self.flush_requests();
+            }
+        }
+    }
 }
 
 fn first_bit(b: u8) -> u8 {
@@ -15,10 +15,12 @@ pub mod modifiers;
 mod randr;
 mod window_property;
 mod wm;
+mod xembed;
+mod xsettings;
 
 pub use self::{
     atom::*, client_msg::*, format::*, geometry::*, hint::*, icon::*, input::*, memory::*,
-    randr::*, window_property::*, wm::*,
+    randr::*, window_property::*, xembed::*,
 };
 
 use std::{
@@ -26,6 +28,7 @@ use std::{
     ops::BitAnd,
     os::raw::*,
     ptr,
+    time::{Duration, Instant},
 };
 
 use super::{ffi, XConnection, XError};
@@ -40,6 +43,27 @@ pub fn maybe_change<T: PartialEq>(field: &mut Option<T>, value: T) -> bool {
     }
 }
 
+/// Converts the server timestamp of an event to an `Instant`.
+///
+/// The X.Org server takes its timestamps from the monotonic clock, in milliseconds, so the age of
+/// an event is how far that clock has moved since. Timestamps that don't fit, e.g. from a remote
+/// server, give `None`.
+pub fn server_time_to_instant(time: ffi::Time) -> Option<Instant> {
+    let mut now = MaybeUninit::<libc::timespec>::uninit();
+    if unsafe { libc::clock_gettime(libc::CLOCK_MONOTONIC, now.as_mut_ptr()) } != 0 {
+        return None;
+    }
+    let now = unsafe { now.assume_init() };
+    let now_ms = (now.tv_sec as u64 * 1000 + now.tv_nsec as u64 / 1_000_000) as u32;
+    // The protocol's timestamps are 32 bits wide and wrap around every ~49.7 days.
+    let age = now_ms.wrapping_sub(time as u32);
+    // Input isn't queued for this long, so such an age means the clocks differ.
+    if age > 10_000 {
+        return None;
+    }
+    Instant::now().checked_sub(Duration::from_millis(age as u64))
+}
+
 pub fn has_flag<T>(bitset: T, flag: T) -> bool
 where
     T: Copy + PartialEq + BitAnd<T, Output = T>,
@@ -2,7 +2,10 @@ use std::{collections::HashMap, slice};
 
 use super::*;
 
-use crate::event::{ElementState, ModifiersState};
+use crate::{
+    event::{ElementState, ModifiersState},
+    platform_impl::platform::keymap,
+};
 
 // Offsets within XModifierKeymap to each set of keycodes.
 // We are only interested in Shift, Control, Alt, and Logo.
@@ -17,6 +20,9 @@ const ALT_OFFSET: usize = 3;
 const LOGO_OFFSET: usize = 6;
 const NUM_MODS: usize = 8;
 
+// The X11 keycodes are the `evdev` scancodes offset by 8.
+const KEYCODE_OFFSET: ffi::KeyCode = 8;
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum Modifier {
     Alt,
@@ -128,12 +134,18 @@ impl ModifierKeyState {
         } else {
             self.keys.retain(|_k, v| get_modifier(&new_state, *v));
             self.state = new_state;
-            Some(new_state)
+            Some(self.modifiers())
         }
     }
 
     pub fn modifiers(&self) -> ModifiersState {
-        self.state
+        // The sides are told apart from the physical location of the held modifier keys.
+        self.keys
+            .keys()
+            .filter_map(|&keycode| keycode.checked_sub(KEYCODE_OFFSET))
+            .fold(self.state, |state, scancode| {
+                state | keymap::modifier_side(keymap::scancode_to_physicalkey(scancode as u32))
+            })
     }
 
     pub fn key_event(&mut self, state: ElementState, keycode: ffi::KeyCode, modifier: Modifier) {
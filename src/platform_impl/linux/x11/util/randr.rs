@@ -35,23 +35,23 @@ pub fn calc_dpi_factor(
 }
 
 impl XConnection {
-    // Retrieve DPI from Xft.dpi property
-    pub unsafe fn get_xft_dpi(&self) -> Option<f64> {
-        (self.xlib.XrmInitialize)();
-        let resource_manager_str = (self.xlib.XResourceManagerString)(self.display);
-        if resource_manager_str.is_null() {
-            return None;
-        }
-        if let Ok(res) = ::std::ffi::CStr::from_ptr(resource_manager_str).to_str() {
-            let name: &str = "Xft.dpi:\t";
-            for pair in res.split('\n') {
-                if pair.starts_with(&name) {
-                    let res = &pair[name.len()..];
-                    return f64::from_str(res).ok();
-                }
-            }
-        }
-        None
+    // Retrieve DPI from Xft.dpi property. The resources are read from the root window, since
+    // `XResourceManagerString` keeps returning them as they were when the display was opened.
+    pub fn get_xft_dpi(&self) -> Option<f64> {
+        let root = unsafe { (self.xlib.XDefaultRootWindow)(self.display) };
+        let resources = self
+            .get_property::<c_uchar>(root, ffi::XA_RESOURCE_MANAGER, ffi::XA_STRING)
+            .ok()?;
+        let resources = String::from_utf8_lossy(&resources);
+        resources
+            .lines()
+            .find_map(|line| line.strip_prefix("Xft.dpi:"))
+            .and_then(|dpi| f64::from_str(dpi.trim()).ok())
+    }
+
+    // Retrieve the DPI set by the desktop environment, preferring Xft.dpi to XSETTINGS
+    pub fn get_desktop_dpi(&self) -> Option<f64> {
+        self.get_xft_dpi().or_else(|| self.get_xsettings_dpi())
     }
     pub unsafe fn get_output_info(
         &self,
@@ -147,7 +147,7 @@ impl XConnection {
                 dpi_override
             }
             EnvVarDPI::NotSet => {
-                if let Some(dpi) = self.get_xft_dpi() {
+                if let Some(dpi) = self.get_desktop_dpi() {
                     dpi / 96.
                 } else {
                     calc_dpi_factor(
@@ -2,28 +2,46 @@ use parking_lot::Mutex;
 
 use super::*;
 
-// This info is global to the window manager.
+// This info is global to the window manager, and only queried once it's needed.
 lazy_static! {
-    static ref SUPPORTED_HINTS: Mutex<Vec<ffi::Atom>> = Mutex::new(Vec::with_capacity(0));
-    static ref WM_NAME: Mutex<Option<String>> = Mutex::new(None);
+    static ref WM_INFO: Mutex<Option<WmInfo>> = Mutex::new(None);
 }
 
-pub fn hint_is_supported(hint: ffi::Atom) -> bool {
-    (*SUPPORTED_HINTS.lock()).contains(&hint)
+struct WmInfo {
+    supported_hints: Vec<ffi::Atom>,
+    name: Option<String>,
 }
 
-pub fn wm_name_is_one_of(names: &[&str]) -> bool {
-    if let Some(ref name) = *WM_NAME.lock() {
-        names.contains(&name.as_str())
-    } else {
-        false
+impl XConnection {
+    pub fn hint_is_supported(&self, hint: ffi::Atom) -> bool {
+        self.with_wm_info(|info| info.supported_hints.contains(&hint))
     }
-}
 
-impl XConnection {
-    pub fn update_cached_wm_info(&self, root: ffi::Window) {
-        *SUPPORTED_HINTS.lock() = self.get_supported_hints(root);
-        *WM_NAME.lock() = self.get_wm_name(root);
+    pub fn wm_name_is_one_of(&self, names: &[&str]) -> bool {
+        self.with_wm_info(|info| {
+            if let Some(ref name) = info.name {
+                names.contains(&name.as_str())
+            } else {
+                false
+            }
+        })
+    }
+
+    /// Forgets the window manager info, so that it's queried again when it's next needed.
+    pub fn invalidate_cached_wm_info(&self) {
+        *WM_INFO.lock() = None;
+    }
+
+    fn with_wm_info<R>(&self, f: impl FnOnce(&WmInfo) -> R) -> R {
+        let mut info = WM_INFO.lock();
+        let info = info.get_or_insert_with(|| {
+            let root = unsafe { (self.xlib.XDefaultRootWindow)(self.display) };
+            WmInfo {
+                supported_hints: self.get_supported_hints(root),
+                name: self.get_wm_name(root),
+            }
+        });
+        f(info)
     }
 
     fn get_supported_hints(&self, root: ffi::Window) -> Vec<ffi::Atom> {
@@ -0,0 +1,87 @@
+//! The [XEmbed](https://specifications.freedesktop.org/xembed-spec/xembed-spec-latest.html)
+//! protocol, through which a window of one client is embedded into a window of another.
+
+use super::*;
+
+/// The version of the protocol we implement.
+pub const XEMBED_VERSION: c_long = 0;
+
+/// The flag of `_XEMBED_INFO` telling that the client wants to be mapped.
+pub const XEMBED_MAPPED: c_ulong = 1 << 0;
+
+/// The `detail` of `XEMBED_FOCUS_IN` keeping the focus where it was in the client.
+pub const XEMBED_FOCUS_CURRENT: c_long = 0;
+
+/// The messages of the protocol we use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XEmbedMessage {
+    EmbeddedNotify = 0,
+    WindowActivate = 1,
+    WindowDeactivate = 2,
+    RequestFocus = 3,
+    FocusIn = 4,
+    FocusOut = 5,
+}
+
+impl XEmbedMessage {
+    pub fn from_raw(message: c_long) -> Option<Self> {
+        match message {
+            0 => Some(XEmbedMessage::EmbeddedNotify),
+            1 => Some(XEmbedMessage::WindowActivate),
+            2 => Some(XEmbedMessage::WindowDeactivate),
+            3 => Some(XEmbedMessage::RequestFocus),
+            4 => Some(XEmbedMessage::FocusIn),
+            5 => Some(XEmbedMessage::FocusOut),
+            _ => None,
+        }
+    }
+}
+
+impl XConnection {
+    /// Sends an XEmbed message to `window`, the meaning of `detail`, `data1` and `data2` depending
+    /// on the message.
+    pub fn send_xembed_message(
+        &self,
+        window: ffi::Window,
+        message: XEmbedMessage,
+        detail: c_long,
+        data1: c_long,
+        data2: c_long,
+    ) -> Flusher<'_> {
+        let xembed_atom = unsafe { self.get_atom_unchecked(b"_XEMBED\0") };
+        self.send_client_msg(
+            window,
+            window,
+            xembed_atom,
+            Some(ffi::NoEventMask),
+            [
+                ffi::CurrentTime as c_long,
+                message as c_long,
+                detail,
+                data1,
+                data2,
+            ],
+        )
+    }
+
+    /// Advertises `window` as an XEmbed client, mapped by its embedder if `mapped` is true.
+    pub fn set_xembed_info(&self, window: ffi::Window, mapped: bool) -> Flusher<'_> {
+        let xembed_info_atom = unsafe { self.get_atom_unchecked(b"_XEMBED_INFO\0") };
+        let flags = if mapped { XEMBED_MAPPED } else { 0 };
+        self.change_property(
+            window,
+            xembed_info_atom,
+            xembed_info_atom,
+            PropMode::Replace,
+            &[XEMBED_VERSION as c_ulong, flags],
+        )
+    }
+
+    /// Returns the flags of the `_XEMBED_INFO` of `window`, or `None` if it doesn't have any.
+    pub fn get_xembed_flags(&self, window: ffi::Window) -> Option<c_ulong> {
+        let xembed_info_atom = unsafe { self.get_atom_unchecked(b"_XEMBED_INFO\0") };
+        self.get_property::<c_ulong>(window, xembed_info_atom, xembed_info_atom)
+            .ok()
+            .and_then(|info| info.get(1).copied())
+    }
+}
@@ -0,0 +1,114 @@
+//! [XSETTINGS](https://specifications.freedesktop.org/xsettings-spec/xsettings-latest.html), through
+//! which desktop environments share their settings, the DPI among them, with X clients.
+
+use std::ffi::CString;
+
+use super::*;
+
+impl XConnection {
+    /// Returns the selection owned by the XSETTINGS manager of the default screen.
+    pub fn xsettings_selection(&self) -> ffi::Atom {
+        let screen = unsafe { (self.xlib.XDefaultScreen)(self.display) };
+        self.get_atom(CString::new(format!("_XSETTINGS_S{}", screen)).unwrap())
+    }
+
+    /// Returns the window of the XSETTINGS manager, or `None` if no manager is running.
+    pub fn xsettings_owner(&self) -> Option<ffi::Window> {
+        let owner =
+            unsafe { (self.xlib.XGetSelectionOwner)(self.display, self.xsettings_selection()) };
+        if owner == 0 {
+            None
+        } else {
+            Some(owner)
+        }
+    }
+
+    /// Watches for changes of the DPI set by the desktop environment: `Xft.dpi` in the
+    /// `RESOURCE_MANAGER` property of `root`, `Xft/DPI` in XSETTINGS, and the XSETTINGS manager
+    /// being replaced, which it announces on `root`.
+    pub fn select_dpi_input(&self, root: ffi::Window) -> Flusher<'_> {
+        unsafe {
+            (self.xlib.XSelectInput)(
+                self.display,
+                root,
+                ffi::PropertyChangeMask | ffi::StructureNotifyMask,
+            );
+        }
+        self.select_xsettings_input()
+    }
+
+    /// Watches the settings of the current XSETTINGS manager, to be called again whenever the
+    /// manager is replaced.
+    pub fn select_xsettings_input(&self) -> Flusher<'_> {
+        if let Some(owner) = self.xsettings_owner() {
+            unsafe { (self.xlib.XSelectInput)(self.display, owner, ffi::PropertyChangeMask) };
+        }
+        Flusher::new(self)
+    }
+
+    /// Returns the DPI set through `Xft/DPI` in XSETTINGS.
+    pub fn get_xsettings_dpi(&self) -> Option<f64> {
+        let owner = self.xsettings_owner()?;
+        let settings_atom = unsafe { self.get_atom_unchecked(b"_XSETTINGS_SETTINGS\0") };
+        let settings = self
+            .get_property::<c_uchar>(owner, settings_atom, settings_atom)
+            .ok()?;
+        // The DPI is in 1024ths of a dot per inch, and -1 stands for the default.
+        match find_integer_setting(&settings, b"Xft/DPI")? {
+            dpi if dpi > 0 => Some(dpi as f64 / 1024.),
+            _ => None,
+        }
+    }
+}
+
+/// Finds the integer setting `name` in the `_XSETTINGS_SETTINGS` property of the manager.
+fn find_integer_setting(settings: &[u8], name: &[u8]) -> Option<i32> {
+    // `LSBFirst` or `MSBFirst`
+    let big_endian = match *settings.first()? {
+        0 => false,
+        1 => true,
+        _ => return None,
+    };
+    let read_u16 = |offset: usize| {
+        let bytes = settings.get(offset..offset + 2)?;
+        let bytes = [bytes[0], bytes[1]];
+        Some(if big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        })
+    };
+    let read_u32 = |offset: usize| {
+        let bytes = settings.get(offset..offset + 4)?;
+        let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+        Some(if big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        })
+    };
+    // Names and strings are padded to 4 bytes.
+    let padded = |len: usize| (len + 3) & !3;
+
+    // The header holds the byte order, the serial of the settings and their count.
+    let count = read_u32(8)?;
+    let mut offset = 12;
+    for _ in 0..count {
+        let setting_type = *settings.get(offset)?;
+        let name_len = read_u16(offset + 2)? as usize;
+        let setting_name = settings.get(offset + 4..offset + 4 + name_len)?;
+        // The value follows the name and the serial of the last change of the setting.
+        let value = offset + 4 + padded(name_len) + 4;
+        offset = match setting_type {
+            // Integer
+            0 if setting_name == name => return read_u32(value).map(|value| value as i32),
+            0 => value + 4,
+            // String
+            1 => value + 4 + padded(read_u32(value)? as usize),
+            // Color, 4 16-bit channels
+            2 => value + 8,
+            _ => return None,
+        };
+    }
+    None
+}
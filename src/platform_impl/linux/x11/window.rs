@@ -25,6 +25,18 @@ use crate::{
     window::{CursorIcon, Fullscreen, Icon, UserAttentionType, WindowAttributes},
 };
 
+#[cfg(feature = "accesskit")]
+use crate::{
+    accessibility::{
+        accesskit::{Rect, TreeUpdate},
+        TreeSource,
+    },
+    platform_impl::{
+        platform::accessibility::{Accessibility, ActionSink},
+        WindowId as PlatformWindowId,
+    },
+};
+
 use super::{
     ffi, util, EventLoopWindowTarget, ImeSender, WakeSender, WindowId, XConnection, XError,
 };
@@ -51,6 +63,10 @@ pub struct SharedState {
     pub resize_increments: Option<Size>,
     pub base_size: Option<Size>,
     pub visibility: Visibility,
+    // The window embedding this one through XEmbed
+    pub xembed_embedder: Option<ffi::Window>,
+    // The windows of other clients embedded into this one through XEmbed
+    pub xembed_clients: Vec<ffi::Window>,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -88,6 +104,8 @@ impl SharedState {
             max_inner_size: None,
             resize_increments: None,
             base_size: None,
+            xembed_embedder: None,
+            xembed_clients: Vec::new(),
         })
     }
 }
@@ -103,9 +121,14 @@ pub struct UnownedWindow {
     cursor: Mutex<CursorIcon>,
     cursor_grabbed: Mutex<bool>,
     cursor_visible: Mutex<bool>,
+    key_repeat_enabled: Mutex<bool>,
     ime_sender: Mutex<ImeSender>,
     pub shared_state: Mutex<SharedState>,
     redraw_sender: WakeSender<WindowId>,
+    #[cfg(feature = "accesskit")]
+    pub(crate) accessibility: Accessibility,
+    #[cfg(feature = "accesskit")]
+    accessibility_sink: ActionSink,
 }
 
 impl UnownedWindow {
@@ -148,10 +171,19 @@ impl UnownedWindow {
             .min_inner_size
             .map(|size| size.to_physical::<u32>(scale_factor).into());
 
-        let position = window_attrs
+        let mut position = window_attrs
             .position
             .map(|position| position.to_physical::<i32>(scale_factor));
 
+        // The position of popups is relative to the client area of their parent.
+        if let (Some(position), Some(parent)) = (position.as_mut(), pl_attribs.x11_parent) {
+            let parent_coords = xconn
+                .translate_coords(parent, root)
+                .map_err(|x_err| os_error!(OsError::XError(x_err)))?;
+            position.x += parent_coords.x_rel_root;
+            position.y += parent_coords.y_rel_root;
+        }
+
         let dimensions = {
             // x11 only applies constraints when the window is actively resized
             // by the user, so we have to manually apply the initial constraints
@@ -251,7 +283,7 @@ impl UnownedWindow {
         let xwindow = unsafe {
             (xconn.xlib.XCreateWindow)(
                 xconn.display,
-                root,
+                pl_attribs.x11_embed_parent.unwrap_or(root),
                 position.map_or(0, |p: PhysicalPosition<i32>| p.x as c_int),
                 position.map_or(0, |p: PhysicalPosition<i32>| p.y as c_int),
                 dimensions.0 as c_uint,
@@ -273,12 +305,17 @@ impl UnownedWindow {
             cursor: Default::default(),
             cursor_grabbed: Mutex::new(false),
             cursor_visible: Mutex::new(true),
+            key_repeat_enabled: Mutex::new(true),
             ime_sender: Mutex::new(event_loop.ime_sender.clone()),
             shared_state: SharedState::new(guessed_monitor, window_attrs.visible),
             redraw_sender: WakeSender {
                 waker: event_loop.redraw_sender.waker.clone(),
                 sender: event_loop.redraw_sender.sender.clone(),
             },
+            #[cfg(feature = "accesskit")]
+            accessibility: Default::default(),
+            #[cfg(feature = "accesskit")]
+            accessibility_sink: event_loop.accessibility_sink.clone(),
         };
 
         // Title must be set before mapping. Some tiling window managers (i.e. i3) use the window
@@ -306,13 +343,7 @@ impl UnownedWindow {
 
             // WM_CLASS must be set *before* mapping the window, as per ICCCM!
             {
-                let (class, instance) = if let Some((instance, class)) = pl_attribs.class {
-                    let instance = CString::new(instance.as_str())
-                        .expect("`WM_CLASS` instance contained null byte");
-                    let class =
-                        CString::new(class.as_str()).expect("`WM_CLASS` class contained null byte");
-                    (instance, class)
-                } else {
+                let (instance, class) = pl_attribs.class.unwrap_or_else(|| {
                     let class = env::args()
                         .next()
                         .as_ref()
@@ -320,33 +351,48 @@ impl UnownedWindow {
                         .and_then(|path| Path::new(path).file_name())
                         .and_then(|bin_name| bin_name.to_str())
                         .map(|bin_name| bin_name.to_owned())
-                        .or_else(|| Some(window_attrs.title.clone()))
-                        .and_then(|string| CString::new(string.as_str()).ok())
-                        .expect("Default `WM_CLASS` class contained null byte");
+                        .unwrap_or_else(|| window_attrs.title.clone());
                     // This environment variable is extraordinarily unlikely to actually be used...
-                    let instance = env::var("RESOURCE_NAME")
-                        .ok()
-                        .and_then(|instance| CString::new(instance.as_str()).ok())
-                        .or_else(|| Some(class.clone()))
-                        .expect("Default `WM_CLASS` instance contained null byte");
+                    let instance = env::var("RESOURCE_NAME").unwrap_or_else(|_| class.clone());
                     (instance, class)
-                };
-
-                let mut class_hint = xconn.alloc_class_hint();
-                (*class_hint).res_name = class.as_ptr() as *mut c_char;
-                (*class_hint).res_class = instance.as_ptr() as *mut c_char;
+                });
 
-                unsafe {
-                    (xconn.xlib.XSetClassHint)(xconn.display, window.xwindow, class_hint.ptr);
-                } //.queue();
+                window.set_class_inner(&instance, &class).queue();
             }
 
             if let Some(flusher) = window.set_pid() {
                 flusher.queue()
             }
 
+            if let Some(embedder) = pl_attribs.x11_embed_parent {
+                xconn
+                    .set_xembed_info(window.xwindow, window_attrs.visible)
+                    .queue();
+                window.shared_state.get_mut().xembed_embedder = Some(embedder);
+            }
+
+            if let Some(parent) = pl_attribs.x11_parent {
+                unsafe {
+                    (xconn.xlib.XSetTransientForHint)(xconn.display, window.xwindow, parent);
+                } //.queue();
+            }
+
             window.set_window_types(pl_attribs.x11_window_types).queue();
 
+            // The states of windows that aren't mapped yet are set directly, as per EWMH.
+            if !pl_attribs.x11_window_state.is_empty() {
+                let state_atom = unsafe { xconn.get_atom_unchecked(b"_NET_WM_STATE\0") };
+                xconn
+                    .change_property(
+                        window.xwindow,
+                        state_atom,
+                        ffi::XA_ATOM,
+                        util::PropMode::Replace,
+                        &pl_attribs.x11_window_state.as_atoms(xconn),
+                    )
+                    .queue();
+            }
+
             if let Some(variant) = pl_attribs.gtk_theme_variant {
                 window.set_gtk_theme_variant(variant).queue();
             }
@@ -361,7 +407,7 @@ impl UnownedWindow {
                     .map(|size| size.to_physical::<u32>(scale_factor));
 
                 if !window_attrs.resizable {
-                    if util::wm_name_is_one_of(&["Xfwm4"]) {
+                    if xconn.wm_name_is_one_of(&["Xfwm4"]) {
                         warn!("To avoid a WM bug, disabling resizing has no effect on Xfwm4");
                     } else {
                         max_inner_size = Some(dimensions.into());
@@ -403,8 +449,10 @@ impl UnownedWindow {
                 (xconn.xlib.XSetWMProtocols)(
                     xconn.display,
                     window.xwindow,
-                    &[event_loop.wm_delete_window, event_loop.net_wm_ping] as *const ffi::Atom
-                        as *mut ffi::Atom,
+                    &[
+                        xconn.get_atom_unchecked(b"WM_DELETE_WINDOW\0"),
+                        xconn.get_atom_unchecked(b"_NET_WM_PING\0"),
+                    ] as *const ffi::Atom as *mut ffi::Atom,
                     2,
                 );
             } //.queue();
@@ -449,7 +497,7 @@ impl UnownedWindow {
                 .queue();
 
             {
-                let result = event_loop.ime.borrow_mut().create_context(window.xwindow);
+                let result = event_loop.ime().borrow_mut().create_context(window.xwindow);
                 if let Err(err) = result {
                     let e = match err {
                         ImeContextCreationError::XError(err) => OsError::XError(err),
@@ -776,6 +824,40 @@ impl UnownedWindow {
         self.xconn.primary_monitor()
     }
 
+    #[cfg(feature = "accesskit")]
+    pub fn enable_accessibility(&self, source: Box<dyn TreeSource>) {
+        self.accessibility.enable(
+            PlatformWindowId::X(self.id()),
+            source,
+            self.accessibility_sink.clone(),
+        );
+        self.accessibility.set_focused(self.has_focus());
+        self.update_accessibility_bounds();
+    }
+
+    #[cfg(feature = "accesskit")]
+    pub fn update_accessibility_tree(&self, update: impl FnOnce() -> TreeUpdate) {
+        self.accessibility.update_tree(update);
+    }
+
+    #[cfg(feature = "accesskit")]
+    pub(crate) fn update_accessibility_bounds(&self) {
+        let rect = |position: PhysicalPosition<i32>, size: PhysicalSize<u32>| {
+            Rect::new(
+                position.x as f64,
+                position.y as f64,
+                position.x as f64 + size.width as f64,
+                position.y as f64 + size.height as f64,
+            )
+        };
+        let inner_position = self.inner_position_physical().into();
+        let outer_position = self.outer_position().unwrap_or(inner_position);
+        self.accessibility.set_bounds(
+            rect(outer_position, self.outer_size()),
+            rect(inner_position, self.inner_size()),
+        );
+    }
+
     fn set_minimized_inner(&self, minimized: bool) -> util::Flusher<'_> {
         unsafe {
             if minimized {
@@ -879,6 +961,27 @@ impl UnownedWindow {
             .expect("Failed to set window title");
     }
 
+    fn set_class_inner(&self, instance: &str, class: &str) -> util::Flusher<'_> {
+        let instance = CString::new(instance).expect("`WM_CLASS` instance contained null byte");
+        let class = CString::new(class).expect("`WM_CLASS` class contained null byte");
+
+        let mut class_hint = self.xconn.alloc_class_hint();
+        class_hint.res_name = instance.as_ptr() as *mut c_char;
+        class_hint.res_class = class.as_ptr() as *mut c_char;
+
+        unsafe {
+            (self.xconn.xlib.XSetClassHint)(self.xconn.display, self.xwindow, class_hint.ptr);
+        }
+        util::Flusher::new(&self.xconn)
+    }
+
+    #[inline]
+    pub fn set_class(&self, instance: &str, class: &str) {
+        self.set_class_inner(instance, class)
+            .flush()
+            .expect("Failed to set `WM_CLASS`");
+    }
+
     fn set_decorations_inner(&self, decorations: bool) -> util::Flusher<'_> {
         let mut hints = self.xconn.get_motif_hints(self.xwindow);
 
@@ -913,6 +1016,32 @@ impl UnownedWindow {
         self.set_netwm(always_on_top.into(), (above_atom as c_long, 0, 0, 0))
     }
 
+    fn set_window_state_inner(&self, state: util::WindowState, enabled: bool) -> util::Flusher<'_> {
+        // A `_NET_WM_STATE` message changes at most two states.
+        for atoms in state.as_atoms(&self.xconn).chunks(2) {
+            let second_atom = atoms.get(1).map_or(0, |&atom| atom as c_long);
+            self.set_netwm(enabled.into(), (atoms[0] as c_long, second_atom, 0, 0))
+                .queue();
+        }
+        util::Flusher::new(&self.xconn)
+    }
+
+    #[inline]
+    pub fn set_window_state(&self, state: util::WindowState, enabled: bool) {
+        self.set_window_state_inner(state, enabled)
+            .flush()
+            .expect("Failed to change window state");
+    }
+
+    #[inline]
+    pub fn window_state(&self) -> util::WindowState {
+        let state_atom = unsafe { self.xconn.get_atom_unchecked(b"_NET_WM_STATE\0") };
+        self.xconn
+            .get_property(self.xwindow, state_atom, ffi::XA_ATOM)
+            .map(|atoms: Vec<ffi::Atom>| util::WindowState::from_atoms(&atoms, &self.xconn))
+            .unwrap_or_default()
+    }
+
     #[inline]
     pub fn set_always_on_top(&self, always_on_top: bool) {
         self.set_always_on_top_inner(always_on_top)
@@ -1040,7 +1169,7 @@ impl UnownedWindow {
     pub(crate) fn set_position_inner(&self, mut x: i32, mut y: i32) -> util::Flusher<'_> {
         // There are a few WMs that set client area position rather than window position, so
         // we'll translate for consistency.
-        if util::wm_name_is_one_of(&["Enlightenment", "FVWM"]) {
+        if self.xconn.wm_name_is_one_of(&["Enlightenment", "FVWM"]) {
             let extents = (*self.shared_state.lock()).frame_extents.clone();
             if let Some(extents) = extents {
                 x += extents.frame_extents.left as i32;
@@ -1181,7 +1310,7 @@ impl UnownedWindow {
     }
 
     pub fn set_resizable(&self, resizable: bool) {
-        if util::wm_name_is_one_of(&["Xfwm4"]) {
+        if self.xconn.wm_name_is_one_of(&["Xfwm4"]) {
             // Making the window unresizable on Xfwm prevents further changes to `WM_NORMAL_HINTS` from being detected.
             // This makes it impossible for resizing to be re-enabled, and also breaks DPI scaling. As such, we choose
             // the lesser of two evils and do nothing.
@@ -1332,6 +1461,36 @@ impl UnownedWindow {
         self.xconn.set_cursor_icon(self.xwindow, cursor);
     }
 
+    pub fn set_key_repeat_enabled(&self, enabled: bool) {
+        let mut enabled_lock = self.key_repeat_enabled.lock();
+        if enabled == *enabled_lock {
+            return;
+        }
+        *enabled_lock = enabled;
+        drop(enabled_lock);
+        // Auto-repeat is a server-wide setting, so it's only turned off while the window is
+        // focused.
+        if self.has_focus() {
+            if enabled {
+                self.xconn.restore_auto_repeat();
+            } else {
+                self.xconn.disable_auto_repeat();
+            }
+        }
+    }
+
+    #[inline]
+    pub fn key_repeat_enabled(&self) -> bool {
+        *self.key_repeat_enabled.lock()
+    }
+
+    pub fn has_focus(&self) -> bool {
+        let mut focus = 0;
+        let mut revert_to = 0;
+        unsafe { (self.xconn.xlib.XGetInputFocus)(self.xconn.display, &mut focus, &mut revert_to) };
+        focus == self.xwindow
+    }
+
     #[inline]
     pub fn scale_factor(&self) -> f64 {
         self.current_monitor().scale_factor
@@ -1407,6 +1566,25 @@ impl UnownedWindow {
 
     #[inline]
     pub fn focus_window(&self) {
+        // Embedded windows are given the focus by their embedder.
+        let embedder = self.shared_state.lock().xembed_embedder;
+        if let Some(embedder) = embedder {
+            let flusher = self.xconn.send_xembed_message(
+                embedder,
+                util::XEmbedMessage::RequestFocus,
+                0,
+                0,
+                0,
+            );
+            if let Err(e) = flusher.flush() {
+                log::error!(
+                    "`flush` returned an error when requesting the focus from the embedder. Error was: {}",
+                    e
+                );
+            }
+            return;
+        }
+
         let state_atom = unsafe { self.xconn.get_atom_unchecked(b"WM_STATE\0") };
         let state_type_atom = unsafe { self.xconn.get_atom_unchecked(b"CARD32\0") };
         let is_minimized = if let Ok(state) =
@@ -1440,6 +1618,138 @@ impl UnownedWindow {
         }
     }
 
+    pub fn embed_client(&self, client: ffi::Window) -> Result<(), ExternalError> {
+        let (width, height) = self.inner_size_physical();
+        unsafe {
+            // Follow the destruction of the client and the changes of its `_XEMBED_INFO`.
+            (self.xconn.xlib.XSelectInput)(
+                self.xconn.display,
+                client,
+                ffi::StructureNotifyMask | ffi::PropertyChangeMask,
+            );
+            (self.xconn.xlib.XReparentWindow)(self.xconn.display, client, self.xwindow, 0, 0);
+            (self.xconn.xlib.XResizeWindow)(self.xconn.display, client, width, height);
+        }
+        // Clients without `_XEMBED_INFO` don't speak XEmbed, but still want to be shown.
+        let mapped = match self.xconn.get_xembed_flags(client) {
+            Some(flags) => flags & util::XEMBED_MAPPED != 0,
+            None => true,
+        };
+        if mapped {
+            unsafe { (self.xconn.xlib.XMapWindow)(self.xconn.display, client) };
+        }
+        self.xconn
+            .send_xembed_message(
+                client,
+                util::XEmbedMessage::EmbeddedNotify,
+                0,
+                self.xwindow as c_long,
+                util::XEMBED_VERSION,
+            )
+            .queue();
+        self.xconn
+            .sync_with_server()
+            .map_err(|err| ExternalError::Os(os_error!(OsError::XError(err))))?;
+
+        self.shared_state.lock().xembed_clients.push(client);
+        if self.has_focus() {
+            self.xembed_focus_in();
+        }
+        Ok(())
+    }
+
+    /// Forgets `client` unless it was reparented to this window, returning whether it was
+    /// embedded before.
+    pub(crate) fn forget_xembed_client(
+        &self,
+        client: ffi::Window,
+        new_parent: Option<ffi::Window>,
+    ) -> bool {
+        if new_parent == Some(self.xwindow) {
+            return false;
+        }
+        let clients = &mut self.shared_state.lock().xembed_clients;
+        let len = clients.len();
+        clients.retain(|&embedded| embedded != client);
+        clients.len() != len
+    }
+
+    /// Maps or unmaps `client` as its `_XEMBED_INFO` requests.
+    pub(crate) fn update_xembed_client_mapping(&self, client: ffi::Window) {
+        if !self.shared_state.lock().xembed_clients.contains(&client) {
+            return;
+        }
+        if let Some(flags) = self.xconn.get_xembed_flags(client) {
+            unsafe {
+                if flags & util::XEMBED_MAPPED != 0 {
+                    (self.xconn.xlib.XMapWindow)(self.xconn.display, client);
+                } else {
+                    (self.xconn.xlib.XUnmapWindow)(self.xconn.display, client);
+                }
+            }
+            // The client may be gone already, which its `DestroyNotify` takes care of.
+            let _ = self.xconn.sync_with_server();
+        }
+    }
+
+    /// Resizes the embedded clients to the new size of the window.
+    pub(crate) fn resize_xembed_clients(&self, (width, height): (u32, u32)) {
+        let clients = self.shared_state.lock().xembed_clients.clone();
+        if clients.is_empty() {
+            return;
+        }
+        for client in clients {
+            unsafe { (self.xconn.xlib.XResizeWindow)(self.xconn.display, client, width, height) };
+        }
+        let _ = self.xconn.sync_with_server();
+    }
+
+    /// Passes the keyboard focus the window just got on to the client embedded last.
+    pub(crate) fn xembed_focus_in(&self) {
+        let client = match self.shared_state.lock().xembed_clients.last() {
+            Some(&client) => client,
+            None => return,
+        };
+        unsafe {
+            (self.xconn.xlib.XSetInputFocus)(
+                self.xconn.display,
+                client,
+                ffi::RevertToParent,
+                ffi::CurrentTime,
+            );
+        }
+        self.xconn
+            .send_xembed_message(client, util::XEmbedMessage::WindowActivate, 0, 0, 0)
+            .queue();
+        self.xconn
+            .send_xembed_message(
+                client,
+                util::XEmbedMessage::FocusIn,
+                util::XEMBED_FOCUS_CURRENT,
+                0,
+                0,
+            )
+            .queue();
+        let _ = self.xconn.sync_with_server();
+    }
+
+    /// Tells the embedded clients that the window lost the keyboard focus.
+    pub(crate) fn xembed_focus_out(&self) {
+        let clients = self.shared_state.lock().xembed_clients.clone();
+        if clients.is_empty() {
+            return;
+        }
+        for client in clients {
+            self.xconn
+                .send_xembed_message(client, util::XEmbedMessage::FocusOut, 0, 0, 0)
+                .queue();
+            self.xconn
+                .send_xembed_message(client, util::XEmbedMessage::WindowDeactivate, 0, 0, 0)
+                .queue();
+        }
+        let _ = self.xconn.sync_with_server();
+    }
+
     #[inline]
     pub fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
         let mut wm_hints = self
@@ -1478,4 +1788,34 @@ impl UnownedWindow {
         handle.display = self.xlib_display();
         handle
     }
+
+    #[cfg(feature = "rwh_05")]
+    pub fn raw_window_handle_rwh_05(&self) -> rwh_05::RawWindowHandle {
+        let mut handle = rwh_05::XlibWindowHandle::empty();
+        handle.window = self.xlib_window();
+        rwh_05::RawWindowHandle::Xlib(handle)
+    }
+
+    #[cfg(feature = "rwh_05")]
+    pub fn raw_display_handle_rwh_05(&self) -> rwh_05::RawDisplayHandle {
+        let mut handle = rwh_05::XlibDisplayHandle::empty();
+        handle.display = self.xlib_display();
+        handle.screen = self.screen_id;
+        rwh_05::RawDisplayHandle::Xlib(handle)
+    }
+
+    #[cfg(feature = "rwh_06")]
+    pub fn raw_window_handle_rwh_06(&self) -> Result<rwh_06::RawWindowHandle, rwh_06::HandleError> {
+        let handle = rwh_06::XlibWindowHandle::new(self.xlib_window());
+        Ok(rwh_06::RawWindowHandle::Xlib(handle))
+    }
+
+    #[cfg(feature = "rwh_06")]
+    pub fn raw_display_handle_rwh_06(
+        &self,
+    ) -> Result<rwh_06::RawDisplayHandle, rwh_06::HandleError> {
+        let display = std::ptr::NonNull::new(self.xlib_display());
+        let handle = rwh_06::XlibDisplayHandle::new(display, self.screen_id);
+        Ok(rwh_06::RawDisplayHandle::Xlib(handle))
+    }
 }
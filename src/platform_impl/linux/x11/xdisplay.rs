@@ -19,9 +19,15 @@ pub struct XConnection {
     pub xlib_xcb: ffi::Xlib_xcb,
     pub xrender: ffi::Xrender,
     pub display: *mut ffi::Display,
+    /// Whether the display was opened by winit, and should be closed along with the connection.
+    owns_display: bool,
+    /// The error handler the application had set on its display, restored along with it.
+    previous_error_handler: XErrorHandler,
     pub x11_fd: c_int,
     pub latest_error: Mutex<Option<XError>>,
     pub cursor_cache: Mutex<HashMap<Option<CursorIcon>, ffi::Cursor>>,
+    /// The server's key auto-repeat setting while a window has turned it off.
+    pub saved_auto_repeat: Mutex<Option<bool>>,
 }
 
 unsafe impl Send for XConnection {}
@@ -32,6 +38,26 @@ pub type XErrorHandler =
 
 impl XConnection {
     pub fn new(error_handler: XErrorHandler) -> Result<XConnection, XNotSupported> {
+        unsafe { Self::open(error_handler, None) }
+    }
+
+    /// Wraps a display opened by the application, which keeps owning it: the display isn't closed
+    /// when the connection is dropped.
+    ///
+    /// # Safety
+    ///
+    /// `display` must be a valid Xlib display that outlives the connection.
+    pub unsafe fn from_display(
+        display: *mut ffi::Display,
+        error_handler: XErrorHandler,
+    ) -> Result<XConnection, XNotSupported> {
+        Self::open(error_handler, Some(display))
+    }
+
+    unsafe fn open(
+        error_handler: XErrorHandler,
+        display: Option<*mut ffi::Display>,
+    ) -> Result<XConnection, XNotSupported> {
         // opening the libraries
         let xlib = ffi::Xlib::open()?;
         let xcursor = ffi::Xcursor::open()?;
@@ -41,20 +67,28 @@ impl XConnection {
         let xlib_xcb = ffi::Xlib_xcb::open()?;
         let xrender = ffi::Xrender::open()?;
 
-        unsafe { (xlib.XInitThreads)() };
-        unsafe { (xlib.XSetErrorHandler)(error_handler) };
-
-        // calling XOpenDisplay
-        let display = unsafe {
-            let display = (xlib.XOpenDisplay)(ptr::null());
-            if display.is_null() {
-                return Err(XNotSupported::XOpenDisplayFailed);
+        // `XInitThreads` must be the first Xlib call, so it's up to the application to have called
+        // it before opening its own display.
+        if display.is_none() {
+            (xlib.XInitThreads)();
+        }
+        let previous_error_handler = (xlib.XSetErrorHandler)(error_handler);
+
+        let owns_display = display.is_none();
+        let display = match display {
+            Some(display) => display,
+            // calling XOpenDisplay
+            None => {
+                let display = (xlib.XOpenDisplay)(ptr::null());
+                if display.is_null() {
+                    return Err(XNotSupported::XOpenDisplayFailed);
+                }
+                display
             }
-            display
         };
 
         // Get X11 socket file descriptor
-        let fd = unsafe { (xlib.XConnectionNumber)(display) };
+        let fd = (xlib.XConnectionNumber)(display);
 
         Ok(XConnection {
             xlib,
@@ -65,12 +99,21 @@ impl XConnection {
             xlib_xcb,
             xrender,
             display,
+            owns_display,
+            previous_error_handler,
             x11_fd: fd,
             latest_error: Mutex::new(None),
             cursor_cache: Default::default(),
+            saved_auto_repeat: Mutex::new(None),
         })
     }
 
+    /// Whether the display was opened by winit, rather than by the application.
+    #[inline]
+    pub fn owns_display(&self) -> bool {
+        self.owns_display
+    }
+
     /// Checks whether an error has been triggered by the previous function calls.
     #[inline]
     pub fn check_errors(&self) -> Result<(), XError> {
@@ -98,7 +141,11 @@ impl fmt::Debug for XConnection {
 impl Drop for XConnection {
     #[inline]
     fn drop(&mut self) {
-        unsafe { (self.xlib.XCloseDisplay)(self.display) };
+        if self.owns_display {
+            unsafe { (self.xlib.XCloseDisplay)(self.display) };
+        } else {
+            unsafe { (self.xlib.XSetErrorHandler)(self.previous_error_handler) };
+        }
     }
 }
 
@@ -130,6 +177,8 @@ pub enum XNotSupported {
     LibraryOpenError(ffi::OpenError),
     /// Connecting to the X server with `XOpenDisplay` failed.
     XOpenDisplayFailed, // TODO: add better message
+    /// The display of the application was given while another one is still in use.
+    DisplayAlreadyOpen,
 }
 
 impl From<ffi::OpenError> for XNotSupported {
@@ -144,6 +193,9 @@ impl XNotSupported {
         match self {
             XNotSupported::LibraryOpenError(_) => "Failed to load one of xlib's shared libraries",
             XNotSupported::XOpenDisplayFailed => "Failed to open connection to X server",
+            XNotSupported::DisplayAlreadyOpen => {
+                "Another X11 display is already in use by a previous event loop"
+            }
         }
     }
 }
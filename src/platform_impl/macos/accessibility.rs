@@ -0,0 +1,57 @@
+//! Accessibility of a window, see [`Window::enable_accessibility`].
+//!
+//! The adapter subclasses the view of the window to answer the accessibility queries of AppKit,
+//! so it's only used on the main thread, like the view.
+//!
+//! [`Window::enable_accessibility`]: crate::window::Window::enable_accessibility
+
+use std::os::raw::c_void;
+
+use accesskit::{ActionHandler, ActionRequest, TreeUpdate};
+use accesskit_macos::{QueuedEvents, SubclassingAdapter};
+use cocoa::base::id;
+
+use crate::{
+    accessibility::{SourceHandler, TreeSource},
+    event::{Event, WindowEvent},
+    platform_impl::platform::{app_state::AppState, event::EventWrapper, window::get_window_id},
+    window::WindowId,
+};
+
+pub struct Accessibility {
+    adapter: SubclassingAdapter,
+}
+
+impl Accessibility {
+    /// Subclasses `ns_view`, which has to be done before the window is shown.
+    pub unsafe fn new(ns_window: id, ns_view: id, source: Box<dyn TreeSource>) -> Self {
+        let window_id = WindowId(get_window_id(ns_window));
+        let adapter = SubclassingAdapter::new(
+            ns_view as *mut c_void,
+            SourceHandler(source),
+            ActionQueuer(window_id),
+        );
+        Accessibility { adapter }
+    }
+
+    pub fn update_tree(&mut self, update: impl FnOnce() -> TreeUpdate) -> Option<QueuedEvents> {
+        self.adapter.update_if_active(update)
+    }
+
+    pub fn set_focused(&mut self, focused: bool) -> Option<QueuedEvents> {
+        self.adapter.update_view_focus_state(focused)
+    }
+}
+
+/// Queues the actions as window events, as AppKit requests them on the main thread.
+struct ActionQueuer(WindowId);
+
+impl ActionHandler for ActionQueuer {
+    fn do_action(&mut self, request: ActionRequest) {
+        let event = Event::WindowEvent {
+            window_id: self.0,
+            event: WindowEvent::AccessibilityActionRequested(request),
+        };
+        AppState::queue_event(EventWrapper::StaticEvent(event));
+    }
+}
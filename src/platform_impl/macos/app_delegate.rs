@@ -1,6 +1,26 @@
-use crate::{platform::macos::ActivationPolicy, platform_impl::platform::app_state::AppState};
+use crate::{
+    event::{Event, SessionSleepReason},
+    event_loop::PowerState,
+    platform::macos::ActivationPolicy,
+    platform_impl::platform::{
+        app_state::AppState,
+        event::EventWrapper,
+        ffi,
+        util::{self, IdRef},
+    },
+};
+
+#[cfg(feature = "gamepad")]
+use crate::platform_impl::platform::gamepad::{self, Gamepads};
 
-use cocoa::base::id;
+use cocoa::{
+    base::{id, nil, BOOL, NO},
+    foundation::NSString,
+};
+use core_foundation::{
+    base::CFRelease,
+    runloop::{kCFRunLoopCommonModes, CFRunLoopAddSource, CFRunLoopGetMain},
+};
 use objc::{
     declare::ClassDecl,
     runtime::{Class, Object, Sel},
@@ -15,6 +35,10 @@ static AUX_DELEGATE_STATE_NAME: &str = "auxState";
 pub struct AuxDelegateState {
     pub activation_policy: ActivationPolicy,
     pub default_menu: bool,
+    /// Last state reported through `Event::PowerStateChanged`.
+    pub power_state: PowerState,
+    #[cfg(feature = "gamepad")]
+    pub gamepads: Gamepads,
 }
 
 pub struct AppDelegateClass(pub *const Class);
@@ -33,6 +57,52 @@ lazy_static! {
             sel!(applicationDidFinishLaunching:),
             did_finish_launching as extern "C" fn(&Object, Sel, id),
         );
+        decl.add_method(
+            sel!(selectedKeyboardInputSourceChanged:),
+            selected_keyboard_input_source_changed as extern "C" fn(&Object, Sel, id),
+        );
+        decl.add_method(
+            sel!(applicationDidHide:),
+            did_hide as extern "C" fn(&Object, Sel, id),
+        );
+        decl.add_method(
+            sel!(applicationDidUnhide:),
+            did_unhide as extern "C" fn(&Object, Sel, id),
+        );
+        decl.add_method(
+            sel!(workspaceWillSleep:),
+            workspace_will_sleep as extern "C" fn(&Object, Sel, id),
+        );
+        decl.add_method(
+            sel!(workspaceDidWake:),
+            workspace_did_wake as extern "C" fn(&Object, Sel, id),
+        );
+        decl.add_method(
+            sel!(screenIsLocked:),
+            screen_is_locked as extern "C" fn(&Object, Sel, id),
+        );
+        decl.add_method(
+            sel!(screenIsUnlocked:),
+            screen_is_unlocked as extern "C" fn(&Object, Sel, id),
+        );
+        decl.add_method(
+            sel!(systemPreferencesChanged:),
+            system_preferences_changed as extern "C" fn(&Object, Sel, id),
+        );
+        decl.add_method(
+            sel!(powerStateChanged:),
+            power_state_changed as extern "C" fn(&Object, Sel, id),
+        );
+        #[cfg(feature = "gamepad")]
+        decl.add_method(
+            sel!(controllerDidConnect:),
+            gamepad::controller_did_connect as extern "C" fn(&Object, Sel, id),
+        );
+        #[cfg(feature = "gamepad")]
+        decl.add_method(
+            sel!(controllerDidDisconnect:),
+            gamepad::controller_did_disconnect as extern "C" fn(&Object, Sel, id),
+        );
         decl.add_ivar::<*mut c_void>(AUX_DELEGATE_STATE_NAME);
 
         AppDelegateClass(decl.register())
@@ -56,6 +126,9 @@ extern "C" fn new(class: &Class, _: Sel) -> id {
             Box::into_raw(Box::new(RefCell::new(AuxDelegateState {
                 activation_policy: ActivationPolicy::Regular,
                 default_menu: true,
+                power_state: util::power_state(),
+                #[cfg(feature = "gamepad")]
+                gamepads: Gamepads::default(),
             }))) as *mut c_void,
         );
         this
@@ -64,6 +137,13 @@ extern "C" fn new(class: &Class, _: Sel) -> id {
 
 extern "C" fn dealloc(this: &Object, _: Sel) {
     unsafe {
+        let center: id = msg_send![class!(NSDistributedNotificationCenter), defaultCenter];
+        let _: () = msg_send![center, removeObserver: this];
+        let center = workspace_notification_center();
+        let _: () = msg_send![center, removeObserver: this];
+        let center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+        let _: () = msg_send![center, removeObserver: this];
+
         let state_ptr: *mut c_void = *(this.get_ivar(AUX_DELEGATE_STATE_NAME));
         // As soon as the box is constructed it is immediately dropped, releasing the underlying
         // memory
@@ -73,5 +153,176 @@ extern "C" fn dealloc(this: &Object, _: Sel) {
 
 extern "C" fn did_finish_launching(this: &Object, _: Sel, _: id) {
     trace_scope!("applicationDidFinishLaunching:");
+    unsafe {
+        // Keyboard input source changes are posted to the distributed notification center.
+        let center: id = msg_send![class!(NSDistributedNotificationCenter), defaultCenter];
+        let _: () = msg_send![
+            center,
+            addObserver: this
+            selector: sel!(selectedKeyboardInputSourceChanged:)
+            name: ffi::kTISNotifySelectedKeyboardInputSourceChanged as id
+            object: nil
+        ];
+        // So are the screen locks, which have no public constant.
+        add_observer(
+            center,
+            this,
+            sel!(screenIsLocked:),
+            "com.apple.screenIsLocked",
+        );
+        add_observer(
+            center,
+            this,
+            sel!(screenIsUnlocked:),
+            "com.apple.screenIsUnlocked",
+        );
+
+        let center = workspace_notification_center();
+        add_observer(
+            center,
+            this,
+            sel!(workspaceWillSleep:),
+            "NSWorkspaceWillSleepNotification",
+        );
+        add_observer(
+            center,
+            this,
+            sel!(workspaceDidWake:),
+            "NSWorkspaceDidWakeNotification",
+        );
+        add_observer(
+            center,
+            this,
+            sel!(systemPreferencesChanged:),
+            "NSWorkspaceAccessibilityDisplayOptionsDidChangeNotification",
+        );
+
+        // The accent color is part of the system colors.
+        let center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+        add_observer(
+            center,
+            this,
+            sel!(systemPreferencesChanged:),
+            "NSSystemColorsDidChangeNotification",
+        );
+        add_observer(
+            center,
+            this,
+            sel!(powerStateChanged:),
+            "NSProcessInfoPowerStateDidChangeNotification",
+        );
+
+        // The power sources have no notification, only a run loop source.
+        let source = ffi::IOPSNotificationCreateRunLoopSource(
+            power_sources_changed,
+            this as *const Object as *mut c_void,
+        );
+        if !source.is_null() {
+            CFRunLoopAddSource(CFRunLoopGetMain(), source, kCFRunLoopCommonModes);
+            CFRelease(source as _);
+        }
+
+        #[cfg(feature = "gamepad")]
+        gamepad::observe(this);
+    }
     AppState::launched(this);
 }
+
+unsafe fn workspace_notification_center() -> id {
+    let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+    msg_send![workspace, notificationCenter]
+}
+
+unsafe fn add_observer(center: id, observer: &Object, selector: Sel, name: &str) {
+    let name = IdRef::new(NSString::alloc(nil).init_str(name));
+    let _: () = msg_send![
+        center,
+        addObserver: observer
+        selector: selector
+        name: *name
+        object: nil
+    ];
+}
+
+extern "C" fn selected_keyboard_input_source_changed(_: &Object, _: Sel, _: id) {
+    trace_scope!("selectedKeyboardInputSourceChanged:");
+    AppState::queue_event(EventWrapper::StaticEvent(Event::KeyboardLayoutChanged));
+}
+
+extern "C" fn did_hide(_: &Object, _: Sel, _: id) {
+    trace_scope!("applicationDidHide:");
+    AppState::queue_event(EventWrapper::StaticEvent(Event::SessionSleep(
+        SessionSleepReason::Hidden,
+    )));
+}
+
+extern "C" fn did_unhide(_: &Object, _: Sel, _: id) {
+    trace_scope!("applicationDidUnhide:");
+    AppState::queue_event(EventWrapper::StaticEvent(Event::SessionWake(
+        SessionSleepReason::Hidden,
+    )));
+}
+
+extern "C" fn workspace_will_sleep(_: &Object, _: Sel, _: id) {
+    trace_scope!("workspaceWillSleep:");
+    AppState::queue_event(EventWrapper::StaticEvent(Event::SessionSleep(
+        SessionSleepReason::SystemSleep,
+    )));
+}
+
+extern "C" fn workspace_did_wake(_: &Object, _: Sel, _: id) {
+    trace_scope!("workspaceDidWake:");
+    AppState::queue_event(EventWrapper::StaticEvent(Event::SessionWake(
+        SessionSleepReason::SystemSleep,
+    )));
+}
+
+extern "C" fn screen_is_locked(_: &Object, _: Sel, _: id) {
+    trace_scope!("screenIsLocked:");
+    AppState::queue_event(EventWrapper::StaticEvent(Event::SessionSleep(
+        SessionSleepReason::Locked,
+    )));
+}
+
+extern "C" fn screen_is_unlocked(_: &Object, _: Sel, _: id) {
+    trace_scope!("screenIsUnlocked:");
+    AppState::queue_event(EventWrapper::StaticEvent(Event::SessionWake(
+        SessionSleepReason::Locked,
+    )));
+}
+
+extern "C" fn system_preferences_changed(_: &Object, _: Sel, _: id) {
+    trace_scope!("systemPreferencesChanged:");
+    AppState::queue_event(EventWrapper::StaticEvent(Event::SystemPreferencesChanged));
+}
+
+extern "C" fn power_state_changed(this: &Object, _: Sel, _: id) {
+    trace_scope!("powerStateChanged:");
+    unsafe {
+        // The low power mode notification is posted on the thread which changed it.
+        let is_main_thread: BOOL = msg_send!(class!(NSThread), isMainThread);
+        if is_main_thread == NO {
+            let _: () = msg_send![
+                this,
+                performSelectorOnMainThread: sel!(powerStateChanged:)
+                withObject: nil
+                waitUntilDone: NO
+            ];
+            return;
+        }
+
+        // The power sources are also notified of every change of the battery level.
+        let state = util::power_state();
+        let mut aux_state = get_aux_state_mut(this);
+        if aux_state.power_state != state {
+            aux_state.power_state = state;
+            drop(aux_state);
+            AppState::queue_event(EventWrapper::StaticEvent(Event::PowerStateChanged));
+        }
+    }
+}
+
+extern "C" fn power_sources_changed(context: *mut c_void) {
+    let this = unsafe { &*(context as *const Object) };
+    power_state_changed(this, sel!(powerStateChanged:), nil);
+}
@@ -4,10 +4,11 @@ use std::{
     fmt::{self, Debug},
     hint::unreachable_unchecked,
     mem,
+    panic::AssertUnwindSafe,
     rc::{Rc, Weak},
     sync::{
         atomic::{AtomicBool, Ordering},
-        Mutex, MutexGuard,
+        Arc, Mutex, MutexGuard,
     },
     time::Instant,
 };
@@ -24,14 +25,14 @@ use objc::{
 
 use crate::{
     dpi::LogicalSize,
-    event::{Event, StartCause, WindowEvent},
+    event::{Event, InnerSizeWriter, StartCause, WindowEvent},
     event_loop::{ControlFlow, EventLoopWindowTarget as RootWindowTarget},
     platform::macos::ActivationPolicy,
     platform_impl::{
         get_aux_state_mut,
         platform::{
             event::{EventProxy, EventWrapper},
-            event_loop::{post_dummy_event, PanicInfo},
+            event_loop::{post_dummy_event, stop_app_on_panic, PanicInfo},
             menu,
             observer::{CFRunLoopGetMain, CFRunLoopWakeUp, EventLoopWaker},
             util::{IdRef, Never},
@@ -45,8 +46,8 @@ lazy_static! {
     static ref HANDLER: Handler = Default::default();
 }
 
-impl<'a, Never> Event<'a, Never> {
-    fn userify<T: 'static>(self) -> Event<'a, T> {
+impl<Never> Event<Never> {
+    fn userify<T: 'static>(self) -> Event<T> {
         self.map_nonuser_event()
             // `Never` can't be constructed, so the `UserEvent` variant can't
             // be present here.
@@ -55,17 +56,18 @@ impl<'a, Never> Event<'a, Never> {
 }
 
 pub trait EventHandler: Debug {
-    // Not sure probably it should accept Event<'static, Never>
-    fn handle_nonuser_event(&mut self, event: Event<'_, Never>, control_flow: &mut ControlFlow);
-    fn handle_user_events(&mut self, control_flow: &mut ControlFlow);
+    // Not sure probably it should accept Event<Never>
+    fn handle_nonuser_event(&mut self, event: Event<Never>, control_flow: &mut ControlFlow);
+    /// Returns whether user events may have been left for the next iteration.
+    fn handle_user_events(&mut self, control_flow: &mut ControlFlow) -> bool;
 }
 
-pub(crate) type Callback<T> =
-    RefCell<dyn FnMut(Event<'_, T>, &RootWindowTarget<T>, &mut ControlFlow)>;
+pub(crate) type Callback<T> = RefCell<dyn FnMut(Event<T>, &RootWindowTarget<T>, &mut ControlFlow)>;
 
 struct EventLoopHandler<T: 'static> {
     callback: Weak<Callback<T>>,
     window_target: Rc<RootWindowTarget<T>>,
+    panic_info: Weak<PanicInfo>,
 }
 
 impl<T> EventLoopHandler<T> {
@@ -73,12 +75,23 @@ impl<T> EventLoopHandler<T> {
     where
         F: FnOnce(
             &mut EventLoopHandler<T>,
-            RefMut<'_, dyn FnMut(Event<'_, T>, &RootWindowTarget<T>, &mut ControlFlow)>,
+            RefMut<'_, dyn FnMut(Event<T>, &RootWindowTarget<T>, &mut ControlFlow)>,
         ),
     {
+        let panic_info = self
+            .panic_info
+            .upgrade()
+            .expect("The panic info must exist here. This failure indicates a developer error.");
+        // Once the callback has panicked, the event loop is on its way out; don't call it again.
+        if panic_info.is_panicking() {
+            return;
+        }
         if let Some(callback) = self.callback.upgrade() {
             let callback = callback.borrow_mut();
-            (f)(self, callback);
+            // The panic is caught here rather than left to unwind through AppKit's frames (and
+            // our own locks), and resumed once `-[NSApplication run]` has returned.
+            let panic_info = Rc::downgrade(&panic_info);
+            stop_app_on_panic(panic_info, AssertUnwindSafe(|| (f)(self, callback)));
         } else {
             panic!(
                 "Tried to dispatch an event, but the event loop that \
@@ -98,7 +111,7 @@ impl<T> Debug for EventLoopHandler<T> {
 }
 
 impl<T> EventHandler for EventLoopHandler<T> {
-    fn handle_nonuser_event(&mut self, event: Event<'_, Never>, control_flow: &mut ControlFlow) {
+    fn handle_nonuser_event(&mut self, event: Event<Never>, control_flow: &mut ControlFlow) {
         self.with_callback(|this, mut callback| {
             if let ControlFlow::ExitWithCode(code) = *control_flow {
                 let dummy = &mut ControlFlow::ExitWithCode(code);
@@ -109,17 +122,19 @@ impl<T> EventHandler for EventLoopHandler<T> {
         });
     }
 
-    fn handle_user_events(&mut self, control_flow: &mut ControlFlow) {
+    fn handle_user_events(&mut self, control_flow: &mut ControlFlow) -> bool {
+        let mut left_behind = false;
         self.with_callback(|this, mut callback| {
-            for event in this.window_target.p.receiver.try_iter() {
+            left_behind = this.window_target.p.receive_user_events(|event| {
                 if let ControlFlow::ExitWithCode(code) = *control_flow {
                     let dummy = &mut ControlFlow::ExitWithCode(code);
                     (callback)(Event::UserEvent(event), &this.window_target, dummy);
                 } else {
                     (callback)(Event::UserEvent(event), &this.window_target, control_flow);
                 }
-            }
+            });
         });
+        left_behind
     }
 }
 
@@ -127,6 +142,7 @@ impl<T> EventHandler for EventLoopHandler<T> {
 struct Handler {
     ready: AtomicBool,
     in_callback: AtomicBool,
+    in_nested_loop: AtomicBool,
     dialog_is_closing: AtomicBool,
     control_flow: Mutex<ControlFlow>,
     control_flow_prev: Mutex<ControlFlow>,
@@ -204,61 +220,63 @@ impl Handler {
         self.in_callback.store(in_callback, Ordering::Release);
     }
 
+    fn get_in_nested_loop(&self) -> bool {
+        self.in_nested_loop.load(Ordering::Acquire)
+    }
+
     fn handle_nonuser_event(&self, wrapper: EventWrapper) {
         if let Some(ref mut callback) = *self.callback.lock().unwrap() {
             match wrapper {
                 EventWrapper::StaticEvent(event) => {
                     callback.handle_nonuser_event(event, &mut *self.control_flow.lock().unwrap())
                 }
-                EventWrapper::EventProxy(proxy) => self.handle_proxy(proxy, callback),
+                EventWrapper::EventProxy(proxy) => dispatch_proxy(proxy, |event| {
+                    callback.handle_nonuser_event(event, &mut *self.control_flow.lock().unwrap())
+                }),
             }
         }
     }
 
-    fn handle_user_events(&self) {
+    fn handle_user_events(&self) -> bool {
         if let Some(ref mut callback) = *self.callback.lock().unwrap() {
-            callback.handle_user_events(&mut *self.control_flow.lock().unwrap());
+            callback.handle_user_events(&mut *self.control_flow.lock().unwrap())
+        } else {
+            false
         }
     }
+}
 
-    fn handle_scale_factor_changed_event(
-        &self,
-        callback: &mut Box<dyn EventHandler + 'static>,
-        ns_window: IdRef,
-        suggested_size: LogicalSize<f64>,
-        scale_factor: f64,
-    ) {
-        let mut size = suggested_size.to_physical(scale_factor);
-        let new_inner_size = &mut size;
-        let event = Event::WindowEvent {
-            window_id: WindowId(get_window_id(*ns_window)),
-            event: WindowEvent::ScaleFactorChanged {
-                scale_factor,
-                new_inner_size,
-            },
-        };
+fn handle_scale_factor_changed_event(
+    ns_window: IdRef,
+    suggested_size: LogicalSize<f64>,
+    scale_factor: f64,
+    send: impl FnOnce(Event<Never>),
+) {
+    let new_inner_size = Arc::new(Mutex::new(suggested_size.to_physical(scale_factor)));
+    let event = Event::WindowEvent {
+        window_id: WindowId(get_window_id(*ns_window)),
+        event: WindowEvent::ScaleFactorChanged {
+            scale_factor,
+            inner_size_writer: InnerSizeWriter::new(Arc::downgrade(&new_inner_size)),
+        },
+    };
 
-        callback.handle_nonuser_event(event, &mut *self.control_flow.lock().unwrap());
-
-        let physical_size = *new_inner_size;
-        let logical_size = physical_size.to_logical(scale_factor);
-        let size = NSSize::new(logical_size.width, logical_size.height);
-        unsafe { NSWindow::setContentSize_(*ns_window, size) };
-    }
-
-    fn handle_proxy(&self, proxy: EventProxy, callback: &mut Box<dyn EventHandler + 'static>) {
-        match proxy {
-            EventProxy::DpiChangedProxy {
-                ns_window,
-                suggested_size,
-                scale_factor,
-            } => self.handle_scale_factor_changed_event(
-                callback,
-                ns_window,
-                suggested_size,
-                scale_factor,
-            ),
-        }
+    send(event);
+
+    let physical_size = *new_inner_size.lock().unwrap();
+    let logical_size = physical_size.to_logical(scale_factor);
+    let size = NSSize::new(logical_size.width, logical_size.height);
+    unsafe { NSWindow::setContentSize_(*ns_window, size) };
+}
+
+/// Sends the event of `proxy` with `send`, and applies what the handler answered to it.
+fn dispatch_proxy(proxy: EventProxy, send: impl FnOnce(Event<Never>)) {
+    match proxy {
+        EventProxy::DpiChangedProxy {
+            ns_window,
+            suggested_size,
+            scale_factor,
+        } => handle_scale_factor_changed_event(ns_window, suggested_size, scale_factor, send),
     }
 }
 
@@ -267,13 +285,24 @@ pub static INTERRUPT_EVENT_LOOP_EXIT: AtomicBool = AtomicBool::new(false);
 pub enum AppState {}
 
 impl AppState {
-    pub fn set_callback<T>(callback: Weak<Callback<T>>, window_target: Rc<RootWindowTarget<T>>) {
+    pub fn set_callback<T>(
+        callback: Weak<Callback<T>>,
+        window_target: Rc<RootWindowTarget<T>>,
+        panic_info: Weak<PanicInfo>,
+    ) {
         *HANDLER.callback.lock().unwrap() = Some(Box::new(EventLoopHandler {
             callback,
             window_target,
+            panic_info,
         }));
     }
 
+    /// Drops the event handler without sending `LoopDestroyed`, used when the callback panicked.
+    pub fn clear_callback() {
+        HANDLER.set_in_callback(false);
+        HANDLER.callback.lock().unwrap().take();
+    }
+
     pub fn exit() -> i32 {
         HANDLER.set_in_callback(true);
         HANDLER.handle_nonuser_event(EventWrapper::StaticEvent(Event::LoopDestroyed));
@@ -355,12 +384,52 @@ impl AppState {
             let rl = CFRunLoopGetMain();
             CFRunLoopWakeUp(rl);
         }
+        Self::wake_nested_loop();
     }
 
     pub fn handle_redraw(window_id: WindowId) {
+        if HANDLER.get_in_nested_loop() {
+            // The outer event handler is running, the nested loop sends the redraw itself.
+            Self::queue_redraw(window_id);
+            return;
+        }
         HANDLER.handle_nonuser_event(EventWrapper::StaticEvent(Event::RedrawRequested(window_id)));
     }
 
+    /// Marks the start of a loop run from within the event handler, by
+    /// `EventLoopWindowTarget::run_nested`. Returns whether one was already running.
+    pub fn enter_nested_loop() -> bool {
+        HANDLER.in_nested_loop.swap(true, Ordering::AcqRel)
+    }
+
+    pub fn leave_nested_loop(was_nested: bool) {
+        HANDLER.in_nested_loop.store(was_nested, Ordering::Release);
+    }
+
+    /// Makes a nested loop waiting for the next `NSEvent` go through another iteration.
+    pub fn wake_nested_loop() {
+        if HANDLER.get_in_nested_loop() {
+            autoreleasepool(|| unsafe { post_dummy_event(NSApp()) });
+        }
+    }
+
+    /// Sends the events queued while the outer event handler is running to the handler of a
+    /// nested loop.
+    pub fn dispatch_nested_events<T: 'static>(mut callback: impl FnMut(Event<T>)) {
+        for wrapper in HANDLER.take_events() {
+            match wrapper {
+                EventWrapper::StaticEvent(event) => callback(event.userify()),
+                EventWrapper::EventProxy(proxy) => {
+                    dispatch_proxy(proxy, |event| callback(event.userify()))
+                }
+            }
+        }
+    }
+
+    pub fn take_nested_redraws() -> Vec<WindowId> {
+        HANDLER.should_redraw()
+    }
+
     pub fn queue_event(wrapper: EventWrapper) {
         let is_main_thread: BOOL = unsafe { msg_send!(class!(NSThread), isMainThread) };
         if is_main_thread == NO {
@@ -388,7 +457,7 @@ impl AppState {
         }
 
         HANDLER.set_in_callback(true);
-        HANDLER.handle_user_events();
+        let user_events_left_behind = HANDLER.handle_user_events();
         for event in HANDLER.take_events() {
             HANDLER.handle_nonuser_event(event);
         }
@@ -400,6 +469,12 @@ impl AppState {
         HANDLER.handle_nonuser_event(EventWrapper::StaticEvent(Event::RedrawEventsCleared));
         HANDLER.set_in_callback(false);
 
+        if user_events_left_behind {
+            // Go through another iteration instead of waiting, so that the events sent after a
+            // `ProxyWakeup::PerEvent` event are delivered.
+            unsafe { CFRunLoopWakeUp(CFRunLoopGetMain()) };
+        }
+
         if HANDLER.should_exit() {
             unsafe {
                 let app: id = NSApp();
@@ -495,3 +570,22 @@ fn apply_activation_policy(app_delegate: &Object) {
         });
     }
 }
+
+/// Sets the activation policy, right away once the application finished launching.
+pub fn set_activation_policy(activation_policy: ActivationPolicy) {
+    unsafe {
+        let ns_app = NSApp();
+        let app_delegate: id = msg_send![ns_app, delegate];
+        get_aux_state_mut(&*app_delegate).activation_policy = activation_policy;
+        if HANDLER.is_ready() {
+            apply_activation_policy(&*app_delegate);
+        }
+    }
+}
+
+pub fn activation_policy() -> ActivationPolicy {
+    unsafe {
+        let app_delegate: id = msg_send![NSApp(), delegate];
+        get_aux_state_mut(&*app_delegate).activation_policy
+    }
+}
@@ -3,20 +3,28 @@ use std::os::raw::c_ushort;
 use cocoa::{
     appkit::{NSEvent, NSEventModifierFlags},
     base::id,
+    foundation::NSUInteger,
+};
+use core_foundation::{
+    base::{CFRelease, TCFType},
+    data::{CFDataGetBytePtr, CFDataRef},
+    string::{CFString, CFStringRef},
 };
 
 use crate::{
     dpi::LogicalSize,
-    event::{ElementState, Event, KeyboardInput, ModifiersState, VirtualKeyCode, WindowEvent},
+    event::{ElementState, Event, KeyboardInput, ModifiersState, WindowEvent},
+    keyboard::{Key, KeyCode, NativeKey, NativeKeyCode, PhysicalKey},
     platform_impl::platform::{
+        ffi,
         util::{IdRef, Never},
-        DEVICE_ID,
+        KeyEventExtra, DEVICE_ID,
     },
 };
 
 #[derive(Debug)]
 pub enum EventWrapper {
-    StaticEvent(Event<'static, Never>),
+    StaticEvent(Event<Never>),
     EventProxy(EventProxy),
 }
 
@@ -29,197 +37,133 @@ pub enum EventProxy {
     },
 }
 
-pub fn char_to_keycode(c: char) -> Option<VirtualKeyCode> {
-    // We only translate keys that are affected by keyboard layout.
-    //
-    // Note that since keys are translated in a somewhat "dumb" way (reading character)
-    // there is a concern that some combination, i.e. Cmd+char, causes the wrong
-    // letter to be received, and so we receive the wrong key.
-    //
-    // Implementation reference: https://github.com/WebKit/webkit/blob/82bae82cf0f329dbe21059ef0986c4e92fea4ba6/Source/WebCore/platform/cocoa/KeyEventCocoa.mm#L626
-    Some(match c {
-        'a' | 'A' => VirtualKeyCode::A,
-        'b' | 'B' => VirtualKeyCode::B,
-        'c' | 'C' => VirtualKeyCode::C,
-        'd' | 'D' => VirtualKeyCode::D,
-        'e' | 'E' => VirtualKeyCode::E,
-        'f' | 'F' => VirtualKeyCode::F,
-        'g' | 'G' => VirtualKeyCode::G,
-        'h' | 'H' => VirtualKeyCode::H,
-        'i' | 'I' => VirtualKeyCode::I,
-        'j' | 'J' => VirtualKeyCode::J,
-        'k' | 'K' => VirtualKeyCode::K,
-        'l' | 'L' => VirtualKeyCode::L,
-        'm' | 'M' => VirtualKeyCode::M,
-        'n' | 'N' => VirtualKeyCode::N,
-        'o' | 'O' => VirtualKeyCode::O,
-        'p' | 'P' => VirtualKeyCode::P,
-        'q' | 'Q' => VirtualKeyCode::Q,
-        'r' | 'R' => VirtualKeyCode::R,
-        's' | 'S' => VirtualKeyCode::S,
-        't' | 'T' => VirtualKeyCode::T,
-        'u' | 'U' => VirtualKeyCode::U,
-        'v' | 'V' => VirtualKeyCode::V,
-        'w' | 'W' => VirtualKeyCode::W,
-        'x' | 'X' => VirtualKeyCode::X,
-        'y' | 'Y' => VirtualKeyCode::Y,
-        'z' | 'Z' => VirtualKeyCode::Z,
-        '1' | '!' => VirtualKeyCode::Key1,
-        '2' | '@' => VirtualKeyCode::Key2,
-        '3' | '#' => VirtualKeyCode::Key3,
-        '4' | '$' => VirtualKeyCode::Key4,
-        '5' | '%' => VirtualKeyCode::Key5,
-        '6' | '^' => VirtualKeyCode::Key6,
-        '7' | '&' => VirtualKeyCode::Key7,
-        '8' | '*' => VirtualKeyCode::Key8,
-        '9' | '(' => VirtualKeyCode::Key9,
-        '0' | ')' => VirtualKeyCode::Key0,
-        '=' | '+' => VirtualKeyCode::Equals,
-        '-' | '_' => VirtualKeyCode::Minus,
-        ']' | '}' => VirtualKeyCode::RBracket,
-        '[' | '{' => VirtualKeyCode::LBracket,
-        '\'' | '"' => VirtualKeyCode::Apostrophe,
-        ';' | ':' => VirtualKeyCode::Semicolon,
-        '\\' | '|' => VirtualKeyCode::Backslash,
-        ',' | '<' => VirtualKeyCode::Comma,
-        '/' | '?' => VirtualKeyCode::Slash,
-        '.' | '>' => VirtualKeyCode::Period,
-        '`' | '~' => VirtualKeyCode::Grave,
-        _ => return None,
-    })
+/// Returns the identifier of the current keyboard input source, such as `com.apple.keylayout.US`.
+pub fn keyboard_layout() -> Option<String> {
+    unsafe {
+        let source = ffi::TISCopyCurrentKeyboardInputSource();
+        if source.is_null() {
+            return None;
+        }
+        let id =
+            ffi::TISGetInputSourceProperty(source, ffi::kTISPropertyInputSourceID) as CFStringRef;
+        let layout = if id.is_null() {
+            None
+        } else {
+            Some(CFString::wrap_under_get_rule(id).to_string())
+        };
+        CFRelease(source);
+        layout
+    }
+}
+
+/// Returns the text the key at the `code` position produces under the current keyboard layout
+/// with the given modifiers, see `EventLoopWindowTarget::key_code_to_text`.
+pub fn key_code_to_text(code: KeyCode, modifiers: ModifiersState) -> Option<String> {
+    let scancode =
+        (0..0x80).find(|&scancode| scancode_to_physicalkey(scancode) == PhysicalKey::Code(code))?;
+    if scancode_to_named_key(scancode).is_some() {
+        return None;
+    }
+
+    let mut modifier_state = 0;
+    if modifiers.shift() {
+        modifier_state |= ffi::shiftKey;
+    }
+    if modifiers.alt() {
+        modifier_state |= ffi::optionKey;
+    }
+
+    unsafe {
+        let source = ffi::TISCopyCurrentKeyboardLayoutInputSource();
+        if source.is_null() {
+            return None;
+        }
+        let layout_data =
+            ffi::TISGetInputSourceProperty(source, ffi::kTISPropertyUnicodeKeyLayoutData)
+                as CFDataRef;
+
+        let mut text = None;
+        if !layout_data.is_null() {
+            let mut dead_key_state = 0;
+            let mut chars = [0u16; 8];
+            let mut len = 0;
+            let status = ffi::UCKeyTranslate(
+                CFDataGetBytePtr(layout_data) as *const ffi::UCKeyboardLayout,
+                scancode,
+                ffi::kUCKeyActionDisplay,
+                // `UCKeyTranslate` takes the Carbon modifiers shifted by 8.
+                (modifier_state >> 8) & 0xff,
+                ffi::LMGetKbdType() as u32,
+                ffi::kUCKeyTranslateNoDeadKeysMask,
+                &mut dead_key_state,
+                chars.len(),
+                &mut len,
+                chars.as_mut_ptr(),
+            );
+            if status == 0 && len > 0 {
+                text = Some(String::from_utf16_lossy(&chars[..len.min(chars.len())]))
+                    .filter(|text| !text.chars().any(char::is_control));
+            }
+        }
+        CFRelease(source);
+
+        text
+    }
 }
 
-pub fn scancode_to_keycode(scancode: c_ushort) -> Option<VirtualKeyCode> {
+/// Maps a macOS scancode to the named logical key it represents, for the keys that don't depend
+/// on the keyboard layout.
+pub fn scancode_to_named_key(scancode: c_ushort) -> Option<Key> {
+    // See `Events.h` in the `HIToolbox` framework for the `kVK_*` constants.
     Some(match scancode {
-        0x00 => VirtualKeyCode::A,
-        0x01 => VirtualKeyCode::S,
-        0x02 => VirtualKeyCode::D,
-        0x03 => VirtualKeyCode::F,
-        0x04 => VirtualKeyCode::H,
-        0x05 => VirtualKeyCode::G,
-        0x06 => VirtualKeyCode::Z,
-        0x07 => VirtualKeyCode::X,
-        0x08 => VirtualKeyCode::C,
-        0x09 => VirtualKeyCode::V,
-        //0x0a => World 1,
-        0x0b => VirtualKeyCode::B,
-        0x0c => VirtualKeyCode::Q,
-        0x0d => VirtualKeyCode::W,
-        0x0e => VirtualKeyCode::E,
-        0x0f => VirtualKeyCode::R,
-        0x10 => VirtualKeyCode::Y,
-        0x11 => VirtualKeyCode::T,
-        0x12 => VirtualKeyCode::Key1,
-        0x13 => VirtualKeyCode::Key2,
-        0x14 => VirtualKeyCode::Key3,
-        0x15 => VirtualKeyCode::Key4,
-        0x16 => VirtualKeyCode::Key6,
-        0x17 => VirtualKeyCode::Key5,
-        0x18 => VirtualKeyCode::Equals,
-        0x19 => VirtualKeyCode::Key9,
-        0x1a => VirtualKeyCode::Key7,
-        0x1b => VirtualKeyCode::Minus,
-        0x1c => VirtualKeyCode::Key8,
-        0x1d => VirtualKeyCode::Key0,
-        0x1e => VirtualKeyCode::RBracket,
-        0x1f => VirtualKeyCode::O,
-        0x20 => VirtualKeyCode::U,
-        0x21 => VirtualKeyCode::LBracket,
-        0x22 => VirtualKeyCode::I,
-        0x23 => VirtualKeyCode::P,
-        0x24 => VirtualKeyCode::Return,
-        0x25 => VirtualKeyCode::L,
-        0x26 => VirtualKeyCode::J,
-        0x27 => VirtualKeyCode::Apostrophe,
-        0x28 => VirtualKeyCode::K,
-        0x29 => VirtualKeyCode::Semicolon,
-        0x2a => VirtualKeyCode::Backslash,
-        0x2b => VirtualKeyCode::Comma,
-        0x2c => VirtualKeyCode::Slash,
-        0x2d => VirtualKeyCode::N,
-        0x2e => VirtualKeyCode::M,
-        0x2f => VirtualKeyCode::Period,
-        0x30 => VirtualKeyCode::Tab,
-        0x31 => VirtualKeyCode::Space,
-        0x32 => VirtualKeyCode::Grave,
-        0x33 => VirtualKeyCode::Back,
-        //0x34 => unkown,
-        0x35 => VirtualKeyCode::Escape,
-        0x36 => VirtualKeyCode::RWin,
-        0x37 => VirtualKeyCode::LWin,
-        0x38 => VirtualKeyCode::LShift,
-        //0x39 => Caps lock,
-        0x3a => VirtualKeyCode::LAlt,
-        0x3b => VirtualKeyCode::LControl,
-        0x3c => VirtualKeyCode::RShift,
-        0x3d => VirtualKeyCode::RAlt,
-        0x3e => VirtualKeyCode::RControl,
-        //0x3f => Fn key,
-        0x40 => VirtualKeyCode::F17,
-        0x41 => VirtualKeyCode::NumpadDecimal,
-        //0x42 -> unkown,
-        0x43 => VirtualKeyCode::NumpadMultiply,
-        //0x44 => unkown,
-        0x45 => VirtualKeyCode::NumpadAdd,
-        //0x46 => unkown,
-        0x47 => VirtualKeyCode::Numlock,
-        //0x48 => KeypadClear,
-        0x49 => VirtualKeyCode::VolumeUp,
-        0x4a => VirtualKeyCode::VolumeDown,
-        0x4b => VirtualKeyCode::NumpadDivide,
-        0x4c => VirtualKeyCode::NumpadEnter,
-        //0x4d => unkown,
-        0x4e => VirtualKeyCode::NumpadSubtract,
-        0x4f => VirtualKeyCode::F18,
-        0x50 => VirtualKeyCode::F19,
-        0x51 => VirtualKeyCode::NumpadEquals,
-        0x52 => VirtualKeyCode::Numpad0,
-        0x53 => VirtualKeyCode::Numpad1,
-        0x54 => VirtualKeyCode::Numpad2,
-        0x55 => VirtualKeyCode::Numpad3,
-        0x56 => VirtualKeyCode::Numpad4,
-        0x57 => VirtualKeyCode::Numpad5,
-        0x58 => VirtualKeyCode::Numpad6,
-        0x59 => VirtualKeyCode::Numpad7,
-        0x5a => VirtualKeyCode::F20,
-        0x5b => VirtualKeyCode::Numpad8,
-        0x5c => VirtualKeyCode::Numpad9,
-        0x5d => VirtualKeyCode::Yen,
-        //0x5e => JIS Ro,
-        //0x5f => unkown,
-        0x60 => VirtualKeyCode::F5,
-        0x61 => VirtualKeyCode::F6,
-        0x62 => VirtualKeyCode::F7,
-        0x63 => VirtualKeyCode::F3,
-        0x64 => VirtualKeyCode::F8,
-        0x65 => VirtualKeyCode::F9,
-        //0x66 => JIS Eisuu (macOS),
-        0x67 => VirtualKeyCode::F11,
-        //0x68 => JIS Kanna (macOS),
-        0x69 => VirtualKeyCode::F13,
-        0x6a => VirtualKeyCode::F16,
-        0x6b => VirtualKeyCode::F14,
-        //0x6c => unkown,
-        0x6d => VirtualKeyCode::F10,
-        //0x6e => unkown,
-        0x6f => VirtualKeyCode::F12,
-        //0x70 => unkown,
-        0x71 => VirtualKeyCode::F15,
-        0x72 => VirtualKeyCode::Insert,
-        0x73 => VirtualKeyCode::Home,
-        0x74 => VirtualKeyCode::PageUp,
-        0x75 => VirtualKeyCode::Delete,
-        0x76 => VirtualKeyCode::F4,
-        0x77 => VirtualKeyCode::End,
-        0x78 => VirtualKeyCode::F2,
-        0x79 => VirtualKeyCode::PageDown,
-        0x7a => VirtualKeyCode::F1,
-        0x7b => VirtualKeyCode::Left,
-        0x7c => VirtualKeyCode::Right,
-        0x7d => VirtualKeyCode::Down,
-        0x7e => VirtualKeyCode::Up,
-        //0x7f =>  unkown,
-        0xa => VirtualKeyCode::Caret,
+        0x24 | 0x4c => Key::Enter,
+        0x30 => Key::Tab,
+        0x31 => Key::Space,
+        0x33 => Key::Backspace,
+        0x35 => Key::Escape,
+        0x36 | 0x37 => Key::Super,
+        0x38 | 0x3c => Key::Shift,
+        0x39 => Key::CapsLock,
+        0x3a | 0x3d => Key::Alt,
+        0x3b | 0x3e => Key::Control,
+        0x3f => Key::Fn,
+        0x40 => Key::F17,
+        0x47 => Key::Clear,
+        0x48 => Key::AudioVolumeUp,
+        0x49 => Key::AudioVolumeDown,
+        0x4a => Key::AudioVolumeMute,
+        0x4f => Key::F18,
+        0x50 => Key::F19,
+        0x5a => Key::F20,
+        0x60 => Key::F5,
+        0x61 => Key::F6,
+        0x62 => Key::F7,
+        0x63 => Key::F3,
+        0x64 => Key::F8,
+        0x65 => Key::F9,
+        0x66 => Key::Eisu,
+        0x67 => Key::F11,
+        0x68 => Key::KanaMode,
+        0x69 => Key::F13,
+        0x6a => Key::F16,
+        0x6b => Key::F14,
+        0x6d => Key::F10,
+        0x6e => Key::ContextMenu,
+        0x6f => Key::F12,
+        0x71 => Key::F15,
+        0x72 => Key::Insert,
+        0x73 => Key::Home,
+        0x74 => Key::PageUp,
+        0x75 => Key::Delete,
+        0x76 => Key::F4,
+        0x77 => Key::End,
+        0x78 => Key::F2,
+        0x79 => Key::PageDown,
+        0x7a => Key::F1,
+        0x7b => Key::ArrowLeft,
+        0x7c => Key::ArrowRight,
+        0x7d => Key::ArrowDown,
+        0x7e => Key::ArrowUp,
         _ => return None,
     })
 }
@@ -227,13 +171,13 @@ pub fn scancode_to_keycode(scancode: c_ushort) -> Option<VirtualKeyCode> {
 // While F1-F20 have scancodes we can match on, we have to check against UTF-16
 // constants for the rest.
 // https://developer.apple.com/documentation/appkit/1535851-function-key_unicodes?preferredLanguage=occ
-pub fn check_function_keys(string: &str) -> Option<VirtualKeyCode> {
+pub fn check_function_keys(string: &str) -> Option<Key> {
     if let Some(ch) = string.encode_utf16().next() {
         return Some(match ch {
-            0xf718 => VirtualKeyCode::F21,
-            0xf719 => VirtualKeyCode::F22,
-            0xf71a => VirtualKeyCode::F23,
-            0xf71b => VirtualKeyCode::F24,
+            0xf718 => Key::F21,
+            0xf719 => Key::F22,
+            0xf71a => Key::F23,
+            0xf71b => Key::F24,
             _ => return None,
         });
     }
@@ -241,6 +185,16 @@ pub fn check_function_keys(string: &str) -> Option<VirtualKeyCode> {
     None
 }
 
+// Device-dependent modifier masks from `IOKit/hidsystem/IOLLEvent.h`.
+const NX_DEVICELCTLKEYMASK: NSUInteger = 0x0000_0001;
+const NX_DEVICELSHIFTKEYMASK: NSUInteger = 0x0000_0002;
+const NX_DEVICERSHIFTKEYMASK: NSUInteger = 0x0000_0004;
+const NX_DEVICELCMDKEYMASK: NSUInteger = 0x0000_0008;
+const NX_DEVICERCMDKEYMASK: NSUInteger = 0x0000_0010;
+const NX_DEVICELALTKEYMASK: NSUInteger = 0x0000_0020;
+const NX_DEVICERALTKEYMASK: NSUInteger = 0x0000_0040;
+const NX_DEVICERCTLKEYMASK: NSUInteger = 0x0000_2000;
+
 pub fn event_mods(event: id) -> ModifiersState {
     let flags = unsafe { NSEvent::modifierFlags(event) };
     let mut m = ModifiersState::empty();
@@ -260,6 +214,21 @@ pub fn event_mods(event: id) -> ModifiersState {
         ModifiersState::LOGO,
         flags.contains(NSEventModifierFlags::NSCommandKeyMask),
     );
+
+    // The low bits of the flags hold the device-dependent state of each side's modifier key.
+    let flags = flags.bits();
+    for &(mask, side) in &[
+        (NX_DEVICELSHIFTKEYMASK, ModifiersState::LSHIFT),
+        (NX_DEVICERSHIFTKEYMASK, ModifiersState::RSHIFT),
+        (NX_DEVICELCTLKEYMASK, ModifiersState::LCTRL),
+        (NX_DEVICERCTLKEYMASK, ModifiersState::RCTRL),
+        (NX_DEVICELALTKEYMASK, ModifiersState::LALT),
+        (NX_DEVICERALTKEYMASK, ModifiersState::RALT),
+        (NX_DEVICELCMDKEYMASK, ModifiersState::LLOGO),
+        (NX_DEVICERCMDKEYMASK, ModifiersState::RLOGO),
+    ] {
+        m.set(side, flags & mask != 0);
+    }
     m
 }
 
@@ -271,11 +240,139 @@ pub fn get_scancode(event: cocoa::base::id) -> c_ushort {
     unsafe { msg_send![event, keyCode] }
 }
 
+/// Maps a macOS scancode, as returned by `get_scancode`, to the physical key it represents.
+pub fn scancode_to_physicalkey(scancode: c_ushort) -> PhysicalKey {
+    // See `Events.h` in the `HIToolbox` framework for the `kVK_*` constants.
+    PhysicalKey::Code(match scancode {
+        0x00 => KeyCode::KeyA,
+        0x01 => KeyCode::KeyS,
+        0x02 => KeyCode::KeyD,
+        0x03 => KeyCode::KeyF,
+        0x04 => KeyCode::KeyH,
+        0x05 => KeyCode::KeyG,
+        0x06 => KeyCode::KeyZ,
+        0x07 => KeyCode::KeyX,
+        0x08 => KeyCode::KeyC,
+        0x09 => KeyCode::KeyV,
+        0x0a => KeyCode::IntlBackslash,
+        0x0b => KeyCode::KeyB,
+        0x0c => KeyCode::KeyQ,
+        0x0d => KeyCode::KeyW,
+        0x0e => KeyCode::KeyE,
+        0x0f => KeyCode::KeyR,
+        0x10 => KeyCode::KeyY,
+        0x11 => KeyCode::KeyT,
+        0x12 => KeyCode::Digit1,
+        0x13 => KeyCode::Digit2,
+        0x14 => KeyCode::Digit3,
+        0x15 => KeyCode::Digit4,
+        0x16 => KeyCode::Digit6,
+        0x17 => KeyCode::Digit5,
+        0x18 => KeyCode::Equal,
+        0x19 => KeyCode::Digit9,
+        0x1a => KeyCode::Digit7,
+        0x1b => KeyCode::Minus,
+        0x1c => KeyCode::Digit8,
+        0x1d => KeyCode::Digit0,
+        0x1e => KeyCode::BracketRight,
+        0x1f => KeyCode::KeyO,
+        0x20 => KeyCode::KeyU,
+        0x21 => KeyCode::BracketLeft,
+        0x22 => KeyCode::KeyI,
+        0x23 => KeyCode::KeyP,
+        0x24 => KeyCode::Enter,
+        0x25 => KeyCode::KeyL,
+        0x26 => KeyCode::KeyJ,
+        0x27 => KeyCode::Quote,
+        0x28 => KeyCode::KeyK,
+        0x29 => KeyCode::Semicolon,
+        0x2a => KeyCode::Backslash,
+        0x2b => KeyCode::Comma,
+        0x2c => KeyCode::Slash,
+        0x2d => KeyCode::KeyN,
+        0x2e => KeyCode::KeyM,
+        0x2f => KeyCode::Period,
+        0x30 => KeyCode::Tab,
+        0x31 => KeyCode::Space,
+        0x32 => KeyCode::Backquote,
+        0x33 => KeyCode::Backspace,
+        0x35 => KeyCode::Escape,
+        0x36 => KeyCode::SuperRight,
+        0x37 => KeyCode::SuperLeft,
+        0x38 => KeyCode::ShiftLeft,
+        0x39 => KeyCode::CapsLock,
+        0x3a => KeyCode::AltLeft,
+        0x3b => KeyCode::ControlLeft,
+        0x3c => KeyCode::ShiftRight,
+        0x3d => KeyCode::AltRight,
+        0x3e => KeyCode::ControlRight,
+        0x3f => KeyCode::Fn,
+        0x40 => KeyCode::F17,
+        0x41 => KeyCode::NumpadDecimal,
+        0x43 => KeyCode::NumpadMultiply,
+        0x45 => KeyCode::NumpadAdd,
+        0x47 => KeyCode::NumLock,
+        0x48 => KeyCode::AudioVolumeUp,
+        0x49 => KeyCode::AudioVolumeDown,
+        0x4a => KeyCode::AudioVolumeMute,
+        0x4b => KeyCode::NumpadDivide,
+        0x4c => KeyCode::NumpadEnter,
+        0x4e => KeyCode::NumpadSubtract,
+        0x4f => KeyCode::F18,
+        0x50 => KeyCode::F19,
+        0x51 => KeyCode::NumpadEqual,
+        0x52 => KeyCode::Numpad0,
+        0x53 => KeyCode::Numpad1,
+        0x54 => KeyCode::Numpad2,
+        0x55 => KeyCode::Numpad3,
+        0x56 => KeyCode::Numpad4,
+        0x57 => KeyCode::Numpad5,
+        0x58 => KeyCode::Numpad6,
+        0x59 => KeyCode::Numpad7,
+        0x5a => KeyCode::F20,
+        0x5b => KeyCode::Numpad8,
+        0x5c => KeyCode::Numpad9,
+        0x5d => KeyCode::IntlYen,
+        0x5e => KeyCode::IntlRo,
+        0x5f => KeyCode::NumpadComma,
+        0x60 => KeyCode::F5,
+        0x61 => KeyCode::F6,
+        0x62 => KeyCode::F7,
+        0x63 => KeyCode::F3,
+        0x64 => KeyCode::F8,
+        0x65 => KeyCode::F9,
+        0x66 => KeyCode::Lang2,
+        0x67 => KeyCode::F11,
+        0x68 => KeyCode::Lang1,
+        0x69 => KeyCode::F13,
+        0x6a => KeyCode::F16,
+        0x6b => KeyCode::F14,
+        0x6d => KeyCode::F10,
+        0x6e => KeyCode::ContextMenu,
+        0x6f => KeyCode::F12,
+        0x71 => KeyCode::F15,
+        0x72 => KeyCode::Insert,
+        0x73 => KeyCode::Home,
+        0x74 => KeyCode::PageUp,
+        0x75 => KeyCode::Delete,
+        0x76 => KeyCode::F4,
+        0x77 => KeyCode::End,
+        0x78 => KeyCode::F2,
+        0x79 => KeyCode::PageDown,
+        0x7a => KeyCode::F1,
+        0x7b => KeyCode::ArrowLeft,
+        0x7c => KeyCode::ArrowRight,
+        0x7d => KeyCode::ArrowDown,
+        0x7e => KeyCode::ArrowUp,
+        _ => return PhysicalKey::Unidentified(NativeKeyCode::MacOS(scancode)),
+    })
+}
+
 pub unsafe fn modifier_event(
     ns_event: id,
     keymask: NSEventModifierFlags,
     was_key_pressed: bool,
-) -> Option<WindowEvent<'static>> {
+) -> Option<WindowEvent> {
     if !was_key_pressed && NSEvent::modifierFlags(ns_event).contains(keymask)
         || was_key_pressed && !NSEvent::modifierFlags(ns_event).contains(keymask)
     {
@@ -286,15 +383,22 @@ pub unsafe fn modifier_event(
         };
 
         let scancode = get_scancode(ns_event);
-        let virtual_keycode = scancode_to_keycode(scancode);
+        let logical_key = scancode_to_named_key(scancode)
+            .unwrap_or(Key::Unidentified(NativeKey::MacOS(scancode)));
         #[allow(deprecated)]
         Some(WindowEvent::KeyboardInput {
             device_id: DEVICE_ID,
             input: KeyboardInput {
                 state,
-                scancode: scancode as _,
-                virtual_keycode,
+                physical_key: scancode_to_physicalkey(scancode),
+                logical_key: logical_key.clone(),
+                text: None,
+                repeat: false,
                 modifiers: event_mods(ns_event),
+                platform_specific: KeyEventExtra {
+                    text_with_all_modifiers: None,
+                    key_without_modifiers: logical_key,
+                },
             },
             is_synthetic: false,
         })
@@ -8,19 +8,29 @@ use std::{
     panic::{catch_unwind, resume_unwind, RefUnwindSafe, UnwindSafe},
     process, ptr,
     rc::{Rc, Weak},
-    sync::mpsc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc,
+    },
+    time::Instant,
 };
 
 use cocoa::{
     appkit::{NSApp, NSEventModifierFlags, NSEventSubtype, NSEventType::NSApplicationDefined},
     base::{id, nil, BOOL, NO, YES},
-    foundation::{NSInteger, NSPoint, NSTimeInterval},
+    foundation::{NSInteger, NSPoint, NSTimeInterval, NSUInteger},
 };
 use objc::rc::autoreleasepool;
 
 use crate::{
-    event::Event,
-    event_loop::{ControlFlow, EventLoopClosed, EventLoopWindowTarget as RootWindowTarget},
+    device::DeviceHandle,
+    error::NotSupportedError,
+    event::{Event, ModifiersState, StartCause},
+    event_loop::{
+        ControlFlow, DeviceEvents, EventLoopClosed, EventLoopWindowTarget as RootWindowTarget,
+        PowerState, ProxyWakeup, SystemPreferences,
+    },
+    keyboard::KeyCode,
     monitor::MonitorHandle as RootMonitorHandle,
     platform::macos::ActivationPolicy,
     platform_impl::{
@@ -28,10 +38,11 @@ use crate::{
         platform::{
             app::APP_CLASS,
             app_delegate::APP_DELEGATE_CLASS,
-            app_state::{AppState, Callback},
+            app_state::{self, AppState, Callback},
+            event,
             monitor::{self, MonitorHandle},
             observer::*,
-            util::IdRef,
+            util::{self, IdRef},
         },
     },
 };
@@ -65,28 +76,180 @@ impl PanicInfo {
 }
 
 pub struct EventLoopWindowTarget<T: 'static> {
-    pub sender: mpsc::Sender<T>, // this is only here to be cloned elsewhere
-    pub receiver: mpsc::Receiver<T>,
+    pub sender: mpsc::Sender<(T, ProxyWakeup)>, // this is only here to be cloned elsewhere
+    pub receiver: mpsc::Receiver<(T, ProxyWakeup)>,
+    // Set when a proxy woke up the run loop for coalesced events, cleared before draining them
+    wakeup_pending: Arc<AtomicBool>,
 }
 
 impl<T> Default for EventLoopWindowTarget<T> {
     fn default() -> Self {
         let (sender, receiver) = mpsc::channel();
-        EventLoopWindowTarget { sender, receiver }
+        EventLoopWindowTarget {
+            sender,
+            receiver,
+            wakeup_pending: Default::default(),
+        }
     }
 }
 
 impl<T: 'static> EventLoopWindowTarget<T> {
+    /// Passes the user events of this iteration to `handler`, stopping after an event sent with
+    /// `ProxyWakeup::PerEvent`. Returns whether events may have been left for the next iteration.
+    pub fn receive_user_events(&self, mut handler: impl FnMut(T)) -> bool {
+        self.wakeup_pending.store(false, Ordering::SeqCst);
+        for (event, wakeup) in self.receiver.try_iter() {
+            handler(event);
+            if wakeup == ProxyWakeup::PerEvent {
+                return true;
+            }
+        }
+        false
+    }
+
     #[inline]
     pub fn available_monitors(&self) -> VecDeque<MonitorHandle> {
         monitor::available_monitors()
     }
 
+    #[cfg(feature = "rwh_05")]
+    #[inline]
+    pub fn raw_display_handle_rwh_05(&self) -> rwh_05::RawDisplayHandle {
+        rwh_05::RawDisplayHandle::AppKit(rwh_05::AppKitDisplayHandle::empty())
+    }
+
+    #[cfg(feature = "rwh_06")]
+    #[inline]
+    pub fn raw_display_handle_rwh_06(
+        &self,
+    ) -> Result<rwh_06::RawDisplayHandle, rwh_06::HandleError> {
+        Ok(rwh_06::RawDisplayHandle::AppKit(
+            rwh_06::AppKitDisplayHandle::new(),
+        ))
+    }
+
     #[inline]
     pub fn primary_monitor(&self) -> Option<RootMonitorHandle> {
         let monitor = monitor::primary_monitor();
         Some(RootMonitorHandle { inner: monitor })
     }
+
+    #[inline]
+    pub fn devices(&self) -> Vec<DeviceHandle> {
+        Vec::new()
+    }
+
+    #[inline]
+    pub fn listen_device_events(&self, _allowed: DeviceEvents) {}
+
+    #[inline]
+    pub fn keyboard_layout(&self) -> Option<String> {
+        event::keyboard_layout()
+    }
+
+    #[inline]
+    pub fn key_code_to_text(&self, code: KeyCode, modifiers: ModifiersState) -> Option<String> {
+        event::key_code_to_text(code, modifiers)
+    }
+
+    #[inline]
+    pub fn system_preferences(&self) -> SystemPreferences {
+        unsafe { util::system_preferences() }
+    }
+
+    #[inline]
+    pub fn power_state(&self) -> PowerState {
+        unsafe { util::power_state() }
+    }
+
+    #[inline]
+    pub fn event_timestamp(&self) -> Option<Instant> {
+        None
+    }
+
+    pub fn run_nested<F>(&self, mut event_handler: F) -> Result<i32, NotSupportedError>
+    where
+        F: FnMut(Event<T>, &mut ControlFlow),
+    {
+        // Restores the outer state even if `event_handler` panics.
+        struct NestedLoopGuard(bool);
+        impl Drop for NestedLoopGuard {
+            fn drop(&mut self) {
+                AppState::leave_nested_loop(self.0);
+            }
+        }
+        let _guard = NestedLoopGuard(AppState::enter_nested_loop());
+
+        let mut control_flow = ControlFlow::Poll;
+        let mut cause = StartCause::Init;
+        loop {
+            event_handler(Event::NewEvents(cause), &mut control_flow);
+            if self.receive_user_events(|event| {
+                event_handler(Event::UserEvent(event), &mut control_flow)
+            }) {
+                // Don't wait for an `NSEvent` before delivering the events left behind.
+                AppState::wake_nested_loop();
+            }
+            AppState::dispatch_nested_events(|event| event_handler(event, &mut control_flow));
+            event_handler(Event::MainEventsCleared, &mut control_flow);
+            for window_id in AppState::take_nested_redraws() {
+                event_handler(Event::RedrawRequested(window_id), &mut control_flow);
+            }
+            event_handler(Event::RedrawEventsCleared, &mut control_flow);
+
+            let start = Instant::now();
+            cause = match control_flow {
+                ControlFlow::ExitWithCode(code) => return Ok(code),
+                ControlFlow::Poll => StartCause::Poll,
+                ControlFlow::Wait => StartCause::WaitCancelled {
+                    start,
+                    requested_resume: None,
+                },
+                ControlFlow::WaitUntil(requested_resume) => StartCause::ResumeTimeReached {
+                    start,
+                    requested_resume,
+                },
+            };
+
+            autoreleasepool(|| unsafe {
+                let until: id = match control_flow {
+                    ControlFlow::Wait => msg_send![class!(NSDate), distantFuture],
+                    ControlFlow::WaitUntil(requested_resume) => {
+                        let timeout = requested_resume.saturating_duration_since(start);
+                        msg_send![
+                            class!(NSDate),
+                            dateWithTimeIntervalSinceNow: timeout.as_secs_f64() as NSTimeInterval
+                        ]
+                    }
+                    _ => msg_send![class!(NSDate), distantPast],
+                };
+                let app = NSApp();
+                let event: id = msg_send![
+                    app,
+                    nextEventMatchingMask: NSUInteger::max_value() // NSEventMaskAny
+                    untilDate: until
+                    inMode: kCFRunLoopDefaultMode as id
+                    dequeue: YES
+                ];
+                if event != nil {
+                    let () = msg_send![app, sendEvent: event];
+                }
+            });
+
+            if let StartCause::ResumeTimeReached {
+                start,
+                requested_resume,
+            } = cause
+            {
+                if Instant::now() < requested_resume {
+                    cause = StartCause::WaitCancelled {
+                        start,
+                        requested_resume: Some(requested_resume),
+                    };
+                }
+            }
+        }
+    }
 }
 
 impl<T> EventLoopWindowTarget<T> {
@@ -96,6 +259,30 @@ impl<T> EventLoopWindowTarget<T> {
         unsafe { msg_send![app, hide: 0] }
     }
 
+    pub(crate) fn set_activation_policy(&self, activation_policy: ActivationPolicy) {
+        app_state::set_activation_policy(activation_policy)
+    }
+
+    pub(crate) fn activation_policy(&self) -> ActivationPolicy {
+        app_state::activation_policy()
+    }
+
+    pub(crate) fn set_allows_automatic_window_tabbing(&self, enabled: bool) {
+        unsafe {
+            let _: () = msg_send![
+                class!(NSWindow),
+                setAllowsAutomaticWindowTabbing: if enabled { YES } else { NO }
+            ];
+        }
+    }
+
+    pub(crate) fn allows_automatic_window_tabbing(&self) -> bool {
+        unsafe {
+            let enabled: BOOL = msg_send![class!(NSWindow), allowsAutomaticWindowTabbing];
+            enabled == YES
+        }
+    }
+
     pub(crate) fn hide_other_applications(&self) {
         let cls = objc::runtime::Class::get("NSApplication").unwrap();
         let app: cocoa::base::id = unsafe { msg_send![cls, sharedApplication] };
@@ -167,6 +354,10 @@ impl<T> EventLoop<T> {
             _delegate: delegate,
             window_target: Rc::new(RootWindowTarget {
                 p: Default::default(),
+                coalescing: Default::default(),
+                exit_code: Default::default(),
+                event_timestamp: Default::default(),
+                dispatcher_settings: Default::default(),
                 _marker: PhantomData,
             }),
             panic_info,
@@ -180,7 +371,7 @@ impl<T> EventLoop<T> {
 
     pub fn run<F>(mut self, callback: F) -> !
     where
-        F: 'static + FnMut(Event<'_, T>, &RootWindowTarget<T>, &mut ControlFlow),
+        F: 'static + FnMut(Event<T>, &RootWindowTarget<T>, &mut ControlFlow),
     {
         let exit_code = self.run_return(callback);
         process::exit(exit_code);
@@ -188,7 +379,7 @@ impl<T> EventLoop<T> {
 
     pub fn run_return<F>(&mut self, callback: F) -> i32
     where
-        F: FnMut(Event<'_, T>, &RootWindowTarget<T>, &mut ControlFlow),
+        F: FnMut(Event<T>, &RootWindowTarget<T>, &mut ControlFlow),
     {
         // This transmute is always safe, in case it was reached through `run`, since our
         // lifetime will be already 'static. In other cases caller should ensure that all data
@@ -196,8 +387,8 @@ impl<T> EventLoop<T> {
         // everything to event loop, so this is something that they should care about.
         let callback = unsafe {
             mem::transmute::<
-                Rc<RefCell<dyn FnMut(Event<'_, T>, &RootWindowTarget<T>, &mut ControlFlow)>>,
-                Rc<RefCell<dyn FnMut(Event<'_, T>, &RootWindowTarget<T>, &mut ControlFlow)>>,
+                Rc<RefCell<dyn FnMut(Event<T>, &RootWindowTarget<T>, &mut ControlFlow)>>,
+                Rc<RefCell<dyn FnMut(Event<T>, &RootWindowTarget<T>, &mut ControlFlow)>>,
             >(Rc::new(RefCell::new(callback)))
         };
 
@@ -212,10 +403,15 @@ impl<T> EventLoop<T> {
             let weak_cb: Weak<_> = Rc::downgrade(&callback);
             mem::drop(callback);
 
-            AppState::set_callback(weak_cb, Rc::clone(&self.window_target));
+            AppState::set_callback(
+                weak_cb,
+                Rc::clone(&self.window_target),
+                Rc::downgrade(&self.panic_info),
+            );
             let () = msg_send![app, run];
 
             if let Some(panic) = self.panic_info.take() {
+                AppState::clear_callback();
                 drop(self._callback.take());
                 resume_unwind(panic);
             }
@@ -227,7 +423,11 @@ impl<T> EventLoop<T> {
     }
 
     pub fn create_proxy(&self) -> Proxy<T> {
-        Proxy::new(self.window_target.p.sender.clone())
+        Proxy::new(
+            self.window_target.p.sender.clone(),
+            self.window_target.p.wakeup_pending.clone(),
+            ProxyWakeup::default(),
+        )
     }
 }
 
@@ -282,7 +482,9 @@ pub fn stop_app_on_panic<F: FnOnce() -> R + UnwindSafe, R>(
 }
 
 pub struct Proxy<T> {
-    sender: mpsc::Sender<T>,
+    sender: mpsc::Sender<(T, ProxyWakeup)>,
+    wakeup_pending: Arc<AtomicBool>,
+    wakeup: ProxyWakeup,
     source: CFRunLoopSourceRef,
 }
 
@@ -298,15 +500,25 @@ impl<T> Drop for Proxy<T> {
 
 impl<T> Clone for Proxy<T> {
     fn clone(&self) -> Self {
-        Proxy::new(self.sender.clone())
+        Proxy::new(
+            self.sender.clone(),
+            self.wakeup_pending.clone(),
+            self.wakeup,
+        )
     }
 }
 
 impl<T> Proxy<T> {
-    fn new(sender: mpsc::Sender<T>) -> Self {
+    fn new(
+        sender: mpsc::Sender<(T, ProxyWakeup)>,
+        wakeup_pending: Arc<AtomicBool>,
+        wakeup: ProxyWakeup,
+    ) -> Self {
         unsafe {
             // just wake up the eventloop
-            extern "C" fn event_loop_proxy_handler(_: *mut c_void) {}
+            extern "C" fn event_loop_proxy_handler(_: *mut c_void) {
+                AppState::wake_nested_loop();
+            }
 
             // adding a Source to the main CFRunLoop lets us wake it up and
             // process user events through the normal OS EventLoop mechanisms.
@@ -318,14 +530,24 @@ impl<T> Proxy<T> {
             CFRunLoopAddSource(rl, source, kCFRunLoopCommonModes);
             CFRunLoopWakeUp(rl);
 
-            Proxy { sender, source }
+            Proxy {
+                sender,
+                wakeup_pending,
+                wakeup,
+                source,
+            }
         }
     }
 
     pub fn send_event(&self, event: T) -> Result<(), EventLoopClosed<T>> {
         self.sender
-            .send(event)
-            .map_err(|mpsc::SendError(x)| EventLoopClosed(x))?;
+            .send((event, self.wakeup))
+            .map_err(|mpsc::SendError((x, _))| EventLoopClosed(x))?;
+        // The run loop is already going to drain the events sent since its last wakeup.
+        if self.wakeup == ProxyWakeup::Coalesced && self.wakeup_pending.swap(true, Ordering::SeqCst)
+        {
+            return Ok(());
+        }
         unsafe {
             // let the main thread know there's a new event
             CFRunLoopSourceSignal(self.source);
@@ -334,4 +556,8 @@ impl<T> Proxy<T> {
         }
         Ok(())
     }
+
+    pub fn set_wakeup(&mut self, wakeup: ProxyWakeup) {
+        self.wakeup = wakeup;
+    }
 }
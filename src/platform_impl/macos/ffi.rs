@@ -9,7 +9,8 @@ use cocoa::{
     foundation::{NSInteger, NSUInteger},
 };
 use core_foundation::{
-    array::CFArrayRef, dictionary::CFDictionaryRef, string::CFStringRef, uuid::CFUUIDRef,
+    array::CFArrayRef, base::CFTypeRef, dictionary::CFDictionaryRef, runloop::CFRunLoopSourceRef,
+    string::CFStringRef, uuid::CFUUIDRef,
 };
 use core_graphics::{
     base::CGError,
@@ -119,6 +120,15 @@ pub enum NSWindowLevel {
     NSScreenSaverWindowLevel = kCGScreenSaverWindowLevelKey as _,
 }
 
+#[derive(Debug, Clone, Copy)]
+#[repr(isize)]
+#[allow(clippy::enum_variant_names)]
+pub enum NSWindowOrderingMode {
+    NSWindowAbove = 1,
+    NSWindowBelow = -1,
+    NSWindowOut = 0,
+}
+
 pub type CGDisplayFadeInterval = f32;
 pub type CGDisplayReservationInterval = f32;
 pub type CGDisplayBlendFraction = f32;
@@ -176,6 +186,10 @@ extern "C" {
     pub fn CGDisplayCreateUUIDFromDisplayID(display: CGDirectDisplayID) -> CFUUIDRef;
 }
 
+// Provides `CAMetalLayer`.
+#[link(name = "QuartzCore", kind = "framework")]
+extern "C" {}
+
 #[link(name = "CoreGraphics", kind = "framework")]
 extern "C" {
     pub fn CGRestorePermanentDisplayConfiguration();
@@ -221,3 +235,58 @@ extern "C" {
     pub fn CGDisplayModeRetain(mode: CGDisplayModeRef);
     pub fn CGDisplayModeRelease(mode: CGDisplayModeRef);
 }
+
+pub type IOPowerSourceCallbackType = extern "C" fn(context: *mut c_void);
+
+// From `IOKit/ps/IOPSKeys.h`.
+pub const kIOPMACPowerKey: &str = "AC Power";
+pub const kIOPMBatteryPowerKey: &str = "Battery Power";
+pub const kIOPMUPSPowerKey: &str = "UPS Power";
+
+#[link(name = "IOKit", kind = "framework")]
+extern "C" {
+    pub fn IOPSCopyPowerSourcesInfo() -> CFTypeRef;
+    pub fn IOPSGetProvidingPowerSourceType(snapshot: CFTypeRef) -> CFStringRef;
+    pub fn IOPSNotificationCreateRunLoopSource(
+        callback: IOPowerSourceCallbackType,
+        context: *mut c_void,
+    ) -> CFRunLoopSourceRef;
+}
+
+pub type TISInputSourceRef = *mut c_void;
+pub type UCKeyboardLayout = c_void;
+pub type OSStatus = i32;
+
+pub const kUCKeyActionDisplay: u16 = 3;
+pub const kUCKeyTranslateNoDeadKeysMask: u32 = 1;
+
+pub const shiftKey: u32 = 1 << 9;
+pub const optionKey: u32 = 1 << 11;
+
+#[link(name = "Carbon", kind = "framework")]
+extern "C" {
+    pub static kTISPropertyInputSourceID: CFStringRef;
+    pub static kTISPropertyUnicodeKeyLayoutData: CFStringRef;
+    pub static kTISNotifySelectedKeyboardInputSourceChanged: CFStringRef;
+
+    pub fn TISCopyCurrentKeyboardInputSource() -> TISInputSourceRef;
+    pub fn TISCopyCurrentKeyboardLayoutInputSource() -> TISInputSourceRef;
+    pub fn TISGetInputSourceProperty(
+        inputSource: TISInputSourceRef,
+        propertyKey: CFStringRef,
+    ) -> *mut c_void;
+
+    pub fn LMGetKbdType() -> u8;
+    pub fn UCKeyTranslate(
+        keyLayoutPtr: *const UCKeyboardLayout,
+        virtualKeyCode: u16,
+        keyAction: u16,
+        modifierKeyState: u32,
+        keyboardType: u32,
+        keyTranslateOptions: u32,
+        deadKeyState: *mut u32,
+        maxStringLength: usize,
+        actualStringLength: *mut usize,
+        unicodeString: *mut u16,
+    ) -> OSStatus;
+}
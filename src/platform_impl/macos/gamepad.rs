@@ -0,0 +1,300 @@
+//! Gamepad support backed by the GameController framework.
+//!
+//! The framework notifies the app delegate when controllers are connected and disconnected. Their
+//! input is polled from a timer on the main run loop, which only runs while a controller is
+//! connected.
+
+use std::{collections::VecDeque, os::raw::c_void};
+
+use cocoa::base::{id, nil, BOOL, NO};
+use core_foundation::{
+    base::TCFType,
+    date::CFAbsoluteTimeGetCurrent,
+    runloop::{
+        kCFRunLoopCommonModes, CFRunLoop, CFRunLoopTimer, CFRunLoopTimerContext,
+        CFRunLoopTimerInvalidate, CFRunLoopTimerRef,
+    },
+};
+use objc::runtime::{Object, Sel};
+
+use crate::{
+    event::{ElementState, Event},
+    gamepad::{GamepadAxis, GamepadButton, GamepadEvent, GamepadId},
+    platform_impl::platform::{
+        app_delegate::get_aux_state_mut,
+        app_state::AppState,
+        event::EventWrapper,
+        util::{self, IdRef},
+    },
+};
+
+#[link(name = "GameController", kind = "framework")]
+extern "C" {
+    static GCControllerDidConnectNotification: id;
+    static GCControllerDidDisconnectNotification: id;
+}
+
+/// How often connected controllers are polled, in seconds.
+const POLL_INTERVAL: f64 = 0.004;
+
+/// The buttons of the extended gamepad profile, by the getters leading to them. Some getters were
+/// added in later versions of macOS, so they're looked up at runtime.
+const BUTTONS: [(&[&str], GamepadButton); 15] = [
+    (&["dpad", "up"], GamepadButton::DPadUp),
+    (&["dpad", "down"], GamepadButton::DPadDown),
+    (&["dpad", "left"], GamepadButton::DPadLeft),
+    (&["dpad", "right"], GamepadButton::DPadRight),
+    (&["buttonMenu"], GamepadButton::Start),
+    (&["buttonOptions"], GamepadButton::Select),
+    (&["buttonHome"], GamepadButton::Mode),
+    (&["leftThumbstickButton"], GamepadButton::LeftThumb),
+    (&["rightThumbstickButton"], GamepadButton::RightThumb),
+    (&["leftShoulder"], GamepadButton::LeftShoulder),
+    (&["rightShoulder"], GamepadButton::RightShoulder),
+    (&["buttonA"], GamepadButton::South),
+    (&["buttonB"], GamepadButton::East),
+    (&["buttonX"], GamepadButton::West),
+    (&["buttonY"], GamepadButton::North),
+];
+
+const AXES: [(&[&str], GamepadAxis); 6] = [
+    (&["leftThumbstick", "xAxis"], GamepadAxis::LeftStickX),
+    (&["leftThumbstick", "yAxis"], GamepadAxis::LeftStickY),
+    (&["rightThumbstick", "xAxis"], GamepadAxis::RightStickX),
+    (&["rightThumbstick", "yAxis"], GamepadAxis::RightStickY),
+    (&["leftTrigger"], GamepadAxis::LeftTrigger),
+    (&["rightTrigger"], GamepadAxis::RightTrigger),
+];
+
+#[derive(Default, Clone, Copy, PartialEq)]
+struct State {
+    buttons: [bool; BUTTONS.len()],
+    axes: [f32; AXES.len()],
+}
+
+struct Controller {
+    controller: IdRef,
+    id: GamepadId,
+    state: State,
+}
+
+/// The connected controllers, owned by the app delegate.
+#[derive(Default)]
+pub struct Gamepads {
+    controllers: Vec<Controller>,
+    timer: Option<CFRunLoopTimer>,
+}
+
+impl Drop for Gamepads {
+    fn drop(&mut self) {
+        self.stop_polling();
+    }
+}
+
+impl Gamepads {
+    fn stop_polling(&mut self) {
+        if let Some(timer) = self.timer.take() {
+            unsafe { CFRunLoopTimerInvalidate(timer.as_concrete_TypeRef()) };
+        }
+    }
+}
+
+/// Observes the controllers from the app delegate, and reports those already connected.
+pub unsafe fn observe(delegate: &Object) {
+    let center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+    let _: () = msg_send![
+        center,
+        addObserver: delegate
+        selector: sel!(controllerDidConnect:)
+        name: GCControllerDidConnectNotification
+        object: nil
+    ];
+    let _: () = msg_send![
+        center,
+        addObserver: delegate
+        selector: sel!(controllerDidDisconnect:)
+        name: GCControllerDidDisconnectNotification
+        object: nil
+    ];
+
+    let controllers: id = msg_send![class!(GCController), controllers];
+    let count: usize = msg_send![controllers, count];
+    for index in 0..count {
+        let controller: id = msg_send![controllers, objectAtIndex: index];
+        connect(delegate, controller);
+    }
+}
+
+pub extern "C" fn controller_did_connect(this: &Object, _: Sel, notification: id) {
+    trace_scope!("controllerDidConnect:");
+    unsafe {
+        let controller: id = msg_send![notification, object];
+        connect(this, controller);
+    }
+}
+
+pub extern "C" fn controller_did_disconnect(this: &Object, _: Sel, notification: id) {
+    trace_scope!("controllerDidDisconnect:");
+    unsafe {
+        let controller: id = msg_send![notification, object];
+        let mut aux_state = get_aux_state_mut(this);
+        let gamepads = &mut aux_state.gamepads;
+        let index = match gamepads
+            .controllers
+            .iter()
+            .position(|slot| *slot.controller == controller)
+        {
+            Some(index) => index,
+            None => return,
+        };
+        let id = gamepads.controllers.remove(index).id;
+        if gamepads.controllers.is_empty() {
+            gamepads.stop_polling();
+        }
+        drop(aux_state);
+        queue_events(vec![(id, GamepadEvent::Disconnected)]);
+    }
+}
+
+unsafe fn connect(delegate: &Object, controller: id) {
+    let mut aux_state = get_aux_state_mut(delegate);
+    let gamepads = &mut aux_state.gamepads;
+    // Controllers connected before launch may be notified as well.
+    if gamepads
+        .controllers
+        .iter()
+        .any(|slot| *slot.controller == controller)
+    {
+        return;
+    }
+
+    let id = GamepadId::next();
+    gamepads.controllers.push(Controller {
+        controller: IdRef::retain(controller),
+        id,
+        state: State::default(),
+    });
+    if gamepads.timer.is_none() {
+        let mut context = CFRunLoopTimerContext {
+            version: 0,
+            info: delegate as *const Object as *mut c_void,
+            retain: None,
+            release: None,
+            copyDescription: None,
+        };
+        let timer = CFRunLoopTimer::new(
+            CFAbsoluteTimeGetCurrent() + POLL_INTERVAL,
+            POLL_INTERVAL,
+            0,
+            0,
+            poll,
+            &mut context,
+        );
+        CFRunLoop::get_main().add_timer(&timer, kCFRunLoopCommonModes);
+        gamepads.timer = Some(timer);
+    }
+    drop(aux_state);
+
+    let vendor_name: id = msg_send![controller, vendorName];
+    let name = if vendor_name == nil {
+        String::new()
+    } else {
+        util::ns_string_to_owned(vendor_name)
+    };
+    queue_events(vec![(id, GamepadEvent::Connected { name })]);
+}
+
+extern "C" fn poll(_: CFRunLoopTimerRef, info: *mut c_void) {
+    let delegate = unsafe { &*(info as *const Object) };
+    let mut events = Vec::new();
+    let mut aux_state = unsafe { get_aux_state_mut(delegate) };
+    for slot in &mut aux_state.gamepads.controllers {
+        let state = unsafe { read_state(*slot.controller) };
+        if state != slot.state {
+            diff_state(slot.id, &slot.state, &state, &mut events);
+            slot.state = state;
+        }
+    }
+    drop(aux_state);
+    queue_events(events);
+}
+
+fn queue_events(events: Vec<(GamepadId, GamepadEvent)>) {
+    if events.is_empty() {
+        return;
+    }
+    AppState::queue_events(
+        events
+            .into_iter()
+            .map(|(gamepad_id, event)| {
+                EventWrapper::StaticEvent(Event::GamepadEvent { gamepad_id, event })
+            })
+            .collect::<VecDeque<_>>(),
+    );
+}
+
+/// Follows the getters of `path` from `object`, or returns `nil` if one isn't available.
+unsafe fn element(object: id, path: &[&str]) -> id {
+    path.iter().fold(object, |object, getter| {
+        let getter = Sel::register(getter);
+        if object == nil {
+            return nil;
+        }
+        let responds: BOOL = msg_send![object, respondsToSelector: getter];
+        if responds == NO {
+            return nil;
+        }
+        msg_send![object, performSelector: getter]
+    })
+}
+
+/// Reads the state of a controller, which is released for controllers without the extended
+/// gamepad profile.
+unsafe fn read_state(controller: id) -> State {
+    let mut state = State::default();
+    let gamepad: id = msg_send![controller, extendedGamepad];
+    if gamepad == nil {
+        return state;
+    }
+
+    for (pressed, (path, _)) in state.buttons.iter_mut().zip(&BUTTONS) {
+        let button = element(gamepad, path);
+        if button != nil {
+            let is_pressed: BOOL = msg_send![button, isPressed];
+            *pressed = is_pressed != NO;
+        }
+    }
+    for (value, (path, _)) in state.axes.iter_mut().zip(&AXES) {
+        let axis = element(gamepad, path);
+        if axis != nil {
+            *value = msg_send![axis, value];
+        }
+    }
+    state
+}
+
+fn diff_state(
+    id: GamepadId,
+    old: &State,
+    new: &State,
+    events: &mut Vec<(GamepadId, GamepadEvent)>,
+) {
+    for (index, &(_, button)) in BUTTONS.iter().enumerate() {
+        let pressed = new.buttons[index];
+        if pressed != old.buttons[index] {
+            let state = if pressed {
+                ElementState::Pressed
+            } else {
+                ElementState::Released
+            };
+            events.push((id, GamepadEvent::Button { button, state }));
+        }
+    }
+
+    for (index, &(_, axis)) in AXES.iter().enumerate() {
+        if new.axes[index] != old.axes[index] {
+            let value = new.axes[index] as f64;
+            events.push((id, GamepadEvent::Axis { axis, value }));
+        }
+    }
+}
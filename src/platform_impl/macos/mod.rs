@@ -3,12 +3,17 @@
 #[macro_use]
 mod util;
 
+#[cfg(feature = "accesskit")]
+mod accessibility;
+
 mod app;
 mod app_delegate;
 mod app_state;
 mod event;
 mod event_loop;
 mod ffi;
+#[cfg(feature = "gamepad")]
+mod gamepad;
 mod menu;
 mod monitor;
 mod observer;
@@ -28,7 +33,10 @@ pub(crate) use self::{
     window::{Id as WindowId, PlatformSpecificWindowBuilderAttributes, UnownedWindow},
 };
 use crate::{
-    error::OsError as RootOsError, event::DeviceId as RootDeviceId, window::WindowAttributes,
+    error::OsError as RootOsError,
+    event::DeviceId as RootDeviceId,
+    keyboard::{Key, NativeKey, SmolStr},
+    window::WindowAttributes,
 };
 use objc::rc::autoreleasepool;
 
@@ -46,6 +54,21 @@ impl DeviceId {
 // Constant device ID; to be removed when if backend is updated to report real device IDs.
 pub(crate) const DEVICE_ID: RootDeviceId = RootDeviceId(DeviceId);
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyEventExtra {
+    pub text_with_all_modifiers: Option<SmolStr>,
+    pub key_without_modifiers: Key,
+}
+
+impl Default for KeyEventExtra {
+    fn default() -> Self {
+        Self {
+            text_with_all_modifiers: None,
+            key_without_modifiers: Key::Unidentified(NativeKey::Unidentified),
+        }
+    }
+}
+
 pub struct Window {
     window: Arc<UnownedWindow>,
     // We keep this around so that it doesn't get dropped until the window does.
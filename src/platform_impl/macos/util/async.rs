@@ -4,16 +4,21 @@ use std::{
 };
 
 use cocoa::{
-    appkit::{CGFloat, NSScreen, NSWindow, NSWindowStyleMask},
+    appkit::{CGFloat, NSScreen, NSView, NSWindow, NSWindowStyleMask},
     base::{id, nil},
     foundation::{NSPoint, NSSize, NSString},
 };
 use dispatch::Queue;
 use objc::rc::autoreleasepool;
-use objc::runtime::{BOOL, NO};
+use objc::runtime::{BOOL, NO, YES};
 
+#[cfg(feature = "accesskit")]
 use crate::{
-    dpi::LogicalSize,
+    accessibility::{accesskit::TreeUpdate, TreeSource},
+    platform_impl::platform::accessibility::Accessibility,
+};
+use crate::{
+    dpi::{LogicalPosition, LogicalSize},
     platform_impl::platform::{
         ffi,
         util::IdRef,
@@ -180,6 +185,14 @@ pub unsafe fn set_maximized_async(
     });
 }
 
+// Moving views isn't thread-safe.
+pub unsafe fn move_traffic_lights_async(ns_window: id, inset: LogicalPosition<f64>) {
+    let ns_window = MainThreadSafe(ns_window);
+    Queue::main().exec_async(move || {
+        super::move_traffic_lights(*ns_window, inset);
+    });
+}
+
 // `orderOut:` isn't thread-safe. Calling it from another thread actually works,
 // but with an odd delay.
 pub unsafe fn order_out_async(ns_window: id) {
@@ -222,3 +235,110 @@ pub unsafe fn close_async(ns_window: IdRef) {
         });
     });
 }
+
+/// Matches the scale and the drawable size of `layer` to the window, which AppKit leaves to the
+/// application with layer-hosting views.
+pub unsafe fn update_metal_layer(ns_window: id, ns_view: id, layer: id) {
+    let scale_factor = NSWindow::backingScaleFactor(ns_window);
+    let size = NSView::frame(ns_view).size;
+    let drawable_size = NSSize::new(size.width * scale_factor, size.height * scale_factor);
+    let _: () = msg_send![layer, setContentsScale: scale_factor];
+    let _: () = msg_send![layer, setDrawableSize: drawable_size];
+}
+
+unsafe fn install_metal_layer(ns_window: id, ns_view: id) -> id {
+    let layer: id = msg_send![ns_view, layer];
+    if layer != nil {
+        let is_metal_layer: BOOL = msg_send![layer, isKindOfClass: class!(CAMetalLayer)];
+        if is_metal_layer != NO {
+            update_metal_layer(ns_window, ns_view, layer);
+            return layer;
+        }
+    }
+
+    let layer: id = msg_send![class!(CAMetalLayer), new];
+    // Setting the layer before `wantsLayer` makes the view layer-hosting, so that AppKit leaves
+    // the contents of the layer to the application.
+    let _: () = msg_send![ns_view, setLayer: layer];
+    let _: () = msg_send![ns_view, setWantsLayer: YES];
+    // The view keeps the layer alive.
+    let _: () = msg_send![layer, release];
+    update_metal_layer(ns_window, ns_view, layer);
+    layer
+}
+
+// Layers are set up on the main thread, as with the rest of the view.
+pub unsafe fn install_metal_layer_sync(ns_window: id, ns_view: id) -> id {
+    let is_main_thread: BOOL = msg_send!(class!(NSThread), isMainThread);
+    if is_main_thread != NO {
+        install_metal_layer(ns_window, ns_view)
+    } else {
+        let ns_window = MainThreadSafe(ns_window);
+        let ns_view = MainThreadSafe(ns_view);
+        Queue::main()
+            .exec_sync(move || MainThreadSafe(install_metal_layer(*ns_window, *ns_view)))
+            .0
+    }
+}
+
+// Runs `f` on the main thread, waiting for it to return.
+#[cfg(feature = "accesskit")]
+unsafe fn exec_sync_on_main<R>(f: impl FnOnce() -> R) -> R {
+    let is_main_thread: BOOL = msg_send!(class!(NSThread), isMainThread);
+    if is_main_thread != NO {
+        f()
+    } else {
+        let f = MainThreadSafe(f);
+        Queue::main()
+            .exec_sync(move || {
+                let f = f;
+                MainThreadSafe((f.0)())
+            })
+            .0
+    }
+}
+
+// The accessibility adapter subclasses the view, and is queried by AppKit on the main thread.
+#[cfg(feature = "accesskit")]
+pub unsafe fn enable_accessibility_sync(
+    ns_window: id,
+    ns_view: id,
+    shared_state: Weak<Mutex<SharedState>>,
+    source: Box<dyn TreeSource>,
+) {
+    exec_sync_on_main(move || {
+        if let Some(shared_state) = shared_state.upgrade() {
+            let accessibility = Accessibility::new(ns_window, ns_view, source);
+            let mut shared_state_lock =
+                SharedStateMutexGuard::new(shared_state.lock().unwrap(), "enable_accessibility");
+            shared_state_lock.accessibility = Some(accessibility);
+        }
+    })
+}
+
+#[cfg(feature = "accesskit")]
+pub unsafe fn update_accessibility_tree_sync(
+    shared_state: Weak<Mutex<SharedState>>,
+    update: impl FnOnce() -> TreeUpdate,
+) {
+    exec_sync_on_main(move || {
+        let shared_state = match shared_state.upgrade() {
+            Some(shared_state) => shared_state,
+            None => return,
+        };
+        // The adapter is taken out of the shared state while `update` runs, as it may call the
+        // methods of the window.
+        let accessibility =
+            SharedStateMutexGuard::new(shared_state.lock().unwrap(), "update_accessibility_tree")
+                .accessibility
+                .take();
+        if let Some(mut accessibility) = accessibility {
+            let events = accessibility.update_tree(update);
+            SharedStateMutexGuard::new(shared_state.lock().unwrap(), "update_accessibility_tree")
+                .accessibility = Some(accessibility);
+            if let Some(events) = events {
+                events.raise();
+            }
+        }
+    })
+}
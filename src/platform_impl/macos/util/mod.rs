@@ -3,17 +3,26 @@ mod cursor;
 
 pub use self::{cursor::*, r#async::*};
 
-use std::ops::{BitAnd, Deref};
+use std::{
+    ops::{BitAnd, Deref},
+    os::raw::c_uchar,
+    slice, str,
+};
 
 use cocoa::{
-    appkit::{NSApp, NSWindowStyleMask},
-    base::{id, nil},
+    appkit::{NSApp, NSView, NSWindow, NSWindowButton, NSWindowStyleMask},
+    base::{id, nil, BOOL, NO, YES},
     foundation::{NSPoint, NSRect, NSString, NSUInteger},
 };
-use core_graphics::display::CGDisplay;
+use core_foundation::{
+    base::{CFRelease, TCFType},
+    string::CFString,
+};
+use core_graphics::{base::CGFloat, display::CGDisplay};
 use objc::runtime::{Class, Object};
 
 use crate::dpi::LogicalPosition;
+use crate::event_loop::{PowerSource, PowerState, SystemPreferences};
 use crate::platform_impl::platform::ffi;
 
 // Replace with `!` once stable
@@ -129,6 +138,14 @@ pub unsafe fn ns_string_id_ref(s: &str) -> IdRef {
     IdRef::new(NSString::alloc(nil).init_str(s))
 }
 
+pub unsafe fn ns_string_to_owned(characters: id) -> String {
+    assert_ne!(characters, nil);
+    let slice = slice::from_raw_parts(characters.UTF8String() as *const c_uchar, characters.len());
+
+    let string = str::from_utf8_unchecked(slice);
+    string.to_owned()
+}
+
 #[allow(dead_code)] // In case we want to use this function in the future
 pub unsafe fn app_name() -> Option<id> {
     let bundle: id = msg_send![class!(NSBundle), mainBundle];
@@ -153,11 +170,119 @@ pub unsafe fn create_input_context(view: id) -> IdRef {
     IdRef::new(input_context)
 }
 
+pub unsafe fn system_preferences() -> SystemPreferences {
+    let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+    let high_contrast: BOOL = msg_send![workspace, accessibilityDisplayShouldIncreaseContrast];
+    // Only available since macOS 10.12.
+    let responds: BOOL = msg_send![
+        workspace,
+        respondsToSelector: sel!(accessibilityDisplayShouldReduceMotion)
+    ];
+    let reduced_motion: BOOL = if responds == YES {
+        msg_send![workspace, accessibilityDisplayShouldReduceMotion]
+    } else {
+        NO
+    };
+    SystemPreferences {
+        accent_color: accent_color(),
+        high_contrast: high_contrast == YES,
+        reduced_motion: reduced_motion == YES,
+    }
+}
+
+pub unsafe fn power_state() -> PowerState {
+    let snapshot = ffi::IOPSCopyPowerSourcesInfo();
+    let source = if snapshot.is_null() {
+        PowerSource::Unknown
+    } else {
+        let source_type = ffi::IOPSGetProvidingPowerSourceType(snapshot);
+        let source = if source_type.is_null() {
+            PowerSource::Unknown
+        } else {
+            match CFString::wrap_under_get_rule(source_type)
+                .to_string()
+                .as_str()
+            {
+                ffi::kIOPMACPowerKey => PowerSource::Ac,
+                ffi::kIOPMBatteryPowerKey | ffi::kIOPMUPSPowerKey => PowerSource::Battery,
+                _ => PowerSource::Unknown,
+            }
+        };
+        CFRelease(snapshot);
+        source
+    };
+
+    let process_info: id = msg_send![class!(NSProcessInfo), processInfo];
+    // Only available since macOS 12.
+    let responds: BOOL = msg_send![
+        process_info,
+        respondsToSelector: sel!(isLowPowerModeEnabled)
+    ];
+    let low_power_mode: BOOL = if responds == YES {
+        msg_send![process_info, isLowPowerModeEnabled]
+    } else {
+        NO
+    };
+
+    PowerState {
+        source,
+        low_power_mode: low_power_mode == YES,
+    }
+}
+
+unsafe fn accent_color() -> Option<[u8; 3]> {
+    // Only available since macOS 10.14.
+    let responds: BOOL = msg_send![class!(NSColor), respondsToSelector: sel!(controlAccentColor)];
+    if responds != YES {
+        return None;
+    }
+    let color: id = msg_send![class!(NSColor), controlAccentColor];
+    let color_space: id = msg_send![class!(NSColorSpace), sRGBColorSpace];
+    let color: id = msg_send![color, colorUsingColorSpace: color_space];
+    if color == nil {
+        return None;
+    }
+    let red: CGFloat = msg_send![color, redComponent];
+    let green: CGFloat = msg_send![color, greenComponent];
+    let blue: CGFloat = msg_send![color, blueComponent];
+    let component = |value: CGFloat| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    Some([component(red), component(green), component(blue)])
+}
+
 #[allow(dead_code)]
 pub unsafe fn open_emoji_picker() {
     let () = msg_send![NSApp(), orderFrontCharacterPalette: nil];
 }
 
+/// Moves the close, minimize and zoom buttons of the title bar to `inset` from the top left corner
+/// of the window. AppKit lays them out again on resizes, so this must be called after them.
+pub unsafe fn move_traffic_lights(window: id, inset: LogicalPosition<f64>) {
+    let close = window.standardWindowButton_(NSWindowButton::NSWindowCloseButton);
+    let miniaturize = window.standardWindowButton_(NSWindowButton::NSWindowMiniaturizeButton);
+    let zoom = window.standardWindowButton_(NSWindowButton::NSWindowZoomButton);
+    // The buttons don't exist without a title bar.
+    if close == nil || miniaturize == nil || zoom == nil {
+        return;
+    }
+
+    // The buttons are laid out in the title bar container, which is grown to keep them vertically
+    // centered in it.
+    let title_bar_container: id = msg_send![close.superview(), superview];
+    let close_frame = NSView::frame(close);
+    let title_bar_height = close_frame.size.height + inset.y as CGFloat;
+    let mut title_bar_frame = NSView::frame(title_bar_container);
+    title_bar_frame.size.height = title_bar_height;
+    title_bar_frame.origin.y = NSWindow::frame(window).size.height - title_bar_height;
+    let () = msg_send![title_bar_container, setFrame: title_bar_frame];
+
+    let spacing = NSView::frame(miniaturize).origin.x - close_frame.origin.x;
+    for (index, button) in [close, miniaturize, zoom].iter().enumerate() {
+        let mut origin = NSView::frame(*button).origin;
+        origin.x = inset.x as CGFloat + index as CGFloat * spacing;
+        let () = msg_send![*button, setFrameOrigin: origin];
+    }
+}
+
 pub unsafe fn toggle_style_mask(window: id, view: id, mask: NSWindowStyleMask, on: bool) {
     use cocoa::appkit::NSWindow;
 
@@ -3,11 +3,14 @@ use std::{
     collections::VecDeque,
     os::raw::*,
     ptr, slice, str,
-    sync::{Arc, Mutex, Weak},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, Weak,
+    },
 };
 
 use cocoa::{
-    appkit::{NSApp, NSEvent, NSEventModifierFlags, NSEventPhase, NSView, NSWindow},
+    appkit::{CGFloat, NSApp, NSEvent, NSEventModifierFlags, NSEventPhase, NSView, NSWindow},
     base::{id, nil},
     foundation::{NSInteger, NSPoint, NSRect, NSSize, NSString, NSUInteger},
 };
@@ -20,18 +23,19 @@ use crate::{
     dpi::LogicalPosition,
     event::{
         DeviceEvent, ElementState, Event, KeyboardInput, ModifiersState, MouseButton,
-        MouseScrollDelta, TouchPhase, VirtualKeyCode, WindowEvent,
+        MouseScrollDelta, Pen, PenPhase, ScrollPhase, TouchPhase, WindowEvent,
     },
+    keyboard::{Key, NativeKey, SmolStr},
     platform_impl::platform::{
         app_state::AppState,
         event::{
-            char_to_keycode, check_function_keys, event_mods, get_scancode, modifier_event,
-            scancode_to_keycode, EventWrapper,
+            check_function_keys, event_mods, get_scancode, modifier_event, scancode_to_named_key,
+            scancode_to_physicalkey, EventWrapper,
         },
         ffi::*,
         util::{self, IdRef},
         window::get_window_id,
-        DEVICE_ID,
+        KeyEventExtra, DEVICE_ID,
     },
     window::WindowId,
 };
@@ -57,6 +61,9 @@ pub(super) struct ViewState {
     raw_characters: Option<String>,
     pub(super) modifiers: ModifiersState,
     tracking_rect: Option<NSInteger>,
+    // Whether the pen in proximity is used with its eraser end.
+    pen_eraser: bool,
+    key_repeat_enabled: Arc<AtomicBool>,
 }
 
 impl ViewState {
@@ -65,7 +72,10 @@ impl ViewState {
     }
 }
 
-pub fn new_view(ns_window: id) -> (IdRef, Weak<Mutex<CursorState>>) {
+pub fn new_view(
+    ns_window: id,
+    key_repeat_enabled: Arc<AtomicBool>,
+) -> (IdRef, Weak<Mutex<CursorState>>) {
     let cursor_state = Default::default();
     let cursor_access = Arc::downgrade(&cursor_state);
     let state = ViewState {
@@ -75,6 +85,8 @@ pub fn new_view(ns_window: id) -> (IdRef, Weak<Mutex<CursorState>>) {
         raw_characters: None,
         modifiers: Default::default(),
         tracking_rect: None,
+        pen_eraser: false,
+        key_repeat_enabled,
     };
     unsafe {
         // This is free'd in `dealloc`
@@ -237,6 +249,22 @@ lazy_static! {
             sel!(scrollWheel:),
             scroll_wheel as extern "C" fn(&Object, Sel, id),
         );
+        decl.add_method(
+            sel!(tabletProximity:),
+            tablet_proximity as extern "C" fn(&Object, Sel, id),
+        );
+        decl.add_method(
+            sel!(magnifyWithEvent:),
+            magnify_with_event as extern "C" fn(&Object, Sel, id),
+        );
+        decl.add_method(
+            sel!(smartMagnifyWithEvent:),
+            smart_magnify_with_event as extern "C" fn(&Object, Sel, id),
+        );
+        decl.add_method(
+            sel!(rotateWithEvent:),
+            rotate_with_event as extern "C" fn(&Object, Sel, id),
+        );
         decl.add_method(
             sel!(pressureChangeWithEvent:),
             pressure_change_with_event as extern "C" fn(&Object, Sel, id),
@@ -571,12 +599,24 @@ fn get_characters(event: id, ignore_modifiers: bool) -> String {
             msg_send![event, characters]
         };
 
-        assert_ne!(characters, nil);
-        let slice =
-            slice::from_raw_parts(characters.UTF8String() as *const c_uchar, characters.len());
+        util::ns_string_to_owned(characters)
+    }
+}
 
-        let string = str::from_utf8_unchecked(slice);
-        string.to_owned()
+// Retrieves the characters the key produces without any modifier, unlike
+// `charactersIgnoringModifiers` which still applies Shift.
+fn get_characters_without_modifiers(event: id) -> String {
+    unsafe {
+        let responds: BOOL =
+            msg_send![event, respondsToSelector: sel!(charactersByApplyingModifiers:)];
+        if responds == NO {
+            // `charactersByApplyingModifiers:` is only available since macOS 10.15.
+            return get_characters(event, true).to_lowercase();
+        }
+
+        let characters: id =
+            msg_send![event, charactersByApplyingModifiers: NSEventModifierFlags::empty()];
+        util::ns_string_to_owned(characters)
     }
 }
 
@@ -594,28 +634,58 @@ fn is_corporate_character(c: char) -> bool {
     )
 }
 
-// Retrieves a layout-independent keycode given an event.
-fn retrieve_keycode(event: id) -> Option<VirtualKeyCode> {
-    #[inline]
-    fn get_code(ev: id, raw: bool) -> Option<VirtualKeyCode> {
-        let characters = get_characters(ev, raw);
-        characters.chars().next().and_then(char_to_keycode)
+// Retrieves the logical key given an event.
+fn retrieve_key(event: id) -> Key {
+    let scancode = get_scancode(event);
+    if let Some(key) = scancode_to_named_key(scancode) {
+        return key;
     }
 
-    // Cmd switches Roman letters for Dvorak-QWERTY layout, so we try modified characters first.
-    // If we don't get a match, then we fall back to unmodified characters.
-    let code = get_code(event, false).or_else(|| get_code(event, true));
+    // `charactersIgnoringModifiers` still applies Shift, which is what we want. We're additionally
+    // checking here for F21-F24 keys, since their keycode can vary, but we know that they are
+    // encoded in characters property.
+    let characters = get_characters(event, true);
+    if let Some(key) = check_function_keys(&characters) {
+        return key;
+    }
 
-    // We've checked all layout related keys, so fall through to scancode.
-    // Reaching this code means that the key is layout-independent (e.g. Backspace, Return).
-    //
-    // We're additionally checking here for F21-F24 keys, since their keycode
-    // can vary, but we know that they are encoded
-    // in characters property.
-    code.or_else(|| {
-        let scancode = get_scancode(event);
-        scancode_to_keycode(scancode).or_else(|| check_function_keys(&get_characters(event, true)))
-    })
+    if characters.is_empty() || characters.chars().any(is_corporate_character) {
+        Key::Unidentified(NativeKey::MacOS(scancode))
+    } else if get_characters(event, false).is_empty() {
+        // Dead keys only produce characters once combined with the next key.
+        Key::Dead(characters.chars().next())
+    } else {
+        Key::Character(characters.into())
+    }
+}
+
+// Retrieves the logical key given an event, as if no modifier was pressed.
+fn retrieve_key_without_modifiers(event: id) -> Key {
+    let scancode = get_scancode(event);
+    if let Some(key) = scancode_to_named_key(scancode) {
+        return key;
+    }
+
+    let characters = get_characters_without_modifiers(event);
+    if let Some(key) = check_function_keys(&characters) {
+        return key;
+    }
+
+    if characters.is_empty() || characters.chars().any(is_corporate_character) {
+        Key::Unidentified(NativeKey::MacOS(scancode))
+    } else {
+        Key::Character(characters.into())
+    }
+}
+
+// Ctrl turns letters into control characters, which aren't useful as text, so the logical key's
+// text is reported instead while it's held.
+fn text_without_ctrl(logical_key: &Key, text: Option<&str>, event: id) -> Option<SmolStr> {
+    if event_mods(event).ctrl() {
+        logical_key.to_text().map(SmolStr::new)
+    } else {
+        text.map(SmolStr::new)
+    }
 }
 
 // Update `state.modifiers` if `event` has something different
@@ -641,13 +711,26 @@ extern "C" fn key_down(this: &Object, _sel: Sel, event: id) {
 
         state.raw_characters = Some(characters.clone());
 
-        let scancode = get_scancode(event) as u32;
-        let virtual_keycode = retrieve_keycode(event);
+        let physical_key = scancode_to_physicalkey(get_scancode(event));
+        let logical_key = retrieve_key(event);
+        let text_with_all_modifiers = Some(
+            characters
+                .chars()
+                .filter(|c| !is_corporate_character(*c))
+                .collect::<String>(),
+        )
+        .filter(|text| !text.is_empty())
+        .map(SmolStr::new);
+        let text = text_without_ctrl(&logical_key, text_with_all_modifiers.as_deref(), event);
 
         let is_repeat: BOOL = msg_send![event, isARepeat];
 
         update_potentially_stale_modifiers(state, event);
 
+        if is_repeat != NO && !state.key_repeat_enabled.load(Ordering::Relaxed) {
+            return;
+        }
+
         #[allow(deprecated)]
         let window_event = Event::WindowEvent {
             window_id,
@@ -655,9 +738,15 @@ extern "C" fn key_down(this: &Object, _sel: Sel, event: id) {
                 device_id: DEVICE_ID,
                 input: KeyboardInput {
                     state: ElementState::Pressed,
-                    scancode,
-                    virtual_keycode,
+                    physical_key,
+                    logical_key,
+                    text,
+                    repeat: is_repeat != NO,
                     modifiers: event_mods(event),
+                    platform_specific: KeyEventExtra {
+                        text_with_all_modifiers,
+                        key_without_modifiers: retrieve_key_without_modifiers(event),
+                    },
                 },
                 is_synthetic: false,
             },
@@ -695,8 +784,8 @@ extern "C" fn key_up(this: &Object, _sel: Sel, event: id) {
         let state_ptr: *mut c_void = *this.get_ivar("winitState");
         let state = &mut *(state_ptr as *mut ViewState);
 
-        let scancode = get_scancode(event) as u32;
-        let virtual_keycode = retrieve_keycode(event);
+        let physical_key = scancode_to_physicalkey(get_scancode(event));
+        let logical_key = retrieve_key(event);
 
         update_potentially_stale_modifiers(state, event);
 
@@ -707,9 +796,15 @@ extern "C" fn key_up(this: &Object, _sel: Sel, event: id) {
                 device_id: DEVICE_ID,
                 input: KeyboardInput {
                     state: ElementState::Released,
-                    scancode,
-                    virtual_keycode,
+                    physical_key,
+                    logical_key,
+                    text: None,
+                    repeat: false,
                     modifiers: event_mods(event),
+                    platform_specific: KeyEventExtra {
+                        text_with_all_modifiers: None,
+                        key_without_modifiers: retrieve_key_without_modifiers(event),
+                    },
                 },
                 is_synthetic: false,
             },
@@ -763,6 +858,13 @@ extern "C" fn flags_changed(this: &Object, _sel: Sel, event: id) {
             events.push_back(window_event);
         }
 
+        // The sides aren't toggled above, so they're taken from the event as is.
+        let generic = ModifiersState::SHIFT
+            | ModifiersState::CTRL
+            | ModifiersState::ALT
+            | ModifiersState::LOGO;
+        state.modifiers = (state.modifiers & generic) | (event_mods(event) - generic);
+
         let window_id = WindowId(get_window_id(state.ns_window));
 
         for event in events {
@@ -812,8 +914,6 @@ extern "C" fn cancel_operation(this: &Object, _sel: Sel, _sender: id) {
         let state = &mut *(state_ptr as *mut ViewState);
 
         let scancode = 0x2f;
-        let virtual_keycode = scancode_to_keycode(scancode);
-        debug_assert_eq!(virtual_keycode, Some(VirtualKeyCode::Period));
 
         let event: id = msg_send![NSApp(), currentEvent];
 
@@ -826,9 +926,15 @@ extern "C" fn cancel_operation(this: &Object, _sel: Sel, _sender: id) {
                 device_id: DEVICE_ID,
                 input: KeyboardInput {
                     state: ElementState::Pressed,
-                    scancode: scancode as _,
-                    virtual_keycode,
+                    physical_key: scancode_to_physicalkey(scancode),
+                    logical_key: Key::Character(".".into()),
+                    text: Some(".".into()),
+                    repeat: false,
                     modifiers: event_mods(event),
+                    platform_specific: KeyEventExtra {
+                        text_with_all_modifiers: Some(".".into()),
+                        key_without_modifiers: Key::Character(".".into()),
+                    },
                 },
                 is_synthetic: false,
             },
@@ -845,12 +951,15 @@ fn mouse_click(this: &Object, event: id, button: MouseButton, button_state: Elem
 
         update_potentially_stale_modifiers(state, event);
 
+        let click_count: NSInteger = msg_send![event, clickCount];
+
         let window_event = Event::WindowEvent {
             window_id: WindowId(get_window_id(state.ns_window)),
             event: WindowEvent::MouseInput {
                 device_id: DEVICE_ID,
                 state: button_state,
                 button,
+                click_count: click_count.max(1) as u32,
                 modifiers: event_mods(event),
             },
         };
@@ -863,12 +972,14 @@ extern "C" fn mouse_down(this: &Object, _sel: Sel, event: id) {
     trace_scope!("mouseDown:");
     mouse_motion(this, event);
     mouse_click(this, event, MouseButton::Left, ElementState::Pressed);
+    pen_input(this, event, PenPhase::Down);
 }
 
 extern "C" fn mouse_up(this: &Object, _sel: Sel, event: id) {
     trace_scope!("mouseUp:");
     mouse_motion(this, event);
     mouse_click(this, event, MouseButton::Left, ElementState::Released);
+    pen_input(this, event, PenPhase::Up);
 }
 
 extern "C" fn right_mouse_down(this: &Object, _sel: Sel, event: id) {
@@ -886,13 +997,34 @@ extern "C" fn right_mouse_up(this: &Object, _sel: Sel, event: id) {
 extern "C" fn other_mouse_down(this: &Object, _sel: Sel, event: id) {
     trace_scope!("otherMouseDown:");
     mouse_motion(this, event);
-    mouse_click(this, event, MouseButton::Middle, ElementState::Pressed);
+    mouse_click(
+        this,
+        event,
+        other_mouse_button(event),
+        ElementState::Pressed,
+    );
 }
 
 extern "C" fn other_mouse_up(this: &Object, _sel: Sel, event: id) {
     trace_scope!("otherMouseUp:");
     mouse_motion(this, event);
-    mouse_click(this, event, MouseButton::Middle, ElementState::Released);
+    mouse_click(
+        this,
+        event,
+        other_mouse_button(event),
+        ElementState::Released,
+    );
+}
+
+fn other_mouse_button(event: id) -> MouseButton {
+    let button_number: NSInteger = unsafe { msg_send![event, buttonNumber] };
+    match button_number {
+        3 => MouseButton::Back,
+        4 => MouseButton::Forward,
+        // `otherMouseDown:` is also sent for the middle button.
+        n if n < 3 => MouseButton::Middle,
+        n => MouseButton::Other(n as u16),
+    }
 }
 
 fn mouse_motion(this: &Object, event: id) {
@@ -938,14 +1070,89 @@ fn mouse_motion(this: &Object, event: id) {
     }
 }
 
+const NS_EVENT_SUBTYPE_TABLET_POINT: c_short = 1;
+const NS_POINTING_DEVICE_TYPE_ERASER: NSUInteger = 3;
+const NS_EVENT_BUTTON_MASK_PEN_LOWER_SIDE: NSUInteger = 2;
+const NS_EVENT_BUTTON_MASK_PEN_UPPER_SIDE: NSUInteger = 4;
+
+fn send_pen_event(this: &Object, event: id, phase: PenPhase) {
+    unsafe {
+        let state_ptr: *mut c_void = *this.get_ivar("winitState");
+        let state = &mut *(state_ptr as *mut ViewState);
+
+        let view: id = this as *const _ as *mut _;
+
+        let window_point = event.locationInWindow();
+        let view_point = view.convertPoint_fromView_(window_point, nil);
+        let view_rect = NSView::frame(view);
+
+        let x = view_point.x as f64;
+        let y = view_rect.size.height as f64 - view_point.y as f64;
+        let logical_position = LogicalPosition::new(x, y);
+
+        let tilt: NSPoint = msg_send![event, tilt];
+        let rotation: f32 = msg_send![event, rotation];
+        let button_mask: NSUInteger = msg_send![event, buttonMask];
+
+        let window_event = Event::WindowEvent {
+            window_id: WindowId(get_window_id(state.ns_window)),
+            event: WindowEvent::Pen(Pen {
+                device_id: DEVICE_ID,
+                phase,
+                location: logical_position.to_physical(state.get_scale_factor()),
+                pressure: event.pressure() as f64,
+                // The tilt is reported between -1 and 1 on each axis.
+                tilt: (tilt.x as f64 * 90.0, tilt.y as f64 * 90.0),
+                twist: rotation as f64,
+                barrel_button: button_mask
+                    & (NS_EVENT_BUTTON_MASK_PEN_LOWER_SIDE | NS_EVENT_BUTTON_MASK_PEN_UPPER_SIDE)
+                    != 0,
+                eraser: state.pen_eraser,
+            }),
+        };
+
+        AppState::queue_event(EventWrapper::StaticEvent(window_event));
+    }
+}
+
+// Mouse events carry the tablet data when they come from a pen.
+fn pen_input(this: &Object, event: id, phase: PenPhase) {
+    let subtype: c_short = unsafe { msg_send![event, subtype] };
+    if subtype == NS_EVENT_SUBTYPE_TABLET_POINT {
+        send_pen_event(this, event, phase);
+    }
+}
+
+extern "C" fn tablet_proximity(this: &Object, _sel: Sel, event: id) {
+    trace_scope!("tabletProximity:");
+
+    unsafe {
+        let state_ptr: *mut c_void = *this.get_ivar("winitState");
+        let state = &mut *(state_ptr as *mut ViewState);
+
+        let device_type: NSUInteger = msg_send![event, pointingDeviceType];
+        state.pen_eraser = device_type == NS_POINTING_DEVICE_TYPE_ERASER;
+
+        let entering: BOOL = msg_send![event, isEnteringProximity];
+        let phase = if entering == YES {
+            PenPhase::Entered
+        } else {
+            PenPhase::Left
+        };
+        send_pen_event(this, event, phase);
+    }
+}
+
 // No tracing on these because that would be overly verbose
 
 extern "C" fn mouse_moved(this: &Object, _sel: Sel, event: id) {
     mouse_motion(this, event);
+    pen_input(this, event, PenPhase::Moved);
 }
 
 extern "C" fn mouse_dragged(this: &Object, _sel: Sel, event: id) {
     mouse_motion(this, event);
+    pen_input(this, event, PenPhase::Moved);
 }
 
 extern "C" fn right_mouse_dragged(this: &Object, _sel: Sel, event: id) {
@@ -1009,12 +1216,19 @@ extern "C" fn scroll_wheel(this: &Object, _sel: Sel, event: id) {
                 MouseScrollDelta::LineDelta(x as f32, y as f32)
             }
         };
-        let phase = match event.phase() {
-            NSEventPhase::NSEventPhaseMayBegin | NSEventPhase::NSEventPhaseBegan => {
-                TouchPhase::Started
+        // Momentum events are sent after the gesture ended, with no gesture phase.
+        let phase = match (event.phase(), event.momentumPhase()) {
+            (_, NSEventPhase::NSEventPhaseBegan) => ScrollPhase::MomentumStarted,
+            (_, NSEventPhase::NSEventPhaseEnded) | (_, NSEventPhase::NSEventPhaseCancelled) => {
+                ScrollPhase::MomentumEnded
             }
-            NSEventPhase::NSEventPhaseEnded => TouchPhase::Ended,
-            _ => TouchPhase::Moved,
+            (NSEventPhase::NSEventPhaseMayBegin, _) | (NSEventPhase::NSEventPhaseBegan, _) => {
+                ScrollPhase::Started
+            }
+            (NSEventPhase::NSEventPhaseEnded, _) | (NSEventPhase::NSEventPhaseCancelled, _) => {
+                ScrollPhase::Ended
+            }
+            _ => ScrollPhase::Moved,
         };
 
         let device_event = Event::DeviceEvent {
@@ -1042,6 +1256,79 @@ extern "C" fn scroll_wheel(this: &Object, _sel: Sel, event: id) {
     }
 }
 
+fn gesture_phase(event: id) -> TouchPhase {
+    unsafe {
+        match event.phase() {
+            NSEventPhase::NSEventPhaseBegan => TouchPhase::Started,
+            NSEventPhase::NSEventPhaseEnded => TouchPhase::Ended,
+            NSEventPhase::NSEventPhaseCancelled => TouchPhase::Cancelled,
+            _ => TouchPhase::Moved,
+        }
+    }
+}
+
+extern "C" fn magnify_with_event(this: &Object, _sel: Sel, event: id) {
+    trace_scope!("magnifyWithEvent:");
+
+    unsafe {
+        let state_ptr: *mut c_void = *this.get_ivar("winitState");
+        let state = &mut *(state_ptr as *mut ViewState);
+
+        let delta: CGFloat = msg_send![event, magnification];
+
+        let window_event = Event::WindowEvent {
+            window_id: WindowId(get_window_id(state.ns_window)),
+            event: WindowEvent::PinchGesture {
+                device_id: DEVICE_ID,
+                delta: delta as f64,
+                phase: gesture_phase(event),
+            },
+        };
+
+        AppState::queue_event(EventWrapper::StaticEvent(window_event));
+    }
+}
+
+extern "C" fn smart_magnify_with_event(this: &Object, _sel: Sel, _event: id) {
+    trace_scope!("smartMagnifyWithEvent:");
+
+    unsafe {
+        let state_ptr: *mut c_void = *this.get_ivar("winitState");
+        let state = &mut *(state_ptr as *mut ViewState);
+
+        let window_event = Event::WindowEvent {
+            window_id: WindowId(get_window_id(state.ns_window)),
+            event: WindowEvent::DoubleTapGesture {
+                device_id: DEVICE_ID,
+            },
+        };
+
+        AppState::queue_event(EventWrapper::StaticEvent(window_event));
+    }
+}
+
+extern "C" fn rotate_with_event(this: &Object, _sel: Sel, event: id) {
+    trace_scope!("rotateWithEvent:");
+
+    unsafe {
+        let state_ptr: *mut c_void = *this.get_ivar("winitState");
+        let state = &mut *(state_ptr as *mut ViewState);
+
+        let delta: f32 = msg_send![event, rotation];
+
+        let window_event = Event::WindowEvent {
+            window_id: WindowId(get_window_id(state.ns_window)),
+            event: WindowEvent::RotationGesture {
+                device_id: DEVICE_ID,
+                delta,
+                phase: gesture_phase(event),
+            },
+        };
+
+        AppState::queue_event(EventWrapper::StaticEvent(window_event));
+    }
+}
+
 extern "C" fn pressure_change_with_event(this: &Object, _sel: Sel, event: id) {
     trace_scope!("pressureChangeWithEvent:");
 
@@ -10,6 +10,11 @@ use std::{
     },
 };
 
+#[cfg(feature = "accesskit")]
+use crate::{
+    accessibility::{accesskit::TreeUpdate, TreeSource},
+    platform_impl::platform::accessibility::Accessibility,
+};
 use crate::{
     dpi::{
         LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Position, Size, Size::Logical,
@@ -17,7 +22,7 @@ use crate::{
     error::{ExternalError, NotSupportedError, OsError as RootOsError},
     icon::Icon,
     monitor::{MonitorHandle as RootMonitorHandle, VideoMode as RootVideoMode},
-    platform::macos::WindowExtMacOS,
+    platform::macos::{BorderlessFullscreenMode, FullscreenPresentationOptions, WindowExtMacOS},
     platform_impl::platform::{
         app_state::AppState,
         app_state::INTERRUPT_EVENT_LOOP_EXIT,
@@ -30,7 +35,8 @@ use crate::{
         OsError,
     },
     window::{
-        CursorIcon, Fullscreen, UserAttentionType, WindowAttributes, WindowId as RootWindowId,
+        CursorIcon, Fullscreen, UserAttentionType, Window as RootWindow, WindowAttributes,
+        WindowId as RootWindowId,
     },
 };
 use cocoa::{
@@ -39,7 +45,7 @@ use cocoa::{
         NSRequestUserAttentionType, NSScreen, NSView, NSWindow, NSWindowButton, NSWindowStyleMask,
     },
     base::{id, nil},
-    foundation::{NSDictionary, NSPoint, NSRect, NSSize, NSUInteger},
+    foundation::{NSArray, NSDictionary, NSPoint, NSRect, NSSize, NSUInteger},
 };
 use core_graphics::display::{CGDisplay, CGDisplayMode};
 use objc::{
@@ -74,6 +80,10 @@ pub struct PlatformSpecificWindowBuilderAttributes {
     pub resize_increments: Option<LogicalSize<f64>>,
     pub disallow_hidpi: bool,
     pub has_shadow: bool,
+    pub tabbing_identifier: Option<String>,
+    pub borderless_fullscreen_mode: BorderlessFullscreenMode,
+    pub fullscreen_presentation_options: Option<FullscreenPresentationOptions>,
+    pub traffic_light_inset: Option<LogicalPosition<f64>>,
 }
 
 impl Default for PlatformSpecificWindowBuilderAttributes {
@@ -89,6 +99,10 @@ impl Default for PlatformSpecificWindowBuilderAttributes {
             resize_increments: None,
             disallow_hidpi: false,
             has_shadow: true,
+            tabbing_identifier: None,
+            borderless_fullscreen_mode: Default::default(),
+            fullscreen_presentation_options: None,
+            traffic_light_inset: None,
         }
     }
 }
@@ -96,8 +110,9 @@ impl Default for PlatformSpecificWindowBuilderAttributes {
 unsafe fn create_view(
     ns_window: id,
     pl_attribs: &PlatformSpecificWindowBuilderAttributes,
+    key_repeat_enabled: Arc<AtomicBool>,
 ) -> Option<(IdRef, Weak<Mutex<CursorState>>)> {
-    let (ns_view, cursor_state) = new_view(ns_window);
+    let (ns_view, cursor_state) = new_view(ns_window, key_repeat_enabled);
     ns_view.non_nil().map(|ns_view| {
         if !pl_attribs.disallow_hidpi {
             ns_view.setWantsBestResolutionOpenGLSurface_(YES);
@@ -235,6 +250,10 @@ fn create_window(
             if !pl_attrs.has_shadow {
                 ns_window.setHasShadow_(NO);
             }
+            if let Some(ref tabbing_identifier) = pl_attrs.tabbing_identifier {
+                let tabbing_identifier = util::ns_string_id_ref(tabbing_identifier);
+                let _: () = msg_send![*ns_window, setTabbingIdentifier: *tabbing_identifier];
+            }
             if attrs.position.is_none() {
                 ns_window.center();
             }
@@ -296,6 +315,18 @@ pub struct SharedState {
     /// transitioning back to borderless fullscreen.
     save_presentation_opts: Option<NSApplicationPresentationOptions>,
     pub saved_desktop_display_mode: Option<(CGDisplay, CGDisplayMode)>,
+    /// Whether borderless fullscreen uses a separate space. Otherwise, it goes through the simple
+    /// fullscreen code, with `is_simple_fullscreen` set along with `fullscreen`.
+    pub borderless_fullscreen_mode: BorderlessFullscreenMode,
+    /// The presentation options of borderless fullscreen, `None` to keep the system defaults.
+    pub fullscreen_presentation_options: Option<FullscreenPresentationOptions>,
+    /// Applied again by the window delegate whenever AppKit lays out the title bar.
+    pub traffic_light_inset: Option<LogicalPosition<f64>>,
+    /// The layer returned by `metal_layer`, kept in sync with the window by the window delegate.
+    pub metal_layer: Option<IdRef>,
+    /// The accessibility adapter, created by `enable_accessibility`.
+    #[cfg(feature = "accesskit")]
+    pub accessibility: Option<Accessibility>,
 }
 
 impl SharedState {
@@ -367,6 +398,7 @@ pub struct UnownedWindow {
     shared_state: Arc<Mutex<SharedState>>,
     decorations: AtomicBool,
     cursor_state: Weak<Mutex<CursorState>>,
+    key_repeat_enabled: Arc<AtomicBool>,
     pub inner_rect: Option<PhysicalSize<u32>>,
 }
 
@@ -389,8 +421,10 @@ impl UnownedWindow {
         let ns_window = create_window(&win_attribs, &pl_attribs)
             .ok_or_else(|| os_error!(OsError::CreationError("Couldn't create `NSWindow`")))?;
 
-        let (ns_view, cursor_state) = unsafe { create_view(*ns_window, &pl_attribs) }
-            .ok_or_else(|| os_error!(OsError::CreationError("Couldn't create `NSView`")))?;
+        let key_repeat_enabled = Arc::new(AtomicBool::new(true));
+        let (ns_view, cursor_state) =
+            unsafe { create_view(*ns_window, &pl_attribs, Arc::clone(&key_repeat_enabled)) }
+                .ok_or_else(|| os_error!(OsError::CreationError("Couldn't create `NSView`")))?;
 
         // Configure the new view as the "key view" for the window
         unsafe {
@@ -439,13 +473,22 @@ impl UnownedWindow {
             .inner_size
             .map(|size| size.to_physical(scale_factor));
 
+        let mut shared_state: SharedState = win_attribs.into();
+        shared_state.borderless_fullscreen_mode = pl_attribs.borderless_fullscreen_mode;
+        shared_state.fullscreen_presentation_options = pl_attribs.fullscreen_presentation_options;
+        shared_state.traffic_light_inset = pl_attribs.traffic_light_inset;
+        if let Some(inset) = pl_attribs.traffic_light_inset {
+            unsafe { util::move_traffic_lights(*ns_window, inset) };
+        }
+
         let window = Arc::new(UnownedWindow {
             ns_view,
             ns_window,
             input_context,
-            shared_state: Arc::new(Mutex::new(win_attribs.into())),
+            shared_state: Arc::new(Mutex::new(shared_state)),
             decorations: AtomicBool::new(decorations),
             cursor_state,
+            key_repeat_enabled,
             inner_rect,
         });
 
@@ -633,6 +676,11 @@ impl UnownedWindow {
             .map_err(|status| ExternalError::Os(os_error!(OsError::CGError(status))))
     }
 
+    #[inline]
+    pub fn set_key_repeat_enabled(&self, enabled: bool) {
+        self.key_repeat_enabled.store(enabled, Ordering::Relaxed);
+    }
+
     #[inline]
     pub fn set_cursor_visible(&self, visible: bool) {
         if let Some(cursor_access) = self.cursor_state.upgrade() {
@@ -780,9 +828,10 @@ impl UnownedWindow {
     }
 
     #[inline]
-    pub fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
+    pub fn set_fullscreen(&self, mut fullscreen: Option<Fullscreen>) {
         let mut shared_state_lock = self.lock_shared_state("set_fullscreen");
-        if shared_state_lock.is_simple_fullscreen {
+        // Simple fullscreen entered with `set_simple_fullscreen`, which must be left first
+        if shared_state_lock.is_simple_fullscreen && shared_state_lock.fullscreen.is_none() {
             return;
         }
         if shared_state_lock.in_fullscreen_transition {
@@ -791,10 +840,41 @@ impl UnownedWindow {
             shared_state_lock.target_fullscreen = Some(fullscreen);
             return;
         }
-        let old_fullscreen = shared_state_lock.fullscreen.clone();
+        let mut old_fullscreen = shared_state_lock.fullscreen.clone();
         if fullscreen == old_fullscreen {
             return;
         }
+
+        if shared_state_lock.borderless_fullscreen_mode == BorderlessFullscreenMode::Simple {
+            let was_simple = shared_state_lock.is_simple_fullscreen;
+            if let Some(Fullscreen::Borderless(monitor)) = fullscreen.clone() {
+                if old_fullscreen.is_none() || was_simple {
+                    let RootMonitorHandle { inner: monitor } =
+                        monitor.unwrap_or_else(|| self.current_monitor_inner());
+                    let screen = monitor.ns_screen().unwrap();
+                    unsafe {
+                        if was_simple {
+                            self.exit_simple_fullscreen(&mut shared_state_lock);
+                        }
+                        self.enter_simple_fullscreen(&mut shared_state_lock, screen);
+                    }
+                    shared_state_lock.fullscreen = fullscreen;
+                    return;
+                }
+
+                // The native fullscreen must be left first, `window_did_exit_fullscreen` then
+                // enters the simple one.
+                shared_state_lock.target_fullscreen = Some(fullscreen);
+                fullscreen = None;
+            } else if was_simple {
+                unsafe { self.exit_simple_fullscreen(&mut shared_state_lock) };
+                shared_state_lock.fullscreen = None;
+                if fullscreen.is_none() {
+                    return;
+                }
+                old_fullscreen = None;
+            }
+        }
         drop(shared_state_lock);
 
         // If the fullscreen is on a different monitor, we must move the window
@@ -977,6 +1057,72 @@ impl UnownedWindow {
         };
     }
 
+    /// Enters the simple fullscreen on `screen`, saving the state restored by
+    /// `exit_simple_fullscreen`.
+    unsafe fn enter_simple_fullscreen(&self, shared_state: &mut SharedState, screen: id) {
+        let app = NSApp();
+
+        // Remember the original window's settings
+        // Exclude title bar
+        shared_state.standard_frame = Some(NSWindow::contentRectForFrameRect_(
+            *self.ns_window,
+            NSWindow::frame(*self.ns_window),
+        ));
+        shared_state.saved_style = Some(self.ns_window.styleMask());
+        shared_state.save_presentation_opts = Some(app.presentationOptions_());
+
+        // Tell our window's state that we're in fullscreen
+        shared_state.is_simple_fullscreen = true;
+
+        // Simulate pre-Lion fullscreen by hiding the dock and menu bar, unless configured
+        // otherwise
+        let options = shared_state
+            .fullscreen_presentation_options
+            .unwrap_or_default();
+        app.setPresentationOptions_(presentation_options(options, false));
+
+        // Hide the titlebar
+        util::toggle_style_mask(
+            *self.ns_window,
+            *self.ns_view,
+            NSWindowStyleMask::NSTitledWindowMask,
+            false,
+        );
+
+        // Set the window frame to the screen frame size
+        let screen_frame = NSScreen::frame(screen);
+        NSWindow::setFrame_display_(*self.ns_window, screen_frame, YES);
+
+        // Fullscreen windows can't be resized, minimized, or moved
+        util::toggle_style_mask(
+            *self.ns_window,
+            *self.ns_view,
+            NSWindowStyleMask::NSMiniaturizableWindowMask,
+            false,
+        );
+        util::toggle_style_mask(
+            *self.ns_window,
+            *self.ns_view,
+            NSWindowStyleMask::NSResizableWindowMask,
+            false,
+        );
+        NSWindow::setMovable_(*self.ns_window, NO);
+    }
+
+    unsafe fn exit_simple_fullscreen(&self, shared_state: &mut SharedState) {
+        let new_mask = self.saved_style(shared_state);
+        self.set_style_mask_async(new_mask);
+        shared_state.is_simple_fullscreen = false;
+
+        if let Some(presentation_opts) = shared_state.save_presentation_opts {
+            NSApp().setPresentationOptions_(presentation_opts);
+        }
+
+        let frame = shared_state.saved_standard_frame();
+        NSWindow::setFrame_display_(*self.ns_window, frame, YES);
+        NSWindow::setMovable_(*self.ns_window, YES);
+    }
+
     #[inline]
     pub fn set_decorations(&self, decorations: bool) {
         if decorations != self.decorations.load(Ordering::Acquire) {
@@ -1115,6 +1261,25 @@ impl UnownedWindow {
         Some(RootMonitorHandle { inner: monitor })
     }
 
+    #[cfg(feature = "accesskit")]
+    pub fn enable_accessibility(&self, source: Box<dyn TreeSource>) {
+        unsafe {
+            util::enable_accessibility_sync(
+                *self.ns_window,
+                *self.ns_view,
+                Arc::downgrade(&self.shared_state),
+                source,
+            );
+        }
+    }
+
+    #[cfg(feature = "accesskit")]
+    pub fn update_accessibility_tree(&self, update: impl FnOnce() -> TreeUpdate) {
+        unsafe {
+            util::update_accessibility_tree_sync(Arc::downgrade(&self.shared_state), update);
+        }
+    }
+
     #[inline]
     pub fn raw_window_handle(&self) -> RawWindowHandle {
         let mut handle = AppKitHandle::empty();
@@ -1122,6 +1287,37 @@ impl UnownedWindow {
         handle.ns_view = *self.ns_view as *mut _;
         RawWindowHandle::AppKit(handle)
     }
+
+    #[cfg(feature = "rwh_05")]
+    pub fn raw_window_handle_rwh_05(&self) -> rwh_05::RawWindowHandle {
+        let mut handle = rwh_05::AppKitWindowHandle::empty();
+        handle.ns_window = *self.ns_window as *mut _;
+        handle.ns_view = *self.ns_view as *mut _;
+        rwh_05::RawWindowHandle::AppKit(handle)
+    }
+
+    #[cfg(feature = "rwh_05")]
+    pub fn raw_display_handle_rwh_05(&self) -> rwh_05::RawDisplayHandle {
+        rwh_05::RawDisplayHandle::AppKit(rwh_05::AppKitDisplayHandle::empty())
+    }
+
+    #[cfg(feature = "rwh_06")]
+    pub fn raw_window_handle_rwh_06(&self) -> Result<rwh_06::RawWindowHandle, rwh_06::HandleError> {
+        let ns_view = std::ptr::NonNull::new(*self.ns_view as *mut _)
+            .ok_or(rwh_06::HandleError::Unavailable)?;
+        Ok(rwh_06::RawWindowHandle::AppKit(
+            rwh_06::AppKitWindowHandle::new(ns_view),
+        ))
+    }
+
+    #[cfg(feature = "rwh_06")]
+    pub fn raw_display_handle_rwh_06(
+        &self,
+    ) -> Result<rwh_06::RawDisplayHandle, rwh_06::HandleError> {
+        Ok(rwh_06::RawDisplayHandle::AppKit(
+            rwh_06::AppKitDisplayHandle::new(),
+        ))
+    }
 }
 
 impl WindowExtMacOS for UnownedWindow {
@@ -1138,7 +1334,7 @@ impl WindowExtMacOS for UnownedWindow {
     #[inline]
     fn simple_fullscreen(&self) -> bool {
         let shared_state_lock = self.shared_state.lock().unwrap();
-        shared_state_lock.is_simple_fullscreen
+        shared_state_lock.is_simple_fullscreen && shared_state_lock.fullscreen.is_none()
     }
 
     #[inline]
@@ -1146,7 +1342,6 @@ impl WindowExtMacOS for UnownedWindow {
         let mut shared_state_lock = self.shared_state.lock().unwrap();
 
         unsafe {
-            let app = NSApp();
             let is_native_fullscreen = shared_state_lock.fullscreen.is_some();
             let is_simple_fullscreen = shared_state_lock.is_simple_fullscreen;
 
@@ -1159,68 +1354,12 @@ impl WindowExtMacOS for UnownedWindow {
             }
 
             if fullscreen {
-                // Remember the original window's settings
-                // Exclude title bar
-                shared_state_lock.standard_frame = Some(NSWindow::contentRectForFrameRect_(
-                    *self.ns_window,
-                    NSWindow::frame(*self.ns_window),
-                ));
-                shared_state_lock.saved_style = Some(self.ns_window.styleMask());
-                shared_state_lock.save_presentation_opts = Some(app.presentationOptions_());
-
-                // Tell our window's state that we're in fullscreen
-                shared_state_lock.is_simple_fullscreen = true;
-
-                // Simulate pre-Lion fullscreen by hiding the dock and menu bar
-                let presentation_options =
-                    NSApplicationPresentationOptions::NSApplicationPresentationAutoHideDock |
-                    NSApplicationPresentationOptions::NSApplicationPresentationAutoHideMenuBar;
-                app.setPresentationOptions_(presentation_options);
-
-                // Hide the titlebar
-                util::toggle_style_mask(
-                    *self.ns_window,
-                    *self.ns_view,
-                    NSWindowStyleMask::NSTitledWindowMask,
-                    false,
-                );
-
-                // Set the window frame to the screen frame size
                 let screen = self.ns_window.screen();
-                let screen_frame = NSScreen::frame(screen);
-                NSWindow::setFrame_display_(*self.ns_window, screen_frame, YES);
-
-                // Fullscreen windows can't be resized, minimized, or moved
-                util::toggle_style_mask(
-                    *self.ns_window,
-                    *self.ns_view,
-                    NSWindowStyleMask::NSMiniaturizableWindowMask,
-                    false,
-                );
-                util::toggle_style_mask(
-                    *self.ns_window,
-                    *self.ns_view,
-                    NSWindowStyleMask::NSResizableWindowMask,
-                    false,
-                );
-                NSWindow::setMovable_(*self.ns_window, NO);
-
-                true
+                self.enter_simple_fullscreen(&mut shared_state_lock, screen);
             } else {
-                let new_mask = self.saved_style(&mut *shared_state_lock);
-                self.set_style_mask_async(new_mask);
-                shared_state_lock.is_simple_fullscreen = false;
-
-                if let Some(presentation_opts) = shared_state_lock.save_presentation_opts {
-                    app.setPresentationOptions_(presentation_opts);
-                }
-
-                let frame = shared_state_lock.saved_standard_frame();
-                NSWindow::setFrame_display_(*self.ns_window, frame, YES);
-                NSWindow::setMovable_(*self.ns_window, YES);
-
-                true
+                self.exit_simple_fullscreen(&mut shared_state_lock);
             }
+            true
         }
     }
 
@@ -1236,6 +1375,137 @@ impl WindowExtMacOS for UnownedWindow {
                 .setHasShadow_(if has_shadow { YES } else { NO })
         }
     }
+
+    fn set_titlebar_transparent(&self, transparent: bool) {
+        unsafe {
+            self.ns_window
+                .setTitlebarAppearsTransparent_(if transparent { YES } else { NO });
+        }
+    }
+
+    fn set_title_hidden(&self, hidden: bool) {
+        let visibility = if hidden {
+            appkit::NSWindowTitleVisibility::NSWindowTitleHidden
+        } else {
+            appkit::NSWindowTitleVisibility::NSWindowTitleVisible
+        };
+        unsafe { self.ns_window.setTitleVisibility_(visibility) };
+    }
+
+    fn set_fullsize_content_view(&self, fullsize_content_view: bool) {
+        let mut mask = unsafe { self.ns_window.styleMask() };
+        if fullsize_content_view {
+            mask |= NSWindowStyleMask::NSFullSizeContentViewWindowMask;
+        } else {
+            mask &= !NSWindowStyleMask::NSFullSizeContentViewWindowMask;
+        }
+        self.set_style_mask_async(mask);
+    }
+
+    fn set_traffic_light_inset(&self, inset: LogicalPosition<f64>) {
+        self.lock_shared_state("set_traffic_light_inset")
+            .traffic_light_inset = Some(inset);
+        unsafe { util::move_traffic_lights_async(*self.ns_window, inset) };
+    }
+
+    fn metal_layer(&self) -> *mut c_void {
+        if let Some(ref layer) = self.lock_shared_state("metal_layer").metal_layer {
+            return **layer as *mut c_void;
+        }
+        // The shared state isn't locked while waiting for the main thread, whose window delegate
+        // might lock it.
+        let layer = unsafe { util::install_metal_layer_sync(*self.ns_window, *self.ns_view) };
+        self.lock_shared_state("metal_layer").metal_layer = Some(IdRef::retain(layer));
+        layer as *mut c_void
+    }
+
+    fn tabbing_identifier(&self) -> String {
+        unsafe {
+            let tabbing_identifier: id = msg_send![*self.ns_window, tabbingIdentifier];
+            util::ns_string_to_owned(tabbing_identifier)
+        }
+    }
+
+    fn set_tabbing_identifier(&self, identifier: &str) {
+        unsafe {
+            let identifier = util::ns_string_id_ref(identifier);
+            let _: () = msg_send![*self.ns_window, setTabbingIdentifier: *identifier];
+        }
+    }
+
+    fn add_tab(&self, window: &RootWindow) {
+        unsafe {
+            let _: () = msg_send![
+                *self.ns_window,
+                addTabbedWindow: *window.window.ns_window
+                ordered: ffi::NSWindowOrderingMode::NSWindowAbove
+            ];
+        }
+    }
+
+    fn merge_all_windows(&self) {
+        unsafe {
+            let _: () = msg_send![*self.ns_window, mergeAllWindows: nil];
+        }
+    }
+
+    fn select_next_tab(&self) {
+        unsafe {
+            let _: () = msg_send![*self.ns_window, selectNextTab: nil];
+        }
+    }
+
+    fn select_previous_tab(&self) {
+        unsafe {
+            let _: () = msg_send![*self.ns_window, selectPreviousTab: nil];
+        }
+    }
+
+    fn select_tab_at_index(&self, index: usize) {
+        unsafe {
+            let tabbed_windows: id = msg_send![*self.ns_window, tabbedWindows];
+            if tabbed_windows != nil && index < NSArray::count(tabbed_windows) as usize {
+                let window: id = NSArray::objectAtIndex(tabbed_windows, index as NSUInteger);
+                let _: () = msg_send![window, makeKeyAndOrderFront: nil];
+            }
+        }
+    }
+
+    fn num_tabs(&self) -> usize {
+        unsafe {
+            let tabbed_windows: id = msg_send![*self.ns_window, tabbedWindows];
+            if tabbed_windows == nil {
+                // The window isn't part of a tab group.
+                1
+            } else {
+                NSArray::count(tabbed_windows) as usize
+            }
+        }
+    }
+
+    fn set_borderless_fullscreen_mode(&self, mode: BorderlessFullscreenMode) {
+        self.lock_shared_state("set_borderless_fullscreen_mode")
+            .borderless_fullscreen_mode = mode;
+    }
+
+    fn borderless_fullscreen_mode(&self) -> BorderlessFullscreenMode {
+        self.lock_shared_state("borderless_fullscreen_mode")
+            .borderless_fullscreen_mode
+    }
+
+    fn set_fullscreen_presentation_options(&self, options: Option<FullscreenPresentationOptions>) {
+        let mut shared_state_lock = self.lock_shared_state("set_fullscreen_presentation_options");
+        shared_state_lock.fullscreen_presentation_options = options;
+
+        // Apply the options right away when the window is already in borderless fullscreen
+        if let Some(Fullscreen::Borderless(_)) = shared_state_lock.fullscreen {
+            let simple = shared_state_lock.is_simple_fullscreen;
+            let options = options.unwrap_or_default();
+            unsafe {
+                NSApp().setPresentationOptions_(presentation_options(options, !simple));
+            }
+        }
+    }
 }
 
 impl Drop for UnownedWindow {
@@ -1248,6 +1518,61 @@ impl Drop for UnownedWindow {
     }
 }
 
+/// Converts `options` to presentation options AppKit accepts, for the native fullscreen if
+/// `native`.
+pub(crate) fn presentation_options(
+    options: FullscreenPresentationOptions,
+    native: bool,
+) -> NSApplicationPresentationOptions {
+    let mut presentation_options = NSApplicationPresentationOptions::empty();
+    if native {
+        presentation_options |=
+            NSApplicationPresentationOptions::NSApplicationPresentationFullScreen;
+    }
+
+    // AppKit raises an exception for the combinations it doesn't support: hiding the menu bar
+    // requires hiding the dock, auto-hiding the menu bar requires at least auto-hiding the dock,
+    // and the native fullscreen requires at least auto-hiding the menu bar.
+    let hide_menu_bar = options.contains(FullscreenPresentationOptions::HIDE_MENU_BAR);
+    let auto_hide_menu_bar = !hide_menu_bar
+        && (native || options.contains(FullscreenPresentationOptions::AUTO_HIDE_MENU_BAR));
+    let hide_dock = hide_menu_bar || options.contains(FullscreenPresentationOptions::HIDE_DOCK);
+    let auto_hide_dock = !hide_dock
+        && (auto_hide_menu_bar || options.contains(FullscreenPresentationOptions::AUTO_HIDE_DOCK));
+
+    if hide_menu_bar {
+        presentation_options |=
+            NSApplicationPresentationOptions::NSApplicationPresentationHideMenuBar;
+    }
+    if auto_hide_menu_bar {
+        presentation_options |=
+            NSApplicationPresentationOptions::NSApplicationPresentationAutoHideMenuBar;
+    }
+    if hide_dock {
+        presentation_options |= NSApplicationPresentationOptions::NSApplicationPresentationHideDock;
+    }
+    if auto_hide_dock {
+        presentation_options |=
+            NSApplicationPresentationOptions::NSApplicationPresentationAutoHideDock;
+    }
+    // Switching processes requires the dock.
+    if (hide_dock || auto_hide_dock)
+        && options.contains(FullscreenPresentationOptions::DISABLE_PROCESS_SWITCHING)
+    {
+        presentation_options |=
+            NSApplicationPresentationOptions::NSApplicationPresentationDisableProcessSwitching;
+    }
+    if options.contains(FullscreenPresentationOptions::DISABLE_FORCE_QUIT) {
+        presentation_options |=
+            NSApplicationPresentationOptions::NSApplicationPresentationDisableForceQuit;
+    }
+    if options.contains(FullscreenPresentationOptions::DISABLE_HIDE_APPLICATION) {
+        presentation_options |=
+            NSApplicationPresentationOptions::NSApplicationPresentationDisableHideApplication;
+    }
+    presentation_options
+}
+
 unsafe fn set_min_inner_size<V: NSWindow + Copy>(window: V, mut min_size: LogicalSize<f64>) {
     let mut current_rect = NSWindow::frame(window);
     let content_rect = NSWindow::contentRectForFrameRect_(window, NSWindow::frame(window));
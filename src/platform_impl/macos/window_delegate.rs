@@ -24,7 +24,7 @@ use crate::{
         event::{EventProxy, EventWrapper},
         util::{self, IdRef},
         view::ViewState,
-        window::{get_window_id, UnownedWindow},
+        window::{get_window_id, presentation_options, UnownedWindow},
     },
     window::{Fullscreen, WindowId},
 };
@@ -75,7 +75,48 @@ impl WindowDelegateState {
         self.window.upgrade().map(|ref window| callback(window))
     }
 
-    pub fn emit_event(&mut self, event: WindowEvent<'static>) {
+    // AppKit lays out the title bar again on resizes, moving the traffic lights back.
+    fn move_traffic_lights(&mut self) {
+        let inset = self.with_window(|window| {
+            window
+                .lock_shared_state("move_traffic_lights")
+                .traffic_light_inset
+        });
+        if let Some(Some(inset)) = inset {
+            unsafe { util::move_traffic_lights(*self.ns_window, inset) };
+        }
+    }
+
+    // The layer of a layer-hosting view isn't resized or rescaled along with the window.
+    fn update_metal_layer(&mut self) {
+        let layer = self.with_window(|window| {
+            window
+                .lock_shared_state("update_metal_layer")
+                .metal_layer
+                .as_ref()
+                .map(|layer| **layer)
+        });
+        if let Some(Some(layer)) = layer {
+            unsafe { util::update_metal_layer(*self.ns_window, *self.ns_view, layer) };
+        }
+    }
+
+    // The adapter reports the focused node only while the window is focused.
+    #[cfg(feature = "accesskit")]
+    fn update_accessibility_focus(&mut self, focused: bool) {
+        let events = self.with_window(|window| {
+            window
+                .lock_shared_state("update_accessibility_focus")
+                .accessibility
+                .as_mut()
+                .and_then(|accessibility| accessibility.set_focused(focused))
+        });
+        if let Some(Some(events)) = events {
+            events.raise();
+        }
+    }
+
+    pub fn emit_event(&mut self, event: WindowEvent) {
         let event = Event::WindowEvent {
             window_id: WindowId(get_window_id(*self.ns_window)),
             event,
@@ -230,6 +271,12 @@ lazy_static! {
             window_did_fail_to_enter_fullscreen as extern "C" fn(&Object, Sel, id),
         );
 
+        // Shows the "+" button of the tab bar.
+        decl.add_method(
+            sel!(newWindowForTab:),
+            new_window_for_tab as extern "C" fn(&Object, Sel, id),
+        );
+
         decl.add_ivar::<*mut c_void>("winitState");
         WindowDelegateClass(decl.register())
     };
@@ -286,6 +333,8 @@ extern "C" fn window_will_close(this: &Object, _: Sel, _: id) {
 extern "C" fn window_did_resize(this: &Object, _: Sel, _: id) {
     trace_scope!("windowDidResize:");
     with_state(this, |state| {
+        state.move_traffic_lights();
+        state.update_metal_layer();
         state.emit_resize_event();
         state.emit_move_event();
     });
@@ -302,6 +351,7 @@ extern "C" fn window_did_move(this: &Object, _: Sel, _: id) {
 extern "C" fn window_did_change_backing_properties(this: &Object, _: Sel, _: id) {
     trace_scope!("windowDidChangeBackingProperties:");
     with_state(this, |state| {
+        state.update_metal_layer();
         state.emit_static_scale_factor_changed_event();
     });
 }
@@ -312,9 +362,16 @@ extern "C" fn window_did_become_key(this: &Object, _: Sel, _: id) {
         // TODO: center the cursor if the window had mouse grab when it
         // lost focus
         state.emit_event(WindowEvent::Focused(true));
+        #[cfg(feature = "accesskit")]
+        state.update_accessibility_focus(true);
     });
 }
 
+extern "C" fn new_window_for_tab(this: &Object, _: Sel, _: id) {
+    trace_scope!("newWindowForTab:");
+    with_state(this, |state| state.emit_event(WindowEvent::NewTabRequested));
+}
+
 extern "C" fn window_did_resign_key(this: &Object, _: Sel, _: id) {
     trace_scope!("windowDidResignKey:");
     with_state(this, |state| {
@@ -342,6 +399,8 @@ extern "C" fn window_did_resign_key(this: &Object, _: Sel, _: id) {
         }
 
         state.emit_event(WindowEvent::Focused(false));
+        #[cfg(feature = "accesskit")]
+        state.update_accessibility_focus(false);
     });
 }
 
@@ -364,7 +423,7 @@ extern "C" fn dragging_entered(this: &Object, _: Sel, sender: id) -> BOOL {
             let path = CStr::from_ptr(f).to_string_lossy().into_owned();
 
             with_state(this, |state| {
-                state.emit_event(WindowEvent::HoveredFile(PathBuf::from(path)));
+                state.emit_event(WindowEvent::HoveredFile(PathBuf::from(path).into()));
             });
         }
     }
@@ -397,7 +456,7 @@ extern "C" fn perform_drag_operation(this: &Object, _: Sel, sender: id) -> BOOL
             let path = CStr::from_ptr(f).to_string_lossy().into_owned();
 
             with_state(this, |state| {
-                state.emit_event(WindowEvent::DroppedFile(PathBuf::from(path)));
+                state.emit_event(WindowEvent::DroppedFile(PathBuf::from(path).into()));
             });
         }
     }
@@ -470,24 +529,30 @@ extern "C" fn window_will_use_fullscreen_presentation_options(
     proposed_options: NSUInteger,
 ) -> NSUInteger {
     trace_scope!("window:willUseFullScreenPresentationOptions:");
-    // Generally, games will want to disable the menu bar and the dock. Ideally,
-    // this would be configurable by the user. Unfortunately because of our
+    // Generally, games will want to disable the menu bar and the dock, which
+    // `WindowExtMacOS::set_fullscreen_presentation_options` configures for
+    // borderless fullscreen. Unfortunately because of our
     // `CGShieldingWindowLevel() + 1` hack (see `set_fullscreen`), our window is
     // placed on top of the menu bar in exclusive fullscreen mode. This looks
-    // broken so we always disable the menu bar in exclusive fullscreen. We may
-    // still want to make this configurable for borderless fullscreen. Right now
-    // we don't, for consistency. If we do, it should be documented that the
-    // user-provided options are ignored in exclusive fullscreen.
+    // broken so we always disable the menu bar in exclusive fullscreen, ignoring
+    // the user-provided options.
     let mut options: NSUInteger = proposed_options;
     with_state(this, |state| {
         state.with_window(|window| {
             let shared_state =
                 window.lock_shared_state("window_will_use_fullscreen_presentation_options");
-            if let Some(Fullscreen::Exclusive(_)) = shared_state.fullscreen {
-                options = (NSApplicationPresentationOptions::NSApplicationPresentationFullScreen
-                    | NSApplicationPresentationOptions::NSApplicationPresentationHideDock
-                    | NSApplicationPresentationOptions::NSApplicationPresentationHideMenuBar)
-                    .bits();
+            match shared_state.fullscreen {
+                Some(Fullscreen::Exclusive(_)) => {
+                    options = (NSApplicationPresentationOptions::NSApplicationPresentationFullScreen
+                        | NSApplicationPresentationOptions::NSApplicationPresentationHideDock
+                        | NSApplicationPresentationOptions::NSApplicationPresentationHideMenuBar)
+                        .bits();
+                }
+                _ => {
+                    if let Some(fullscreen_options) = shared_state.fullscreen_presentation_options {
+                        options = presentation_options(fullscreen_options, true).bits();
+                    }
+                }
             }
         })
     });
@@ -524,6 +589,10 @@ extern "C" fn window_did_exit_fullscreen(this: &Object, _: Sel, _: id) {
             window.restore_state_from_fullscreen();
             let mut shared_state = window.lock_shared_state("window_did_exit_fullscreen");
             shared_state.in_fullscreen_transition = false;
+            // After the style mask restored asynchronously by `restore_state_from_fullscreen`
+            if let Some(inset) = shared_state.traffic_light_inset {
+                unsafe { util::move_traffic_lights_async(*window.ns_window, inset) };
+            }
             let target_fullscreen = shared_state.target_fullscreen.take();
             drop(shared_state);
             if let Some(target_fullscreen) = target_fullscreen {
@@ -1,5 +1,16 @@
 pub use self::platform::*;
 
+#[cfg(any(
+    target_os = "windows",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_arch = "wasm32"
+))]
+mod click;
+
 #[cfg(target_os = "windows")]
 #[path = "windows/mod.rs"]
 mod platform;
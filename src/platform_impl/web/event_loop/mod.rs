@@ -24,6 +24,10 @@ impl<T> EventLoop<T> {
         EventLoop {
             elw: root::EventLoopWindowTarget {
                 p: WindowTarget::new(),
+                coalescing: Default::default(),
+                exit_code: Default::default(),
+                event_timestamp: Default::default(),
+                dispatcher_settings: Default::default(),
                 _marker: PhantomData,
             },
         }
@@ -31,10 +35,14 @@ impl<T> EventLoop<T> {
 
     pub fn run<F>(self, mut event_handler: F) -> !
     where
-        F: 'static + FnMut(Event<'_, T>, &root::EventLoopWindowTarget<T>, &mut root::ControlFlow),
+        F: 'static + FnMut(Event<T>, &root::EventLoopWindowTarget<T>, &mut root::ControlFlow),
     {
         let target = root::EventLoopWindowTarget {
             p: self.elw.p.clone(),
+            coalescing: self.elw.coalescing.clone(),
+            exit_code: self.elw.exit_code.clone(),
+            event_timestamp: self.elw.event_timestamp.clone(),
+            dispatcher_settings: self.elw.dispatcher_settings.clone(),
             _marker: PhantomData,
         };
 
@@ -1,6 +1,6 @@
 use super::runner;
 use crate::event::Event;
-use crate::event_loop::EventLoopClosed;
+use crate::event_loop::{EventLoopClosed, ProxyWakeup};
 
 pub struct Proxy<T: 'static> {
     runner: runner::Shared<T>,
@@ -15,6 +15,8 @@ impl<T: 'static> Proxy<T> {
         self.runner.send_event(Event::UserEvent(event));
         Ok(())
     }
+
+    pub fn set_wakeup(&mut self, _wakeup: ProxyWakeup) {}
 }
 
 impl<T: 'static> Clone for Proxy<T> {
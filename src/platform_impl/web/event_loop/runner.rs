@@ -1,5 +1,5 @@
 use super::{super::ScaleChangeArgs, backend, state::State};
-use crate::event::{Event, StartCause};
+use crate::event::{Event, InnerSizeWriter, SessionSleepReason, StartCause};
 use crate::event_loop as root;
 use crate::window::WindowId;
 
@@ -11,6 +11,7 @@ use std::{
     iter,
     ops::Deref,
     rc::{Rc, Weak},
+    sync::{Arc, Mutex},
 };
 
 pub struct Shared<T: 'static>(Rc<Execution<T>>);
@@ -23,13 +24,15 @@ impl<T> Clone for Shared<T> {
 
 pub struct Execution<T: 'static> {
     runner: RefCell<RunnerEnum<T>>,
-    events: RefCell<VecDeque<Event<'static, T>>>,
+    events: RefCell<VecDeque<Event<T>>>,
     id: RefCell<u32>,
     all_canvases: RefCell<Vec<(WindowId, Weak<RefCell<backend::Canvas>>)>>,
     redraw_pending: RefCell<HashSet<WindowId>>,
     destroy_pending: RefCell<VecDeque<WindowId>>,
     scale_change_detector: RefCell<Option<backend::ScaleChangeDetector>>,
     unload_event_handle: RefCell<Option<backend::UnloadEventHandle>>,
+    visibility_change_event_handle: RefCell<Option<backend::VisibilityChangeEventHandle>>,
+    system_preferences_change_handle: RefCell<Option<backend::SystemPreferencesChangeHandle>>,
 }
 
 enum RunnerEnum<T: 'static> {
@@ -54,11 +57,11 @@ impl<T: 'static> RunnerEnum<T> {
 
 struct Runner<T: 'static> {
     state: State,
-    event_handler: Box<dyn FnMut(Event<'_, T>, &mut root::ControlFlow)>,
+    event_handler: Box<dyn FnMut(Event<T>, &mut root::ControlFlow)>,
 }
 
 impl<T: 'static> Runner<T> {
-    pub fn new(event_handler: Box<dyn FnMut(Event<'_, T>, &mut root::ControlFlow)>) -> Self {
+    pub fn new(event_handler: Box<dyn FnMut(Event<T>, &mut root::ControlFlow)>) -> Self {
         Runner {
             state: State::Init,
             event_handler,
@@ -83,7 +86,7 @@ impl<T: 'static> Runner<T> {
         })
     }
 
-    fn handle_single_event(&mut self, event: Event<'_, T>, control: &mut root::ControlFlow) {
+    fn handle_single_event(&mut self, event: Event<T>, control: &mut root::ControlFlow) {
         let is_closed = matches!(*control, root::ControlFlow::ExitWithCode(_));
 
         (self.event_handler)(event, control);
@@ -106,6 +109,8 @@ impl<T: 'static> Shared<T> {
             destroy_pending: RefCell::new(VecDeque::new()),
             scale_change_detector: RefCell::new(None),
             unload_event_handle: RefCell::new(None),
+            visibility_change_event_handle: RefCell::new(None),
+            system_preferences_change_handle: RefCell::new(None),
         }))
     }
 
@@ -123,10 +128,7 @@ impl<T: 'static> Shared<T> {
     // Set the event callback to use for the event loop runner
     // This the event callback is a fairly thin layer over the user-provided callback that closes
     // over a RootEventLoopWindowTarget reference
-    pub fn set_listener(
-        &self,
-        event_handler: Box<dyn FnMut(Event<'_, T>, &mut root::ControlFlow)>,
-    ) {
+    pub fn set_listener(&self, event_handler: Box<dyn FnMut(Event<T>, &mut root::ControlFlow)>) {
         {
             let mut runner = self.0.runner.borrow_mut();
             assert!(matches!(*runner, RunnerEnum::Pending));
@@ -137,6 +139,22 @@ impl<T: 'static> Shared<T> {
         let close_instance = self.clone();
         *self.0.unload_event_handle.borrow_mut() =
             Some(backend::on_unload(move || close_instance.handle_unload()));
+
+        let visibility_instance = self.clone();
+        *self.0.visibility_change_event_handle.borrow_mut() =
+            Some(backend::on_visibility_change(move |hidden| {
+                visibility_instance.send_event(if hidden {
+                    Event::SessionSleep(SessionSleepReason::Hidden)
+                } else {
+                    Event::SessionWake(SessionSleepReason::Hidden)
+                })
+            }));
+
+        let preferences_instance = self.clone();
+        *self.0.system_preferences_change_handle.borrow_mut() =
+            Some(backend::on_system_preferences_change(move || {
+                preferences_instance.send_event(Event::SystemPreferencesChanged)
+            }));
     }
 
     pub(crate) fn set_on_scale_change<F>(&self, handler: F)
@@ -184,14 +202,14 @@ impl<T: 'static> Shared<T> {
     // Add an event to the event loop runner, from the user or an event handler
     //
     // It will determine if the event should be immediately sent to the user or buffered for later
-    pub fn send_event(&self, event: Event<'static, T>) {
+    pub fn send_event(&self, event: Event<T>) {
         self.send_events(iter::once(event));
     }
 
     // Add a series of events to the event loop runner
     //
     // It will determine if the event should be immediately sent to the user or buffered for later
-    pub fn send_events(&self, events: impl Iterator<Item = Event<'static, T>>) {
+    pub fn send_events(&self, events: impl Iterator<Item = Event<T>>) {
         // If the event loop is closed, it should discard any new events
         if self.is_closed() {
             return;
@@ -266,7 +284,7 @@ impl<T: 'static> Shared<T> {
     // cleared
     //
     // This will also process any events that have been queued or that are queued during processing
-    fn run_until_cleared(&self, events: impl Iterator<Item = Event<'static, T>>) {
+    fn run_until_cleared(&self, events: impl Iterator<Item = Event<T>>) {
         let mut control = self.current_control_flow();
         for event in events {
             self.handle_event(event, &mut control);
@@ -315,8 +333,11 @@ impl<T: 'static> Shared<T> {
 
         // Now handle the `ScaleFactorChanged` events.
         for &(id, ref canvas) in &*self.0.all_canvases.borrow() {
-            let canvas = match canvas.upgrade() {
-                Some(rc) => rc.borrow().raw().clone(),
+            let (canvas, follows_parent_size) = match canvas.upgrade() {
+                Some(rc) => {
+                    let canvas = rc.borrow();
+                    (canvas.raw().clone(), canvas.follows_parent_size())
+                }
                 // This shouldn't happen, but just in case...
                 None => continue,
             };
@@ -326,20 +347,27 @@ impl<T: 'static> Shared<T> {
                 height: canvas.height() as u32,
             };
             let logical_size = current_size.to_logical::<f64>(old_scale);
-            let mut new_size = logical_size.to_physical(new_scale);
+            let new_size = Arc::new(Mutex::new(logical_size.to_physical(new_scale)));
             self.handle_single_event_sync(
                 Event::WindowEvent {
                     window_id: id,
                     event: crate::event::WindowEvent::ScaleFactorChanged {
                         scale_factor: new_scale,
-                        new_inner_size: &mut new_size,
+                        inner_size_writer: InnerSizeWriter::new(Arc::downgrade(&new_size)),
                     },
                 },
                 &mut control,
             );
+            let new_size = *new_size.lock().unwrap();
 
             // Then we resize the canvas to the new size and send a `Resized` event:
-            backend::set_canvas_size(&canvas, crate::dpi::Size::Physical(new_size));
+            if follows_parent_size {
+                // The size on the page is controlled by the parent, only resize the drawing buffer.
+                canvas.set_width(new_size.width);
+                canvas.set_height(new_size.height);
+            } else {
+                backend::set_canvas_size(&canvas, crate::dpi::Size::Physical(new_size));
+            }
             self.handle_single_event_sync(
                 Event::WindowEvent {
                     window_id: id,
@@ -379,7 +407,7 @@ impl<T: 'static> Shared<T> {
     // handle_single_event_sync takes in an event and handles it synchronously.
     //
     // It should only ever be called from `scale_changed`.
-    fn handle_single_event_sync(&self, event: Event<'_, T>, control: &mut root::ControlFlow) {
+    fn handle_single_event_sync(&self, event: Event<T>, control: &mut root::ControlFlow) {
         if self.is_closed() {
             *control = root::ControlFlow::Exit;
         }
@@ -394,7 +422,7 @@ impl<T: 'static> Shared<T> {
     // handle_event takes in events and either queues them or applies a callback
     //
     // It should only ever be called from `run_until_cleared` and `scale_changed`.
-    fn handle_event(&self, event: Event<'static, T>, control: &mut root::ControlFlow) {
+    fn handle_event(&self, event: Event<T>, control: &mut root::ControlFlow) {
         if self.is_closed() {
             *control = root::ControlFlow::Exit;
         }
@@ -472,6 +500,8 @@ impl<T: 'static> Shared<T> {
         let all_canvases = std::mem::take(&mut *self.0.all_canvases.borrow_mut());
         *self.0.scale_change_detector.borrow_mut() = None;
         *self.0.unload_event_handle.borrow_mut() = None;
+        *self.0.visibility_change_event_handle.borrow_mut() = None;
+        *self.0.system_preferences_change_handle.borrow_mut() = None;
         // Dropping the `Runner` drops the event handler closure, which will in
         // turn drop all `Window`s moved into the closure.
         *self.0.runner.borrow_mut() = RunnerEnum::Destroyed;
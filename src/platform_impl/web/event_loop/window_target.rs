@@ -1,11 +1,17 @@
-use super::{super::monitor, backend, device, proxy::Proxy, runner, window};
+use super::{super::monitor, super::KeyEventExtra, backend, device, proxy::Proxy, runner, window};
+use crate::device::DeviceHandle as RootDeviceHandle;
 use crate::dpi::{PhysicalSize, Size};
+use crate::error::NotSupportedError;
 use crate::event::{
-    DeviceEvent, DeviceId, ElementState, Event, KeyboardInput, TouchPhase, WindowEvent,
+    DeviceEvent, DeviceId, ElementState, Event, KeyboardInput, ModifiersState, ScrollPhase,
+    WindowEvent,
 };
-use crate::event_loop::ControlFlow;
+use crate::event_loop::{ControlFlow, DeviceEvents, PowerState, SystemPreferences};
+use crate::keyboard::{KeyCode, SmolStr};
 use crate::monitor::MonitorHandle as RootMH;
+use crate::platform_impl::click::ClickCounter;
 use crate::window::{Theme, WindowId};
+use instant::Instant;
 use std::cell::RefCell;
 use std::clone::Clone;
 use std::collections::{vec_deque::IntoIter as VecDequeIter, VecDeque};
@@ -30,11 +36,25 @@ impl<T> WindowTarget<T> {
         }
     }
 
+    #[cfg(feature = "rwh_05")]
+    pub fn raw_display_handle_rwh_05(&self) -> rwh_05::RawDisplayHandle {
+        rwh_05::RawDisplayHandle::Web(rwh_05::WebDisplayHandle::empty())
+    }
+
+    #[cfg(feature = "rwh_06")]
+    pub fn raw_display_handle_rwh_06(
+        &self,
+    ) -> Result<rwh_06::RawDisplayHandle, rwh_06::HandleError> {
+        Ok(rwh_06::RawDisplayHandle::Web(
+            rwh_06::WebDisplayHandle::new(),
+        ))
+    }
+
     pub fn proxy(&self) -> Proxy<T> {
         Proxy::new(self.runner.clone())
     }
 
-    pub fn run(&self, event_handler: Box<dyn FnMut(Event<'_, T>, &mut ControlFlow)>) {
+    pub fn run(&self, event_handler: Box<dyn FnMut(Event<T>, &mut ControlFlow)>) {
         self.runner.set_listener(event_handler);
         let runner = self.runner.clone();
         self.runner.set_on_scale_change(move |arg| {
@@ -68,17 +88,20 @@ impl<T> WindowTarget<T> {
         });
 
         let runner = self.runner.clone();
-        canvas.on_keyboard_press(move |scancode, virtual_keycode, modifiers| {
+        canvas.on_keyboard_press(move |physical_key, logical_key, repeat, modifiers| {
             #[allow(deprecated)]
             runner.send_event(Event::WindowEvent {
                 window_id: WindowId(id),
                 event: WindowEvent::KeyboardInput {
                     device_id: DeviceId(unsafe { device::Id::dummy() }),
                     input: KeyboardInput {
-                        scancode,
+                        physical_key,
                         state: ElementState::Pressed,
-                        virtual_keycode,
+                        text: logical_key.to_text().map(SmolStr::new),
+                        repeat,
+                        logical_key,
                         modifiers,
+                        platform_specific: KeyEventExtra,
                     },
                     is_synthetic: false,
                 },
@@ -86,17 +109,20 @@ impl<T> WindowTarget<T> {
         });
 
         let runner = self.runner.clone();
-        canvas.on_keyboard_release(move |scancode, virtual_keycode, modifiers| {
+        canvas.on_keyboard_release(move |physical_key, logical_key, modifiers| {
             #[allow(deprecated)]
             runner.send_event(Event::WindowEvent {
                 window_id: WindowId(id),
                 event: WindowEvent::KeyboardInput {
                     device_id: DeviceId(unsafe { device::Id::dummy() }),
                     input: KeyboardInput {
-                        scancode,
+                        physical_key,
                         state: ElementState::Released,
-                        virtual_keycode,
+                        logical_key,
+                        text: None,
+                        repeat: false,
                         modifiers,
+                        platform_specific: KeyEventExtra,
                     },
                     is_synthetic: false,
                 },
@@ -149,8 +175,18 @@ impl<T> WindowTarget<T> {
             });
         });
 
+        let click_counter = Rc::new(RefCell::new(ClickCounter::default()));
+
         let runner = self.runner.clone();
+        let press_click_counter = click_counter.clone();
         canvas.on_mouse_press(move |pointer_id, position, button, modifiers| {
+            let click_count = press_click_counter.borrow_mut().press(
+                WindowId(id),
+                button,
+                position,
+                Default::default(),
+            );
+
             // A mouse down event may come in without any prior CursorMoved events,
             // therefore we should send a CursorMoved event to make sure that the
             // user code has the correct cursor position.
@@ -169,6 +205,7 @@ impl<T> WindowTarget<T> {
                         device_id: DeviceId(device::Id(pointer_id)),
                         state: ElementState::Pressed,
                         button,
+                        click_count,
                         modifiers,
                     },
                 })),
@@ -177,12 +214,15 @@ impl<T> WindowTarget<T> {
 
         let runner = self.runner.clone();
         canvas.on_mouse_release(move |pointer_id, button, modifiers| {
+            let click_count = click_counter.borrow().release(button);
+
             runner.send_event(Event::WindowEvent {
                 window_id: WindowId(id),
                 event: WindowEvent::MouseInput {
                     device_id: DeviceId(device::Id(pointer_id)),
                     state: ElementState::Released,
                     button,
+                    click_count,
                     modifiers,
                 },
             });
@@ -195,7 +235,7 @@ impl<T> WindowTarget<T> {
                 event: WindowEvent::MouseWheel {
                     device_id: DeviceId(device::Id(pointer_id)),
                     delta,
-                    phase: TouchPhase::Moved,
+                    phase: ScrollPhase::Moved,
                     modifiers,
                 },
             });
@@ -254,4 +294,37 @@ impl<T> WindowTarget<T> {
             inner: monitor::Handle,
         })
     }
+
+    pub fn devices(&self) -> Vec<RootDeviceHandle> {
+        Vec::new()
+    }
+
+    pub fn listen_device_events(&self, _allowed: DeviceEvents) {}
+
+    pub fn keyboard_layout(&self) -> Option<String> {
+        None
+    }
+
+    pub fn key_code_to_text(&self, _code: KeyCode, _modifiers: ModifiersState) -> Option<String> {
+        None
+    }
+
+    pub fn system_preferences(&self) -> SystemPreferences {
+        backend::system_preferences()
+    }
+
+    pub fn power_state(&self) -> PowerState {
+        PowerState::default()
+    }
+
+    pub fn event_timestamp(&self) -> Option<Instant> {
+        None
+    }
+
+    pub fn run_nested<F>(&self, _event_handler: F) -> Result<i32, NotSupportedError>
+    where
+        F: FnMut(Event<T>, &mut ControlFlow),
+    {
+        Err(NotSupportedError::new())
+    }
 }
@@ -40,6 +40,9 @@ pub use self::window::{
 
 pub(crate) use crate::icon::NoIcon as PlatformIcon;
 
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct KeyEventExtra;
+
 #[derive(Clone, Copy)]
 pub(crate) struct ScaleChangeArgs {
     old_scale: f64,
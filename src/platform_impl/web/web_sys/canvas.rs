@@ -1,12 +1,15 @@
 use super::event;
 use super::event_handle::EventListenerHandle;
 use super::media_query_handle::MediaQueryListHandle;
-use crate::dpi::{LogicalPosition, PhysicalPosition, PhysicalSize};
+use super::resize_observer::ResizeObserverHandle;
+use crate::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
 use crate::error::OsError as RootOE;
-use crate::event::{ModifiersState, MouseButton, MouseScrollDelta, ScanCode, VirtualKeyCode};
+use crate::event::{ModifiersState, MouseButton, MouseScrollDelta};
+use crate::keyboard::{Key, PhysicalKey};
+use crate::platform::web::PreventDefault;
 use crate::platform_impl::{OsError, PlatformSpecificWindowBuilderAttributes};
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 
 use wasm_bindgen::{closure::Closure, JsCast};
@@ -28,7 +31,9 @@ pub struct Canvas {
     on_received_character: Option<EventListenerHandle<dyn FnMut(KeyboardEvent)>>,
     on_mouse_wheel: Option<EventListenerHandle<dyn FnMut(WheelEvent)>>,
     on_fullscreen_change: Option<EventListenerHandle<dyn FnMut(Event)>>,
+    on_context_menu: Option<EventListenerHandle<dyn FnMut(MouseEvent)>>,
     on_dark_mode: Option<MediaQueryListHandle>,
+    on_resize: Option<ResizeObserverHandle>,
     mouse_state: MouseState,
 }
 
@@ -36,13 +41,14 @@ struct Common {
     /// Note: resizing the HTMLCanvasElement should go through `backend::set_canvas_size` to ensure the DPI factor is maintained.
     raw: HtmlCanvasElement,
     wants_fullscreen: Rc<RefCell<bool>>,
+    prevent_default: Rc<Cell<PreventDefault>>,
 }
 
 impl Canvas {
     pub fn create(attr: PlatformSpecificWindowBuilderAttributes) -> Result<Self, RootOE> {
-        let canvas = match attr.canvas {
-            Some(canvas) => canvas,
-            None => {
+        let canvas = match (attr.canvas, attr.canvas_id) {
+            (Some(canvas), _) => canvas,
+            (None, canvas_id) => {
                 let window = web_sys::window()
                     .ok_or(os_error!(OsError("Failed to obtain window".to_owned())))?;
 
@@ -50,10 +56,20 @@ impl Canvas {
                     .document()
                     .ok_or(os_error!(OsError("Failed to obtain document".to_owned())))?;
 
-                document
-                    .create_element("canvas")
-                    .map_err(|_| os_error!(OsError("Failed to create canvas element".to_owned())))?
-                    .unchecked_into()
+                match canvas_id {
+                    Some(id) => document
+                        .get_element_by_id(&id)
+                        .and_then(|element| element.dyn_into::<HtmlCanvasElement>().ok())
+                        .ok_or_else(|| {
+                            os_error!(OsError(format!("No canvas element with id \"{}\"", id)))
+                        })?,
+                    None => document
+                        .create_element("canvas")
+                        .map_err(|_| {
+                            os_error!(OsError("Failed to create canvas element".to_owned()))
+                        })?
+                        .unchecked_into(),
+                }
             }
         };
 
@@ -72,11 +88,21 @@ impl Canvas {
             MouseState::NoPointerEvent(mouse_handler::MouseHandler::new())
         };
 
+        let common = Common {
+            raw: canvas,
+            wants_fullscreen: Rc::new(RefCell::new(false)),
+            prevent_default: Rc::new(Cell::new(attr.prevent_default)),
+        };
+
+        let prevent_default = common.prevent_default.clone();
+        let on_context_menu = Some(common.add_event("contextmenu", move |event: MouseEvent| {
+            if prevent_default.get().contains(PreventDefault::CONTEXT_MENU) {
+                event.prevent_default();
+            }
+        }));
+
         Ok(Canvas {
-            common: Common {
-                raw: canvas,
-                wants_fullscreen: Rc::new(RefCell::new(false)),
-            },
+            common,
             on_blur: None,
             on_focus: None,
             on_keyboard_release: None,
@@ -84,11 +110,25 @@ impl Canvas {
             on_received_character: None,
             on_mouse_wheel: None,
             on_fullscreen_change: None,
+            on_context_menu,
             on_dark_mode: None,
+            on_resize: None,
             mouse_state,
         })
     }
 
+    pub fn set_prevent_default(&self, prevent_default: PreventDefault) {
+        self.common.prevent_default.set(prevent_default);
+    }
+
+    pub fn prevent_default(&self) -> PreventDefault {
+        self.common.prevent_default.get()
+    }
+
+    pub fn follows_parent_size(&self) -> bool {
+        self.on_resize.is_some()
+    }
+
     pub fn set_cursor_grab(&self, grab: bool) -> Result<(), RootOE> {
         if grab {
             self.raw().request_pointer_lock();
@@ -130,6 +170,38 @@ impl Canvas {
         &self.common.raw
     }
 
+    /// Stretches the canvas over its parent element and calls `handler` with the new size of the
+    /// drawing buffer every time the size of the canvas on the page changes.
+    pub fn on_parent_resize<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(PhysicalSize<u32>),
+    {
+        let canvas = self.common.raw.clone();
+        super::set_canvas_style_property(&canvas, "width", "100%");
+        super::set_canvas_style_property(&canvas, "height", "100%");
+
+        let closure = Closure::wrap(Box::new({
+            let canvas = canvas.clone();
+            move || {
+                let logical_size = LogicalSize {
+                    width: canvas.client_width() as f64,
+                    height: canvas.client_height() as f64,
+                };
+                let size = logical_size.to_physical::<u32>(super::scale_factor());
+                if size.width != canvas.width() || size.height != canvas.height() {
+                    canvas.set_width(size.width);
+                    canvas.set_height(size.height);
+                    handler(size);
+                }
+            }
+        }) as Box<dyn FnMut()>);
+        self.on_resize = ResizeObserverHandle::new(&canvas, closure);
+    }
+
+    pub fn remove_parent_resize(&mut self) {
+        self.on_resize = None;
+    }
+
     pub fn on_blur<F>(&mut self, mut handler: F)
     where
         F: 'static + FnMut(),
@@ -150,15 +222,18 @@ impl Canvas {
 
     pub fn on_keyboard_release<F>(&mut self, mut handler: F)
     where
-        F: 'static + FnMut(ScanCode, Option<VirtualKeyCode>, ModifiersState),
+        F: 'static + FnMut(PhysicalKey, Key, ModifiersState),
     {
+        let prevent_default = self.common.prevent_default.clone();
         self.on_keyboard_release = Some(self.common.add_user_event(
             "keyup",
             move |event: KeyboardEvent| {
-                event.prevent_default();
+                if prevent_default.get().contains(PreventDefault::KEYBOARD) {
+                    event.prevent_default();
+                }
                 handler(
-                    event::scan_code(&event),
-                    event::virtual_key_code(&event),
+                    event::physical_key(&event),
+                    event::logical_key(&event),
                     event::keyboard_modifiers(&event),
                 );
             },
@@ -167,8 +242,9 @@ impl Canvas {
 
     pub fn on_keyboard_press<F>(&mut self, mut handler: F)
     where
-        F: 'static + FnMut(ScanCode, Option<VirtualKeyCode>, ModifiersState),
+        F: 'static + FnMut(PhysicalKey, Key, bool, ModifiersState),
     {
+        let prevent_default = self.common.prevent_default.clone();
         self.on_keyboard_press = Some(self.common.add_user_event(
             "keydown",
             move |event: KeyboardEvent| {
@@ -180,12 +256,15 @@ impl Canvas {
                 let is_key_string = event_key.len() == 1 || !event_key.is_ascii();
                 let is_shortcut_modifiers =
                     (event.ctrl_key() || event.alt_key()) && !event.get_modifier_state("AltGr");
-                if !is_key_string || is_shortcut_modifiers {
+                if prevent_default.get().contains(PreventDefault::KEYBOARD)
+                    && (!is_key_string || is_shortcut_modifiers)
+                {
                     event.prevent_default();
                 }
                 handler(
-                    event::scan_code(&event),
-                    event::virtual_key_code(&event),
+                    event::physical_key(&event),
+                    event::logical_key(&event),
+                    event.repeat(),
                     event::keyboard_modifiers(&event),
                 );
             },
@@ -201,11 +280,14 @@ impl Canvas {
         // The `keypress` event is deprecated, but there does not seem to be a
         // viable/compatible alternative as of now. `beforeinput` is still widely
         // unsupported.
+        let prevent_default = self.common.prevent_default.clone();
         self.on_received_character = Some(self.common.add_user_event(
             "keypress",
             move |event: KeyboardEvent| {
                 // Supress further handling to stop keys like the space key from scrolling the page.
-                event.prevent_default();
+                if prevent_default.get().contains(PreventDefault::KEYBOARD) {
+                    event.prevent_default();
+                }
                 handler(event::codepoint(&event));
             },
         ));
@@ -265,8 +347,11 @@ impl Canvas {
     where
         F: 'static + FnMut(i32, MouseScrollDelta, ModifiersState),
     {
+        let prevent_default = self.common.prevent_default.clone();
         self.on_mouse_wheel = Some(self.common.add_event("wheel", move |event: WheelEvent| {
-            event.prevent_default();
+            if prevent_default.get().contains(PreventDefault::WHEEL) {
+                event.prevent_default();
+            }
             if let Some(delta) = event::mouse_scroll_delta(&event) {
                 handler(0, delta, event::mouse_modifiers(&event));
             }
@@ -311,7 +396,9 @@ impl Canvas {
         self.on_received_character = None;
         self.on_mouse_wheel = None;
         self.on_fullscreen_change = None;
+        self.on_context_menu = None;
         self.on_dark_mode = None;
+        self.on_resize = None;
         match &mut self.mouse_state {
             MouseState::HasPointerEvent(h) => h.remove_listeners(),
             MouseState::NoPointerEvent(h) => h.remove_listeners(),
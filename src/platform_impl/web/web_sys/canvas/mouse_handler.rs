@@ -2,6 +2,7 @@ use super::event;
 use super::EventListenerHandle;
 use crate::dpi::PhysicalPosition;
 use crate::event::{ModifiersState, MouseButton};
+use crate::platform::web::PreventDefault;
 
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -78,6 +79,7 @@ impl MouseHandler {
         let on_mouse_leave_handler = self.on_mouse_leave_handler.clone();
         let mouse_capture_state = self.mouse_capture_state.clone();
         let canvas = canvas_common.raw.clone();
+        let prevent_default = canvas_common.prevent_default.clone();
         self.on_mouse_release = Some(canvas_common.add_window_mouse_event(
             "mouseup",
             move |event: MouseEvent| {
@@ -97,6 +99,9 @@ impl MouseHandler {
                     MouseCaptureState::Captured => {}
                 }
                 event.stop_propagation();
+                if prevent_default.get().contains(PreventDefault::POINTER) {
+                    event.prevent_default();
+                }
                 handler(
                     0,
                     event::mouse_button(&event),
@@ -128,6 +133,7 @@ impl MouseHandler {
     {
         let mouse_capture_state = self.mouse_capture_state.clone();
         let canvas = canvas_common.raw.clone();
+        let prevent_default = canvas_common.prevent_default.clone();
         self.on_mouse_press = Some(canvas_common.add_window_mouse_event(
             "mousedown",
             move |event: MouseEvent| {
@@ -135,9 +141,9 @@ impl MouseHandler {
                 let mut mouse_capture_state = mouse_capture_state.borrow_mut();
                 match &*mouse_capture_state {
                     MouseCaptureState::NotCaptured
-                        if event
-                            .target()
-                            .map_or(false, |target| target != EventTarget::from(canvas)) =>
+                        if event.target().map_or(false, |target| {
+                            target != EventTarget::from(canvas.clone())
+                        }) =>
                     {
                         // The target isn't our canvas which means the
                         // mouse is pressed outside of it.
@@ -149,9 +155,14 @@ impl MouseHandler {
                 }
                 *mouse_capture_state = MouseCaptureState::Captured;
                 event.stop_propagation();
+                if prevent_default.get().contains(PreventDefault::POINTER) {
+                    event.prevent_default();
+                    // Preventing the default action also prevents the canvas from being focused.
+                    let _ = canvas.focus();
+                }
                 handler(
                     0,
-                    event::mouse_position(&event).to_physical(super::super::scale_factor()),
+                    super::super::to_canvas_physical(&canvas, event::mouse_position(&event)),
                     event::mouse_button(&event),
                     event::mouse_modifiers(&event),
                 );
@@ -165,6 +176,7 @@ impl MouseHandler {
     {
         let mouse_capture_state = self.mouse_capture_state.clone();
         let canvas = canvas_common.raw.clone();
+        let prevent_default = canvas_common.prevent_default.clone();
         self.on_mouse_move = Some(canvas_common.add_window_mouse_event(
             "mousemove",
             move |event: MouseEvent| {
@@ -184,6 +196,9 @@ impl MouseHandler {
                         if *mouse_capture_state == MouseCaptureState::Captured {
                             event.stop_propagation();
                         }
+                        if prevent_default.get().contains(PreventDefault::POINTER) {
+                            event.prevent_default();
+                        }
                         let mouse_pos = if is_over_canvas {
                             event::mouse_position(&event)
                         } else {
@@ -194,8 +209,8 @@ impl MouseHandler {
                         let mouse_delta = event::mouse_delta(&event);
                         handler(
                             0,
-                            mouse_pos.to_physical(super::super::scale_factor()),
-                            mouse_delta.to_physical(super::super::scale_factor()),
+                            super::super::to_canvas_physical(&canvas, mouse_pos),
+                            super::super::to_canvas_physical(&canvas, mouse_delta),
                             event::mouse_modifiers(&event),
                         );
                     }
@@ -2,6 +2,7 @@ use super::event;
 use super::EventListenerHandle;
 use crate::dpi::PhysicalPosition;
 use crate::event::{ModifiersState, MouseButton};
+use crate::platform::web::PreventDefault;
 
 use web_sys::PointerEvent;
 
@@ -53,9 +54,13 @@ impl PointerHandler {
     where
         F: 'static + FnMut(i32, MouseButton, ModifiersState),
     {
+        let prevent_default = canvas_common.prevent_default.clone();
         self.on_pointer_release = Some(canvas_common.add_user_event(
             "pointerup",
             move |event: PointerEvent| {
+                if prevent_default.get().contains(PreventDefault::POINTER) {
+                    event.prevent_default();
+                }
                 handler(
                     event.pointer_id(),
                     event::mouse_button(&event),
@@ -70,12 +75,18 @@ impl PointerHandler {
         F: 'static + FnMut(i32, PhysicalPosition<f64>, MouseButton, ModifiersState),
     {
         let canvas = canvas_common.raw.clone();
+        let prevent_default = canvas_common.prevent_default.clone();
         self.on_pointer_press = Some(canvas_common.add_user_event(
             "pointerdown",
             move |event: PointerEvent| {
+                if prevent_default.get().contains(PreventDefault::POINTER) {
+                    event.prevent_default();
+                    // Preventing the default action also prevents the canvas from being focused.
+                    let _ = canvas.focus();
+                }
                 handler(
                     event.pointer_id(),
-                    event::mouse_position(&event).to_physical(super::super::scale_factor()),
+                    super::super::to_canvas_physical(&canvas, event::mouse_position(&event)),
                     event::mouse_button(&event),
                     event::mouse_modifiers(&event),
                 );
@@ -92,13 +103,18 @@ impl PointerHandler {
     where
         F: 'static + FnMut(i32, PhysicalPosition<f64>, PhysicalPosition<f64>, ModifiersState),
     {
+        let canvas = canvas_common.raw.clone();
+        let prevent_default = canvas_common.prevent_default.clone();
         self.on_cursor_move = Some(canvas_common.add_event(
             "pointermove",
             move |event: PointerEvent| {
+                if prevent_default.get().contains(PreventDefault::POINTER) {
+                    event.prevent_default();
+                }
                 handler(
                     event.pointer_id(),
-                    event::mouse_position(&event).to_physical(super::super::scale_factor()),
-                    event::mouse_delta(&event).to_physical(super::super::scale_factor()),
+                    super::super::to_canvas_physical(&canvas, event::mouse_position(&event)),
+                    super::super::to_canvas_physical(&canvas, event::mouse_delta(&event)),
                     event::mouse_modifiers(&event),
                 );
             },
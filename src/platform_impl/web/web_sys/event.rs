@@ -1,5 +1,6 @@
 use crate::dpi::LogicalPosition;
-use crate::event::{ModifiersState, MouseButton, MouseScrollDelta, ScanCode, VirtualKeyCode};
+use crate::event::{ModifiersState, MouseButton, MouseScrollDelta};
+use crate::keyboard::{Key, KeyCode, NativeKey, NativeKeyCode, PhysicalKey};
 
 use std::convert::TryInto;
 use web_sys::{HtmlCanvasElement, KeyboardEvent, MouseEvent, WheelEvent};
@@ -9,6 +10,8 @@ pub fn mouse_button(event: &MouseEvent) -> MouseButton {
         0 => MouseButton::Left,
         1 => MouseButton::Middle,
         2 => MouseButton::Right,
+        3 => MouseButton::Back,
+        4 => MouseButton::Forward,
         i => MouseButton::Other((i - 3).try_into().expect("very large mouse button value")),
     }
 }
@@ -61,174 +64,351 @@ pub fn mouse_scroll_delta(event: &WheelEvent) -> Option<MouseScrollDelta> {
     }
 }
 
-pub fn scan_code(event: &KeyboardEvent) -> ScanCode {
-    match event.key_code() {
-        0 => event.char_code(),
-        i => i,
-    }
+pub fn physical_key(event: &KeyboardEvent) -> PhysicalKey {
+    // The variants of `KeyCode` are named after the values of `KeyboardEvent.code`.
+    PhysicalKey::Code(match &event.code()[..] {
+        "Backquote" => KeyCode::Backquote,
+        "Backslash" => KeyCode::Backslash,
+        "BracketLeft" => KeyCode::BracketLeft,
+        "BracketRight" => KeyCode::BracketRight,
+        "Comma" => KeyCode::Comma,
+        "Digit0" => KeyCode::Digit0,
+        "Digit1" => KeyCode::Digit1,
+        "Digit2" => KeyCode::Digit2,
+        "Digit3" => KeyCode::Digit3,
+        "Digit4" => KeyCode::Digit4,
+        "Digit5" => KeyCode::Digit5,
+        "Digit6" => KeyCode::Digit6,
+        "Digit7" => KeyCode::Digit7,
+        "Digit8" => KeyCode::Digit8,
+        "Digit9" => KeyCode::Digit9,
+        "Equal" => KeyCode::Equal,
+        "IntlBackslash" => KeyCode::IntlBackslash,
+        "IntlRo" => KeyCode::IntlRo,
+        "IntlYen" => KeyCode::IntlYen,
+        "KeyA" => KeyCode::KeyA,
+        "KeyB" => KeyCode::KeyB,
+        "KeyC" => KeyCode::KeyC,
+        "KeyD" => KeyCode::KeyD,
+        "KeyE" => KeyCode::KeyE,
+        "KeyF" => KeyCode::KeyF,
+        "KeyG" => KeyCode::KeyG,
+        "KeyH" => KeyCode::KeyH,
+        "KeyI" => KeyCode::KeyI,
+        "KeyJ" => KeyCode::KeyJ,
+        "KeyK" => KeyCode::KeyK,
+        "KeyL" => KeyCode::KeyL,
+        "KeyM" => KeyCode::KeyM,
+        "KeyN" => KeyCode::KeyN,
+        "KeyO" => KeyCode::KeyO,
+        "KeyP" => KeyCode::KeyP,
+        "KeyQ" => KeyCode::KeyQ,
+        "KeyR" => KeyCode::KeyR,
+        "KeyS" => KeyCode::KeyS,
+        "KeyT" => KeyCode::KeyT,
+        "KeyU" => KeyCode::KeyU,
+        "KeyV" => KeyCode::KeyV,
+        "KeyW" => KeyCode::KeyW,
+        "KeyX" => KeyCode::KeyX,
+        "KeyY" => KeyCode::KeyY,
+        "KeyZ" => KeyCode::KeyZ,
+        "Minus" => KeyCode::Minus,
+        "Period" => KeyCode::Period,
+        "Quote" => KeyCode::Quote,
+        "Semicolon" => KeyCode::Semicolon,
+        "Slash" => KeyCode::Slash,
+        "AltLeft" => KeyCode::AltLeft,
+        "AltRight" => KeyCode::AltRight,
+        "Backspace" => KeyCode::Backspace,
+        "CapsLock" => KeyCode::CapsLock,
+        "ContextMenu" => KeyCode::ContextMenu,
+        "ControlLeft" => KeyCode::ControlLeft,
+        "ControlRight" => KeyCode::ControlRight,
+        "Enter" => KeyCode::Enter,
+        "MetaLeft" | "OSLeft" => KeyCode::SuperLeft,
+        "MetaRight" | "OSRight" => KeyCode::SuperRight,
+        "ShiftLeft" => KeyCode::ShiftLeft,
+        "ShiftRight" => KeyCode::ShiftRight,
+        "Space" => KeyCode::Space,
+        "Tab" => KeyCode::Tab,
+        "Convert" => KeyCode::Convert,
+        "KanaMode" => KeyCode::KanaMode,
+        "Lang1" => KeyCode::Lang1,
+        "Lang2" => KeyCode::Lang2,
+        "Lang3" => KeyCode::Lang3,
+        "Lang4" => KeyCode::Lang4,
+        "Lang5" => KeyCode::Lang5,
+        "NonConvert" => KeyCode::NonConvert,
+        "Delete" => KeyCode::Delete,
+        "End" => KeyCode::End,
+        "Help" => KeyCode::Help,
+        "Home" => KeyCode::Home,
+        "Insert" => KeyCode::Insert,
+        "PageDown" => KeyCode::PageDown,
+        "PageUp" => KeyCode::PageUp,
+        "ArrowDown" => KeyCode::ArrowDown,
+        "ArrowLeft" => KeyCode::ArrowLeft,
+        "ArrowRight" => KeyCode::ArrowRight,
+        "ArrowUp" => KeyCode::ArrowUp,
+        "NumLock" => KeyCode::NumLock,
+        "Numpad0" => KeyCode::Numpad0,
+        "Numpad1" => KeyCode::Numpad1,
+        "Numpad2" => KeyCode::Numpad2,
+        "Numpad3" => KeyCode::Numpad3,
+        "Numpad4" => KeyCode::Numpad4,
+        "Numpad5" => KeyCode::Numpad5,
+        "Numpad6" => KeyCode::Numpad6,
+        "Numpad7" => KeyCode::Numpad7,
+        "Numpad8" => KeyCode::Numpad8,
+        "Numpad9" => KeyCode::Numpad9,
+        "NumpadAdd" => KeyCode::NumpadAdd,
+        "NumpadBackspace" => KeyCode::NumpadBackspace,
+        "NumpadClear" => KeyCode::NumpadClear,
+        "NumpadClearEntry" => KeyCode::NumpadClearEntry,
+        "NumpadComma" => KeyCode::NumpadComma,
+        "NumpadDecimal" => KeyCode::NumpadDecimal,
+        "NumpadDivide" => KeyCode::NumpadDivide,
+        "NumpadEnter" => KeyCode::NumpadEnter,
+        "NumpadEqual" => KeyCode::NumpadEqual,
+        "NumpadMultiply" => KeyCode::NumpadMultiply,
+        "NumpadParenLeft" => KeyCode::NumpadParenLeft,
+        "NumpadParenRight" => KeyCode::NumpadParenRight,
+        "NumpadSubtract" => KeyCode::NumpadSubtract,
+        "Escape" => KeyCode::Escape,
+        "Fn" => KeyCode::Fn,
+        "PrintScreen" => KeyCode::PrintScreen,
+        "ScrollLock" => KeyCode::ScrollLock,
+        "Pause" => KeyCode::Pause,
+        "BrowserBack" => KeyCode::BrowserBack,
+        "BrowserFavorites" => KeyCode::BrowserFavorites,
+        "BrowserForward" => KeyCode::BrowserForward,
+        "BrowserHome" => KeyCode::BrowserHome,
+        "BrowserRefresh" => KeyCode::BrowserRefresh,
+        "BrowserSearch" => KeyCode::BrowserSearch,
+        "BrowserStop" => KeyCode::BrowserStop,
+        "Eject" => KeyCode::Eject,
+        "LaunchApp1" => KeyCode::LaunchApp1,
+        "LaunchApp2" => KeyCode::LaunchApp2,
+        "LaunchMail" => KeyCode::LaunchMail,
+        "MediaPlayPause" => KeyCode::MediaPlayPause,
+        "MediaSelect" => KeyCode::MediaSelect,
+        "MediaStop" => KeyCode::MediaStop,
+        "MediaTrackNext" => KeyCode::MediaTrackNext,
+        "MediaTrackPrevious" => KeyCode::MediaTrackPrevious,
+        "Power" => KeyCode::Power,
+        "Sleep" => KeyCode::Sleep,
+        "AudioVolumeDown" => KeyCode::AudioVolumeDown,
+        "AudioVolumeMute" => KeyCode::AudioVolumeMute,
+        "AudioVolumeUp" => KeyCode::AudioVolumeUp,
+        "WakeUp" => KeyCode::WakeUp,
+        "Again" => KeyCode::Again,
+        "Copy" => KeyCode::Copy,
+        "Cut" => KeyCode::Cut,
+        "Find" => KeyCode::Find,
+        "Open" => KeyCode::Open,
+        "Paste" => KeyCode::Paste,
+        "Props" => KeyCode::Props,
+        "Select" => KeyCode::Select,
+        "Undo" => KeyCode::Undo,
+        "Hiragana" => KeyCode::Hiragana,
+        "Katakana" => KeyCode::Katakana,
+        "F1" => KeyCode::F1,
+        "F2" => KeyCode::F2,
+        "F3" => KeyCode::F3,
+        "F4" => KeyCode::F4,
+        "F5" => KeyCode::F5,
+        "F6" => KeyCode::F6,
+        "F7" => KeyCode::F7,
+        "F8" => KeyCode::F8,
+        "F9" => KeyCode::F9,
+        "F10" => KeyCode::F10,
+        "F11" => KeyCode::F11,
+        "F12" => KeyCode::F12,
+        "F13" => KeyCode::F13,
+        "F14" => KeyCode::F14,
+        "F15" => KeyCode::F15,
+        "F16" => KeyCode::F16,
+        "F17" => KeyCode::F17,
+        "F18" => KeyCode::F18,
+        "F19" => KeyCode::F19,
+        "F20" => KeyCode::F20,
+        "F21" => KeyCode::F21,
+        "F22" => KeyCode::F22,
+        "F23" => KeyCode::F23,
+        "F24" => KeyCode::F24,
+        _ => return PhysicalKey::Unidentified(NativeKeyCode::Unidentified),
+    })
 }
 
-pub fn virtual_key_code(event: &KeyboardEvent) -> Option<VirtualKeyCode> {
-    Some(match &event.code()[..] {
-        "Digit1" => VirtualKeyCode::Key1,
-        "Digit2" => VirtualKeyCode::Key2,
-        "Digit3" => VirtualKeyCode::Key3,
-        "Digit4" => VirtualKeyCode::Key4,
-        "Digit5" => VirtualKeyCode::Key5,
-        "Digit6" => VirtualKeyCode::Key6,
-        "Digit7" => VirtualKeyCode::Key7,
-        "Digit8" => VirtualKeyCode::Key8,
-        "Digit9" => VirtualKeyCode::Key9,
-        "Digit0" => VirtualKeyCode::Key0,
-        "KeyA" => VirtualKeyCode::A,
-        "KeyB" => VirtualKeyCode::B,
-        "KeyC" => VirtualKeyCode::C,
-        "KeyD" => VirtualKeyCode::D,
-        "KeyE" => VirtualKeyCode::E,
-        "KeyF" => VirtualKeyCode::F,
-        "KeyG" => VirtualKeyCode::G,
-        "KeyH" => VirtualKeyCode::H,
-        "KeyI" => VirtualKeyCode::I,
-        "KeyJ" => VirtualKeyCode::J,
-        "KeyK" => VirtualKeyCode::K,
-        "KeyL" => VirtualKeyCode::L,
-        "KeyM" => VirtualKeyCode::M,
-        "KeyN" => VirtualKeyCode::N,
-        "KeyO" => VirtualKeyCode::O,
-        "KeyP" => VirtualKeyCode::P,
-        "KeyQ" => VirtualKeyCode::Q,
-        "KeyR" => VirtualKeyCode::R,
-        "KeyS" => VirtualKeyCode::S,
-        "KeyT" => VirtualKeyCode::T,
-        "KeyU" => VirtualKeyCode::U,
-        "KeyV" => VirtualKeyCode::V,
-        "KeyW" => VirtualKeyCode::W,
-        "KeyX" => VirtualKeyCode::X,
-        "KeyY" => VirtualKeyCode::Y,
-        "KeyZ" => VirtualKeyCode::Z,
-        "Escape" => VirtualKeyCode::Escape,
-        "F1" => VirtualKeyCode::F1,
-        "F2" => VirtualKeyCode::F2,
-        "F3" => VirtualKeyCode::F3,
-        "F4" => VirtualKeyCode::F4,
-        "F5" => VirtualKeyCode::F5,
-        "F6" => VirtualKeyCode::F6,
-        "F7" => VirtualKeyCode::F7,
-        "F8" => VirtualKeyCode::F8,
-        "F9" => VirtualKeyCode::F9,
-        "F10" => VirtualKeyCode::F10,
-        "F11" => VirtualKeyCode::F11,
-        "F12" => VirtualKeyCode::F12,
-        "F13" => VirtualKeyCode::F13,
-        "F14" => VirtualKeyCode::F14,
-        "F15" => VirtualKeyCode::F15,
-        "F16" => VirtualKeyCode::F16,
-        "F17" => VirtualKeyCode::F17,
-        "F18" => VirtualKeyCode::F18,
-        "F19" => VirtualKeyCode::F19,
-        "F20" => VirtualKeyCode::F20,
-        "F21" => VirtualKeyCode::F21,
-        "F22" => VirtualKeyCode::F22,
-        "F23" => VirtualKeyCode::F23,
-        "F24" => VirtualKeyCode::F24,
-        "PrintScreen" => VirtualKeyCode::Snapshot,
-        "ScrollLock" => VirtualKeyCode::Scroll,
-        "Pause" => VirtualKeyCode::Pause,
-        "Insert" => VirtualKeyCode::Insert,
-        "Home" => VirtualKeyCode::Home,
-        "Delete" => VirtualKeyCode::Delete,
-        "End" => VirtualKeyCode::End,
-        "PageDown" => VirtualKeyCode::PageDown,
-        "PageUp" => VirtualKeyCode::PageUp,
-        "ArrowLeft" => VirtualKeyCode::Left,
-        "ArrowUp" => VirtualKeyCode::Up,
-        "ArrowRight" => VirtualKeyCode::Right,
-        "ArrowDown" => VirtualKeyCode::Down,
-        "Backspace" => VirtualKeyCode::Back,
-        "Enter" => VirtualKeyCode::Return,
-        "Space" => VirtualKeyCode::Space,
-        "Compose" => VirtualKeyCode::Compose,
-        "Caret" => VirtualKeyCode::Caret,
-        "NumLock" => VirtualKeyCode::Numlock,
-        "Numpad0" => VirtualKeyCode::Numpad0,
-        "Numpad1" => VirtualKeyCode::Numpad1,
-        "Numpad2" => VirtualKeyCode::Numpad2,
-        "Numpad3" => VirtualKeyCode::Numpad3,
-        "Numpad4" => VirtualKeyCode::Numpad4,
-        "Numpad5" => VirtualKeyCode::Numpad5,
-        "Numpad6" => VirtualKeyCode::Numpad6,
-        "Numpad7" => VirtualKeyCode::Numpad7,
-        "Numpad8" => VirtualKeyCode::Numpad8,
-        "Numpad9" => VirtualKeyCode::Numpad9,
-        "AbntC1" => VirtualKeyCode::AbntC1,
-        "AbntC2" => VirtualKeyCode::AbntC2,
-        "NumpadAdd" => VirtualKeyCode::NumpadAdd,
-        "Quote" => VirtualKeyCode::Apostrophe,
-        "Apps" => VirtualKeyCode::Apps,
-        "At" => VirtualKeyCode::At,
-        "Ax" => VirtualKeyCode::Ax,
-        "Backslash" => VirtualKeyCode::Backslash,
-        "Calculator" => VirtualKeyCode::Calculator,
-        "Capital" => VirtualKeyCode::Capital,
-        "Semicolon" => VirtualKeyCode::Semicolon,
-        "Comma" => VirtualKeyCode::Comma,
-        "Convert" => VirtualKeyCode::Convert,
-        "NumpadDecimal" => VirtualKeyCode::NumpadDecimal,
-        "NumpadDivide" => VirtualKeyCode::NumpadDivide,
-        "Equal" => VirtualKeyCode::Equals,
-        "Backquote" => VirtualKeyCode::Grave,
-        "Kana" => VirtualKeyCode::Kana,
-        "Kanji" => VirtualKeyCode::Kanji,
-        "AltLeft" => VirtualKeyCode::LAlt,
-        "BracketLeft" => VirtualKeyCode::LBracket,
-        "ControlLeft" => VirtualKeyCode::LControl,
-        "ShiftLeft" => VirtualKeyCode::LShift,
-        "MetaLeft" => VirtualKeyCode::LWin,
-        "Mail" => VirtualKeyCode::Mail,
-        "MediaSelect" => VirtualKeyCode::MediaSelect,
-        "MediaStop" => VirtualKeyCode::MediaStop,
-        "Minus" => VirtualKeyCode::Minus,
-        "NumpadMultiply" => VirtualKeyCode::NumpadMultiply,
-        "Mute" => VirtualKeyCode::Mute,
-        "LaunchMyComputer" => VirtualKeyCode::MyComputer,
-        "NavigateForward" => VirtualKeyCode::NavigateForward,
-        "NavigateBackward" => VirtualKeyCode::NavigateBackward,
-        "NextTrack" => VirtualKeyCode::NextTrack,
-        "NoConvert" => VirtualKeyCode::NoConvert,
-        "NumpadComma" => VirtualKeyCode::NumpadComma,
-        "NumpadEnter" => VirtualKeyCode::NumpadEnter,
-        "NumpadEquals" => VirtualKeyCode::NumpadEquals,
-        "OEM102" => VirtualKeyCode::OEM102,
-        "Period" => VirtualKeyCode::Period,
-        "PlayPause" => VirtualKeyCode::PlayPause,
-        "Power" => VirtualKeyCode::Power,
-        "PrevTrack" => VirtualKeyCode::PrevTrack,
-        "AltRight" => VirtualKeyCode::RAlt,
-        "BracketRight" => VirtualKeyCode::RBracket,
-        "ControlRight" => VirtualKeyCode::RControl,
-        "ShiftRight" => VirtualKeyCode::RShift,
-        "MetaRight" => VirtualKeyCode::RWin,
-        "Slash" => VirtualKeyCode::Slash,
-        "Sleep" => VirtualKeyCode::Sleep,
-        "Stop" => VirtualKeyCode::Stop,
-        "NumpadSubtract" => VirtualKeyCode::NumpadSubtract,
-        "Sysrq" => VirtualKeyCode::Sysrq,
-        "Tab" => VirtualKeyCode::Tab,
-        "Underline" => VirtualKeyCode::Underline,
-        "Unlabeled" => VirtualKeyCode::Unlabeled,
-        "AudioVolumeDown" => VirtualKeyCode::VolumeDown,
-        "AudioVolumeUp" => VirtualKeyCode::VolumeUp,
-        "Wake" => VirtualKeyCode::Wake,
-        "WebBack" => VirtualKeyCode::WebBack,
-        "WebFavorites" => VirtualKeyCode::WebFavorites,
-        "WebForward" => VirtualKeyCode::WebForward,
-        "WebHome" => VirtualKeyCode::WebHome,
-        "WebRefresh" => VirtualKeyCode::WebRefresh,
-        "WebSearch" => VirtualKeyCode::WebSearch,
-        "WebStop" => VirtualKeyCode::WebStop,
-        "Yen" => VirtualKeyCode::Yen,
-        _ => return None,
-    })
+pub fn logical_key(event: &KeyboardEvent) -> Key {
+    let key = event.key();
+    let mut chars = key.chars();
+    // Named keys are made of several ASCII letters, so anything else is a character.
+    if let (Some(ch), None) = (chars.next(), chars.next()) {
+        if ch == ' ' {
+            return Key::Space;
+        }
+        return Key::Character(key.into());
+    }
+
+    // https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/key/Key_Values
+    match &key[..] {
+        "Unidentified" => Key::Unidentified(NativeKey::Unidentified),
+        "Dead" => Key::Dead(None),
+        "Meta" | "OS" => Key::Super,
+        "Alt" => Key::Alt,
+        "AltGraph" => Key::AltGraph,
+        "CapsLock" => Key::CapsLock,
+        "Control" => Key::Control,
+        "Fn" => Key::Fn,
+        "FnLock" => Key::FnLock,
+        "NumLock" => Key::NumLock,
+        "ScrollLock" => Key::ScrollLock,
+        "Shift" => Key::Shift,
+        "Symbol" => Key::Symbol,
+        "SymbolLock" => Key::SymbolLock,
+        "Hyper" => Key::Hyper,
+        "Enter" => Key::Enter,
+        "Tab" => Key::Tab,
+        "Space" => Key::Space,
+        "ArrowDown" => Key::ArrowDown,
+        "ArrowLeft" => Key::ArrowLeft,
+        "ArrowRight" => Key::ArrowRight,
+        "ArrowUp" => Key::ArrowUp,
+        "End" => Key::End,
+        "Home" => Key::Home,
+        "PageDown" => Key::PageDown,
+        "PageUp" => Key::PageUp,
+        "Backspace" => Key::Backspace,
+        "Clear" => Key::Clear,
+        "Copy" => Key::Copy,
+        "CrSel" => Key::CrSel,
+        "Cut" => Key::Cut,
+        "Delete" => Key::Delete,
+        "EraseEof" => Key::EraseEof,
+        "ExSel" => Key::ExSel,
+        "Insert" => Key::Insert,
+        "Paste" => Key::Paste,
+        "Redo" => Key::Redo,
+        "Undo" => Key::Undo,
+        "Accept" => Key::Accept,
+        "Again" => Key::Again,
+        "Attn" => Key::Attn,
+        "Cancel" => Key::Cancel,
+        "ContextMenu" => Key::ContextMenu,
+        "Escape" => Key::Escape,
+        "Execute" => Key::Execute,
+        "Find" => Key::Find,
+        "Help" => Key::Help,
+        "Pause" => Key::Pause,
+        "Play" => Key::Play,
+        "Props" => Key::Props,
+        "Select" => Key::Select,
+        "ZoomIn" => Key::ZoomIn,
+        "ZoomOut" => Key::ZoomOut,
+        "BrightnessDown" => Key::BrightnessDown,
+        "BrightnessUp" => Key::BrightnessUp,
+        "Eject" => Key::Eject,
+        "LogOff" => Key::LogOff,
+        "Power" => Key::Power,
+        "PowerOff" => Key::PowerOff,
+        "PrintScreen" => Key::PrintScreen,
+        "Hibernate" => Key::Hibernate,
+        "Standby" => Key::Standby,
+        "WakeUp" => Key::WakeUp,
+        "AllCandidates" => Key::AllCandidates,
+        "Alphanumeric" => Key::Alphanumeric,
+        "CodeInput" => Key::CodeInput,
+        "Compose" => Key::Compose,
+        "Convert" => Key::Convert,
+        "FinalMode" => Key::FinalMode,
+        "GroupFirst" => Key::GroupFirst,
+        "GroupLast" => Key::GroupLast,
+        "GroupNext" => Key::GroupNext,
+        "GroupPrevious" => Key::GroupPrevious,
+        "ModeChange" => Key::ModeChange,
+        "NextCandidate" => Key::NextCandidate,
+        "NonConvert" => Key::NonConvert,
+        "PreviousCandidate" => Key::PreviousCandidate,
+        "Process" => Key::Process,
+        "SingleCandidate" => Key::SingleCandidate,
+        "HangulMode" => Key::HangulMode,
+        "HanjaMode" => Key::HanjaMode,
+        "JunjaMode" => Key::JunjaMode,
+        "Eisu" => Key::Eisu,
+        "Hankaku" => Key::Hankaku,
+        "Hiragana" => Key::Hiragana,
+        "HiraganaKatakana" => Key::HiraganaKatakana,
+        "KanaMode" => Key::KanaMode,
+        "KanjiMode" => Key::KanjiMode,
+        "Katakana" => Key::Katakana,
+        "Romaji" => Key::Romaji,
+        "Zenkaku" => Key::Zenkaku,
+        "ZenkakuHankaku" => Key::ZenkakuHankaku,
+        "Close" => Key::Close,
+        "MailForward" => Key::MailForward,
+        "MailReply" => Key::MailReply,
+        "MailSend" => Key::MailSend,
+        "MediaClose" => Key::MediaClose,
+        "MediaFastForward" => Key::MediaFastForward,
+        "MediaPause" => Key::MediaPause,
+        "MediaPlay" => Key::MediaPlay,
+        "MediaPlayPause" => Key::MediaPlayPause,
+        "MediaRecord" => Key::MediaRecord,
+        "MediaRewind" => Key::MediaRewind,
+        "MediaStop" => Key::MediaStop,
+        "MediaTrackNext" => Key::MediaTrackNext,
+        "MediaTrackPrevious" => Key::MediaTrackPrevious,
+        "New" => Key::New,
+        "Open" => Key::Open,
+        "Print" => Key::Print,
+        "Save" => Key::Save,
+        "SpellCheck" => Key::SpellCheck,
+        "AudioVolumeDown" => Key::AudioVolumeDown,
+        "AudioVolumeUp" => Key::AudioVolumeUp,
+        "AudioVolumeMute" => Key::AudioVolumeMute,
+        "MicrophoneToggle" => Key::MicrophoneToggle,
+        "LaunchApplication2" => Key::LaunchApplication2,
+        "LaunchCalendar" => Key::LaunchCalendar,
+        "LaunchMail" => Key::LaunchMail,
+        "LaunchMediaPlayer" => Key::LaunchMediaPlayer,
+        "LaunchMusicPlayer" => Key::LaunchMusicPlayer,
+        "LaunchApplication1" => Key::LaunchApplication1,
+        "LaunchWebBrowser" => Key::LaunchWebBrowser,
+        "BrowserBack" => Key::BrowserBack,
+        "BrowserFavorites" => Key::BrowserFavorites,
+        "BrowserForward" => Key::BrowserForward,
+        "BrowserHome" => Key::BrowserHome,
+        "BrowserRefresh" => Key::BrowserRefresh,
+        "BrowserSearch" => Key::BrowserSearch,
+        "BrowserStop" => Key::BrowserStop,
+        "F1" => Key::F1,
+        "F2" => Key::F2,
+        "F3" => Key::F3,
+        "F4" => Key::F4,
+        "F5" => Key::F5,
+        "F6" => Key::F6,
+        "F7" => Key::F7,
+        "F8" => Key::F8,
+        "F9" => Key::F9,
+        "F10" => Key::F10,
+        "F11" => Key::F11,
+        "F12" => Key::F12,
+        "F13" => Key::F13,
+        "F14" => Key::F14,
+        "F15" => Key::F15,
+        "F16" => Key::F16,
+        "F17" => Key::F17,
+        "F18" => Key::F18,
+        "F19" => Key::F19,
+        "F20" => Key::F20,
+        "F21" => Key::F21,
+        "F22" => Key::F22,
+        "F23" => Key::F23,
+        "F24" => Key::F24,
+        _ if !key.is_ascii() => Key::Character(key.into()),
+        _ => Key::Unidentified(NativeKey::Web(key.into())),
+    }
 }
 
 pub fn keyboard_modifiers(event: &KeyboardEvent) -> ModifiersState {
@@ -2,6 +2,7 @@ mod canvas;
 mod event;
 mod event_handle;
 mod media_query_handle;
+mod resize_observer;
 mod scaling;
 mod timeout;
 
@@ -9,11 +10,18 @@ pub use self::canvas::Canvas;
 pub use self::scaling::ScaleChangeDetector;
 pub use self::timeout::{AnimationFrameRequest, Timeout};
 
-use crate::dpi::{LogicalSize, Size};
-use crate::platform::web::WindowExtWebSys;
+use self::media_query_handle::MediaQueryListHandle;
+use crate::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, Size};
+use crate::event_loop::SystemPreferences;
+use crate::platform::web::{PreventDefault, WindowExtWebSys};
 use crate::window::Window;
+use std::cell::RefCell;
+use std::rc::Rc;
 use wasm_bindgen::closure::Closure;
-use web_sys::{window, BeforeUnloadEvent, Element, HtmlCanvasElement};
+use web_sys::{window, BeforeUnloadEvent, Element, Event, HtmlCanvasElement, MediaQueryListEvent};
+
+const HIGH_CONTRAST_QUERY: &str = "(prefers-contrast: more)";
+const REDUCED_MOTION_QUERY: &str = "(prefers-reduced-motion: reduce)";
 
 pub fn throw(msg: &str) {
     wasm_bindgen::throw_str(msg);
@@ -43,6 +51,71 @@ pub fn on_unload(mut handler: impl FnMut() + 'static) -> UnloadEventHandle {
     }
 }
 
+pub struct VisibilityChangeEventHandle {
+    _listener: event_handle::EventListenerHandle<dyn FnMut(Event)>,
+}
+
+pub fn on_visibility_change(
+    mut handler: impl FnMut(bool) + 'static,
+) -> VisibilityChangeEventHandle {
+    let window = web_sys::window().expect("Failed to obtain window");
+    let document = window.document().expect("Failed to obtain document");
+
+    let closure = Closure::wrap({
+        let document = document.clone();
+        Box::new(move |_: Event| handler(document.hidden())) as Box<dyn FnMut(Event)>
+    });
+
+    let listener = event_handle::EventListenerHandle::new(&document, "visibilitychange", closure);
+    VisibilityChangeEventHandle {
+        _listener: listener,
+    }
+}
+
+pub fn system_preferences() -> SystemPreferences {
+    SystemPreferences {
+        accent_color: None,
+        high_contrast: matches_media(HIGH_CONTRAST_QUERY),
+        reduced_motion: matches_media(REDUCED_MOTION_QUERY),
+    }
+}
+
+fn matches_media(query: &str) -> bool {
+    let window = web_sys::window().expect("Failed to obtain window");
+
+    window
+        .match_media(query)
+        .ok()
+        .flatten()
+        .map(|media| media.matches())
+        .unwrap_or(false)
+}
+
+pub struct SystemPreferencesChangeHandle {
+    _listeners: Vec<MediaQueryListHandle>,
+}
+
+pub fn on_system_preferences_change(
+    handler: impl FnMut() + 'static,
+) -> SystemPreferencesChangeHandle {
+    let handler = Rc::new(RefCell::new(handler));
+    let listeners = [HIGH_CONTRAST_QUERY, REDUCED_MOTION_QUERY]
+        .iter()
+        .filter_map(|query| {
+            let handler = handler.clone();
+            let closure =
+                Closure::wrap(
+                    Box::new(move |_: MediaQueryListEvent| (handler.borrow_mut())())
+                        as Box<dyn FnMut(_)>,
+                );
+            MediaQueryListHandle::new(query, closure)
+        })
+        .collect();
+    SystemPreferencesChangeHandle {
+        _listeners: listeners,
+    }
+}
+
 impl WindowExtWebSys for Window {
     fn canvas(&self) -> HtmlCanvasElement {
         self.window.canvas().raw().clone()
@@ -58,6 +131,18 @@ impl WindowExtWebSys for Window {
             .map(|media| media.matches())
             .unwrap_or(false)
     }
+
+    fn set_prevent_default(&self, prevent_default: PreventDefault) {
+        self.window.canvas().set_prevent_default(prevent_default);
+    }
+
+    fn prevent_default(&self) -> PreventDefault {
+        self.window.canvas().prevent_default()
+    }
+
+    fn set_follow_parent_size(&self, follow: bool) {
+        self.window.set_follow_parent_size(follow);
+    }
 }
 
 pub fn window_size() -> LogicalSize<f64> {
@@ -94,6 +179,25 @@ pub fn set_canvas_size(raw: &HtmlCanvasElement, size: Size) {
     set_canvas_style_property(raw, "height", &format!("{}px", logical_size.height));
 }
 
+/// Converts a position in CSS pixels relative to the canvas to pixels of its drawing buffer.
+///
+/// The ratio between the two is usually the device pixel ratio, but it differs when the size of
+/// the canvas on the page isn't controlled by winit.
+pub fn to_canvas_physical(
+    raw: &HtmlCanvasElement,
+    position: LogicalPosition<f64>,
+) -> PhysicalPosition<f64> {
+    let (client_width, client_height) = (raw.client_width(), raw.client_height());
+    if client_width <= 0 || client_height <= 0 {
+        return position.to_physical(scale_factor());
+    }
+
+    PhysicalPosition {
+        x: position.x * raw.width() as f64 / client_width as f64,
+        y: position.y * raw.height() as f64 / client_height as f64,
+    }
+}
+
 pub fn set_canvas_style_property(raw: &HtmlCanvasElement, property: &str, value: &str) {
     let style = raw.style();
     style
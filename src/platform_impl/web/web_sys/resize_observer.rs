@@ -0,0 +1,39 @@
+use wasm_bindgen::prelude::*;
+use web_sys::Element;
+
+// `ResizeObserver` is still behind `web_sys_unstable_apis` in `web-sys`.
+#[wasm_bindgen]
+extern "C" {
+    type ResizeObserver;
+
+    #[wasm_bindgen(constructor, catch)]
+    fn new(callback: &JsValue) -> Result<ResizeObserver, JsValue>;
+
+    #[wasm_bindgen(method)]
+    fn observe(this: &ResizeObserver, target: &Element);
+
+    #[wasm_bindgen(method)]
+    fn disconnect(this: &ResizeObserver);
+}
+
+pub(super) struct ResizeObserverHandle {
+    observer: ResizeObserver,
+    _listener: Closure<dyn FnMut()>,
+}
+
+impl ResizeObserverHandle {
+    pub fn new(target: &Element, listener: Closure<dyn FnMut()>) -> Option<Self> {
+        let observer = ResizeObserver::new(listener.as_ref()).ok()?;
+        observer.observe(target);
+        Some(Self {
+            observer,
+            _listener: listener,
+        })
+    }
+}
+
+impl Drop for ResizeObserverHandle {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
@@ -10,6 +10,7 @@ use crate::window::{
 use raw_window_handle::{RawWindowHandle, WebHandle};
 
 use super::{backend, monitor, EventLoopWindowTarget};
+use crate::platform::web::PreventDefault;
 
 use std::cell::{Ref, RefCell};
 use std::collections::vec_deque::IntoIter as VecDequeIter;
@@ -21,7 +22,7 @@ pub struct Window {
     previous_pointer: RefCell<&'static str>,
     id: Id,
     register_redraw_request: Box<dyn Fn()>,
-    resize_notify_fn: Box<dyn Fn(PhysicalSize<u32>)>,
+    resize_notify_fn: Rc<dyn Fn(PhysicalSize<u32>)>,
     destroy_fn: Option<Box<dyn FnOnce()>>,
 }
 
@@ -35,6 +36,8 @@ impl Window {
 
         let id = target.generate_id();
 
+        let adopted = platform_attr.canvas.is_some() || platform_attr.canvas_id.is_some();
+        let follow_parent_size = platform_attr.follow_parent_size;
         let canvas = backend::Canvas::create(platform_attr)?;
         let mut canvas = Rc::new(RefCell::new(canvas));
 
@@ -43,7 +46,7 @@ impl Window {
         target.register(&mut canvas, id);
 
         let runner = target.runner.clone();
-        let resize_notify_fn = Box::new(move |new_size| {
+        let resize_notify_fn = Rc::new(move |new_size| {
             runner.send_event(event::Event::WindowEvent {
                 window_id: RootWI(id),
                 event: event::WindowEvent::Resized(new_size),
@@ -62,13 +65,33 @@ impl Window {
             destroy_fn: Some(destroy_fn),
         };
 
-        backend::set_canvas_size(
-            window.canvas.borrow().raw(),
-            attr.inner_size.unwrap_or(Size::Logical(LogicalSize {
-                width: 1024.0,
-                height: 768.0,
-            })),
-        );
+        let raw = window.canvas.borrow().raw().clone();
+        match attr.inner_size {
+            Some(size) => backend::set_canvas_size(&raw, size),
+            // An adopted canvas keeps the size given to it by the page, only its drawing buffer
+            // is matched to it.
+            None if adopted => {
+                if raw.client_width() > 0 && raw.client_height() > 0 {
+                    backend::set_canvas_size(
+                        &raw,
+                        Size::Logical(LogicalSize {
+                            width: raw.client_width() as f64,
+                            height: raw.client_height() as f64,
+                        }),
+                    );
+                }
+            }
+            None => backend::set_canvas_size(
+                &raw,
+                Size::Logical(LogicalSize {
+                    width: 1024.0,
+                    height: 768.0,
+                }),
+            ),
+        }
+        if follow_parent_size {
+            window.set_follow_parent_size(true);
+        }
         window.set_title(&attr.title);
         window.set_maximized(attr.maximized);
         window.set_visible(attr.visible);
@@ -133,6 +156,7 @@ impl Window {
 
     #[inline]
     pub fn set_inner_size(&self, size: Size) {
+        self.canvas.borrow_mut().remove_parent_resize();
         let old_size = self.inner_size();
         backend::set_canvas_size(self.canvas.borrow().raw(), size);
         let new_size = self.inner_size();
@@ -141,6 +165,16 @@ impl Window {
         }
     }
 
+    pub fn set_follow_parent_size(&self, follow: bool) {
+        let mut canvas = self.canvas.borrow_mut();
+        if follow {
+            let resize_notify_fn = self.resize_notify_fn.clone();
+            canvas.on_parent_resize(move |new_size| resize_notify_fn(new_size));
+        } else {
+            canvas.remove_parent_resize();
+        }
+    }
+
     #[inline]
     pub fn set_min_inner_size(&self, _dimensions: Option<Size>) {
         // Intentionally a no-op: users can't resize canvas elements
@@ -239,6 +273,11 @@ impl Window {
         Err(ExternalError::NotSupported(NotSupportedError::new()))
     }
 
+    #[inline]
+    pub fn set_key_repeat_enabled(&self, _enabled: bool) {
+        // Intentionally a no-op, as the browser owns the keyboard's auto-repeat
+    }
+
     #[inline]
     pub fn set_minimized(&self, _minimized: bool) {
         // Intentionally a no-op, as canvases cannot be 'minimized'
@@ -332,6 +371,14 @@ impl Window {
         })
     }
 
+    #[cfg(feature = "accesskit")]
+    #[inline]
+    pub fn enable_accessibility(&self, _source: Box<dyn crate::accessibility::TreeSource>) {}
+
+    #[cfg(feature = "accesskit")]
+    #[inline]
+    pub fn update_accessibility_tree(&self, _update: impl FnOnce() -> accesskit::TreeUpdate) {}
+
     #[inline]
     pub fn id(&self) -> Id {
         return self.id;
@@ -343,6 +390,34 @@ impl Window {
         handle.id = self.id.0;
         RawWindowHandle::Web(handle)
     }
+
+    #[cfg(feature = "rwh_05")]
+    pub fn raw_window_handle_rwh_05(&self) -> rwh_05::RawWindowHandle {
+        let mut handle = rwh_05::WebWindowHandle::empty();
+        handle.id = self.id.0;
+        rwh_05::RawWindowHandle::Web(handle)
+    }
+
+    #[cfg(feature = "rwh_05")]
+    pub fn raw_display_handle_rwh_05(&self) -> rwh_05::RawDisplayHandle {
+        rwh_05::RawDisplayHandle::Web(rwh_05::WebDisplayHandle::empty())
+    }
+
+    #[cfg(feature = "rwh_06")]
+    pub fn raw_window_handle_rwh_06(&self) -> Result<rwh_06::RawWindowHandle, rwh_06::HandleError> {
+        Ok(rwh_06::RawWindowHandle::Web(rwh_06::WebWindowHandle::new(
+            self.id.0,
+        )))
+    }
+
+    #[cfg(feature = "rwh_06")]
+    pub fn raw_display_handle_rwh_06(
+        &self,
+    ) -> Result<rwh_06::RawDisplayHandle, rwh_06::HandleError> {
+        Ok(rwh_06::RawDisplayHandle::Web(
+            rwh_06::WebDisplayHandle::new(),
+        ))
+    }
 }
 
 impl Drop for Window {
@@ -365,4 +440,7 @@ impl Id {
 #[derive(Default, Clone)]
 pub struct PlatformSpecificBuilderAttributes {
     pub(crate) canvas: Option<backend::RawCanvasType>,
+    pub(crate) canvas_id: Option<String>,
+    pub(crate) prevent_default: PreventDefault,
+    pub(crate) follow_parent_size: bool,
 }
@@ -0,0 +1,97 @@
+//! Accessibility of a window, see [`Window::enable_accessibility`].
+//!
+//! The adapter answers `WM_GETOBJECT` in the window procedure. The actions it receives on the
+//! threads of UI Automation are posted to the window, to be delivered by the event loop.
+//!
+//! [`Window::enable_accessibility`]: crate::window::Window::enable_accessibility
+
+use parking_lot::Mutex;
+use winapi::{
+    shared::{
+        minwindef::{LPARAM, LRESULT, WPARAM},
+        windef::HWND,
+    },
+    um::winuser,
+};
+
+use accesskit::{ActionHandler, ActionRequest, TreeUpdate};
+use accesskit_windows::{Adapter, QueuedEvents};
+
+use crate::{
+    accessibility::{SourceHandler, TreeSource},
+    platform_impl::platform::{event_loop::ACCESSIBILITY_ACTION_MSG_ID, util},
+};
+
+/// The adapter of a window, shared by the window and its window procedure.
+#[derive(Default)]
+pub struct Accessibility {
+    adapter: Mutex<Option<(Adapter, SourceHandler)>>,
+}
+
+impl Accessibility {
+    /// Creates the adapter of `window`, which has to be done on the thread of the window.
+    pub fn enable(&self, window: HWND, source: Box<dyn TreeSource>) {
+        let adapter = Adapter::new(
+            accesskit_windows::HWND(window as _),
+            util::is_focused(window),
+            ActionPoster(window as usize),
+        );
+        *self.adapter.lock() = Some((adapter, SourceHandler(source)));
+    }
+
+    /// Applies the update returned by `update`, returning the events to raise on the thread of
+    /// the window.
+    pub fn update_tree(&self, update: impl FnOnce() -> TreeUpdate) -> Option<QueuedEvents> {
+        let mut adapter = self.adapter.lock();
+        adapter.as_mut()?.0.update_if_active(update)
+    }
+
+    pub fn set_focused(&self, focused: bool) {
+        // The events are raised without the lock, as `WM_GETOBJECT` may be sent meanwhile.
+        let events = match self.adapter.lock().as_mut() {
+            Some((adapter, _)) => adapter.update_window_focus_state(focused),
+            None => None,
+        };
+        if let Some(events) = events {
+            events.raise();
+        }
+    }
+
+    /// Answers `WM_GETOBJECT`, returning `None` when it should be passed to `DefWindowProcW`.
+    pub fn handle_wm_getobject(&self, wparam: WPARAM, lparam: LPARAM) -> Option<LRESULT> {
+        // The result is converted without the lock, as UI Automation may send `WM_GETOBJECT`
+        // again meanwhile.
+        let result = match self.adapter.lock().as_mut() {
+            Some((adapter, source)) => adapter.handle_wm_getobject(
+                accesskit_windows::WPARAM(wparam),
+                accesskit_windows::LPARAM(lparam),
+                source,
+            ),
+            None => None,
+        };
+        result.map(|result| {
+            let result: accesskit_windows::LRESULT = result.into();
+            result.0
+        })
+    }
+}
+
+/// Posts the actions to the window, as `ACCESSIBILITY_ACTION_MSG_ID`.
+struct ActionPoster(usize);
+
+impl ActionHandler for ActionPoster {
+    fn do_action(&mut self, request: ActionRequest) {
+        let request = Box::into_raw(Box::new(request));
+        unsafe {
+            if winuser::PostMessageW(
+                self.0 as HWND,
+                *ACCESSIBILITY_ACTION_MSG_ID,
+                0,
+                request as LPARAM,
+            ) == 0
+            {
+                drop(Box::from_raw(request));
+            }
+        }
+    }
+}
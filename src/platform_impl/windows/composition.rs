@@ -0,0 +1,113 @@
+//! DirectComposition visual tree of a window, see
+//! [`WindowExtWindows::composition_visual`].
+//!
+//! The tree is created on first use, with a root visual set on a target for the window. The
+//! device is created without a rendering device, which needs Windows 8.1, as the content of the
+//! visuals, e.g. flip model swap chains, is created by the application.
+//!
+//! [`WindowExtWindows::composition_visual`]: crate::platform::windows::WindowExtWindows::composition_visual
+
+use std::{io, ptr};
+
+use winapi::{
+    ctypes::c_void,
+    shared::{
+        guiddef::REFIID,
+        minwindef::TRUE,
+        ntdef::HRESULT,
+        windef::HWND,
+        winerror::{ERROR_NOT_SUPPORTED, FAILED},
+    },
+    um::{
+        dcomp::{IDCompositionDevice, IDCompositionTarget, IDCompositionVisual},
+        unknwnbase::IUnknown,
+    },
+    Interface,
+};
+
+type DCompositionCreateDevice2 = unsafe extern "system" fn(
+    rendering_device: *const IUnknown,
+    iid: REFIID,
+    dcomposition_device: *mut *mut c_void,
+) -> HRESULT;
+
+lazy_static! {
+    static ref DCOMPOSITION_CREATE_DEVICE2: Option<DCompositionCreateDevice2> =
+        get_function!("dcomp.dll", DCompositionCreateDevice2);
+}
+
+pub struct Composition {
+    device: *mut IDCompositionDevice,
+    target: *mut IDCompositionTarget,
+    visual: *mut IDCompositionVisual,
+}
+
+// DirectComposition objects can be used from any thread, and the window state serializes the
+// calls.
+unsafe impl Send for Composition {}
+
+impl Composition {
+    /// Creates a device and a target for `window`, with an empty root visual.
+    pub fn new(window: HWND) -> Result<Self, io::Error> {
+        let create_device = DCOMPOSITION_CREATE_DEVICE2
+            .ok_or_else(|| io::Error::from_raw_os_error(ERROR_NOT_SUPPORTED as i32))?;
+
+        let mut composition = Composition {
+            device: ptr::null_mut(),
+            target: ptr::null_mut(),
+            visual: ptr::null_mut(),
+        };
+        // The objects created so far are released by `drop` on errors.
+        unsafe {
+            check(create_device(
+                ptr::null(),
+                &IDCompositionDevice::uuidof(),
+                &mut composition.device as *mut _ as *mut _,
+            ))?;
+            let device = &*composition.device;
+            check(device.CreateTargetForHwnd(window, TRUE, &mut composition.target))?;
+            check(device.CreateVisual(&mut composition.visual))?;
+            check((*composition.target).SetRoot(composition.visual))?;
+            check(device.Commit())?;
+        }
+        Ok(composition)
+    }
+
+    pub fn device(&self) -> *mut IDCompositionDevice {
+        self.device
+    }
+
+    pub fn visual(&self) -> *mut IDCompositionVisual {
+        self.visual
+    }
+
+    /// Sets the content of the root visual, or removes it if `content` is null, and commits it.
+    pub unsafe fn set_content(&self, content: *mut IUnknown) -> Result<(), io::Error> {
+        check((*self.visual).SetContent(content))?;
+        check((*self.device).Commit())
+    }
+}
+
+impl Drop for Composition {
+    fn drop(&mut self) {
+        unsafe {
+            if !self.visual.is_null() {
+                (*self.visual).Release();
+            }
+            if !self.target.is_null() {
+                (*self.target).Release();
+            }
+            if !self.device.is_null() {
+                (*self.device).Release();
+            }
+        }
+    }
+}
+
+fn check(hr: HRESULT) -> Result<(), io::Error> {
+    if FAILED(hr) {
+        Err(io::Error::from_raw_os_error(hr))
+    } else {
+        Ok(())
+    }
+}
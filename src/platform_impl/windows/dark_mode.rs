@@ -9,9 +9,9 @@ use winapi::{
         minwindef::{BOOL, DWORD, FALSE, WORD},
         ntdef::{NTSTATUS, NT_SUCCESS, PVOID},
         windef::HWND,
-        winerror::S_OK,
+        winerror::{SUCCEEDED, S_OK},
     },
-    um::{libloaderapi, uxtheme, winnt, winuser},
+    um::{dwmapi, libloaderapi, uxtheme, winnt, winuser},
 };
 
 use crate::window::Theme;
@@ -87,7 +87,33 @@ pub fn try_theme(hwnd: HWND, preferred_theme: Option<Theme>) -> Theme {
     Theme::Light
 }
 
+/// Makes DWM draw the title bar of the window in dark colors, through the attribute documented
+/// since Windows 11, which is also what turns the title bar dark there.
+fn set_immersive_dark_mode(hwnd: HWND, is_dark_mode: bool) -> bool {
+    // From `dwmapi.h`, whose value changed with Windows 10 20H1 (build 18985).
+    const DWMWA_USE_IMMERSIVE_DARK_MODE_BEFORE_20H1: DWORD = 19;
+    const DWMWA_USE_IMMERSIVE_DARK_MODE: DWORD = 20;
+
+    let attribute = match *WIN10_BUILD_VERSION {
+        Some(build) if build < 18985 => DWMWA_USE_IMMERSIVE_DARK_MODE_BEFORE_20H1,
+        _ => DWMWA_USE_IMMERSIVE_DARK_MODE,
+    };
+    // The attribute is a bigbool (i32), not a bool.
+    let is_dark_mode_bigbool = is_dark_mode as BOOL;
+    let hr = unsafe {
+        dwmapi::DwmSetWindowAttribute(
+            hwnd,
+            attribute,
+            &is_dark_mode_bigbool as *const BOOL as _,
+            std::mem::size_of::<BOOL>() as DWORD,
+        )
+    };
+    SUCCEEDED(hr)
+}
+
 fn set_dark_mode_for_window(hwnd: HWND, is_dark_mode: bool) -> bool {
+    let immersive_dark_mode = set_immersive_dark_mode(hwnd, is_dark_mode);
+
     // Uses Windows undocumented API SetWindowCompositionAttribute,
     // as seen in win32-darkmode example linked at top of file.
 
@@ -125,10 +151,10 @@ fn set_dark_mode_for_window(hwnd: HWND, is_dark_mode: bool) -> bool {
 
             let status = set_window_composition_attribute(hwnd, &mut data as *mut _);
 
-            status != FALSE
+            status != FALSE || immersive_dark_mode
         }
     } else {
-        false
+        immersive_dark_mode
     }
 }
 
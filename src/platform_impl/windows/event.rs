@@ -5,7 +5,10 @@ use std::{
     sync::atomic::{AtomicBool, AtomicPtr, Ordering},
 };
 
-use crate::event::{ModifiersState, ScanCode, VirtualKeyCode};
+use crate::{
+    event::ModifiersState,
+    keyboard::{Key, KeyCode, NativeKey, NativeKeyCode, PhysicalKey, SmolStr},
+};
 
 use winapi::{
     shared::minwindef::{HKL, HKL__, LPARAM, UINT, WPARAM},
@@ -33,6 +36,17 @@ pub fn get_key_mods() -> ModifiersState {
         ModifiersState::LOGO,
         key_pressed(winuser::VK_LWIN) || key_pressed(winuser::VK_RWIN),
     );
+
+    mods.set(ModifiersState::LSHIFT, key_pressed(winuser::VK_LSHIFT));
+    mods.set(ModifiersState::RSHIFT, key_pressed(winuser::VK_RSHIFT));
+    if !filter_out_altgr {
+        mods.set(ModifiersState::LCTRL, key_pressed(winuser::VK_LCONTROL));
+        mods.set(ModifiersState::RCTRL, key_pressed(winuser::VK_RCONTROL));
+        mods.set(ModifiersState::LALT, key_pressed(winuser::VK_LMENU));
+        mods.set(ModifiersState::RALT, key_pressed(winuser::VK_RMENU));
+    }
+    mods.set(ModifiersState::LLOGO, key_pressed(winuser::VK_LWIN));
+    mods.set(ModifiersState::RLOGO, key_pressed(winuser::VK_RWIN));
     mods
 }
 
@@ -81,7 +95,8 @@ impl From<ModifiersStateSide> for ModifiersState {
             Self::LOGO,
             side.intersects(ModifiersStateSide::LLOGO | ModifiersStateSide::RLOGO),
         );
-        state
+        // The side flags share their bits with `ModifiersState`'s.
+        state | ModifiersState::from_bits_truncate(side.bits())
     }
 }
 
@@ -160,183 +175,269 @@ fn layout_uses_altgr() -> bool {
     }
 }
 
-pub fn vkey_to_winit_vkey(vkey: c_int) -> Option<VirtualKeyCode> {
-    // VK_* codes are documented here https://msdn.microsoft.com/en-us/library/windows/desktop/dd375731(v=vs.85).aspx
-    match vkey {
-        //winuser::VK_LBUTTON => Some(VirtualKeyCode::Lbutton),
-        //winuser::VK_RBUTTON => Some(VirtualKeyCode::Rbutton),
-        //winuser::VK_CANCEL => Some(VirtualKeyCode::Cancel),
-        //winuser::VK_MBUTTON => Some(VirtualKeyCode::Mbutton),
-        //winuser::VK_XBUTTON1 => Some(VirtualKeyCode::Xbutton1),
-        //winuser::VK_XBUTTON2 => Some(VirtualKeyCode::Xbutton2),
-        winuser::VK_BACK => Some(VirtualKeyCode::Back),
-        winuser::VK_TAB => Some(VirtualKeyCode::Tab),
-        //winuser::VK_CLEAR => Some(VirtualKeyCode::Clear),
-        winuser::VK_RETURN => Some(VirtualKeyCode::Return),
-        winuser::VK_LSHIFT => Some(VirtualKeyCode::LShift),
-        winuser::VK_RSHIFT => Some(VirtualKeyCode::RShift),
-        winuser::VK_LCONTROL => Some(VirtualKeyCode::LControl),
-        winuser::VK_RCONTROL => Some(VirtualKeyCode::RControl),
-        winuser::VK_LMENU => Some(VirtualKeyCode::LAlt),
-        winuser::VK_RMENU => Some(VirtualKeyCode::RAlt),
-        winuser::VK_PAUSE => Some(VirtualKeyCode::Pause),
-        winuser::VK_CAPITAL => Some(VirtualKeyCode::Capital),
-        winuser::VK_KANA => Some(VirtualKeyCode::Kana),
-        //winuser::VK_HANGUEL => Some(VirtualKeyCode::Hanguel),
-        //winuser::VK_HANGUL => Some(VirtualKeyCode::Hangul),
-        //winuser::VK_JUNJA => Some(VirtualKeyCode::Junja),
-        //winuser::VK_FINAL => Some(VirtualKeyCode::Final),
-        //winuser::VK_HANJA => Some(VirtualKeyCode::Hanja),
-        winuser::VK_KANJI => Some(VirtualKeyCode::Kanji),
-        winuser::VK_ESCAPE => Some(VirtualKeyCode::Escape),
-        winuser::VK_CONVERT => Some(VirtualKeyCode::Convert),
-        winuser::VK_NONCONVERT => Some(VirtualKeyCode::NoConvert),
-        //winuser::VK_ACCEPT => Some(VirtualKeyCode::Accept),
-        //winuser::VK_MODECHANGE => Some(VirtualKeyCode::Modechange),
-        winuser::VK_SPACE => Some(VirtualKeyCode::Space),
-        winuser::VK_PRIOR => Some(VirtualKeyCode::PageUp),
-        winuser::VK_NEXT => Some(VirtualKeyCode::PageDown),
-        winuser::VK_END => Some(VirtualKeyCode::End),
-        winuser::VK_HOME => Some(VirtualKeyCode::Home),
-        winuser::VK_LEFT => Some(VirtualKeyCode::Left),
-        winuser::VK_UP => Some(VirtualKeyCode::Up),
-        winuser::VK_RIGHT => Some(VirtualKeyCode::Right),
-        winuser::VK_DOWN => Some(VirtualKeyCode::Down),
-        //winuser::VK_SELECT => Some(VirtualKeyCode::Select),
-        //winuser::VK_PRINT => Some(VirtualKeyCode::Print),
-        //winuser::VK_EXECUTE => Some(VirtualKeyCode::Execute),
-        winuser::VK_SNAPSHOT => Some(VirtualKeyCode::Snapshot),
-        winuser::VK_INSERT => Some(VirtualKeyCode::Insert),
-        winuser::VK_DELETE => Some(VirtualKeyCode::Delete),
-        //winuser::VK_HELP => Some(VirtualKeyCode::Help),
-        0x30 => Some(VirtualKeyCode::Key0),
-        0x31 => Some(VirtualKeyCode::Key1),
-        0x32 => Some(VirtualKeyCode::Key2),
-        0x33 => Some(VirtualKeyCode::Key3),
-        0x34 => Some(VirtualKeyCode::Key4),
-        0x35 => Some(VirtualKeyCode::Key5),
-        0x36 => Some(VirtualKeyCode::Key6),
-        0x37 => Some(VirtualKeyCode::Key7),
-        0x38 => Some(VirtualKeyCode::Key8),
-        0x39 => Some(VirtualKeyCode::Key9),
-        0x41 => Some(VirtualKeyCode::A),
-        0x42 => Some(VirtualKeyCode::B),
-        0x43 => Some(VirtualKeyCode::C),
-        0x44 => Some(VirtualKeyCode::D),
-        0x45 => Some(VirtualKeyCode::E),
-        0x46 => Some(VirtualKeyCode::F),
-        0x47 => Some(VirtualKeyCode::G),
-        0x48 => Some(VirtualKeyCode::H),
-        0x49 => Some(VirtualKeyCode::I),
-        0x4A => Some(VirtualKeyCode::J),
-        0x4B => Some(VirtualKeyCode::K),
-        0x4C => Some(VirtualKeyCode::L),
-        0x4D => Some(VirtualKeyCode::M),
-        0x4E => Some(VirtualKeyCode::N),
-        0x4F => Some(VirtualKeyCode::O),
-        0x50 => Some(VirtualKeyCode::P),
-        0x51 => Some(VirtualKeyCode::Q),
-        0x52 => Some(VirtualKeyCode::R),
-        0x53 => Some(VirtualKeyCode::S),
-        0x54 => Some(VirtualKeyCode::T),
-        0x55 => Some(VirtualKeyCode::U),
-        0x56 => Some(VirtualKeyCode::V),
-        0x57 => Some(VirtualKeyCode::W),
-        0x58 => Some(VirtualKeyCode::X),
-        0x59 => Some(VirtualKeyCode::Y),
-        0x5A => Some(VirtualKeyCode::Z),
-        winuser::VK_LWIN => Some(VirtualKeyCode::LWin),
-        winuser::VK_RWIN => Some(VirtualKeyCode::RWin),
-        winuser::VK_APPS => Some(VirtualKeyCode::Apps),
-        winuser::VK_SLEEP => Some(VirtualKeyCode::Sleep),
-        winuser::VK_NUMPAD0 => Some(VirtualKeyCode::Numpad0),
-        winuser::VK_NUMPAD1 => Some(VirtualKeyCode::Numpad1),
-        winuser::VK_NUMPAD2 => Some(VirtualKeyCode::Numpad2),
-        winuser::VK_NUMPAD3 => Some(VirtualKeyCode::Numpad3),
-        winuser::VK_NUMPAD4 => Some(VirtualKeyCode::Numpad4),
-        winuser::VK_NUMPAD5 => Some(VirtualKeyCode::Numpad5),
-        winuser::VK_NUMPAD6 => Some(VirtualKeyCode::Numpad6),
-        winuser::VK_NUMPAD7 => Some(VirtualKeyCode::Numpad7),
-        winuser::VK_NUMPAD8 => Some(VirtualKeyCode::Numpad8),
-        winuser::VK_NUMPAD9 => Some(VirtualKeyCode::Numpad9),
-        winuser::VK_MULTIPLY => Some(VirtualKeyCode::NumpadMultiply),
-        winuser::VK_ADD => Some(VirtualKeyCode::NumpadAdd),
-        //winuser::VK_SEPARATOR => Some(VirtualKeyCode::Separator),
-        winuser::VK_SUBTRACT => Some(VirtualKeyCode::NumpadSubtract),
-        winuser::VK_DECIMAL => Some(VirtualKeyCode::NumpadDecimal),
-        winuser::VK_DIVIDE => Some(VirtualKeyCode::NumpadDivide),
-        winuser::VK_F1 => Some(VirtualKeyCode::F1),
-        winuser::VK_F2 => Some(VirtualKeyCode::F2),
-        winuser::VK_F3 => Some(VirtualKeyCode::F3),
-        winuser::VK_F4 => Some(VirtualKeyCode::F4),
-        winuser::VK_F5 => Some(VirtualKeyCode::F5),
-        winuser::VK_F6 => Some(VirtualKeyCode::F6),
-        winuser::VK_F7 => Some(VirtualKeyCode::F7),
-        winuser::VK_F8 => Some(VirtualKeyCode::F8),
-        winuser::VK_F9 => Some(VirtualKeyCode::F9),
-        winuser::VK_F10 => Some(VirtualKeyCode::F10),
-        winuser::VK_F11 => Some(VirtualKeyCode::F11),
-        winuser::VK_F12 => Some(VirtualKeyCode::F12),
-        winuser::VK_F13 => Some(VirtualKeyCode::F13),
-        winuser::VK_F14 => Some(VirtualKeyCode::F14),
-        winuser::VK_F15 => Some(VirtualKeyCode::F15),
-        winuser::VK_F16 => Some(VirtualKeyCode::F16),
-        winuser::VK_F17 => Some(VirtualKeyCode::F17),
-        winuser::VK_F18 => Some(VirtualKeyCode::F18),
-        winuser::VK_F19 => Some(VirtualKeyCode::F19),
-        winuser::VK_F20 => Some(VirtualKeyCode::F20),
-        winuser::VK_F21 => Some(VirtualKeyCode::F21),
-        winuser::VK_F22 => Some(VirtualKeyCode::F22),
-        winuser::VK_F23 => Some(VirtualKeyCode::F23),
-        winuser::VK_F24 => Some(VirtualKeyCode::F24),
-        winuser::VK_NUMLOCK => Some(VirtualKeyCode::Numlock),
-        winuser::VK_SCROLL => Some(VirtualKeyCode::Scroll),
-        winuser::VK_BROWSER_BACK => Some(VirtualKeyCode::NavigateBackward),
-        winuser::VK_BROWSER_FORWARD => Some(VirtualKeyCode::NavigateForward),
-        winuser::VK_BROWSER_REFRESH => Some(VirtualKeyCode::WebRefresh),
-        winuser::VK_BROWSER_STOP => Some(VirtualKeyCode::WebStop),
-        winuser::VK_BROWSER_SEARCH => Some(VirtualKeyCode::WebSearch),
-        winuser::VK_BROWSER_FAVORITES => Some(VirtualKeyCode::WebFavorites),
-        winuser::VK_BROWSER_HOME => Some(VirtualKeyCode::WebHome),
-        winuser::VK_VOLUME_MUTE => Some(VirtualKeyCode::Mute),
-        winuser::VK_VOLUME_DOWN => Some(VirtualKeyCode::VolumeDown),
-        winuser::VK_VOLUME_UP => Some(VirtualKeyCode::VolumeUp),
-        winuser::VK_MEDIA_NEXT_TRACK => Some(VirtualKeyCode::NextTrack),
-        winuser::VK_MEDIA_PREV_TRACK => Some(VirtualKeyCode::PrevTrack),
-        winuser::VK_MEDIA_STOP => Some(VirtualKeyCode::MediaStop),
-        winuser::VK_MEDIA_PLAY_PAUSE => Some(VirtualKeyCode::PlayPause),
-        winuser::VK_LAUNCH_MAIL => Some(VirtualKeyCode::Mail),
-        winuser::VK_LAUNCH_MEDIA_SELECT => Some(VirtualKeyCode::MediaSelect),
-        /*winuser::VK_LAUNCH_APP1 => Some(VirtualKeyCode::Launch_app1),
-        winuser::VK_LAUNCH_APP2 => Some(VirtualKeyCode::Launch_app2),*/
-        winuser::VK_OEM_PLUS => Some(VirtualKeyCode::Equals),
-        winuser::VK_OEM_COMMA => Some(VirtualKeyCode::Comma),
-        winuser::VK_OEM_MINUS => Some(VirtualKeyCode::Minus),
-        winuser::VK_OEM_PERIOD => Some(VirtualKeyCode::Period),
-        winuser::VK_OEM_1 => map_text_keys(vkey),
-        winuser::VK_OEM_2 => map_text_keys(vkey),
-        winuser::VK_OEM_3 => map_text_keys(vkey),
-        winuser::VK_OEM_4 => map_text_keys(vkey),
-        winuser::VK_OEM_5 => map_text_keys(vkey),
-        winuser::VK_OEM_6 => map_text_keys(vkey),
-        winuser::VK_OEM_7 => map_text_keys(vkey),
-        /* winuser::VK_OEM_8 => Some(VirtualKeyCode::Oem_8), */
-        winuser::VK_OEM_102 => Some(VirtualKeyCode::OEM102),
-        /*winuser::VK_PROCESSKEY => Some(VirtualKeyCode::Processkey),
-        winuser::VK_PACKET => Some(VirtualKeyCode::Packet),
-        winuser::VK_ATTN => Some(VirtualKeyCode::Attn),
-        winuser::VK_CRSEL => Some(VirtualKeyCode::Crsel),
-        winuser::VK_EXSEL => Some(VirtualKeyCode::Exsel),
-        winuser::VK_EREOF => Some(VirtualKeyCode::Ereof),
-        winuser::VK_PLAY => Some(VirtualKeyCode::Play),
-        winuser::VK_ZOOM => Some(VirtualKeyCode::Zoom),
-        winuser::VK_NONAME => Some(VirtualKeyCode::Noname),
-        winuser::VK_PA1 => Some(VirtualKeyCode::Pa1),
-        winuser::VK_OEM_CLEAR => Some(VirtualKeyCode::Oem_clear),*/
-        _ => None,
+/// Returns the identifier of the active keyboard layout, such as `00000409` for US English.
+pub fn keyboard_layout_name() -> Option<String> {
+    let mut name = [0u16; winuser::KL_NAMELENGTH as usize];
+    if unsafe { winuser::GetKeyboardLayoutNameW(name.as_mut_ptr()) } == 0 {
+        return None;
+    }
+    let len = name.iter().position(|&c| c == 0).unwrap_or(name.len());
+    Some(String::from_utf16_lossy(&name[..len]))
+}
+
+/// Flag telling `ToUnicodeEx` not to change the keyboard state, so that looking up a key doesn't
+/// consume a pending dead key. It is ignored before Windows 10 1607.
+const TOUNICODE_NO_SIDE_EFFECTS: UINT = 1 << 2;
+
+enum KeyText {
+    None,
+    Dead(Option<char>),
+    Text(String),
+}
+
+unsafe fn to_unicode(v_key: c_int, keyboard_state: &[u8; 256], hkl: HKL) -> KeyText {
+    let mut unicode_bytes = [0u16; 8];
+    let len = winuser::ToUnicodeEx(
+        v_key as UINT,
+        0,
+        keyboard_state.as_ptr(),
+        unicode_bytes.as_mut_ptr(),
+        unicode_bytes.len() as _,
+        TOUNICODE_NO_SIDE_EFFECTS,
+        hkl,
+    );
+    match len {
+        0 => KeyText::None,
+        len if len < 0 => KeyText::Dead(
+            char::decode_utf16(unicode_bytes[..1].iter().cloned())
+                .next()
+                .and_then(|c| c.ok()),
+        ),
+        len => KeyText::Text(String::from_utf16_lossy(
+            &unicode_bytes[..(len as usize).min(unicode_bytes.len())],
+        )),
+    }
+}
+
+/// Returns the current keyboard state with <kbd>Ctrl</kbd> and <kbd>Alt</kbd> released, as they
+/// would turn the character keys into control characters. They are kept when they stand for
+/// <kbd>AltGr</kbd>.
+unsafe fn keyboard_state_without_ctrl() -> [u8; 256] {
+    let mut keyboard_state = [0u8; 256];
+    winuser::GetKeyboardState(keyboard_state.as_mut_ptr());
+    if !(layout_uses_altgr() && key_pressed(winuser::VK_RMENU)) {
+        for &modifier in &[
+            winuser::VK_CONTROL,
+            winuser::VK_LCONTROL,
+            winuser::VK_RCONTROL,
+            winuser::VK_MENU,
+            winuser::VK_LMENU,
+            winuser::VK_RMENU,
+        ] {
+            keyboard_state[modifier as usize] = 0;
+        }
+    }
+    keyboard_state
+}
+
+/// Maps a virtual key to the logical key it represents under the active keyboard layout.
+///
+/// The character keys are looked up with the current modifiers, except for <kbd>Ctrl</kbd> and
+/// <kbd>Alt</kbd>, see `keyboard_state_without_ctrl`.
+pub fn vkey_to_key(vkey: c_int) -> Key {
+    if let Some(key) = vkey_to_named_key(vkey) {
+        return key;
+    }
+
+    unsafe {
+        let keyboard_state = keyboard_state_without_ctrl();
+        match to_unicode(vkey, &keyboard_state, winuser::GetKeyboardLayout(0)) {
+            KeyText::Text(text) => Key::Character(text.into()),
+            KeyText::Dead(ch) => Key::Dead(ch),
+            KeyText::None => Key::Unidentified(NativeKey::Windows(vkey as u16)),
+        }
+    }
+}
+
+/// Maps a virtual key to the logical key it represents under the active keyboard layout, as if
+/// no modifier was held. Dead keys are reported as the character they stand for.
+pub fn vkey_to_key_without_modifiers(vkey: c_int) -> Key {
+    if let Some(key) = vkey_to_named_key(vkey) {
+        return key;
+    }
+
+    let keyboard_state = [0u8; 256];
+    unsafe {
+        match to_unicode(vkey, &keyboard_state, winuser::GetKeyboardLayout(0)) {
+            KeyText::Text(text) => Key::Character(text.into()),
+            KeyText::Dead(Some(ch)) => Key::Character(SmolStr::new(ch.encode_utf8(&mut [0; 4]))),
+            KeyText::Dead(None) => Key::Dead(None),
+            KeyText::None => Key::Unidentified(NativeKey::Windows(vkey as u16)),
+        }
+    }
+}
+
+/// Returns the text the virtual key produces with the modifiers currently held, except for
+/// <kbd>Ctrl</kbd> and <kbd>Alt</kbd>, see `keyboard_state_without_ctrl`.
+pub fn vkey_to_text(vkey: c_int) -> Option<SmolStr> {
+    unsafe {
+        let keyboard_state = keyboard_state_without_ctrl();
+        match to_unicode(vkey, &keyboard_state, winuser::GetKeyboardLayout(0)) {
+            KeyText::Text(text) => Some(text.into()),
+            KeyText::Dead(_) | KeyText::None => None,
+        }
     }
 }
 
+/// Returns the text the virtual key produces with all the modifiers currently held.
+pub fn vkey_to_text_with_all_modifiers(vkey: c_int) -> Option<SmolStr> {
+    let mut keyboard_state = [0u8; 256];
+    unsafe {
+        winuser::GetKeyboardState(keyboard_state.as_mut_ptr());
+        match to_unicode(vkey, &keyboard_state, winuser::GetKeyboardLayout(0)) {
+            KeyText::Text(text) => Some(text.into()),
+            KeyText::Dead(_) | KeyText::None => None,
+        }
+    }
+}
+
+/// Returns the text the key at the `code` position produces under the active keyboard layout
+/// with the given modifiers, see `EventLoopWindowTarget::key_code_to_text`.
+pub fn key_code_to_text(code: KeyCode, modifiers: ModifiersState) -> Option<String> {
+    let scancode = (0x0000..=0x00ff)
+        .chain(0xe000..=0xe0ff)
+        .find(|&scancode| scancode_to_physicalkey(scancode) == PhysicalKey::Code(code))?;
+
+    unsafe {
+        let hkl = winuser::GetKeyboardLayout(0);
+        let vkey = winuser::MapVirtualKeyExW(scancode, winuser::MAPVK_VSC_TO_VK_EX, hkl) as c_int;
+        if vkey == 0 || vkey_to_named_key(vkey).is_some() {
+            return None;
+        }
+
+        let mut keyboard_state = [0u8; 256];
+        if modifiers.shift() {
+            keyboard_state[winuser::VK_SHIFT as usize] = 0x80;
+        }
+        // Ctrl is only taken into account as part of AltGr.
+        if modifiers.ctrl() && modifiers.alt() {
+            keyboard_state[winuser::VK_CONTROL as usize] = 0x80;
+            keyboard_state[winuser::VK_MENU as usize] = 0x80;
+        }
+
+        match to_unicode(vkey, &keyboard_state, hkl) {
+            KeyText::Text(text) => Some(text.into()),
+            KeyText::Dead(ch) => ch.map(String::from),
+            KeyText::None => None,
+        }
+    }
+}
+
+fn vkey_to_named_key(vkey: c_int) -> Option<Key> {
+    // VK_* codes are documented here https://msdn.microsoft.com/en-us/library/windows/desktop/dd375731(v=vs.85).aspx
+    Some(match vkey {
+        winuser::VK_CANCEL => Key::Cancel,
+        winuser::VK_BACK => Key::Backspace,
+        winuser::VK_TAB => Key::Tab,
+        winuser::VK_CLEAR | winuser::VK_OEM_CLEAR => Key::Clear,
+        winuser::VK_RETURN => Key::Enter,
+        winuser::VK_SHIFT | winuser::VK_LSHIFT | winuser::VK_RSHIFT => Key::Shift,
+        winuser::VK_CONTROL | winuser::VK_LCONTROL | winuser::VK_RCONTROL => Key::Control,
+        winuser::VK_MENU | winuser::VK_LMENU => Key::Alt,
+        winuser::VK_RMENU => {
+            if layout_uses_altgr() {
+                Key::AltGraph
+            } else {
+                Key::Alt
+            }
+        }
+        winuser::VK_PAUSE => Key::Pause,
+        winuser::VK_CAPITAL => Key::CapsLock,
+        // VK_KANA and VK_HANGUL share their value.
+        winuser::VK_KANA => Key::KanaMode,
+        winuser::VK_JUNJA => Key::JunjaMode,
+        winuser::VK_FINAL => Key::FinalMode,
+        // VK_KANJI and VK_HANJA share their value.
+        winuser::VK_KANJI => Key::KanjiMode,
+        winuser::VK_ESCAPE => Key::Escape,
+        winuser::VK_CONVERT => Key::Convert,
+        winuser::VK_NONCONVERT => Key::NonConvert,
+        winuser::VK_ACCEPT => Key::Accept,
+        winuser::VK_MODECHANGE => Key::ModeChange,
+        winuser::VK_SPACE => Key::Space,
+        winuser::VK_PRIOR => Key::PageUp,
+        winuser::VK_NEXT => Key::PageDown,
+        winuser::VK_END => Key::End,
+        winuser::VK_HOME => Key::Home,
+        winuser::VK_LEFT => Key::ArrowLeft,
+        winuser::VK_UP => Key::ArrowUp,
+        winuser::VK_RIGHT => Key::ArrowRight,
+        winuser::VK_DOWN => Key::ArrowDown,
+        winuser::VK_SELECT => Key::Select,
+        winuser::VK_PRINT => Key::Print,
+        winuser::VK_EXECUTE => Key::Execute,
+        winuser::VK_SNAPSHOT => Key::PrintScreen,
+        winuser::VK_INSERT => Key::Insert,
+        winuser::VK_DELETE => Key::Delete,
+        winuser::VK_HELP => Key::Help,
+        winuser::VK_LWIN | winuser::VK_RWIN => Key::Super,
+        winuser::VK_APPS => Key::ContextMenu,
+        winuser::VK_SLEEP => Key::Standby,
+        winuser::VK_F1 => Key::F1,
+        winuser::VK_F2 => Key::F2,
+        winuser::VK_F3 => Key::F3,
+        winuser::VK_F4 => Key::F4,
+        winuser::VK_F5 => Key::F5,
+        winuser::VK_F6 => Key::F6,
+        winuser::VK_F7 => Key::F7,
+        winuser::VK_F8 => Key::F8,
+        winuser::VK_F9 => Key::F9,
+        winuser::VK_F10 => Key::F10,
+        winuser::VK_F11 => Key::F11,
+        winuser::VK_F12 => Key::F12,
+        winuser::VK_F13 => Key::F13,
+        winuser::VK_F14 => Key::F14,
+        winuser::VK_F15 => Key::F15,
+        winuser::VK_F16 => Key::F16,
+        winuser::VK_F17 => Key::F17,
+        winuser::VK_F18 => Key::F18,
+        winuser::VK_F19 => Key::F19,
+        winuser::VK_F20 => Key::F20,
+        winuser::VK_F21 => Key::F21,
+        winuser::VK_F22 => Key::F22,
+        winuser::VK_F23 => Key::F23,
+        winuser::VK_F24 => Key::F24,
+        winuser::VK_NUMLOCK => Key::NumLock,
+        winuser::VK_SCROLL => Key::ScrollLock,
+        winuser::VK_BROWSER_BACK => Key::BrowserBack,
+        winuser::VK_BROWSER_FORWARD => Key::BrowserForward,
+        winuser::VK_BROWSER_REFRESH => Key::BrowserRefresh,
+        winuser::VK_BROWSER_STOP => Key::BrowserStop,
+        winuser::VK_BROWSER_SEARCH => Key::BrowserSearch,
+        winuser::VK_BROWSER_FAVORITES => Key::BrowserFavorites,
+        winuser::VK_BROWSER_HOME => Key::BrowserHome,
+        winuser::VK_VOLUME_MUTE => Key::AudioVolumeMute,
+        winuser::VK_VOLUME_DOWN => Key::AudioVolumeDown,
+        winuser::VK_VOLUME_UP => Key::AudioVolumeUp,
+        winuser::VK_MEDIA_NEXT_TRACK => Key::MediaTrackNext,
+        winuser::VK_MEDIA_PREV_TRACK => Key::MediaTrackPrevious,
+        winuser::VK_MEDIA_STOP => Key::MediaStop,
+        winuser::VK_MEDIA_PLAY_PAUSE => Key::MediaPlayPause,
+        winuser::VK_LAUNCH_MAIL => Key::LaunchMail,
+        winuser::VK_LAUNCH_MEDIA_SELECT => Key::LaunchMediaPlayer,
+        winuser::VK_LAUNCH_APP1 => Key::LaunchApplication1,
+        winuser::VK_LAUNCH_APP2 => Key::LaunchApplication2,
+        winuser::VK_PROCESSKEY => Key::Process,
+        winuser::VK_ATTN => Key::Attn,
+        winuser::VK_CRSEL => Key::CrSel,
+        winuser::VK_EXSEL => Key::ExSel,
+        winuser::VK_EREOF => Key::EraseEof,
+        winuser::VK_PLAY => Key::Play,
+        winuser::VK_ZOOM => Key::ZoomIn,
+        _ => return None,
+    })
+}
+
 pub fn handle_extended_keys(
     vkey: c_int,
     mut scancode: UINT,
@@ -388,30 +489,172 @@ pub fn handle_extended_keys(
     Some((vkey, scancode))
 }
 
-pub fn process_key_params(
-    wparam: WPARAM,
-    lparam: LPARAM,
-) -> Option<(ScanCode, Option<VirtualKeyCode>)> {
+pub fn process_key_params(wparam: WPARAM, lparam: LPARAM) -> Option<(PhysicalKey, Key)> {
     let scancode = ((lparam >> 16) & 0xff) as UINT;
     let extended = (lparam & 0x01000000) != 0;
     handle_extended_keys(wparam as _, scancode, extended)
-        .map(|(vkey, scancode)| (scancode, vkey_to_winit_vkey(vkey)))
+        .map(|(vkey, scancode)| (scancode_to_physicalkey(scancode), vkey_to_key(vkey)))
 }
 
-// This is needed as windows doesn't properly distinguish
-// some virtual key codes for different keyboard layouts
-fn map_text_keys(win_virtual_key: i32) -> Option<VirtualKeyCode> {
-    let char_key =
-        unsafe { winuser::MapVirtualKeyA(win_virtual_key as u32, winuser::MAPVK_VK_TO_CHAR) }
-            & 0x7FFF;
-    match char::from_u32(char_key) {
-        Some(';') => Some(VirtualKeyCode::Semicolon),
-        Some('/') => Some(VirtualKeyCode::Slash),
-        Some('`') => Some(VirtualKeyCode::Grave),
-        Some('[') => Some(VirtualKeyCode::LBracket),
-        Some(']') => Some(VirtualKeyCode::RBracket),
-        Some('\'') => Some(VirtualKeyCode::Apostrophe),
-        Some('\\') => Some(VirtualKeyCode::Backslash),
-        _ => None,
-    }
+/// Maps a scancode, as returned by `handle_extended_keys`, to the physical key it represents.
+pub fn scancode_to_physicalkey(scancode: UINT) -> PhysicalKey {
+    // See https://docs.microsoft.com/en-us/windows/win32/inputdev/about-keyboard-input#scan-codes
+    PhysicalKey::Code(match scancode {
+        0x0001 => KeyCode::Escape,
+        0x0002 => KeyCode::Digit1,
+        0x0003 => KeyCode::Digit2,
+        0x0004 => KeyCode::Digit3,
+        0x0005 => KeyCode::Digit4,
+        0x0006 => KeyCode::Digit5,
+        0x0007 => KeyCode::Digit6,
+        0x0008 => KeyCode::Digit7,
+        0x0009 => KeyCode::Digit8,
+        0x000A => KeyCode::Digit9,
+        0x000B => KeyCode::Digit0,
+        0x000C => KeyCode::Minus,
+        0x000D => KeyCode::Equal,
+        0x000E => KeyCode::Backspace,
+        0x000F => KeyCode::Tab,
+        0x0010 => KeyCode::KeyQ,
+        0x0011 => KeyCode::KeyW,
+        0x0012 => KeyCode::KeyE,
+        0x0013 => KeyCode::KeyR,
+        0x0014 => KeyCode::KeyT,
+        0x0015 => KeyCode::KeyY,
+        0x0016 => KeyCode::KeyU,
+        0x0017 => KeyCode::KeyI,
+        0x0018 => KeyCode::KeyO,
+        0x0019 => KeyCode::KeyP,
+        0x001A => KeyCode::BracketLeft,
+        0x001B => KeyCode::BracketRight,
+        0x001C => KeyCode::Enter,
+        0x001D => KeyCode::ControlLeft,
+        0x001E => KeyCode::KeyA,
+        0x001F => KeyCode::KeyS,
+        0x0020 => KeyCode::KeyD,
+        0x0021 => KeyCode::KeyF,
+        0x0022 => KeyCode::KeyG,
+        0x0023 => KeyCode::KeyH,
+        0x0024 => KeyCode::KeyJ,
+        0x0025 => KeyCode::KeyK,
+        0x0026 => KeyCode::KeyL,
+        0x0027 => KeyCode::Semicolon,
+        0x0028 => KeyCode::Quote,
+        0x0029 => KeyCode::Backquote,
+        0x002A => KeyCode::ShiftLeft,
+        0x002B => KeyCode::Backslash,
+        0x002C => KeyCode::KeyZ,
+        0x002D => KeyCode::KeyX,
+        0x002E => KeyCode::KeyC,
+        0x002F => KeyCode::KeyV,
+        0x0030 => KeyCode::KeyB,
+        0x0031 => KeyCode::KeyN,
+        0x0032 => KeyCode::KeyM,
+        0x0033 => KeyCode::Comma,
+        0x0034 => KeyCode::Period,
+        0x0035 => KeyCode::Slash,
+        0x0036 | 0xE036 => KeyCode::ShiftRight,
+        0x0037 => KeyCode::NumpadMultiply,
+        0x0038 => KeyCode::AltLeft,
+        0x0039 => KeyCode::Space,
+        0x003A => KeyCode::CapsLock,
+        0x003B => KeyCode::F1,
+        0x003C => KeyCode::F2,
+        0x003D => KeyCode::F3,
+        0x003E => KeyCode::F4,
+        0x003F => KeyCode::F5,
+        0x0040 => KeyCode::F6,
+        0x0041 => KeyCode::F7,
+        0x0042 => KeyCode::F8,
+        0x0043 => KeyCode::F9,
+        0x0044 => KeyCode::F10,
+        0x0045 | 0xE045 => KeyCode::NumLock,
+        0x0046 => KeyCode::ScrollLock,
+        0x0047 => KeyCode::Numpad7,
+        0x0048 => KeyCode::Numpad8,
+        0x0049 => KeyCode::Numpad9,
+        0x004A => KeyCode::NumpadSubtract,
+        0x004B => KeyCode::Numpad4,
+        0x004C => KeyCode::Numpad5,
+        0x004D => KeyCode::Numpad6,
+        0x004E => KeyCode::NumpadAdd,
+        0x004F => KeyCode::Numpad1,
+        0x0050 => KeyCode::Numpad2,
+        0x0051 => KeyCode::Numpad3,
+        0x0052 => KeyCode::Numpad0,
+        0x0053 => KeyCode::NumpadDecimal,
+        0x0054 | 0xE037 => KeyCode::PrintScreen,
+        0x0056 => KeyCode::IntlBackslash,
+        0x0057 => KeyCode::F11,
+        0x0058 => KeyCode::F12,
+        0x0059 => KeyCode::NumpadEqual,
+        0x0064 => KeyCode::F13,
+        0x0065 => KeyCode::F14,
+        0x0066 => KeyCode::F15,
+        0x0067 => KeyCode::F16,
+        0x0068 => KeyCode::F17,
+        0x0069 => KeyCode::F18,
+        0x006A => KeyCode::F19,
+        0x006B => KeyCode::F20,
+        0x006C => KeyCode::F21,
+        0x006D => KeyCode::F22,
+        0x006E => KeyCode::F23,
+        0x0070 => KeyCode::KanaMode,
+        0x0071 => KeyCode::Lang2,
+        0x0072 => KeyCode::Lang1,
+        0x0073 => KeyCode::IntlRo,
+        0x0076 => KeyCode::F24,
+        0x0077 => KeyCode::Lang4,
+        0x0078 => KeyCode::Lang3,
+        0x0079 => KeyCode::Convert,
+        0x007B => KeyCode::NonConvert,
+        0x007D => KeyCode::IntlYen,
+        0x007E => KeyCode::NumpadComma,
+        0xE008 => KeyCode::Undo,
+        0xE00A => KeyCode::Paste,
+        0xE010 => KeyCode::MediaTrackPrevious,
+        0xE017 => KeyCode::Cut,
+        0xE018 => KeyCode::Copy,
+        0xE019 => KeyCode::MediaTrackNext,
+        0xE01C => KeyCode::NumpadEnter,
+        0xE01D => KeyCode::ControlRight,
+        0xE020 => KeyCode::AudioVolumeMute,
+        0xE021 => KeyCode::LaunchApp2,
+        0xE022 => KeyCode::MediaPlayPause,
+        0xE024 => KeyCode::MediaStop,
+        0xE02C => KeyCode::Eject,
+        0xE02E => KeyCode::AudioVolumeDown,
+        0xE030 => KeyCode::AudioVolumeUp,
+        0xE032 => KeyCode::BrowserHome,
+        0xE035 => KeyCode::NumpadDivide,
+        0xE038 => KeyCode::AltRight,
+        0xE03B => KeyCode::Help,
+        0xE047 => KeyCode::Home,
+        0xE048 => KeyCode::ArrowUp,
+        0xE049 => KeyCode::PageUp,
+        0xE04B => KeyCode::ArrowLeft,
+        0xE04D => KeyCode::ArrowRight,
+        0xE04F => KeyCode::End,
+        0xE050 => KeyCode::ArrowDown,
+        0xE051 => KeyCode::PageDown,
+        0xE052 => KeyCode::Insert,
+        0xE053 => KeyCode::Delete,
+        0xE059 => KeyCode::Pause,
+        0xE05B => KeyCode::SuperLeft,
+        0xE05C => KeyCode::SuperRight,
+        0xE05D => KeyCode::ContextMenu,
+        0xE05E => KeyCode::Power,
+        0xE05F => KeyCode::Sleep,
+        0xE063 => KeyCode::WakeUp,
+        0xE065 => KeyCode::BrowserSearch,
+        0xE066 => KeyCode::BrowserFavorites,
+        0xE067 => KeyCode::BrowserRefresh,
+        0xE068 => KeyCode::BrowserStop,
+        0xE069 => KeyCode::BrowserForward,
+        0xE06A => KeyCode::BrowserBack,
+        0xE06B => KeyCode::LaunchApp1,
+        0xE06C => KeyCode::LaunchMail,
+        0xE06D => KeyCode::MediaSelect,
+        _ => return PhysicalKey::Unidentified(NativeKeyCode::Windows(scancode as u16)),
+    })
 }
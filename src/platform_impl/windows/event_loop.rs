@@ -10,6 +10,7 @@ use std::{
     mem, panic, ptr,
     rc::Rc,
     sync::{
+        atomic::{AtomicBool, Ordering},
         mpsc::{self, Receiver, Sender},
         Arc,
     },
@@ -20,8 +21,11 @@ use std::{
 use winapi::{
     shared::{
         basetsd::LONG_PTR,
-        minwindef::{BOOL, DWORD, HIWORD, INT, LOWORD, LPARAM, LRESULT, UINT, WORD, WPARAM},
-        windef::{HWND, POINT, RECT},
+        guiddef::GUID,
+        minwindef::{
+            BOOL, DWORD, FALSE, HIWORD, INT, LOWORD, LPARAM, LRESULT, TRUE, UINT, WORD, WPARAM,
+        },
+        windef::{HWND, POINT, RECT, SIZE},
         windowsx, winerror,
     },
     um::{
@@ -31,25 +35,52 @@ use winapi::{
     },
 };
 
+#[cfg(feature = "accesskit")]
 use crate::{
+    accessibility::accesskit::ActionRequest, platform_impl::platform::accessibility::Accessibility,
+};
+use crate::{
+    device::{DeviceCapabilities, DeviceHandle, DeviceKind},
     dpi::{PhysicalPosition, PhysicalSize},
-    event::{DeviceEvent, Event, Force, KeyboardInput, Touch, TouchPhase, WindowEvent},
-    event_loop::{ControlFlow, EventLoopClosed, EventLoopWindowTarget as RootELW},
+    error::NotSupportedError,
+    event::{
+        DeviceEvent, ElementState, Event, Force, InnerSizeWriter, KeyboardInput, ModifiersState,
+        MouseButton, Pen, PenPhase, ScrollPhase, SessionSleepReason, Touch, TouchPhase,
+        WindowEvent,
+    },
+    event_loop::{
+        ControlFlow, DeviceEvents, EventLoopClosed, EventLoopWindowTarget as RootELW, PowerState,
+        ProxyWakeup, SystemPreferences,
+    },
+    keyboard::KeyCode,
     monitor::MonitorHandle as RootMonitorHandle,
-    platform_impl::platform::{
-        dark_mode::try_theme,
-        dpi::{become_dpi_aware, dpi_to_scale_factor},
-        drop_handler::FileDropHandler,
-        event::{self, handle_extended_keys, process_key_params, vkey_to_winit_vkey},
-        monitor::{self, MonitorHandle},
-        raw_input, util,
-        window::InitData,
-        window_state::{CursorFlags, WindowFlags, WindowState},
-        wrap_device_id, WindowId, DEVICE_ID,
+    platform::windows::RawInputDevices,
+    platform_impl::{
+        click::DoubleClickSettings,
+        platform::{
+            dark_mode::try_theme,
+            dpi::{become_dpi_aware, dpi_to_scale_factor},
+            drop_handler::FileDropHandler,
+            event::{self, handle_extended_keys, process_key_params, vkey_to_key},
+            gesture,
+            input_thread::{InputThread, RawInputBatch, RawInputReport},
+            memory,
+            monitor::{self, MonitorHandle},
+            raw_input::{self, RawDeviceInfo},
+            util,
+            window::{self as platform_window, InitData},
+            window_state::{CursorFlags, WindowFlags, WindowState},
+            wrap_device_id, KeyEventExtra, WindowId, DEVICE_ID,
+        },
     },
     window::{Fullscreen, WindowId as RootWindowId},
 };
-use runner::{EventLoopRunner, EventLoopRunnerShared};
+#[cfg(feature = "gamepad")]
+use crate::{
+    gamepad::{GamepadEvent, GamepadId},
+    platform_impl::platform::gamepad,
+};
+use runner::{EventLoopRunner, EventLoopRunnerShared, WindowProcHook};
 
 type GetPointerFrameInfoHistory = unsafe extern "system" fn(
     pointerId: UINT,
@@ -71,6 +102,37 @@ type GetPointerTouchInfo =
 type GetPointerPenInfo =
     unsafe extern "system" fn(pointId: UINT, penInfo: *mut winuser::POINTER_PEN_INFO) -> BOOL;
 
+type GetCurrentInputMessageSource =
+    unsafe extern "system" fn(inputMessageSource: *mut INPUT_MESSAGE_SOURCE) -> BOOL;
+
+// From `winuser.h`, Windows 8 and later, which `winapi` doesn't cover.
+#[allow(clippy::upper_case_acronyms)]
+#[repr(C)]
+struct INPUT_MESSAGE_SOURCE {
+    deviceType: DWORD,
+    originId: DWORD,
+}
+const IMDT_TOUCHPAD: DWORD = 0x8;
+
+// From `winuser.h`, Windows 10 Creators Update (1703) and later.
+const WM_GETDPISCALEDSIZE: UINT = 0x02E4;
+
+// From `shobjidl_core.h`.
+const THBN_CLICKED: WORD = 0x1800;
+
+// From `wtsapi32.h`, which `winapi` only partially covers.
+const NOTIFY_FOR_THIS_SESSION: DWORD = 0;
+const WTS_SESSION_LOCK: WPARAM = 0x7;
+const WTS_SESSION_UNLOCK: WPARAM = 0x8;
+
+// From `winnt.h`, which `winapi` doesn't cover.
+const GUID_POWER_SAVING_STATUS: GUID = GUID {
+    Data1: 0xe00958c0,
+    Data2: 0xc213,
+    Data3: 0x4ace,
+    Data4: [0xac, 0x77, 0xfe, 0xcc, 0xed, 0x2e, 0xee, 0xa5],
+};
+
 lazy_static! {
     static ref GET_POINTER_FRAME_INFO_HISTORY: Option<GetPointerFrameInfoHistory> =
         get_function!("user32.dll", GetPointerFrameInfoHistory);
@@ -82,34 +144,64 @@ lazy_static! {
         get_function!("user32.dll", GetPointerTouchInfo);
     static ref GET_POINTER_PEN_INFO: Option<GetPointerPenInfo> =
         get_function!("user32.dll", GetPointerPenInfo);
+    static ref GET_CURRENT_INPUT_MESSAGE_SOURCE: Option<GetCurrentInputMessageSource> =
+        get_function!("user32.dll", GetCurrentInputMessageSource);
 }
+
+/// The timer ending a touchpad scroll once its wheel messages stopped.
+const SCROLL_END_TIMER_ID: usize = 0x5343_524c;
+
+/// How long a touchpad scroll lasts after its last wheel message, in milliseconds.
+///
+/// Precision touchpads send wheel messages for as long as the fingers move, but nothing tells
+/// when they are lifted.
+const SCROLL_END_DELAY_MS: UINT = 100;
 pub(crate) struct WindowData<T: 'static> {
     pub window_state: Arc<Mutex<WindowState>>,
     pub event_loop_runner: EventLoopRunnerShared<T>,
     pub _file_drop_handler: Option<FileDropHandler>,
     pub userdata_removed: Cell<bool>,
     pub recurse_depth: Cell<u32>,
+    #[cfg(feature = "accesskit")]
+    pub accessibility: Arc<Accessibility>,
 }
 
 impl<T> WindowData<T> {
-    unsafe fn send_event(&self, event: Event<'_, T>) {
+    unsafe fn send_event(&self, event: Event<T>) {
         self.event_loop_runner.send_event(event);
     }
 }
 
 struct ThreadMsgTargetData<T: 'static> {
     event_loop_runner: EventLoopRunnerShared<T>,
-    user_event_receiver: Receiver<T>,
+    user_event_receiver: Receiver<(T, ProxyWakeup)>,
+    // Set when a wakeup has been posted for coalesced user events, cleared before draining them
+    user_wakeup_pending: Arc<AtomicBool>,
+    // Set after delivering a `ProxyWakeup::PerEvent` user event, so that the following user events
+    // wait for the next iteration
+    user_events_deferred: Cell<bool>,
+    #[cfg(feature = "gamepad")]
+    gamepad_event_receiver: Receiver<(GamepadId, GamepadEvent)>,
+    // Last preferences reported through `Event::SystemPreferencesChanged`
+    system_preferences: Cell<SystemPreferences>,
+    // Last state reported through `Event::PowerStateChanged`
+    power_state: Cell<PowerState>,
+    power_saving_notification: winuser::HPOWERNOTIFY,
+    // Whether `WM_INPUT` drains the raw input queued for the thread
+    buffered_raw_input: bool,
+    // The raw input read by the input thread, if there's one
+    raw_input_batch: RawInputBatch,
 }
 
 impl<T> ThreadMsgTargetData<T> {
-    unsafe fn send_event(&self, event: Event<'_, T>) {
+    unsafe fn send_event(&self, event: Event<T>) {
         self.event_loop_runner.send_event(event);
     }
 }
 
 pub struct EventLoop<T: 'static> {
-    thread_msg_sender: Sender<T>,
+    thread_msg_sender: Sender<(T, ProxyWakeup)>,
+    user_wakeup_pending: Arc<AtomicBool>,
     window_target: RootELW<T>,
 }
 
@@ -117,6 +209,9 @@ pub struct EventLoop<T: 'static> {
 pub(crate) struct PlatformSpecificEventLoopAttributes {
     pub(crate) any_thread: bool,
     pub(crate) dpi_aware: bool,
+    pub(crate) raw_input_devices: RawInputDevices,
+    pub(crate) buffered_raw_input: bool,
+    pub(crate) raw_input_thread: bool,
 }
 
 impl Default for PlatformSpecificEventLoopAttributes {
@@ -124,6 +219,9 @@ impl Default for PlatformSpecificEventLoopAttributes {
         Self {
             any_thread: false,
             dpi_aware: true,
+            raw_input_devices: RawInputDevices::all(),
+            buffered_raw_input: false,
+            raw_input_thread: false,
         }
     }
 }
@@ -131,6 +229,8 @@ impl Default for PlatformSpecificEventLoopAttributes {
 pub struct EventLoopWindowTarget<T: 'static> {
     thread_id: DWORD,
     thread_msg_target: HWND,
+    raw_input_devices: RawInputDevices,
+    raw_input_thread: Option<InputThread>,
     pub(crate) runner_shared: EventLoopRunnerShared<T>,
 }
 
@@ -159,18 +259,47 @@ impl<T: 'static> EventLoop<T> {
 
         let runner_shared = Rc::new(EventLoopRunner::new(thread_msg_target, wait_thread_id));
 
-        let thread_msg_sender =
-            insert_event_target_window_data::<T>(thread_msg_target, runner_shared.clone());
-        raw_input::register_all_mice_and_keyboards_for_raw_input(thread_msg_target);
+        let raw_input_batch = RawInputBatch::default();
+        let (thread_msg_sender, user_wakeup_pending) = insert_event_target_window_data::<T>(
+            thread_msg_target,
+            runner_shared.clone(),
+            attributes.buffered_raw_input,
+            raw_input_batch.clone(),
+        );
+        let raw_input_thread = if attributes.raw_input_thread {
+            InputThread::spawn(
+                thread_msg_target,
+                *RAW_INPUT_BATCH_MSG_ID,
+                attributes.raw_input_devices,
+                raw_input_batch,
+            )
+        } else {
+            None
+        };
+        // Fall back to reading the raw input on this thread if the input thread failed to start.
+        if raw_input_thread.is_none() {
+            raw_input::register_for_raw_input(
+                thread_msg_target,
+                attributes.raw_input_devices,
+                Default::default(),
+            );
+        }
 
         EventLoop {
             thread_msg_sender,
+            user_wakeup_pending,
             window_target: RootELW {
                 p: EventLoopWindowTarget {
                     thread_id,
                     thread_msg_target,
+                    raw_input_devices: attributes.raw_input_devices,
+                    raw_input_thread,
                     runner_shared,
                 },
+                coalescing: Default::default(),
+                exit_code: Default::default(),
+                event_timestamp: Default::default(),
+                dispatcher_settings: Default::default(),
                 _marker: PhantomData,
             },
         }
@@ -182,7 +311,7 @@ impl<T: 'static> EventLoop<T> {
 
     pub fn run<F>(mut self, event_handler: F) -> !
     where
-        F: 'static + FnMut(Event<'_, T>, &RootELW<T>, &mut ControlFlow),
+        F: 'static + FnMut(Event<T>, &RootELW<T>, &mut ControlFlow),
     {
         let exit_code = self.run_return(event_handler);
         ::std::process::exit(exit_code);
@@ -190,7 +319,7 @@ impl<T: 'static> EventLoop<T> {
 
     pub fn run_return<F>(&mut self, mut event_handler: F) -> i32
     where
-        F: FnMut(Event<'_, T>, &RootELW<T>, &mut ControlFlow),
+        F: FnMut(Event<T>, &RootELW<T>, &mut ControlFlow),
     {
         let event_loop_windows_ref = &self.window_target;
 
@@ -210,7 +339,11 @@ impl<T: 'static> EventLoop<T> {
 
             runner.poll();
             'main: loop {
-                if 0 == winuser::GetMessageW(&mut msg, ptr::null_mut(), 0, 0) {
+                let result = {
+                    trace_span!("wait");
+                    winuser::GetMessageW(&mut msg, ptr::null_mut(), 0, 0)
+                };
+                if 0 == result {
                     break 'main 0;
                 }
                 winuser::TranslateMessage(&msg);
@@ -241,6 +374,8 @@ impl<T: 'static> EventLoop<T> {
         EventLoopProxy {
             target_window: self.window_target.p.thread_msg_target,
             event_send: self.thread_msg_sender.clone(),
+            wakeup_pending: self.user_wakeup_pending.clone(),
+            wakeup: ProxyWakeup::default(),
         }
     }
 }
@@ -263,6 +398,142 @@ impl<T> EventLoopWindowTarget<T> {
         let monitor = monitor::primary_monitor();
         Some(RootMonitorHandle { inner: monitor })
     }
+
+    pub fn listen_device_events(&self, allowed: DeviceEvents) {
+        match &self.raw_input_thread {
+            Some(raw_input_thread) => raw_input_thread.listen_device_events(allowed),
+            None => {
+                raw_input::register_for_raw_input(
+                    self.thread_msg_target,
+                    self.raw_input_devices,
+                    allowed,
+                );
+            }
+        }
+    }
+
+    pub fn keyboard_layout(&self) -> Option<String> {
+        event::keyboard_layout_name()
+    }
+
+    pub fn key_code_to_text(&self, code: KeyCode, modifiers: ModifiersState) -> Option<String> {
+        event::key_code_to_text(code, modifiers)
+    }
+
+    pub fn system_preferences(&self) -> SystemPreferences {
+        util::system_preferences()
+    }
+
+    pub fn power_state(&self) -> PowerState {
+        util::power_state()
+    }
+
+    pub fn event_timestamp(&self) -> Option<Instant> {
+        self.runner_shared
+            .input_timestamp()
+            .or_else(util::message_time)
+    }
+
+    #[cfg(feature = "rwh_05")]
+    pub fn raw_display_handle_rwh_05(&self) -> rwh_05::RawDisplayHandle {
+        rwh_05::RawDisplayHandle::Windows(rwh_05::WindowsDisplayHandle::empty())
+    }
+
+    #[cfg(feature = "rwh_06")]
+    pub fn raw_display_handle_rwh_06(
+        &self,
+    ) -> Result<rwh_06::RawDisplayHandle, rwh_06::HandleError> {
+        Ok(rwh_06::RawDisplayHandle::Windows(
+            rwh_06::WindowsDisplayHandle::new(),
+        ))
+    }
+
+    pub fn run_nested<F>(&self, event_handler: F) -> Result<i32, NotSupportedError>
+    where
+        F: FnMut(Event<T>, &mut ControlFlow),
+    {
+        let runner = &self.runner_shared;
+
+        let exit_code = unsafe {
+            let outer = runner.enter_nested_loop(event_handler);
+            let mut msg = mem::zeroed();
+
+            runner.poll();
+            let exit_code = loop {
+                let result = {
+                    trace_span!("wait");
+                    winuser::GetMessageW(&mut msg, ptr::null_mut(), 0, 0)
+                };
+                if 0 == result {
+                    // Leave `WM_QUIT` for the outer loop to see as well.
+                    winuser::PostQuitMessage(msg.wParam as i32);
+                    break 0;
+                }
+                winuser::TranslateMessage(&msg);
+                winuser::DispatchMessageW(&msg);
+
+                if let Err(payload) = runner.take_panic_error() {
+                    runner.leave_nested_loop(outer);
+                    panic::resume_unwind(payload);
+                }
+
+                if let ControlFlow::ExitWithCode(code) = runner.control_flow() {
+                    if !runner.handling_events() {
+                        break code;
+                    }
+                }
+            };
+            runner.leave_nested_loop(outer);
+            exit_code
+        };
+        Ok(exit_code)
+    }
+
+    pub fn set_window_proc_hook(&self, hook: Option<Box<WindowProcHook>>) {
+        self.runner_shared.set_window_proc_hook(hook);
+    }
+
+    pub fn devices(&self) -> Vec<DeviceHandle> {
+        raw_input::get_raw_input_device_list()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|device| {
+                let info = raw_input::get_raw_input_device_info(device.hDevice)?;
+                let path = raw_input::get_raw_input_device_name(device.hDevice);
+                let (kind, capabilities, product_id) = match info {
+                    RawDeviceInfo::Mouse(_) => (
+                        DeviceKind::Mouse,
+                        DeviceCapabilities::POINTER | DeviceCapabilities::SCROLL,
+                        path.as_deref().and_then(raw_input::device_name_product_id),
+                    ),
+                    RawDeviceInfo::Keyboard(_) => (
+                        DeviceKind::Keyboard,
+                        DeviceCapabilities::KEYBOARD,
+                        path.as_deref().and_then(raw_input::device_name_product_id),
+                    ),
+                    RawDeviceInfo::Hid(hid) => {
+                        let (kind, capabilities) = raw_input::hid_device_kind(&hid);
+                        let product_id = (hid.dwVendorId as u16, hid.dwProductId as u16);
+                        (kind, capabilities, Some(product_id))
+                    }
+                };
+
+                Some(DeviceHandle {
+                    id: wrap_device_id(device.hDevice as _),
+                    name: path
+                        .as_deref()
+                        .and_then(raw_input::device_name)
+                        .unwrap_or_default(),
+                    kind,
+                    capabilities,
+                    vendor_id: product_id.map(|(vendor, _)| vendor),
+                    product_id: product_id.map(|(_, product)| product),
+                    // The interface path is derived from the hardware IDs and the port.
+                    persistent_id: path,
+                })
+            })
+            .collect()
+    }
 }
 
 /// Returns the id of the main thread.
@@ -448,6 +719,20 @@ impl<T> Drop for EventLoop<T> {
     fn drop(&mut self) {
         unsafe {
             winuser::DestroyWindow(self.window_target.p.thread_msg_target);
+            winuser::PostThreadMessageW(
+                self.window_target.p.runner_shared.wait_thread_id(),
+                winuser::WM_QUIT,
+                0,
+                0,
+            );
+
+            // The window classes refer to callbacks specific to `T`, so a later event loop has to
+            // register its own.
+            winuser::UnregisterClassW(
+                THREAD_EVENT_TARGET_WINDOW_CLASS.as_ptr(),
+                libloaderapi::GetModuleHandleW(ptr::null()),
+            );
+            platform_window::unregister_window_class();
         }
     }
 }
@@ -510,7 +795,9 @@ type ThreadExecFn = Box<Box<dyn FnMut()>>;
 
 pub struct EventLoopProxy<T: 'static> {
     target_window: HWND,
-    event_send: Sender<T>,
+    event_send: Sender<(T, ProxyWakeup)>,
+    wakeup_pending: Arc<AtomicBool>,
+    wakeup: ProxyWakeup,
 }
 unsafe impl<T: Send + 'static> Send for EventLoopProxy<T> {}
 
@@ -519,26 +806,70 @@ impl<T: 'static> Clone for EventLoopProxy<T> {
         Self {
             target_window: self.target_window,
             event_send: self.event_send.clone(),
+            wakeup_pending: self.wakeup_pending.clone(),
+            wakeup: self.wakeup,
         }
     }
 }
 
 impl<T: 'static> EventLoopProxy<T> {
     pub fn send_event(&self, event: T) -> Result<(), EventLoopClosed<T>> {
-        unsafe {
-            if winuser::PostMessageW(self.target_window, *USER_EVENT_MSG_ID, 0, 0) != 0 {
-                self.event_send.send(event).ok();
-                Ok(())
-            } else {
-                Err(EventLoopClosed(event))
-            }
+        // The event is queued before posting the wakeup, so that the event loop can't clear the
+        // pending flag and drain the queue in between.
+        self.event_send
+            .send((event, self.wakeup))
+            .map_err(|mpsc::SendError((event, _))| EventLoopClosed(event))?;
+        if self.wakeup == ProxyWakeup::PerEvent || !self.wakeup_pending.swap(true, Ordering::SeqCst)
+        {
+            unsafe { winuser::PostMessageW(self.target_window, *USER_EVENT_MSG_ID, 0, 0) };
         }
+        Ok(())
     }
+
+    pub fn set_wakeup(&mut self, wakeup: ProxyWakeup) {
+        self.wakeup = wakeup;
+    }
+}
+
+#[cfg(feature = "gamepad")]
+lazy_static! {
+    // Message sent by the gamepad thread after it queued gamepad events.
+    // WPARAM and LPARAM are unused.
+    static ref GAMEPAD_EVENT_MSG_ID: u32 = {
+        unsafe {
+            winuser::RegisterWindowMessageA("Winit::GamepadEvent\0".as_ptr() as LPCSTR)
+        }
+    };
+}
+
+#[cfg(feature = "accesskit")]
+lazy_static! {
+    // Message posted by the accessibility adapter of a window when an action is requested.
+    // WPARAM is unused, and LPARAM is a `Box<ActionRequest>` which the window procedure frees.
+    pub static ref ACCESSIBILITY_ACTION_MSG_ID: u32 = {
+        unsafe {
+            winuser::RegisterWindowMessageA("Winit::AccessibilityAction\0".as_ptr() as LPCSTR)
+        }
+    };
 }
 
 type WaitUntilInstantBox = Box<Instant>;
 
 lazy_static! {
+    // Message sent by the memory thread when the available memory gets low.
+    // WPARAM and LPARAM are unused.
+    static ref MEMORY_WARNING_MSG_ID: u32 = {
+        unsafe {
+            winuser::RegisterWindowMessageA("Winit::MemoryWarning\0".as_ptr() as LPCSTR)
+        }
+    };
+    // Message sent by the input thread when it added raw input to an empty batch.
+    // WPARAM and LPARAM are unused.
+    static ref RAW_INPUT_BATCH_MSG_ID: u32 = {
+        unsafe {
+            winuser::RegisterWindowMessageA("Winit::RawInputBatch\0".as_ptr() as LPCSTR)
+        }
+    };
     // Message sent by the `EventLoopProxy` when we want to wake up the thread.
     // WPARAM and LPARAM are unused.
     static ref USER_EVENT_MSG_ID: u32 = {
@@ -589,6 +920,11 @@ lazy_static! {
     pub static ref SET_RETAIN_STATE_ON_SIZE_MSG_ID: u32 = unsafe {
         winuser::RegisterWindowMessageA("Winit::SetRetainMaximized\0".as_ptr() as LPCSTR)
     };
+    // Message sent by the shell once the taskbar button of a window was created, e.g. when the
+    // taskbar restarts. WPARAM and LPARAM are unused.
+    static ref TASKBAR_BUTTON_CREATED_MSG_ID: u32 = unsafe {
+        winuser::RegisterWindowMessageA("TaskbarButtonCreated\0".as_ptr() as LPCSTR)
+    };
     static ref THREAD_EVENT_TARGET_WINDOW_CLASS: Vec<u16> =  {
         use std::ffi::OsStr;
         use std::os::windows::ffi::OsStrExt;
@@ -653,6 +989,10 @@ fn create_event_target_window<T: 'static>() -> HWND {
             // the LAYERED style.
             (winuser::WS_VISIBLE | winuser::WS_POPUP) as WindowLongPtr,
         );
+        // Session locks are only reported to the windows which asked for them.
+        if let Some(register) = *util::WTS_REGISTER_SESSION_NOTIFICATION {
+            register(window, NOTIFY_FOR_THIS_SESSION);
+        }
         window
     }
 }
@@ -660,12 +1000,40 @@ fn create_event_target_window<T: 'static>() -> HWND {
 fn insert_event_target_window_data<T>(
     thread_msg_target: HWND,
     event_loop_runner: EventLoopRunnerShared<T>,
-) -> Sender<T> {
+    buffered_raw_input: bool,
+    raw_input_batch: RawInputBatch,
+) -> (Sender<(T, ProxyWakeup)>, Arc<AtomicBool>) {
     let (tx, rx) = mpsc::channel();
+    let user_wakeup_pending = Arc::new(AtomicBool::new(false));
+
+    #[cfg(feature = "gamepad")]
+    let gamepad_event_receiver = {
+        let (gamepad_tx, gamepad_rx) = mpsc::channel();
+        gamepad::spawn(thread_msg_target, *GAMEPAD_EVENT_MSG_ID, gamepad_tx);
+        gamepad_rx
+    };
+
+    memory::spawn(thread_msg_target, *MEMORY_WARNING_MSG_ID);
 
     let userdata = ThreadMsgTargetData {
         event_loop_runner,
         user_event_receiver: rx,
+        user_wakeup_pending: user_wakeup_pending.clone(),
+        user_events_deferred: Cell::new(false),
+        #[cfg(feature = "gamepad")]
+        gamepad_event_receiver,
+        system_preferences: Cell::new(util::system_preferences()),
+        power_state: Cell::new(util::power_state()),
+        // The battery saver isn't covered by `PBT_APMPOWERSTATUSCHANGE`.
+        power_saving_notification: unsafe {
+            winuser::RegisterPowerSettingNotification(
+                thread_msg_target as HANDLE,
+                &GUID_POWER_SAVING_STATUS,
+                winuser::DEVICE_NOTIFY_WINDOW_HANDLE,
+            )
+        },
+        buffered_raw_input,
+        raw_input_batch,
     };
     let input_ptr = Box::into_raw(Box::new(userdata));
 
@@ -677,7 +1045,7 @@ fn insert_event_target_window_data<T>(
         )
     };
 
-    tx
+    (tx, user_wakeup_pending)
 }
 
 /// Capture mouse input, allowing `window` to receive mouse events when the cursor is outside of
@@ -705,6 +1073,97 @@ fn normalize_pointer_pressure(pressure: u32) -> Option<Force> {
     }
 }
 
+/// Returns the event to report for the pen with the given pointer id, if it is a pen.
+unsafe fn pen_event(
+    pointer_id: UINT,
+    phase: PenPhase,
+    location: PhysicalPosition<f64>,
+) -> Option<Pen> {
+    let GetPointerPenInfo = (*GET_POINTER_PEN_INFO)?;
+    let mut pen_info = mem::MaybeUninit::uninit();
+    if GetPointerPenInfo(pointer_id, pen_info.as_mut_ptr()) == 0 {
+        return None;
+    }
+    let pen_info = pen_info.assume_init();
+
+    let pressure = if pen_info.penMask & winuser::PEN_MASK_PRESSURE != 0 {
+        pen_info.pressure as f64 / 1024.0
+    } else {
+        0.0
+    };
+    let tilt = (
+        if pen_info.penMask & winuser::PEN_MASK_TILT_X != 0 {
+            pen_info.tiltX as f64
+        } else {
+            0.0
+        },
+        if pen_info.penMask & winuser::PEN_MASK_TILT_Y != 0 {
+            pen_info.tiltY as f64
+        } else {
+            0.0
+        },
+    );
+    let twist = if pen_info.penMask & winuser::PEN_MASK_ROTATION != 0 {
+        pen_info.rotation as f64
+    } else {
+        0.0
+    };
+
+    Some(Pen {
+        device_id: DEVICE_ID,
+        phase,
+        location,
+        pressure,
+        tilt,
+        twist,
+        barrel_button: pen_info.penFlags & winuser::PEN_FLAG_BARREL != 0,
+        eraser: pen_info.penFlags & (winuser::PEN_FLAG_INVERTED | winuser::PEN_FLAG_ERASER) != 0,
+    })
+}
+
+/// Returns the click count to report with a mouse button message.
+unsafe fn mouse_click_count<T>(
+    window: HWND,
+    userdata: &WindowData<T>,
+    button: MouseButton,
+    state: ElementState,
+    lparam: LPARAM,
+) -> u32 {
+    let mut window_state = userdata.window_state.lock();
+    match state {
+        ElementState::Pressed => {
+            let position = PhysicalPosition::new(
+                windowsx::GET_X_LPARAM(lparam) as f64,
+                windowsx::GET_Y_LPARAM(lparam) as f64,
+            );
+            // The metrics are the size of the rectangle centered on the first click the second
+            // click must fall in.
+            let settings = DoubleClickSettings {
+                interval: Duration::from_millis(winuser::GetDoubleClickTime() as u64),
+                distance: PhysicalSize::new(
+                    winuser::GetSystemMetrics(winuser::SM_CXDOUBLECLK) as f64 / 2.0,
+                    winuser::GetSystemMetrics(winuser::SM_CYDOUBLECLK) as f64 / 2.0,
+                ),
+            };
+            window_state.mouse.click_counter.press(
+                RootWindowId(WindowId(window)),
+                button,
+                position,
+                settings,
+            )
+        }
+        ElementState::Released => window_state.mouse.click_counter.release(button),
+    }
+}
+
+fn xbutton_to_mouse_button(xbutton: WORD) -> MouseButton {
+    match xbutton {
+        winuser::XBUTTON1 => MouseButton::Back,
+        winuser::XBUTTON2 => MouseButton::Forward,
+        xbutton => MouseButton::Other(xbutton),
+    }
+}
+
 /// Flush redraw events for Winit's windows.
 ///
 /// Winit's API guarantees that all redraw events will be clustered together and dispatched all at
@@ -767,6 +1226,35 @@ unsafe fn process_control_flow<T: 'static>(runner: &EventLoopRunner<T>) {
     }
 }
 
+/// Returns the phase of the scroll reported by the current wheel message.
+///
+/// The scrolls of precision touchpads start with their first wheel message and end once they
+/// stopped for `SCROLL_END_DELAY_MS`. Other devices only report `Moved`.
+unsafe fn scroll_phase<T>(window: HWND, userdata: &WindowData<T>) -> ScrollPhase {
+    let mut source = INPUT_MESSAGE_SOURCE {
+        deviceType: 0,
+        originId: 0,
+    };
+    let from_touchpad = match *GET_CURRENT_INPUT_MESSAGE_SOURCE {
+        Some(GetCurrentInputMessageSource) => {
+            GetCurrentInputMessageSource(&mut source) != 0 && source.deviceType == IMDT_TOUCHPAD
+        }
+        None => false,
+    };
+    if !from_touchpad {
+        return ScrollPhase::Moved;
+    }
+
+    // Setting the timer again pushes back the end of the scroll.
+    winuser::SetTimer(window, SCROLL_END_TIMER_ID, SCROLL_END_DELAY_MS, None);
+    let mut window_state = userdata.window_state.lock();
+    if mem::replace(&mut window_state.touchpad_scrolling, true) {
+        ScrollPhase::Moved
+    } else {
+        ScrollPhase::Started
+    }
+}
+
 /// Emit a `ModifiersChanged` event whenever modifiers have changed.
 fn update_modifiers<T>(window: HWND, userdata: &WindowData<T>) {
     use crate::event::WindowEvent::ModifiersChanged;
@@ -866,6 +1354,7 @@ unsafe fn public_window_callback_inner<T: 'static>(
     lparam: LPARAM,
     userdata: &WindowData<T>,
 ) -> LRESULT {
+    trace_span!("window message", msg);
     winuser::RedrawWindow(
         userdata.event_loop_runner.thread_msg_target(),
         ptr::null(),
@@ -876,6 +1365,15 @@ unsafe fn public_window_callback_inner<T: 'static>(
     // I decided to bind the closure to `callback` and pass it to catch_unwind rather than passing
     // the closure to catch_unwind directly so that the match body indendation wouldn't change and
     // the git blame and history would be preserved.
+    let hook_result = userdata.event_loop_runner.catch_unwind(|| {
+        userdata
+            .event_loop_runner
+            .call_window_proc_hook(window, msg, wparam, lparam)
+    });
+    if let Some(Some(result)) = hook_result {
+        return result;
+    }
+
     let callback = || match msg {
         winuser::WM_ENTERSIZEMOVE => {
             userdata
@@ -1047,6 +1545,11 @@ unsafe fn public_window_callback_inner<T: 'static>(
             use std::char;
             let is_high_surrogate = (0xD800..=0xDBFF).contains(&wparam);
             let is_low_surrogate = (0xDC00..=0xDFFF).contains(&wparam);
+            // Bit 30 of `lparam` is set when the key was already down.
+            let repeat = lparam & 0x4000_0000 != 0;
+            if repeat && !userdata.window_state.lock().key_repeat_enabled {
+                return 0;
+            }
 
             if is_high_surrogate {
                 userdata.window_state.lock().high_surrogate = Some(wparam as u16);
@@ -1181,13 +1684,14 @@ unsafe fn public_window_callback_inner<T: 'static>(
             let value = value as f32 / winuser::WHEEL_DELTA as f32;
 
             update_modifiers(window, userdata);
+            let phase = scroll_phase(window, userdata);
 
             userdata.send_event(Event::WindowEvent {
                 window_id: RootWindowId(WindowId(window)),
                 event: WindowEvent::MouseWheel {
                     device_id: DEVICE_ID,
                     delta: LineDelta(0.0, value),
-                    phase: TouchPhase::Moved,
+                    phase,
                     modifiers: event::get_key_mods(),
                 },
             });
@@ -1203,13 +1707,14 @@ unsafe fn public_window_callback_inner<T: 'static>(
             let value = value as f32 / winuser::WHEEL_DELTA as f32;
 
             update_modifiers(window, userdata);
+            let phase = scroll_phase(window, userdata);
 
             userdata.send_event(Event::WindowEvent {
                 window_id: RootWindowId(WindowId(window)),
                 event: WindowEvent::MouseWheel {
                     device_id: DEVICE_ID,
                     delta: LineDelta(value, 0.0),
-                    phase: TouchPhase::Moved,
+                    phase,
                     modifiers: event::get_key_mods(),
                 },
             });
@@ -1217,13 +1722,37 @@ unsafe fn public_window_callback_inner<T: 'static>(
             0
         }
 
+        winuser::WM_TIMER if wparam == SCROLL_END_TIMER_ID => {
+            use crate::event::MouseScrollDelta::LineDelta;
+
+            winuser::KillTimer(window, SCROLL_END_TIMER_ID);
+            if mem::take(&mut userdata.window_state.lock().touchpad_scrolling) {
+                userdata.send_event(Event::WindowEvent {
+                    window_id: RootWindowId(WindowId(window)),
+                    event: WindowEvent::MouseWheel {
+                        device_id: DEVICE_ID,
+                        delta: LineDelta(0.0, 0.0),
+                        phase: ScrollPhase::Ended,
+                        modifiers: event::get_key_mods(),
+                    },
+                });
+            }
+            0
+        }
+
         winuser::WM_KEYDOWN | winuser::WM_SYSKEYDOWN => {
-            use crate::event::{ElementState::Pressed, VirtualKeyCode};
+            use crate::{event::ElementState::Pressed, keyboard::Key};
             if msg == winuser::WM_SYSKEYDOWN && wparam as i32 == winuser::VK_F4 {
                 winuser::DefWindowProcW(window, msg, wparam, lparam)
             } else {
-                if let Some((scancode, vkey)) = process_key_params(wparam, lparam) {
+                if let Some((physical_key, logical_key)) = process_key_params(wparam, lparam) {
+                    let is_delete = logical_key == Key::Delete;
+                    // Bit 30 of `lparam` is set when the key was already down.
+                    let repeat = lparam & 0x4000_0000 != 0;
                     update_modifiers(window, userdata);
+                    if repeat && !userdata.window_state.lock().key_repeat_enabled {
+                        return 0;
+                    }
 
                     #[allow(deprecated)]
                     userdata.send_event(Event::WindowEvent {
@@ -1232,16 +1761,26 @@ unsafe fn public_window_callback_inner<T: 'static>(
                             device_id: DEVICE_ID,
                             input: KeyboardInput {
                                 state: Pressed,
-                                scancode,
-                                virtual_keycode: vkey,
+                                physical_key,
+                                logical_key,
+                                text: event::vkey_to_text(wparam as _),
+                                repeat,
                                 modifiers: event::get_key_mods(),
+                                platform_specific: KeyEventExtra {
+                                    text_with_all_modifiers: event::vkey_to_text_with_all_modifiers(
+                                        wparam as _,
+                                    ),
+                                    key_without_modifiers: event::vkey_to_key_without_modifiers(
+                                        wparam as _,
+                                    ),
+                                },
                             },
                             is_synthetic: false,
                         },
                     });
                     // Windows doesn't emit a delete character by default, but in order to make it
                     // consistent with the other platforms we'll emit a delete character here.
-                    if vkey == Some(VirtualKeyCode::Delete) {
+                    if is_delete {
                         userdata.send_event(Event::WindowEvent {
                             window_id: RootWindowId(WindowId(window)),
                             event: WindowEvent::ReceivedCharacter('\u{7F}'),
@@ -1254,7 +1793,7 @@ unsafe fn public_window_callback_inner<T: 'static>(
 
         winuser::WM_KEYUP | winuser::WM_SYSKEYUP => {
             use crate::event::ElementState::Released;
-            if let Some((scancode, vkey)) = process_key_params(wparam, lparam) {
+            if let Some((physical_key, logical_key)) = process_key_params(wparam, lparam) {
                 update_modifiers(window, userdata);
 
                 #[allow(deprecated)]
@@ -1264,9 +1803,17 @@ unsafe fn public_window_callback_inner<T: 'static>(
                         device_id: DEVICE_ID,
                         input: KeyboardInput {
                             state: Released,
-                            scancode,
-                            virtual_keycode: vkey,
+                            physical_key,
+                            logical_key,
+                            text: None,
+                            repeat: false,
                             modifiers: event::get_key_mods(),
+                            platform_specific: KeyEventExtra {
+                                text_with_all_modifiers: None,
+                                key_without_modifiers: event::vkey_to_key_without_modifiers(
+                                    wparam as _,
+                                ),
+                            },
                         },
                         is_synthetic: false,
                     },
@@ -1282,12 +1829,15 @@ unsafe fn public_window_callback_inner<T: 'static>(
 
             update_modifiers(window, userdata);
 
+            let click_count = mouse_click_count(window, userdata, Left, Pressed, lparam);
+
             userdata.send_event(Event::WindowEvent {
                 window_id: RootWindowId(WindowId(window)),
                 event: MouseInput {
                     device_id: DEVICE_ID,
                     state: Pressed,
                     button: Left,
+                    click_count,
                     modifiers: event::get_key_mods(),
                 },
             });
@@ -1303,12 +1853,15 @@ unsafe fn public_window_callback_inner<T: 'static>(
 
             update_modifiers(window, userdata);
 
+            let click_count = mouse_click_count(window, userdata, Left, Released, lparam);
+
             userdata.send_event(Event::WindowEvent {
                 window_id: RootWindowId(WindowId(window)),
                 event: MouseInput {
                     device_id: DEVICE_ID,
                     state: Released,
                     button: Left,
+                    click_count,
                     modifiers: event::get_key_mods(),
                 },
             });
@@ -1324,12 +1877,15 @@ unsafe fn public_window_callback_inner<T: 'static>(
 
             update_modifiers(window, userdata);
 
+            let click_count = mouse_click_count(window, userdata, Right, Pressed, lparam);
+
             userdata.send_event(Event::WindowEvent {
                 window_id: RootWindowId(WindowId(window)),
                 event: MouseInput {
                     device_id: DEVICE_ID,
                     state: Pressed,
                     button: Right,
+                    click_count,
                     modifiers: event::get_key_mods(),
                 },
             });
@@ -1345,12 +1901,15 @@ unsafe fn public_window_callback_inner<T: 'static>(
 
             update_modifiers(window, userdata);
 
+            let click_count = mouse_click_count(window, userdata, Right, Released, lparam);
+
             userdata.send_event(Event::WindowEvent {
                 window_id: RootWindowId(WindowId(window)),
                 event: MouseInput {
                     device_id: DEVICE_ID,
                     state: Released,
                     button: Right,
+                    click_count,
                     modifiers: event::get_key_mods(),
                 },
             });
@@ -1366,12 +1925,15 @@ unsafe fn public_window_callback_inner<T: 'static>(
 
             update_modifiers(window, userdata);
 
+            let click_count = mouse_click_count(window, userdata, Middle, Pressed, lparam);
+
             userdata.send_event(Event::WindowEvent {
                 window_id: RootWindowId(WindowId(window)),
                 event: MouseInput {
                     device_id: DEVICE_ID,
                     state: Pressed,
                     button: Middle,
+                    click_count,
                     modifiers: event::get_key_mods(),
                 },
             });
@@ -1387,12 +1949,15 @@ unsafe fn public_window_callback_inner<T: 'static>(
 
             update_modifiers(window, userdata);
 
+            let click_count = mouse_click_count(window, userdata, Middle, Released, lparam);
+
             userdata.send_event(Event::WindowEvent {
                 window_id: RootWindowId(WindowId(window)),
                 event: MouseInput {
                     device_id: DEVICE_ID,
                     state: Released,
                     button: Middle,
+                    click_count,
                     modifiers: event::get_key_mods(),
                 },
             });
@@ -1400,21 +1965,22 @@ unsafe fn public_window_callback_inner<T: 'static>(
         }
 
         winuser::WM_XBUTTONDOWN => {
-            use crate::event::{
-                ElementState::Pressed, MouseButton::Other, WindowEvent::MouseInput,
-            };
-            let xbutton = winuser::GET_XBUTTON_WPARAM(wparam);
+            use crate::event::{ElementState::Pressed, WindowEvent::MouseInput};
+            let button = xbutton_to_mouse_button(winuser::GET_XBUTTON_WPARAM(wparam));
 
             capture_mouse(window, &mut *userdata.window_state.lock());
 
             update_modifiers(window, userdata);
 
+            let click_count = mouse_click_count(window, userdata, button, Pressed, lparam);
+
             userdata.send_event(Event::WindowEvent {
                 window_id: RootWindowId(WindowId(window)),
                 event: MouseInput {
                     device_id: DEVICE_ID,
                     state: Pressed,
-                    button: Other(xbutton),
+                    button,
+                    click_count,
                     modifiers: event::get_key_mods(),
                 },
             });
@@ -1422,21 +1988,22 @@ unsafe fn public_window_callback_inner<T: 'static>(
         }
 
         winuser::WM_XBUTTONUP => {
-            use crate::event::{
-                ElementState::Released, MouseButton::Other, WindowEvent::MouseInput,
-            };
-            let xbutton = winuser::GET_XBUTTON_WPARAM(wparam);
+            use crate::event::{ElementState::Released, WindowEvent::MouseInput};
+            let button = xbutton_to_mouse_button(winuser::GET_XBUTTON_WPARAM(wparam));
 
             release_mouse(userdata.window_state.lock());
 
             update_modifiers(window, userdata);
 
+            let click_count = mouse_click_count(window, userdata, button, Released, lparam);
+
             userdata.send_event(Event::WindowEvent {
                 window_id: RootWindowId(WindowId(window)),
                 event: MouseInput {
                     device_id: DEVICE_ID,
                     state: Released,
-                    button: Other(xbutton),
+                    button,
+                    click_count,
                     modifiers: event::get_key_mods(),
                 },
             });
@@ -1454,6 +2021,20 @@ unsafe fn public_window_callback_inner<T: 'static>(
             0
         }
 
+        winuser::WM_GESTURE => {
+            let event = gesture::translate(lparam, &mut userdata.window_state.lock().gesture);
+            match event {
+                Some(event) => {
+                    userdata.send_event(Event::WindowEvent {
+                        window_id: RootWindowId(WindowId(window)),
+                        event,
+                    });
+                    0
+                }
+                None => winuser::DefWindowProcW(window, msg, wparam, lparam),
+            }
+        }
+
         winuser::WM_TOUCH => {
             let pcount = LOWORD(wparam as DWORD) as usize;
             let mut inputs = Vec::with_capacity(pcount);
@@ -1539,6 +2120,15 @@ unsafe fn public_window_callback_inner<T: 'static>(
                     return 0;
                 }
 
+                // The default procedure turns the touch pointers into `WM_GESTURE` messages.
+                if userdata.window_state.lock().touch_gestures
+                    && pointer_infos
+                        .first()
+                        .map_or(false, |info| info.pointerType == winuser::PT_TOUCH)
+                {
+                    return winuser::DefWindowProcW(window, msg, wparam, lparam);
+                }
+
                 // https://docs.microsoft.com/en-us/windows/desktop/api/winuser/nf-winuser-getpointerframeinfohistory
                 // The information retrieved appears in reverse chronological order, with the most recent entry in the first
                 // row of the returned array
@@ -1582,6 +2172,29 @@ unsafe fn public_window_callback_inner<T: 'static>(
                         continue;
                     }
 
+                    let x = location.x as f64 + x.fract();
+                    let y = location.y as f64 + y.fract();
+                    let location = PhysicalPosition::new(x, y);
+
+                    if pointer_info.pointerType == winuser::PT_PEN {
+                        let phase = if pointer_info.pointerFlags & winuser::POINTER_FLAG_DOWN != 0 {
+                            PenPhase::Down
+                        } else if pointer_info.pointerFlags & winuser::POINTER_FLAG_UP != 0 {
+                            PenPhase::Up
+                        } else if pointer_info.pointerFlags & winuser::POINTER_FLAG_UPDATE != 0 {
+                            PenPhase::Moved
+                        } else {
+                            continue;
+                        };
+                        if let Some(pen) = pen_event(pointer_info.pointerId, phase, location) {
+                            userdata.send_event(Event::WindowEvent {
+                                window_id: RootWindowId(WindowId(window)),
+                                event: WindowEvent::Pen(pen),
+                            });
+                        }
+                        continue;
+                    }
+
                     let force = match pointer_info.pointerType {
                         winuser::PT_TOUCH => {
                             let mut touch_info = mem::MaybeUninit::uninit();
@@ -1597,26 +2210,9 @@ unsafe fn public_window_callback_inner<T: 'static>(
                                 }
                             })
                         }
-                        winuser::PT_PEN => {
-                            let mut pen_info = mem::MaybeUninit::uninit();
-                            GET_POINTER_PEN_INFO.and_then(|GetPointerPenInfo| {
-                                match GetPointerPenInfo(
-                                    pointer_info.pointerId,
-                                    pen_info.as_mut_ptr(),
-                                ) {
-                                    0 => None,
-                                    _ => {
-                                        normalize_pointer_pressure(pen_info.assume_init().pressure)
-                                    }
-                                }
-                            })
-                        }
                         _ => None,
                     };
 
-                    let x = location.x as f64 + x.fract();
-                    let y = location.y as f64 + y.fract();
-                    let location = PhysicalPosition::new(x, y);
                     userdata.send_event(Event::WindowEvent {
                         window_id: RootWindowId(WindowId(window)),
                         event: WindowEvent::Touch(Touch {
@@ -1643,12 +2239,41 @@ unsafe fn public_window_callback_inner<T: 'static>(
             0
         }
 
+        winuser::WM_POINTERENTER | winuser::WM_POINTERLEAVE => {
+            let pointer_id = LOWORD(wparam as DWORD) as UINT;
+            let phase = if msg == winuser::WM_POINTERENTER {
+                PenPhase::Entered
+            } else {
+                PenPhase::Left
+            };
+
+            let mut location = POINT {
+                x: windowsx::GET_X_LPARAM(lparam),
+                y: windowsx::GET_Y_LPARAM(lparam),
+            };
+            winuser::ScreenToClient(window, &mut location);
+            let location = PhysicalPosition::new(location.x as f64, location.y as f64);
+
+            // Only pens have a proximity, other pointers are handled by the default procedure.
+            match pen_event(pointer_id, phase, location) {
+                Some(pen) => {
+                    userdata.send_event(Event::WindowEvent {
+                        window_id: RootWindowId(WindowId(window)),
+                        event: WindowEvent::Pen(pen),
+                    });
+                    0
+                }
+                None => winuser::DefWindowProcW(window, msg, wparam, lparam),
+            }
+        }
+
         winuser::WM_SETFOCUS => {
             use crate::event::{ElementState::Released, WindowEvent::Focused};
             for windows_keycode in event::get_pressed_keys() {
                 let scancode =
-                    winuser::MapVirtualKeyA(windows_keycode as _, winuser::MAPVK_VK_TO_VSC);
-                let virtual_keycode = event::vkey_to_winit_vkey(windows_keycode);
+                    winuser::MapVirtualKeyA(windows_keycode as _, winuser::MAPVK_VK_TO_VSC_EX);
+                let physical_key = event::scancode_to_physicalkey(scancode);
+                let logical_key = event::vkey_to_key(windows_keycode);
 
                 update_modifiers(window, userdata);
 
@@ -1658,10 +2283,18 @@ unsafe fn public_window_callback_inner<T: 'static>(
                     event: WindowEvent::KeyboardInput {
                         device_id: DEVICE_ID,
                         input: KeyboardInput {
-                            scancode,
-                            virtual_keycode,
+                            physical_key,
+                            logical_key,
+                            text: None,
+                            repeat: false,
                             state: Released,
                             modifiers: event::get_key_mods(),
+                            platform_specific: KeyEventExtra {
+                                text_with_all_modifiers: None,
+                                key_without_modifiers: event::vkey_to_key_without_modifiers(
+                                    windows_keycode,
+                                ),
+                            },
                         },
                         is_synthetic: true,
                     },
@@ -1673,6 +2306,9 @@ unsafe fn public_window_callback_inner<T: 'static>(
                 event: Focused(true),
             });
 
+            #[cfg(feature = "accesskit")]
+            userdata.accessibility.set_focused(true);
+
             0
         }
 
@@ -1684,8 +2320,9 @@ unsafe fn public_window_callback_inner<T: 'static>(
             };
             for windows_keycode in event::get_pressed_keys() {
                 let scancode =
-                    winuser::MapVirtualKeyA(windows_keycode as _, winuser::MAPVK_VK_TO_VSC);
-                let virtual_keycode = event::vkey_to_winit_vkey(windows_keycode);
+                    winuser::MapVirtualKeyA(windows_keycode as _, winuser::MAPVK_VK_TO_VSC_EX);
+                let physical_key = event::scancode_to_physicalkey(scancode);
+                let logical_key = event::vkey_to_key(windows_keycode);
 
                 #[allow(deprecated)]
                 userdata.send_event(Event::WindowEvent {
@@ -1693,10 +2330,18 @@ unsafe fn public_window_callback_inner<T: 'static>(
                     event: WindowEvent::KeyboardInput {
                         device_id: DEVICE_ID,
                         input: KeyboardInput {
-                            scancode,
-                            virtual_keycode,
+                            physical_key,
+                            logical_key,
+                            text: None,
+                            repeat: false,
                             state: Released,
                             modifiers: event::get_key_mods(),
+                            platform_specific: KeyEventExtra {
+                                text_with_all_modifiers: None,
+                                key_without_modifiers: event::vkey_to_key_without_modifiers(
+                                    windows_keycode,
+                                ),
+                            },
                         },
                         is_synthetic: true,
                     },
@@ -1713,9 +2358,19 @@ unsafe fn public_window_callback_inner<T: 'static>(
                 window_id: RootWindowId(WindowId(window)),
                 event: Focused(false),
             });
+
+            #[cfg(feature = "accesskit")]
+            userdata.accessibility.set_focused(false);
+
             0
         }
 
+        #[cfg(feature = "accesskit")]
+        winuser::WM_GETOBJECT => userdata
+            .accessibility
+            .handle_wm_getobject(wparam, lparam)
+            .unwrap_or_else(|| winuser::DefWindowProcW(window, msg, wparam, lparam)),
+
         winuser::WM_SETCURSOR => {
             let set_cursor_to = {
                 let window_state = userdata.window_state.lock();
@@ -1772,6 +2427,61 @@ unsafe fn public_window_callback_inner<T: 'static>(
             0
         }
 
+        // Only sent with per-monitor v2 awareness, right before `WM_DPICHANGED`, to ask for the
+        // outer size of the window at the new DPI. Answering with the size `WM_DPICHANGED` picks
+        // makes the rect it suggests match, so that the window doesn't jump around while it's
+        // dragged between monitors with different DPIs.
+        WM_GETDPISCALEDSIZE => {
+            let new_dpi = wparam as u32;
+            let new_scale_factor = dpi_to_scale_factor(new_dpi);
+
+            let (old_scale_factor, allow_resize) = {
+                let window_state = userdata.window_state.lock();
+                (
+                    window_state.scale_factor,
+                    window_state.fullscreen.is_none()
+                        && !window_state.window_flags().contains(WindowFlags::MAXIMIZED),
+                )
+            };
+            // Let Windows scale the outer size linearly otherwise.
+            if !allow_resize {
+                return FALSE as LRESULT;
+            }
+
+            let mut client_rect: RECT = mem::zeroed();
+            winuser::GetClientRect(window, &mut client_rect);
+            let new_physical_inner_size = PhysicalSize::new(
+                (client_rect.right - client_rect.left) as u32,
+                (client_rect.bottom - client_rect.top) as u32,
+            )
+            .to_logical::<f64>(old_scale_factor)
+            .to_physical::<u32>(new_scale_factor);
+
+            let style = winuser::GetWindowLongW(window, winuser::GWL_STYLE) as _;
+            let style_ex = winuser::GetWindowLongW(window, winuser::GWL_EXSTYLE) as _;
+            let inner_rect = RECT {
+                left: 0,
+                top: 0,
+                right: new_physical_inner_size.width as LONG,
+                bottom: new_physical_inner_size.height as LONG,
+            };
+            match util::adjust_window_rect_for_dpi(
+                window,
+                style,
+                style_ex,
+                inner_rect,
+                Some(new_dpi),
+            ) {
+                Some(outer_rect) => {
+                    let size = &mut *(lparam as *mut SIZE);
+                    size.cx = outer_rect.right - outer_rect.left;
+                    size.cy = outer_rect.bottom - outer_rect.top;
+                    TRUE as LRESULT
+                }
+                None => FALSE as LRESULT,
+            }
+        }
+
         // Only sent on Windows 8.1 or newer. On Windows 7 and older user has to log out to change
         // DPI, therefore all applications are closed while DPI is changing.
         winuser::WM_DPICHANGED => {
@@ -1813,9 +2523,16 @@ unsafe fn public_window_callback_inner<T: 'static>(
             // let margin_right: i32;
             // let margin_bottom: i32;
             {
-                let adjusted_rect =
-                    util::adjust_window_rect_with_styles(window, style, style_ex, suggested_rect)
-                        .unwrap_or(suggested_rect);
+                // The decorations are those at the new DPI, which the window may not have switched
+                // to yet with per-monitor v1 awareness.
+                let adjusted_rect = util::adjust_window_rect_for_dpi(
+                    window,
+                    style,
+                    style_ex,
+                    suggested_rect,
+                    Some(new_dpi_x),
+                )
+                .unwrap_or(suggested_rect);
                 margin_left = suggested_rect.left - adjusted_rect.left;
                 margin_top = suggested_rect.top - adjusted_rect.top;
                 // margin_right = adjusted_rect.right - suggested_rect.right;
@@ -1842,7 +2559,7 @@ unsafe fn public_window_callback_inner<T: 'static>(
 
             // `allow_resize` prevents us from re-applying DPI adjustment to the restored size after
             // exiting fullscreen (the restored size is already DPI adjusted).
-            let mut new_physical_inner_size = match allow_resize {
+            let new_physical_inner_size = match allow_resize {
                 // We calculate our own size because the default suggested rect doesn't do a great job
                 // of preserving the window's logical size.
                 true => old_physical_inner_size
@@ -1851,13 +2568,18 @@ unsafe fn public_window_callback_inner<T: 'static>(
                 false => old_physical_inner_size,
             };
 
+            // The window state uses `parking_lot`, but the writer shares the size through `std`.
+            let new_physical_inner_size = Arc::new(std::sync::Mutex::new(new_physical_inner_size));
             let _ = userdata.send_event(Event::WindowEvent {
                 window_id: RootWindowId(WindowId(window)),
                 event: ScaleFactorChanged {
                     scale_factor: new_scale_factor,
-                    new_inner_size: &mut new_physical_inner_size,
+                    inner_size_writer: InnerSizeWriter::new(Arc::downgrade(
+                        &new_physical_inner_size,
+                    )),
                 },
             });
+            let new_physical_inner_size = *new_physical_inner_size.lock().unwrap();
 
             let dragging_window: bool;
 
@@ -1888,11 +2610,12 @@ unsafe fn public_window_callback_inner<T: 'static>(
                     bottom: suggested_ul.1 + new_physical_inner_size.height as LONG,
                 };
 
-                conservative_rect = util::adjust_window_rect_with_styles(
+                conservative_rect = util::adjust_window_rect_for_dpi(
                     window,
                     style,
                     style_ex,
                     conservative_rect,
+                    Some(new_dpi_x),
                 )
                 .unwrap_or(conservative_rect);
 
@@ -1989,6 +2712,30 @@ unsafe fn public_window_callback_inner<T: 'static>(
             0
         }
 
+        winuser::WM_INPUTLANGCHANGE => {
+            userdata.send_event(Event::KeyboardLayoutChanged);
+            winuser::DefWindowProcW(window, msg, wparam, lparam)
+        }
+
+        winuser::WM_COMMAND if HIWORD(wparam as DWORD) == THBN_CLICKED => {
+            use crate::event::WindowEvent::ThumbnailButtonClicked;
+
+            let index = LOWORD(wparam as DWORD) as usize;
+            let id = userdata
+                .window_state
+                .lock()
+                .thumbnail_buttons
+                .get(index)
+                .map(|button| button.id);
+            if let Some(id) = id {
+                userdata.send_event(Event::WindowEvent {
+                    window_id: RootWindowId(WindowId(window)),
+                    event: ThumbnailButtonClicked(id),
+                });
+            }
+            0
+        }
+
         winuser::WM_SETTINGCHANGE => {
             use crate::event::WindowEvent::ThemeChanged;
 
@@ -2021,7 +2768,22 @@ unsafe fn public_window_callback_inner<T: 'static>(
                     f.set(WindowFlags::MARKER_RETAIN_STATE_ON_SIZE, wparam != 0)
                 });
                 0
+            } else if msg == *TASKBAR_BUTTON_CREATED_MSG_ID {
+                let mut window_state = userdata.window_state.lock();
+                window_state.taskbar_button_created = true;
+                window_state.thumbnail_toolbar_added = false;
+                platform_window::taskbar_update_thumbnail(window, &mut window_state);
+                0
             } else {
+                #[cfg(feature = "accesskit")]
+                if msg == *ACCESSIBILITY_ACTION_MSG_ID {
+                    let request = Box::from_raw(lparam as *mut ActionRequest);
+                    userdata.send_event(Event::WindowEvent {
+                        window_id: RootWindowId(WindowId(window)),
+                        event: WindowEvent::AccessibilityActionRequested(*request),
+                    });
+                    return 0;
+                }
                 winuser::DefWindowProcW(window, msg, wparam, lparam)
             }
         }
@@ -2047,6 +2809,7 @@ unsafe extern "system" fn thread_event_target_callback<T: 'static>(
         return winuser::DefWindowProcW(window, msg, wparam, lparam);
     }
     let userdata = Box::from_raw(userdata_ptr);
+    trace_span!("thread message", msg);
 
     if msg != winuser::WM_PAINT {
         winuser::RedrawWindow(
@@ -2064,10 +2827,58 @@ unsafe extern "system" fn thread_event_target_callback<T: 'static>(
     // the git blame and history would be preserved.
     let callback = || match msg {
         winuser::WM_NCDESTROY => {
+            if let Some(unregister) = *util::WTS_UNREGISTER_SESSION_NOTIFICATION {
+                unregister(window);
+            }
+            if !userdata.power_saving_notification.is_null() {
+                winuser::UnregisterPowerSettingNotification(userdata.power_saving_notification);
+            }
             winuser::SetWindowLongPtrW(window, winuser::GWL_USERDATA, 0);
             userdata_removed = true;
             0
         }
+
+        // Both are broadcast to the top-level windows, including this one.
+        winuser::WM_SETTINGCHANGE | winuser::WM_DWMCOLORIZATIONCOLORCHANGED => {
+            let preferences = util::system_preferences();
+            if preferences != userdata.system_preferences.replace(preferences) {
+                userdata.send_event(Event::SystemPreferencesChanged);
+            }
+            winuser::DefWindowProcW(window, msg, wparam, lparam)
+        }
+
+        winuser::WM_POWERBROADCAST => {
+            match wparam {
+                winuser::PBT_APMSUSPEND => {
+                    userdata.send_event(Event::SessionSleep(SessionSleepReason::SystemSleep))
+                }
+                // Sent on every resume, unlike `PBT_APMRESUMESUSPEND` which requires user input.
+                winuser::PBT_APMRESUMEAUTOMATIC => {
+                    userdata.send_event(Event::SessionWake(SessionSleepReason::SystemSleep))
+                }
+                winuser::PBT_APMPOWERSTATUSCHANGE | winuser::PBT_POWERSETTINGCHANGE => {
+                    let state = util::power_state();
+                    if state != userdata.power_state.replace(state) {
+                        userdata.send_event(Event::PowerStateChanged);
+                    }
+                }
+                _ => (),
+            }
+            TRUE as LRESULT
+        }
+
+        winuser::WM_WTSSESSION_CHANGE => {
+            match wparam {
+                WTS_SESSION_LOCK => {
+                    userdata.send_event(Event::SessionSleep(SessionSleepReason::Locked))
+                }
+                WTS_SESSION_UNLOCK => {
+                    userdata.send_event(Event::SessionWake(SessionSleepReason::Locked))
+                }
+                _ => (),
+            }
+            0
+        }
         // Because WM_PAINT comes after all other messages, we use it during modal loops to detect
         // when the event queue has been emptied. See `process_event` for more details.
         winuser::WM_PAINT => {
@@ -2091,6 +2902,9 @@ unsafe extern "system" fn thread_event_target_callback<T: 'static>(
                     assert!(flush_paint_messages(None, &userdata.event_loop_runner));
                     userdata.event_loop_runner.redraw_events_cleared();
                     process_control_flow(&userdata.event_loop_runner);
+                    if userdata.user_events_deferred.replace(false) {
+                        winuser::PostMessageW(window, *USER_EVENT_MSG_ID, 0, 0);
+                    }
                 }
             }
 
@@ -2114,101 +2928,14 @@ unsafe extern "system" fn thread_event_target_callback<T: 'static>(
         }
 
         winuser::WM_INPUT => {
-            use crate::event::{
-                DeviceEvent::{Button, Key, Motion, MouseMotion, MouseWheel},
-                ElementState::{Pressed, Released},
-                MouseScrollDelta::LineDelta,
-            };
-
             if let Some(data) = raw_input::get_raw_input_data(lparam as _) {
-                let device_id = wrap_device_id(data.header.hDevice as _);
-
-                if data.header.dwType == winuser::RIM_TYPEMOUSE {
-                    let mouse = data.data.mouse();
-
-                    if util::has_flag(mouse.usFlags, winuser::MOUSE_MOVE_RELATIVE) {
-                        let x = mouse.lLastX as f64;
-                        let y = mouse.lLastY as f64;
-
-                        if x != 0.0 {
-                            userdata.send_event(Event::DeviceEvent {
-                                device_id,
-                                event: Motion { axis: 0, value: x },
-                            });
-                        }
-
-                        if y != 0.0 {
-                            userdata.send_event(Event::DeviceEvent {
-                                device_id,
-                                event: Motion { axis: 1, value: y },
-                            });
-                        }
-
-                        if x != 0.0 || y != 0.0 {
-                            userdata.send_event(Event::DeviceEvent {
-                                device_id,
-                                event: MouseMotion { delta: (x, y) },
-                            });
-                        }
-                    }
-
-                    if util::has_flag(mouse.usButtonFlags, winuser::RI_MOUSE_WHEEL) {
-                        let delta =
-                            mouse.usButtonData as SHORT as f32 / winuser::WHEEL_DELTA as f32;
-                        userdata.send_event(Event::DeviceEvent {
-                            device_id,
-                            event: MouseWheel {
-                                delta: LineDelta(0.0, delta),
-                            },
-                        });
-                    }
-
-                    let button_state = raw_input::get_raw_mouse_button_state(mouse.usButtonFlags);
-                    // Left, middle, and right, respectively.
-                    for (index, state) in button_state.iter().enumerate() {
-                        if let Some(state) = *state {
-                            // This gives us consistency with X11, since there doesn't
-                            // seem to be anything else reasonable to do for a mouse
-                            // button ID.
-                            let button = (index + 1) as _;
-                            userdata.send_event(Event::DeviceEvent {
-                                device_id,
-                                event: Button { button, state },
-                            });
-                        }
-                    }
-                } else if data.header.dwType == winuser::RIM_TYPEKEYBOARD {
-                    let keyboard = data.data.keyboard();
-
-                    let pressed = keyboard.Message == winuser::WM_KEYDOWN
-                        || keyboard.Message == winuser::WM_SYSKEYDOWN;
-                    let released = keyboard.Message == winuser::WM_KEYUP
-                        || keyboard.Message == winuser::WM_SYSKEYUP;
-
-                    if pressed || released {
-                        let state = if pressed { Pressed } else { Released };
-
-                        let scancode = keyboard.MakeCode as _;
-                        let extended = util::has_flag(keyboard.Flags, winuser::RI_KEY_E0 as _)
-                            | util::has_flag(keyboard.Flags, winuser::RI_KEY_E1 as _);
-
-                        if let Some((vkey, scancode)) =
-                            handle_extended_keys(keyboard.VKey as _, scancode, extended)
-                        {
-                            let virtual_keycode = vkey_to_winit_vkey(vkey);
-
-                            #[allow(deprecated)]
-                            userdata.send_event(Event::DeviceEvent {
-                                device_id,
-                                event: Key(KeyboardInput {
-                                    scancode,
-                                    state,
-                                    virtual_keycode,
-                                    modifiers: event::get_key_mods(),
-                                }),
-                            });
-                        }
-                    }
+                handle_raw_input(&userdata, data);
+            }
+            // Drain the input queued since, rather than waking up for every message of
+            // high-frequency devices.
+            if userdata.buffered_raw_input {
+                for data in raw_input::get_raw_input_buffer() {
+                    handle_raw_input(&userdata, data);
                 }
             }
 
@@ -2216,9 +2943,40 @@ unsafe extern "system" fn thread_event_target_callback<T: 'static>(
         }
 
         _ if msg == *USER_EVENT_MSG_ID => {
-            if let Ok(event) = userdata.user_event_receiver.recv() {
-                userdata.send_event(Event::UserEvent(event));
+            // The events left behind by a `PerEvent` event are delivered once the `WM_PAINT`
+            // handler has ended the iteration.
+            if !userdata.user_events_deferred.get() {
+                userdata.user_wakeup_pending.store(false, Ordering::SeqCst);
+                while let Ok((event, wakeup)) = userdata.user_event_receiver.try_recv() {
+                    userdata.send_event(Event::UserEvent(event));
+                    if wakeup == ProxyWakeup::PerEvent {
+                        userdata.user_events_deferred.set(true);
+                        break;
+                    }
+                }
+            }
+            0
+        }
+        #[cfg(feature = "gamepad")]
+        _ if msg == *GAMEPAD_EVENT_MSG_ID => {
+            while let Ok((gamepad_id, event)) = userdata.gamepad_event_receiver.try_recv() {
+                userdata.send_event(Event::GamepadEvent { gamepad_id, event });
+            }
+            0
+        }
+        _ if msg == *RAW_INPUT_BATCH_MSG_ID => {
+            let batch = mem::take(&mut *userdata.raw_input_batch.lock());
+            for (timestamp, RawInputReport(data)) in batch {
+                userdata
+                    .event_loop_runner
+                    .set_input_timestamp(Some(timestamp));
+                handle_raw_input(&userdata, data);
             }
+            userdata.event_loop_runner.set_input_timestamp(None);
+            0
+        }
+        _ if msg == *MEMORY_WARNING_MSG_ID => {
+            userdata.send_event(Event::MemoryWarning);
             0
         }
         _ if msg == *EXEC_MSG_ID => {
@@ -2279,3 +3037,107 @@ unsafe extern "system" fn thread_event_target_callback<T: 'static>(
     }
     result
 }
+
+/// Sends the device events of a raw input report.
+unsafe fn handle_raw_input<T: 'static>(userdata: &ThreadMsgTargetData<T>, data: winuser::RAWINPUT) {
+    use crate::event::{
+        DeviceEvent::{Button, Key, Motion, MouseMotion, MouseWheel},
+        ElementState::{Pressed, Released},
+        MouseScrollDelta::LineDelta,
+    };
+
+    let device_id = wrap_device_id(data.header.hDevice as _);
+
+    if data.header.dwType == winuser::RIM_TYPEMOUSE {
+        let mouse = data.data.mouse();
+
+        if util::has_flag(mouse.usFlags, winuser::MOUSE_MOVE_RELATIVE) {
+            let x = mouse.lLastX as f64;
+            let y = mouse.lLastY as f64;
+
+            if x != 0.0 {
+                userdata.send_event(Event::DeviceEvent {
+                    device_id,
+                    event: Motion { axis: 0, value: x },
+                });
+            }
+
+            if y != 0.0 {
+                userdata.send_event(Event::DeviceEvent {
+                    device_id,
+                    event: Motion { axis: 1, value: y },
+                });
+            }
+
+            if x != 0.0 || y != 0.0 {
+                userdata.send_event(Event::DeviceEvent {
+                    device_id,
+                    event: MouseMotion { delta: (x, y) },
+                });
+            }
+        }
+
+        if util::has_flag(mouse.usButtonFlags, winuser::RI_MOUSE_WHEEL) {
+            let delta = mouse.usButtonData as SHORT as f32 / winuser::WHEEL_DELTA as f32;
+            userdata.send_event(Event::DeviceEvent {
+                device_id,
+                event: MouseWheel {
+                    delta: LineDelta(0.0, delta),
+                },
+            });
+        }
+
+        let button_state = raw_input::get_raw_mouse_button_state(mouse.usButtonFlags);
+        // Left, middle, and right, respectively.
+        for (index, state) in button_state.iter().enumerate() {
+            if let Some(state) = *state {
+                // This gives us consistency with X11, since there doesn't
+                // seem to be anything else reasonable to do for a mouse
+                // button ID.
+                let button = (index + 1) as _;
+                userdata.send_event(Event::DeviceEvent {
+                    device_id,
+                    event: Button { button, state },
+                });
+            }
+        }
+    } else if data.header.dwType == winuser::RIM_TYPEKEYBOARD {
+        let keyboard = data.data.keyboard();
+
+        let pressed =
+            keyboard.Message == winuser::WM_KEYDOWN || keyboard.Message == winuser::WM_SYSKEYDOWN;
+        let released =
+            keyboard.Message == winuser::WM_KEYUP || keyboard.Message == winuser::WM_SYSKEYUP;
+
+        if pressed || released {
+            let state = if pressed { Pressed } else { Released };
+
+            let scancode = keyboard.MakeCode as _;
+            let extended = util::has_flag(keyboard.Flags, winuser::RI_KEY_E0 as _)
+                | util::has_flag(keyboard.Flags, winuser::RI_KEY_E1 as _);
+
+            if let Some((vkey, scancode)) =
+                handle_extended_keys(keyboard.VKey as _, scancode, extended)
+            {
+                let logical_key = vkey_to_key(vkey);
+
+                #[allow(deprecated)]
+                userdata.send_event(Event::DeviceEvent {
+                    device_id,
+                    event: Key(KeyboardInput {
+                        physical_key: event::scancode_to_physicalkey(scancode),
+                        state,
+                        logical_key,
+                        text: None,
+                        repeat: false,
+                        modifiers: event::get_key_mods(),
+                        platform_specific: KeyEventExtra {
+                            text_with_all_modifiers: None,
+                            key_without_modifiers: event::vkey_to_key_without_modifiers(vkey),
+                        },
+                    }),
+                });
+            }
+        }
+    }
+}
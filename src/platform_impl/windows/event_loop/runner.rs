@@ -4,23 +4,29 @@ use std::{
     collections::{HashSet, VecDeque},
     mem, panic, ptr,
     rc::Rc,
+    sync::{Arc, Mutex},
     time::Instant,
 };
 
 use winapi::{
-    shared::{minwindef::DWORD, windef::HWND},
+    shared::{
+        minwindef::{DWORD, LPARAM, LRESULT, UINT, WPARAM},
+        windef::HWND,
+    },
     um::winuser,
 };
 
 use crate::{
     dpi::PhysicalSize,
-    event::{Event, StartCause, WindowEvent},
+    event::{Event, InnerSizeWriter, StartCause, WindowEvent},
     event_loop::ControlFlow,
     platform_impl::platform::util,
     window::WindowId,
 };
 
 pub(crate) type EventLoopRunnerShared<T> = Rc<EventLoopRunner<T>>;
+
+pub(crate) type WindowProcHook = dyn FnMut(HWND, UINT, WPARAM, LPARAM) -> Option<LRESULT>;
 pub(crate) struct EventLoopRunner<T: 'static> {
     // The event loop's win32 handles
     pub(super) thread_msg_target: HWND,
@@ -30,12 +36,19 @@ pub(crate) struct EventLoopRunner<T: 'static> {
     runner_state: Cell<RunnerState>,
     last_events_cleared: Cell<Instant>,
 
-    event_handler: Cell<Option<Box<dyn FnMut(Event<'_, T>, &mut ControlFlow)>>>,
+    event_handler: Cell<Option<Box<dyn FnMut(Event<T>, &mut ControlFlow)>>>,
     event_buffer: RefCell<VecDeque<BufferedEvent<T>>>,
 
     owned_windows: Cell<HashSet<HWND>>,
 
+    window_proc_hook: Cell<Option<Box<WindowProcHook>>>,
+    // Set when the hook is replaced, so that a hook replacing itself isn't put back afterwards.
+    window_proc_hook_replaced: Cell<bool>,
+
     panic_error: Cell<Option<PanicError>>,
+
+    // Time of the raw input read by the input thread that is being dispatched
+    input_timestamp: Cell<Option<Instant>>,
 }
 
 pub type PanicError = Box<dyn Any + Send + 'static>;
@@ -58,7 +71,7 @@ enum RunnerState {
 }
 
 enum BufferedEvent<T: 'static> {
-    Event(Event<'static, T>),
+    Event(Event<T>),
     ScaleFactorChanged(WindowId, f64, PhysicalSize<u32>),
 }
 
@@ -74,16 +87,19 @@ impl<T> EventLoopRunner<T> {
             event_handler: Cell::new(None),
             event_buffer: RefCell::new(VecDeque::new()),
             owned_windows: Cell::new(HashSet::new()),
+            window_proc_hook: Cell::new(None),
+            window_proc_hook_replaced: Cell::new(false),
+            input_timestamp: Cell::new(None),
         }
     }
 
     pub(crate) unsafe fn set_event_handler<F>(&self, f: F)
     where
-        F: FnMut(Event<'_, T>, &mut ControlFlow),
+        F: FnMut(Event<T>, &mut ControlFlow),
     {
         let old_event_handler = self.event_handler.replace(mem::transmute::<
-            Option<Box<dyn FnMut(Event<'_, T>, &mut ControlFlow)>>,
-            Option<Box<dyn FnMut(Event<'_, T>, &mut ControlFlow)>>,
+            Option<Box<dyn FnMut(Event<T>, &mut ControlFlow)>>,
+            Option<Box<dyn FnMut(Event<T>, &mut ControlFlow)>>,
         >(Some(Box::new(f))));
         assert!(old_event_handler.is_none());
     }
@@ -99,14 +115,46 @@ impl<T> EventLoopRunner<T> {
             event_handler,
             event_buffer: _,
             owned_windows: _,
+            window_proc_hook: _,
+            window_proc_hook_replaced: _,
+            input_timestamp,
         } = self;
         runner_state.set(RunnerState::Uninitialized);
         panic_error.set(None);
         control_flow.set(ControlFlow::Poll);
         event_handler.set(None);
+        input_timestamp.set(None);
+    }
+
+    /// Puts the state of the running loop aside and sends the following events to `f`, until
+    /// `leave_nested_loop` is called. Must be called from within the event handler.
+    pub(crate) unsafe fn enter_nested_loop<F>(&self, f: F) -> NestedLoopState
+    where
+        F: FnMut(Event<T>, &mut ControlFlow),
+    {
+        let outer = NestedLoopState {
+            runner_state: self.runner_state.replace(RunnerState::Uninitialized),
+            last_events_cleared: self.last_events_cleared.get(),
+        };
+        // The outer handler's control flow was taken out for its call, and is put back after it.
+        self.control_flow.set(ControlFlow::Poll);
+        self.set_event_handler(f);
+        outer
+    }
+
+    pub(crate) fn leave_nested_loop(&self, outer: NestedLoopState) {
+        self.event_handler.set(None);
+        self.runner_state.set(outer.runner_state);
+        self.last_events_cleared.set(outer.last_events_cleared);
     }
 }
 
+/// The state of a loop interrupted by a nested loop, see `enter_nested_loop`.
+pub(crate) struct NestedLoopState {
+    runner_state: RunnerState,
+    last_events_cleared: Instant,
+}
+
 /// State retrieval functions.
 impl<T> EventLoopRunner<T> {
     pub fn thread_msg_target(&self) -> HWND {
@@ -136,6 +184,10 @@ impl<T> EventLoopRunner<T> {
         self.runner_state.get() != RunnerState::Idle
     }
 
+    pub fn input_timestamp(&self) -> Option<Instant> {
+        self.input_timestamp.get()
+    }
+
     pub fn should_buffer(&self) -> bool {
         let handler = self.event_handler.take();
         let should_buffer = handler.is_none();
@@ -146,6 +198,10 @@ impl<T> EventLoopRunner<T> {
 
 /// Misc. functions
 impl<T> EventLoopRunner<T> {
+    pub fn set_input_timestamp(&self, timestamp: Option<Instant>) {
+        self.input_timestamp.set(timestamp);
+    }
+
     pub fn catch_unwind<R>(&self, f: impl FnOnce() -> R) -> Option<R> {
         let panic_error = self.panic_error.take();
         if panic_error.is_none() {
@@ -201,7 +257,7 @@ impl<T> EventLoopRunner<T> {
         self.move_state_to(RunnerState::HandlingMainEvents);
     }
 
-    pub(crate) unsafe fn send_event(&self, event: Event<'_, T>) {
+    pub(crate) unsafe fn send_event(&self, event: Event<T>) {
         if let Event::RedrawRequested(_) = event {
             if self.runner_state.get() != RunnerState::HandlingRedrawEvents {
                 warn!("RedrawRequested dispatched without explicit MainEventsCleared");
@@ -235,7 +291,30 @@ impl<T> EventLoopRunner<T> {
         self.move_state_to(RunnerState::Destroyed);
     }
 
-    unsafe fn call_event_handler(&self, event: Event<'_, T>) {
+    pub(crate) fn set_window_proc_hook(&self, hook: Option<Box<WindowProcHook>>) {
+        self.window_proc_hook.set(hook);
+        self.window_proc_hook_replaced.set(true);
+    }
+
+    /// Passes a message to the window procedure hook, returning the result of the message if the
+    /// hook consumed it. The hook doesn't see the messages it sends itself.
+    pub(crate) fn call_window_proc_hook(
+        &self,
+        window: HWND,
+        msg: UINT,
+        wparam: WPARAM,
+        lparam: LPARAM,
+    ) -> Option<LRESULT> {
+        self.window_proc_hook_replaced.set(false);
+        let mut hook = self.window_proc_hook.take()?;
+        let result = hook(window, msg, wparam, lparam);
+        if !self.window_proc_hook_replaced.get() {
+            self.window_proc_hook.set(Some(hook));
+        }
+        result
+    }
+
+    unsafe fn call_event_handler(&self, event: Event<T>) {
         self.catch_unwind(|| {
             let mut control_flow = self.control_flow.take();
             let mut event_handler = self.event_handler.take()
@@ -409,31 +488,47 @@ impl<T> EventLoopRunner<T> {
 }
 
 impl<T> BufferedEvent<T> {
-    pub fn from_event(event: Event<'_, T>) -> BufferedEvent<T> {
+    pub fn from_event(event: Event<T>) -> BufferedEvent<T> {
         match event {
             Event::WindowEvent {
                 event:
                     WindowEvent::ScaleFactorChanged {
                         scale_factor,
-                        new_inner_size,
+                        inner_size_writer,
                     },
                 window_id,
-            } => BufferedEvent::ScaleFactorChanged(window_id, scale_factor, *new_inner_size),
-            event => BufferedEvent::Event(event.to_static().unwrap()),
+            } => match inner_size_writer.new_inner_size.upgrade() {
+                // The size gets applied once the buffered event is dispatched instead.
+                Some(new_inner_size) => BufferedEvent::ScaleFactorChanged(
+                    window_id,
+                    scale_factor,
+                    *new_inner_size.lock().unwrap(),
+                ),
+                None => BufferedEvent::Event(Event::WindowEvent {
+                    window_id,
+                    event: WindowEvent::ScaleFactorChanged {
+                        scale_factor,
+                        inner_size_writer,
+                    },
+                }),
+            },
+            event => BufferedEvent::Event(event),
         }
     }
 
-    pub fn dispatch_event(self, dispatch: impl FnOnce(Event<'_, T>)) {
+    pub fn dispatch_event(self, dispatch: impl FnOnce(Event<T>)) {
         match self {
             Self::Event(event) => dispatch(event),
-            Self::ScaleFactorChanged(window_id, scale_factor, mut new_inner_size) => {
+            Self::ScaleFactorChanged(window_id, scale_factor, new_inner_size) => {
+                let new_inner_size = Arc::new(Mutex::new(new_inner_size));
                 dispatch(Event::WindowEvent {
                     window_id,
                     event: WindowEvent::ScaleFactorChanged {
                         scale_factor,
-                        new_inner_size: &mut new_inner_size,
+                        inner_size_writer: InnerSizeWriter::new(Arc::downgrade(&new_inner_size)),
                     },
                 });
+                let new_inner_size = *new_inner_size.lock().unwrap();
                 util::set_inner_size_physical(
                     (window_id.0).0,
                     new_inner_size.width as _,
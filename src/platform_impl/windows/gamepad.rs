@@ -0,0 +1,184 @@
+//! Gamepad support backed by XInput.
+//!
+//! XInput has no notifications, so a background thread polls the controllers and forwards the
+//! changes to the thread message target of the event loop.
+
+use std::{
+    mem,
+    sync::mpsc::Sender,
+    thread,
+    time::{Duration, Instant},
+};
+
+use winapi::{
+    shared::{minwindef::WORD, windef::HWND, winerror::ERROR_SUCCESS},
+    um::{
+        winuser,
+        xinput::{self, *},
+    },
+};
+
+use crate::{
+    event::ElementState,
+    gamepad::{GamepadAxis, GamepadButton, GamepadEvent, GamepadId},
+};
+
+/// How often connected controllers are polled.
+const POLL_INTERVAL: Duration = Duration::from_millis(4);
+
+/// How often empty slots are checked for new controllers. Calling `XInputGetState` on an empty
+/// slot is expensive, so this is done less frequently.
+const CONNECT_INTERVAL: Duration = Duration::from_secs(1);
+
+const BUTTONS: [(WORD, GamepadButton); 14] = [
+    (XINPUT_GAMEPAD_DPAD_UP, GamepadButton::DPadUp),
+    (XINPUT_GAMEPAD_DPAD_DOWN, GamepadButton::DPadDown),
+    (XINPUT_GAMEPAD_DPAD_LEFT, GamepadButton::DPadLeft),
+    (XINPUT_GAMEPAD_DPAD_RIGHT, GamepadButton::DPadRight),
+    (XINPUT_GAMEPAD_START, GamepadButton::Start),
+    (XINPUT_GAMEPAD_BACK, GamepadButton::Select),
+    (XINPUT_GAMEPAD_LEFT_THUMB, GamepadButton::LeftThumb),
+    (XINPUT_GAMEPAD_RIGHT_THUMB, GamepadButton::RightThumb),
+    (XINPUT_GAMEPAD_LEFT_SHOULDER, GamepadButton::LeftShoulder),
+    (XINPUT_GAMEPAD_RIGHT_SHOULDER, GamepadButton::RightShoulder),
+    (XINPUT_GAMEPAD_A, GamepadButton::South),
+    (XINPUT_GAMEPAD_B, GamepadButton::East),
+    (XINPUT_GAMEPAD_X, GamepadButton::West),
+    (XINPUT_GAMEPAD_Y, GamepadButton::North),
+];
+
+struct Slot {
+    id: GamepadId,
+    packet_number: u32,
+    state: xinput::XINPUT_GAMEPAD,
+}
+
+/// Spawns the thread polling the controllers.
+///
+/// Each batch of events is followed by a `msg_id` message posted to `target_window`. The thread
+/// stops once `target_window` is destroyed.
+pub fn spawn(target_window: HWND, msg_id: u32, sender: Sender<(GamepadId, GamepadEvent)>) {
+    let target_window = target_window as usize;
+    let result = thread::Builder::new()
+        .name("winit gamepad".into())
+        .spawn(move || poll_thread(target_window as HWND, msg_id, sender));
+    if let Err(err) = result {
+        warn!("Failed to start monitoring gamepads: {}", err);
+    }
+}
+
+fn poll_thread(target_window: HWND, msg_id: u32, sender: Sender<(GamepadId, GamepadEvent)>) {
+    let mut slots: [Option<Slot>; xinput::XUSER_MAX_COUNT as usize] = Default::default();
+    let mut last_connect_check: Option<Instant> = None;
+    let mut events = Vec::new();
+
+    loop {
+        let check_connections =
+            last_connect_check.map_or(true, |instant| instant.elapsed() >= CONNECT_INTERVAL);
+        if check_connections {
+            if unsafe { winuser::IsWindow(target_window) } == 0 {
+                return;
+            }
+            last_connect_check = Some(Instant::now());
+        }
+
+        for (index, slot) in slots.iter_mut().enumerate() {
+            if slot.is_none() && !check_connections {
+                continue;
+            }
+
+            let mut state: xinput::XINPUT_STATE = unsafe { mem::zeroed() };
+            let result = unsafe { xinput::XInputGetState(index as u32, &mut state) };
+            if result != ERROR_SUCCESS {
+                if let Some(slot) = slot.take() {
+                    events.push((slot.id, GamepadEvent::Disconnected));
+                }
+                continue;
+            }
+
+            let slot = slot.get_or_insert_with(|| {
+                let id = GamepadId::next();
+                events.push((
+                    id,
+                    GamepadEvent::Connected {
+                        name: String::new(),
+                    },
+                ));
+                Slot {
+                    id,
+                    packet_number: 0,
+                    state: unsafe { mem::zeroed() },
+                }
+            });
+            if slot.packet_number != state.dwPacketNumber {
+                slot.packet_number = state.dwPacketNumber;
+                diff_state(slot.id, &slot.state, &state.Gamepad, &mut events);
+                slot.state = state.Gamepad;
+            }
+        }
+
+        if !events.is_empty() {
+            for event in events.drain(..) {
+                if sender.send(event).is_err() {
+                    return;
+                }
+            }
+            if unsafe { winuser::PostMessageW(target_window, msg_id, 0, 0) } == 0 {
+                return;
+            }
+        }
+
+        if slots.iter().any(Option::is_some) {
+            thread::sleep(POLL_INTERVAL);
+        } else {
+            thread::sleep(CONNECT_INTERVAL);
+        }
+    }
+}
+
+fn diff_state(
+    id: GamepadId,
+    old: &xinput::XINPUT_GAMEPAD,
+    new: &xinput::XINPUT_GAMEPAD,
+    events: &mut Vec<(GamepadId, GamepadEvent)>,
+) {
+    for &(mask, button) in &BUTTONS {
+        let pressed = new.wButtons & mask != 0;
+        if pressed != (old.wButtons & mask != 0) {
+            let state = if pressed {
+                ElementState::Pressed
+            } else {
+                ElementState::Released
+            };
+            events.push((id, GamepadEvent::Button { button, state }));
+        }
+    }
+
+    let sticks = [
+        (GamepadAxis::LeftStickX, old.sThumbLX, new.sThumbLX),
+        (GamepadAxis::LeftStickY, old.sThumbLY, new.sThumbLY),
+        (GamepadAxis::RightStickX, old.sThumbRX, new.sThumbRX),
+        (GamepadAxis::RightStickY, old.sThumbRY, new.sThumbRY),
+    ];
+    for (axis, old, new) in sticks {
+        if old != new {
+            let value = (new as f64 / i16::MAX as f64).max(-1.0);
+            events.push((id, GamepadEvent::Axis { axis, value }));
+        }
+    }
+
+    let triggers = [
+        (GamepadAxis::LeftTrigger, old.bLeftTrigger, new.bLeftTrigger),
+        (
+            GamepadAxis::RightTrigger,
+            old.bRightTrigger,
+            new.bRightTrigger,
+        ),
+    ];
+    for (axis, old, new) in triggers {
+        if old != new {
+            let value = new as f64 / u8::MAX as f64;
+            events.push((id, GamepadEvent::Axis { axis, value }));
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Pinch and rotation gestures backed by `WM_GESTURE`.
+//!
+//! Windows only sends `WM_GESTURE` to windows which aren't registered for `WM_TOUCH`, and for
+//! the touch pointers left to the default window procedure. The windows asking for gestures
+//! therefore don't report `WindowEvent::Touch`.
+
+#![allow(non_snake_case)]
+
+use std::{f64::consts::PI, mem};
+
+use winapi::shared::{
+    minwindef::{BOOL, DWORD, LPARAM, UINT},
+    ntdef::ULONGLONG,
+    windef::{HWND, POINTS},
+};
+
+use crate::event::{TouchPhase, WindowEvent};
+
+use super::DEVICE_ID;
+
+// From `winuser.h`, which `winapi` doesn't cover.
+#[allow(clippy::upper_case_acronyms)]
+type HGESTUREINFO = LPARAM;
+
+#[allow(clippy::upper_case_acronyms)]
+#[repr(C)]
+struct GESTUREINFO {
+    cbSize: UINT,
+    dwFlags: DWORD,
+    dwID: DWORD,
+    hwndTarget: HWND,
+    ptsLocation: POINTS,
+    dwInstanceID: DWORD,
+    dwSequenceID: DWORD,
+    ullArguments: ULONGLONG,
+    cbExtraArgs: UINT,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[repr(C)]
+struct GESTURECONFIG {
+    dwID: DWORD,
+    dwWant: DWORD,
+    dwBlock: DWORD,
+}
+
+const GF_BEGIN: DWORD = 0x1;
+const GF_END: DWORD = 0x4;
+const GID_ZOOM: DWORD = 3;
+const GID_ROTATE: DWORD = 5;
+const GC_ZOOM: DWORD = 0x1;
+const GC_ROTATE: DWORD = 0x1;
+
+type GetGestureInfo =
+    unsafe extern "system" fn(hGestureInfo: HGESTUREINFO, pGestureInfo: *mut GESTUREINFO) -> BOOL;
+type CloseGestureInfoHandle = unsafe extern "system" fn(hGestureInfo: HGESTUREINFO) -> BOOL;
+type SetGestureConfig = unsafe extern "system" fn(
+    hwnd: HWND,
+    dwReserved: DWORD,
+    cIDs: UINT,
+    pGestureConfig: *mut GESTURECONFIG,
+    cbSize: UINT,
+) -> BOOL;
+
+lazy_static! {
+    static ref GET_GESTURE_INFO: Option<GetGestureInfo> =
+        get_function!("user32.dll", GetGestureInfo);
+    static ref CLOSE_GESTURE_INFO_HANDLE: Option<CloseGestureInfoHandle> =
+        get_function!("user32.dll", CloseGestureInfoHandle);
+    static ref SET_GESTURE_CONFIG: Option<SetGestureConfig> =
+        get_function!("user32.dll", SetGestureConfig);
+}
+
+/// The progress of the gestures of a window, as `WM_GESTURE` reports absolute values.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct GestureState {
+    /// The distance between the fingers at the previous zoom message.
+    distance: u64,
+    /// The rotation since the start of the gesture at the previous rotate message, in radians.
+    angle: f64,
+}
+
+/// Asks for the rotate gesture, which is disabled by default, next to the zoom gesture.
+pub unsafe fn enable(window: HWND) {
+    if let Some(SetGestureConfig) = *SET_GESTURE_CONFIG {
+        let mut config = [
+            GESTURECONFIG {
+                dwID: GID_ZOOM,
+                dwWant: GC_ZOOM,
+                dwBlock: 0,
+            },
+            GESTURECONFIG {
+                dwID: GID_ROTATE,
+                dwWant: GC_ROTATE,
+                dwBlock: 0,
+            },
+        ];
+        SetGestureConfig(
+            window,
+            0,
+            config.len() as UINT,
+            config.as_mut_ptr(),
+            mem::size_of::<GESTURECONFIG>() as UINT,
+        );
+    }
+}
+
+/// Translates the gesture of a `WM_GESTURE` message, closing its handle.
+///
+/// Returns `None` for the gestures which aren't reported, whose message should go to the default
+/// window procedure instead.
+pub unsafe fn translate(lparam: LPARAM, state: &mut GestureState) -> Option<WindowEvent> {
+    let (GetGestureInfo, CloseGestureInfoHandle) =
+        match (*GET_GESTURE_INFO, *CLOSE_GESTURE_INFO_HANDLE) {
+            (Some(get), Some(close)) => (get, close),
+            _ => return None,
+        };
+    let mut info: GESTUREINFO = mem::zeroed();
+    info.cbSize = mem::size_of::<GESTUREINFO>() as UINT;
+    if GetGestureInfo(lparam, &mut info) == 0 {
+        return None;
+    }
+
+    let phase = if info.dwFlags & GF_BEGIN != 0 {
+        TouchPhase::Started
+    } else if info.dwFlags & GF_END != 0 {
+        TouchPhase::Ended
+    } else {
+        TouchPhase::Moved
+    };
+    let event = match info.dwID {
+        GID_ZOOM => {
+            let distance = info.ullArguments;
+            let delta = if phase == TouchPhase::Started || state.distance == 0 {
+                0.0
+            } else {
+                distance as f64 / state.distance as f64 - 1.0
+            };
+            state.distance = distance;
+            WindowEvent::PinchGesture {
+                device_id: DEVICE_ID,
+                delta,
+                phase,
+            }
+        }
+        GID_ROTATE => {
+            // The argument of the first message is the initial angle between the fingers, the
+            // following ones carry the rotation since then.
+            let angle = if phase == TouchPhase::Started {
+                0.0
+            } else {
+                (info.ullArguments as f64 / 65535.0 * 4.0 - 2.0) * PI
+            };
+            let delta = (angle - state.angle).to_degrees();
+            state.angle = angle;
+            WindowEvent::RotationGesture {
+                device_id: DEVICE_ID,
+                delta: delta as f32,
+                phase,
+            }
+        }
+        _ => return None,
+    };
+    CloseGestureInfoHandle(lparam);
+    Some(event)
+}
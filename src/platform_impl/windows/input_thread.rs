@@ -0,0 +1,281 @@
+//! Raw input read on a dedicated thread, see
+//! [`EventLoopBuilderExtWindows::with_raw_input_thread`].
+//!
+//! The thread owns a message-only window registered for raw input. Each report is stamped with
+//! the time of its message and queued, and the thread message target of the event loop is only
+//! woken up when the queue was empty, so the reports read while the event loop is busy, e.g.
+//! rendering a frame, are delivered as one batch.
+//!
+//! [`EventLoopBuilderExtWindows::with_raw_input_thread`]: crate::platform::windows::EventLoopBuilderExtWindows::with_raw_input_thread
+
+use std::{
+    mem, ptr,
+    sync::{mpsc, Arc},
+    thread,
+    time::Instant,
+};
+
+use parking_lot::Mutex;
+use winapi::{
+    shared::{
+        minwindef::{DWORD, LPARAM, LRESULT, UINT, WPARAM},
+        windef::HWND,
+    },
+    um::{libloaderapi, processthreadsapi, winnt::LPCSTR, winuser},
+};
+
+use crate::{
+    event_loop::DeviceEvents,
+    platform::windows::RawInputDevices,
+    platform_impl::platform::{raw_input, util},
+};
+
+/// The raw input reports read since the event loop last took them, with the time of each one.
+pub type RawInputBatch = Arc<Mutex<Vec<(Instant, RawInputReport)>>>;
+
+pub struct RawInputReport(pub winuser::RAWINPUT);
+
+// The device handle of the report is only used as an identifier.
+unsafe impl Send for RawInputReport {}
+
+lazy_static! {
+    // Message posted to the input window to change when raw input is received.
+    // WPARAM is the `DeviceEvents` filter, see `filter_to_wparam`. LPARAM is unused.
+    static ref LISTEN_DEVICE_EVENTS_MSG_ID: u32 = unsafe {
+        winuser::RegisterWindowMessageA("Winit::ListenDeviceEvents\0".as_ptr() as LPCSTR)
+    };
+    static ref INPUT_WINDOW_CLASS: Vec<u16> = {
+        use std::ffi::OsStr;
+        use std::os::windows::ffi::OsStrExt;
+
+        OsStr::new("Winit Raw Input")
+            .encode_wide()
+            .chain(Some(0).into_iter())
+            .collect()
+    };
+}
+
+/// The thread reading the raw input, which exits once this is dropped.
+pub struct InputThread {
+    window: HWND,
+}
+
+struct InputWindowData {
+    target_window: HWND,
+    msg_id: u32,
+    devices: RawInputDevices,
+    filter: DeviceEvents,
+    batch: RawInputBatch,
+}
+
+impl InputThread {
+    /// Spawns the thread reading the raw input of `devices` into `batch`.
+    ///
+    /// A `msg_id` message is posted to `target_window` when reports are added to an empty
+    /// `batch`, and the `WM_INPUT_DEVICE_CHANGE` messages are forwarded to it.
+    pub fn spawn(
+        target_window: HWND,
+        msg_id: u32,
+        devices: RawInputDevices,
+        batch: RawInputBatch,
+    ) -> Option<Self> {
+        let (window_tx, window_rx) = mpsc::channel();
+        let target_window = target_window as usize;
+        let result = thread::Builder::new()
+            .name("winit input".into())
+            .spawn(move || {
+                let data = InputWindowData {
+                    target_window: target_window as HWND,
+                    msg_id,
+                    devices,
+                    filter: DeviceEvents::default(),
+                    batch,
+                };
+                let window = unsafe { create_input_window(data) };
+                let _ = window_tx.send(window as usize);
+                if !window.is_null() {
+                    run_message_loop();
+                }
+            });
+        if let Err(err) = result {
+            warn!("Failed to start the raw input thread: {}", err);
+            return None;
+        }
+
+        let window = window_rx.recv().ok()? as HWND;
+        if window.is_null() {
+            warn!("Failed to create the raw input window");
+            return None;
+        }
+        Some(InputThread { window })
+    }
+
+    /// Changes when the raw input is received, see
+    /// [`EventLoopWindowTarget::listen_device_events`](crate::event_loop::EventLoopWindowTarget::listen_device_events).
+    pub fn listen_device_events(&self, filter: DeviceEvents) {
+        unsafe {
+            winuser::PostMessageW(
+                self.window,
+                *LISTEN_DEVICE_EVENTS_MSG_ID,
+                filter_to_wparam(filter),
+                0,
+            );
+        }
+    }
+}
+
+impl Drop for InputThread {
+    fn drop(&mut self) {
+        // Destroying the window ends the message loop of the thread.
+        unsafe { winuser::PostMessageW(self.window, winuser::WM_CLOSE, 0, 0) };
+    }
+}
+
+unsafe fn create_input_window(data: InputWindowData) -> HWND {
+    let class = winuser::WNDCLASSEXW {
+        cbSize: mem::size_of::<winuser::WNDCLASSEXW>() as UINT,
+        style: 0,
+        lpfnWndProc: Some(input_window_callback),
+        cbClsExtra: 0,
+        cbWndExtra: 0,
+        hInstance: libloaderapi::GetModuleHandleW(ptr::null()),
+        hIcon: ptr::null_mut(),
+        hCursor: ptr::null_mut(),
+        hbrBackground: ptr::null_mut(),
+        lpszMenuName: ptr::null(),
+        lpszClassName: INPUT_WINDOW_CLASS.as_ptr(),
+        hIconSm: ptr::null_mut(),
+    };
+    // This fails once the class is registered by an earlier event loop, which is fine as the
+    // window procedure doesn't depend on the event loop.
+    winuser::RegisterClassExW(&class);
+
+    let window = winuser::CreateWindowExW(
+        0,
+        INPUT_WINDOW_CLASS.as_ptr(),
+        ptr::null(),
+        0,
+        0,
+        0,
+        0,
+        0,
+        winuser::HWND_MESSAGE,
+        ptr::null_mut(),
+        libloaderapi::GetModuleHandleW(ptr::null()),
+        ptr::null_mut(),
+    );
+    if window.is_null() {
+        return window;
+    }
+
+    register(window, data.devices, data.filter);
+    let data = Box::into_raw(Box::new(data));
+    winuser::SetWindowLongPtrW(window, winuser::GWL_USERDATA, data as _);
+    window
+}
+
+fn run_message_loop() {
+    unsafe {
+        let mut msg = mem::zeroed();
+        while winuser::GetMessageW(&mut msg, ptr::null_mut(), 0, 0) > 0 {
+            winuser::DispatchMessageW(&msg);
+        }
+    }
+}
+
+/// Registers `window` for the raw input of `devices`.
+///
+/// A message-only window is never in the foreground, so the input is always received in the
+/// background, and `WM_INPUT` filters it when device events are only wanted while focused.
+fn register(window: HWND, devices: RawInputDevices, filter: DeviceEvents) {
+    let filter = match filter {
+        DeviceEvents::Never => DeviceEvents::Never,
+        DeviceEvents::WhenFocused | DeviceEvents::Always => DeviceEvents::Always,
+    };
+    if !raw_input::register_for_raw_input(window, devices, filter) {
+        warn!("Failed to register the raw input thread for raw input");
+    }
+}
+
+/// Whether the foreground window belongs to this process.
+fn is_process_in_foreground() -> bool {
+    unsafe {
+        let window = winuser::GetForegroundWindow();
+        if window.is_null() {
+            return false;
+        }
+        let mut process_id: DWORD = 0;
+        winuser::GetWindowThreadProcessId(window, &mut process_id);
+        process_id == processthreadsapi::GetCurrentProcessId()
+    }
+}
+
+fn filter_to_wparam(filter: DeviceEvents) -> WPARAM {
+    match filter {
+        DeviceEvents::Always => 0,
+        DeviceEvents::WhenFocused => 1,
+        DeviceEvents::Never => 2,
+    }
+}
+
+fn filter_from_wparam(wparam: WPARAM) -> DeviceEvents {
+    match wparam {
+        0 => DeviceEvents::Always,
+        1 => DeviceEvents::WhenFocused,
+        _ => DeviceEvents::Never,
+    }
+}
+
+unsafe extern "system" fn input_window_callback(
+    window: HWND,
+    msg: UINT,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    let data = winuser::GetWindowLongPtrW(window, winuser::GWL_USERDATA) as *mut InputWindowData;
+    if data.is_null() {
+        return winuser::DefWindowProcW(window, msg, wparam, lparam);
+    }
+
+    match msg {
+        winuser::WM_INPUT => {
+            let data = &*data;
+            // Read one report at a time, so that each one gets the time of its own message.
+            let wanted = data.filter != DeviceEvents::WhenFocused || is_process_in_foreground();
+            if let Some(report) = raw_input::get_raw_input_data(lparam as _).filter(|_| wanted) {
+                let timestamp = util::message_time().unwrap_or_else(Instant::now);
+                let was_empty = {
+                    let mut batch = data.batch.lock();
+                    let was_empty = batch.is_empty();
+                    batch.push((timestamp, RawInputReport(report)));
+                    was_empty
+                };
+                if was_empty {
+                    winuser::PostMessageW(data.target_window, data.msg_id, 0, 0);
+                }
+            }
+            winuser::DefWindowProcW(window, msg, wparam, lparam)
+        }
+
+        winuser::WM_INPUT_DEVICE_CHANGE => {
+            winuser::PostMessageW((*data).target_window, msg, wparam, lparam);
+            0
+        }
+
+        _ if msg == *LISTEN_DEVICE_EVENTS_MSG_ID => {
+            let data = &mut *data;
+            data.filter = filter_from_wparam(wparam);
+            register(window, data.devices, data.filter);
+            0
+        }
+
+        winuser::WM_NCDESTROY => {
+            winuser::SetWindowLongPtrW(window, winuser::GWL_USERDATA, 0);
+            drop(Box::from_raw(data));
+            winuser::PostQuitMessage(0);
+            0
+        }
+
+        _ => winuser::DefWindowProcW(window, msg, wparam, lparam),
+    }
+}
@@ -0,0 +1,80 @@
+//! Low memory notifications backed by memory resource notification objects.
+//!
+//! These objects are only signaled, so a background thread waits on them and forwards the changes
+//! to the thread message target of the event loop.
+
+use std::thread;
+
+use winapi::{
+    shared::{minwindef::DWORD, windef::HWND, winerror::WAIT_TIMEOUT},
+    um::{
+        handleapi,
+        memoryapi::{self, MEMORY_RESOURCE_NOTIFICATION_TYPE},
+        synchapi,
+        winbase::WAIT_OBJECT_0,
+        winnt::HANDLE,
+        winuser,
+    },
+};
+
+/// How long to wait on a notification before checking whether the target window still exists.
+const CHECK_INTERVAL_MS: DWORD = 1000;
+
+/// Spawns the thread watching the available memory.
+///
+/// A `msg_id` message is posted to `target_window` each time the available memory gets low. It
+/// isn't posted again before the available memory got high in between, as the notification
+/// stays signaled for as long as the memory is low. The thread exits once `target_window` is
+/// destroyed.
+pub fn spawn(target_window: HWND, msg_id: u32) {
+    let target_window = target_window as usize;
+    let result = thread::Builder::new()
+        .name("winit memory".into())
+        .spawn(move || watch_thread(target_window as HWND, msg_id));
+    if let Err(err) = result {
+        warn!("Failed to start monitoring memory: {}", err);
+    }
+}
+
+fn watch_thread(target_window: HWND, msg_id: u32) {
+    let low = create_notification(memoryapi::LowMemoryResourceNotification);
+    let high = create_notification(memoryapi::HighMemoryResourceNotification);
+
+    if let (Some(low), Some(high)) = (low, high) {
+        while wait(target_window, low) {
+            unsafe { winuser::PostMessageW(target_window, msg_id, 0, 0) };
+            if !wait(target_window, high) {
+                break;
+            }
+        }
+    } else {
+        warn!("Failed to create memory resource notifications");
+    }
+
+    for handle in [low, high].iter().flatten() {
+        unsafe { handleapi::CloseHandle(*handle) };
+    }
+}
+
+fn create_notification(ty: MEMORY_RESOURCE_NOTIFICATION_TYPE) -> Option<HANDLE> {
+    let handle = unsafe { memoryapi::CreateMemoryResourceNotification(ty) };
+    if handle.is_null() {
+        None
+    } else {
+        Some(handle)
+    }
+}
+
+/// Waits for `notification` to be signaled, returning `false` if `target_window` got destroyed
+/// in the meantime or the wait failed.
+fn wait(target_window: HWND, notification: HANDLE) -> bool {
+    loop {
+        if unsafe { winuser::IsWindow(target_window) } == 0 {
+            return false;
+        }
+        let result = unsafe { synchapi::WaitForSingleObject(notification, CHECK_INTERVAL_MS) };
+        if result != WAIT_TIMEOUT {
+            return result == WAIT_OBJECT_0;
+        }
+    }
+}
@@ -15,6 +15,8 @@ pub use self::icon::WinIcon as PlatformIcon;
 
 use crate::event::DeviceId as RootDeviceId;
 use crate::icon::Icon;
+use crate::keyboard::{Key, NativeKey, SmolStr};
+use crate::platform::windows::BackdropType;
 use crate::window::Theme;
 
 #[derive(Clone)]
@@ -32,6 +34,8 @@ pub struct PlatformSpecificWindowBuilderAttributes {
     pub no_redirection_bitmap: bool,
     pub drag_and_drop: bool,
     pub preferred_theme: Option<Theme>,
+    pub backdrop_type: BackdropType,
+    pub touch_gestures: bool,
 }
 
 impl Default for PlatformSpecificWindowBuilderAttributes {
@@ -43,6 +47,8 @@ impl Default for PlatformSpecificWindowBuilderAttributes {
             no_redirection_bitmap: false,
             drag_and_drop: true,
             preferred_theme: None,
+            backdrop_type: BackdropType::Auto,
+            touch_gestures: false,
         }
     }
 }
@@ -82,6 +88,21 @@ fn wrap_device_id(id: u32) -> RootDeviceId {
     RootDeviceId(DeviceId(id))
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyEventExtra {
+    pub text_with_all_modifiers: Option<SmolStr>,
+    pub key_without_modifiers: Key,
+}
+
+impl Default for KeyEventExtra {
+    fn default() -> Self {
+        Self {
+            text_with_all_modifiers: None,
+            key_without_modifiers: Key::Unidentified(NativeKey::Unidentified),
+        }
+    }
+}
+
 pub type OsError = std::io::Error;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -99,12 +120,20 @@ impl WindowId {
 
 #[macro_use]
 mod util;
+#[cfg(feature = "accesskit")]
+mod accessibility;
+mod composition;
 mod dark_mode;
 mod dpi;
 mod drop_handler;
 mod event;
 mod event_loop;
+#[cfg(feature = "gamepad")]
+mod gamepad;
+mod gesture;
 mod icon;
+mod input_thread;
+mod memory;
 mod monitor;
 mod raw_input;
 mod window;
@@ -1,29 +1,41 @@
 use std::{
+    ffi::OsStr,
+    iter::once,
     mem::{self, size_of},
+    os::windows::ffi::OsStrExt,
     ptr,
 };
 
 use winapi::{
     ctypes::wchar_t,
     shared::{
+        hidsdi,
         hidusage::{HID_USAGE_GENERIC_KEYBOARD, HID_USAGE_GENERIC_MOUSE, HID_USAGE_PAGE_GENERIC},
-        minwindef::{TRUE, UINT, USHORT},
+        minwindef::{TRUE, UINT, ULONG, USHORT},
         windef::HWND,
     },
     um::{
-        winnt::HANDLE,
+        cfgmgr32::{self, CM_DRP_DEVICEDESC, CM_LOCATE_DEVNODE_NORMAL, CR_SUCCESS},
+        fileapi::{self, OPEN_EXISTING},
+        handleapi::{self, INVALID_HANDLE_VALUE},
+        winnt::{FILE_SHARE_READ, FILE_SHARE_WRITE, HANDLE},
         winuser::{
             self, HRAWINPUT, RAWINPUT, RAWINPUTDEVICE, RAWINPUTDEVICELIST, RAWINPUTHEADER,
-            RIDEV_DEVNOTIFY, RIDEV_INPUTSINK, RIDI_DEVICEINFO, RIDI_DEVICENAME, RID_DEVICE_INFO,
-            RID_DEVICE_INFO_HID, RID_DEVICE_INFO_KEYBOARD, RID_DEVICE_INFO_MOUSE, RID_INPUT,
-            RIM_TYPEHID, RIM_TYPEKEYBOARD, RIM_TYPEMOUSE,
+            RIDEV_DEVNOTIFY, RIDEV_INPUTSINK, RIDEV_REMOVE, RIDI_DEVICEINFO, RIDI_DEVICENAME,
+            RID_DEVICE_INFO, RID_DEVICE_INFO_HID, RID_DEVICE_INFO_KEYBOARD, RID_DEVICE_INFO_MOUSE,
+            RID_INPUT, RIM_TYPEHID, RIM_TYPEKEYBOARD, RIM_TYPEMOUSE,
         },
     },
 };
 
-use crate::{event::ElementState, platform_impl::platform::util};
+use crate::{
+    device::{DeviceCapabilities, DeviceKind},
+    event::ElementState,
+    event_loop::DeviceEvents,
+    platform::windows::RawInputDevices,
+    platform_impl::platform::util,
+};
 
-#[allow(dead_code)]
 pub fn get_raw_input_device_list() -> Option<Vec<RAWINPUTDEVICELIST>> {
     let list_size = size_of::<RAWINPUTDEVICELIST>() as UINT;
 
@@ -72,7 +84,6 @@ impl From<RID_DEVICE_INFO> for RawDeviceInfo {
     }
 }
 
-#[allow(dead_code)]
 pub fn get_raw_input_device_info(handle: HANDLE) -> Option<RawDeviceInfo> {
     let mut info: RID_DEVICE_INFO = unsafe { mem::zeroed() };
     let info_size = size_of::<RID_DEVICE_INFO>() as UINT;
@@ -98,6 +109,35 @@ pub fn get_raw_input_device_info(handle: HANDLE) -> Option<RawDeviceInfo> {
     Some(info.into())
 }
 
+const HID_USAGE_PAGE_DIGITIZER: USHORT = 0x0D;
+const HID_USAGE_DIGITIZER_PEN: USHORT = 0x02;
+const HID_USAGE_DIGITIZER_TOUCH_SCREEN: USHORT = 0x04;
+const HID_USAGE_DIGITIZER_TOUCH_PAD: USHORT = 0x05;
+const HID_USAGE_GENERIC_JOYSTICK: USHORT = 0x04;
+const HID_USAGE_GENERIC_GAMEPAD: USHORT = 0x05;
+
+/// Classifies a HID device from its top-level usage.
+pub fn hid_device_kind(info: &RID_DEVICE_INFO_HID) -> (DeviceKind, DeviceCapabilities) {
+    match (info.usUsagePage, info.usUsage) {
+        (HID_USAGE_PAGE_DIGITIZER, HID_USAGE_DIGITIZER_PEN) => (
+            DeviceKind::Tablet,
+            DeviceCapabilities::POINTER | DeviceCapabilities::PRESSURE,
+        ),
+        (HID_USAGE_PAGE_DIGITIZER, HID_USAGE_DIGITIZER_TOUCH_SCREEN) => {
+            (DeviceKind::Touchscreen, DeviceCapabilities::TOUCH)
+        }
+        (HID_USAGE_PAGE_DIGITIZER, HID_USAGE_DIGITIZER_TOUCH_PAD) => (
+            DeviceKind::Touchpad,
+            DeviceCapabilities::TOUCH | DeviceCapabilities::POINTER,
+        ),
+        (HID_USAGE_PAGE_GENERIC, HID_USAGE_GENERIC_JOYSTICK)
+        | (HID_USAGE_PAGE_GENERIC, HID_USAGE_GENERIC_GAMEPAD) => {
+            (DeviceKind::Gamepad, DeviceCapabilities::empty())
+        }
+        _ => (DeviceKind::Other, DeviceCapabilities::empty()),
+    }
+}
+
 pub fn get_raw_input_device_name(handle: HANDLE) -> Option<String> {
     let mut minimum_size = 0;
     let status = unsafe {
@@ -130,6 +170,107 @@ pub fn get_raw_input_device_name(handle: HANDLE) -> Option<String> {
     Some(util::wchar_to_string(&name))
 }
 
+/// Returns a human-readable name for the device with the given interface path.
+///
+/// This is the product string reported by HID devices, such as "USB Receiver", or the
+/// description of the device node for the others, such as "Standard PS/2 Keyboard".
+pub fn device_name(interface_path: &str) -> Option<String> {
+    hid_product_string(interface_path).or_else(|| device_description(interface_path))
+}
+
+fn hid_product_string(interface_path: &str) -> Option<String> {
+    let path: Vec<wchar_t> = OsStr::new(interface_path)
+        .encode_wide()
+        .chain(once(0))
+        .collect();
+    // The device is only queried, which doesn't need any access right.
+    let file = unsafe {
+        fileapi::CreateFileW(
+            path.as_ptr(),
+            0,
+            FILE_SHARE_READ | FILE_SHARE_WRITE,
+            ptr::null_mut(),
+            OPEN_EXISTING,
+            0,
+            ptr::null_mut(),
+        )
+    };
+    if file == INVALID_HANDLE_VALUE {
+        return None;
+    }
+    // USB string descriptors hold at most 126 characters.
+    let mut buffer = [0 as wchar_t; 127];
+    let status = unsafe {
+        hidsdi::HidD_GetProductString(
+            file,
+            buffer.as_mut_ptr() as _,
+            mem::size_of_val(&buffer) as ULONG,
+        )
+    };
+    unsafe { handleapi::CloseHandle(file) };
+    if status == 0 {
+        return None;
+    }
+    nonempty_string(&buffer)
+}
+
+fn device_description(interface_path: &str) -> Option<String> {
+    // `\\?\ACPI#PNP0303#4&1d401fb5&0#{884b96c3-...}` is an interface of the device instance
+    // `ACPI\PNP0303\4&1d401fb5&0`.
+    let instance_id = interface_path.strip_prefix(r"\\?\")?;
+    let instance_id = &instance_id[..instance_id.rfind('#')?];
+    let instance_id: Vec<wchar_t> = OsStr::new(&instance_id.replace('#', r"\"))
+        .encode_wide()
+        .chain(once(0))
+        .collect();
+
+    let mut devnode = 0;
+    let mut buffer = [0 as wchar_t; 256];
+    let mut length = mem::size_of_val(&buffer) as ULONG;
+    unsafe {
+        if cfgmgr32::CM_Locate_DevNodeW(
+            &mut devnode,
+            instance_id.as_ptr() as _,
+            CM_LOCATE_DEVNODE_NORMAL,
+        ) != CR_SUCCESS
+            || cfgmgr32::CM_Get_DevNode_Registry_PropertyW(
+                devnode,
+                CM_DRP_DEVICEDESC,
+                ptr::null_mut(),
+                buffer.as_mut_ptr() as _,
+                &mut length,
+                0,
+            ) != CR_SUCCESS
+        {
+            return None;
+        }
+    }
+    nonempty_string(&buffer)
+}
+
+/// Reads a nul-terminated string, which may be padded with spaces.
+fn nonempty_string(buffer: &[wchar_t]) -> Option<String> {
+    let length = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+    let string = util::wchar_to_string(&buffer[..length]);
+    let string = string.trim();
+    if string.is_empty() {
+        None
+    } else {
+        Some(string.to_owned())
+    }
+}
+
+/// Extracts the USB vendor and product IDs from a device interface path, such as
+/// `\\?\HID#VID_046D&PID_C52B&MI_00#...`.
+pub fn device_name_product_id(name: &str) -> Option<(u16, u16)> {
+    let name = name.to_ascii_uppercase();
+    let id_after = |prefix: &str| {
+        let start = name.find(prefix)? + prefix.len();
+        u16::from_str_radix(name.get(start..start + 4)?, 16).ok()
+    };
+    Some((id_after("VID_")?, id_after("PID_")?))
+}
+
 pub fn register_raw_input_devices(devices: &[RAWINPUTDEVICE]) -> bool {
     let device_size = size_of::<RAWINPUTDEVICE>() as UINT;
 
@@ -140,25 +281,40 @@ pub fn register_raw_input_devices(devices: &[RAWINPUTDEVICE]) -> bool {
     success == TRUE
 }
 
-pub fn register_all_mice_and_keyboards_for_raw_input(window_handle: HWND) -> bool {
+pub fn register_for_raw_input(
+    mut window_handle: HWND,
+    devices: RawInputDevices,
+    filter: DeviceEvents,
+) -> bool {
     // RIDEV_DEVNOTIFY: receive hotplug events
     // RIDEV_INPUTSINK: receive events even if we're not in the foreground
-    let flags = RIDEV_DEVNOTIFY | RIDEV_INPUTSINK;
+    // RIDEV_REMOVE: don't receive device events
+    let flags = match filter {
+        DeviceEvents::Never => {
+            window_handle = ptr::null_mut();
+            RIDEV_REMOVE
+        }
+        DeviceEvents::WhenFocused => RIDEV_DEVNOTIFY,
+        DeviceEvents::Always => RIDEV_DEVNOTIFY | RIDEV_INPUTSINK,
+    };
 
-    let devices: [RAWINPUTDEVICE; 2] = [
-        RAWINPUTDEVICE {
-            usUsagePage: HID_USAGE_PAGE_GENERIC,
-            usUsage: HID_USAGE_GENERIC_MOUSE,
-            dwFlags: flags,
-            hwndTarget: window_handle,
-        },
-        RAWINPUTDEVICE {
+    let usages = [
+        (RawInputDevices::MOUSE, HID_USAGE_GENERIC_MOUSE),
+        (RawInputDevices::KEYBOARD, HID_USAGE_GENERIC_KEYBOARD),
+    ];
+    let devices: Vec<RAWINPUTDEVICE> = usages
+        .iter()
+        .filter(|(class, _)| devices.contains(*class))
+        .map(|&(_, usage)| RAWINPUTDEVICE {
             usUsagePage: HID_USAGE_PAGE_GENERIC,
-            usUsage: HID_USAGE_GENERIC_KEYBOARD,
+            usUsage: usage,
             dwFlags: flags,
             hwndTarget: window_handle,
-        },
-    ];
+        })
+        .collect();
+    if devices.is_empty() {
+        return true;
+    }
 
     register_raw_input_devices(&devices)
 }
@@ -185,6 +341,35 @@ pub fn get_raw_input_data(handle: HRAWINPUT) -> Option<RAWINPUT> {
     Some(data)
 }
 
+/// Reads the raw input queued for the thread, removing the matching `WM_INPUT` messages.
+pub fn get_raw_input_buffer() -> Vec<RAWINPUT> {
+    // The reports are aligned to pointers in the buffer, see `NEXTRAWINPUTBLOCK`.
+    let align = |size: usize| (size + size_of::<usize>() - 1) & !(size_of::<usize>() - 1);
+    let header_size = size_of::<RAWINPUTHEADER>() as UINT;
+
+    let mut reports = Vec::new();
+    loop {
+        let mut buffer: [RAWINPUT; 16] = unsafe { mem::zeroed() };
+        let mut buffer_size = mem::size_of_val(&buffer) as UINT;
+        let count = unsafe {
+            winuser::GetRawInputBuffer(buffer.as_mut_ptr(), &mut buffer_size, header_size)
+        };
+        if count == 0 || count == UINT::max_value() {
+            break;
+        }
+
+        // Mouse and keyboard reports are never larger than `RAWINPUT`, so reading one from the
+        // start of each report stays within the buffer.
+        let mut report = buffer.as_ptr() as *const u8;
+        for _ in 0..count {
+            let data = unsafe { *(report as *const RAWINPUT) };
+            report = unsafe { report.add(align(data.header.dwSize as usize)) };
+            reports.push(data);
+        }
+    }
+    reports
+}
+
 fn button_flags_to_element_state(
     button_flags: USHORT,
     down_flag: USHORT,
@@ -4,19 +4,28 @@ use std::{
     os::raw::c_void,
     ptr, slice,
     sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
 };
 
-use crate::{dpi::PhysicalSize, window::CursorIcon};
+use crate::{
+    dpi::PhysicalSize,
+    event_loop::{PowerSource, PowerState, SystemPreferences},
+    platform::windows::BackdropType,
+    window::CursorIcon,
+};
 use winapi::{
     ctypes::wchar_t,
     shared::{
-        minwindef::{BOOL, DWORD, UINT},
+        minwindef::{BOOL, DWORD, FALSE, TRUE, UINT},
         windef::{DPI_AWARENESS_CONTEXT, HMONITOR, HWND, LPRECT, RECT},
+        winerror::SUCCEEDED,
     },
     um::{
+        dwmapi::{DwmGetColorizationColor, DwmSetWindowAttribute},
         libloaderapi::{GetProcAddress, LoadLibraryA},
         shellscalingapi::{MONITOR_DPI_TYPE, PROCESS_DPI_AWARENESS},
-        winbase::lstrlenW,
+        sysinfoapi::GetTickCount,
+        winbase::{lstrlenW, GetSystemPowerStatus, SYSTEM_POWER_STATUS},
         winnt::{HRESULT, LONG, LPCSTR},
         winuser,
     },
@@ -133,16 +142,28 @@ pub fn adjust_window_rect_with_styles(
     style: DWORD,
     style_ex: DWORD,
     rect: RECT,
+) -> Option<RECT> {
+    let dpi = unsafe { (*GET_DPI_FOR_WINDOW).map(|get_dpi_for_window| get_dpi_for_window(hwnd)) };
+    adjust_window_rect_for_dpi(hwnd, style, style_ex, rect, dpi)
+}
+
+/// Adjusts `rect` for the decorations the window has at `dpi`, which may not be its current DPI
+/// while it moves to a monitor with another DPI. `None` stands for the system DPI.
+pub fn adjust_window_rect_for_dpi(
+    hwnd: HWND,
+    style: DWORD,
+    style_ex: DWORD,
+    rect: RECT,
+    dpi: Option<u32>,
 ) -> Option<RECT> {
     unsafe {
         status_map(|r| {
             *r = rect;
 
             let b_menu = !winuser::GetMenu(hwnd).is_null() as BOOL;
-            if let (Some(get_dpi_for_window), Some(adjust_window_rect_ex_for_dpi)) =
-                (*GET_DPI_FOR_WINDOW, *ADJUST_WINDOW_RECT_EX_FOR_DPI)
+            if let (Some(dpi), Some(adjust_window_rect_ex_for_dpi)) =
+                (dpi, *ADJUST_WINDOW_RECT_EX_FOR_DPI)
             {
-                let dpi = get_dpi_for_window(hwnd);
                 adjust_window_rect_ex_for_dpi(r, style as _, b_menu, style_ex as _, dpi)
             } else {
                 winuser::AdjustWindowRectEx(r, style as _, b_menu, style_ex as _)
@@ -151,6 +172,24 @@ pub fn adjust_window_rect_with_styles(
     }
 }
 
+/// Sets the material DWM draws behind the window, returning whether DWM supports it, which it
+/// does from Windows 11 22H2 onwards.
+pub fn set_system_backdrop(hwnd: HWND, backdrop_type: BackdropType) -> bool {
+    // From `dwmapi.h`, which `winapi` doesn't cover.
+    const DWMWA_SYSTEMBACKDROP_TYPE: DWORD = 38;
+
+    let backdrop_type = backdrop_type as DWORD;
+    let hr = unsafe {
+        DwmSetWindowAttribute(
+            hwnd,
+            DWMWA_SYSTEMBACKDROP_TYPE,
+            &backdrop_type as *const DWORD as _,
+            mem::size_of::<DWORD>() as DWORD,
+        )
+    };
+    SUCCEEDED(hr)
+}
+
 pub fn set_cursor_hidden(hidden: bool) {
     static HIDDEN: AtomicBool = AtomicBool::new(false);
     let changed = HIDDEN.swap(hidden, Ordering::SeqCst) ^ hidden;
@@ -196,6 +235,79 @@ pub fn is_focused(window: HWND) -> bool {
     window == unsafe { winuser::GetActiveWindow() }
 }
 
+pub fn system_preferences() -> SystemPreferences {
+    let accent_color = unsafe {
+        let mut color: DWORD = 0;
+        let mut opaque_blend: BOOL = FALSE;
+        if SUCCEEDED(DwmGetColorizationColor(&mut color, &mut opaque_blend)) {
+            // The color is in the 0xAARRGGBB format.
+            Some([(color >> 16) as u8, (color >> 8) as u8, color as u8])
+        } else {
+            None
+        }
+    };
+
+    let high_contrast = unsafe {
+        let mut high_contrast: winuser::HIGHCONTRASTW = mem::zeroed();
+        high_contrast.cbSize = mem::size_of::<winuser::HIGHCONTRASTW>() as UINT;
+        winuser::SystemParametersInfoW(
+            winuser::SPI_GETHIGHCONTRAST,
+            high_contrast.cbSize,
+            &mut high_contrast as *mut _ as *mut _,
+            0,
+        ) != FALSE
+            && has_flag(high_contrast.dwFlags, winuser::HCF_HIGHCONTRASTON)
+    };
+
+    // This is the "Show animations in Windows" setting.
+    let reduced_motion = unsafe {
+        let mut animation: BOOL = TRUE;
+        winuser::SystemParametersInfoW(
+            winuser::SPI_GETCLIENTAREAANIMATION,
+            0,
+            &mut animation as *mut _ as *mut _,
+            0,
+        ) != FALSE
+            && animation == FALSE
+    };
+
+    SystemPreferences {
+        accent_color,
+        high_contrast,
+        reduced_motion,
+    }
+}
+
+/// Converts the time of the last message retrieved by the thread to an `Instant`.
+///
+/// Both the message time and `GetTickCount` count the milliseconds since the system started, so
+/// the age of the message is the difference between them.
+pub fn message_time() -> Option<Instant> {
+    let age = unsafe { GetTickCount().wrapping_sub(winuser::GetMessageTime() as DWORD) };
+    // Input isn't queued for this long, so the message being processed was sent, not retrieved.
+    if age > 10_000 {
+        return None;
+    }
+    Instant::now().checked_sub(Duration::from_millis(age as u64))
+}
+
+pub fn power_state() -> PowerState {
+    let mut status: SYSTEM_POWER_STATUS = unsafe { mem::zeroed() };
+    if unsafe { GetSystemPowerStatus(&mut status) } == FALSE {
+        return PowerState::default();
+    }
+    let source = match status.ACLineStatus {
+        0 => PowerSource::Battery,
+        1 => PowerSource::Ac,
+        _ => PowerSource::Unknown,
+    };
+    PowerState {
+        source,
+        // This is `SystemStatusFlag` since Windows 10, which is set while the battery saver is on.
+        low_power_mode: status.Reserved1 == 1,
+    }
+}
+
 impl CursorIcon {
     pub(crate) fn to_windows_cursor(self) -> *const wchar_t {
         match self {
@@ -265,6 +377,9 @@ pub type SetProcessDpiAwareness =
 pub type SetProcessDpiAwarenessContext =
     unsafe extern "system" fn(value: DPI_AWARENESS_CONTEXT) -> BOOL;
 pub type GetDpiForWindow = unsafe extern "system" fn(hwnd: HWND) -> UINT;
+pub type WTSRegisterSessionNotification =
+    unsafe extern "system" fn(hwnd: HWND, flags: DWORD) -> BOOL;
+pub type WTSUnRegisterSessionNotification = unsafe extern "system" fn(hwnd: HWND) -> BOOL;
 pub type GetDpiForMonitor = unsafe extern "system" fn(
     hmonitor: HMONITOR,
     dpi_type: MONITOR_DPI_TYPE,
@@ -295,4 +410,8 @@ lazy_static! {
         get_function!("shcore.dll", SetProcessDpiAwareness);
     pub static ref SET_PROCESS_DPI_AWARE: Option<SetProcessDPIAware> =
         get_function!("user32.dll", SetProcessDPIAware);
+    pub static ref WTS_REGISTER_SESSION_NOTIFICATION: Option<WTSRegisterSessionNotification> =
+        get_function!("wtsapi32.dll", WTSRegisterSessionNotification);
+    pub static ref WTS_UNREGISTER_SESSION_NOTIFICATION: Option<WTSUnRegisterSessionNotification> =
+        get_function!("wtsapi32.dll", WTSUnRegisterSessionNotification);
 }
@@ -6,7 +6,7 @@ use std::{
     cell::Cell,
     ffi::OsStr,
     io, mem,
-    os::windows::ffi::OsStrExt,
+    os::{raw::c_void, windows::ffi::OsStrExt},
     panic, ptr,
     sync::{mpsc::channel, Arc},
 };
@@ -25,23 +25,34 @@ use winapi::{
         objbase::COINIT_APARTMENTTHREADED,
         ole2,
         oleidl::LPDROPTARGET,
-        shobjidl_core::{CLSID_TaskbarList, ITaskbarList2},
+        shobjidl_core::{
+            CLSID_TaskbarList, ITaskbarList3, THBF_DISABLED, THBF_DISMISSONCLICK, THBF_ENABLED,
+            THBF_HIDDEN, THB_FLAGS, THB_ICON, THB_TOOLTIP, THUMBBUTTON,
+        },
         wingdi::{CreateRectRgn, DeleteObject},
         winnt::{LPCWSTR, SHORT},
         winuser,
     },
 };
 
+#[cfg(feature = "accesskit")]
+use crate::{
+    accessibility::{accesskit::TreeUpdate, TreeSource},
+    platform_impl::platform::accessibility::Accessibility,
+};
 use crate::{
     dpi::{PhysicalPosition, PhysicalSize, Position, Size},
     error::{ExternalError, NotSupportedError, OsError as RootOsError},
     icon::Icon,
     monitor::MonitorHandle as RootMonitorHandle,
+    platform::windows::{BackdropType, ThumbnailButton},
     platform_impl::platform::{
+        composition::Composition,
         dark_mode::try_theme,
         dpi::{dpi_to_scale_factor, enable_non_client_dpi_scaling, hwnd_dpi},
         drop_handler::FileDropHandler,
         event_loop::{self, EventLoopWindowTarget, WindowLongPtr, DESTROY_MSG_ID},
+        gesture,
         icon::{self, IconType},
         monitor, util,
         window_state::{CursorFlags, SavedWindow, WindowFlags, WindowState},
@@ -60,6 +71,10 @@ pub struct Window {
 
     // The events loop proxy.
     thread_executor: event_loop::EventLoopThreadExecutor,
+
+    /// The accessibility adapter, shared with the window procedure.
+    #[cfg(feature = "accesskit")]
+    accessibility: Arc<Accessibility>,
 }
 
 impl Window {
@@ -255,6 +270,37 @@ impl Window {
         RawWindowHandle::Win32(handle)
     }
 
+    #[cfg(feature = "rwh_05")]
+    pub fn raw_window_handle_rwh_05(&self) -> rwh_05::RawWindowHandle {
+        let mut handle = rwh_05::Win32WindowHandle::empty();
+        handle.hwnd = self.window.0 as *mut _;
+        handle.hinstance = self.hinstance() as *mut _;
+        rwh_05::RawWindowHandle::Win32(handle)
+    }
+
+    #[cfg(feature = "rwh_05")]
+    pub fn raw_display_handle_rwh_05(&self) -> rwh_05::RawDisplayHandle {
+        rwh_05::RawDisplayHandle::Windows(rwh_05::WindowsDisplayHandle::empty())
+    }
+
+    #[cfg(feature = "rwh_06")]
+    pub fn raw_window_handle_rwh_06(&self) -> Result<rwh_06::RawWindowHandle, rwh_06::HandleError> {
+        let hwnd = std::num::NonZeroIsize::new(self.window.0 as isize)
+            .ok_or(rwh_06::HandleError::Unavailable)?;
+        let mut handle = rwh_06::Win32WindowHandle::new(hwnd);
+        handle.hinstance = std::num::NonZeroIsize::new(self.hinstance() as isize);
+        Ok(rwh_06::RawWindowHandle::Win32(handle))
+    }
+
+    #[cfg(feature = "rwh_06")]
+    pub fn raw_display_handle_rwh_06(
+        &self,
+    ) -> Result<rwh_06::RawDisplayHandle, rwh_06::HandleError> {
+        Ok(rwh_06::RawDisplayHandle::Windows(
+            rwh_06::WindowsDisplayHandle::new(),
+        ))
+    }
+
     #[inline]
     pub fn set_cursor_icon(&self, cursor: CursorIcon) {
         self.window_state.lock().mouse.cursor = cursor;
@@ -300,6 +346,11 @@ impl Window {
         rx.recv().unwrap().ok();
     }
 
+    #[inline]
+    pub fn set_key_repeat_enabled(&self, enabled: bool) {
+        self.window_state.lock().key_repeat_enabled = enabled;
+    }
+
     #[inline]
     pub fn scale_factor(&self) -> f64 {
         self.window_state.lock().scale_factor
@@ -668,6 +719,112 @@ impl Window {
         self.window_state.lock().current_theme
     }
 
+    #[inline]
+    pub fn set_theme(&self, theme: Option<Theme>) {
+        let window = self.window.clone();
+        let window_state = Arc::clone(&self.window_state);
+
+        self.thread_executor.execute_in_thread(move || {
+            let _ = &window;
+            let current_theme = try_theme(window.0, theme);
+            let mut window_state = window_state.lock();
+            window_state.preferred_theme = theme;
+            window_state.current_theme = current_theme;
+        });
+    }
+
+    #[inline]
+    pub fn set_system_backdrop(&self, backdrop_type: BackdropType) {
+        if !util::set_system_backdrop(self.window.0, backdrop_type) {
+            warn!("The system backdrop isn't supported before Windows 11 22H2");
+        }
+    }
+
+    pub fn set_thumbnail_buttons(&self, mut buttons: Vec<ThumbnailButton>) {
+        let window = self.window.clone();
+        let window_state = Arc::clone(&self.window_state);
+
+        self.thread_executor.execute_in_thread(move || {
+            let _ = &window;
+            let mut window_state = window_state.lock();
+            window_state.thumbnail_buttons = mem::take(&mut buttons);
+            unsafe { taskbar_update_thumbnail_buttons(window.0, &mut window_state) };
+        });
+    }
+
+    pub fn set_thumbnail_clip(&self, clip: Option<(PhysicalPosition<i32>, PhysicalSize<u32>)>) {
+        let window = self.window.clone();
+        let window_state = Arc::clone(&self.window_state);
+
+        self.thread_executor.execute_in_thread(move || {
+            let _ = &window;
+            let mut window_state = window_state.lock();
+            window_state.thumbnail_clip = clip;
+            unsafe { taskbar_update_thumbnail_clip(window.0, &window_state) };
+        });
+    }
+
+    pub fn set_thumbnail_tooltip(&self, mut tooltip: Option<String>) {
+        let window = self.window.clone();
+        let window_state = Arc::clone(&self.window_state);
+
+        self.thread_executor.execute_in_thread(move || {
+            let _ = &window;
+            let mut window_state = window_state.lock();
+            window_state.thumbnail_tooltip = tooltip.take();
+            unsafe { taskbar_update_thumbnail_tooltip(window.0, &window_state) };
+        });
+    }
+
+    pub fn composition_device(&self) -> Result<*mut c_void, RootOsError> {
+        self.with_composition(|composition| composition.device() as *mut c_void)
+    }
+
+    pub fn composition_visual(&self) -> Result<*mut c_void, RootOsError> {
+        self.with_composition(|composition| composition.visual() as *mut c_void)
+    }
+
+    pub fn set_composition_content(&self, content: *mut c_void) -> Result<(), RootOsError> {
+        self.with_composition(|composition| unsafe { composition.set_content(content as _) })?
+            .map_err(|e| os_error!(e))
+    }
+
+    #[cfg(feature = "accesskit")]
+    pub fn enable_accessibility(&self, source: Box<dyn TreeSource>) {
+        let window = self.window.clone();
+        let accessibility = self.accessibility.clone();
+        let mut source = Some(source);
+        self.thread_executor.execute_in_thread(move || {
+            let _ = &window;
+            if let Some(source) = source.take() {
+                accessibility.enable(window.0, source);
+            }
+        });
+    }
+
+    #[cfg(feature = "accesskit")]
+    pub fn update_accessibility_tree(&self, update: impl FnOnce() -> TreeUpdate) {
+        // The events are raised on the thread of the window, as recommended by UI Automation.
+        let mut events = self.accessibility.update_tree(update);
+        if events.is_some() {
+            self.thread_executor.execute_in_thread(move || {
+                if let Some(events) = events.take() {
+                    events.raise();
+                }
+            });
+        }
+    }
+
+    /// Calls `f` with the composition of the window, creating it on first use.
+    fn with_composition<T>(&self, f: impl FnOnce(&Composition) -> T) -> Result<T, RootOsError> {
+        let mut window_state = self.window_state.lock();
+        if window_state.composition.is_none() {
+            let composition = Composition::new(self.hwnd()).map_err(|e| os_error!(e))?;
+            window_state.composition = Some(composition);
+        }
+        Ok(f(window_state.composition.as_ref().unwrap()))
+    }
+
     #[inline]
     pub fn focus_window(&self) {
         let window = self.window.clone();
@@ -718,8 +875,10 @@ pub(super) struct InitData<'a, T: 'static> {
 
 impl<'a, T: 'static> InitData<'a, T> {
     unsafe fn create_window(&self, window: HWND) -> Window {
-        // Register for touch events if applicable
-        {
+        // Register for touch events if applicable, which disables the gestures
+        if self.pl_attribs.touch_gestures {
+            gesture::enable(window);
+        } else {
             let digitizer = winuser::GetSystemMetrics(winuser::SM_DIGITIZER) as u32;
             if digitizer & winuser::NID_READY != 0 {
                 winuser::RegisterTouchWindow(window, winuser::TWF_WANTPALM);
@@ -733,6 +892,11 @@ impl<'a, T: 'static> InitData<'a, T> {
         // before we update the window flags (and possibly show the
         // window for the first time).
         let current_theme = try_theme(window, self.pl_attribs.preferred_theme);
+        if self.pl_attribs.backdrop_type != BackdropType::Auto
+            && !util::set_system_backdrop(window, self.pl_attribs.backdrop_type)
+        {
+            warn!("The system backdrop isn't supported before Windows 11 22H2");
+        }
 
         let window_state = {
             let window_state = WindowState::new(
@@ -741,6 +905,7 @@ impl<'a, T: 'static> InitData<'a, T> {
                 scale_factor,
                 current_theme,
                 self.pl_attribs.preferred_theme,
+                self.pl_attribs.touch_gestures,
             );
             let window_state = Arc::new(Mutex::new(window_state));
             WindowState::set_window_flags(window_state.lock(), window, |f| *f = self.window_flags);
@@ -753,6 +918,8 @@ impl<'a, T: 'static> InitData<'a, T> {
             window: WindowWrapper(window),
             window_state,
             thread_executor: self.event_loop.create_thread_executor(),
+            #[cfg(feature = "accesskit")]
+            accessibility: Default::default(),
         }
     }
 
@@ -801,6 +968,8 @@ impl<'a, T: 'static> InitData<'a, T> {
             _file_drop_handler: file_drop_handler,
             userdata_removed: Cell::new(false),
             recurse_depth: Cell::new(0),
+            #[cfg(feature = "accesskit")]
+            accessibility: win.accessibility.clone(),
         }
     }
 
@@ -954,14 +1123,18 @@ where
     Ok(initdata.window.unwrap())
 }
 
+fn window_class_name() -> Vec<u16> {
+    OsStr::new("Window Class")
+        .encode_wide()
+        .chain(Some(0).into_iter())
+        .collect()
+}
+
 unsafe fn register_window_class<T: 'static>(
     window_icon: &Option<Icon>,
     taskbar_icon: &Option<Icon>,
 ) -> Vec<u16> {
-    let class_name: Vec<_> = OsStr::new("Window Class")
-        .encode_wide()
-        .chain(Some(0).into_iter())
-        .collect();
+    let class_name = window_class_name();
 
     let h_icon = taskbar_icon
         .as_ref()
@@ -996,6 +1169,16 @@ unsafe fn register_window_class<T: 'static>(
     class_name
 }
 
+/// Unregisters the class of the windows, whose callback is specific to the user event type of the
+/// event loop that registered it. This fails while windows of the class still exist.
+pub(crate) unsafe fn unregister_window_class() {
+    let class_name = window_class_name();
+    winuser::UnregisterClassW(
+        class_name.as_ptr(),
+        libloaderapi::GetModuleHandleW(ptr::null()),
+    );
+}
+
 struct ComInitialized(*mut ());
 impl Drop for ComInitialized {
     fn drop(&mut self) {
@@ -1011,7 +1194,7 @@ thread_local! {
         }
     };
 
-    static TASKBAR_LIST: Cell<*mut ITaskbarList2> = Cell::new(ptr::null_mut());
+    static TASKBAR_LIST: Cell<*mut ITaskbarList3> = Cell::new(ptr::null_mut());
 }
 
 pub fn com_initialized() {
@@ -1027,6 +1210,12 @@ pub fn com_initialized() {
 // heuristics to determine how the window should be treated, which means
 // that it could still consider the window as fullscreen. :(
 unsafe fn taskbar_mark_fullscreen(handle: HWND, fullscreen: bool) {
+    with_taskbar_list(|task_bar_list| {
+        task_bar_list.MarkFullscreenWindow(handle, if fullscreen { 1 } else { 0 });
+    })
+}
+
+unsafe fn with_taskbar_list<F: FnOnce(&ITaskbarList3)>(f: F) {
     com_initialized();
 
     TASKBAR_LIST.with(|task_bar_list_ptr| {
@@ -1039,7 +1228,7 @@ unsafe fn taskbar_mark_fullscreen(handle: HWND, fullscreen: bool) {
                 &CLSID_TaskbarList,
                 ptr::null_mut(),
                 combaseapi::CLSCTX_ALL,
-                &ITaskbarList2::uuidof(),
+                &ITaskbarList3::uuidof(),
                 &mut task_bar_list as *mut _ as *mut _,
             );
 
@@ -1051,10 +1240,113 @@ unsafe fn taskbar_mark_fullscreen(handle: HWND, fullscreen: bool) {
         }
 
         task_bar_list = task_bar_list_ptr.get();
-        (*task_bar_list).MarkFullscreenWindow(handle, if fullscreen { 1 } else { 0 });
+        f(&*task_bar_list);
     })
 }
 
+// The taskbar doesn't allow removing the buttons of a thumbnail toolbar once they were added, so
+// all of them are added at once, hiding the unused ones.
+const THUMBNAIL_BUTTON_COUNT: usize = 7;
+
+/// Applies the thumbnail state of the window, once its taskbar button was created.
+pub(crate) unsafe fn taskbar_update_thumbnail(handle: HWND, window_state: &mut WindowState) {
+    taskbar_update_thumbnail_buttons(handle, window_state);
+    taskbar_update_thumbnail_clip(handle, window_state);
+    taskbar_update_thumbnail_tooltip(handle, window_state);
+}
+
+unsafe fn taskbar_update_thumbnail_buttons(handle: HWND, window_state: &mut WindowState) {
+    if !window_state.taskbar_button_created
+        || (window_state.thumbnail_buttons.is_empty() && !window_state.thumbnail_toolbar_added)
+    {
+        return;
+    }
+
+    if window_state.thumbnail_buttons.len() > THUMBNAIL_BUTTON_COUNT {
+        warn!(
+            "The taskbar shows up to {} thumbnail buttons",
+            THUMBNAIL_BUTTON_COUNT
+        );
+    }
+
+    let mut buttons: [THUMBBUTTON; THUMBNAIL_BUTTON_COUNT] = mem::zeroed();
+    for (index, button) in buttons.iter_mut().enumerate() {
+        button.dwMask = THB_FLAGS;
+        // The identifiers are indices into `thumbnail_buttons`, see `WM_COMMAND`.
+        button.iId = index as UINT;
+        button.dwFlags = THBF_HIDDEN;
+
+        if let Some(thumbnail_button) = window_state.thumbnail_buttons.get(index) {
+            button.dwMask |= THB_ICON | THB_TOOLTIP;
+            button.hIcon = thumbnail_button.icon.inner.as_raw_handle();
+            let tooltip = OsStr::new(&thumbnail_button.tooltip).encode_wide();
+            let tip_len = button.szTip.len();
+            for (dst, src) in button.szTip[..tip_len - 1].iter_mut().zip(tooltip) {
+                *dst = src;
+            }
+
+            button.dwFlags = if thumbnail_button.enabled {
+                THBF_ENABLED
+            } else {
+                THBF_DISABLED
+            };
+            if thumbnail_button.dismiss_on_click {
+                button.dwFlags |= THBF_DISMISSONCLICK;
+            }
+        }
+    }
+
+    let added = window_state.thumbnail_toolbar_added;
+    with_taskbar_list(|task_bar_list| {
+        let hr = if added {
+            task_bar_list.ThumbBarUpdateButtons(handle, buttons.len() as UINT, buttons.as_mut_ptr())
+        } else {
+            task_bar_list.ThumbBarAddButtons(handle, buttons.len() as UINT, buttons.as_mut_ptr())
+        };
+        if SUCCEEDED(hr) {
+            window_state.thumbnail_toolbar_added = true;
+        } else {
+            warn!("Failed to set the thumbnail buttons: {:#x}", hr);
+        }
+    });
+}
+
+unsafe fn taskbar_update_thumbnail_clip(handle: HWND, window_state: &WindowState) {
+    if !window_state.taskbar_button_created {
+        return;
+    }
+
+    let mut rect = window_state.thumbnail_clip.map(|(position, size)| RECT {
+        left: position.x,
+        top: position.y,
+        right: position.x + size.width as i32,
+        bottom: position.y + size.height as i32,
+    });
+    let rect_ptr = rect.as_mut().map_or(ptr::null_mut(), |rect| rect as *mut _);
+    with_taskbar_list(|task_bar_list| {
+        task_bar_list.SetThumbnailClip(handle, rect_ptr);
+    });
+}
+
+unsafe fn taskbar_update_thumbnail_tooltip(handle: HWND, window_state: &WindowState) {
+    if !window_state.taskbar_button_created {
+        return;
+    }
+
+    let tooltip = window_state.thumbnail_tooltip.as_ref().map(|tooltip| {
+        OsStr::new(tooltip)
+            .encode_wide()
+            .chain(Some(0).into_iter())
+            .collect::<Vec<_>>()
+    });
+    let tooltip_ptr = tooltip
+        .as_ref()
+        .map_or(ptr::null(), |tooltip| tooltip.as_ptr());
+    with_taskbar_list(|task_bar_list| {
+        task_bar_list.SetThumbnailTooltip(handle, tooltip_ptr);
+    });
+}
+
 unsafe fn force_window_active(handle: HWND) {
     // In some situation, calling SetForegroundWindow could not bring up the window,
     // This is a little hack which can "steal" the foreground window permission
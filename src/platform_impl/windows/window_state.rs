@@ -1,8 +1,12 @@
 use crate::{
-    dpi::{PhysicalPosition, Size},
+    dpi::{PhysicalPosition, PhysicalSize, Size},
     event::ModifiersState,
     icon::Icon,
-    platform_impl::platform::{event_loop, util},
+    platform::windows::ThumbnailButton,
+    platform_impl::{
+        click::ClickCounter,
+        platform::{composition::Composition, event_loop, gesture::GestureState, util},
+    },
     window::{CursorIcon, Fullscreen, Theme, WindowAttributes},
 };
 use parking_lot::MutexGuard;
@@ -35,6 +39,24 @@ pub struct WindowState {
     pub preferred_theme: Option<Theme>,
     pub high_surrogate: Option<u16>,
     pub window_flags: WindowFlags,
+    pub key_repeat_enabled: bool,
+    /// Whether a touchpad scroll started, and its `ScrollPhase::Ended` is still to be reported.
+    pub touchpad_scrolling: bool,
+
+    /// The taskbar thumbnail state, applied again when the taskbar button is recreated.
+    pub thumbnail_buttons: Vec<ThumbnailButton>,
+    pub thumbnail_clip: Option<(PhysicalPosition<i32>, PhysicalSize<u32>)>,
+    pub thumbnail_tooltip: Option<String>,
+    /// Whether the taskbar button exists, and whether its thumbnail toolbar was added.
+    pub taskbar_button_created: bool,
+    pub thumbnail_toolbar_added: bool,
+
+    /// The DirectComposition visual tree of the window, created on first use.
+    pub composition: Option<Composition>,
+
+    /// Whether touch input is turned into `WM_GESTURE` rather than reported as touch events.
+    pub touch_gestures: bool,
+    pub gesture: GestureState,
 }
 
 #[derive(Clone)]
@@ -48,6 +70,7 @@ pub struct MouseProperties {
     pub capture_count: u32,
     cursor_flags: CursorFlags,
     pub last_position: Option<PhysicalPosition<f64>>,
+    pub click_counter: ClickCounter,
 }
 
 bitflags! {
@@ -99,6 +122,7 @@ impl WindowState {
         scale_factor: f64,
         current_theme: Theme,
         preferred_theme: Option<Theme>,
+        touch_gestures: bool,
     ) -> WindowState {
         WindowState {
             mouse: MouseProperties {
@@ -106,6 +130,7 @@ impl WindowState {
                 capture_count: 0,
                 cursor_flags: CursorFlags::empty(),
                 last_position: None,
+                click_counter: Default::default(),
             },
 
             min_size: attributes.min_inner_size,
@@ -123,6 +148,19 @@ impl WindowState {
             preferred_theme,
             high_surrogate: None,
             window_flags: WindowFlags::empty(),
+            key_repeat_enabled: true,
+            touchpad_scrolling: false,
+
+            thumbnail_buttons: Vec::new(),
+            thumbnail_clip: None,
+            thumbnail_tooltip: None,
+            taskbar_button_created: false,
+            thumbnail_toolbar_added: false,
+
+            composition: None,
+
+            touch_gestures,
+            gesture: GestureState::default(),
         }
     }
 
@@ -0,0 +1,163 @@
+//! Creation of Vulkan surfaces for windows, with [`ash`].
+//!
+//! The instance has to be created with the extensions returned by [`required_extensions`] for
+//! [`create_surface`] to work, which spares applications from listing the surface extension of
+//! each platform themselves.
+//!
+//! ```no_run
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use winit::{
+//!     event_loop::EventLoop,
+//!     vulkan::{self, ash::vk},
+//!     window::Window,
+//! };
+//!
+//! # fn load_entry() -> winit::vulkan::ash::Entry { unimplemented!() }
+//! let event_loop = EventLoop::new();
+//! let window = Window::new(&event_loop)?;
+//!
+//! // Typically `ash::Entry::load()`, which needs the `loaded` feature of `ash`.
+//! let entry = load_entry();
+//! let extensions = vulkan::required_extensions(&event_loop)?;
+//! let create_info = vk::InstanceCreateInfo::default().enabled_extension_names(extensions);
+//! let instance = unsafe { entry.create_instance(&create_info, None) }?;
+//! let surface = unsafe { vulkan::create_surface(&entry, &instance, &window, None) }?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ## Platform-specific
+//!
+//! - **macOS:** Surfaces are created with `VK_EXT_metal_surface`, from the layer returned by
+//!   `WindowExtMacOS::metal_layer`.
+//! - **Android:** Surfaces can only be created while the native window exists, i.e. between
+//!   `Event::Resumed` and `Event::Suspended`.
+//! - **iOS:** Unsupported.
+//! - **Web:** Unsupported, this module isn't available.
+
+pub use ash;
+
+use std::os::raw::c_char;
+
+use ash::{ext, khr, prelude::VkResult, vk, Entry, Instance};
+use rwh_06::{HasDisplayHandle, HasWindowHandle, RawDisplayHandle, RawWindowHandle};
+
+use crate::{event_loop::EventLoopWindowTarget, window::Window};
+
+/// Returns the instance extensions needed to create surfaces for the windows of `target`.
+///
+/// Returns `ERROR_EXTENSION_NOT_PRESENT` when the platform isn't supported.
+pub fn required_extensions<T>(
+    target: &EventLoopWindowTarget<T>,
+) -> VkResult<&'static [*const c_char]> {
+    let display_handle = target
+        .display_handle()
+        .map_err(|_| vk::Result::ERROR_INITIALIZATION_FAILED)?;
+
+    let extensions = match display_handle.as_raw() {
+        RawDisplayHandle::Windows(_) => {
+            const WINDOWS_EXTENSIONS: [*const c_char; 2] = [
+                khr::surface::NAME.as_ptr(),
+                khr::win32_surface::NAME.as_ptr(),
+            ];
+            &WINDOWS_EXTENSIONS
+        }
+        RawDisplayHandle::Xlib(_) => {
+            const XLIB_EXTENSIONS: [*const c_char; 2] = [
+                khr::surface::NAME.as_ptr(),
+                khr::xlib_surface::NAME.as_ptr(),
+            ];
+            &XLIB_EXTENSIONS
+        }
+        RawDisplayHandle::Wayland(_) => {
+            const WAYLAND_EXTENSIONS: [*const c_char; 2] = [
+                khr::surface::NAME.as_ptr(),
+                khr::wayland_surface::NAME.as_ptr(),
+            ];
+            &WAYLAND_EXTENSIONS
+        }
+        RawDisplayHandle::AppKit(_) => {
+            const METAL_EXTENSIONS: [*const c_char; 2] = [
+                khr::surface::NAME.as_ptr(),
+                ext::metal_surface::NAME.as_ptr(),
+            ];
+            &METAL_EXTENSIONS
+        }
+        RawDisplayHandle::Android(_) => {
+            const ANDROID_EXTENSIONS: [*const c_char; 2] = [
+                khr::surface::NAME.as_ptr(),
+                khr::android_surface::NAME.as_ptr(),
+            ];
+            &ANDROID_EXTENSIONS
+        }
+        _ => return Err(vk::Result::ERROR_EXTENSION_NOT_PRESENT),
+    };
+    Ok(extensions)
+}
+
+/// Creates a surface for `window`.
+///
+/// Returns `ERROR_EXTENSION_NOT_PRESENT` when the platform isn't supported, and
+/// `ERROR_INITIALIZATION_FAILED` when the window has no native handle, e.g. on Android while the
+/// application is suspended.
+///
+/// # Safety
+///
+/// `instance` must have been created from `entry` with the extensions returned by
+/// [`required_extensions`] enabled, and the surface must be destroyed before the window.
+pub unsafe fn create_surface(
+    entry: &Entry,
+    instance: &Instance,
+    window: &Window,
+    allocation_callbacks: Option<&vk::AllocationCallbacks<'_>>,
+) -> VkResult<vk::SurfaceKHR> {
+    let display_handle = window
+        .display_handle()
+        .map_err(|_| vk::Result::ERROR_INITIALIZATION_FAILED)?;
+    let window_handle = window
+        .window_handle()
+        .map_err(|_| vk::Result::ERROR_INITIALIZATION_FAILED)?;
+
+    match (display_handle.as_raw(), window_handle.as_raw()) {
+        (RawDisplayHandle::Windows(_), RawWindowHandle::Win32(handle)) => {
+            let create_info = vk::Win32SurfaceCreateInfoKHR::default()
+                .hwnd(handle.hwnd.get())
+                .hinstance(handle.hinstance.map_or(0, |hinstance| hinstance.get()));
+            khr::win32_surface::Instance::new(entry, instance)
+                .create_win32_surface(&create_info, allocation_callbacks)
+        }
+        (RawDisplayHandle::Xlib(display), RawWindowHandle::Xlib(handle)) => {
+            let display = display
+                .display
+                .ok_or(vk::Result::ERROR_INITIALIZATION_FAILED)?;
+            let create_info = vk::XlibSurfaceCreateInfoKHR::default()
+                .dpy(display.as_ptr())
+                .window(handle.window);
+            khr::xlib_surface::Instance::new(entry, instance)
+                .create_xlib_surface(&create_info, allocation_callbacks)
+        }
+        (RawDisplayHandle::Wayland(display), RawWindowHandle::Wayland(handle)) => {
+            let create_info = vk::WaylandSurfaceCreateInfoKHR::default()
+                .display(display.display.as_ptr())
+                .surface(handle.surface.as_ptr());
+            khr::wayland_surface::Instance::new(entry, instance)
+                .create_wayland_surface(&create_info, allocation_callbacks)
+        }
+        #[cfg(target_os = "macos")]
+        (RawDisplayHandle::AppKit(_), RawWindowHandle::AppKit(_)) => {
+            use crate::platform::macos::WindowExtMacOS;
+
+            let create_info =
+                vk::MetalSurfaceCreateInfoEXT::default().layer(window.metal_layer() as *const _);
+            ext::metal_surface::Instance::new(entry, instance)
+                .create_metal_surface(&create_info, allocation_callbacks)
+        }
+        (RawDisplayHandle::Android(_), RawWindowHandle::AndroidNdk(handle)) => {
+            let create_info =
+                vk::AndroidSurfaceCreateInfoKHR::default().window(handle.a_native_window.as_ptr());
+            khr::android_surface::Instance::new(entry, instance)
+                .create_android_surface(&create_info, allocation_callbacks)
+        }
+        _ => Err(vk::Result::ERROR_EXTENSION_NOT_PRESENT),
+    }
+}
@@ -1,5 +1,9 @@
 //! The `Window` struct and associated types.
 use std::fmt;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
 
 use crate::{
     dpi::{PhysicalPosition, PhysicalSize, Position, Size},
@@ -9,6 +13,9 @@ use crate::{
     platform_impl,
 };
 
+#[cfg(feature = "accesskit")]
+use crate::accessibility::{accesskit::TreeUpdate, TreeSource};
+
 pub use crate::icon::{BadIcon, Icon};
 
 /// Represents a window.
@@ -39,6 +46,7 @@ pub use crate::icon::{BadIcon, Icon};
 /// ```
 pub struct Window {
     pub(crate) window: platform_impl::Window,
+    cursor_moved_coalescing: Arc<AtomicBool>,
 }
 
 impl fmt::Debug for Window {
@@ -102,7 +110,12 @@ impl fmt::Debug for WindowBuilder {
 }
 
 /// Attributes to use when creating a window.
+///
+/// With the `serde` feature, missing fields deserialize to their default values. The fullscreen
+/// mode and the window icon refer to resources of the running system, and are skipped.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct WindowAttributes {
     /// The dimensions of the window. If this is `None`, some platform-specific dimensions will be
     /// used.
@@ -153,6 +166,7 @@ pub struct WindowAttributes {
     /// Whether the window should be set as fullscreen upon creation.
     ///
     /// The default is `None`.
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub fullscreen: Option<Fullscreen>,
 
     /// The title of the window in the title bar.
@@ -189,7 +203,13 @@ pub struct WindowAttributes {
     /// The window icon.
     ///
     /// The default is `None`.
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub window_icon: Option<Icon>,
+
+    /// Whether consecutive `CursorMoved` events should be merged into a single event.
+    ///
+    /// The default is `false`.
+    pub cursor_moved_coalescing: bool,
 }
 
 impl Default for WindowAttributes {
@@ -209,6 +229,7 @@ impl Default for WindowAttributes {
             decorations: true,
             always_on_top: false,
             window_icon: None,
+            cursor_moved_coalescing: false,
         }
     }
 }
@@ -359,6 +380,17 @@ impl WindowBuilder {
         self
     }
 
+    /// Sets whether consecutive `CursorMoved` events should be merged into a single event.
+    ///
+    /// See [`Window::set_cursor_moved_coalescing`] for details.
+    ///
+    /// [`Window::set_cursor_moved_coalescing`]: crate::window::Window::set_cursor_moved_coalescing
+    #[inline]
+    pub fn with_cursor_moved_coalescing(mut self, coalescing: bool) -> Self {
+        self.window.cursor_moved_coalescing = coalescing;
+        self
+    }
+
     /// Builds the window.
     ///
     /// Possible causes of error include denied permission, incompatible system, and lack of memory.
@@ -371,10 +403,19 @@ impl WindowBuilder {
         self,
         window_target: &EventLoopWindowTarget<T>,
     ) -> Result<Window, OsError> {
+        let cursor_moved_coalescing =
+            Arc::new(AtomicBool::new(self.window.cursor_moved_coalescing));
         platform_impl::Window::new(&window_target.p, self.window, self.platform_specific).map(
             |window| {
                 window.request_redraw();
-                Window { window }
+                let window = Window {
+                    window,
+                    cursor_moved_coalescing,
+                };
+                window_target
+                    .coalescing
+                    .register(window.id(), &window.cursor_moved_coalescing);
+                window
             },
         )
     }
@@ -416,7 +457,7 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
-    /// - **X11:** This respects Xft.dpi, and can be overridden using the `WINIT_X11_SCALE_FACTOR` environment variable.
+    /// - **X11:** This respects Xft.dpi and XSETTINGS, and can be overridden using the `WINIT_X11_SCALE_FACTOR` environment variable.
     /// - **Android:** Always returns 1.0.
     /// - **iOS:** Can only be called on the main thread. Returns the underlying `UIView`'s
     ///   [`contentScaleFactor`].
@@ -445,8 +486,27 @@ impl Window {
     /// - **Android:** Subsequent calls after `MainEventsCleared` are not handled.
     #[inline]
     pub fn request_redraw(&self) {
+        trace_event!("redraw requested", window_id = ?self.id());
         self.window.request_redraw()
     }
+
+    /// Sets whether consecutive `CursorMoved` events should be merged into a single event.
+    ///
+    /// When enabled, the `CursorMoved` events this window receives during one iteration of the
+    /// event loop are merged into a single event carrying the latest position, which is delivered
+    /// right before the next event of another kind. This is useful for applications doing
+    /// expensive work for every cursor movement, such as a relayout.
+    ///
+    /// `DeviceEvent::MouseMotion` isn't tied to a window: while any window has coalescing
+    /// enabled, consecutive motions from the same device are merged as well, with their deltas
+    /// summed.
+    ///
+    /// The default is `false`.
+    #[inline]
+    pub fn set_cursor_moved_coalescing(&self, coalescing: bool) {
+        self.cursor_moved_coalescing
+            .store(coalescing, Ordering::Relaxed);
+    }
 }
 
 /// Position and size functions.
@@ -835,6 +895,25 @@ impl Window {
         self.window.set_ime_position(position.into())
     }
 
+    /// Sets whether the window receives key auto-repeats while it has focus.
+    ///
+    /// When disabled, holding a key down only produces a single press, followed by a release when
+    /// the key is let go, which is what games usually want. No `KeyboardInput` with `repeat` set
+    /// nor `ReceivedCharacter` is sent for repeats.
+    ///
+    /// The default is `true`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Auto-repeat is a setting of the whole X server. It's turned off while the window
+    ///   has focus, and the previous setting is restored when the window loses focus or is
+    ///   destroyed. It stays off if the application is killed while the window is focused.
+    /// - **iOS / Android / Web:** Unsupported.
+    #[inline]
+    pub fn set_key_repeat_enabled(&self, enabled: bool) {
+        self.window.set_key_repeat_enabled(enabled)
+    }
+
     /// Brings the window to the front and sets input focus. Has no effect if the window is
     /// already in focus, minimized, or not visible.
     ///
@@ -844,7 +923,9 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
-    /// - **iOS / Android / Web / Wayland:** Unsupported.
+    /// - **iOS / Android / Web:** Unsupported.
+    /// - **Wayland:** Requires `xdg_activation_v1` protocol. Compositors may only raise the
+    ///   window if it received input recently.
     #[inline]
     pub fn focus_window(&self) {
         self.window.focus_window()
@@ -997,6 +1078,37 @@ impl Window {
     }
 }
 
+/// Accessibility functions.
+#[cfg(feature = "accesskit")]
+impl Window {
+    /// Makes the window accessible to assistive technologies, with the tree supplied by `source`,
+    /// see the [`accessibility`](crate::accessibility) module.
+    ///
+    /// This should be called before the window is made visible, and only once.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Web:** Unsupported.
+    #[inline]
+    pub fn enable_accessibility(&self, source: impl TreeSource) {
+        self.window.enable_accessibility(Box::new(source))
+    }
+
+    /// Updates the accessibility tree of the window with the update returned by `update`.
+    ///
+    /// `update` is only called while an assistive technology uses the tree, so building the
+    /// update is skipped otherwise. It must contain the whole tree if
+    /// [`TreeSource::initial_tree`] returned `None`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Web:** Unsupported.
+    #[inline]
+    pub fn update_accessibility_tree(&self, update: impl FnOnce() -> TreeUpdate) {
+        self.window.update_accessibility_tree(update)
+    }
+}
+
 unsafe impl raw_window_handle::HasRawWindowHandle for Window {
     /// Returns a `raw_window_handle::RawWindowHandle` for the Window
     ///
@@ -1009,6 +1121,52 @@ unsafe impl raw_window_handle::HasRawWindowHandle for Window {
     }
 }
 
+#[cfg(feature = "rwh_05")]
+unsafe impl rwh_05::HasRawWindowHandle for Window {
+    /// Returns a `raw-window-handle` 0.5 `RawWindowHandle` for the Window
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android:** Only available after receiving the Resumed event and before Suspended. *If you*
+    /// *try to get the handle outside of that period, this function will panic*!
+    fn raw_window_handle(&self) -> rwh_05::RawWindowHandle {
+        self.window.raw_window_handle_rwh_05()
+    }
+}
+
+#[cfg(feature = "rwh_05")]
+unsafe impl rwh_05::HasRawDisplayHandle for Window {
+    /// Returns a `raw-window-handle` 0.5 `RawDisplayHandle` for the display of the Window
+    fn raw_display_handle(&self) -> rwh_05::RawDisplayHandle {
+        self.window.raw_display_handle_rwh_05()
+    }
+}
+
+#[cfg(feature = "rwh_06")]
+impl rwh_06::HasWindowHandle for Window {
+    /// Returns a `raw-window-handle` 0.6 `WindowHandle` for the Window
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android:** Only available after receiving the Resumed event and before Suspended,
+    ///   [`HandleError::Unavailable`](rwh_06::HandleError::Unavailable) is returned otherwise.
+    fn window_handle(&self) -> Result<rwh_06::WindowHandle<'_>, rwh_06::HandleError> {
+        let raw = self.window.raw_window_handle_rwh_06()?;
+        // The handle stays valid for as long as the window is borrowed.
+        Ok(unsafe { rwh_06::WindowHandle::borrow_raw(raw) })
+    }
+}
+
+#[cfg(feature = "rwh_06")]
+impl rwh_06::HasDisplayHandle for Window {
+    /// Returns a `raw-window-handle` 0.6 `DisplayHandle` for the display of the Window
+    fn display_handle(&self) -> Result<rwh_06::DisplayHandle<'_>, rwh_06::HandleError> {
+        let raw = self.window.raw_display_handle_rwh_06()?;
+        // The display outlives its windows.
+        Ok(unsafe { rwh_06::DisplayHandle::borrow_raw(raw) })
+    }
+}
+
 /// Describes the appearance of the mouse cursor.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -1084,6 +1242,7 @@ pub enum Fullscreen {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Theme {
     Light,
     Dark,
@@ -1093,6 +1252,7 @@ pub enum Theme {
 ///
 /// - **X11:** Sets the WM's `XUrgencyHint`. No distinction between `Critical` and `Informational`.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum UserAttentionType {
     /// ## Platform-specific
     /// - **macOS:** Bounces the dock icon until the application is in focus.
@@ -1109,3 +1269,50 @@ impl Default for UserAttentionType {
         UserAttentionType::Informational
     }
 }
+
+/// The side drawing the decorations of a window.
+///
+/// ## Platform-specific
+///
+/// - **Wayland:** Client-side decorations are drawn by winit.
+/// - **Windows / macOS / iOS / Android / Web / X11:** Unsupported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DecorationMode {
+    /// The application draws the decorations.
+    ClientSide,
+    /// The compositor draws the decorations.
+    ServerSide,
+}
+
+/// A token that lets a window be activated, that is brought to the front and focused, in
+/// response to an action of the user in another window or another application.
+///
+/// Tokens are passed to the applications launched on behalf of the user through the
+/// `XDG_ACTIVATION_TOKEN` environment variable.
+///
+/// ## Platform-specific
+///
+/// - **Wayland:** This is an `xdg_activation_v1` token.
+/// - **Windows / macOS / iOS / Android / Web / X11:** Unsupported.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ActivationToken {
+    token: String,
+}
+
+impl ActivationToken {
+    /// Creates a token from the string representation exchanged between applications.
+    pub fn from_raw(token: String) -> Self {
+        Self { token }
+    }
+
+    /// Returns the string representation of the token.
+    pub fn as_raw(&self) -> &str {
+        &self.token
+    }
+
+    /// Converts the token into its string representation.
+    pub fn into_raw(self) -> String {
+        self.token
+    }
+}
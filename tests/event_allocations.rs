@@ -0,0 +1,74 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use winit::dpi::PhysicalPosition;
+use winit::event::{DeviceEvent, DeviceId, Event, ModifiersState, WindowEvent};
+use winit::window::WindowId;
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations<R>(f: impl FnOnce() -> R) -> (usize, R) {
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+    let result = f();
+    (ALLOCATIONS.load(Ordering::SeqCst) - before, result)
+}
+
+// The backends clone events out of their queues and convert them to the user event type of the
+// event loop. Dispatching them is checked by the tests of `EventDispatcher`.
+//
+// Everything is checked in a single test, as the test harness allocates on other threads while
+// tests run in parallel.
+#[test]
+fn cloning_common_events_doesnt_allocate() {
+    let window_id = unsafe { WindowId::dummy() };
+    let device_id = unsafe { DeviceId::dummy() };
+
+    #[allow(deprecated)]
+    let cursor_moved: Event<()> = Event::WindowEvent {
+        window_id,
+        event: WindowEvent::CursorMoved {
+            device_id,
+            position: PhysicalPosition::new(1.0, 2.0),
+            modifiers: ModifiersState::empty(),
+        },
+    };
+    let mouse_motion: Event<()> = Event::DeviceEvent {
+        device_id,
+        event: DeviceEvent::MouseMotion { delta: (1.0, 2.0) },
+    };
+    let dropped_file: Event<()> = Event::WindowEvent {
+        window_id,
+        event: WindowEvent::DroppedFile(Arc::from(Path::new("/tmp/file"))),
+    };
+
+    for event in [cursor_moved, mouse_motion, dropped_file] {
+        let (count, _) = allocations(|| {
+            let cloned = event.clone();
+            cloned.map_nonuser_event::<()>().ok()
+        });
+        assert_eq!(count, 0, "cloning {:?} allocated", event);
+    }
+}
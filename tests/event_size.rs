@@ -0,0 +1,11 @@
+use std::mem::size_of;
+
+use winit::event::Event;
+
+// Events are moved through queues and matched on for every iteration of the event loop, so they
+// should stay small. Key events store their text inline to avoid allocating, which takes them a
+// little past two cache lines.
+#[test]
+fn event_stays_small() {
+    assert!(size_of::<Event<()>>() <= 160, "{}", size_of::<Event<()>>());
+}
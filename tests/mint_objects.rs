@@ -0,0 +1,19 @@
+#![cfg(feature = "mint")]
+
+use winit::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
+
+#[allow(dead_code)]
+fn needs_mint<T, M>()
+where
+    T: From<M>,
+    M: From<T>,
+{
+}
+
+#[test]
+fn dpi_mint() {
+    needs_mint::<LogicalPosition<f64>, mint::Point2<f64>>();
+    needs_mint::<PhysicalPosition<i32>, mint::Point2<i32>>();
+    needs_mint::<LogicalSize<f64>, mint::Vector2<f64>>();
+    needs_mint::<PhysicalSize<u32>, mint::Vector2<u32>>();
+}
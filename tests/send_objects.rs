@@ -8,6 +8,10 @@ fn event_loop_proxy_send() {
     fn is_send<T: 'static + Send>() {
         // ensures that `winit::EventLoopProxy` implements `Send`
         needs_send::<winit::event_loop::EventLoopProxy<T>>();
+        // ensures that `winit::BoundedEventLoopProxy` implements `Send`
+        needs_send::<winit::event_loop::BoundedEventLoopProxy<T>>();
+        // ensures that `winit::TypedEventLoopProxy` implements `Send`
+        needs_send::<winit::event_loop::TypedEventLoopProxy<T>>();
     }
 }
 
@@ -25,3 +29,11 @@ fn ids_send() {
     needs_send::<winit::event::DeviceId>();
     needs_send::<winit::monitor::MonitorHandle>();
 }
+
+#[test]
+fn event_send() {
+    // ensures that events, including `ScaleFactorChanged`, can be sent to other threads
+    needs_send::<winit::event::Event<()>>();
+    needs_send::<winit::event::Event<winit::event_loop::AnyUserEvent>>();
+    needs_send::<winit::event::WindowEvent>();
+}
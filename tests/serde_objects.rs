@@ -2,12 +2,15 @@
 
 use serde::{Deserialize, Serialize};
 use winit::{
-    dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize},
+    dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Position, Size},
     event::{
-        ElementState, KeyboardInput, ModifiersState, MouseButton, MouseScrollDelta, TouchPhase,
-        VirtualKeyCode,
+        ElementState, Force, KeyboardInput, ModifiersState, MouseButton, MouseScrollDelta,
+        PenPhase, ScrollPhase, SessionSleepReason, TouchPhase,
     },
-    window::CursorIcon,
+    event_loop::{PowerSource, PowerState, SystemPreferences},
+    gamepad::{GamepadAxis, GamepadButton, GamepadEvent},
+    keyboard::{Key, KeyCode, NativeKey, NativeKeyCode, PhysicalKey},
+    window::{CursorIcon, DecorationMode, Theme, UserAttentionType, WindowAttributes},
 };
 
 #[allow(dead_code)]
@@ -16,17 +19,43 @@ fn needs_serde<S: Serialize + Deserialize<'static>>() {}
 #[test]
 fn window_serde() {
     needs_serde::<CursorIcon>();
+    needs_serde::<Theme>();
+    needs_serde::<UserAttentionType>();
+    needs_serde::<DecorationMode>();
+    needs_serde::<WindowAttributes>();
 }
 
 #[test]
 fn events_serde() {
     needs_serde::<KeyboardInput>();
     needs_serde::<TouchPhase>();
+    needs_serde::<PenPhase>();
+    needs_serde::<Force>();
+    needs_serde::<ScrollPhase>();
     needs_serde::<ElementState>();
     needs_serde::<MouseButton>();
     needs_serde::<MouseScrollDelta>();
-    needs_serde::<VirtualKeyCode>();
     needs_serde::<ModifiersState>();
+    needs_serde::<SessionSleepReason>();
+    needs_serde::<SystemPreferences>();
+    needs_serde::<PowerState>();
+    needs_serde::<PowerSource>();
+}
+
+#[test]
+fn keyboard_serde() {
+    needs_serde::<PhysicalKey>();
+    needs_serde::<KeyCode>();
+    needs_serde::<NativeKeyCode>();
+    needs_serde::<Key>();
+    needs_serde::<NativeKey>();
+}
+
+#[test]
+fn gamepad_serde() {
+    needs_serde::<GamepadEvent>();
+    needs_serde::<GamepadButton>();
+    needs_serde::<GamepadAxis>();
 }
 
 #[test]
@@ -36,4 +65,6 @@ fn dpi_serde() {
     needs_serde::<PhysicalPosition<f64>>();
     needs_serde::<LogicalSize<f64>>();
     needs_serde::<PhysicalSize<u32>>();
+    needs_serde::<Size>();
+    needs_serde::<Position>();
 }